@@ -9,4 +9,6 @@ fn main() {
     println!("cargo:rerun-if-changed=src/gfx/vulkan/shaders/fragment.glsl");
     println!("cargo:rerun-if-changed=src/gfx/dx12/shaders/vertex.hlsl");
     println!("cargo:rerun-if-changed=src/gfx/dx12/shaders/fragment.hlsl");
+    // Lighting shared by all backends (expanded by gfx::shader_preprocessor, or #include for GLSL)
+    println!("cargo:rerun-if-changed=src/gfx/shaders/directional_light.inc");
 }
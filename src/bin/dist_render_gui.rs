@@ -17,17 +17,7 @@ fn main() {
     config.apply_args(std::env::args());
     let scene = SceneConfig::from_file_or_default("scene.toml");
 
-    let packet0 = GuiStatePacket {
-        clear_color: scene.clear_color,
-        light_intensity: scene.light.intensity,
-        light_direction: scene.light.transform.rotation,
-        model_position: scene.model.transform.position,
-        model_rotation: scene.model.transform.rotation,
-        model_scale: scene.model.transform.scale,
-        camera_fov: scene.camera.fov,
-        camera_near: scene.camera.near_clip,
-        camera_far: scene.camera.far_clip,
-    };
+    let packet0 = GuiState::new(&config, &scene).to_packet();
 
     let shmem = create_or_open_shmem(DEFAULT_SHM_NAME, packet0);
     let shared = unsafe { &*(shmem.as_ptr() as *const SharedGuiState) };
@@ -92,6 +82,12 @@ fn main() {
                             ui.separator();
 
                             panels::backend::render(ui, &mut gui_state);
+                            ui.separator();
+
+                            panels::debug::render(ui, &mut gui_state);
+                            ui.separator();
+
+                            panels::post::render(ui, &mut gui_state);
                         });
 
                     let full_output = egui_ctx.end_frame();
@@ -100,18 +96,7 @@ fn main() {
                     egui_state.handle_platform_output(&window, full_output.platform_output);
 
                     // write shared memory
-                    let packet = GuiStatePacket {
-                        clear_color: gui_state.clear_color,
-                        light_intensity: gui_state.light_intensity,
-                        light_direction: gui_state.light_direction,
-                        model_position: gui_state.model_position,
-                        model_rotation: gui_state.model_rotation,
-                        model_scale: gui_state.model_scale,
-                        camera_fov: gui_state.camera_fov,
-                        camera_near: gui_state.camera_near,
-                        camera_far: gui_state.camera_far,
-                    };
-                    shared.write_latest(packet);
+                    shared.write_latest(gui_state.to_packet());
 
                     // render egui with wgpu
                     if let Err(e) = gfx.render_egui(
@@ -0,0 +1,88 @@
+//! 模型自动旋转
+//!
+//! 展示模型时让场景中的模型绕各自的 Y 轴匀速旋转。累计的旋转角叠加在 GUI 设置的旋转之上，
+//! 不覆盖用户调整的旋转；关闭后停止累计，模型保持在当前角度。
+
+use crate::core::scene::ModelConfig;
+
+/// 默认旋转速度（度/秒）
+pub const DEFAULT_AUTO_ROTATE_SPEED: f32 = 30.0;
+
+/// 自动旋转状态
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoRotate {
+    /// 是否正在旋转
+    pub enabled: bool,
+
+    /// 旋转速度（度/秒），负值反向旋转
+    pub speed: f32,
+
+    /// 累计的旋转角（度，0 ~ 360）
+    angle: f32,
+}
+
+impl Default for AutoRotate {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            speed: DEFAULT_AUTO_ROTATE_SPEED,
+            angle: 0.0,
+        }
+    }
+}
+
+impl AutoRotate {
+    /// 按帧间隔推进旋转角（每帧调用，关闭时不变）
+    pub fn advance(&mut self, delta_time: f32) {
+        if self.enabled {
+            self.angle = (self.angle + self.speed * delta_time).rem_euclid(360.0);
+        }
+    }
+
+    /// 累计的旋转角（度）
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    /// 把累计的旋转角叠加到模型的 Y 轴旋转上（在写入 GUI 设置的变换之后调用）
+    pub fn apply(&self, models: &mut [ModelConfig]) {
+        if self.angle == 0.0 {
+            return;
+        }
+        for model in models {
+            model.transform.rotation[1] += self.angle;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_accumulates_only_while_enabled() {
+        let mut auto_rotate = AutoRotate { speed: 90.0, ..Default::default() };
+        auto_rotate.advance(1.0);
+        assert_eq!(auto_rotate.angle(), 0.0);
+
+        auto_rotate.enabled = true;
+        auto_rotate.advance(1.0);
+        auto_rotate.advance(4.0);
+        assert!((auto_rotate.angle() - 90.0).abs() < 1e-4);
+
+        // 关闭后保持当前角度
+        auto_rotate.enabled = false;
+        auto_rotate.advance(1.0);
+        assert!((auto_rotate.angle() - 90.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_applied_on_top_of_user_rotation() {
+        let mut models = vec![ModelConfig::default()];
+        models[0].transform.rotation = [10.0, 20.0, 0.0];
+        let mut auto_rotate = AutoRotate { enabled: true, speed: 45.0, angle: 0.0 };
+        auto_rotate.advance(1.0);
+        auto_rotate.apply(&mut models);
+        assert_eq!(models[0].transform.rotation, [10.0, 65.0, 0.0]);
+    }
+}
@@ -3,10 +3,77 @@
 //! 参考 DistEngine 的 Camera 类实现
 //! 管理相机的视锥体和视图矩阵
 
-use super::{Component, Transform};
-use crate::math::{Vector3, Matrix4};
+use super::{CameraShake, Component, ShakeOffset, Transform};
+use crate::math::aabb::Aabb;
+use crate::math::frustum::Frustum;
+use crate::math::matrix::translation;
+use crate::math::noise::halton;
+use crate::math::ray::Ray;
+use crate::math::{Vector2, Vector3, Matrix4};
+use serde::{Deserialize, Serialize};
 use std::f32::consts::PI;
 
+/// 自动适配裁剪面时近裁剪面的最小距离
+pub const MIN_NEAR_Z: f32 = 0.01;
+
+/// 自动适配裁剪面时在包围盒前后留出的余量（占最远深度的比例）
+const CLIP_PLANE_MARGIN: f32 = 0.01;
+
+/// 投影抖动的 Halton 序列长度，超过后从头循环
+pub const JITTER_SEQUENCE_LENGTH: u32 = 8;
+
+/// 第 `frame` 帧的投影抖动（NDC 单位）
+///
+/// 取以 2 和 3 为底的 Halton 序列，居中到 `[-0.5, 0.5)` 像素，再按 `viewport`（像素宽高）换算到 NDC。
+pub fn halton_jitter(frame: u32, viewport: (f32, f32)) -> Vector2 {
+    let index = frame % JITTER_SEQUENCE_LENGTH + 1;
+    Vector2::new(
+        (halton(index, 2) - 0.5) * 2.0 / viewport.0,
+        (halton(index, 3) - 0.5) * 2.0 / viewport.1,
+    )
+}
+
+/// FOV 所对应的轴
+///
+/// 宽高比变化时保持该轴的视场角不变，另一个轴随宽高比换算。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FovAxis {
+    /// 垂直 FOV（默认）：改变窗口宽度时水平可见范围随之变化
+    #[default]
+    Vertical,
+    /// 水平 FOV：改变窗口高度时垂直可见范围随之变化
+    Horizontal,
+}
+
+impl FovAxis {
+    /// 将该轴上的 FOV 换算为垂直 FOV（弧度）
+    pub fn to_vertical(self, fov: f32, aspect: f32) -> f32 {
+        match self {
+            FovAxis::Vertical => fov,
+            FovAxis::Horizontal => horizontal_to_vertical_fov(fov, aspect),
+        }
+    }
+
+    /// 将垂直 FOV 换算为该轴上的 FOV（弧度）
+    pub fn from_vertical(self, fov_y: f32, aspect: f32) -> f32 {
+        match self {
+            FovAxis::Vertical => fov_y,
+            FovAxis::Horizontal => vertical_to_horizontal_fov(fov_y, aspect),
+        }
+    }
+}
+
+/// 水平 FOV 换算为垂直 FOV（弧度）
+pub fn horizontal_to_vertical_fov(fov_x: f32, aspect: f32) -> f32 {
+    2.0 * ((0.5 * fov_x).tan() / aspect).atan()
+}
+
+/// 垂直 FOV 换算为水平 FOV（弧度）
+pub fn vertical_to_horizontal_fov(fov_y: f32, aspect: f32) -> f32 {
+    2.0 * ((0.5 * fov_y).tan() * aspect).atan()
+}
+
 /// Camera 组件
 ///
 /// 管理相机的视图和投影，支持移动、旋转等操作
@@ -35,6 +102,12 @@ pub struct Camera {
     /// 垂直视场角（弧度）
     fov_y: f32,
 
+    /// 保持不变的 FOV 轴
+    fov_axis: FovAxis,
+
+    /// `fov_axis` 上的视场角（弧度）
+    fov: f32,
+
     /// 近平面高度
     near_window_height: f32,
 
@@ -49,6 +122,17 @@ pub struct Camera {
 
     /// 视图矩阵是否需要更新
     view_dirty: bool,
+
+    /// 相机抖动状态和当前帧叠加在视图矩阵上的偏移
+    shake: CameraShake,
+    shake_offset: ShakeOffset,
+
+    /// 投影矩阵的亚像素抖动（NDC 单位），为零时投影不变
+    jitter: Vector2,
+
+    /// 当前帧和上一帧未抖动的视图投影矩阵（用于之后的运动向量）
+    view_proj: Matrix4,
+    prev_view_proj: Matrix4,
 }
 
 impl Camera {
@@ -63,11 +147,18 @@ impl Camera {
             far_z: 0.0,
             aspect: 0.0,
             fov_y: 0.0,
+            fov_axis: FovAxis::Vertical,
+            fov: 0.0,
             near_window_height: 0.0,
             far_window_height: 0.0,
             view_matrix: Matrix4::identity(),
             proj_matrix: Matrix4::identity(),
             view_dirty: true,
+            shake: CameraShake::default(),
+            shake_offset: ShakeOffset::default(),
+            jitter: Vector2::zeros(),
+            view_proj: Matrix4::identity(),
+            prev_view_proj: Matrix4::identity(),
         };
 
         // 默认透视投影设置：FOV=45度，aspect=1.0，near=1.0，far=1000.0
@@ -145,8 +236,17 @@ impl Camera {
 
     /// 获取水平 FOV（弧度）
     pub fn fov_x(&self) -> f32 {
-        let half_width = 0.5 * self.near_window_width();
-        2.0 * (half_width / self.near_z).atan()
+        vertical_to_horizontal_fov(self.fov_y, self.aspect)
+    }
+
+    /// 获取 FOV 轴
+    pub fn fov_axis(&self) -> FovAxis {
+        self.fov_axis
+    }
+
+    /// 获取 FOV 轴上的视场角（弧度）
+    pub fn fov(&self) -> f32 {
+        self.fov
     }
 
     /// 获取近平面宽度
@@ -171,7 +271,7 @@ impl Camera {
 
     // ========== 设置透视投影 ==========
 
-    /// 设置透视投影参数
+    /// 设置透视投影参数（垂直 FOV）
     ///
     /// # 参数
     /// - `fov_y`: 垂直视场角（弧度）
@@ -179,16 +279,24 @@ impl Camera {
     /// - `near_z`: 近裁剪面距离
     /// - `far_z`: 远裁剪面距离
     pub fn set_lens(&mut self, fov_y: f32, aspect: f32, near_z: f32, far_z: f32) {
-        self.fov_y = fov_y;
+        self.set_perspective(fov_y, FovAxis::Vertical, aspect, near_z, far_z);
+    }
+
+    /// 设置透视投影参数
+    ///
+    /// # 参数
+    /// - `fov`: `fov_axis` 上的视场角（弧度）
+    /// - `fov_axis`: FOV 轴，之后改变宽高比时保持该轴的视场角不变
+    /// - `aspect`: 宽高比
+    /// - `near_z`: 近裁剪面距离
+    /// - `far_z`: 远裁剪面距离
+    pub fn set_perspective(&mut self, fov: f32, fov_axis: FovAxis, aspect: f32, near_z: f32, far_z: f32) {
+        self.fov = fov;
+        self.fov_axis = fov_axis;
         self.aspect = aspect;
         self.near_z = near_z;
         self.far_z = far_z;
-
-        self.near_window_height = 2.0 * self.near_z * (0.5 * self.fov_y).tan();
-        self.far_window_height = 2.0 * self.far_z * (0.5 * self.fov_y).tan();
-
-        // 创建透视投影矩阵
-        self.proj_matrix = Matrix4::new_perspective(aspect, fov_y, near_z, far_z);
+        self.update_projection();
     }
 
     /// 设置宽高比
@@ -198,11 +306,21 @@ impl Camera {
     pub fn set_aspect(&mut self, aspect: f32) {
         if (self.aspect - aspect).abs() > f32::EPSILON {
             self.aspect = aspect;
-            // 重新计算投影矩阵
-            self.proj_matrix = Matrix4::new_perspective(self.aspect, self.fov_y, self.near_z, self.far_z);
+            self.update_projection();
         }
     }
 
+    /// 根据 FOV 轴和宽高比重新计算垂直 FOV 和投影矩阵
+    fn update_projection(&mut self) {
+        self.fov_y = self.fov_axis.to_vertical(self.fov, self.aspect);
+
+        self.near_window_height = 2.0 * self.near_z * (0.5 * self.fov_y).tan();
+        self.far_window_height = 2.0 * self.far_z * (0.5 * self.fov_y).tan();
+
+        // 创建透视投影矩阵
+        self.proj_matrix = Matrix4::new_perspective(self.aspect, self.fov_y, self.near_z, self.far_z);
+    }
+
     // ========== LookAt ==========
 
     /// 设置相机朝向目标点
@@ -229,21 +347,181 @@ impl Camera {
         self.view_dirty = true;
     }
 
+    /// 让包围球完整位于视野内
+    ///
+    /// 保持当前朝向，把相机放到沿视线方向距球心一定距离处，
+    /// 使包围球恰好被水平和垂直视场角中较小的一个容纳。
+    pub fn frame_bounds(&mut self, center: Vector3, radius: f32) {
+        let half_fov = 0.5 * self.fov_x().min(self.fov_y);
+        let distance = radius.max(f32::EPSILON) / half_fov.sin();
+
+        // look 为相机的后向轴，相机位于球心的 +look 方向
+        self.transform.set_position(center + self.look * distance);
+        self.view_dirty = true;
+    }
+
+    /// 根据包围盒收紧近远裁剪面
+    ///
+    /// 按当前位置和朝向计算包围盒八个角点的视空间深度，近远裁剪面贴着深度范围并留出少量余量，
+    /// 避免过大的远裁剪面浪费深度精度。近裁剪面不小于 [`MIN_NEAR_Z`]（相机位于包围盒内时即为该值）。
+    /// 包围盒完全在相机后方时不做修改。
+    pub fn fit_clip_planes(&mut self, aabb: &Aabb) {
+        let view = self.view_matrix();
+        let (nearest, farthest) = aabb
+            .corners()
+            .iter()
+            .map(|corner| -(view * corner.push(1.0)).z)
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(near, far), depth| (near.min(depth), far.max(depth)));
+        if farthest <= MIN_NEAR_Z {
+            return;
+        }
+
+        let margin = farthest * CLIP_PLANE_MARGIN;
+        self.near_z = (nearest - margin).max(MIN_NEAR_Z);
+        self.far_z = (farthest + margin).max(self.near_z + MIN_NEAR_Z);
+        self.update_projection();
+    }
+
+    /// 视锥体（从翻转 Y 之前的投影矩阵提取）
+    pub fn frustum(&mut self) -> Frustum {
+        Frustum::from_matrix(&(self.proj_matrix * self.view_matrix()))
+    }
+
+    /// 创建从外部观察本相机视锥体的观察者相机
+    ///
+    /// 观察者与本相机的朝向和视场角相同，沿视线后退到能完整看到视锥体的位置，
+    /// 远裁剪面随之放大，用于调试视锥体剔除。
+    pub fn observer(&mut self) -> Camera {
+        let corners = self.frustum().corners();
+        let center = corners.iter().sum::<Vector3>() / corners.len() as f32;
+        let radius = corners.iter().map(|corner| (corner - center).norm()).fold(0.0f32, f32::max);
+
+        let mut observer = Camera::new("ObserverCamera");
+        let position = self.position();
+        observer.look_at(position, position + self.look, self.up);
+        observer.set_perspective(self.fov, self.fov_axis, self.aspect, self.near_z, 1.0);
+        observer.frame_bounds(center, radius);
+
+        let distance = (observer.position() - center).norm();
+        observer.set_perspective(self.fov, self.fov_axis, self.aspect, self.near_z, distance + radius * 2.0);
+        observer
+    }
+
     // ========== 获取矩阵 ==========
 
-    /// 获取视图矩阵
+    /// 获取视图矩阵（包含相机抖动的偏移）
     pub fn view_matrix(&mut self) -> Matrix4 {
         if self.view_dirty {
             self.update_view_matrix();
         }
-        self.view_matrix
+        if self.shake_offset.is_zero() {
+            self.view_matrix
+        } else {
+            self.shake_offset.matrix() * self.view_matrix
+        }
+    }
+
+    // ========== 相机抖动 ==========
+
+    /// 增加 `trauma` 的抖动创伤值并推进 `delta_time` 秒
+    ///
+    /// 每帧调用一次（没有新的抖动时 `trauma` 为 0），创伤值随时间衰减。
+    /// 偏移只叠加在 [`view_matrix`](Self::view_matrix) 上，不修改相机的位置和朝向。
+    pub fn apply_shake(&mut self, trauma: f32, delta_time: f32) {
+        self.shake_offset = self.shake.update(trauma, delta_time);
+    }
+
+    /// 当前的抖动创伤值（0 到 1）
+    pub fn shake_trauma(&self) -> f32 {
+        self.shake.trauma()
     }
 
-    /// 获取投影矩阵
+    /// 获取投影矩阵（包含亚像素抖动）
     pub fn proj_matrix(&self) -> Matrix4 {
+        if self.jitter == Vector2::zeros() {
+            self.proj_matrix
+        } else {
+            translation(self.jitter.x, self.jitter.y, 0.0) * self.proj_matrix
+        }
+    }
+
+    /// 获取不含亚像素抖动的投影矩阵
+    pub fn unjittered_proj_matrix(&self) -> Matrix4 {
         self.proj_matrix
     }
 
+    // ========== 投影抖动 ==========
+
+    /// 设置投影矩阵的亚像素抖动
+    ///
+    /// `offset` 为 NDC 单位的偏移（一个像素宽为 `2 / 视口宽度`），通常每帧由 [`halton_jitter`] 生成。
+    /// 偏移为零时投影矩阵不变。抖动只影响 [`proj_matrix`](Self::proj_matrix)，剔除和拾取不受影响。
+    pub fn set_jitter(&mut self, offset: Vector2) {
+        self.jitter = offset;
+    }
+
+    /// 当前的投影抖动（NDC 单位）
+    pub fn jitter(&self) -> Vector2 {
+        self.jitter
+    }
+
+    /// 记录本帧未抖动的视图投影矩阵，之前的值移到上一帧（每帧绘制前调用一次）
+    pub fn update_view_proj(&mut self) {
+        self.prev_view_proj = self.view_proj;
+        self.view_proj = self.proj_matrix * self.view_matrix();
+    }
+
+    /// 本帧未抖动的视图投影矩阵
+    pub fn view_proj(&self) -> Matrix4 {
+        self.view_proj
+    }
+
+    /// 上一帧未抖动的视图投影矩阵
+    pub fn prev_view_proj(&self) -> Matrix4 {
+        self.prev_view_proj
+    }
+
+    /// 视图投影矩阵（视图矩阵需要更新时临时计算，不修改相机）
+    fn view_proj_matrix(&self) -> Matrix4 {
+        let view = if self.view_dirty { self.orthonormal_view().3 } else { self.view_matrix };
+        self.proj_matrix * view
+    }
+
+    // ========== 屏幕坐标转换 ==========
+
+    /// 世界坐标投影到屏幕像素坐标
+    ///
+    /// `viewport` 为视口的宽高（像素），屏幕坐标原点在左上角、y 向下。
+    /// 点位于相机后方时返回 `None`；在视锥体外但位于相机前方的点仍返回屏幕外的坐标。
+    pub fn world_to_screen(&self, world: Vector3, viewport: (f32, f32)) -> Option<Vector2> {
+        let clip = self.view_proj_matrix() * world.push(1.0);
+        if clip.w <= 0.0 {
+            return None;
+        }
+        let ndc = clip.xy() / clip.w;
+        Some(Vector2::new(
+            (ndc.x + 1.0) * 0.5 * viewport.0,
+            (1.0 - ndc.y) * 0.5 * viewport.1,
+        ))
+    }
+
+    /// 屏幕像素坐标对应的世界空间射线
+    ///
+    /// 射线从近裁剪面上的对应点出发，穿过远裁剪面上的对应点；
+    /// 坐标约定与 [`world_to_screen`](Self::world_to_screen) 相同。
+    pub fn screen_to_ray(&self, screen: Vector2, viewport: (f32, f32)) -> Ray {
+        let ndc_x = screen.x / viewport.0 * 2.0 - 1.0;
+        let ndc_y = 1.0 - screen.y / viewport.1 * 2.0;
+        let inverse = self.view_proj_matrix().try_inverse().unwrap_or_else(Matrix4::identity);
+        let unproject = |ndc_z: f32| {
+            let p = inverse * Vector3::new(ndc_x, ndc_y, ndc_z).push(1.0);
+            p.xyz() / p.w
+        };
+
+        let near = unproject(-1.0);
+        Ray::new(near, unproject(1.0) - near)
+    }
+
     // ========== 相机移动 ==========
 
     /// 左右平移（Strafe）
@@ -307,6 +585,19 @@ impl Camera {
             return;
         }
 
+        let (right, up, look, view) = self.orthonormal_view();
+
+        // 更新坐标轴
+        self.right = right;
+        self.up = up;
+        self.look = look;
+
+        self.view_matrix = view;
+        self.view_dirty = false;
+    }
+
+    /// 正交归一化后的相机坐标轴（右、上、前）和对应的视图矩阵
+    fn orthonormal_view(&self) -> (Vector3, Vector3, Vector3, Matrix4) {
         // 保持相机坐标轴正交归一化
         let look = self.look.normalize();
         let up = look.cross(&self.right).normalize();
@@ -319,11 +610,6 @@ impl Camera {
         let y = -position.dot(&up);
         let z = -position.dot(&look);
 
-        // 更新坐标轴
-        self.right = right;
-        self.up = up;
-        self.look = look;
-
         // 手动构建视图矩阵（行主序）
         #[rustfmt::skip]
         let view = Matrix4::new(
@@ -333,8 +619,7 @@ impl Camera {
             0.0,     0.0,     0.0,     1.0,
         );
 
-        self.view_matrix = view;
-        self.view_dirty = false;
+        (right, up, look, view)
     }
 
     /// 获取 Transform 组件的可变引用
@@ -369,3 +654,209 @@ impl Default for Camera {
         Self::main_camera()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fov_conversion_round_trips() {
+        for aspect in [0.5, 1.0, 16.0 / 9.0, 3.0] {
+            for degrees in [30.0f32, 60.0, 90.0, 120.0] {
+                let fov = degrees.to_radians();
+
+                let fov_y = horizontal_to_vertical_fov(fov, aspect);
+                assert!((vertical_to_horizontal_fov(fov_y, aspect) - fov).abs() < 1e-5);
+
+                let fov_x = vertical_to_horizontal_fov(fov, aspect);
+                assert!((horizontal_to_vertical_fov(fov_x, aspect) - fov).abs() < 1e-5);
+            }
+        }
+
+        // 宽高比为 1 时两个轴相同
+        assert!((horizontal_to_vertical_fov(1.0, 1.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_horizontal_fov_is_kept_on_resize() {
+        let mut camera = Camera::main_camera();
+        let fov = 90f32.to_radians();
+        camera.set_perspective(fov, FovAxis::Horizontal, 16.0 / 9.0, 0.1, 100.0);
+        assert!((camera.fov_x() - fov).abs() < 1e-5);
+
+        camera.set_aspect(4.0 / 3.0);
+        assert!((camera.fov_x() - fov).abs() < 1e-5);
+        assert!((camera.fov_y() - horizontal_to_vertical_fov(fov, 4.0 / 3.0)).abs() < 1e-6);
+        assert_eq!(camera.fov(), fov);
+    }
+
+    #[test]
+    fn test_vertical_fov_is_kept_on_resize() {
+        let mut camera = Camera::main_camera();
+        let fov = 60f32.to_radians();
+        camera.set_lens(fov, 16.0 / 9.0, 0.1, 100.0);
+
+        camera.set_aspect(1.0);
+        assert_eq!(camera.fov_axis(), FovAxis::Vertical);
+        assert!((camera.fov_y() - fov).abs() < 1e-6);
+        assert!((camera.fov_x() - fov).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_observer_sees_whole_frustum() {
+        let mut camera = Camera::main_camera();
+        camera.set_lens(60f32.to_radians(), 16.0 / 9.0, 0.5, 20.0);
+        camera.look_at(Vector3::new(1.0, 2.0, 3.0), Vector3::new(1.0, 2.0, 13.0), Vector3::y());
+
+        let corners = camera.frustum().corners();
+        let mut observer = camera.observer();
+        let view_proj = observer.proj_matrix() * observer.view_matrix();
+
+        // 视锥体的每个角点都在观察者的裁剪空间内
+        for corner in corners {
+            let clip = view_proj * corner.push(1.0);
+            assert!(clip.w > 0.0);
+            let ndc = clip.xyz() / clip.w;
+            assert!(ndc.iter().all(|v| v.abs() <= 1.0), "{} outside observer view", corner);
+        }
+        assert!((observer.look() - camera.look()).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_frame_bounds_fits_sphere() {
+        let mut camera = Camera::main_camera();
+        camera.set_lens(60f32.to_radians(), 16.0 / 9.0, 0.1, 100.0);
+        let center = Vector3::new(1.0, 2.0, 3.0);
+        let radius = 2.0;
+
+        camera.frame_bounds(center, radius);
+
+        let view_proj = camera.proj_matrix() * camera.view_matrix();
+        let project = |p: Vector3| {
+            let clip = view_proj * p.push(1.0);
+            assert!(clip.w > 0.0);
+            Vector3::new(clip.x / clip.w, clip.y / clip.w, clip.z / clip.w)
+        };
+
+        // 球心位于画面中央，球的上下边缘在垂直方向上贴近画面边缘
+        let ndc = project(center);
+        assert!(ndc.x.abs() < 1e-4 && ndc.y.abs() < 1e-4);
+        let top = project(center + camera.up() * radius);
+        assert!(top.y.abs() <= 1.0 && top.y.abs() > 0.8);
+    }
+
+    #[test]
+    fn test_target_projects_to_screen_center() {
+        let mut camera = Camera::main_camera();
+        camera.set_lens(60f32.to_radians(), 4.0 / 3.0, 0.1, 100.0);
+        let position = Vector3::new(1.0, 2.0, 3.0);
+        camera.look_at(position, Vector3::new(-2.0, 0.5, 10.0), Vector3::y());
+        // look 为相机的后向轴，相机看向 -look 方向
+        let target = position - camera.look() * 8.0;
+        let viewport = (800.0, 600.0);
+
+        let center = camera.world_to_screen(target, viewport).unwrap();
+        assert!((center - Vector2::new(400.0, 300.0)).norm() < 1e-2, "center = {}", center);
+
+        // 相机上方的点在屏幕上半部分（y 向下）
+        let above = camera.world_to_screen(target + camera.up(), viewport).unwrap();
+        assert!(above.y < 300.0);
+
+        // 相机后方的点不可投影
+        assert_eq!(camera.world_to_screen(position * 2.0 - target, viewport), None);
+    }
+
+    #[test]
+    fn test_screen_ray_passes_through_world_point() {
+        let mut camera = Camera::main_camera();
+        camera.set_lens(75f32.to_radians(), 16.0 / 9.0, 0.5, 50.0);
+        // look 为后向轴，相机看向原点
+        camera.look_at(Vector3::new(0.0, 4.0, -6.0), Vector3::new(0.0, 8.0, -12.0), Vector3::y());
+        let viewport = (1280.0, 720.0);
+
+        for world in [Vector3::zeros(), Vector3::new(1.5, 0.5, -1.0), Vector3::new(-3.0, 2.0, 4.0)] {
+            let screen = camera.world_to_screen(world, viewport).unwrap();
+            let ray = camera.screen_to_ray(screen, viewport);
+
+            assert!(ray.distance_to_point(world) < 1e-3, "{} misses {}", world, screen);
+            // 射线朝向该点，起点在近裁剪面上
+            assert!((world - ray.origin).dot(&ray.direction) > 0.0);
+            assert!(((ray.origin - camera.position()).norm() - 0.5).abs() < 0.5);
+        }
+    }
+
+    #[test]
+    fn test_shake_offsets_view_without_moving_camera() {
+        let mut camera = Camera::main_camera();
+        camera.look_at(Vector3::new(0.0, 2.0, -5.0), Vector3::zeros(), Vector3::y());
+        let view = camera.view_matrix();
+
+        // 没有创伤值时不产生偏移
+        camera.apply_shake(0.0, 0.016);
+        assert_eq!(camera.view_matrix(), view);
+
+        camera.apply_shake(1.0, 0.016);
+        assert_ne!(camera.view_matrix(), view);
+        assert_eq!(camera.position(), Vector3::new(0.0, 2.0, -5.0));
+
+        // 创伤值衰减完后回到原来的视图
+        for _ in 0..100 {
+            camera.apply_shake(0.0, 0.016);
+        }
+        assert_eq!(camera.shake_trauma(), 0.0);
+        assert_eq!(camera.view_matrix(), view);
+    }
+
+    #[test]
+    fn test_jitter_offsets_projection_in_ndc() {
+        let mut camera = Camera::main_camera();
+        let proj = camera.proj_matrix();
+        let point = Vector3::new(0.3, -0.2, -5.0).push(1.0);
+        let project = |m: Matrix4| {
+            let clip = m * point;
+            clip.xy() / clip.w
+        };
+
+        let viewport = (1280.0, 720.0);
+        let offset = halton_jitter(0, viewport);
+        assert!(offset.x.abs() <= 1.0 / viewport.0 && offset.y.abs() <= 1.0 / viewport.1);
+
+        camera.set_jitter(offset);
+        assert!((project(camera.proj_matrix()) - project(proj) - offset).norm() < 1e-6);
+        assert_eq!(camera.unjittered_proj_matrix(), proj);
+
+        // 关闭抖动后投影恢复原样
+        camera.set_jitter(Vector2::zeros());
+        assert_eq!(camera.proj_matrix(), proj);
+    }
+
+    #[test]
+    fn test_fit_clip_planes_to_box() {
+        let mut camera = Camera::main_camera();
+        camera.set_position(Vector3::new(0.0, 0.0, 10.0));
+        let unit_box = Aabb::new(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0));
+
+        camera.fit_clip_planes(&unit_box);
+        assert!(camera.near_z() < 9.0 && camera.near_z() > 8.8, "near = {}", camera.near_z());
+        assert!(camera.far_z() > 11.0 && camera.far_z() < 11.2, "far = {}", camera.far_z());
+
+        // 相机在包围盒内时近裁剪面取最小值
+        camera.set_position(Vector3::zeros());
+        camera.fit_clip_planes(&unit_box);
+        assert_eq!(camera.near_z(), MIN_NEAR_Z);
+        assert!(camera.far_z() > 1.0);
+    }
+
+    #[test]
+    fn test_view_proj_history() {
+        let mut camera = Camera::main_camera();
+        camera.look_at(Vector3::new(0.0, 0.0, -5.0), Vector3::zeros(), Vector3::y());
+        camera.update_view_proj();
+        let first = camera.view_proj();
+
+        camera.strafe(1.0);
+        camera.update_view_proj();
+        assert_eq!(camera.prev_view_proj(), first);
+        assert_ne!(camera.view_proj(), first);
+    }
+}
@@ -0,0 +1,123 @@
+//! 相机抖动
+//!
+//! 基于“创伤值”（trauma）的相机抖动：触发时增加创伤值（0 到 1），之后随时间线性衰减。
+//! 抖动强度为创伤值的平方，位置和旋转的偏移由连续的值噪声驱动，噪声的种子来自确定性的
+//! [`Rng`]，同一种子每次运行得到相同的抖动。偏移只叠加在视图矩阵上，相机存储的位置和朝向不变。
+
+use crate::math::matrix::{rotation_x, rotation_y, rotation_z, translation};
+use crate::math::noise::{value_noise, Rng};
+use crate::math::{Matrix4, Vector3};
+
+/// 按键或 GUI 按钮触发一次抖动时增加的创伤值
+pub const SHAKE_TRAUMA: f32 = 0.6;
+
+/// 创伤值为 1 时的最大位移（视空间单位）
+const MAX_OFFSET: f32 = 0.15;
+
+/// 创伤值为 1 时的最大旋转角（弧度，约 3 度）
+const MAX_ANGLE: f32 = 0.05;
+
+/// 每秒衰减的创伤值
+const TRAUMA_DECAY: f32 = 1.0;
+
+/// 噪声采样频率（每秒经过的格点数）
+const NOISE_FREQUENCY: f32 = 15.0;
+
+/// 视空间中的抖动偏移
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ShakeOffset {
+    /// 位移（视空间的右、上、前）
+    pub translation: Vector3,
+    /// 旋转角（俯仰、偏航、滚转，弧度）
+    pub rotation: Vector3,
+}
+
+impl ShakeOffset {
+    /// 是否没有任何偏移
+    pub fn is_zero(&self) -> bool {
+        self.translation == Vector3::zeros() && self.rotation == Vector3::zeros()
+    }
+
+    /// 左乘到视图矩阵上的偏移矩阵
+    pub fn matrix(&self) -> Matrix4 {
+        let t = -self.translation;
+        translation(t.x, t.y, t.z)
+            * rotation_z(self.rotation.z)
+            * rotation_x(self.rotation.x)
+            * rotation_y(self.rotation.y)
+    }
+}
+
+/// 相机抖动状态
+#[derive(Debug, Clone)]
+pub struct CameraShake {
+    /// 当前创伤值（0 到 1）
+    trauma: f32,
+    /// 噪声时间（秒）
+    time: f32,
+    /// 位移和旋转六个通道各自的噪声种子
+    seeds: [u32; 6],
+}
+
+impl CameraShake {
+    /// 用种子创建抖动状态
+    pub fn new(seed: u32) -> Self {
+        let mut rng = Rng::new(seed);
+        Self {
+            trauma: 0.0,
+            time: 0.0,
+            seeds: std::array::from_fn(|_| rng.next_u32()),
+        }
+    }
+
+    /// 当前创伤值
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// 增加 `trauma` 的创伤值并推进 `delta_time` 秒，返回这一帧的偏移
+    ///
+    /// 偏移按衰减前的创伤值计算，因此触发当帧即可看到抖动；创伤值为 0 时偏移为零。
+    pub fn update(&mut self, trauma: f32, delta_time: f32) -> ShakeOffset {
+        self.trauma = (self.trauma + trauma.max(0.0)).min(1.0);
+        self.time += delta_time.max(0.0);
+
+        let shake = self.trauma * self.trauma;
+        let offset = if shake > 0.0 {
+            let noise = |channel: usize| value_noise(self.seeds[channel], self.time * NOISE_FREQUENCY);
+            ShakeOffset {
+                translation: Vector3::new(noise(0), noise(1), noise(2)) * MAX_OFFSET * shake,
+                rotation: Vector3::new(noise(3), noise(4), noise(5)) * MAX_ANGLE * shake,
+            }
+        } else {
+            ShakeOffset::default()
+        };
+
+        self.trauma = (self.trauma - TRAUMA_DECAY * delta_time.max(0.0)).max(0.0);
+        offset
+    }
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trauma_decays_to_zero() {
+        let mut shake = CameraShake::new(1);
+        assert!(!shake.update(1.0, 0.1).is_zero());
+        assert!((shake.trauma() - 0.9).abs() < 1e-6);
+
+        for _ in 0..10 {
+            shake.update(0.0, 0.1);
+        }
+        assert_eq!(shake.trauma(), 0.0);
+        assert!(shake.update(0.0, 0.1).is_zero());
+    }
+}
@@ -69,7 +69,7 @@ impl GameObject {
     /// let mut go = GameObject::new("Player");
     /// go.add_component(Transform::new("PlayerTransform"));
     /// ```
-    pub fn add_component<T: 'static>(&mut self, component: T) {
+    pub fn add_component<T: Component + 'static>(&mut self, component: T) {
         let type_id = TypeId::of::<T>();
         let name = format!("{:?}", type_id); // 简化的名称
 
@@ -84,7 +84,7 @@ impl GameObject {
     ///
     /// # 返回
     /// 如果找到并移除了组件，返回 `true`；否则返回 `false`
-    pub fn remove_component<T: 'static>(&mut self) -> bool {
+    pub fn remove_component<T: Component + 'static>(&mut self) -> bool {
         let type_id = TypeId::of::<T>();
 
         if let Some(index) = self.components.iter().position(|c| c.type_id == type_id) {
@@ -99,7 +99,7 @@ impl GameObject {
     ///
     /// # 返回
     /// 移除的组件数量
-    pub fn remove_all_components<T: 'static>(&mut self) -> usize {
+    pub fn remove_all_components<T: Component + 'static>(&mut self) -> usize {
         let type_id = TypeId::of::<T>();
         let original_len = self.components.len();
 
@@ -124,7 +124,7 @@ impl GameObject {
     ///     println!("Position: {:?}", transform.position);
     /// }
     /// ```
-    pub fn get_component<T: 'static>(&self) -> Option<&T> {
+    pub fn get_component<T: Component + 'static>(&self) -> Option<&T> {
         let type_id = TypeId::of::<T>();
 
         self.components
@@ -141,7 +141,7 @@ impl GameObject {
     /// # 示例
     /// ```
     /// use dist_render::component::{GameObject, Transform};
-    /// use dist_render::core::math::Vector3;
+    /// use dist_render::math::Vector3;
     ///
     /// let mut go = GameObject::new("Player");
     /// go.add_component(Transform::new("PlayerTransform"));
@@ -150,7 +150,7 @@ impl GameObject {
     ///     transform.set_position(Vector3::new(1.0, 0.0, 0.0));
     /// }
     /// ```
-    pub fn get_component_mut<T: 'static>(&mut self) -> Option<&mut T> {
+    pub fn get_component_mut<T: Component + 'static>(&mut self) -> Option<&mut T> {
         let type_id = TypeId::of::<T>();
 
         self.components
@@ -166,7 +166,7 @@ impl GameObject {
     ///
     /// # 返回
     /// 如果找到了指定索引的组件，返回 `Some(&T)`；否则返回 `None`
-    pub fn get_component_at<T: 'static>(&self, index: usize) -> Option<&T> {
+    pub fn get_component_at<T: Component + 'static>(&self, index: usize) -> Option<&T> {
         let type_id = TypeId::of::<T>();
 
         self.components
@@ -177,7 +177,7 @@ impl GameObject {
     }
 
     /// 获取指定索引的组件的可变引用（按类型）
-    pub fn get_component_at_mut<T: 'static>(&mut self, index: usize) -> Option<&mut T> {
+    pub fn get_component_at_mut<T: Component + 'static>(&mut self, index: usize) -> Option<&mut T> {
         let type_id = TypeId::of::<T>();
 
         self.components
@@ -191,7 +191,7 @@ impl GameObject {
     ///
     /// # 返回
     /// 包含所有匹配类型组件的不可变引用的 Vec
-    pub fn get_components<T: 'static>(&self) -> Vec<&T> {
+    pub fn get_components<T: Component + 'static>(&self) -> Vec<&T> {
         let type_id = TypeId::of::<T>();
 
         self.components
@@ -202,7 +202,7 @@ impl GameObject {
     }
 
     /// 获取所有指定类型的组件的可变引用
-    pub fn get_components_mut<T: 'static>(&mut self) -> Vec<&mut T> {
+    pub fn get_components_mut<T: Component + 'static>(&mut self) -> Vec<&mut T> {
         let type_id = TypeId::of::<T>();
 
         self.components
@@ -213,7 +213,7 @@ impl GameObject {
     }
 
     /// 检查是否有指定类型的组件
-    pub fn has_component<T: 'static>(&self) -> bool {
+    pub fn has_component<T: Component + 'static>(&self) -> bool {
         let type_id = TypeId::of::<T>();
         self.components.iter().any(|c| c.type_id == type_id)
     }
@@ -224,7 +224,7 @@ impl GameObject {
     }
 
     /// 获取指定类型的组件数量
-    pub fn component_count_of_type<T: 'static>(&self) -> usize {
+    pub fn component_count_of_type<T: Component + 'static>(&self) -> usize {
         let type_id = TypeId::of::<T>();
         self.components.iter().filter(|c| c.type_id == type_id).count()
     }
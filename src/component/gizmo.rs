@@ -0,0 +1,361 @@
+//! 平移 Gizmo
+//!
+//! 在选中物体的原点绘制三个坐标轴箭头，拖拽某个轴时把鼠标在屏幕上的移动
+//! 投影到该轴的屏幕方向上，换算为沿世界坐标轴的位移。
+//!
+//! 所有计算都基于传给 GPU 的视图投影矩阵和窗口像素坐标（左上角为原点，y 向下），
+//! 与具体后端无关；绘制通过 [`DebugDraw`] 完成。
+
+use crate::math::{Matrix4, Vector2, Vector3};
+use crate::renderer::debug_draw::DebugDraw;
+
+/// 箭头长度占裁剪空间 w 的比例（使箭头在屏幕上大小基本恒定）
+const GIZMO_SIZE: f32 = 0.2;
+
+/// 箭头头部长度占箭头长度的比例
+const ARROW_HEAD_SIZE: f32 = 0.2;
+
+/// 拾取轴时允许的最大像素距离
+const PICK_RADIUS: f32 = 8.0;
+
+/// 按住吸附键时的网格间距（世界单位）
+pub const GIZMO_SNAP_STEP: f32 = 0.5;
+
+/// 悬停或拖拽中的轴使用的颜色
+const ACTIVE_COLOR: [f32; 3] = [1.0, 0.9, 0.1];
+
+/// Gizmo 坐标轴
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GizmoAxis {
+    X,
+    Y,
+    Z,
+}
+
+impl GizmoAxis {
+    /// 所有坐标轴
+    pub const ALL: [GizmoAxis; 3] = [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z];
+
+    /// 分量序号
+    pub fn index(self) -> usize {
+        match self {
+            GizmoAxis::X => 0,
+            GizmoAxis::Y => 1,
+            GizmoAxis::Z => 2,
+        }
+    }
+
+    /// 世界空间方向
+    pub fn direction(self) -> Vector3 {
+        let mut direction = Vector3::zeros();
+        direction[self.index()] = 1.0;
+        direction
+    }
+
+    /// 绘制颜色（X 红、Y 绿、Z 蓝）
+    pub fn color(self) -> [f32; 3] {
+        let mut color = [0.0; 3];
+        color[self.index()] = 1.0;
+        color
+    }
+}
+
+/// 每帧传给 Gizmo 的输入状态
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GizmoInput {
+    /// 鼠标位置（窗口像素坐标）
+    pub cursor: Vector2,
+
+    /// 左键是否按下
+    pub pressed: bool,
+
+    /// 吸附网格间距，`None` 表示不吸附
+    pub snap: Option<f32>,
+}
+
+/// 拖拽状态
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GizmoDrag {
+    axis: GizmoAxis,
+    start_position: Vector3,
+    start_cursor: Vector2,
+}
+
+/// 平移 Gizmo
+#[derive(Debug, Clone, Default)]
+pub struct TranslateGizmo {
+    drag: Option<GizmoDrag>,
+    hovered: Option<GizmoAxis>,
+    was_pressed: bool,
+}
+
+impl TranslateGizmo {
+    /// 创建 Gizmo
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 是否正在拖拽
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// 当前高亮的轴（拖拽中的轴优先，其次是悬停的轴）
+    pub fn active_axis(&self) -> Option<GizmoAxis> {
+        self.drag.map(|drag| drag.axis).or(self.hovered)
+    }
+
+    /// 取消拖拽（例如选中的物体发生变化）
+    pub fn reset(&mut self) {
+        self.drag = None;
+        self.hovered = None;
+    }
+
+    /// 处理一帧输入
+    ///
+    /// 左键按下的那一帧若鼠标位于某个轴附近则开始拖拽，松开时结束。
+    /// 按下时不在轴上的点击不会在之后移到轴上时触发拖拽。
+    ///
+    /// # 参数
+    ///
+    /// - `position`: 物体当前位置
+    /// - `view_proj`: 视图投影矩阵（与绘制使用的一致）
+    /// - `viewport`: 视口尺寸（像素）
+    ///
+    /// # 返回
+    ///
+    /// 拖拽中返回物体的新位置，否则返回 `None`
+    pub fn update(
+        &mut self,
+        input: GizmoInput,
+        position: Vector3,
+        view_proj: &Matrix4,
+        viewport: [f32; 2],
+    ) -> Option<Vector3> {
+        let just_pressed = input.pressed && !self.was_pressed;
+        self.was_pressed = input.pressed;
+
+        if !input.pressed {
+            self.drag = None;
+        }
+
+        if self.drag.is_none() {
+            self.hovered = hit_test(position, view_proj, viewport, input.cursor);
+            if just_pressed {
+                self.drag = self.hovered.map(|axis| GizmoDrag {
+                    axis,
+                    start_position: position,
+                    start_cursor: input.cursor,
+                });
+            }
+        }
+
+        let drag = self.drag?;
+        let offset = axis_offset(drag.start_position, drag.axis, view_proj, viewport, input.cursor - drag.start_cursor);
+
+        let mut new_position = drag.start_position + drag.axis.direction() * offset;
+        if let Some(step) = input.snap.filter(|step| *step > 0.0) {
+            let i = drag.axis.index();
+            new_position[i] = (new_position[i] / step).round() * step;
+        }
+        Some(new_position)
+    }
+
+    /// 将三个轴箭头添加到调试绘制器
+    pub fn draw(&self, debug_draw: &mut DebugDraw, position: Vector3, view_proj: &Matrix4) {
+        let Some(length) = axis_length(position, view_proj) else {
+            return;
+        };
+        let active = self.active_axis();
+
+        for axis in GizmoAxis::ALL {
+            let color = if active == Some(axis) { ACTIVE_COLOR } else { axis.color() };
+            let tip = position + axis.direction() * length;
+
+            // 箭头头部：从末端向回折出两条短线，方向取另一个坐标轴
+            let back = tip - axis.direction() * (length * ARROW_HEAD_SIZE);
+            let side = GizmoAxis::ALL[(axis.index() + 1) % 3].direction() * (length * ARROW_HEAD_SIZE * 0.5);
+
+            debug_draw.line(position, tip, color);
+            debug_draw.line(tip, back + side, color);
+            debug_draw.line(tip, back - side, color);
+        }
+    }
+}
+
+/// 箭头的世界空间长度，物体在相机后方时返回 `None`
+fn axis_length(position: Vector3, view_proj: &Matrix4) -> Option<f32> {
+    let w = (view_proj * position.push(1.0)).w;
+    (w > 0.0).then_some(w * GIZMO_SIZE)
+}
+
+/// 世界坐标投影到窗口像素坐标（y 向下），位于相机后方时返回 `None`
+fn to_screen(point: Vector3, view_proj: &Matrix4, viewport: [f32; 2]) -> Option<Vector2> {
+    let clip = view_proj * point.push(1.0);
+    if clip.w <= 0.0 {
+        return None;
+    }
+    let ndc = clip.xy() / clip.w;
+    Some(Vector2::new(
+        (ndc.x + 1.0) * 0.5 * viewport[0],
+        (1.0 - ndc.y) * 0.5 * viewport[1],
+    ))
+}
+
+/// 查找鼠标附近的轴，多个轴都在范围内时取最近的
+fn hit_test(position: Vector3, view_proj: &Matrix4, viewport: [f32; 2], cursor: Vector2) -> Option<GizmoAxis> {
+    let length = axis_length(position, view_proj)?;
+    let origin = to_screen(position, view_proj, viewport)?;
+
+    GizmoAxis::ALL
+        .into_iter()
+        .filter_map(|axis| {
+            let tip = to_screen(position + axis.direction() * length, view_proj, viewport)?;
+            Some((axis, distance_to_segment(cursor, origin, tip)))
+        })
+        .filter(|(_, distance)| *distance <= PICK_RADIUS)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(axis, _)| axis)
+}
+
+/// 鼠标位移在轴上对应的世界空间距离
+///
+/// 轴的屏幕投影给出每单位世界距离对应的像素向量，鼠标位移投影到该向量上即可。
+/// 轴几乎指向相机时屏幕投影退化，此时不移动。
+fn axis_offset(
+    position: Vector3,
+    axis: GizmoAxis,
+    view_proj: &Matrix4,
+    viewport: [f32; 2],
+    cursor_delta: Vector2,
+) -> f32 {
+    let (Some(origin), Some(unit)) = (
+        to_screen(position, view_proj, viewport),
+        to_screen(position + axis.direction(), view_proj, viewport),
+    ) else {
+        return 0.0;
+    };
+
+    let screen_axis = unit - origin;
+    let length_squared = screen_axis.norm_squared();
+    if length_squared < 1e-6 {
+        return 0.0;
+    }
+    cursor_delta.dot(&screen_axis) / length_squared
+}
+
+/// 点到线段的距离
+fn distance_to_segment(point: Vector2, start: Vector2, end: Vector2) -> f32 {
+    let segment = end - start;
+    let length_squared = segment.norm_squared();
+    let t = if length_squared > 0.0 {
+        ((point - start).dot(&segment) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (point - (start + segment * t)).norm()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix;
+
+    const VIEWPORT: [f32; 2] = [800.0, 600.0];
+
+    /// 相机位于 (0, 0, 10) 看向原点
+    fn view_proj() -> Matrix4 {
+        let view = matrix::look_at(
+            &Vector3::new(0.0, 0.0, 10.0),
+            &Vector3::zeros(),
+            &Vector3::new(0.0, 1.0, 0.0),
+        );
+        let proj = matrix::perspective(std::f32::consts::FRAC_PI_4, VIEWPORT[0] / VIEWPORT[1], 0.1, 100.0);
+        proj * view
+    }
+
+    fn input(cursor: Vector2, pressed: bool) -> GizmoInput {
+        GizmoInput { cursor, pressed, snap: None }
+    }
+
+    /// X 轴中点的屏幕坐标
+    fn x_axis_midpoint(position: Vector3) -> Vector2 {
+        let length = axis_length(position, &view_proj()).unwrap();
+        to_screen(position + Vector3::x() * (length * 0.5), &view_proj(), VIEWPORT).unwrap()
+    }
+
+    #[test]
+    fn test_hit_test_picks_axis_under_cursor() {
+        let position = Vector3::zeros();
+        let cursor = x_axis_midpoint(position);
+
+        assert_eq!(hit_test(position, &view_proj(), VIEWPORT, cursor), Some(GizmoAxis::X));
+        assert_eq!(hit_test(position, &view_proj(), VIEWPORT, cursor + Vector2::new(0.0, 50.0)), None);
+    }
+
+    #[test]
+    fn test_drag_moves_along_axis() {
+        let mut gizmo = TranslateGizmo::new();
+        let position = Vector3::zeros();
+        let start = x_axis_midpoint(position);
+
+        assert_eq!(gizmo.update(input(start, false), position, &view_proj(), VIEWPORT), None);
+        assert_eq!(gizmo.update(input(start, true), position, &view_proj(), VIEWPORT), Some(position));
+        assert_eq!(gizmo.active_axis(), Some(GizmoAxis::X));
+
+        // 向右拖动一个世界单位对应的像素距离，垂直方向的移动被忽略
+        let pixels_per_unit = (to_screen(Vector3::x(), &view_proj(), VIEWPORT).unwrap()
+            - to_screen(Vector3::zeros(), &view_proj(), VIEWPORT).unwrap())
+            .x;
+        let cursor = start + Vector2::new(pixels_per_unit, 30.0);
+        let moved = gizmo.update(input(cursor, true), position, &view_proj(), VIEWPORT).unwrap();
+        assert!((moved - Vector3::x()).norm() < 1e-4, "moved = {:?}", moved);
+
+        // 松开后结束拖拽
+        assert_eq!(gizmo.update(input(cursor, false), moved, &view_proj(), VIEWPORT), None);
+        assert!(!gizmo.is_dragging());
+    }
+
+    #[test]
+    fn test_press_outside_axis_does_not_start_drag() {
+        let mut gizmo = TranslateGizmo::new();
+        let position = Vector3::zeros();
+        let on_axis = x_axis_midpoint(position);
+
+        gizmo.update(input(on_axis + Vector2::new(0.0, 100.0), true), position, &view_proj(), VIEWPORT);
+        assert_eq!(gizmo.update(input(on_axis, true), position, &view_proj(), VIEWPORT), None);
+    }
+
+    #[test]
+    fn test_snap_rounds_to_grid() {
+        let mut gizmo = TranslateGizmo::new();
+        let position = Vector3::new(0.1, 0.0, 0.0);
+        let start = x_axis_midpoint(position);
+        let snapped = |cursor| GizmoInput {
+            cursor,
+            pressed: true,
+            snap: Some(GIZMO_SNAP_STEP),
+        };
+
+        gizmo.update(snapped(start), position, &view_proj(), VIEWPORT);
+        let moved = gizmo
+            .update(snapped(start + Vector2::new(20.0, 0.0)), position, &view_proj(), VIEWPORT)
+            .unwrap();
+
+        let steps = moved.x / GIZMO_SNAP_STEP;
+        assert!((steps - steps.round()).abs() < 1e-5, "x = {}", moved.x);
+        assert_eq!(moved.y, 0.0);
+    }
+
+    #[test]
+    fn test_draw_adds_three_arrows() {
+        let mut debug_draw = DebugDraw::new();
+        TranslateGizmo::new().draw(&mut debug_draw, Vector3::zeros(), &view_proj());
+        assert_eq!(debug_draw.lines().len(), 9);
+
+        // 相机后方的物体不绘制
+        debug_draw.clear();
+        TranslateGizmo::new().draw(&mut debug_draw, Vector3::new(0.0, 0.0, 20.0), &view_proj());
+        assert!(debug_draw.is_empty());
+    }
+}
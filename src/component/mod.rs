@@ -1,15 +1,26 @@
 //! 组件系统模块
 //!
 //! 参考 DistEngine 的 Component 架构实现的组件系统。
-//! 提供 GameObject、Transform、Camera、Light 等游戏对象组件。
+//! 提供 GameObject、Transform、Camera、Light 等游戏对象组件，以及按组件类型查询游戏对象的 Scene。
 
 mod component;
 mod transform;
 mod camera;
+mod camera_shake;
 mod game_object;
+mod scene;
 mod light;
+mod gizmo;
+mod turntable;
+mod auto_rotate;
 
 pub use component::Component;
 pub use transform::Transform;
-pub use camera::Camera;
+pub use camera::{halton_jitter, Camera, FovAxis, MIN_NEAR_Z};
+pub use camera_shake::{CameraShake, ShakeOffset, SHAKE_TRAUMA};
+pub use game_object::GameObject;
+pub use scene::Scene;
 pub use light::{Color, DirectionalLight};
+pub use gizmo::{GizmoAxis, GizmoInput, TranslateGizmo, GIZMO_SNAP_STEP};
+pub use turntable::Turntable;
+pub use auto_rotate::{AutoRotate, DEFAULT_AUTO_ROTATE_SPEED};
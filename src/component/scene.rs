@@ -0,0 +1,121 @@
+//! 游戏对象场景
+//!
+//! 持有一组 GameObject，并按组件类型查询，例如遍历场景中所有带 `DirectionalLight` 的对象。
+//! 组件按 `TypeId` 匹配并通过 `Any` 向下转型，与 [`GameObject::get_component`] 相同。
+
+use super::{Component, GameObject};
+
+/// 游戏对象场景
+#[derive(Default)]
+pub struct Scene {
+    /// 按添加顺序排列的游戏对象
+    objects: Vec<GameObject>,
+}
+
+impl Scene {
+    /// 创建空场景
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加游戏对象，返回其序号
+    pub fn add(&mut self, object: GameObject) -> usize {
+        self.objects.push(object);
+        self.objects.len() - 1
+    }
+
+    /// 所有游戏对象
+    pub fn objects(&self) -> &[GameObject] {
+        &self.objects
+    }
+
+    /// 所有游戏对象的可变引用
+    pub fn objects_mut(&mut self) -> &mut [GameObject] {
+        &mut self.objects
+    }
+
+    /// 按名称查找游戏对象
+    pub fn find(&self, name: &str) -> Option<&GameObject> {
+        self.objects.iter().find(|object| object.get_name() == name)
+    }
+
+    /// 遍历启用的游戏对象上所有类型为 `T` 的组件及其所属对象
+    ///
+    /// 同一对象上有多个 `T` 组件时逐个返回，按对象添加顺序排列。
+    ///
+    /// # 示例
+    /// ```
+    /// use dist_render::component::{DirectionalLight, GameObject, Scene};
+    ///
+    /// let mut scene = Scene::new();
+    /// let mut sun = GameObject::new("Sun");
+    /// sun.add_component(DirectionalLight::new("SunLight"));
+    /// scene.add(sun);
+    ///
+    /// for (object, light) in scene.query::<DirectionalLight>() {
+    ///     println!("{}: {}", object.get_name(), light.intensity);
+    /// }
+    /// ```
+    pub fn query<T: Component + 'static>(&self) -> impl Iterator<Item = (&GameObject, &T)> {
+        self.objects
+            .iter()
+            .filter(|object| object.enabled)
+            .flat_map(|object| object.get_components::<T>().into_iter().map(move |component| (object, component)))
+    }
+
+    /// 遍历启用的游戏对象上所有类型为 `T` 的组件的可变引用
+    pub fn query_mut<T: Component + 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        self.objects
+            .iter_mut()
+            .filter(|object| object.enabled)
+            .flat_map(|object| object.get_components_mut::<T>())
+    }
+
+    /// 更新所有游戏对象
+    pub fn tick(&mut self, delta_time: f32) {
+        for object in &mut self.objects {
+            object.tick(delta_time);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::{Camera, DirectionalLight, Transform};
+
+    #[test]
+    fn test_query_cameras_and_lights() {
+        let mut scene = Scene::new();
+
+        let mut camera = GameObject::new("MainCamera");
+        camera.add_component(Camera::new("Camera"));
+        camera.add_component(Transform::new("CameraTransform"));
+        scene.add(camera);
+
+        let mut sun = GameObject::new("Sun");
+        sun.add_component(DirectionalLight::new("SunLight"));
+        scene.add(sun);
+
+        let mut disabled = GameObject::new("DisabledLight");
+        disabled.add_component(DirectionalLight::new("Hidden"));
+        disabled.enabled = false;
+        scene.add(disabled);
+
+        let cameras: Vec<&str> = scene.query::<Camera>().map(|(object, _)| object.get_name()).collect();
+        assert_eq!(cameras, vec!["MainCamera"]);
+
+        let lights: Vec<(&str, &str)> = scene
+            .query::<DirectionalLight>()
+            .map(|(object, light)| (object.get_name(), light.name()))
+            .collect();
+        assert_eq!(lights, vec![("Sun", "SunLight")]);
+
+        for light in scene.query_mut::<DirectionalLight>() {
+            light.intensity = 3.0;
+        }
+        let sun = scene.find("Sun").unwrap().get_component::<DirectionalLight>().unwrap();
+        assert_eq!(sun.intensity, 3.0);
+        assert_eq!(scene.query::<Transform>().count(), 1);
+    }
+}
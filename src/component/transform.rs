@@ -2,13 +2,20 @@
 //!
 //! 参考 DistEngine 的 Transform 类实现
 //! 管理游戏对象的位置、旋转和缩放
+//!
+//! 序列化格式与场景文件中的变换相同（`position`、`rotation`（欧拉角，度数）、`scale`），
+//! 组件名称不参与序列化。
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use super::Component;
-use crate::math::{Vector3, Matrix4, Quaternion};
+use crate::core::scene::Transform as TransformConfig;
+use crate::math::{quaternion, Vector3, Matrix4, Quaternion};
 
 /// Transform 组件
 ///
 /// 管理游戏对象的空间变换（位置、旋转、缩放）
+#[derive(Debug)]
 pub struct Transform {
     /// 组件名称
     name: String,
@@ -126,7 +133,17 @@ impl Transform {
 
     /// 获取四元数
     pub fn quaternion(&self) -> Quaternion {
-        self.quaternion
+        if self.world_dirty {
+            quaternion::from_euler_degrees(&self.euler_angle)
+        } else {
+            self.quaternion
+        }
+    }
+
+    /// 用四元数设置旋转（转换为欧拉角保存）
+    pub fn set_quaternion(&mut self, rotation: Quaternion) {
+        self.euler_angle = quaternion::to_euler_degrees(&rotation);
+        self.world_dirty = true;
     }
 
     /// 获取世界矩阵
@@ -161,8 +178,8 @@ impl Transform {
         // 组合：T * R * S
         self.world_matrix = translation * rotation * scale;
 
-        // 更新四元数
-        self.quaternion = Quaternion::from_euler_angles(roll, pitch, yaw);
+        // 更新四元数（与上面的 Rz * Ry * Rx 顺序一致）
+        self.quaternion = quaternion::from_euler_degrees(&self.euler_angle);
 
         // 更新前向向量
         self.forward = rotation.transform_vector(&Vector3::new(0.0, 0.0, -1.0)).normalize();
@@ -186,3 +203,78 @@ impl Default for Transform {
         Self::new("Transform")
     }
 }
+
+impl From<&Transform> for TransformConfig {
+    fn from(transform: &Transform) -> Self {
+        Self {
+            position: transform.position.into(),
+            rotation: transform.euler_angle.into(),
+            scale: transform.scale.into(),
+        }
+    }
+}
+
+impl From<&TransformConfig> for Transform {
+    fn from(config: &TransformConfig) -> Self {
+        let mut transform = Self::with_position_rotation(
+            "Transform",
+            Vector3::from(config.position),
+            Vector3::from(config.rotation),
+        );
+        transform.scale = Vector3::from(config.scale);
+        transform
+    }
+}
+
+impl Serialize for Transform {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TransformConfig::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Transform {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        TransformConfig::deserialize(deserializer).map(|config| Transform::from(&config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transform_toml_round_trip() {
+        let mut transform = Transform::with_position("Model", Vector3::new(1.0, 2.0, 3.0));
+        transform.set_euler_angle_xyz(10.0, 20.0, 30.0);
+        transform.set_scale_xyz(2.0, 2.0, 0.5);
+
+        let text = toml::to_string(&transform).unwrap();
+        assert!(text.contains("rotation"));
+
+        let mut parsed: Transform = toml::from_str(&text).unwrap();
+        assert_eq!(parsed.position, transform.position);
+        assert_eq!(parsed.euler_angle, transform.euler_angle);
+        assert_eq!(parsed.scale, transform.scale);
+        assert!((parsed.world_matrix() - transform.world_matrix()).norm() < 1e-6);
+
+        // 缺省字段使用场景文件的默认值
+        let parsed: Transform = toml::from_str("position = [0.0, 1.0, 0.0]").unwrap();
+        assert_eq!(parsed.scale, Vector3::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_quaternion_matches_world_matrix() {
+        let mut transform = Transform::new("Model");
+        transform.set_euler_angle_xyz(35.0, -60.0, 15.0);
+
+        // 没有平移和缩放时，世界矩阵就是四元数对应的旋转矩阵
+        let rotation = transform.quaternion().to_homogeneous();
+        assert!((transform.world_matrix() - rotation).norm() < 1e-5);
+
+        // 通过四元数设置旋转后得到相同的变换
+        let mut copy = Transform::new("Copy");
+        copy.set_quaternion(transform.quaternion());
+        assert!((copy.euler_angle - transform.euler_angle).norm() < 1e-3);
+        assert!((copy.world_matrix() - transform.world_matrix()).norm() < 1e-5);
+    }
+}
@@ -0,0 +1,156 @@
+//! 转台录制
+//!
+//! 相机绕目标的竖直轴匀速旋转一周，每一步截取一帧并保存为编号的 PNG 序列，
+//! 用于生成资源预览。开始前先用 [`Camera::frame_bounds`] 让目标完整入镜，
+//! 之后以此时的相机位置作为起点，保持距离和高度不变。
+
+use std::f32::consts::TAU;
+use std::path::{Path, PathBuf};
+
+use crate::component::Camera;
+use crate::math::{Matrix4, Vector3};
+
+/// 转台录制状态
+#[derive(Debug, Clone)]
+pub struct Turntable {
+    /// 旋转中心
+    center: Vector3,
+
+    /// 起始帧相机相对旋转中心的偏移
+    offset: Vector3,
+
+    /// 总帧数
+    frame_count: u32,
+
+    /// 当前帧序号
+    frame: u32,
+
+    /// 输出目录
+    output_dir: PathBuf,
+}
+
+impl Turntable {
+    /// 开始录制
+    ///
+    /// # 参数
+    ///
+    /// - `camera`: 录制使用的相机，会先调用 `frame_bounds` 框住目标
+    /// - `center`/`radius`: 目标的包围球
+    /// - `frame_count`: 旋转一周的帧数（至少 1）
+    /// - `output_dir`: PNG 序列的输出目录
+    pub fn new(
+        camera: &mut Camera,
+        center: Vector3,
+        radius: f32,
+        frame_count: u32,
+        output_dir: impl Into<PathBuf>,
+    ) -> Self {
+        camera.frame_bounds(center, radius);
+
+        Self {
+            center,
+            offset: camera.position() - center,
+            frame_count: frame_count.max(1),
+            frame: 0,
+            output_dir: output_dir.into(),
+        }
+    }
+
+    /// 当前帧序号
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// 总帧数
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    /// 输出目录
+    pub fn output_dir(&self) -> &Path {
+        &self.output_dir
+    }
+
+    /// 是否已录完所有帧
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.frame_count
+    }
+
+    /// 当前帧绕竖直轴的旋转角（弧度）
+    pub fn angle(&self) -> f32 {
+        TAU * self.frame as f32 / self.frame_count as f32
+    }
+
+    /// 把相机放到当前帧的位置并朝向旋转中心
+    pub fn apply(&self, camera: &mut Camera) {
+        let rotation = Matrix4::from_axis_angle(&Vector3::y_axis(), self.angle());
+        let eye = self.center + rotation.transform_vector(&self.offset);
+
+        // look 为相机的后向轴，朝向中心时 look 指向远离中心的方向
+        camera.look_at(eye, eye + (eye - self.center), Vector3::new(0.0, 1.0, 0.0));
+    }
+
+    /// 当前帧的输出路径（`frame_0000.png` 形式）
+    pub fn frame_path(&self) -> PathBuf {
+        self.output_dir.join(format!("frame_{:04}.png", self.frame))
+    }
+
+    /// 前进到下一帧
+    pub fn advance(&mut self) {
+        self.frame = (self.frame + 1).min(self.frame_count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_camera() -> Camera {
+        let mut camera = Camera::main_camera();
+        camera.set_lens(60f32.to_radians(), 1.0, 0.1, 100.0);
+        camera
+    }
+
+    #[test]
+    fn test_turntable_orbits_full_circle() {
+        let mut camera = test_camera();
+        let center = Vector3::new(0.0, 1.0, 0.0);
+        let mut turntable = Turntable::new(&mut camera, center, 1.0, 4, "captures");
+        let distance = (camera.position() - center).norm();
+
+        let mut positions = Vec::new();
+        while !turntable.is_finished() {
+            turntable.apply(&mut camera);
+            assert!(((camera.position() - center).norm() - distance).abs() < 1e-4);
+
+            // 旋转中心始终位于画面中央
+            let clip = camera.proj_matrix() * camera.view_matrix() * center.push(1.0);
+            assert!(clip.w > 0.0);
+            assert!((clip.x / clip.w).abs() < 1e-4 && (clip.y / clip.w).abs() < 1e-4);
+
+            positions.push(camera.position());
+            turntable.advance();
+        }
+
+        assert_eq!(positions.len(), 4);
+        // 每帧旋转 90 度，相对的两帧关于中心对称
+        assert!(((positions[0] - center) + (positions[2] - center)).norm() < 1e-4);
+        assert!((positions[0] - center).dot(&(positions[1] - center)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_turntable_frame_paths() {
+        let mut camera = test_camera();
+        let mut turntable = Turntable::new(&mut camera, Vector3::zeros(), 1.0, 12, "out/turntable");
+
+        assert_eq!(turntable.frame_path(), Path::new("out/turntable/frame_0000.png"));
+        turntable.advance();
+        assert_eq!(turntable.frame_path(), Path::new("out/turntable/frame_0001.png"));
+
+        for _ in 0..20 {
+            turntable.advance();
+        }
+        assert!(turntable.is_finished());
+        assert_eq!(turntable.frame(), 12);
+    }
+}
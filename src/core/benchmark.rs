@@ -0,0 +1,224 @@
+//! 基准测试模式
+//!
+//! 使用 `--benchmark <帧数>` 启动时，相机沿固定的环绕路径运动，主循环记录每帧耗时，
+//! 渲染完指定帧数后把统计结果以 JSON 输出到标准输出并退出，便于在 CI 中跟踪性能回归。
+//! 目前没有后端提供 GPU 计时，报告中的 `gpu_time_ms` 为 `null`；
+//! `present` 为后端的呈现统计（从启动开始累计，包含预热帧）。
+
+use std::f32::consts::TAU;
+
+use crate::math::{Matrix4, Vector3};
+use crate::renderer::stats::PresentStats;
+
+/// 预热帧数：最初几帧包含管线创建和资源上传，不计入统计
+pub const WARMUP_FRAMES: u32 = 10;
+
+/// 相机起点与环绕中心重合时使用的环绕半径
+const DEFAULT_ORBIT_RADIUS: f32 = 5.0;
+
+/// 解析 `--benchmark <帧数>` 参数，帧数缺失或无效时返回 `None`
+pub fn parse_args(args: &[String]) -> Option<u32> {
+    let idx = args.iter().position(|a| a == "--benchmark")?;
+    args.get(idx + 1)?.parse().ok().filter(|&frames| frames > 0)
+}
+
+/// 帧耗时统计（毫秒）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimingSummary {
+    pub min_ms: f32,
+    pub avg_ms: f32,
+    pub max_ms: f32,
+    /// 第 99 百分位（最近秩法）
+    pub p99_ms: f32,
+}
+
+impl TimingSummary {
+    /// 由每帧耗时计算统计，没有样本时返回 `None`
+    pub fn from_samples(samples: &[f32]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f32::total_cmp);
+        let rank = (sorted.len() as f32 * 0.99).ceil() as usize;
+
+        Some(Self {
+            min_ms: sorted[0],
+            avg_ms: sorted.iter().sum::<f32>() / sorted.len() as f32,
+            max_ms: sorted[sorted.len() - 1],
+            p99_ms: sorted[rank.clamp(1, sorted.len()) - 1],
+        })
+    }
+
+    fn to_json(self) -> String {
+        format!(
+            "{{\"min\": {:.3}, \"avg\": {:.3}, \"max\": {:.3}, \"p99\": {:.3}}}",
+            self.min_ms, self.avg_ms, self.max_ms, self.p99_ms
+        )
+    }
+}
+
+/// 基准测试的相机路径：绕环绕中心的竖直轴匀速旋转一周，始终朝向中心
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraPath {
+    /// 环绕中心
+    center: Vector3,
+
+    /// 起点相对环绕中心的偏移
+    offset: Vector3,
+
+    /// 旋转一周的帧数
+    frame_count: u32,
+}
+
+impl CameraPath {
+    /// 从 `start` 出发绕 `center` 旋转一周，共 `frame_count` 帧
+    pub fn new(start: Vector3, center: Vector3, frame_count: u32) -> Self {
+        let offset = start - center;
+        let offset = if offset.norm() < 1e-3 {
+            Vector3::new(0.0, 0.0, -DEFAULT_ORBIT_RADIUS)
+        } else {
+            offset
+        };
+        Self {
+            center,
+            offset,
+            frame_count: frame_count.max(1),
+        }
+    }
+
+    /// 第 `frame` 帧的相机位置和注视点
+    pub fn pose(&self, frame: u32) -> (Vector3, Vector3) {
+        let angle = TAU * (frame % self.frame_count) as f32 / self.frame_count as f32;
+        let rotation = Matrix4::from_axis_angle(&Vector3::y_axis(), angle);
+        (self.center + rotation.transform_vector(&self.offset), self.center)
+    }
+}
+
+/// 基准测试状态
+#[derive(Debug, Clone)]
+pub struct Benchmark {
+    /// 计入统计的帧数
+    frame_count: u32,
+
+    /// 已渲染的帧数（含预热帧）
+    frame: u32,
+
+    path: CameraPath,
+
+    /// 每帧的总耗时（相邻两次重绘的间隔）
+    frame_times_ms: Vec<f32>,
+
+    /// 每帧 CPU 记录和提交命令的耗时
+    cpu_times_ms: Vec<f32>,
+}
+
+impl Benchmark {
+    /// 渲染 `frame_count` 帧（不含预热帧），相机从 `camera_position` 出发绕原点旋转一周
+    pub fn new(frame_count: u32, camera_position: Vector3) -> Self {
+        let frame_count = frame_count.max(1);
+        Self {
+            frame_count,
+            frame: 0,
+            path: CameraPath::new(camera_position, Vector3::zeros(), frame_count),
+            frame_times_ms: Vec::with_capacity(frame_count as usize),
+            cpu_times_ms: Vec::with_capacity(frame_count as usize),
+        }
+    }
+
+    /// 当前帧的相机位置和注视点（预热期间停在起点）
+    pub fn camera_pose(&self) -> (Vector3, Vector3) {
+        self.path.pose(self.frame.saturating_sub(WARMUP_FRAMES))
+    }
+
+    /// 记录一帧的耗时，预热帧只计数
+    pub fn record(&mut self, frame_time_ms: f32, cpu_time_ms: f32) {
+        if self.frame >= WARMUP_FRAMES && !self.is_finished() {
+            self.frame_times_ms.push(frame_time_ms);
+            self.cpu_times_ms.push(cpu_time_ms);
+        }
+        self.frame += 1;
+    }
+
+    /// 是否已渲染完所有帧
+    pub fn is_finished(&self) -> bool {
+        self.frame >= WARMUP_FRAMES + self.frame_count
+    }
+
+    /// JSON 格式的统计报告，`present` 为后端的呈现统计
+    pub fn report(&self, present: &PresentStats) -> String {
+        let summary = |samples: &[f32]| TimingSummary::from_samples(samples).map_or("null".to_string(), TimingSummary::to_json);
+        format!(
+            "{{\"frames\": {}, \"frame_time_ms\": {}, \"cpu_time_ms\": {}, \"gpu_time_ms\": null, \
+             \"present\": {{\"presented_frames\": {}, \"dropped_frames\": {}, \"present_mode\": \"{}\"}}}}",
+            self.frame_times_ms.len(),
+            summary(&self.frame_times_ms),
+            summary(&self.cpu_times_ms),
+            present.presented_frames,
+            present.dropped_frames,
+            present.present_mode
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::stats::PresentMode;
+
+    #[test]
+    fn test_parse_args() {
+        let args = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+        assert_eq!(parse_args(&args("dist_render --benchmark 500")), Some(500));
+        assert_eq!(parse_args(&args("dist_render --benchmark")), None);
+        assert_eq!(parse_args(&args("dist_render --benchmark 0")), None);
+        assert_eq!(parse_args(&args("dist_render --wgpu")), None);
+    }
+
+    #[test]
+    fn test_timing_summary() {
+        let samples: Vec<f32> = (1..=200).map(|i| i as f32).collect();
+        let summary = TimingSummary::from_samples(&samples).unwrap();
+        assert_eq!(summary.min_ms, 1.0);
+        assert_eq!(summary.max_ms, 200.0);
+        assert!((summary.avg_ms - 100.5).abs() < 1e-4);
+        assert_eq!(summary.p99_ms, 198.0);
+
+        assert_eq!(TimingSummary::from_samples(&[4.0]).unwrap().p99_ms, 4.0);
+        assert_eq!(TimingSummary::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_benchmark_skips_warmup_and_orbits() {
+        let start = Vector3::new(0.0, 1.0, -5.0);
+        let mut benchmark = Benchmark::new(4, start);
+
+        for frame in 0..WARMUP_FRAMES + 4 {
+            assert!(!benchmark.is_finished());
+            let (eye, target) = benchmark.camera_pose();
+            assert_eq!(target, Vector3::zeros());
+            assert!((eye.y - 1.0).abs() < 1e-5);
+            if frame <= WARMUP_FRAMES {
+                assert!((eye - start).norm() < 1e-5);
+            }
+            // 预热帧的耗时不计入统计
+            benchmark.record(if frame < WARMUP_FRAMES { 100.0 } else { 2.0 }, 1.0);
+        }
+        assert!(benchmark.is_finished());
+
+        // 第 3 个统计帧转过半圈
+        assert!((benchmark.path.pose(2).0 - Vector3::new(0.0, 1.0, 5.0)).norm() < 1e-4);
+
+        let present = PresentStats {
+            presented_frames: 14,
+            dropped_frames: 1,
+            present_mode: PresentMode::Mailbox,
+        };
+        assert_eq!(
+            benchmark.report(&present),
+            "{\"frames\": 4, \"frame_time_ms\": {\"min\": 2.000, \"avg\": 2.000, \"max\": 2.000, \"p99\": 2.000}, \
+             \"cpu_time_ms\": {\"min\": 1.000, \"avg\": 1.000, \"max\": 1.000, \"p99\": 1.000}, \"gpu_time_ms\": null, \
+             \"present\": {\"presented_frames\": 14, \"dropped_frames\": 1, \"present_mode\": \"Mailbox\"}}"
+        );
+    }
+}
@@ -11,27 +11,66 @@
 //! height = 600
 //! title = "DistRender"
 //! resizable = true
+//! position = [100, 100]  # 可选，相对所选显示器左上角的位置
+//! monitor = 1            # 可选，显示器序号，省略表示主显示器
+//! gui_scale = 1.5        # 可选，内置 GUI 缩放系数，省略时跟随系统缩放
+//! capture_key = "Tab"    # 切换鼠标捕获（锁定光标并用鼠标转动相机）的按键
+//! show_error_dialog = true  # 初始化失败时用系统对话框显示错误
+//! resize_debounce_ms = 50   # 窗口尺寸停止变化多久后才重建交换链，0 表示立即重建
 //!
 //! [graphics]
 //! backend = "vulkan"  # 或 "dx12"
 //! vsync = true
 //! msaa_samples = 4
+//! depth_resolve = "min"  # 开启 MSAA 时多重采样深度的解析方式：min、max 或 average
+//! buffer_count = 2    # 交换链缓冲数：2（双缓冲）或 3（三缓冲）
+//! frames_in_flight = 1  # 可选，同时在 GPU 上执行的最大帧数，1 为低延迟模式，省略时与缓冲数相同
+//! max_texture_size = 2048  # 可选，模型纹理的最大边长，更大的纹理加载时缩小
+//! max_fps = 144       # 可选，省略表示不限制帧率
+//! fixed_update_rate = 60  # 逻辑更新频率（Hz），0 表示跟随帧间隔
+//!
+//! [graphics.overlay_depth_bias]  # 轮廓等叠加通道的深度偏移，主通道使用 depth_bias（默认 0）
+//! constant = -2
+//! slope_scale = -1.0
+//! clamp = 0.0
 //!
 //! [logging]
 //! level = "info"      # trace, debug, info, warn, error
 //! file_output = true
 //! ```
+//!
+//! 运行 `dist_render --dump-config` 可以生成一份包含全部默认值的配置文件。
 
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 use super::error::{ConfigError, Result};
+use super::input::parse_capture_key;
+use crate::renderer::resources::sampler::MAX_ANISOTROPY;
+
+/// 默认配置文件路径
+pub const DEFAULT_CONFIG_PATH: &str = "config.toml";
+
+/// `write_default` 写入的文件头，列出默认值中省略的可选字段
+const DEFAULT_CONFIG_HEADER: &str = "\
+# DistRender 引擎配置文件（由 --dump-config 生成，包含全部默认值）
+#
+# 可选字段（默认省略）：
+#   [window] position = [100, 100]   # 窗口位置（相对所选显示器左上角，物理像素）
+#   [window] monitor = 1             # 显示器序号，省略表示主显示器
+#   [window] gui_scale = 1.5         # 内置 GUI 缩放系数，省略表示跟随系统缩放
+#   [graphics] max_fps = 144   # 最大帧率，省略表示不限制
+#   [graphics] frames_in_flight = 1   # 同时在 GPU 上执行的最大帧数，省略表示与缓冲数相同
+#   [graphics] target_aspect = 1.7778   # 固定画面宽高比（信箱模式），省略表示跟随窗口
+#   [graphics] max_texture_size = 2048   # 模型纹理最大边长，省略表示不限制
+
+";
 
 /// 引擎配置
 ///
 /// 包含了引擎运行所需的所有配置项。
 /// 可以从配置文件加载，也可以通过代码构建。
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Config {
     /// 窗口配置
     pub window: WindowConfig,
@@ -44,7 +83,7 @@ pub struct Config {
 }
 
 /// 窗口配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WindowConfig {
     /// 窗口宽度
     #[serde(default = "default_width")]
@@ -61,10 +100,38 @@ pub struct WindowConfig {
     /// 是否可调整大小
     #[serde(default = "default_resizable")]
     pub resizable: bool,
+
+    /// 窗口左上角相对所选显示器左上角的位置（物理像素），省略时由系统决定
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<[i32; 2]>,
+
+    /// 窗口所在显示器的序号（从 0 开始），省略时使用主显示器；只指定显示器时窗口居中
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<usize>,
+
+    /// 内置 GUI 的缩放系数（egui 每点像素数），限制在 0.5 到 3.0 之间，省略时跟随系统缩放
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gui_scale: Option<f32>,
+
+    /// 切换鼠标捕获的按键（Tab、Escape、Backquote、CapsLock、Space 或 F1 ~ F12）
+    ///
+    /// 捕获时光标锁定并隐藏，移动鼠标直接转动相机；释放后光标可以自由点击 GUI，相机视角暂停。
+    #[serde(default = "default_capture_key")]
+    pub capture_key: String,
+
+    /// 渲染器初始化失败时是否在退出前用系统对话框显示错误（可复制到剪贴板），错误总是同时写入标准错误输出
+    #[serde(default = "default_show_error_dialog")]
+    pub show_error_dialog: bool,
+
+    /// 窗口尺寸停止变化多少毫秒后才重建交换链，0 表示每次变化立即重建
+    ///
+    /// 拖动窗口时合并连续的尺寸变化，避免 DX12 等后端反复等待 GPU 空闲而闪烁。
+    #[serde(default = "default_resize_debounce_ms")]
+    pub resize_debounce_ms: u64,
 }
 
 /// 图形配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GraphicsConfig {
     /// 图形后端选择
     #[serde(default = "default_backend")]
@@ -75,8 +142,123 @@ pub struct GraphicsConfig {
     pub vsync: bool,
 
     /// MSAA 采样数
+    ///
+    /// 目前只有 wgpu 后端使用；设备不支持时取不超过该值的最大受支持采样数，见 [`effective_msaa_samples`]。
     #[serde(default = "default_msaa")]
     pub msaa_samples: u32,
+
+    /// 开启 MSAA 时把多重采样深度解析为单采样深度的方式（默认取最小值）
+    ///
+    /// 解析后的深度供之后的通道（如深度读回）采样。wgpu 后端使用。
+    #[serde(default)]
+    pub depth_resolve: DepthResolveMode,
+
+    /// 交换链缓冲数（2 为双缓冲，3 为三缓冲）
+    ///
+    /// 同时决定帧资源池的大小；实际值会按表面能力钳制，见 [`effective_buffer_count`]。
+    #[serde(default = "default_buffer_count")]
+    pub buffer_count: u32,
+
+    /// 同时在 GPU 上执行的最大帧数（1 ~ 3，`None` 表示与交换链缓冲数相同）
+    ///
+    /// 各后端每帧提交后等到未完成的帧少于该值。
+    /// 为 1 时每帧开始前等待上一帧执行完毕（低延迟模式）：CPU 与 GPU 不再并行，吞吐量下降，
+    /// 但输入到画面显示的延迟更低。超过缓冲数的值按缓冲数处理，见 [`effective_frames_in_flight`]。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frames_in_flight: Option<u32>,
+
+    /// 最大帧率（`None` 表示不限制）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fps: Option<u32>,
+
+    /// 固定逻辑更新频率（Hz），默认 60
+    ///
+    /// 动画等逻辑按固定步长推进，与渲染帧率无关；0 表示直接使用帧间隔（可变步长）。
+    /// 相机和输入仍按帧处理。
+    #[serde(default = "default_fixed_update_rate")]
+    pub fixed_update_rate: u32,
+
+    /// 主通道的深度偏移（默认为 0）
+    #[serde(default)]
+    pub depth_bias: DepthBias,
+
+    /// 叠加通道（选中轮廓等）的深度偏移
+    ///
+    /// 叠加几何与主通道表面共面时，负的偏移把它拉近相机以避免 z-fighting。
+    #[serde(default = "default_overlay_depth_bias")]
+    pub overlay_depth_bias: DepthBias,
+
+    /// 是否启用图形 API 的验证（Vulkan 验证层 `VK_LAYER_KHRONOS_validation`、D3D12 调试层）
+    ///
+    /// 启用后验证消息按严重程度输出到日志；未安装对应组件时给出警告并跳过。
+    /// 同时按用途为缓冲、纹理和管线设置调试名称，便于在 RenderDoc、PIX、Xcode 抓帧中辨认（所有后端）。
+    /// 调试构建默认开启，发布构建默认关闭。
+    #[serde(default = "default_validation")]
+    pub validation: bool,
+
+    /// 交换链颜色格式，默认自动选择（优先 sRGB）
+    ///
+    /// 指定的格式表面不支持时列出可用格式并回退到自动选择。选中 UNORM 格式时由着色器手动做 sRGB 编码。
+    /// 所有后端使用，Metal 只支持 BGRA 格式。
+    #[serde(default)]
+    pub surface_format: SurfaceFormat,
+
+    /// 各向异性过滤的最大等级（1 到 16，默认 16）
+    ///
+    /// 实际等级还会钳制到设备支持的上限。wgpu 后端使用。
+    #[serde(default = "default_max_anisotropy")]
+    pub max_anisotropy: u16,
+
+    /// 模型纹理的最大边长（像素，`None` 表示只受设备上限限制）
+    ///
+    /// 更大的纹理在加载时按比例缩小后再上传，避免单张高分辨率纹理占满低端 GPU 的显存。
+    /// 实际值还会钳制到设备支持的最大纹理尺寸，见 [`effective_max_texture_size`]。wgpu 后端使用。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_texture_size: Option<u32>,
+
+    /// 是否使用带图元重启的三角形带绘制地形等网格（默认开启）
+    ///
+    /// 关闭时或后端不支持时，三角形带在加载时转换为三角形列表。wgpu 后端支持。
+    #[serde(default = "default_primitive_restart")]
+    pub primitive_restart: bool,
+
+    /// 是否缓存模型的解析结果（默认关闭）
+    ///
+    /// 开启后加载器的输出写入模型文件旁的 `<文件名>.distmesh`，之后启动时源文件未变化就直接读取缓存，
+    /// 大幅缩短大型 FBX 模型的加载时间。蒙皮 glTF 模型不使用缓存。
+    #[serde(default)]
+    pub mesh_cache: bool,
+
+    /// 固定的画面宽高比（宽 / 高，`None` 表示跟随窗口）
+    ///
+    /// 窗口形状不同时视口取居中的最大矩形，多出的部分填充黑边。
+    /// Metal 后端无法只清除视口区域，视口内同样清除为黑色。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_aspect: Option<f32>,
+}
+
+/// 深度偏移（polygon offset）
+///
+/// 最终偏移为 `constant * r + slope_scale * max_slope`，其中 `r` 为深度格式的最小可分辨值；
+/// `clamp` 非 0 时限制偏移的绝对值上限。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DepthBias {
+    /// 常量偏移（以深度最小可分辨值为单位）
+    pub constant: i32,
+
+    /// 按多边形深度斜率缩放的偏移
+    pub slope_scale: f32,
+
+    /// 偏移的最大绝对值（0 表示不限制）
+    pub clamp: f32,
+}
+
+impl DepthBias {
+    /// 是否产生任何偏移
+    pub fn is_enabled(&self) -> bool {
+        self.constant != 0 || self.slope_scale != 0.0
+    }
 }
 
 /// 图形后端类型
@@ -93,8 +275,38 @@ pub enum GraphicsBackend {
     Wgpu,
 }
 
+/// 交换链颜色格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SurfaceFormat {
+    /// 优先选择 sRGB 格式，表面不支持时使用其首选格式
+    #[default]
+    Auto,
+    /// BGRA 8 位 sRGB（写入时硬件自动编码）
+    Bgra8Srgb,
+    /// RGBA 8 位 sRGB（写入时硬件自动编码）
+    Rgba8Srgb,
+    /// BGRA 8 位 UNORM（着色器手动编码）
+    Bgra8Unorm,
+    /// RGBA 8 位 UNORM（着色器手动编码）
+    Rgba8Unorm,
+}
+
+/// 多重采样深度的解析方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepthResolveMode {
+    /// 取离相机最近的采样，边缘像素保留前景深度（推荐）
+    #[default]
+    Min,
+    /// 取离相机最远的采样
+    Max,
+    /// 取所有采样的平均值，边缘像素得到介于前景和背景之间的深度
+    Average,
+}
+
 /// 日志配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LoggingConfig {
     /// 日志级别
     #[serde(default = "default_log_level")]
@@ -128,7 +340,16 @@ fn default_resizable() -> bool { true }
 fn default_backend() -> GraphicsBackend { GraphicsBackend::Vulkan }
 fn default_vsync() -> bool { true }
 fn default_msaa() -> u32 { 1 }
+fn default_buffer_count() -> u32 { 2 }
+fn default_fixed_update_rate() -> u32 { 60 }
+fn default_validation() -> bool { cfg!(debug_assertions) }
+fn default_max_anisotropy() -> u16 { MAX_ANISOTROPY }
+fn default_primitive_restart() -> bool { true }
+fn default_overlay_depth_bias() -> DepthBias { DepthBias { constant: -2, slope_scale: -1.0, clamp: 0.0 } }
 fn default_log_level() -> LogLevel { LogLevel::Info }
+fn default_capture_key() -> String { "Tab".to_string() }
+fn default_show_error_dialog() -> bool { true }
+fn default_resize_debounce_ms() -> u64 { 50 }
 fn default_file_output() -> bool { false }
 fn default_log_file() -> String { "distrender.log".to_string() }
 
@@ -149,6 +370,12 @@ impl Default for WindowConfig {
             height: default_height(),
             title: default_title(),
             resizable: default_resizable(),
+            position: None,
+            monitor: None,
+            gui_scale: None,
+            capture_key: default_capture_key(),
+            show_error_dialog: default_show_error_dialog(),
+            resize_debounce_ms: default_resize_debounce_ms(),
         }
     }
 }
@@ -159,6 +386,20 @@ impl Default for GraphicsConfig {
             backend: default_backend(),
             vsync: default_vsync(),
             msaa_samples: default_msaa(),
+            depth_resolve: DepthResolveMode::default(),
+            buffer_count: default_buffer_count(),
+            frames_in_flight: None,
+            max_fps: None,
+            fixed_update_rate: default_fixed_update_rate(),
+            depth_bias: DepthBias::default(),
+            overlay_depth_bias: default_overlay_depth_bias(),
+            validation: default_validation(),
+            surface_format: SurfaceFormat::default(),
+            max_anisotropy: default_max_anisotropy(),
+            max_texture_size: None,
+            primitive_restart: default_primitive_restart(),
+            mesh_cache: false,
+            target_aspect: None,
         }
     }
 }
@@ -199,6 +440,20 @@ impl Config {
         Ok(())
     }
 
+    /// 将默认配置写入文件
+    ///
+    /// 写入的文件包含所有字段，可直接作为配置模板使用，并能重新解析为 `Config::default()`。
+    pub fn write_default<P: AsRef<Path>>(path: P) -> Result<()> {
+        let contents = toml::to_string_pretty(&Self::default())
+            .map_err(|e| ConfigError::ParseError(e.to_string()))?;
+
+        std::fs::write(path, format!("{}{}", DEFAULT_CONFIG_HEADER, contents))?;
+        Ok(())
+    }
+
+    /// 应用命令行参数
+    ///
+    /// `--dump-config [path]` 会把默认配置写入 `path`（默认 `config.toml`）后直接退出进程。
     pub fn apply_args<I>(&mut self, args: I)
     where
         I: IntoIterator,
@@ -206,6 +461,24 @@ impl Config {
     {
         let args: Vec<String> = args.into_iter().map(|s| s.as_ref().to_string()).collect();
 
+        if let Some(idx) = args.iter().position(|a| a == "--dump-config") {
+            let path = args
+                .get(idx + 1)
+                .filter(|a| !a.starts_with("--"))
+                .map_or(DEFAULT_CONFIG_PATH, String::as_str);
+
+            match Self::write_default(path) {
+                Ok(()) => {
+                    println!("Default configuration written to {}", path);
+                    std::process::exit(0);
+                }
+                Err(e) => {
+                    eprintln!("Failed to write default configuration to {}: {}", path, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
         if args.iter().any(|a| a == "--dx12") {
             self.graphics.backend = GraphicsBackend::Dx12;
         }
@@ -237,6 +510,14 @@ impl Config {
                 }
             }
         }
+
+        if let Some(idx) = args.iter().position(|a| a == "--max-fps") {
+            if let Some(fps_str) = args.get(idx + 1) {
+                if let Ok(fps) = fps_str.parse() {
+                    self.graphics.max_fps = Some(fps);
+                }
+            }
+        }
     }
 
     pub fn validate(&self) -> Result<()> {
@@ -248,6 +529,14 @@ impl Config {
             .into());
         }
 
+        if parse_capture_key(&self.window.capture_key).is_none() {
+            return Err(ConfigError::InvalidValue {
+                field: "window.capture_key".to_string(),
+                reason: format!("Unknown capture key '{}'", self.window.capture_key),
+            }
+            .into());
+        }
+
         if !matches!(self.graphics.msaa_samples, 1 | 2 | 4 | 8 | 16) {
             return Err(ConfigError::InvalidValue {
                 field: "graphics.msaa_samples".to_string(),
@@ -256,10 +545,105 @@ impl Config {
             .into());
         }
 
+        if !(1..=MAX_ANISOTROPY).contains(&self.graphics.max_anisotropy) {
+            return Err(ConfigError::InvalidValue {
+                field: "graphics.max_anisotropy".to_string(),
+                reason: format!("Max anisotropy must be between 1 and {}", MAX_ANISOTROPY),
+            }
+            .into());
+        }
+
+        if self.graphics.max_texture_size == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                field: "graphics.max_texture_size".to_string(),
+                reason: "Max texture size must be at least 1".to_string(),
+            }
+            .into());
+        }
+
+        if !matches!(self.graphics.buffer_count, 2 | 3) {
+            return Err(ConfigError::InvalidValue {
+                field: "graphics.buffer_count".to_string(),
+                reason: "Buffer count must be 2 or 3".to_string(),
+            }
+            .into());
+        }
+
+        if self.graphics.frames_in_flight.is_some_and(|n| !(1..=3).contains(&n)) {
+            return Err(ConfigError::InvalidValue {
+                field: "graphics.frames_in_flight".to_string(),
+                reason: "Frames in flight must be between 1 and 3".to_string(),
+            }
+            .into());
+        }
+
+        for (field, bias) in [
+            ("graphics.depth_bias", &self.graphics.depth_bias),
+            ("graphics.overlay_depth_bias", &self.graphics.overlay_depth_bias),
+        ] {
+            if !bias.slope_scale.is_finite() || !bias.clamp.is_finite() {
+                return Err(ConfigError::InvalidValue {
+                    field: field.to_string(),
+                    reason: "Depth bias factors must be finite".to_string(),
+                }
+                .into());
+            }
+        }
+
+        if self
+            .graphics
+            .target_aspect
+            .is_some_and(|aspect| !aspect.is_finite() || aspect <= 0.0)
+        {
+            return Err(ConfigError::InvalidValue {
+                field: "graphics.target_aspect".to_string(),
+                reason: "Target aspect ratio must be a positive number".to_string(),
+            }
+            .into());
+        }
+
+        if self.graphics.max_fps == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                field: "graphics.max_fps".to_string(),
+                reason: "Max FPS must be greater than 0".to_string(),
+            }
+            .into());
+        }
+
         Ok(())
     }
 }
 
+/// 按表面支持的范围钳制交换链缓冲数
+///
+/// `max` 为 `None` 表示表面没有上限。
+pub fn effective_buffer_count(requested: u32, min: u32, max: Option<u32>) -> u32 {
+    let count = requested.max(min);
+    max.map_or(count, |max| count.min(max.max(min)))
+}
+
+/// 同时在 GPU 上执行的最大帧数
+///
+/// 未配置时与交换链缓冲数相同，否则钳制到 1 ~ `buffer_count`（帧资源池只有 `buffer_count` 份）。
+pub fn effective_frames_in_flight(requested: Option<u32>, buffer_count: u32) -> u32 {
+    requested.map_or(buffer_count, |n| n.clamp(1, buffer_count.max(1)))
+}
+
+/// 模型纹理的最大边长：配置值钳制到设备支持的最大纹理尺寸，未配置时为设备上限
+pub fn effective_max_texture_size(requested: Option<u32>, device_max: u32) -> u32 {
+    requested.map_or(device_max, |size| size.min(device_max)).max(1)
+}
+
+/// 按设备支持的采样数选择 MSAA 采样数
+///
+/// 取不超过请求值的最大受支持采样数，都不支持时为 1（禁用 MSAA）。
+pub fn effective_msaa_samples(requested: u32, supported: impl Fn(u32) -> bool) -> u32 {
+    [16, 8, 4, 2]
+        .into_iter()
+        .find(|&count| count <= requested && supported(count))
+        .unwrap_or(1)
+}
+
 impl GraphicsBackend {
     #[allow(dead_code)]
     pub fn is_dx12(&self) -> bool {
@@ -307,4 +691,176 @@ mod tests {
         config.window.width = 0;
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_write_default_round_trips() {
+        let path = std::env::temp_dir().join(format!("dist_render_config_{}.toml", std::process::id()));
+
+        Config::write_default(&path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let parsed = Config::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(parsed.unwrap(), Config::default());
+        assert!(contents.contains("msaa_samples"));
+        assert!(contents.contains("max_fps"));
+    }
+
+    #[test]
+    fn test_target_aspect() {
+        let mut config = Config::default();
+        assert_eq!(config.graphics.target_aspect, None);
+        config.graphics.target_aspect = Some(16.0 / 9.0);
+        assert!(config.validate().is_ok());
+        config.graphics.target_aspect = Some(0.0);
+        assert!(config.validate().is_err());
+        config.graphics.target_aspect = Some(f32::NAN);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_window_position_and_monitor() {
+        let config: Config = toml::from_str("[window]\nposition = [100, -20]\nmonitor = 1\n[graphics]\n[logging]\n").unwrap();
+        assert_eq!(config.window.position, Some([100, -20]));
+        assert_eq!(config.window.monitor, Some(1));
+
+        let config = Config::default();
+        assert_eq!(config.window.position, None);
+        assert!(!toml::to_string(&config).unwrap().contains("monitor"));
+    }
+
+    #[test]
+    fn test_capture_key() {
+        let mut config = Config::default();
+        assert_eq!(config.window.capture_key, "Tab");
+        config.window.capture_key = "F2".to_string();
+        assert!(config.validate().is_ok());
+        config.window.capture_key = "NotAKey".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_max_fps() {
+        let mut config: Config = toml::from_str("[window]\n[graphics]\n[logging]\n").unwrap();
+        assert_eq!(config.graphics.max_fps, None);
+
+        config.apply_args(["dist_render", "--max-fps", "60"]);
+        assert_eq!(config.graphics.max_fps, Some(60));
+        assert!(config.validate().is_ok());
+
+        config.graphics.max_fps = Some(0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_buffer_count() {
+        let mut config: Config = toml::from_str("[window]\n[graphics]\n[logging]\n").unwrap();
+        assert_eq!(config.graphics.buffer_count, 2);
+
+        config.graphics.buffer_count = 3;
+        assert!(config.validate().is_ok());
+        config.graphics.buffer_count = 4;
+        assert!(config.validate().is_err());
+
+        assert_eq!(effective_buffer_count(3, 2, Some(8)), 3);
+        assert_eq!(effective_buffer_count(2, 3, None), 3);
+        assert_eq!(effective_buffer_count(3, 1, Some(2)), 2);
+    }
+
+    #[test]
+    fn test_frames_in_flight() {
+        let mut config = Config::default();
+        config.graphics.frames_in_flight = Some(1);
+        assert!(config.validate().is_ok());
+        config.graphics.frames_in_flight = Some(0);
+        assert!(config.validate().is_err());
+
+        assert_eq!(effective_frames_in_flight(None, 3), 3);
+        assert_eq!(effective_frames_in_flight(Some(1), 3), 1);
+        assert_eq!(effective_frames_in_flight(Some(3), 2), 2);
+    }
+
+    #[test]
+    fn test_effective_msaa_samples() {
+        let only_four = |count| count == 4;
+        assert_eq!(effective_msaa_samples(4, only_four), 4);
+        assert_eq!(effective_msaa_samples(16, only_four), 4);
+        assert_eq!(effective_msaa_samples(2, only_four), 1);
+        assert_eq!(effective_msaa_samples(1, |_| true), 1);
+    }
+
+    #[test]
+    fn test_validation() {
+        let config: Config = toml::from_str("[window]\n[graphics]\n[logging]\n").unwrap();
+        assert_eq!(config.graphics.validation, cfg!(debug_assertions));
+
+        let config: Config = toml::from_str("[window]\n[graphics]\nvalidation = true\n[logging]\n").unwrap();
+        assert!(config.graphics.validation);
+    }
+
+    #[test]
+    fn test_depth_bias() {
+        let config: Config = toml::from_str(
+            "[window]\n[graphics]\n[graphics.overlay_depth_bias]\nconstant = -4\n[logging]\n",
+        )
+        .unwrap();
+        assert!(!config.graphics.depth_bias.is_enabled());
+        assert_eq!(
+            config.graphics.overlay_depth_bias,
+            DepthBias { constant: -4, slope_scale: 0.0, clamp: 0.0 }
+        );
+        assert!(config.validate().is_ok());
+
+        let mut config = Config::default();
+        assert!(config.graphics.overlay_depth_bias.is_enabled());
+        config.graphics.depth_bias.slope_scale = f32::NAN;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_surface_format() {
+        let config: Config = toml::from_str("[window]\n[graphics]\n[logging]\n").unwrap();
+        assert_eq!(config.graphics.surface_format, SurfaceFormat::Auto);
+
+        let config: Config =
+            toml::from_str("[window]\n[graphics]\nsurface_format = \"bgra8_unorm\"\n[logging]\n").unwrap();
+        assert_eq!(config.graphics.surface_format, SurfaceFormat::Bgra8Unorm);
+    }
+
+    #[test]
+    fn test_depth_resolve() {
+        let config: Config = toml::from_str("[window]\n[graphics]\n[logging]\n").unwrap();
+        assert_eq!(config.graphics.depth_resolve, DepthResolveMode::Min);
+
+        let config: Config =
+            toml::from_str("[window]\n[graphics]\ndepth_resolve = \"average\"\n[logging]\n").unwrap();
+        assert_eq!(config.graphics.depth_resolve, DepthResolveMode::Average);
+    }
+
+    #[test]
+    fn test_max_anisotropy() {
+        let config: Config = toml::from_str("[window]\n[graphics]\n[logging]\n").unwrap();
+        assert_eq!(config.graphics.max_anisotropy, 16);
+
+        let mut config = Config::default();
+        config.graphics.max_anisotropy = 4;
+        assert!(config.validate().is_ok());
+        config.graphics.max_anisotropy = 0;
+        assert!(config.validate().is_err());
+        config.graphics.max_anisotropy = 32;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_max_texture_size() {
+        let mut config = Config::default();
+        config.graphics.max_texture_size = Some(2048);
+        assert!(config.validate().is_ok());
+        config.graphics.max_texture_size = Some(0);
+        assert!(config.validate().is_err());
+
+        assert_eq!(effective_max_texture_size(None, 16384), 16384);
+        assert_eq!(effective_max_texture_size(Some(2048), 16384), 2048);
+        assert_eq!(effective_max_texture_size(Some(32768), 8192), 8192);
+    }
 }
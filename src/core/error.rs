@@ -79,6 +79,9 @@ pub enum GraphicsError {
 
     /// 渲染命令执行失败
     CommandExecution(String),
+
+    /// 设备丢失（驱动更新、TDR 等导致设备被移除或重置），需要重建设备
+    DeviceLost(String),
 }
 
 /// 网格加载相关的错误
@@ -139,6 +142,7 @@ impl fmt::Display for GraphicsError {
             GraphicsError::ShaderCompilation(msg) => write!(f, "Shader compilation failed: {}", msg),
             GraphicsError::ResourceCreation(msg) => write!(f, "Resource creation failed: {}", msg),
             GraphicsError::CommandExecution(msg) => write!(f, "Command execution failed: {}", msg),
+            GraphicsError::DeviceLost(msg) => write!(f, "Device lost: {}", msg),
         }
     }
 }
@@ -0,0 +1,138 @@
+//! 错误对话框
+//!
+//! 渲染器初始化失败等致命错误原本只写到标准错误输出，双击启动的 GUI 程序看不到。
+//! 开启 `show_error_dialog` 时，退出前用系统对话框显示错误信息，并可以把完整信息复制到剪贴板，
+//! 方便用户反馈问题。
+//!
+//! 不引入额外依赖：Windows 使用 `MessageBoxW`，macOS 使用 `osascript`，其他平台依次尝试
+//! `zenity` 和 `kdialog`；剪贴板通过系统命令（`clip`、`pbcopy`、`wl-copy`、`xclip`、`xsel`）写入。
+//! 都不可用时只保留标准错误输出和日志。
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use tracing::warn;
+
+/// 对话框中最多显示的行数，完整信息可以复制到剪贴板
+const MAX_DIALOG_LINES: usize = 20;
+
+/// 对话框中每行最多显示的字符数
+const MAX_LINE_CHARS: usize = 200;
+
+/// 显示错误对话框并等待用户关闭
+///
+/// 用户选择复制时把完整的 `message` 写入剪贴板。对话框不可用或复制失败时只输出警告日志。
+pub fn show_error_dialog(title: &str, message: &str) {
+    let text = format!("{}\n\nCopy the full message to the clipboard?", dialog_summary(message));
+    match ask_copy(title, &text) {
+        Some(true) => {
+            if !copy_to_clipboard(message) {
+                warn!("Failed to copy the error message to the clipboard");
+            }
+        }
+        Some(false) => {}
+        None => warn!("No native dialog is available to show the error"),
+    }
+}
+
+/// 对话框中显示的摘要：过长的行和超出的行被截断
+fn dialog_summary(message: &str) -> String {
+    let mut lines: Vec<String> = message
+        .lines()
+        .take(MAX_DIALOG_LINES)
+        .map(|line| match line.char_indices().nth(MAX_LINE_CHARS) {
+            Some((end, _)) => format!("{}…", &line[..end]),
+            None => line.to_string(),
+        })
+        .collect();
+    if message.lines().count() > MAX_DIALOG_LINES {
+        lines.push("…".to_string());
+    }
+    lines.join("\n")
+}
+
+/// 显示带“复制”和“关闭”选项的对话框，返回用户是否选择复制，无法显示时返回 `None`
+#[cfg(target_os = "windows")]
+fn ask_copy(title: &str, text: &str) -> Option<bool> {
+    use windows::core::HSTRING;
+    use windows::Win32::UI::WindowsAndMessaging::{MessageBoxW, IDYES, MB_ICONERROR, MB_YESNO};
+
+    let result = unsafe { MessageBoxW(None, &HSTRING::from(text), &HSTRING::from(title), MB_YESNO | MB_ICONERROR) };
+    Some(result == IDYES)
+}
+
+#[cfg(target_os = "macos")]
+fn ask_copy(title: &str, text: &str) -> Option<bool> {
+    // 文本通过参数传入，不需要转义 AppleScript 字符串
+    let output = Command::new("osascript")
+        .args([
+            "-e",
+            "on run argv",
+            "-e",
+            "display dialog (item 1 of argv) with title (item 2 of argv) buttons {\"Copy\", \"Close\"} \
+             default button \"Close\" with icon stop",
+            "-e",
+            "end run",
+            "--",
+            text,
+            title,
+        ])
+        .output()
+        .ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).contains("Copy"))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn ask_copy(title: &str, text: &str) -> Option<bool> {
+    let dialogs: [(&str, Vec<&str>); 2] = [
+        ("zenity", vec!["--question", "--ok-label=Copy", "--cancel-label=Close", "--title", title, "--text", text]),
+        ("kdialog", vec!["--title", title, "--yes-label", "Copy", "--no-label", "Close", "--warningyesno", text]),
+    ];
+    // 确认按钮退出码为 0，关闭为 1，其他退出码表示对话框本身出错
+    dialogs.iter().find_map(|(program, args)| match Command::new(program).args(args).status() {
+        Ok(status) => match status.code() {
+            Some(0) => Some(true),
+            Some(1) => Some(false),
+            _ => None,
+        },
+        Err(_) => None,
+    })
+}
+
+/// 通过系统命令把文本写入剪贴板，返回是否成功
+fn copy_to_clipboard(text: &str) -> bool {
+    let commands: &[(&str, &[&str])] = if cfg!(target_os = "windows") {
+        &[("clip", &[])]
+    } else if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else {
+        &[("wl-copy", &[]), ("xclip", &["-selection", "clipboard"]), ("xsel", &["--clipboard", "--input"])]
+    };
+
+    commands.iter().any(|(program, args)| {
+        let Ok(mut child) = Command::new(program).args(*args).stdin(Stdio::piped()).spawn() else {
+            return false;
+        };
+        let written = child.stdin.take().is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        child.wait().is_ok_and(|status| status.success()) && written
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dialog_summary_truncates_long_messages() {
+        assert_eq!(dialog_summary("Failed to create device\nNo adapter"), "Failed to create device\nNo adapter");
+
+        let long_line = "x".repeat(MAX_LINE_CHARS + 50);
+        let message = format!("{}\n{}", long_line, "line\n".repeat(MAX_DIALOG_LINES + 5));
+        let summary = dialog_summary(&message);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines.len(), MAX_DIALOG_LINES + 1);
+        assert_eq!(lines[0].chars().count(), MAX_LINE_CHARS + 1);
+        assert!(lines[0].ends_with('…'));
+        assert_eq!(lines[MAX_DIALOG_LINES], "…");
+    }
+}
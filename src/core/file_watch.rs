@@ -0,0 +1,96 @@
+//! 文件变化监视
+//!
+//! 按固定间隔查询文件的修改时间，发现变化时通知调用方，用于外部工具保存模型后自动重新加载。
+//! 只依赖文件元数据，不使用各平台的文件系统通知；文件被删除或重新出现也算作变化。
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// 轮询文件修改时间的监视器
+#[derive(Debug, Clone)]
+pub struct FileWatcher {
+    /// 两次查询之间的间隔，为 0 时不监视
+    interval: Duration,
+
+    /// 上一次查询的时间
+    last_poll: Option<Instant>,
+
+    /// 监视的文件及其上一次查询到的修改时间（文件不存在时为 `None`）
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl FileWatcher {
+    /// 监视 `paths` 中的文件，每隔 `interval_ms` 毫秒查询一次，为 0 时不监视
+    pub fn new<P: Into<PathBuf>>(paths: impl IntoIterator<Item = P>, interval_ms: u64) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let path = path.into();
+                let modified = modified_time(&path);
+                (path, modified)
+            })
+            .collect();
+        Self {
+            interval: Duration::from_millis(interval_ms),
+            last_poll: None,
+            files,
+        }
+    }
+
+    /// 到达查询间隔时检查所有文件，有文件变化时返回 true（每帧调用）
+    pub fn poll(&mut self, now: Instant) -> bool {
+        if self.interval.is_zero() || self.files.is_empty() {
+            return false;
+        }
+        match self.last_poll {
+            Some(last) if now.saturating_duration_since(last) < self.interval => return false,
+            _ => self.last_poll = Some(now),
+        }
+
+        let mut changed = false;
+        for (path, last_modified) in &mut self.files {
+            let modified = modified_time(path);
+            if modified != *last_modified {
+                *last_modified = modified;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// 文件的修改时间，文件不存在或平台不支持时返回 `None`
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modified_and_deleted_files_are_reported_once() {
+        let path = std::env::temp_dir().join(format!("dist_render_watch_{}.obj", std::process::id()));
+        std::fs::write(&path, "v 0 0 0\n").unwrap();
+        let mut watcher = FileWatcher::new([&path], 100);
+        let start = Instant::now();
+        assert!(!watcher.poll(start));
+
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(10)).unwrap();
+        drop(file);
+        // 未到查询间隔时不检查
+        assert!(!watcher.poll(start + Duration::from_millis(50)));
+        assert!(watcher.poll(start + Duration::from_millis(100)));
+        assert!(!watcher.poll(start + Duration::from_millis(200)));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(watcher.poll(start + Duration::from_millis(300)));
+    }
+
+    #[test]
+    fn test_zero_interval_disables_watching() {
+        let mut watcher = FileWatcher::new(["does/not/exist.obj"], 0);
+        assert!(!watcher.poll(Instant::now()));
+    }
+}
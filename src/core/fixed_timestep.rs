@@ -0,0 +1,89 @@
+//! 固定步长更新
+//!
+//! 渲染按显示器刷新率进行，动画等逻辑按固定频率推进：
+//! 每帧把帧间隔累加到累加器中，再按固定步长取出若干次更新，余下的时间留到下一帧。
+//! 这样逻辑结果与帧率无关，剩余时间占步长的比例可用于渲染插值。
+
+/// 单帧最多执行的更新次数
+///
+/// 卡顿（断点、窗口拖动等）后累加器可能积攒很长时间，超过上限的部分直接丢弃，
+/// 避免追赶更新本身又拖慢下一帧。
+pub const MAX_STEPS_PER_FRAME: u32 = 8;
+
+/// 固定步长累加器
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    /// 更新步长（秒），`None` 表示可变步长
+    step: Option<f32>,
+
+    /// 尚未用于更新的时间（秒）
+    accumulator: f32,
+}
+
+impl FixedTimestep {
+    /// 创建固定步长累加器
+    ///
+    /// `rate` 为每秒更新次数，0 表示可变步长（每帧更新一次，步长为帧间隔）。
+    pub fn new(rate: u32) -> Self {
+        Self {
+            step: (rate > 0).then(|| 1.0 / rate as f32),
+            accumulator: 0.0,
+        }
+    }
+
+    /// 更新步长（秒），可变步长时为 `None`
+    pub fn step(&self) -> Option<f32> {
+        self.step
+    }
+
+    /// 累加帧间隔，返回本帧要执行的各次更新的步长
+    pub fn advance(&mut self, delta_time: f32) -> impl Iterator<Item = f32> {
+        let Some(step) = self.step else {
+            return std::iter::repeat(delta_time).take(1);
+        };
+
+        self.accumulator += delta_time.max(0.0);
+        let steps = ((self.accumulator / step) as u32).min(MAX_STEPS_PER_FRAME);
+        self.accumulator = (self.accumulator - steps as f32 * step).min(step);
+        std::iter::repeat(step).take(steps as usize)
+    }
+
+    /// 插值系数：累加器中剩余时间占步长的比例，范围 [0, 1]
+    ///
+    /// 渲染时在上一次和本次更新的状态之间按该系数插值可以消除低更新频率下的抖动。
+    /// 可变步长时总是 1。
+    pub fn alpha(&self) -> f32 {
+        self.step.map_or(1.0, |step| (self.accumulator / step).clamp(0.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_steps_accumulate_across_frames() {
+        // 步长 1/64 秒，测试中的时间均可精确表示
+        let mut timestep = FixedTimestep::new(64);
+        assert_eq!(timestep.step(), Some(1.0 / 64.0));
+
+        // 1.5 步：一次更新，剩余半步
+        assert_eq!(timestep.advance(1.5 / 64.0).count(), 1);
+        assert_eq!(timestep.alpha(), 0.5);
+        // 再半步凑满一步
+        assert_eq!(timestep.advance(0.5 / 64.0).count(), 1);
+        assert_eq!(timestep.alpha(), 0.0);
+
+        // 长时间卡顿后最多追赶 MAX_STEPS_PER_FRAME 次
+        assert_eq!(timestep.advance(10.0).count(), MAX_STEPS_PER_FRAME as usize);
+        assert!(timestep.alpha() <= 1.0);
+    }
+
+    #[test]
+    fn test_variable_step_uses_frame_delta() {
+        let mut timestep = FixedTimestep::new(0);
+        assert_eq!(timestep.step(), None);
+        assert_eq!(timestep.advance(0.016).collect::<Vec<_>>(), vec![0.016]);
+        assert_eq!(timestep.alpha(), 1.0);
+    }
+}
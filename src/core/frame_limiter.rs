@@ -0,0 +1,105 @@
+//! 帧率限制
+//!
+//! 在关闭垂直同步或使用 Immediate 呈现模式时，主循环可能以数千 FPS 空转。
+//! `FrameLimiter` 在每帧绘制结束后等待到下一帧的截止时间：
+//! 先用 `std::thread::sleep` 睡过大部分时间，最后一小段再自旋等待，
+//! 以弥补操作系统调度粒度带来的误差。
+
+use std::time::{Duration, Instant};
+
+/// 截止时间前改为自旋等待的时长
+const SPIN_MARGIN: Duration = Duration::from_millis(1);
+
+/// 帧率限制器
+#[derive(Debug, Clone)]
+pub struct FrameLimiter {
+    /// 目标帧时间，`None` 表示不限制
+    frame_time: Option<Duration>,
+
+    /// 下一帧的截止时间
+    next_frame: Instant,
+}
+
+impl FrameLimiter {
+    /// 创建帧率限制器
+    ///
+    /// `max_fps` 为 `None` 或 0 时不限制帧率。
+    pub fn new(max_fps: Option<u32>) -> Self {
+        let frame_time = max_fps
+            .filter(|&fps| fps > 0)
+            .map(|fps| Duration::from_secs_f64(1.0 / fps as f64));
+
+        Self {
+            frame_time,
+            next_frame: Instant::now(),
+        }
+    }
+
+    /// 目标帧时间
+    pub fn frame_time(&self) -> Option<Duration> {
+        self.frame_time
+    }
+
+    /// 是否启用限制
+    pub fn is_limited(&self) -> bool {
+        self.frame_time.is_some()
+    }
+
+    /// 等待到下一帧的截止时间（在绘制之后调用）
+    pub fn wait(&mut self) {
+        let Some(frame_time) = self.frame_time else {
+            return;
+        };
+
+        // 截止时间按固定步长推进，避免累积误差；
+        // 落后超过一帧时从当前时间重新开始，避免连续多帧不等待地追赶
+        let now = Instant::now();
+        if now > self.next_frame + frame_time {
+            self.next_frame = now;
+        }
+        let deadline = self.next_frame;
+
+        if let Some(remaining) = deadline.checked_duration_since(now) {
+            if remaining > SPIN_MARGIN {
+                std::thread::sleep(remaining - SPIN_MARGIN);
+            }
+            while Instant::now() < deadline {
+                std::hint::spin_loop();
+            }
+        }
+
+        self.next_frame = deadline + frame_time;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_does_not_wait() {
+        let mut limiter = FrameLimiter::new(None);
+        assert!(!limiter.is_limited());
+        assert!(!FrameLimiter::new(Some(0)).is_limited());
+
+        let start = Instant::now();
+        for _ in 0..100 {
+            limiter.wait();
+        }
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_limited_frames_take_target_time() {
+        let mut limiter = FrameLimiter::new(Some(200));
+        assert_eq!(limiter.frame_time(), Some(Duration::from_millis(5)));
+
+        // 第一次调用建立节拍，之后每帧至少间隔 5ms
+        limiter.wait();
+        let start = Instant::now();
+        for _ in 0..10 {
+            limiter.wait();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(45));
+    }
+}
@@ -4,26 +4,169 @@
 //! similar to the DistEngine C++ InputSystem.
 
 use std::collections::HashSet;
-use winit::event::{ElementState, MouseButton};
+use serde::{Deserialize, Serialize};
+use winit::event::{ElementState, MouseButton, MouseScrollDelta};
 use winit::keyboard::KeyCode;
 use winit::window::Window;
 use tracing::{debug, warn};
-use crate::component::Camera;
+use crate::component::{Camera, DirectionalLight, GizmoInput, GIZMO_SNAP_STEP, SHAKE_TRAUMA};
+use crate::math::{Vector2, Vector3};
+
+/// Time constant (seconds) of the velocity smoothing at full strength
+const MAX_SMOOTHING_TIME: f32 = 0.5;
+
+/// Speed (units per second) below which a coasting camera is stopped
+const MIN_COAST_SPEED: f32 = 1e-3;
+
+/// Keys that enable grid snapping while dragging a gizmo
+const SNAP_MODIFIER_KEYS: [KeyCode; 2] = [KeyCode::ControlLeft, KeyCode::ControlRight];
+
+/// Keys that multiply the movement speed while held
+const BOOST_KEYS: [KeyCode; 2] = [KeyCode::ShiftLeft, KeyCode::ShiftRight];
+
+/// Key that switches the arrow keys to light direction control while held
+const LIGHT_MODIFIER_KEY: KeyCode = KeyCode::KeyL;
+
+/// Key that shakes the camera
+const SHAKE_KEY: KeyCode = KeyCode::KeyK;
+
+/// Light rotation speed of the arrow keys in radians per second
+const LIGHT_ROTATION_SPEED: f32 = 1.5;
+
+/// Maximum light elevation (radians) above or below the horizon, keeps the
+/// direction away from the poles so the azimuth never flips
+const MAX_LIGHT_ELEVATION: f32 = 89.0 * std::f32::consts::PI / 180.0;
+
+/// Factor applied to the speed scale per scroll wheel notch
+const SCROLL_SPEED_STEP: f32 = 1.2;
+
+/// Range of the scroll wheel speed scale
+const MIN_SPEED_SCALE: f32 = 1.0 / 16.0;
+const MAX_SPEED_SCALE: f32 = 16.0;
+
+/// Pixels of a touchpad scroll that count as one wheel notch
+const PIXELS_PER_SCROLL_LINE: f32 = 40.0;
+
+/// Default key that toggles mouse capture
+pub const DEFAULT_CAPTURE_KEY: KeyCode = KeyCode::Tab;
+
+/// Keys that can be bound to the capture toggle, by their name in the config file
+const CAPTURE_KEY_NAMES: [(&str, KeyCode); 17] = [
+    ("Tab", KeyCode::Tab),
+    ("Escape", KeyCode::Escape),
+    ("Backquote", KeyCode::Backquote),
+    ("CapsLock", KeyCode::CapsLock),
+    ("Space", KeyCode::Space),
+    ("F1", KeyCode::F1),
+    ("F2", KeyCode::F2),
+    ("F3", KeyCode::F3),
+    ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5),
+    ("F6", KeyCode::F6),
+    ("F7", KeyCode::F7),
+    ("F8", KeyCode::F8),
+    ("F9", KeyCode::F9),
+    ("F10", KeyCode::F10),
+    ("F11", KeyCode::F11),
+    ("F12", KeyCode::F12),
+];
+
+/// Parse the name of a capture toggle key (case-insensitive), e.g. "Tab" or "F1"
+pub fn parse_capture_key(name: &str) -> Option<KeyCode> {
+    CAPTURE_KEY_NAMES
+        .iter()
+        .find(|(key_name, _)| key_name.eq_ignore_ascii_case(name.trim()))
+        .map(|&(_, key)| key)
+}
+
+/// Limits applied to the free-fly camera after every input update
+///
+/// The default is unconstrained. Architectural walkthroughs typically lock the
+/// height and roll and narrow the pitch range so the view cannot tip over.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CameraConstraints {
+    /// Keep the camera height constant while moving
+    pub lock_y: bool,
+    /// Allowed pitch range in degrees (negative looks down)
+    pub lock_pitch_range: (f32, f32),
+    /// Keep the camera's right axis horizontal
+    pub lock_roll: bool,
+}
+
+impl Default for CameraConstraints {
+    fn default() -> Self {
+        Self {
+            lock_y: false,
+            lock_pitch_range: (-90.0, 90.0),
+            lock_roll: false,
+        }
+    }
+}
+
+impl CameraConstraints {
+    /// Clamp the camera orientation into the allowed pitch range and remove roll if locked
+    pub fn apply_rotation(&self, camera: &mut Camera) {
+        if self.lock_roll {
+            level_roll(camera);
+        }
+
+        let (min, max) = self.lock_pitch_range;
+        let pitch = camera_pitch(camera);
+        let clamped = pitch.clamp(min.min(max).to_radians(), max.max(min).to_radians());
+        if clamped != pitch {
+            camera.pitch(clamped - pitch);
+        }
+    }
+}
+
+/// Pitch of the view direction in radians, positive when looking up
+///
+/// The camera looks along -look.
+pub fn camera_pitch(camera: &Camera) -> f32 {
+    (-camera.look().y).clamp(-1.0, 1.0).asin()
+}
+
+/// Rebuild the camera basis from the world up axis so the right axis is horizontal
+fn level_roll(camera: &mut Camera) {
+    let look = camera.look();
+    // Looking straight up or down leaves the roll undefined
+    if look.y.abs() > 0.999 || camera.right().y.abs() < 1e-6 {
+        return;
+    }
+    let position = camera.position();
+    camera.look_at(position, position + look, Vector3::y());
+}
 
 /// Configuration for InputSystem behavior
 #[derive(Debug, Clone)]
 pub struct InputConfig {
     /// Camera movement speed in units per second
     pub move_speed: f32,
+    /// Speed multiplier while Shift is held
+    pub boost_multiplier: f32,
     /// Mouse sensitivity in degrees per pixel
     pub mouse_sensitivity: f32,
+    /// Smooth camera movement with an accelerating/decaying velocity
+    pub smoothing_enabled: bool,
+    /// Smoothing strength in [0, 1]; 0 is instantaneous
+    pub smoothing_strength: f32,
+    /// Movement and rotation limits
+    pub constraints: CameraConstraints,
+    /// Key that toggles mouse capture
+    pub capture_key: KeyCode,
 }
 
 impl Default for InputConfig {
     fn default() -> Self {
         Self {
             move_speed: 10.0,
+            boost_multiplier: 4.0,
             mouse_sensitivity: 0.25,
+            smoothing_enabled: false,
+            smoothing_strength: 0.5,
+            constraints: CameraConstraints::default(),
+            capture_key: DEFAULT_CAPTURE_KEY,
         }
     }
 }
@@ -41,13 +184,33 @@ pub struct InputSystem {
 
     // Movement configuration
     move_speed: f32,        // Units per second
+    boost_multiplier: f32,  // Applied while Shift is held
+    speed_scale: f32,       // Adjusted by the scroll wheel, kept across frames
     mouse_sensitivity: f32, // Degrees per pixel
 
+    // Movement smoothing
+    smoothing_enabled: bool,
+    smoothing_strength: f32,
+    velocity: Vector2, // (strafe, walk) in units per second
+
+    // Movement and rotation limits
+    constraints: CameraConstraints,
+
+    // Scripted camera pose (eye, target) that overrides user input
+    camera_pose: Option<(Vector3, Vector3)>,
+
     // First mouse movement flag
     first_mouse: bool,
 
     // Cursor lock state
     cursor_locked: bool,
+
+    // Mouse capture: the cursor stays locked and mouse motion always turns the camera
+    captured: bool,
+    capture_key: KeyCode,
+
+    // Camera shake trauma requested by the shake key since it was last taken
+    shake_trauma: f32,
 }
 
 impl InputSystem {
@@ -64,9 +227,19 @@ impl InputSystem {
             mouse_buttons: HashSet::new(),
             mouse_delta: (0.0, 0.0),
             move_speed: config.move_speed,
+            boost_multiplier: config.boost_multiplier,
+            speed_scale: 1.0,
             mouse_sensitivity: config.mouse_sensitivity,
+            smoothing_enabled: config.smoothing_enabled,
+            smoothing_strength: config.smoothing_strength.clamp(0.0, 1.0),
+            velocity: Vector2::zeros(),
+            constraints: config.constraints,
+            camera_pose: None,
             first_mouse: true,
             cursor_locked: false,
+            captured: false,
+            capture_key: config.capture_key,
+            shake_trauma: 0.0,
         }
     }
 
@@ -79,7 +252,10 @@ impl InputSystem {
     ) -> bool {
         match state {
             ElementState::Pressed => {
-                self.pressed_keys.insert(keycode);
+                // Key repeat does not add more trauma
+                if self.pressed_keys.insert(keycode) && keycode == SHAKE_KEY {
+                    self.shake_trauma += SHAKE_TRAUMA;
+                }
             }
             ElementState::Released => {
                 self.pressed_keys.remove(&keycode);
@@ -108,8 +284,8 @@ impl InputSystem {
             ElementState::Released => {
                 self.mouse_buttons.remove(&button);
 
-                // Unlock cursor when right mouse button is released
-                if button == MouseButton::Right {
+                // Unlock cursor when right mouse button is released, unless the mouse is captured
+                if button == MouseButton::Right && !self.captured {
                     self.unlock_cursor(window);
                 }
             }
@@ -118,6 +294,12 @@ impl InputSystem {
 
     /// Process mouse movement event
     pub fn on_mouse_move(&mut self, position: (f64, f64)) {
+        if self.captured {
+            // While captured the camera follows the raw motion from `on_mouse_motion`;
+            // a locked cursor may not report positions at all
+            self.last_mouse_pos = position;
+            return;
+        }
         if self.first_mouse {
             self.last_mouse_pos = position;
             self.first_mouse = false;
@@ -136,21 +318,93 @@ impl InputSystem {
         self.last_mouse_pos = position;
     }
 
+    /// Process raw mouse motion (device event), which keeps arriving when the cursor
+    /// is locked or pinned to the window edge; only used while the mouse is captured
+    pub fn on_mouse_motion(&mut self, delta: (f64, f64)) {
+        if !self.captured {
+            return;
+        }
+        // Same y flip as on_mouse_move
+        self.mouse_delta.0 += delta.0 as f32;
+        self.mouse_delta.1 -= delta.1 as f32;
+    }
+
+    /// Process mouse wheel event
+    /// Scrolling up speeds the camera up, scrolling down slows it down
+    pub fn on_mouse_wheel(&mut self, delta: MouseScrollDelta) {
+        let notches = match delta {
+            MouseScrollDelta::LineDelta(_, y) => y,
+            MouseScrollDelta::PixelDelta(position) => position.y as f32 / PIXELS_PER_SCROLL_LINE,
+        };
+        self.speed_scale = (self.speed_scale * SCROLL_SPEED_STEP.powf(notches))
+            .clamp(MIN_SPEED_SCALE, MAX_SPEED_SCALE);
+    }
+
     /// Update camera based on current input state
     /// Called every frame with delta time
     pub fn update_camera(&mut self, camera: &mut Camera, delta_time: f32) {
+        if let Some((eye, target)) = self.camera_pose {
+            // look is the camera's backward axis, so it points away from the target
+            camera.look_at(eye, eye + (eye - target), Vector3::y());
+            return;
+        }
+
         // Handle keyboard movement (WASD)
-        self.handle_keyboard_movement(camera, delta_time);
+        let start = camera.position();
+        if self.smoothing_enabled {
+            self.handle_smoothed_movement(camera, delta_time);
+        } else {
+            self.handle_keyboard_movement(camera, delta_time);
+        }
+        if self.constraints.lock_y {
+            let position = camera.position();
+            camera.set_position(Vector3::new(position.x, start.y, position.z));
+        }
 
         // Handle mouse rotation (right button drag)
         self.handle_mouse_rotation(camera);
+        self.constraints.apply_rotation(camera);
 
         // Note: mouse_delta is now reset inside handle_mouse_rotation after use
     }
 
+    /// Rotate the directional light with the arrow keys while L is held
+    ///
+    /// Left/Right change the azimuth around the Y axis and Up/Down raise or lower
+    /// the light; the elevation is clamped so the direction never flips over the pole.
+    /// Returns true if the light direction changed this frame.
+    pub fn update_light(&self, light: &mut DirectionalLight, delta_time: f32) -> bool {
+        if !self.is_light_modifier_held() {
+            return false;
+        }
+
+        let axis = |positive: KeyCode, negative: KeyCode| {
+            self.pressed_keys.contains(&positive) as i32 - self.pressed_keys.contains(&negative) as i32
+        };
+        let azimuth_input = axis(KeyCode::ArrowRight, KeyCode::ArrowLeft);
+        let elevation_input = axis(KeyCode::ArrowUp, KeyCode::ArrowDown);
+        if azimuth_input == 0 && elevation_input == 0 {
+            return false;
+        }
+
+        // The light shines along `direction`, so its elevation above the horizon is -asin(y)
+        let direction = light.direction.try_normalize(1e-6).unwrap_or(Vector3::new(0.0, -1.0, 0.0));
+        let step = LIGHT_ROTATION_SPEED * delta_time;
+        let azimuth = direction.z.atan2(direction.x) + azimuth_input as f32 * step;
+        let elevation = ((-direction.y).clamp(-1.0, 1.0).asin() + elevation_input as f32 * step)
+            .clamp(-MAX_LIGHT_ELEVATION, MAX_LIGHT_ELEVATION);
+
+        light.direction = Vector3::new(
+            elevation.cos() * azimuth.cos(),
+            -elevation.sin(),
+            elevation.cos() * azimuth.sin(),
+        );
+        true
+    }
+
     /// Handle keyboard-based camera movement
     fn handle_keyboard_movement(&self, camera: &mut Camera, delta_time: f32) {
-        let distance = self.move_speed * delta_time;
+        let distance = self.effective_move_speed() * delta_time;
 
         if self.pressed_keys.contains(&KeyCode::KeyW) {
             camera.walk(-distance);
@@ -166,10 +420,54 @@ impl InputSystem {
         }
     }
 
+    /// Direction requested by the pressed keys as (strafe, walk)
+    fn input_direction(&self) -> Vector2 {
+        let mut direction = Vector2::zeros();
+
+        if self.pressed_keys.contains(&KeyCode::KeyW) {
+            direction.y -= 1.0;
+        }
+        if self.pressed_keys.contains(&KeyCode::KeyS) {
+            direction.y += 1.0;
+        }
+        if self.pressed_keys.contains(&KeyCode::KeyA) {
+            direction.x -= 1.0;
+        }
+        if self.pressed_keys.contains(&KeyCode::KeyD) {
+            direction.x += 1.0;
+        }
+
+        direction
+    }
+
+    /// Handle keyboard-based camera movement with velocity smoothing
+    ///
+    /// The velocity approaches the input direction exponentially and decays
+    /// towards zero once the keys are released.
+    fn handle_smoothed_movement(&mut self, camera: &mut Camera, delta_time: f32) {
+        let target = self.input_direction() * self.effective_move_speed();
+
+        let time_constant = self.smoothing_strength * MAX_SMOOTHING_TIME;
+        let blend = if time_constant > 0.0 {
+            1.0 - (-delta_time / time_constant).exp()
+        } else {
+            1.0
+        };
+        self.velocity += (target - self.velocity) * blend;
+
+        if target == Vector2::zeros() && self.velocity.norm() < MIN_COAST_SPEED {
+            self.velocity = Vector2::zeros();
+            return;
+        }
+
+        camera.strafe(self.velocity.x * delta_time);
+        camera.walk(self.velocity.y * delta_time);
+    }
+
     /// Handle mouse-based camera rotation
     fn handle_mouse_rotation(&mut self, camera: &mut Camera) {
-        // Only rotate if right mouse button is pressed
-        if !self.mouse_buttons.contains(&MouseButton::Right) {
+        // Only rotate if right mouse button is pressed or the mouse is captured
+        if !self.captured && !self.mouse_buttons.contains(&MouseButton::Right) {
             // Reset delta when not rotating to prevent accumulation
             self.mouse_delta = (0.0, 0.0);
             return;
@@ -240,12 +538,50 @@ impl InputSystem {
         self.cursor_locked = false;
     }
 
+    /// Whether `keycode` is the capture toggle key
+    pub fn is_capture_key(&self, keycode: KeyCode) -> bool {
+        keycode == self.capture_key
+    }
+
+    /// Whether the mouse is captured for camera look
+    pub fn is_captured(&self) -> bool {
+        self.captured
+    }
+
+    /// Capture or release the mouse
+    ///
+    /// While captured the cursor is locked and hidden and mouse motion turns the camera
+    /// without holding the right button. Releasing frees the cursor (e.g. to click the GUI)
+    /// and pauses camera look; losing window focus always releases.
+    pub fn set_capture(&mut self, window: &Window, captured: bool) {
+        if captured {
+            self.lock_cursor(window);
+        } else {
+            self.unlock_cursor(window);
+        }
+        self.set_captured(captured);
+    }
+
+    /// Capture state bookkeeping of `set_capture`, without touching the window
+    fn set_captured(&mut self, captured: bool) {
+        if captured != self.captured {
+            debug!(captured, "Mouse capture changed");
+        }
+        self.captured = captured;
+        self.reset_mouse();
+    }
+
     /// Reset mouse state (useful when window loses focus)
     pub fn reset_mouse(&mut self) {
         self.mouse_delta = (0.0, 0.0);
         self.first_mouse = true;
     }
 
+    /// Take the camera shake trauma requested by the shake key (K) since the last call
+    pub fn take_shake_trauma(&mut self) -> f32 {
+        std::mem::take(&mut self.shake_trauma)
+    }
+
     /// Check if a specific key is currently pressed
     pub fn is_key_pressed(&self, key: KeyCode) -> bool {
         self.pressed_keys.contains(&key)
@@ -256,6 +592,36 @@ impl InputSystem {
         self.mouse_buttons.contains(&button)
     }
 
+    /// Current cursor position in window pixels (origin at the top-left corner)
+    pub fn cursor_position(&self) -> Vector2 {
+        Vector2::new(self.last_mouse_pos.0 as f32, self.last_mouse_pos.1 as f32)
+    }
+
+    /// Check if the grid snapping modifier (Ctrl) is held
+    pub fn is_snap_modifier_held(&self) -> bool {
+        SNAP_MODIFIER_KEYS.iter().any(|key| self.pressed_keys.contains(key))
+    }
+
+    /// Check if the light control modifier (L) is held
+    pub fn is_light_modifier_held(&self) -> bool {
+        self.pressed_keys.contains(&LIGHT_MODIFIER_KEY)
+    }
+
+    /// Check if the speed boost modifier (Shift) is held
+    pub fn is_boost_held(&self) -> bool {
+        BOOST_KEYS.iter().any(|key| self.pressed_keys.contains(key))
+    }
+
+    /// Build the gizmo input for this frame
+    /// The left mouse button drags, holding Ctrl snaps to the grid
+    pub fn gizmo_input(&self) -> GizmoInput {
+        GizmoInput {
+            cursor: self.cursor_position(),
+            pressed: self.mouse_buttons.contains(&MouseButton::Left),
+            snap: self.is_snap_modifier_held().then_some(GIZMO_SNAP_STEP),
+        }
+    }
+
     /// Get the current movement speed
     pub fn move_speed(&self) -> f32 {
         self.move_speed
@@ -266,6 +632,27 @@ impl InputSystem {
         self.move_speed = speed;
     }
 
+    /// Get the speed multiplier applied while Shift is held
+    pub fn boost_multiplier(&self) -> f32 {
+        self.boost_multiplier
+    }
+
+    /// Set the speed multiplier applied while Shift is held
+    pub fn set_boost_multiplier(&mut self, multiplier: f32) {
+        self.boost_multiplier = multiplier;
+    }
+
+    /// Get the scroll wheel speed scale
+    pub fn speed_scale(&self) -> f32 {
+        self.speed_scale
+    }
+
+    /// Movement speed for this frame: base speed, scroll scale and boost combined
+    pub fn effective_move_speed(&self) -> f32 {
+        let boost = if self.is_boost_held() { self.boost_multiplier } else { 1.0 };
+        self.move_speed * self.speed_scale * boost
+    }
+
     /// Get the current mouse sensitivity
     pub fn mouse_sensitivity(&self) -> f32 {
         self.mouse_sensitivity
@@ -275,6 +662,42 @@ impl InputSystem {
     pub fn set_mouse_sensitivity(&mut self, sensitivity: f32) {
         self.mouse_sensitivity = sensitivity;
     }
+
+    /// Check if movement smoothing is enabled
+    pub fn smoothing_enabled(&self) -> bool {
+        self.smoothing_enabled
+    }
+
+    /// Get the movement smoothing strength
+    pub fn smoothing_strength(&self) -> f32 {
+        self.smoothing_strength
+    }
+
+    /// Configure movement smoothing
+    /// Disabling it drops any remaining velocity so the camera stops immediately
+    pub fn set_smoothing(&mut self, enabled: bool, strength: f32) {
+        if !enabled {
+            self.velocity = Vector2::zeros();
+        }
+        self.smoothing_enabled = enabled;
+        self.smoothing_strength = strength.clamp(0.0, 1.0);
+    }
+
+    /// Get the camera movement and rotation limits
+    pub fn constraints(&self) -> CameraConstraints {
+        self.constraints
+    }
+
+    /// Set the camera movement and rotation limits
+    pub fn set_constraints(&mut self, constraints: CameraConstraints) {
+        self.constraints = constraints;
+    }
+
+    /// Drive the camera from a scripted (eye, target) pose instead of user input,
+    /// e.g. the fixed path of benchmark mode; `None` restores normal control
+    pub fn set_camera_pose(&mut self, pose: Option<(Vector3, Vector3)>) {
+        self.camera_pose = pose;
+    }
 }
 
 impl Default for InputSystem {
@@ -282,3 +705,285 @@ impl Default for InputSystem {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn hold_w(input: &mut InputSystem) {
+        input.on_keyboard_input(KeyCode::KeyW, ElementState::Pressed);
+    }
+
+    fn release_w(input: &mut InputSystem) {
+        input.on_keyboard_input(KeyCode::KeyW, ElementState::Released);
+    }
+
+    #[test]
+    fn test_capture_turns_camera_without_right_button() {
+        let mut input = InputSystem::new();
+        let mut camera = Camera::main_camera();
+        let start = camera.look();
+
+        // Not captured: raw motion is ignored
+        input.on_mouse_motion((40.0, 0.0));
+        input.update_camera(&mut camera, DT);
+        assert_eq!(camera.look(), start);
+
+        input.set_captured(true);
+        assert!(input.is_captured());
+        input.on_mouse_motion((20.0, 0.0));
+        input.on_mouse_motion((20.0, 0.0));
+        input.update_camera(&mut camera, DT);
+        let turned = camera.look();
+        assert!((turned - start).norm() > 1e-3);
+
+        // Released: camera look pauses
+        input.set_captured(false);
+        input.on_mouse_motion((40.0, 0.0));
+        input.update_camera(&mut camera, DT);
+        assert_eq!(camera.look(), turned);
+    }
+
+    #[test]
+    fn test_shake_key_adds_trauma_once_per_press() {
+        let mut input = InputSystem::new();
+        input.on_keyboard_input(SHAKE_KEY, ElementState::Pressed);
+        // Key repeat
+        input.on_keyboard_input(SHAKE_KEY, ElementState::Pressed);
+        assert_eq!(input.take_shake_trauma(), SHAKE_TRAUMA);
+        assert_eq!(input.take_shake_trauma(), 0.0);
+
+        input.on_keyboard_input(SHAKE_KEY, ElementState::Released);
+        input.on_keyboard_input(SHAKE_KEY, ElementState::Pressed);
+        assert_eq!(input.take_shake_trauma(), SHAKE_TRAUMA);
+    }
+
+    #[test]
+    fn test_parse_capture_key() {
+        assert_eq!(parse_capture_key("Tab"), Some(KeyCode::Tab));
+        assert_eq!(parse_capture_key(" f1 "), Some(KeyCode::F1));
+        assert_eq!(parse_capture_key("Backquote"), Some(KeyCode::Backquote));
+        assert_eq!(parse_capture_key("KeyW"), None);
+        assert!(InputSystem::new().is_capture_key(DEFAULT_CAPTURE_KEY));
+    }
+
+    #[test]
+    fn test_disabled_smoothing_moves_instantly() {
+        let mut input = InputSystem::new();
+        let mut camera = Camera::main_camera();
+        let start = camera.position();
+
+        hold_w(&mut input);
+        input.update_camera(&mut camera, DT);
+        let moved = (camera.position() - start).norm();
+        assert!((moved - input.move_speed() * DT).abs() < 1e-5);
+
+        release_w(&mut input);
+        let stopped = camera.position();
+        input.update_camera(&mut camera, DT);
+        assert_eq!(camera.position(), stopped);
+    }
+
+    #[test]
+    fn test_smoothing_accelerates_and_coasts() {
+        let mut input = InputSystem::new();
+        input.set_smoothing(true, 0.5);
+        let mut camera = Camera::main_camera();
+
+        hold_w(&mut input);
+        let before = camera.position();
+        input.update_camera(&mut camera, DT);
+        let first_step = (camera.position() - before).norm();
+        assert!(first_step > 0.0 && first_step < input.move_speed() * DT);
+
+        for _ in 0..120 {
+            input.update_camera(&mut camera, DT);
+        }
+        let before = camera.position();
+        input.update_camera(&mut camera, DT);
+        let full_step = (camera.position() - before).norm();
+        assert!((full_step - input.move_speed() * DT).abs() < 1e-3);
+
+        // Releasing the key coasts and eventually stops
+        release_w(&mut input);
+        let before = camera.position();
+        input.update_camera(&mut camera, DT);
+        let coast_step = (camera.position() - before).norm();
+        assert!(coast_step > 0.0 && coast_step < full_step);
+
+        for _ in 0..600 {
+            input.update_camera(&mut camera, DT);
+        }
+        let before = camera.position();
+        input.update_camera(&mut camera, DT);
+        assert_eq!(camera.position(), before);
+    }
+
+    #[test]
+    fn test_boost_multiplies_speed() {
+        let mut input = InputSystem::with_config(InputConfig {
+            move_speed: 5.0,
+            boost_multiplier: 3.0,
+            ..Default::default()
+        });
+        let mut camera = Camera::main_camera();
+        let start = camera.position();
+
+        hold_w(&mut input);
+        input.on_keyboard_input(KeyCode::ShiftLeft, ElementState::Pressed);
+        assert_eq!(input.effective_move_speed(), 15.0);
+        input.update_camera(&mut camera, DT);
+        assert!(((camera.position() - start).norm() - 15.0 * DT).abs() < 1e-5);
+
+        input.on_keyboard_input(KeyCode::ShiftLeft, ElementState::Released);
+        assert_eq!(input.effective_move_speed(), 5.0);
+    }
+
+    #[test]
+    fn test_scroll_scales_speed_within_range() {
+        let mut input = InputSystem::new();
+        let base = input.move_speed();
+
+        input.on_mouse_wheel(MouseScrollDelta::LineDelta(0.0, 1.0));
+        assert!((input.effective_move_speed() - base * SCROLL_SPEED_STEP).abs() < 1e-4);
+        input.on_mouse_wheel(MouseScrollDelta::LineDelta(0.0, -2.0));
+        assert!((input.effective_move_speed() - base / SCROLL_SPEED_STEP).abs() < 1e-4);
+
+        // The scale persists across scrolls and is clamped to its range
+        for _ in 0..100 {
+            input.on_mouse_wheel(MouseScrollDelta::LineDelta(0.0, 1.0));
+        }
+        assert_eq!(input.speed_scale(), MAX_SPEED_SCALE);
+        for _ in 0..100 {
+            input.on_mouse_wheel(MouseScrollDelta::PixelDelta((0.0, -PIXELS_PER_SCROLL_LINE as f64).into()));
+        }
+        assert_eq!(input.speed_scale(), MIN_SPEED_SCALE);
+
+        // Changing the base speed keeps the scroll scale
+        input.set_move_speed(2.0);
+        assert_eq!(input.effective_move_speed(), 2.0 * MIN_SPEED_SCALE);
+    }
+
+    #[test]
+    fn test_gizmo_input_tracks_cursor_and_snap_modifier() {
+        let mut input = InputSystem::new();
+        input.on_mouse_move((120.0, 80.0));
+
+        let gizmo_input = input.gizmo_input();
+        assert_eq!(gizmo_input.cursor, Vector2::new(120.0, 80.0));
+        assert!(!gizmo_input.pressed);
+        assert_eq!(gizmo_input.snap, None);
+
+        input.on_keyboard_input(KeyCode::ControlLeft, ElementState::Pressed);
+        assert_eq!(input.gizmo_input().snap, Some(GIZMO_SNAP_STEP));
+    }
+
+    #[test]
+    fn test_arrow_keys_rotate_light_only_with_modifier() {
+        let mut input = InputSystem::new();
+        let mut light = DirectionalLight::new("Light");
+        light.direction = Vector3::new(1.0, -1.0, 0.0).normalize();
+
+        input.on_keyboard_input(KeyCode::ArrowRight, ElementState::Pressed);
+        assert!(!input.update_light(&mut light, 0.5));
+        assert_eq!(light.direction, Vector3::new(1.0, -1.0, 0.0).normalize());
+
+        // Right arrow turns the azimuth, elevation stays at 45 degrees
+        input.on_keyboard_input(KeyCode::KeyL, ElementState::Pressed);
+        assert!(input.update_light(&mut light, 0.5));
+        assert!((light.direction.norm() - 1.0).abs() < 1e-5);
+        assert!((light.direction.y + std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-5);
+        assert!(light.direction.z > 0.0);
+
+        // Holding Up for a long time stops just short of straight down
+        input.on_keyboard_input(KeyCode::ArrowRight, ElementState::Released);
+        input.on_keyboard_input(KeyCode::ArrowUp, ElementState::Pressed);
+        for _ in 0..10 {
+            input.update_light(&mut light, 1.0);
+        }
+        assert!((light.direction.y + MAX_LIGHT_ELEVATION.sin()).abs() < 1e-5);
+        assert!(light.direction.x.hypot(light.direction.z) > 1e-3);
+    }
+
+    #[test]
+    fn test_zero_strength_matches_instant_movement() {
+        let mut input = InputSystem::new();
+        input.set_smoothing(true, 0.0);
+        let mut camera = Camera::main_camera();
+        let start = camera.position();
+
+        hold_w(&mut input);
+        input.update_camera(&mut camera, DT);
+        let moved = (camera.position() - start).norm();
+        assert!((moved - input.move_speed() * DT).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_camera_pose_overrides_input() {
+        let mut input = InputSystem::new();
+        let mut camera = Camera::main_camera();
+        let eye = Vector3::new(3.0, 1.0, 0.0);
+        input.set_camera_pose(Some((eye, Vector3::zeros())));
+
+        hold_w(&mut input);
+        input.update_camera(&mut camera, DT);
+        assert_eq!(camera.position(), eye);
+        // The camera faces the target, i.e. looks along -look
+        assert!((camera.look() - eye.normalize()).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_lock_y_keeps_height_while_moving_forward() {
+        let mut input = InputSystem::with_config(InputConfig {
+            constraints: CameraConstraints {
+                lock_y: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let mut camera = Camera::main_camera();
+        // Look down so walking forward would otherwise descend
+        camera.pitch(-0.5);
+        let start = camera.position();
+
+        hold_w(&mut input);
+        for _ in 0..30 {
+            input.update_camera(&mut camera, DT);
+        }
+        assert_eq!(camera.position().y, start.y);
+        assert!((camera.position() - start).norm() > 0.0);
+    }
+
+    #[test]
+    fn test_pitch_range_and_roll_lock() {
+        let mut input = InputSystem::with_config(InputConfig {
+            constraints: CameraConstraints {
+                lock_pitch_range: (-30.0, 30.0),
+                lock_roll: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let mut camera = Camera::main_camera();
+        camera.pitch(1.2);
+        input.update_camera(&mut camera, DT);
+        assert!((camera_pitch(&camera) - 30f32.to_radians()).abs() < 1e-4);
+
+        // A tilted up vector rolls the camera; the lock levels it without turning the view
+        let mut camera = Camera::main_camera();
+        camera.look_at(Vector3::zeros(), Vector3::new(0.0, 0.0, 1.0), Vector3::new(0.5, 1.0, 0.0));
+        assert!(camera.right().y.abs() > 0.1);
+        input.update_camera(&mut camera, DT);
+        assert!(camera.right().y.abs() < 1e-6);
+        assert!((camera.look() - Vector3::new(0.0, 0.0, 1.0)).norm() < 1e-6);
+
+        // The default constraints leave the camera free
+        let mut camera = Camera::main_camera();
+        camera.pitch(1.2);
+        InputSystem::new().update_camera(&mut camera, DT);
+        assert!((camera_pitch(&camera) - 1.2).abs() < 1e-4);
+    }
+}
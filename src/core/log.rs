@@ -9,6 +9,7 @@
 //! - 高性能：零成本抽象，编译时优化
 //! - 灵活输出：支持控制台和文件输出
 //! - 日志级别：trace, debug, info, warn, error
+//! - 引擎内查看：最近的日志保存在环形缓冲中，由 GUI 的控制台面板显示
 //!
 //! # 使用示例
 //!
@@ -27,14 +28,19 @@
 //! log::info!(width = 800, height = 600, "Window created");
 //! ```
 
-use tracing::Level;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
 use tracing_subscriber::{
     fmt::{self, format::FmtSpan},
+    layer::Context,
     prelude::*,
-    EnvFilter,
+    EnvFilter, Layer,
 };
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use std::collections::VecDeque;
+use std::fmt::Debug;
 use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
 
 use super::config::LogLevel;
 
@@ -103,6 +109,7 @@ pub fn init_logger(level: LogLevel, file_output: bool, log_file_path: Option<&st
             .with(filter)
             .with(console_layer)
             .with(file_layer)
+            .with(LogBufferLayer::new(log_buffer().clone()))
             .init();
     } else {
         // 仅控制台输出
@@ -116,6 +123,7 @@ pub fn init_logger(level: LogLevel, file_output: bool, log_file_path: Option<&st
         tracing_subscriber::registry()
             .with(filter)
             .with(fmt_layer)
+            .with(LogBufferLayer::new(log_buffer().clone()))
             .init();
     }
 }
@@ -128,6 +136,124 @@ pub fn init_simple() {
     init_logger(LogLevel::Info, false, None);
 }
 
+/// 日志缓冲默认保留的记录数
+pub const LOG_BUFFER_CAPACITY: usize = 1000;
+
+/// 一条日志记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    pub level: Level,
+    pub target: String,
+    /// 消息及其后的键值对（`message key=value ...`）
+    pub message: String,
+}
+
+/// 最近日志的环形缓冲
+///
+/// 可在线程间共享，超过容量时丢弃最早的记录。
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    capacity: usize,
+    records: Arc<Mutex<VecDeque<LogRecord>>>,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            records: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// 追加一条记录
+    pub fn push(&self, record: LogRecord) {
+        let mut records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// 不比 `max_level` 更详细的记录（按时间顺序）
+    pub fn records(&self, max_level: Level) -> Vec<LogRecord> {
+        let records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        records.iter().filter(|record| record.level <= max_level).cloned().collect()
+    }
+
+    /// 记录数量
+    pub fn len(&self) -> usize {
+        self.records.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清空所有记录
+    pub fn clear(&self) {
+        self.records.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+/// 全局日志缓冲，[`init_logger`] 安装的订阅者把日志写入这里
+pub fn log_buffer() -> &'static LogBuffer {
+    static BUFFER: OnceLock<LogBuffer> = OnceLock::new();
+    BUFFER.get_or_init(|| LogBuffer::new(LOG_BUFFER_CAPACITY))
+}
+
+/// 把事件格式化后写入 [`LogBuffer`] 的 `tracing` 层
+///
+/// 只接收通过日志级别过滤的事件。
+pub struct LogBufferLayer {
+    buffer: LogBuffer,
+}
+
+impl LogBufferLayer {
+    pub fn new(buffer: LogBuffer) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        self.buffer.push(LogRecord {
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+        });
+    }
+}
+
+/// 拼接事件的消息和其他字段
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl MessageVisitor {
+    fn append(&mut self, field: &Field, value: &dyn std::fmt::Display) {
+        if field.name() == "message" {
+            self.message.insert_str(0, &value.to_string());
+        } else {
+            self.message.push_str(&format!(" {}={}", field.name(), value));
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.append(field, &value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.append(field, &format_args!("{:?}", value));
+    }
+}
+
 // 重新导出 tracing 的宏，提供类似 spdlog 的接口
 
 // 定义类似 DistEngine 的宏
@@ -228,4 +354,29 @@ mod tests {
         assert_eq!(Level::from(LogLevel::Info), Level::INFO);
         assert_eq!(Level::from(LogLevel::Error), Level::ERROR);
     }
+
+    #[test]
+    fn test_log_buffer_collects_events() {
+        let buffer = LogBuffer::new(3);
+        let subscriber = tracing_subscriber::registry().with(LogBufferLayer::new(buffer.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!("first");
+            tracing::warn!(width = 800, name = "main", "Window created");
+            tracing::info!("third");
+            tracing::error!("fourth");
+        });
+
+        // 超过容量时丢弃最早的记录
+        assert_eq!(buffer.len(), 3);
+        let records = buffer.records(Level::TRACE);
+        assert_eq!(records[0].level, Level::WARN);
+        assert_eq!(records[0].message, "Window created width=800 name=main");
+
+        // 按级别过滤
+        let warnings: Vec<String> = buffer.records(Level::WARN).into_iter().map(|r| r.message).collect();
+        assert_eq!(warnings, vec!["Window created width=800 name=main", "fourth"]);
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+    }
 }
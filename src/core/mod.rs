@@ -8,9 +8,15 @@
 //! - `log`：日志系统，提供结构化的日志记录功能
 //! - `config`：配置管理，支持从配置文件加载引擎设置
 //! - `error`：错误处理，定义统一的错误类型
+//! - `error_dialog`：错误对话框，致命错误退出前用系统对话框显示错误信息
 //! - `event`：事件系统，提供统一的事件处理机制
 //! - `scene`：场景配置，管理相机和模型的变换数据
 //! - `input`：输入系统，处理键盘和鼠标输入
+//! - `frame_limiter`：帧率限制，避免无垂直同步时主循环空转
+//! - `fixed_timestep`：固定步长更新，使动画等逻辑与帧率无关
+//! - `resize_debounce`：窗口尺寸变化去抖，拖动窗口时只在停止拖动后重建交换链
+//! - `file_watch`：文件变化监视，模型文件被外部工具修改后自动重新加载
+//! - `benchmark`：基准测试模式，按固定相机路径渲染指定帧数并输出耗时统计
 //! - `runtime`：运行时管理，负责后端初始化
 //!
 //! # 设计理念
@@ -24,9 +30,15 @@
 pub mod log;
 pub mod config;
 pub mod error;
+pub mod error_dialog;
 pub mod event;
 pub mod scene;
 pub mod input;
+pub mod frame_limiter;
+pub mod fixed_timestep;
+pub mod resize_debounce;
+pub mod file_watch;
+pub mod benchmark;
 
 pub mod runtime;
 
@@ -0,0 +1,73 @@
+//! 窗口尺寸变化去抖
+//!
+//! 拖动窗口边框时每移动一个像素都会产生一次 `Resized` 事件。DX12 等后端重建交换链需要等待 GPU 空闲，
+//! 每个事件都重建会造成明显的闪烁和卡顿。`ResizeDebounce` 记录最后一次尺寸变化的时间，
+//! 尺寸停止变化超过设定的时长后才通知渲染器重建一次。
+
+use std::time::{Duration, Instant};
+
+/// 窗口尺寸变化去抖器
+#[derive(Debug, Clone)]
+pub struct ResizeDebounce {
+    /// 尺寸停止变化后等待的时长，为 0 时每次变化立即生效
+    delay: Duration,
+
+    /// 最后一次尚未处理的尺寸变化时间
+    pending: Option<Instant>,
+}
+
+impl ResizeDebounce {
+    /// 创建去抖器，`delay_ms` 为 0 时不去抖
+    pub fn new(delay_ms: u64) -> Self {
+        Self {
+            delay: Duration::from_millis(delay_ms),
+            pending: None,
+        }
+    }
+
+    /// 记录一次尺寸变化，返回 true 表示应立即重建（未启用去抖）
+    pub fn request(&mut self, now: Instant) -> bool {
+        if self.delay.is_zero() {
+            return true;
+        }
+        self.pending = Some(now);
+        false
+    }
+
+    /// 尺寸已稳定足够长时间时返回 true 并清除待处理的变化（每帧绘制前调用）
+    pub fn poll(&mut self, now: Instant) -> bool {
+        match self.pending {
+            Some(last) if now.saturating_duration_since(last) >= self.delay => {
+                self.pending = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_delay_resizes_immediately() {
+        let mut debounce = ResizeDebounce::new(0);
+        let now = Instant::now();
+        assert!(debounce.request(now));
+        assert!(!debounce.poll(now));
+    }
+
+    #[test]
+    fn test_consecutive_resizes_collapse_into_one() {
+        let mut debounce = ResizeDebounce::new(50);
+        let start = Instant::now();
+        assert!(!debounce.request(start));
+        assert!(!debounce.request(start + Duration::from_millis(30)));
+
+        // 计时从最后一次变化开始
+        assert!(!debounce.poll(start + Duration::from_millis(60)));
+        assert!(debounce.poll(start + Duration::from_millis(80)));
+        assert!(!debounce.poll(start + Duration::from_millis(200)));
+    }
+}
@@ -2,11 +2,21 @@
 //!
 //! 定义场景配置，包括相机、模型等元素的变换和参数。
 
-use serde::{Deserialize, Serialize};
-use std::path::Path;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::{Path, PathBuf};
 use std::fs;
 use crate::core::error::{Result, DistRenderError, ConfigError};
-use crate::math::{Vector3, Matrix4};
+use crate::math::{color_space, Vector3, Matrix4};
+use crate::component::FovAxis;
+use crate::core::input::CameraConstraints;
+use crate::geometry::coordinate::{CoordinateSystem, Handedness, UpAxis};
+use crate::geometry::loaders::{load_base_color_texture, load_mesh, GltfLoader};
+use crate::geometry::material::TextureSource;
+use crate::geometry::mesh_cache;
+use crate::geometry::mesh::MeshData;
+use crate::geometry::primitives::PrimitiveShape;
+use crate::geometry::skinning::SkinnedMesh;
+use crate::renderer::stencil::StencilMask;
 
 /// 3D 变换数据
 ///
@@ -102,9 +112,21 @@ pub struct DirectionalLightConfig {
     pub intensity: f32,
 }
 
+/// 清除颜色循环一圈色相的时间（秒）
+pub const CLEAR_COLOR_CYCLE_PERIOD: f32 = 6.0;
+
 fn default_light_color() -> [f32; 3] { [1.0, 1.0, 1.0] }
 fn default_light_intensity() -> f32 { 1.0 }
 fn default_clear_color() -> [f32; 4] { [0.0, 0.0, 0.2, 1.0] }
+fn default_clear_color_enabled() -> bool { true }
+fn default_ambient_color() -> [f32; 3] { [1.0, 1.0, 1.0] }
+fn default_ambient_intensity() -> f32 { 0.1 }
+fn default_exposure() -> f32 { 1.0 }
+fn default_import_scale() -> f32 { 1.0 }
+fn default_ibl_intensity() -> f32 { 1.0 }
+fn default_gradient_top() -> [f32; 4] { [0.35, 0.45, 0.6, 1.0] }
+fn default_gradient_bottom() -> [f32; 4] { [0.05, 0.05, 0.08, 1.0] }
+fn default_tint() -> [f32; 4] { [1.0, 1.0, 1.0, 1.0] }
 
 impl Default for DirectionalLightConfig {
     fn default() -> Self {
@@ -151,6 +173,62 @@ impl DirectionalLightConfig {
     }
 }
 
+/// 环境贴图配置
+///
+/// 环境贴图用于图像光照（IBL）：启动时预计算辐照度贴图、预过滤环境贴图和 BRDF 查找表，
+/// 替代 PBR 着色中的常量环境光。只有使用 PBR 着色的模型受影响。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentConfig {
+    /// 等距柱状投影（2:1）的环境贴图路径，支持 HDR/EXR 和常见的 LDR 格式；未设置时不使用 IBL
+    #[serde(default)]
+    pub map: Option<String>,
+
+    /// IBL 强度，环境漫反射和镜面反射乘以该值
+    #[serde(default = "default_ibl_intensity")]
+    pub ibl_intensity: f32,
+}
+
+impl Default for EnvironmentConfig {
+    fn default() -> Self {
+        Self {
+            map: None,
+            ibl_intensity: default_ibl_intensity(),
+        }
+    }
+}
+
+/// 背景模式
+///
+/// 纯色背景直接使用 [`SceneConfig::clear_color`] 清除；渐变背景在场景几何之前
+/// 用全屏三角形绘制自上而下的两色渐变（目前只有 wgpu 后端支持，其他后端仍按纯色清除）。
+/// 两种模式都只在清除颜色缓冲的帧生效。
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum BackgroundMode {
+    /// 使用清除颜色的纯色背景
+    #[default]
+    Solid,
+    /// 垂直两色渐变（线性空间 RGBA）
+    Gradient {
+        /// 屏幕顶部的颜色
+        #[serde(default = "default_gradient_top")]
+        top: [f32; 4],
+        /// 屏幕底部的颜色
+        #[serde(default = "default_gradient_bottom")]
+        bottom: [f32; 4],
+    },
+}
+
+impl BackgroundMode {
+    /// 渐变的顶部和底部颜色，纯色模式返回默认渐变色（供 GUI 切换模式时使用）
+    pub fn gradient_colors(&self) -> ([f32; 4], [f32; 4]) {
+        match *self {
+            BackgroundMode::Solid => (default_gradient_top(), default_gradient_bottom()),
+            BackgroundMode::Gradient { top, bottom } => (top, bottom),
+        }
+    }
+}
+
 /// 相机配置
 ///
 /// 定义相机的位置、朝向和投影参数。
@@ -159,10 +237,14 @@ pub struct CameraConfig {
     /// 相机变换
     pub transform: Transform,
 
-    /// 视野角度（Field of View，度数）
+    /// 视野角度（Field of View，度数），对应 `fov_axis` 指定的轴
     #[serde(default = "default_fov")]
     pub fov: f32,
 
+    /// FOV 所对应的轴（"vertical" 或 "horizontal"），默认垂直
+    #[serde(default)]
+    pub fov_axis: FovAxis,
+
     /// 近裁剪面距离
     #[serde(default = "default_near_clip")]
     pub near_clip: f32,
@@ -170,12 +252,36 @@ pub struct CameraConfig {
     /// 远裁剪面距离
     #[serde(default = "default_far_clip")]
     pub far_clip: f32,
+
+    /// 加载模型后按场景包围盒收紧近远裁剪面（覆盖 `near_clip` 和 `far_clip`），默认关闭
+    #[serde(default)]
+    pub fit_clip_planes: bool,
+
+    /// 相机移动速度（单位/秒），滚轮在此基础上缩放
+    #[serde(default = "default_move_speed")]
+    pub move_speed: f32,
+
+    /// 按住 Shift 时的速度倍数
+    #[serde(default = "default_boost_multiplier")]
+    pub boost_multiplier: f32,
+
+    /// 移动和旋转限制（锁定高度、俯仰范围、锁定翻滚），默认不限制
+    #[serde(default)]
+    pub constraints: CameraConstraints,
 }
 
 fn default_fov() -> f32 {
     60.0
 }
 
+fn default_move_speed() -> f32 {
+    10.0
+}
+
+fn default_boost_multiplier() -> f32 {
+    4.0
+}
+
 fn default_near_clip() -> f32 {
     0.1
 }
@@ -192,8 +298,13 @@ impl Default for CameraConfig {
                 ..Transform::default()
             },
             fov: 60.0,
+            fov_axis: FovAxis::default(),
             near_clip: 0.1,
             far_clip: 100.0,
+            fit_clip_planes: false,
+            move_speed: default_move_speed(),
+            boost_multiplier: default_boost_multiplier(),
+            constraints: CameraConstraints::default(),
         }
     }
 }
@@ -234,11 +345,14 @@ impl CameraConfig {
     /// 使用 FOV、宽高比和裁剪面创建投影矩阵。
     pub fn projection_matrix(&self, aspect_ratio: f32) -> Matrix4 {
         use std::f32::consts::PI;
-        let fov_rad = self.fov * PI / 180.0;
-        Matrix4::new_perspective(aspect_ratio, fov_rad, self.near_clip, self.far_clip)
+        let fov_y = self.fov_axis.to_vertical(self.fov * PI / 180.0, aspect_ratio);
+        Matrix4::new_perspective(aspect_ratio, fov_y, self.near_clip, self.far_clip)
     }
 }
 
+/// 缩放后的模型尺寸（包围盒最大边长）超出该范围时提示检查导入缩放
+const SUSPICIOUS_MODEL_SIZE: std::ops::RangeInclusive<f32> = 0.01..=1000.0;
+
 /// 模型配置
 ///
 /// 定义模型的文件路径和变换。
@@ -250,6 +364,50 @@ pub struct ModelConfig {
     /// 模型变换
     #[serde(default)]
     pub transform: Transform,
+
+    /// 模型文件的上轴方向（"y" 或 "z"），默认 Y-up 不做转换
+    #[serde(default)]
+    pub up_axis: UpAxis,
+
+    /// 模型文件的坐标系手性（"right" 或 "left"），默认右手系不做转换
+    #[serde(default)]
+    pub handedness: Handedness,
+
+    /// 是否翻转三角形绕序（同时反转法线），用于修正绕序相反导致大片发黑的模型
+    #[serde(default)]
+    pub flip_winding: bool,
+
+    /// 远距离使用的简化级别，未配置时始终使用原始网格
+    #[serde(default)]
+    pub lods: Vec<LodConfig>,
+
+    /// 颜色色调（线性空间 RGBA），在着色器中乘以最终颜色，默认白色不改变颜色
+    #[serde(default = "default_tint")]
+    pub tint: [f32; 4],
+
+    /// 覆盖模型的纹理（基础颜色贴图），同一网格文件可配合不同纹理重复使用（目前只有 wgpu 后端支持）
+    ///
+    /// 未设置时使用模型材质引用的贴图（见 [`base_color_texture`](Self::base_color_texture)）。
+    #[serde(default)]
+    pub texture_path: Option<String>,
+
+    /// 导入缩放，加载时乘以顶点位置（例如厘米单位的模型用 0.01 转换为米）
+    ///
+    /// 省略时使用场景的 [`import_scale`](SceneConfig::import_scale)。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_scale: Option<f32>,
+}
+
+/// LOD 级别配置
+///
+/// 模型到相机的距离不小于 `distance` 时使用按 `ratio` 简化后的网格。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LodConfig {
+    /// 切换到该级别的最小距离
+    pub distance: f32,
+
+    /// 相对原始网格的三角形比例（0-1）
+    pub ratio: f32,
 }
 
 impl Default for ModelConfig {
@@ -257,10 +415,144 @@ impl Default for ModelConfig {
         Self {
             path: "assets/models/sphere.obj".to_string(),
             transform: Transform::default(),
+            up_axis: UpAxis::default(),
+            handedness: Handedness::default(),
+            flip_winding: false,
+            lods: Vec::new(),
+            tint: default_tint(),
+            texture_path: None,
+            import_scale: None,
+        }
+    }
+}
+
+impl ModelConfig {
+    /// 模型文件声明的坐标系
+    pub fn coordinate_system(&self) -> CoordinateSystem {
+        CoordinateSystem::new(self.up_axis, self.handedness)
+    }
+
+    /// 按距离排序后的 LOD 级别
+    pub fn sorted_lods(&self) -> Vec<LodConfig> {
+        let mut lods = self.lods.clone();
+        lods.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        lods
+    }
+
+    /// 导入缩放，未配置或无效（非正数）时为 1.0
+    pub fn import_scale(&self) -> f32 {
+        match self.import_scale {
+            Some(scale) if scale.is_finite() && scale > 0.0 => scale,
+            Some(scale) => {
+                tracing::warn!("Invalid import_scale {} for model {}, using 1.0", scale, self.path);
+                1.0
+            }
+            None => 1.0,
+        }
+    }
+
+    /// 模型的基础颜色贴图
+    ///
+    /// 设置了 `texture_path` 时使用该文件，否则读取模型材质引用的贴图；
+    /// 读取材质失败时记录警告并返回 `None`（按白色纹理绘制）。
+    pub fn base_color_texture(&self) -> Option<TextureSource> {
+        if let Some(path) = &self.texture_path {
+            return Some(TextureSource::File(PathBuf::from(path)));
+        }
+        load_base_color_texture(Path::new(&self.path)).unwrap_or_else(|e| {
+            tracing::warn!("Failed to read the material texture of model {}: {}", self.path, e);
+            None
+        })
+    }
+
+    /// 加载模型网格
+    ///
+    /// 根据扩展名选择加载器，并将网格转换到引擎使用的 Y-up 右手坐标系，
+    /// 按 `import_scale` 缩放，设置了 `flip_winding` 时再翻转绕序。
+    /// `use_cache` 为 `true` 时加载器的输出经过磁盘缓存（见 [`mesh_cache`]）。
+    pub fn load_mesh(&self, use_cache: bool) -> Result<MeshData> {
+        let path = Path::new(&self.path);
+        let mut mesh_data = if use_cache {
+            mesh_cache::load_cached(path, load_mesh)?
+        } else {
+            load_mesh(path)?
+        };
+        mesh_data.convert_coordinate_system(self.coordinate_system(), CoordinateSystem::default());
+        mesh_data.scale(self.import_scale());
+        if self.flip_winding {
+            mesh_data.flip_winding();
+        }
+        self.check_size(&mesh_data);
+        Ok(mesh_data)
+    }
+
+    /// 加载蒙皮网格
+    ///
+    /// 只有 glTF 模型可能带蒙皮，其他格式或没有蒙皮时返回 `Ok(None)`。
+    /// 坐标系转换、导入缩放和绕序翻转与 [`load_mesh`](Self::load_mesh) 相同。
+    pub fn load_skinned_mesh(&self) -> Result<Option<SkinnedMesh>> {
+        let path = Path::new(&self.path);
+        let is_gltf = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("gltf") || e.eq_ignore_ascii_case("glb"));
+        if !is_gltf {
+            return Ok(None);
+        }
+
+        let Some(mut skinned) = GltfLoader::load_skinned_from_file(path)? else {
+            return Ok(None);
+        };
+        skinned.convert_coordinate_system(self.coordinate_system(), CoordinateSystem::default());
+        skinned.scale(self.import_scale());
+        if self.flip_winding {
+            skinned.mesh.flip_winding();
+        }
+        self.check_size(&skinned.mesh);
+        Ok(Some(skinned))
+    }
+
+    /// 缩放后的包围盒过大或过小时提示检查模型单位
+    fn check_size(&self, mesh: &MeshData) {
+        let Some((min, max)) = mesh.bounds() else {
+            return;
+        };
+        let size = (max - min).max();
+        if !SUSPICIOUS_MODEL_SIZE.contains(&size) {
+            tracing::warn!(
+                "Model {} is {} units across after import_scale {}, check the model's units",
+                self.path,
+                size,
+                self.import_scale()
+            );
         }
     }
 }
 
+/// 反序列化模型列表
+///
+/// 同时接受 `[[models]]` 数组和旧格式的单个 `[model]` 表。
+fn deserialize_models<'de, D>(deserializer: D) -> std::result::Result<Vec<ModelConfig>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(ModelConfig),
+        Many(Vec<ModelConfig>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(model) => vec![model],
+        OneOrMany::Many(models) => models,
+    })
+}
+
+fn default_models() -> Vec<ModelConfig> {
+    vec![ModelConfig::default()]
+}
+
 /// 场景配置
 ///
 /// 包含场景中的所有元素配置，包括相机、模型和灯光。
@@ -270,9 +562,9 @@ pub struct SceneConfig {
     #[serde(default)]
     pub camera: CameraConfig,
 
-    /// 模型配置
-    #[serde(default)]
-    pub model: ModelConfig,
+    /// 模型列表（兼容旧的单个 `[model]` 表）
+    #[serde(default = "default_models", alias = "model", deserialize_with = "deserialize_models")]
+    pub models: Vec<ModelConfig>,
 
     /// 平行光配置
     #[serde(default)]
@@ -281,21 +573,106 @@ pub struct SceneConfig {
     /// 背景清空颜色 (RGBA)，范围 0-1
     #[serde(default = "default_clear_color")]
     pub clear_color: [f32; 4],
+
+    /// 每帧是否清除颜色缓冲
+    ///
+    /// 关闭时保留上一次写入交换链图像的内容（用于叠加和累积效果），
+    /// 但创建和尺寸变化后的第一帧仍会清除，避免显示未初始化的内容。深度缓冲始终清除。
+    #[serde(default = "default_clear_color_enabled")]
+    pub clear_color_enabled: bool,
+
+    /// 调试用：清除颜色随时间循环色相
+    ///
+    /// 画面持续变色说明渲染循环仍在运行和呈现，用于排查“窗口卡住”的问题。
+    #[serde(default)]
+    pub clear_color_cycle: bool,
+
+    /// 背景模式，默认使用清除颜色的纯色背景
+    #[serde(default)]
+    pub background: BackgroundMode,
+
+    /// 模板遮罩，设置后场景只在该矩形内可见（默认关闭，目前只有 Vulkan 和 DX12 后端支持）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stencil_mask: Option<StencilMask>,
+
+    /// 环境光颜色（线性空间 RGB），范围 0-1
+    #[serde(default = "default_ambient_color")]
+    pub ambient_color: [f32; 3],
+
+    /// 环境光强度
+    #[serde(default = "default_ambient_intensity")]
+    pub ambient_intensity: f32,
+
+    /// 曝光，片元着色器在输出前把光照结果乘以该值（默认 1.0，即不改变亮度）
+    ///
+    /// 与光照强度无关，用于整体调节画面亮度。目前没有 HDR 渲染目标和色调映射，
+    /// 乘以曝光后超过 1 的分量在写入 sRGB 交换链时被直接截断。
+    #[serde(default = "default_exposure")]
+    pub exposure: f32,
+
+    /// 环境贴图和图像光照配置
+    #[serde(default)]
+    pub environment: EnvironmentConfig,
+
+    /// 模型文件缺失或加载失败时显示的占位几何体
+    ///
+    /// 可选 "triangle"、"cube"、"sphere"、"plane"、"cylinder"、"torus"、"cone"，默认立方体。
+    #[serde(default)]
+    pub fallback_primitive: PrimitiveShape,
+
+    /// 模型的默认导入缩放，没有单独配置 `import_scale` 的模型使用该值（默认 1.0）
+    #[serde(default = "default_import_scale")]
+    pub import_scale: f32,
 }
 
 impl Default for SceneConfig {
     fn default() -> Self {
         Self {
             camera: CameraConfig::default(),
-            model: ModelConfig::default(),
+            models: default_models(),
             light: DirectionalLightConfig::default(),
             clear_color: default_clear_color(),
+            clear_color_enabled: default_clear_color_enabled(),
+            clear_color_cycle: false,
+            background: BackgroundMode::default(),
+            stencil_mask: None,
+            ambient_color: default_ambient_color(),
+            ambient_intensity: default_ambient_intensity(),
+            exposure: default_exposure(),
+            environment: EnvironmentConfig::default(),
+            fallback_primitive: PrimitiveShape::default(),
+            import_scale: default_import_scale(),
         }
     }
 }
 
 
 impl SceneConfig {
+    /// 环境光项（颜色 * 强度），w 分量为曝光
+    ///
+    /// 颜色在线性空间中参与光照计算，由 sRGB 交换链统一完成伽马转换。
+    /// 曝光借用 w 分量传给片元着色器，各后端的 UBO 布局因此不需要改变。
+    pub fn ambient_light(&self) -> [f32; 4] {
+        [
+            self.ambient_color[0] * self.ambient_intensity,
+            self.ambient_color[1] * self.ambient_intensity,
+            self.ambient_color[2] * self.ambient_intensity,
+            self.exposure,
+        ]
+    }
+
+    /// 本帧的清除颜色
+    ///
+    /// 开启 [`clear_color_cycle`](Self::clear_color_cycle) 时按运行时间循环色相（alpha 不变），
+    /// 否则为配置的清除颜色。
+    pub fn clear_color_at(&self, total_time: f32) -> [f32; 4] {
+        if !self.clear_color_cycle {
+            return self.clear_color;
+        }
+        let rgb = color_space::hsv_to_rgb(total_time / CLEAR_COLOR_CYCLE_PERIOD, 0.8, 0.8);
+        [rgb.x, rgb.y, rgb.z, self.clear_color[3]]
+    }
+
     /// 从文件加载场景配置
     ///
     /// # 参数
@@ -315,11 +692,20 @@ impl SceneConfig {
                 e
             ))))?;
 
-        toml::from_str(&contents)
+        let mut config: Self = toml::from_str(&contents)
             .map_err(|e| DistRenderError::Config(ConfigError::ParseError(format!(
                 "Failed to parse scene config: {}",
                 e
-            ))))
+            ))))?;
+        config.apply_default_import_scale();
+        Ok(config)
+    }
+
+    /// 把场景的默认导入缩放填入没有单独配置 `import_scale` 的模型
+    pub fn apply_default_import_scale(&mut self) {
+        for model in &mut self.models {
+            model.import_scale.get_or_insert(self.import_scale);
+        }
     }
 
     /// 从文件加载，如果文件不存在则返回默认配置
@@ -402,8 +788,191 @@ mod tests {
     fn test_default_scene() {
         let scene = SceneConfig::default();
         assert_eq!(scene.camera.fov, 60.0);
-        assert_eq!(scene.model.path, "assets/models/sphere.obj");
+        assert_eq!(scene.models.len(), 1);
+        assert_eq!(scene.models[0].path, "assets/models/sphere.obj");
         assert_eq!(scene.light.intensity, 1.0);
+        assert_eq!(scene.models[0].coordinate_system(), CoordinateSystem::default());
+        assert_eq!(scene.ambient_light(), [0.1, 0.1, 0.1, 1.0]);
+        assert!(scene.clear_color_enabled);
+    }
+
+    #[test]
+    fn test_clear_color_enabled_from_toml() {
+        let scene: SceneConfig = toml::from_str("clear_color_enabled = false").unwrap();
+        assert!(!scene.clear_color_enabled);
+    }
+
+    #[test]
+    fn test_clear_color_cycle() {
+        let mut scene: SceneConfig = toml::from_str("clear_color = [0.1, 0.2, 0.3, 0.5]").unwrap();
+        assert!(!scene.clear_color_cycle);
+        assert_eq!(scene.clear_color_at(1.0), [0.1, 0.2, 0.3, 0.5]);
+
+        scene.clear_color_cycle = true;
+        let start = scene.clear_color_at(0.0);
+        assert_ne!(start, scene.clear_color_at(CLEAR_COLOR_CYCLE_PERIOD / 3.0));
+        assert_eq!(start[3], 0.5);
+        // 一个周期后回到相同颜色
+        let end = scene.clear_color_at(CLEAR_COLOR_CYCLE_PERIOD);
+        assert!(start.iter().zip(end).all(|(a, b)| (a - b).abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_import_scale_shrinks_model_bounds() {
+        let model = ModelConfig::default();
+        let (min, max) = model.load_mesh(false).unwrap().bounds().unwrap();
+
+        let scaled = ModelConfig {
+            import_scale: Some(0.01),
+            ..ModelConfig::default()
+        };
+        let (scaled_min, scaled_max) = scaled.load_mesh(false).unwrap().bounds().unwrap();
+        assert!((scaled_max - scaled_min - (max - min) * 0.01).norm() < 1e-6);
+    }
+
+    #[test]
+    fn test_default_import_scale_applies_to_unset_models() {
+        let toml = "import_scale = 0.01\n[[models]]\npath = \"a.obj\"\n[[models]]\npath = \"b.obj\"\nimport_scale = 2.0\n";
+        let mut scene: SceneConfig = toml::from_str(toml).unwrap();
+        scene.apply_default_import_scale();
+
+        assert_eq!(scene.models[0].import_scale(), 0.01);
+        assert_eq!(scene.models[1].import_scale(), 2.0);
+        assert_eq!(SceneConfig::default().import_scale, 1.0);
+        assert_eq!(ModelConfig::default().import_scale(), 1.0);
+    }
+
+    #[test]
+    fn test_camera_constraints_from_toml() {
+        let scene: SceneConfig = toml::from_str("[camera]\n[camera.transform]\n").unwrap();
+        assert_eq!(scene.camera.constraints, CameraConstraints::default());
+
+        let toml = "[camera]\n[camera.transform]\n[camera.constraints]\nlock_y = true\nlock_pitch_range = [-60.0, 45.0]\n";
+        let scene: SceneConfig = toml::from_str(toml).unwrap();
+        assert!(scene.camera.constraints.lock_y);
+        assert!(!scene.camera.constraints.lock_roll);
+        assert_eq!(scene.camera.constraints.lock_pitch_range, (-60.0, 45.0));
+    }
+
+    #[test]
+    fn test_fallback_primitive_from_toml() {
+        let scene: SceneConfig = toml::from_str("fallback_primitive = \"sphere\"").unwrap();
+        assert_eq!(scene.fallback_primitive, PrimitiveShape::Sphere);
+        assert_eq!(SceneConfig::default().fallback_primitive, PrimitiveShape::Cube);
+        assert!(toml::from_str::<SceneConfig>("fallback_primitive = \"teapot\"").is_err());
+    }
+
+    #[test]
+    fn test_ambient_from_toml() {
+        let scene: SceneConfig = toml::from_str(
+            "ambient_color = [1.0, 0.5, 0.0]\nambient_intensity = 0.5",
+        )
+        .unwrap();
+        assert_eq!(scene.ambient_light(), [0.5, 0.25, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_exposure_from_toml() {
+        let scene: SceneConfig = toml::from_str("exposure = 2.0").unwrap();
+        assert_eq!(scene.exposure, 2.0);
+        assert_eq!(scene.ambient_light()[3], 2.0);
+        assert_eq!(SceneConfig::default().exposure, 1.0);
+    }
+
+    #[test]
+    fn test_environment_from_toml() {
+        let scene: SceneConfig = toml::from_str("[environment]\nmap = \"sky.hdr\"").unwrap();
+        assert_eq!(scene.environment.map.as_deref(), Some("sky.hdr"));
+        assert_eq!(scene.environment.ibl_intensity, 1.0);
+        assert!(SceneConfig::default().environment.map.is_none());
+    }
+
+    #[test]
+    fn test_background_from_toml() {
+        assert_eq!(SceneConfig::default().background, BackgroundMode::Solid);
+
+        let scene: SceneConfig = toml::from_str(
+            "[background]\nmode = \"gradient\"\ntop = [1.0, 1.0, 1.0, 1.0]",
+        )
+        .unwrap();
+        let (top, bottom) = scene.background.gradient_colors();
+        assert_eq!(top, [1.0; 4]);
+        assert_eq!(bottom, default_gradient_bottom());
+        assert!(matches!(scene.background, BackgroundMode::Gradient { .. }));
+    }
+
+    #[test]
+    fn test_stencil_mask_from_toml() {
+        assert_eq!(SceneConfig::default().stencil_mask, None);
+
+        let scene: SceneConfig = toml::from_str("[stencil_mask]\nrect = [0.25, 0.25, 0.5, 0.5]").unwrap();
+        assert_eq!(scene.stencil_mask, Some(StencilMask { rect: [0.25, 0.25, 0.5, 0.5] }));
+    }
+
+    #[test]
+    fn test_model_coordinate_system_from_toml() {
+        let model: ModelConfig = toml::from_str(
+            "path = \"model.fbx\"\nup_axis = \"z\"\nhandedness = \"left\"",
+        )
+        .unwrap();
+        assert_eq!(model.up_axis, UpAxis::Z);
+        assert_eq!(model.handedness, Handedness::Left);
+    }
+
+    #[test]
+    fn test_model_tint_and_texture_from_toml() {
+        let model: ModelConfig = toml::from_str("path = \"a.obj\"").unwrap();
+        assert_eq!(model.tint, [1.0; 4]);
+        assert!(model.texture_path.is_none());
+
+        let model: ModelConfig = toml::from_str(
+            "path = \"a.obj\"\ntint = [1.0, 0.5, 0.5, 1.0]\ntexture_path = \"assets/textures/brick.png\"",
+        )
+        .unwrap();
+        assert_eq!(model.tint, [1.0, 0.5, 0.5, 1.0]);
+        assert_eq!(model.texture_path.as_deref(), Some("assets/textures/brick.png"));
+    }
+
+    #[test]
+    fn test_models_from_toml() {
+        // 旧格式：单个 [model] 表
+        let scene: SceneConfig = toml::from_str(
+            "[model]\npath = \"a.obj\"\n[model.transform]\nposition = [1.0, 0.0, 0.0]",
+        )
+        .unwrap();
+        assert_eq!(scene.models.len(), 1);
+        assert_eq!(scene.models[0].path, "a.obj");
+        assert_eq!(scene.models[0].transform.position, [1.0, 0.0, 0.0]);
+
+        // 新格式：[[models]] 数组
+        let scene: SceneConfig = toml::from_str(
+            "[[models]]\npath = \"a.obj\"\n[[models]]\npath = \"b.obj\"\nup_axis = \"z\"\nflip_winding = true",
+        )
+        .unwrap();
+        assert_eq!(scene.models.len(), 2);
+        assert_eq!(scene.models[1].path, "b.obj");
+        assert_eq!(scene.models[1].up_axis, UpAxis::Z);
+        assert!(!scene.models[0].flip_winding);
+        assert!(scene.models[1].flip_winding);
+        assert!(scene.models[0].lods.is_empty());
+
+        // 序列化后可以重新解析
+        let text = toml::to_string(&scene).unwrap();
+        let reparsed: SceneConfig = toml::from_str(&text).unwrap();
+        assert_eq!(reparsed.models.len(), 2);
+    }
+
+    #[test]
+    fn test_model_lods_from_toml() {
+        let scene: SceneConfig = toml::from_str(
+            "[[models]]\npath = \"a.obj\"\n[[models.lods]]\ndistance = 30.0\nratio = 0.1\n[[models.lods]]\ndistance = 10.0\nratio = 0.5",
+        )
+        .unwrap();
+
+        let lods = scene.models[0].sorted_lods();
+        assert_eq!(lods.len(), 2);
+        assert_eq!(lods[0], LodConfig { distance: 10.0, ratio: 0.5 });
+        assert_eq!(lods[1].distance, 30.0);
     }
 }
 
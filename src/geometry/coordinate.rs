@@ -0,0 +1,114 @@
+/// 坐标系定义模块
+///
+/// 不同的模型格式在上轴和手性上并不统一（glTF 为 Y-up 右手系，
+/// 许多 FBX 为 Z-up）。引擎内部统一使用 Y-up 右手坐标系，
+/// 导入时可根据模型声明的坐标系进行转换。
+use serde::{Deserialize, Serialize};
+
+/// 上轴方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpAxis {
+    /// Y 轴朝上（引擎默认）
+    #[default]
+    Y,
+    /// Z 轴朝上
+    Z,
+}
+
+/// 坐标系手性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Handedness {
+    /// 右手系（引擎默认）
+    #[default]
+    Right,
+    /// 左手系
+    Left,
+}
+
+/// 坐标系描述
+///
+/// 由上轴和手性组成，默认值即引擎使用的 Y-up 右手系。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CoordinateSystem {
+    /// 上轴方向
+    pub up_axis: UpAxis,
+
+    /// 手性
+    pub handedness: Handedness,
+}
+
+impl CoordinateSystem {
+    /// 创建坐标系描述
+    #[inline]
+    pub fn new(up_axis: UpAxis, handedness: Handedness) -> Self {
+        Self { up_axis, handedness }
+    }
+
+    /// 判断从 `self` 转换到 `to` 时是否需要翻转三角形绕序
+    #[inline]
+    pub fn flips_winding(&self, to: &CoordinateSystem) -> bool {
+        self.handedness != to.handedness
+    }
+
+    /// 将向量从当前坐标系转换到目标坐标系
+    ///
+    /// 位置、法线和切线均可使用此函数转换（转换是正交的）。
+    pub fn convert_vector(&self, to: &CoordinateSystem, v: [f32; 3]) -> [f32; 3] {
+        to.decanonicalize(self.canonicalize(v))
+    }
+
+    /// 转换到 Y-up 右手系
+    fn canonicalize(&self, v: [f32; 3]) -> [f32; 3] {
+        // Z-up -> Y-up：绕 X 轴旋转 -90 度，(x, y, z) -> (x, z, -y)
+        let v = match self.up_axis {
+            UpAxis::Y => v,
+            UpAxis::Z => [v[0], v[2], -v[1]],
+        };
+        match self.handedness {
+            Handedness::Right => v,
+            Handedness::Left => [v[0], v[1], -v[2]],
+        }
+    }
+
+    /// 从 Y-up 右手系转换到当前坐标系
+    fn decanonicalize(&self, v: [f32; 3]) -> [f32; 3] {
+        let v = match self.handedness {
+            Handedness::Right => v,
+            Handedness::Left => [v[0], v[1], -v[2]],
+        };
+        match self.up_axis {
+            UpAxis::Y => v,
+            UpAxis::Z => [v[0], -v[2], v[1]],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_z_up_to_y_up() {
+        let from = CoordinateSystem::new(UpAxis::Z, Handedness::Right);
+        let to = CoordinateSystem::default();
+        assert_eq!(from.convert_vector(&to, [0.0, 0.0, 1.0]), [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let a = CoordinateSystem::new(UpAxis::Z, Handedness::Left);
+        let b = CoordinateSystem::default();
+        let v = [1.0, 2.0, 3.0];
+        assert_eq!(b.convert_vector(&a, a.convert_vector(&b, v)), v);
+    }
+
+    #[test]
+    fn test_flips_winding() {
+        let rh = CoordinateSystem::default();
+        let lh = CoordinateSystem::new(UpAxis::Y, Handedness::Left);
+        assert!(rh.flips_winding(&lh));
+        assert!(!rh.flips_winding(&rh));
+    }
+}
@@ -1,25 +1,39 @@
 /// FBX 文件加载器
 ///
-/// 使用 russimp (Assimp) 加载 Autodesk FBX 格式的3D模型。
-/// 支持复杂的场景层次、多网格、多材质等高级特性。
+/// 最小的 ASCII FBX 解析器，从文件或内存中读取 `Vertices` / `PolygonVertexIndex` 几何数据。
+/// 不读取材质、UV 和场景层次；二进制 FBX 返回 `UnsupportedFormat` 错误。
 
 use super::MeshLoader;
-use crate::core::error::{MeshLoadError, Result};
-use crate::geometry::mesh::MeshData;
+use crate::core::error::{DistRenderError, MeshLoadError, Result};
+use crate::geometry::mesh::{MeshData, Subset};
+use crate::geometry::vertex::Vertex;
+use crate::math::geometry::reconstruct_normals;
 use std::path::Path;
 
+/// 二进制 FBX 文件头魔数
+const FBX_BINARY_MAGIC: &[u8] = b"Kaydara FBX Binary  \0";
+
+/// ASCII FBX 文件必然包含的头部节点
+const FBX_ASCII_HEADER: &str = "FBXHeaderExtension";
+
+/// FBX 数据的编码格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FbxFormat {
+    Binary,
+    Ascii,
+}
+
 /// FBX 格式加载器
 ///
 /// 实现 `MeshLoader` trait，提供 FBX 文件的加载功能。
-/// 使用 Assimp 库通过 russimp crate 进行加载。
+/// 只解析 ASCII FBX（FBX 6.x 和 7.x 的数组写法）。
 ///
 /// # 特性
 ///
-/// - 支持 FBX 2011 及以上版本
-/// - 递归遍历场景层次
-/// - 自动三角化
-/// - 自动生成法线和切线（通过 Assimp 后处理）
-/// - 支持多网格和多材质
+/// - 每组 `Vertices` + `PolygonVertexIndex` 生成一个子网格，不区分材质
+/// - 多边形按扇形三角化
+/// - 法线按控制点重建，UV 和切线为零
+/// - 支持从内存加载；二进制 FBX（`Kaydara FBX Binary` 文件头）和无法识别的数据返回 `UnsupportedFormat` 错误
 ///
 /// # 使用示例
 ///
@@ -33,21 +47,163 @@ use std::path::Path;
 /// ```
 pub struct FbxLoader;
 
+impl FbxLoader {
+    /// 根据文件头判断 FBX 编码格式
+    ///
+    /// 无法识别时返回 `None`。
+    fn detect_format(data: &[u8]) -> Option<FbxFormat> {
+        if data.starts_with(FBX_BINARY_MAGIC) {
+            return Some(FbxFormat::Binary);
+        }
+
+        // ASCII FBX 以 `;` 注释开头，头部节点通常位于文件前部
+        let head = &data[..data.len().min(4096)];
+        let text = String::from_utf8_lossy(head);
+        if text.contains(FBX_ASCII_HEADER) {
+            Some(FbxFormat::Ascii)
+        } else {
+            None
+        }
+    }
+
+    /// 解析 ASCII FBX 中的网格几何
+    ///
+    /// 每一组 `Vertices` + `PolygonVertexIndex` 生成一个子网格，
+    /// 多边形按扇形三角化。
+    fn parse_ascii(text: &str) -> Result<MeshData> {
+        let mut mesh_data = MeshData::new();
+
+        let vertex_keys: Vec<usize> = find_keys(text, "Vertices:");
+        for (mesh_idx, &key_pos) in vertex_keys.iter().enumerate() {
+            let vertices_body = array_body(&text[key_pos + "Vertices:".len()..]);
+
+            // 对应的索引数组必须在下一个 Vertices 之前（排除 Shape 等非网格节点）
+            let section_end = vertex_keys
+                .get(mesh_idx + 1)
+                .copied()
+                .unwrap_or(text.len());
+            let section = &text[key_pos..section_end];
+            let index_pos = match find_keys(section, "PolygonVertexIndex:").first() {
+                Some(&pos) => pos,
+                None => continue,
+            };
+            let index_body = array_body(&section[index_pos + "PolygonVertexIndex:".len()..]);
+
+            let positions = parse_numbers::<f32>(vertices_body, "Vertices")?;
+            let polygon_indices = parse_numbers::<i32>(index_body, "PolygonVertexIndex")?;
+
+            if positions.len() % 3 != 0 {
+                return Err(MeshLoadError::InvalidGeometry(format!(
+                    "顶点位置数据不完整: {} 个浮点数",
+                    positions.len()
+                ))
+                .into());
+            }
+
+            let vertex_count = positions.len() / 3;
+            let vertex_start = mesh_data.vertices.len() as u32;
+            let face_start = mesh_data.triangle_count() as u32;
+
+            for p in positions.chunks_exact(3) {
+                mesh_data.vertices.push(Vertex::new(
+                    [p[0], p[1], p[2]],
+                    [0.0, 0.0, 0.0],
+                    [0.0, 0.0],
+                    [0.0, 0.0, 0.0],
+                ));
+            }
+
+            // 负数索引标记多边形结束，真实索引为 -index - 1
+            let mut polygon: Vec<u32> = Vec::new();
+            let mut face_count = 0u32;
+            for &raw in &polygon_indices {
+                let (index, is_last) = if raw < 0 { (!raw, true) } else { (raw, false) };
+                if index as usize >= vertex_count {
+                    return Err(MeshLoadError::InvalidGeometry(format!(
+                        "多边形索引越界: {} (顶点数 {})",
+                        index, vertex_count
+                    ))
+                    .into());
+                }
+                polygon.push(vertex_start + index as u32);
+
+                if is_last {
+                    for i in 1..polygon.len().saturating_sub(1) {
+                        mesh_data.indices.push(polygon[0]);
+                        mesh_data.indices.push(polygon[i]);
+                        mesh_data.indices.push(polygon[i + 1]);
+                        face_count += 1;
+                    }
+                    polygon.clear();
+                }
+            }
+
+            mesh_data.subsets.push(Subset::new(
+                mesh_data.subsets.len() as u32,
+                vertex_start,
+                vertex_count as u32,
+                face_start,
+                face_count,
+            ));
+        }
+
+        if mesh_data.subsets.is_empty() {
+            return Err(MeshLoadError::ValidationError("FBX 数据不包含任何网格".to_string()).into());
+        }
+
+        // FBX 法线通常按多边形顶点存储，这里统一按控制点重建
+        reconstruct_normals(&mut mesh_data.vertices, &mesh_data.indices);
+
+        mesh_data
+            .validate()
+            .map_err(MeshLoadError::ValidationError)?;
+
+        Ok(mesh_data)
+    }
+}
+
 impl MeshLoader for FbxLoader {
     fn load_from_file(path: &Path) -> Result<MeshData> {
-        // TODO: 将在 Phase 4 实现
-        // 这是一个占位符实现
         if !path.exists() {
             return Err(MeshLoadError::FileNotFound(path.to_path_buf()).into());
         }
 
-        // 返回空网格数据作为占位
-        Ok(MeshData::new())
+        let data = std::fs::read(path)?;
+        let mut mesh_data = Self::load_from_memory(&data)?;
+        mesh_data.name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_string());
+
+        Ok(mesh_data)
     }
 
-    fn load_from_memory(_data: &[u8]) -> Result<MeshData> {
-        // TODO: 将在 Phase 4 实现
-        Ok(MeshData::new())
+    fn load_from_memory(data: &[u8]) -> Result<MeshData> {
+        match Self::detect_format(data) {
+            Some(FbxFormat::Ascii) => {
+                let text = std::str::from_utf8(data).map_err(|e| {
+                    MeshLoadError::ParseError(format!("ASCII FBX 不是有效的 UTF-8: {}", e))
+                })?;
+                let mesh_data = Self::parse_ascii(text)?;
+
+                tracing::info!(
+                    "成功从内存加载 FBX: {} 个顶点, {} 个三角形, {} 个子网格",
+                    mesh_data.vertex_count(),
+                    mesh_data.triangle_count(),
+                    mesh_data.subsets.len()
+                );
+
+                Ok(mesh_data)
+            }
+            Some(FbxFormat::Binary) => Err(MeshLoadError::UnsupportedFormat(
+                "暂不支持二进制 FBX，请导出为 ASCII FBX".to_string(),
+            )
+            .into()),
+            None => Err(MeshLoadError::UnsupportedFormat(
+                "无法识别 FBX 数据格式（缺少二进制文件头或 FBXHeaderExtension）".to_string(),
+            )
+            .into()),
+        }
     }
 
     fn supported_extensions() -> &'static [&'static str] {
@@ -55,10 +211,72 @@ impl MeshLoader for FbxLoader {
     }
 }
 
+/// 查找位于行首（忽略缩进）的节点名位置
+fn find_keys(text: &str, key: &str) -> Vec<usize> {
+    text.match_indices(key)
+        .filter(|(pos, _)| {
+            text[..*pos]
+                .chars()
+                .next_back()
+                .is_none_or(|c| c.is_whitespace())
+        })
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+/// 截取节点名之后的数组内容
+///
+/// 支持 FBX 7.x 的 `*N { a: ... }` 写法和 FBX 6.x 的逗号续行写法。
+fn array_body(rest: &str) -> &str {
+    let trimmed = rest.trim_start();
+    if trimmed.starts_with('*') {
+        let body = match trimmed.find('{') {
+            Some(open) => &trimmed[open + 1..],
+            None => return "",
+        };
+        let body = body.trim_start();
+        let body = body.strip_prefix("a:").unwrap_or(body);
+        let end = body.find('}').unwrap_or(body.len());
+        &body[..end]
+    } else {
+        let mut end = 0;
+        let mut lines = trimmed.split_inclusive('\n').peekable();
+        while let Some(line) = lines.next() {
+            end += line.len();
+            let continues = line.trim_end().ends_with(',')
+                || lines
+                    .peek()
+                    .is_some_and(|next| next.trim_start().starts_with(','));
+            if !continues {
+                break;
+            }
+        }
+        &trimmed[..end]
+    }
+}
+
+/// 解析逗号分隔的数值列表
+fn parse_numbers<T: std::str::FromStr>(body: &str, key: &str) -> Result<Vec<T>> {
+    body.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<T>().map_err(|_| {
+                DistRenderError::from(MeshLoadError::ParseError(format!(
+                    "{} 中存在无效数值: {}",
+                    key, s
+                )))
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const QUAD_FBX: &[u8] = include_bytes!("../../../assets/models/quad.fbx");
+
     #[test]
     fn test_supported_extensions() {
         let exts = FbxLoader::supported_extensions();
@@ -70,4 +288,34 @@ mod tests {
         let result = FbxLoader::load_from_file(Path::new("nonexistent.fbx"));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_load_from_memory() {
+        let mesh = FbxLoader::load_from_memory(QUAD_FBX).unwrap();
+        assert_eq!(mesh.vertex_count(), 4);
+        assert_eq!(mesh.triangle_count(), 2);
+        assert_eq!(mesh.subsets.len(), 1);
+        assert!(mesh.validate().is_ok());
+
+        // 四边形位于 XY 平面，重建的法线应沿 Z 轴
+        for v in &mesh.vertices {
+            assert!((v.normal[2].abs() - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_load_from_memory_unknown_format() {
+        let result = FbxLoader::load_from_memory(b"not an fbx file");
+        assert!(matches!(
+            result,
+            Err(DistRenderError::MeshLoading(MeshLoadError::UnsupportedFormat(_)))
+        ));
+    }
+
+    #[test]
+    fn test_load_from_memory_binary_unsupported() {
+        let mut data = FBX_BINARY_MAGIC.to_vec();
+        data.extend_from_slice(&[0x1A, 0x00, 0xE8, 0x1C, 0x00, 0x00]);
+        assert!(FbxLoader::load_from_memory(&data).is_err());
+    }
 }
@@ -0,0 +1,642 @@
+/// glTF 文件加载器
+///
+/// 使用 gltf crate 加载 glTF 2.0 模型（`.gltf` 文本格式和 `.glb` 二进制格式）。
+/// 静态加载时遍历默认场景的节点层级，把各网格按节点的全局变换合并，每个图元一个子网格，
+/// 子网格 ID 为图元的材质序号；
+/// 蒙皮加载时读取第一个带蒙皮的网格节点的关节、逆绑定矩阵和第一个动画片段。
+use super::MeshLoader;
+use crate::core::error::{MeshLoadError, Result};
+use crate::geometry::material::{Material, TextureSource};
+use crate::geometry::mesh::{MeshData, Subset};
+use crate::geometry::skinning::{
+    AnimationChannel, AnimationClip, ChannelValues, Joint, JointPose, Skeleton, SkinnedMesh, JOINTS_PER_VERTEX,
+};
+use crate::geometry::vertex::Vertex;
+use crate::math::geometry::{compute_tangent_space, reconstruct_normals};
+use crate::math::{Matrix4, Quaternion, Vector3};
+use gltf::animation::util::ReadOutputs;
+use gltf::animation::Interpolation;
+use gltf::buffer::Data as BufferData;
+use gltf::{Document, Node};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// glTF 格式加载器
+///
+/// 实现 `MeshLoader` trait 加载静态网格，并通过
+/// [`load_skinned_from_file`](GltfLoader::load_skinned_from_file) 加载蒙皮网格和骨骼动画。
+///
+/// # 特性
+///
+/// - 支持 `.gltf`（外部或 base64 内嵌缓冲）和 `.glb`
+/// - 只读取三角形图元，其他拓扑跳过
+/// - 缺少法线时重建法线，有 UV 但缺少切线时计算切线空间
+/// - 材质只读取金属-粗糙度的系数；基础颜色贴图通过
+///   [`base_color_texture`](GltfLoader::base_color_texture) 单独解析，其他纹理忽略
+/// - 动画只支持线性插值：阶跃插值按线性处理，三次样条只取关键帧值
+///
+/// # 使用示例
+///
+/// ```rust,no_run
+/// use distrender::geometry::loaders::{MeshLoader, GltfLoader};
+/// use std::path::Path;
+///
+/// let mesh = GltfLoader::load_from_file(Path::new("model.glb"))?;
+/// println!("加载了 {} 个顶点", mesh.vertex_count());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct GltfLoader;
+
+/// 解析后的文档和缓冲数据
+struct GltfData {
+    document: Document,
+    buffers: Vec<BufferData>,
+    name: String,
+}
+
+impl GltfLoader {
+    /// 从文件加载蒙皮网格
+    ///
+    /// 文件中没有带蒙皮的网格节点时返回 `Ok(None)`。
+    pub fn load_skinned_from_file(path: &Path) -> Result<Option<SkinnedMesh>> {
+        Self::build_skinned(&Self::open(path)?)
+    }
+
+    /// 从内存加载蒙皮网格（`.glb` 或缓冲全部内嵌的 `.gltf`）
+    pub fn load_skinned_from_memory(data: &[u8]) -> Result<Option<SkinnedMesh>> {
+        Self::build_skinned(&Self::parse(data, None, "Unnamed")?)
+    }
+
+    /// 模型的基础颜色贴图
+    ///
+    /// 取第一个带基础颜色贴图的材质。外部图片返回相对模型文件所在目录的路径，
+    /// data URI 和 GLB 二进制块中的图片在此解码。没有贴图时返回 `Ok(None)`。
+    pub fn base_color_texture(path: &Path) -> Result<Option<TextureSource>> {
+        let data = Self::open(path)?;
+        let Some(info) = data
+            .document
+            .materials()
+            .find_map(|material| material.pbr_metallic_roughness().base_color_texture())
+        else {
+            return Ok(None);
+        };
+
+        let image = info.texture().source();
+        let base = path.parent().unwrap_or(Path::new(""));
+        if let gltf::image::Source::Uri { uri, .. } = image.source() {
+            if !uri.starts_with("data:") {
+                return Ok(Some(TextureSource::File(base.join(uri))));
+            }
+        }
+
+        let decoded = gltf::image::Data::from_source(image.source(), Some(base), &data.buffers)
+            .map_err(|e| MeshLoadError::ParseError(format!("glTF 内嵌图片解码失败: {}", e)))?;
+        let format = decoded.format;
+        let rgba = to_rgba8(decoded)
+            .ok_or_else(|| MeshLoadError::UnsupportedFormat(format!("glTF 内嵌图片格式 {:?}", format)))?;
+        Ok(Some(TextureSource::Embedded {
+            key: format!("{}#image{}", path.display(), image.index()),
+            image: rgba,
+        }))
+    }
+
+    /// 打开文件并读取缓冲（外部缓冲相对文件所在目录解析）
+    fn open(path: &Path) -> Result<GltfData> {
+        if !path.exists() {
+            return Err(MeshLoadError::FileNotFound(path.to_path_buf()).into());
+        }
+
+        let data = std::fs::read(path)?;
+        let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("Unnamed");
+        Self::parse(&data, path.parent(), name)
+    }
+
+    fn parse(data: &[u8], base: Option<&Path>, name: &str) -> Result<GltfData> {
+        let gltf = gltf::Gltf::from_slice(data)
+            .map_err(|e| MeshLoadError::ParseError(format!("glTF 解析失败: {}", e)))?;
+        let buffers = gltf::import_buffers(&gltf.document, base, gltf.blob)
+            .map_err(|e| MeshLoadError::ParseError(format!("glTF 缓冲读取失败: {}", e)))?;
+
+        Ok(GltfData {
+            document: gltf.document,
+            buffers,
+            name: name.to_string(),
+        })
+    }
+
+    /// 默认场景（未指定时为第一个场景）的所有网格，按节点全局变换合并
+    fn build_mesh(data: &GltfData) -> Result<MeshData> {
+        let mut mesh_data = MeshData::with_name(data.name.as_str());
+        let scene = data
+            .document
+            .default_scene()
+            .or_else(|| data.document.scenes().next())
+            .ok_or_else(|| MeshLoadError::ValidationError("glTF 文件不包含任何场景".to_string()))?;
+
+        let mut stack: Vec<(Node, Matrix4)> = scene.nodes().map(|node| (node, Matrix4::identity())).collect();
+        while let Some((node, parent)) = stack.pop() {
+            let world = parent * Matrix4::from(node.transform().matrix());
+            if let Some(mesh) = node.mesh() {
+                let (node_mesh, _) = read_mesh(&mesh, &data.buffers, data.document.materials().len(), false)?;
+                mesh_data.merge(&node_mesh, &world);
+            }
+            stack.extend(node.children().map(|child| (child, world)));
+        }
+        mesh_data.materials = read_materials(&data.document);
+
+        if mesh_data.vertices.is_empty() {
+            return Err(MeshLoadError::ValidationError("glTF 场景不包含任何三角形网格".to_string()).into());
+        }
+
+        mesh_data.validate().map_err(MeshLoadError::ValidationError)?;
+        tracing::info!(
+            "成功加载 glTF 文件: {} 个顶点, {} 个三角形, {} 个子网格",
+            mesh_data.vertex_count(),
+            mesh_data.triangle_count(),
+            mesh_data.subsets.len()
+        );
+
+        Ok(mesh_data)
+    }
+
+    /// 第一个带蒙皮的网格节点
+    fn build_skinned(data: &GltfData) -> Result<Option<SkinnedMesh>> {
+        let Some((mesh, skin)) = data
+            .document
+            .nodes()
+            .find_map(|node| node.mesh().zip(node.skin()))
+        else {
+            return Ok(None);
+        };
+
+        let (mut mesh_data, skin_weights) = read_mesh(&mesh, &data.buffers, data.document.materials().len(), true)?;
+        mesh_data.name = Some(data.name.clone());
+        mesh_data.materials = read_materials(&data.document);
+        let (joint_indices, joint_weights) = skin_weights.into_iter().unzip();
+
+        let parents = node_parents(&data.document);
+        let joint_nodes: Vec<Node> = skin.joints().collect();
+        let joint_of_node: HashMap<usize, usize> =
+            joint_nodes.iter().enumerate().map(|(joint, node)| (node.index(), joint)).collect();
+
+        let inverse_bind_matrices: Vec<Matrix4> = skin
+            .reader(|buffer| data.buffers.get(buffer.index()).map(|d| d.0.as_slice()))
+            .read_inverse_bind_matrices()
+            .map(|matrices| matrices.map(Matrix4::from).collect())
+            .unwrap_or_default();
+
+        let joints: Vec<Joint> = joint_nodes
+            .iter()
+            .enumerate()
+            .map(|(joint, node)| {
+                let (translation, rotation, scale) = node.transform().decomposed();
+                Joint {
+                    name: node.name().unwrap_or_default().to_string(),
+                    parent: ancestors(node.index(), &parents).find_map(|n| joint_of_node.get(&n).copied()),
+                    inverse_bind_matrix: inverse_bind_matrices.get(joint).copied().unwrap_or_else(Matrix4::identity),
+                    rest_pose: JointPose {
+                        translation: Vector3::from(translation),
+                        rotation: quaternion_from_xyzw(rotation),
+                        scale: Vector3::from(scale),
+                    },
+                }
+            })
+            .collect();
+
+        // 根关节之上非关节节点的全局变换
+        let root_transform = joints
+            .iter()
+            .zip(&joint_nodes)
+            .find(|(joint, _)| joint.parent.is_none())
+            .and_then(|(_, node)| parents.get(&node.index()).copied())
+            .map(|parent| world_transform(&data.document, parent, &parents))
+            .unwrap_or_else(Matrix4::identity);
+
+        let animation = data.document.animations().next().map(|animation| {
+            let channels = animation
+                .channels()
+                .filter_map(|channel| {
+                    let joint = *joint_of_node.get(&channel.target().node().index())?;
+                    let reader = channel.reader(|buffer| data.buffers.get(buffer.index()).map(|d| d.0.as_slice()));
+                    let times: Vec<f32> = reader.read_inputs()?.collect();
+                    // 三次样条的每个关键帧依次存放入切线、值、出切线
+                    let (skip, step) = match channel.sampler().interpolation() {
+                        Interpolation::CubicSpline => (1, 3),
+                        Interpolation::Linear | Interpolation::Step => (0, 1),
+                    };
+                    let values = match reader.read_outputs()? {
+                        ReadOutputs::Translations(values) => {
+                            ChannelValues::Translation(values.skip(skip).step_by(step).map(Vector3::from).collect())
+                        }
+                        ReadOutputs::Rotations(values) => ChannelValues::Rotation(
+                            values.into_f32().skip(skip).step_by(step).map(quaternion_from_xyzw).collect(),
+                        ),
+                        ReadOutputs::Scales(values) => {
+                            ChannelValues::Scale(values.skip(skip).step_by(step).map(Vector3::from).collect())
+                        }
+                        ReadOutputs::MorphTargetWeights(_) => return None,
+                    };
+                    Some(AnimationChannel { joint, times, values })
+                })
+                .collect();
+            AnimationClip::new(animation.name().unwrap_or_default(), channels)
+        });
+
+        let mut skinned = SkinnedMesh {
+            mesh: mesh_data,
+            joint_indices,
+            joint_weights,
+            skeleton: Skeleton { joints, root_transform },
+            animation,
+        };
+        skinned.normalize_weights();
+        if let Some(bad) = skinned
+            .joint_indices
+            .iter()
+            .flatten()
+            .find(|&&joint| joint as usize >= skinned.skeleton.joints.len())
+        {
+            return Err(MeshLoadError::ValidationError(format!("顶点引用了不存在的关节: {}", bad)).into());
+        }
+
+        skinned.mesh.validate().map_err(MeshLoadError::ValidationError)?;
+        tracing::info!(
+            "成功加载 glTF 蒙皮网格: {} 个顶点, {} 个关节, 动画 {}",
+            skinned.mesh.vertex_count(),
+            skinned.skeleton.joints.len(),
+            skinned.animation.as_ref().map_or("无".to_string(), |clip| format!("{:.2} 秒", clip.duration))
+        );
+
+        Ok(Some(skinned))
+    }
+}
+
+impl MeshLoader for GltfLoader {
+    fn load_from_file(path: &Path) -> Result<MeshData> {
+        Self::build_mesh(&Self::open(path)?)
+    }
+
+    fn load_from_memory(data: &[u8]) -> Result<MeshData> {
+        Self::build_mesh(&Self::parse(data, None, "Unnamed")?)
+    }
+
+    fn supported_extensions() -> &'static [&'static str] {
+        &["gltf", "glb"]
+    }
+}
+
+/// 每个顶点的关节索引和权重
+type SkinWeights = ([u16; JOINTS_PER_VERTEX], [f32; JOINTS_PER_VERTEX]);
+
+/// 读取网格的所有三角形图元，每个图元一个子网格
+///
+/// 子网格 ID 为图元的材质序号，未指定材质的图元使用 [`read_materials`] 末尾的默认材质。
+/// `skinned` 为 `true` 时同时读取第一组关节索引和权重，缺少时返回错误。
+fn read_mesh(
+    mesh: &gltf::Mesh,
+    buffers: &[BufferData],
+    default_material: usize,
+    skinned: bool,
+) -> Result<(MeshData, Vec<SkinWeights>)> {
+    let mut mesh_data = MeshData::new();
+    let mut skin_weights = Vec::new();
+    let mut has_normals = true;
+    let mut has_tangents = true;
+    let mut has_texcoords = false;
+
+    for primitive in mesh.primitives() {
+        if primitive.mode() != gltf::mesh::Mode::Triangles {
+            tracing::warn!("跳过非三角形图元: {:?}", primitive.mode());
+            continue;
+        }
+
+        let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|d| d.0.as_slice()));
+        let Some(positions) = reader.read_positions() else {
+            continue;
+        };
+        let positions: Vec<[f32; 3]> = positions.collect();
+        let vertex_start = mesh_data.vertices.len() as u32;
+        let face_start = mesh_data.triangle_count() as u32;
+
+        let normals: Option<Vec<[f32; 3]>> = reader.read_normals().map(Iterator::collect);
+        let texcoords: Option<Vec<[f32; 2]>> = reader.read_tex_coords(0).map(|t| t.into_f32().collect());
+        let tangents: Option<Vec<[f32; 4]>> = reader.read_tangents().map(Iterator::collect);
+        has_normals &= normals.is_some();
+        has_tangents &= tangents.is_some();
+        has_texcoords |= texcoords.is_some();
+
+        for (i, &position) in positions.iter().enumerate() {
+            let normal = normals.as_ref().and_then(|n| n.get(i)).copied().unwrap_or_default();
+            let texcoord = texcoords.as_ref().and_then(|t| t.get(i)).copied().unwrap_or_default();
+            let tangent = tangents.as_ref().and_then(|t| t.get(i)).map_or([0.0; 3], |t| [t[0], t[1], t[2]]);
+            mesh_data.vertices.push(Vertex::new(position, normal, texcoord, tangent));
+        }
+
+        let indices: Vec<u32> = match reader.read_indices() {
+            Some(indices) => indices.into_u32().collect(),
+            None => (0..positions.len() as u32).collect(),
+        };
+        let face_count = indices.len() / 3;
+        mesh_data
+            .indices
+            .extend(indices[..face_count * 3].iter().map(|&index| vertex_start + index));
+
+        if skinned {
+            let (Some(joints), Some(weights)) = (reader.read_joints(0), reader.read_weights(0)) else {
+                return Err(MeshLoadError::InvalidGeometry("蒙皮网格缺少 JOINTS_0/WEIGHTS_0 属性".to_string()).into());
+            };
+            let start = skin_weights.len();
+            skin_weights.extend(joints.into_u16().zip(weights.into_f32()));
+            skin_weights.resize(start + positions.len(), ([0; JOINTS_PER_VERTEX], [0.0; JOINTS_PER_VERTEX]));
+        }
+
+        let material = primitive.material().index().unwrap_or(default_material);
+        mesh_data.subsets.push(Subset::new(
+            material as u32,
+            vertex_start,
+            positions.len() as u32,
+            face_start,
+            face_count as u32,
+        ));
+    }
+
+    if !has_normals {
+        tracing::info!("glTF 网格缺少法线数据，正在重建...");
+        reconstruct_normals(&mut mesh_data.vertices, &mesh_data.indices);
+    }
+    if has_texcoords && !has_tangents {
+        compute_tangent_space(&mut mesh_data.vertices, &mesh_data.indices);
+    }
+
+    Ok((mesh_data, skin_weights))
+}
+
+/// 文档中的所有材质，末尾追加一个默认材质供未指定材质的图元使用
+fn read_materials(document: &Document) -> Vec<Material> {
+    document
+        .materials()
+        .map(|material| {
+            let pbr = material.pbr_metallic_roughness();
+            Material {
+                name: material.name().map(str::to_string),
+                base_color: pbr.base_color_factor(),
+                metallic: pbr.metallic_factor(),
+                roughness: pbr.roughness_factor(),
+                transparent: material.alpha_mode() == gltf::material::AlphaMode::Blend,
+            }
+        })
+        .chain(std::iter::once(Material::default()))
+        .collect()
+}
+
+/// 把 gltf 解码的图片转换为 RGBA8（灰度扩展到 RGB，16 位通道取高 8 位），不支持浮点格式
+fn to_rgba8(data: gltf::image::Data) -> Option<image::RgbaImage> {
+    use gltf::image::Format;
+
+    let (channels, channel_size) = match data.format {
+        Format::R8 => (1, 1),
+        Format::R8G8 => (2, 1),
+        Format::R8G8B8 => (3, 1),
+        Format::R8G8B8A8 => (4, 1),
+        Format::R16 => (1, 2),
+        Format::R16G16 => (2, 2),
+        Format::R16G16B16 => (3, 2),
+        Format::R16G16B16A16 => (4, 2),
+        _ => return None,
+    };
+    let pixels = data
+        .pixels
+        .chunks_exact(channels * channel_size)
+        .flat_map(|texel| {
+            let channel = |i: usize| match channel_size {
+                1 => texel[i],
+                _ => (u16::from_ne_bytes([texel[i * 2], texel[i * 2 + 1]]) >> 8) as u8,
+            };
+            match channels {
+                1 => [channel(0), channel(0), channel(0), 255],
+                2 => [channel(0), channel(0), channel(0), channel(1)],
+                3 => [channel(0), channel(1), channel(2), 255],
+                _ => [channel(0), channel(1), channel(2), channel(3)],
+            }
+        })
+        .collect();
+    image::RgbaImage::from_raw(data.width, data.height, pixels)
+}
+
+/// 每个节点的父节点
+fn node_parents(document: &Document) -> HashMap<usize, usize> {
+    document
+        .nodes()
+        .flat_map(|node| node.children().map(move |child| (child.index(), node.index())))
+        .collect()
+}
+
+/// 节点的所有祖先（由近到远）
+fn ancestors(node: usize, parents: &HashMap<usize, usize>) -> impl Iterator<Item = usize> + '_ {
+    std::iter::successors(parents.get(&node).copied(), |n| parents.get(n).copied()).take(parents.len())
+}
+
+/// 节点的全局变换
+fn world_transform(document: &Document, node: usize, parents: &HashMap<usize, usize>) -> Matrix4 {
+    let local = |index: usize| {
+        document
+            .nodes()
+            .nth(index)
+            .map_or_else(Matrix4::identity, |node| Matrix4::from(node.transform().matrix()))
+    };
+    ancestors(node, parents).fold(local(node), |world, ancestor| local(ancestor) * world)
+}
+
+/// glTF 的四元数按 `[x, y, z, w]` 存放
+fn quaternion_from_xyzw(q: [f32; 4]) -> Quaternion {
+    Quaternion::from_quaternion(nalgebra::Quaternion::new(q[3], q[0], q[1], q[2]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::skinning::AnimatedSkeleton;
+
+    /// 组装 GLB：JSON 块补空格、二进制块补零到 4 字节对齐
+    fn glb(json: &str, bin: &[u8]) -> Vec<u8> {
+        let mut json = json.as_bytes().to_vec();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let mut bin = bin.to_vec();
+        bin.resize(bin.len().next_multiple_of(4), 0);
+
+        let total = 12 + 8 + json.len() + 8 + bin.len();
+        let mut data = Vec::with_capacity(total);
+        data.extend_from_slice(b"glTF");
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&(total as u32).to_le_bytes());
+        data.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        data.extend_from_slice(b"JSON");
+        data.extend_from_slice(&json);
+        data.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+        data.extend_from_slice(b"BIN\0");
+        data.extend_from_slice(&bin);
+        data
+    }
+
+    fn push_f32s(bin: &mut Vec<u8>, values: &[f32]) {
+        values.iter().for_each(|v| bin.extend_from_slice(&v.to_le_bytes()));
+    }
+
+    /// 两个关节的蒙皮三角形：根关节在原点，子关节在 (0, 1, 0) 且在 1 秒内绕 Z 轴旋转 90 度
+    fn skinned_triangle() -> Vec<u8> {
+        let mut bin = Vec::new();
+        push_f32s(&mut bin, &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0, 0.0]); // 0: positions
+        for joints in [[0u16, 0, 0, 0], [0, 0, 0, 0], [1, 0, 0, 0]] {
+            joints.iter().for_each(|j| bin.extend_from_slice(&j.to_le_bytes())); // 36: joints
+        }
+        push_f32s(&mut bin, &[1.0, 0.0, 0.0, 0.0].repeat(3)); // 60: weights
+        [0u16, 1, 2, 0].iter().for_each(|i| bin.extend_from_slice(&i.to_le_bytes())); // 108: indices
+        let mut inverse_bind = Matrix4::identity();
+        push_f32s(&mut bin, inverse_bind.as_slice()); // 116: inverse bind matrices
+        inverse_bind[(1, 3)] = -1.0;
+        push_f32s(&mut bin, inverse_bind.as_slice());
+        push_f32s(&mut bin, &[0.0, 1.0]); // 244: times
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        push_f32s(&mut bin, &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, half, half]); // 252: rotations
+        assert_eq!(bin.len(), 284);
+
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "scene": 0,
+            "scenes": [{"nodes": [0, 2]}],
+            "nodes": [
+                {"name": "root", "children": [1]},
+                {"name": "child", "translation": [0, 1, 0]},
+                {"mesh": 0, "skin": 0}
+            ],
+            "meshes": [{"primitives": [{
+                "attributes": {"POSITION": 0, "JOINTS_0": 1, "WEIGHTS_0": 2},
+                "indices": 3,
+                "material": 0
+            }]}],
+            "materials": [{"name": "gold", "pbrMetallicRoughness": {
+                "baseColorFactor": [1.0, 0.8, 0.3, 1.0], "roughnessFactor": 0.25
+            }}],
+            "skins": [{"joints": [0, 1], "inverseBindMatrices": 4}],
+            "animations": [{
+                "channels": [{"sampler": 0, "target": {"node": 1, "path": "rotation"}}],
+                "samplers": [{"input": 5, "output": 6}]
+            }],
+            "buffers": [{"byteLength": 284}],
+            "bufferViews": [
+                {"buffer": 0, "byteOffset": 0, "byteLength": 36},
+                {"buffer": 0, "byteOffset": 36, "byteLength": 24},
+                {"buffer": 0, "byteOffset": 60, "byteLength": 48},
+                {"buffer": 0, "byteOffset": 108, "byteLength": 6},
+                {"buffer": 0, "byteOffset": 116, "byteLength": 128},
+                {"buffer": 0, "byteOffset": 244, "byteLength": 8},
+                {"buffer": 0, "byteOffset": 252, "byteLength": 32}
+            ],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+                 "min": [0, 0, 0], "max": [1, 2, 0]},
+                {"bufferView": 1, "componentType": 5123, "count": 3, "type": "VEC4"},
+                {"bufferView": 2, "componentType": 5126, "count": 3, "type": "VEC4"},
+                {"bufferView": 3, "componentType": 5123, "count": 3, "type": "SCALAR"},
+                {"bufferView": 4, "componentType": 5126, "count": 2, "type": "MAT4"},
+                {"bufferView": 5, "componentType": 5126, "count": 2, "type": "SCALAR", "min": [0], "max": [1]},
+                {"bufferView": 6, "componentType": 5126, "count": 2, "type": "VEC4"}
+            ]
+        }"#;
+
+        glb(json, &bin)
+    }
+
+    #[test]
+    fn test_supported_extensions() {
+        assert_eq!(GltfLoader::supported_extensions(), &["gltf", "glb"]);
+    }
+
+    #[test]
+    fn test_load_nonexistent_file() {
+        assert!(GltfLoader::load_from_file(Path::new("nonexistent.glb")).is_err());
+        assert!(GltfLoader::load_from_memory(b"not gltf").is_err());
+    }
+
+    #[test]
+    fn test_load_static_mesh_from_memory() {
+        let mesh = GltfLoader::load_from_memory(&skinned_triangle()).unwrap();
+
+        assert_eq!(mesh.vertex_count(), 3);
+        assert_eq!(mesh.indices, vec![0, 1, 2]);
+        assert_eq!(mesh.subsets.len(), 1);
+        // 未指定的金属度使用默认值 1，材质列表末尾为默认材质
+        assert_eq!(mesh.materials.len(), 2);
+        let material = &mesh.materials[mesh.subsets[0].id as usize];
+        assert_eq!(material.name.as_deref(), Some("gold"));
+        assert_eq!(material.base_color, [1.0, 0.8, 0.3, 1.0]);
+        assert_eq!((material.metallic, material.roughness), (1.0, 0.25));
+        // 缺少法线时按绕序重建
+        assert!((Vector3::from(mesh.vertices[0].normal) - Vector3::z()).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_load_skinned_mesh_and_animation() {
+        let skinned = GltfLoader::load_skinned_from_memory(&skinned_triangle()).unwrap().unwrap();
+
+        assert_eq!(skinned.skeleton.joints.len(), 2);
+        assert_eq!(skinned.skeleton.joints[0].parent, None);
+        assert_eq!(skinned.skeleton.joints[1].parent, Some(0));
+        assert_eq!(skinned.joint_indices[2], [1, 0, 0, 0]);
+        let clip = skinned.animation.clone().unwrap();
+        assert_eq!(clip.duration, 1.0);
+        assert_eq!(clip.channels[0].joint, 1);
+
+        // 绑定姿态下蒙皮结果与原始位置一致，动画结束时末端顶点转到 (-1, 1, 0)
+        let mut animated = AnimatedSkeleton::new(skinned.skeleton.clone(), skinned.animation.clone());
+        let rest = skinned.skinned_positions(&animated.joint_matrices());
+        assert!((rest[2] - Vector3::new(0.0, 2.0, 0.0)).norm() < 1e-5);
+
+        animated.time = 1.0;
+        let posed = skinned.skinned_positions(&animated.joint_matrices());
+        assert!((posed[1] - Vector3::new(1.0, 0.0, 0.0)).norm() < 1e-5);
+        assert!((posed[2] - Vector3::new(-1.0, 1.0, 0.0)).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_base_color_texture_sources() {
+        let dir = std::env::temp_dir().join(format!("dist_render_gltf_texture_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // 外部图片：返回相对模型文件所在目录的路径
+        std::fs::write(
+            dir.join("external.gltf"),
+            r#"{"asset": {"version": "2.0"}, "images": [{"uri": "textures/albedo.png"}], "textures": [{"source": 0}],
+                "materials": [{"pbrMetallicRoughness": {"baseColorTexture": {"index": 0}}}]}"#,
+        )
+        .unwrap();
+
+        // GLB 内嵌图片：2x1 的 RGB PNG
+        let mut png = std::io::Cursor::new(Vec::new());
+        image::RgbImage::from_raw(2, 1, vec![255, 0, 0, 0, 0, 255])
+            .unwrap()
+            .write_to(&mut png, image::ImageOutputFormat::Png)
+            .unwrap();
+        let png = png.into_inner();
+        let json = format!(
+            r#"{{"asset": {{"version": "2.0"}}, "buffers": [{{"byteLength": {0}}}],
+                "bufferViews": [{{"buffer": 0, "byteLength": {0}}}],
+                "images": [{{"bufferView": 0, "mimeType": "image/png"}}], "textures": [{{"source": 0}}],
+                "materials": [{{}}, {{"pbrMetallicRoughness": {{"baseColorTexture": {{"index": 0}}}}}}]}}"#,
+            png.len()
+        );
+        std::fs::write(dir.join("embedded.glb"), glb(&json, &png)).unwrap();
+
+        let external = GltfLoader::base_color_texture(&dir.join("external.gltf"));
+        let embedded = GltfLoader::base_color_texture(&dir.join("embedded.glb"));
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(external.unwrap(), Some(TextureSource::File(dir.join("textures/albedo.png"))));
+        let Some(TextureSource::Embedded { key, image }) = embedded.unwrap() else {
+            panic!("expected an embedded texture");
+        };
+        assert!(key.ends_with("embedded.glb#image0"));
+        assert_eq!(image.dimensions(), (2, 1));
+        assert_eq!(image.as_raw(), &[255, 0, 0, 255, 0, 0, 255, 255]);
+    }
+}
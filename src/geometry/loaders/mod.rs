@@ -5,7 +5,8 @@
 /// # 支持的格式
 ///
 /// - **OBJ**: Wavefront OBJ 格式（使用 tobj crate）
-/// - **FBX**: Autodesk FBX 格式（使用 russimp/Assimp）
+/// - **FBX**: Autodesk FBX 格式（内置的最小 ASCII 解析器，只读取几何数据，不支持二进制 FBX）
+/// - **glTF**: glTF 2.0 格式（使用 gltf crate，支持 `.gltf`/`.glb`、蒙皮和骨骼动画）
 ///
 /// # 使用示例
 ///
@@ -17,15 +18,18 @@
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 use crate::core::error::Result;
+use crate::geometry::material::TextureSource;
 use crate::geometry::mesh::MeshData;
 use std::path::Path;
 
 pub mod obj_loader;
 pub mod fbx_loader;
+pub mod gltf_loader;
 
 // 重新导出加载器
 pub use obj_loader::ObjLoader;
 pub use fbx_loader::FbxLoader;
+pub use gltf_loader::GltfLoader;
 
 /// 网格加载器 trait
 ///
@@ -142,6 +146,7 @@ pub fn load_mesh(path: &Path) -> Result<MeshData> {
     match extension.as_str() {
         "obj" => ObjLoader::load_from_file(path),
         "fbx" => FbxLoader::load_from_file(path),
+        "gltf" | "glb" => GltfLoader::load_from_file(path),
         _ => Err(crate::core::error::DistRenderError::MeshLoading(
             crate::core::error::MeshLoadError::UnsupportedFormat(format!(
                 "不支持的文件格式: .{}",
@@ -151,6 +156,19 @@ pub fn load_mesh(path: &Path) -> Result<MeshData> {
     }
 }
 
+/// 读取模型材质引用的基础颜色贴图
+///
+/// OBJ 取材质库中的漫反射贴图，glTF 取基础颜色贴图（包括内嵌图片），贴图路径相对模型文件所在目录。
+/// 其他格式或模型没有贴图时返回 `Ok(None)`。
+pub fn load_base_color_texture(path: &Path) -> Result<Option<TextureSource>> {
+    let extension = path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase());
+    match extension.as_deref() {
+        Some("obj") => ObjLoader::base_color_texture(path),
+        Some("gltf" | "glb") => GltfLoader::base_color_texture(path),
+        _ => Ok(None),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,5 +180,8 @@ mod tests {
 
         let fbx_exts = FbxLoader::supported_extensions();
         assert!(fbx_exts.contains(&"fbx"));
+
+        let gltf_exts = GltfLoader::supported_extensions();
+        assert!(gltf_exts.contains(&"glb"));
     }
 }
@@ -2,13 +2,19 @@
 ///
 /// 使用 tobj crate 加载 Wavefront OBJ 格式的3D模型。
 /// 支持顶点位置、法线、纹理坐标的加载，并可自动重建缺失的法线和切线。
+/// 重建法线时按 OBJ 的平滑组（`s` 指令）区分软硬边，文件没有平滑组时按夹角阈值判断。
+/// 基础颜色贴图取 `mtllib` 引用的材质库中第一个带漫反射贴图（`map_Kd`）的材质。
 use super::MeshLoader;
 use crate::core::error::{MeshLoadError, Result};
+use crate::geometry::material::TextureSource;
 use crate::geometry::mesh::{MeshData, Subset};
 use crate::geometry::vertex::Vertex;
-use crate::math::geometry::{reconstruct_normals, compute_tangent_space, smooth_normals_by_position};
+use crate::math::geometry::{compute_tangent_space, reconstruct_normals_by_angle, reconstruct_normals_by_group};
 use std::path::Path;
 
+/// 没有平滑组时，相邻面夹角不超过该值（度）的边视为平滑边
+pub const DEFAULT_SMOOTHING_ANGLE: f32 = 60.0;
+
 /// OBJ 格式加载器
 ///
 /// 实现 `MeshLoader` trait，提供 OBJ 文件的加载功能。
@@ -18,7 +24,7 @@ use std::path::Path;
 /// - 使用 tobj crate 解析 OBJ 文件
 /// - 自动三角化（如果需要）
 /// - UV 坐标翻转（V轴：1.0 - v）
-/// - 自动重建缺失的法线
+/// - 自动重建缺失的法线（遵循平滑组，没有平滑组时按夹角阈值）
 /// - 自动计算切线空间
 ///
 /// # 使用示例
@@ -62,8 +68,20 @@ impl MeshLoader for ObjLoader {
                 .unwrap_or("Unnamed")
         );
 
+        // tobj 不解析平滑组，缺少法线时需要自己从文件中读取
+        let mut smoothing_groups = None;
+        if models.iter().any(|model| model.mesh.normals.is_empty()) {
+            smoothing_groups = std::fs::read_to_string(path).ok().and_then(|source| parse_smoothing_groups(&source));
+            let triangle_count: usize = models.iter().map(|model| model.mesh.indices.len() / 3).sum();
+            if smoothing_groups.as_ref().is_some_and(|groups| groups.len() != triangle_count) {
+                tracing::warn!("OBJ 平滑组与三角形数量不一致，改为按夹角重建法线");
+                smoothing_groups = None;
+            }
+        }
+
         let mut has_normals = false;
         let mut has_texcoords = false;
+        let mut triangle_offset = 0;
 
         // 遍历所有模型（OBJ 可能包含多个对象）
         for (mesh_idx, model) in models.iter().enumerate() {
@@ -71,6 +89,7 @@ impl MeshLoader for ObjLoader {
 
             let vertex_start = mesh_data.vertices.len() as u32;
             let face_start = mesh_data.triangle_count() as u32;
+            let mut vertices = Vec::new();
 
             // 检查数据完整性
             let positions = &mesh.positions;
@@ -125,7 +144,7 @@ impl MeshLoader for ObjLoader {
                 // 切线将在后处理中计算
                 let tangent = [0.0, 0.0, 0.0];
 
-                mesh_data.vertices.push(Vertex {
+                vertices.push(Vertex {
                     position,
                     normal,
                     texcoord,
@@ -133,11 +152,23 @@ impl MeshLoader for ObjLoader {
                 });
             }
 
-            // 提取索引
-            let face_count = mesh.indices.len() / 3;
-            for &index in &mesh.indices {
-                mesh_data.indices.push(vertex_start + index);
+            // 重建缺失的法线：有平滑组时按组区分软硬边，否则按夹角阈值（硬边处会拆分顶点）
+            let mut indices = mesh.indices.clone();
+            let face_count = indices.len() / 3;
+            if normals.is_empty() {
+                match &smoothing_groups {
+                    Some(groups) => {
+                        let face_groups = &groups[triangle_offset..triangle_offset + face_count];
+                        reconstruct_normals_by_group(&mut vertices, &mut indices, face_groups);
+                    }
+                    None => reconstruct_normals_by_angle(&mut vertices, &mut indices, DEFAULT_SMOOTHING_ANGLE),
+                }
             }
+            triangle_offset += face_count;
+
+            let vertex_count = vertices.len();
+            mesh_data.vertices.extend(vertices);
+            mesh_data.indices.extend(indices.iter().map(|&index| vertex_start + index));
 
             // 创建子网格
             let subset = Subset::new(
@@ -150,17 +181,12 @@ impl MeshLoader for ObjLoader {
             mesh_data.subsets.push(subset);
         }
 
-        // 后处理：重建法线（如果缺失）
         if !has_normals {
-            tracing::info!("OBJ 文件缺少法线数据，正在重建...");
-            reconstruct_normals(&mut mesh_data.vertices, &mesh_data.indices);
-
-            // 后处理：seam 平滑处理（按 position 聚类法线）
-            // OBJ 常在 UV seam 处拆顶点，导致同位置不同法线，从而出现"切边"。
-            // 这里用一个小的 epsilon 将同一位置附近的顶点归为一组并平均法线。
-            // 注意：只在重建法线时才执行平滑，保留原始 OBJ 文件提供的法线数据
-            tracing::info!("平滑重建的法线...");
-            smooth_normals_by_position(&mut mesh_data.vertices, 1e-5);
+            // 重建时按位置合并同组的法线，OBJ 在 UV seam 处拆开的顶点不会出现"切边"
+            match smoothing_groups {
+                Some(_) => tracing::info!("OBJ 文件缺少法线数据，已按平滑组重建"),
+                None => tracing::info!("OBJ 文件缺少法线数据，已按 {} 度夹角阈值重建", DEFAULT_SMOOTHING_ANGLE),
+            }
         } else {
             tracing::info!("使用 OBJ 文件提供的法线数据");
         }
@@ -200,6 +226,55 @@ impl MeshLoader for ObjLoader {
     }
 }
 
+impl ObjLoader {
+    /// 模型的基础颜色贴图
+    ///
+    /// 依次读取 `mtllib` 引用的材质库，返回第一个漫反射贴图（`map_Kd`），
+    /// 材质库和贴图路径都相对模型文件所在目录解析。没有材质库或贴图时返回 `Ok(None)`。
+    pub fn base_color_texture(path: &Path) -> Result<Option<TextureSource>> {
+        let source = std::fs::read_to_string(path)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        let libraries = source.lines().filter_map(|line| line.trim().strip_prefix("mtllib "));
+        for library in libraries.flat_map(str::split_whitespace) {
+            let (materials, _) = tobj::load_mtl(dir.join(library))
+                .map_err(|e| MeshLoadError::ParseError(format!("材质库 {} 解析失败: {}", library, e)))?;
+            if let Some(texture) = materials.into_iter().find_map(|material| material.diffuse_texture) {
+                return Ok(Some(TextureSource::File(dir.join(texture))));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// 按文件中的顺序读取每个三角形所属的平滑组
+///
+/// `s off` 和 `s 0` 表示关闭平滑（组号 0）。多边形按 tobj 的扇形三角化拆为 n - 2 个三角形，
+/// 与 tobj 输出的三角形顺序一致。文件中没有 `s` 指令时返回 `None`。
+fn parse_smoothing_groups(source: &str) -> Option<Vec<u32>> {
+    let mut groups = Vec::new();
+    let mut current = 0;
+    let mut has_groups = false;
+
+    for line in source.lines() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("s") => {
+                has_groups = true;
+                current = tokens.next().and_then(|group| group.parse().ok()).unwrap_or(0);
+            }
+            Some("f") => {
+                let corners = tokens.count();
+                groups.extend(std::iter::repeat_n(current, corners.saturating_sub(2)));
+            }
+            _ => {}
+        }
+    }
+
+    has_groups.then_some(groups)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -216,6 +291,72 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// 沿 X 轴折成直角的两个四边形
+    const FOLDED_OBJ: &str = "\
+v 0 0 0
+v 1 0 0
+v 1 0 -1
+v 0 0 -1
+v 0 -1 0
+v 1 -1 0
+s 1
+f 1 2 3 4
+s 2
+f 2 1 5 6
+";
+
+    fn load_obj_source(name: &str, source: &str) -> MeshData {
+        let path = std::env::temp_dir().join(format!("dist_render_{}_{}.obj", name, std::process::id()));
+        std::fs::write(&path, source).unwrap();
+        let mesh = ObjLoader::load_from_file(&path);
+        std::fs::remove_file(&path).ok();
+        mesh.unwrap()
+    }
+
+    #[test]
+    fn test_parse_smoothing_groups() {
+        assert_eq!(parse_smoothing_groups(FOLDED_OBJ), Some(vec![1, 1, 2, 2]));
+        assert_eq!(parse_smoothing_groups("s off\nf 1 2 3\ns 3\nf 1 2 3\n"), Some(vec![0, 3]));
+        assert_eq!(parse_smoothing_groups("f 1 2 3\n"), None);
+    }
+
+    #[test]
+    fn test_smoothing_groups_keep_hard_edge() {
+        let mesh = load_obj_source("smoothing_groups", FOLDED_OBJ);
+
+        // 折痕上的顶点按组拆开，每个面使用自己的平面法线
+        assert_eq!(mesh.vertex_count(), 8);
+        let triangles: Vec<&[u32]> = mesh.indices.chunks_exact(3).collect();
+        for (triangle, expected) in triangles.iter().zip([[0.0, 1.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0, 1.0]]) {
+            for &index in triangle.iter() {
+                assert_eq!(mesh.vertices[index as usize].normal, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_smoothing_group_is_smooth() {
+        let mesh = load_obj_source("single_smoothing_group", &FOLDED_OBJ.replace("s 2", "s 1"));
+        assert_eq!(mesh.vertex_count(), 6);
+    }
+
+    #[test]
+    fn test_base_color_texture_from_material_library() {
+        let dir = std::env::temp_dir().join(format!("dist_render_mtl_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("model.mtl"), "newmtl plain\nKd 1 1 1\nnewmtl brick\nmap_Kd textures/brick.png\n").unwrap();
+        std::fs::write(dir.join("model.obj"), format!("mtllib model.mtl\nusemtl brick\n{}", FOLDED_OBJ)).unwrap();
+        std::fs::write(dir.join("plain.obj"), FOLDED_OBJ).unwrap();
+
+        let texture = ObjLoader::base_color_texture(&dir.join("model.obj"));
+        let plain = ObjLoader::base_color_texture(&dir.join("plain.obj"));
+        std::fs::remove_dir_all(&dir).ok();
+
+        // 贴图路径相对模型文件所在目录
+        assert_eq!(texture.unwrap(), Some(TextureSource::File(dir.join("textures/brick.png"))));
+        assert_eq!(plain.unwrap(), None);
+    }
+
     #[test]
     fn test_load_from_memory_unsupported() {
         let result = ObjLoader::load_from_memory(&[]);
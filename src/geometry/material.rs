@@ -0,0 +1,139 @@
+//! 材质
+//!
+//! glTF 金属-粗糙度工作流的材质参数（基础颜色、金属度、粗糙度）。
+//! 网格的子网格 ID 即其材质在 [`MeshData::materials`] 中的序号；
+//! 没有材质的网格（OBJ、程序化几何体等）按简单着色绘制。
+//! 材质引用的基础颜色贴图由 [`TextureSource`] 描述，与网格数据分开解析（见
+//! [`load_base_color_texture`](super::loaders::load_base_color_texture)）。
+
+use super::mesh::MeshData;
+use std::path::PathBuf;
+
+/// 金属-粗糙度材质
+#[derive(Debug, Clone, PartialEq)]
+pub struct Material {
+    /// 材质名称（可选）
+    pub name: Option<String>,
+
+    /// 基础颜色（线性空间 RGBA）
+    pub base_color: [f32; 4],
+
+    /// 金属度，0 为电介质，1 为金属
+    pub metallic: f32,
+
+    /// 感知粗糙度，0 为完全光滑，1 为完全粗糙
+    pub roughness: f32,
+
+    /// 是否半透明（glTF 的 `alphaMode` 为 `BLEND`）
+    ///
+    /// 半透明子网格按基础颜色的 alpha 混合，在不透明几何之后从远到近绘制且不写深度。
+    pub transparent: bool,
+}
+
+/// glTF 规范的默认材质：白色、金属度和粗糙度均为 1
+static DEFAULT_MATERIAL: Material = Material {
+    name: None,
+    base_color: [1.0; 4],
+    metallic: 1.0,
+    roughness: 1.0,
+    transparent: false,
+};
+
+impl Default for Material {
+    fn default() -> Self {
+        DEFAULT_MATERIAL.clone()
+    }
+}
+
+impl Material {
+    /// 绘制时的不透明度：半透明材质为基础颜色的 alpha，不透明材质总是 1
+    pub fn opacity(&self) -> f32 {
+        if self.transparent {
+            self.base_color[3]
+        } else {
+            1.0
+        }
+    }
+}
+
+/// 材质引用的基础颜色贴图
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextureSource {
+    /// 外部图片文件（已相对模型文件所在目录解析）
+    File(PathBuf),
+
+    /// 内嵌在模型文件中的图片（glTF 的 data URI 或 GLB 二进制块），已解码为 RGBA8
+    Embedded {
+        /// 缓存键：模型文件路径加图片序号
+        key: String,
+        image: image::RgbaImage,
+    },
+}
+
+impl TextureSource {
+    /// 缓存键，相同的键对应同一张纹理
+    pub fn key(&self) -> String {
+        match self {
+            Self::File(path) => path.to_string_lossy().into_owned(),
+            Self::Embedded { key, .. } => key.clone(),
+        }
+    }
+}
+
+impl MeshData {
+    /// 每个顶点所属的材质
+    ///
+    /// 按子网格的三角形范围分配，子网格 ID 越界或未被子网格覆盖的顶点使用默认材质；
+    /// 多个子网格共享的顶点使用最后一个子网格的材质。网格没有材质时返回 `None`。
+    pub fn vertex_materials(&self) -> Option<Vec<&Material>> {
+        if self.materials.is_empty() {
+            return None;
+        }
+
+        let mut vertex_materials = vec![&DEFAULT_MATERIAL; self.vertices.len()];
+        let triangle_count = self.triangle_count();
+        for subset in &self.subsets {
+            let material = self.materials.get(subset.id as usize).unwrap_or(&DEFAULT_MATERIAL);
+            let end = ((subset.face_start + subset.face_count) as usize).min(triangle_count);
+            let start = (subset.face_start as usize).min(end);
+            for &index in &self.indices[start * 3..end * 3] {
+                if let Some(slot) = vertex_materials.get_mut(index as usize) {
+                    *slot = material;
+                }
+            }
+        }
+
+        Some(vertex_materials)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::mesh::Subset;
+    use crate::geometry::vertex::Vertex;
+
+    #[test]
+    fn test_vertex_materials_follow_subsets() {
+        let mut mesh = MeshData::new();
+        for x in 0..6 {
+            mesh.vertices.push(Vertex::new([x as f32, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+        }
+        mesh.indices = vec![0, 1, 2, 3, 4, 5];
+        assert!(mesh.vertex_materials().is_none());
+
+        let red = Material {
+            base_color: [1.0, 0.0, 0.0, 1.0],
+            metallic: 0.0,
+            ..Default::default()
+        };
+        mesh.materials = vec![red.clone()];
+        mesh.subsets = vec![Subset::new(0, 0, 3, 0, 1), Subset::new(7, 3, 3, 1, 1)];
+
+        let materials = mesh.vertex_materials().unwrap();
+        assert_eq!(materials.len(), 6);
+        assert!(materials[..3].iter().all(|&m| *m == red));
+        // 越界的子网格 ID 使用默认材质
+        assert!(materials[3..].iter().all(|&m| *m == Material::default()));
+    }
+}
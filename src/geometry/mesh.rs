@@ -2,7 +2,10 @@
 ///
 /// 定义CPU侧的网格数据容器，用于存储从文件加载的原始几何数据。
 /// 对应 DistEngine 的 MeshData 和 Subset 结构。
+use super::coordinate::CoordinateSystem;
+use super::material::Material;
 use super::vertex::Vertex;
+use crate::math::{Matrix3, Matrix4, Vector3};
 
 /// 子网格描述符
 ///
@@ -85,6 +88,148 @@ impl Subset {
     }
 }
 
+/// 图元重启索引
+///
+/// 三角形带中遇到该索引时结束当前的带，下一个索引开始新的带。
+/// 打包为 16 位索引时对应 `u16::MAX`。
+pub const PRIMITIVE_RESTART_INDEX: u32 = u32::MAX;
+
+/// 索引的图元拓扑
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrimitiveTopology {
+    /// 三角形列表，每 3 个索引定义一个三角形
+    #[default]
+    TriangleList,
+    /// 带图元重启的三角形带
+    ///
+    /// 每个索引与前两个索引组成一个三角形，奇数序号的三角形交换前两个顶点以保持绕序；
+    /// [`PRIMITIVE_RESTART_INDEX`] 分隔相互独立的带。网格（地形）每行一条带，
+    /// 索引数量约为三角形列表的三分之一。
+    TriangleStrip,
+}
+
+impl PrimitiveTopology {
+    /// 按拓扑把索引展开为三角形
+    pub fn triangles(self, indices: &[u32]) -> Vec<[u32; 3]> {
+        match self {
+            PrimitiveTopology::TriangleList => indices.chunks_exact(3).map(|t| [t[0], t[1], t[2]]).collect(),
+            PrimitiveTopology::TriangleStrip => strip_triangles(indices),
+        }
+    }
+}
+
+/// 无向边的键，两个顶点索引按升序排列
+pub(crate) fn edge_key(a: u32, b: u32) -> (u32, u32) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// 展开带图元重启的三角形带，跳过退化三角形（用于连接带的重复索引）
+fn strip_triangles(indices: &[u32]) -> Vec<[u32; 3]> {
+    indices
+        .split(|&i| i == PRIMITIVE_RESTART_INDEX)
+        .flat_map(|strip| {
+            strip.windows(3).enumerate().map(|(k, w)| {
+                if k % 2 == 0 {
+                    [w[0], w[1], w[2]]
+                } else {
+                    [w[1], w[0], w[2]]
+                }
+            })
+        })
+        .filter(|[a, b, c]| a != b && b != c && a != c)
+        .collect()
+}
+
+/// 索引宽度
+///
+/// 顶点数不超过 65535 时使用 16 位索引，索引缓冲占用减半。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexWidth {
+    /// 16 位无符号索引
+    U16,
+    /// 32 位无符号索引
+    U32,
+}
+
+impl IndexWidth {
+    /// 根据顶点数选择索引宽度
+    #[inline]
+    pub fn for_vertex_count(vertex_count: usize) -> Self {
+        if vertex_count <= u16::MAX as usize {
+            IndexWidth::U16
+        } else {
+            IndexWidth::U32
+        }
+    }
+
+    /// 单个索引的字节数
+    #[inline]
+    pub fn size_in_bytes(&self) -> usize {
+        match self {
+            IndexWidth::U16 => 2,
+            IndexWidth::U32 => 4,
+        }
+    }
+}
+
+/// 按索引宽度打包的索引数据，可直接上传到 GPU
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexData {
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl IndexData {
+    /// 根据顶点数选择宽度并打包索引
+    pub fn new(indices: &[u32], vertex_count: usize) -> Self {
+        match IndexWidth::for_vertex_count(vertex_count) {
+            IndexWidth::U16 => IndexData::U16(
+                indices
+                    .iter()
+                    .map(|&i| if i == PRIMITIVE_RESTART_INDEX { u16::MAX } else { i as u16 })
+                    .collect(),
+            ),
+            IndexWidth::U32 => IndexData::U32(indices.to_vec()),
+        }
+    }
+
+    /// 索引宽度
+    #[inline]
+    pub fn width(&self) -> IndexWidth {
+        match self {
+            IndexData::U16(_) => IndexWidth::U16,
+            IndexData::U32(_) => IndexWidth::U32,
+        }
+    }
+
+    /// 索引数量
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            IndexData::U16(indices) => indices.len(),
+            IndexData::U32(indices) => indices.len(),
+        }
+    }
+
+    /// 是否没有索引
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 以字节形式访问索引数据
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            IndexData::U16(indices) => bytemuck::cast_slice(indices),
+            IndexData::U32(indices) => bytemuck::cast_slice(indices),
+        }
+    }
+}
+
 /// CPU侧网格数据
 ///
 /// 存储从文件加载的原始网格数据，包含顶点、索引和子网格信息。
@@ -98,7 +243,7 @@ impl Subset {
 /// # 示例
 ///
 /// ```rust
-/// use distrender::geometry::mesh::MeshData;
+/// use distrender::geometry::mesh::{MeshData, PrimitiveTopology};
 /// use distrender::geometry::vertex::Vertex;
 ///
 /// let mesh = MeshData {
@@ -108,7 +253,9 @@ impl Subset {
 ///         Vertex::new([0.0, 0.0, 1.0], [0.0, 1.0, 0.0], [0.0, 1.0], [1.0, 0.0, 0.0]),
 ///     ],
 ///     indices: vec![0, 1, 2],
+///     topology: PrimitiveTopology::TriangleList,
 ///     subsets: vec![],
+///     materials: vec![],
 ///     name: Some("Triangle".to_string()),
 /// };
 /// ```
@@ -121,16 +268,28 @@ pub struct MeshData {
 
     /// 索引数组
     ///
-    /// 三角形顶点索引，每3个索引定义一个三角形。
+    /// 三角形顶点索引，按 `topology` 解释（默认每3个索引定义一个三角形）。
     /// 使用32位索引以支持超过65535个顶点的模型。
     pub indices: Vec<u32>,
 
+    /// 索引的图元拓扑
+    ///
+    /// 模型加载器总是生成三角形列表；程序化网格（平面、地形）可以生成三角形带。
+    /// 编辑网格的操作（合并、简化、优化等）会先把三角形带转换为列表。
+    pub topology: PrimitiveTopology,
+
     /// 子网格列表
     ///
     /// 用于多材质模型，每个子网格对应一个材质。
     /// 如果模型只有一个材质，此列表可以为空。
     pub subsets: Vec<Subset>,
 
+    /// 材质列表
+    ///
+    /// 子网格 ID 为其材质在此列表中的序号。没有材质信息的格式（如 OBJ）此列表为空，
+    /// 渲染时使用简单着色。
+    pub materials: Vec<Material>,
+
     /// 网格名称（可选）
     ///
     /// 从文件中读取的网格名称，用于调试和识别。
@@ -143,7 +302,9 @@ impl MeshData {
         Self {
             vertices: Vec::new(),
             indices: Vec::new(),
+            topology: PrimitiveTopology::TriangleList,
             subsets: Vec::new(),
+            materials: Vec::new(),
             name: None,
         }
     }
@@ -153,7 +314,9 @@ impl MeshData {
         Self {
             vertices: Vec::new(),
             indices: Vec::new(),
+            topology: PrimitiveTopology::TriangleList,
             subsets: Vec::new(),
+            materials: Vec::new(),
             name: Some(name.into()),
         }
     }
@@ -168,7 +331,9 @@ impl MeshData {
         Self {
             vertices: Vec::with_capacity(vertex_capacity),
             indices: Vec::with_capacity(index_capacity),
+            topology: PrimitiveTopology::TriangleList,
             subsets: Vec::new(),
+            materials: Vec::new(),
             name: None,
         }
     }
@@ -186,16 +351,65 @@ impl MeshData {
     }
 
     /// 获取三角形数量
-    #[inline]
+    ///
+    /// 三角形带不计入退化三角形。
     pub fn triangle_count(&self) -> usize {
-        self.indices.len() / 3
+        match self.topology {
+            PrimitiveTopology::TriangleList => self.indices.len() / 3,
+            PrimitiveTopology::TriangleStrip => self.triangles().len(),
+        }
+    }
+
+    /// 按拓扑展开的三角形，绕序与 GPU 光栅化时相同
+    ///
+    /// 三角形带中的退化三角形（用于连接带的重复索引）被跳过。
+    pub fn triangles(&self) -> Vec<[u32; 3]> {
+        self.topology.triangles(&self.indices)
+    }
+
+    /// 所有顶点位置的轴对齐包围盒 (最小值, 最大值)，没有顶点时为 `None`
+    pub fn bounds(&self) -> Option<(Vector3, Vector3)> {
+        let mut positions = self.vertices.iter().map(|v| Vector3::from(v.position));
+        let first = positions.next()?;
+        Some(positions.fold((first, first), |(min, max), p| (min.inf(&p), max.sup(&p))))
+    }
+
+    /// 按 `factor` 均匀缩放顶点位置
+    ///
+    /// 均匀缩放不改变法线和切线方向。
+    pub fn scale(&mut self, factor: f32) {
+        for vertex in &mut self.vertices {
+            vertex.position = vertex.position.map(|p| p * factor);
+        }
+    }
+
+    /// 把三角形带转换为三角形列表（已是列表时不变）
+    ///
+    /// 子网格的面范围按转换后的三角形计算，带拓扑的网格不应带子网格。
+    pub fn to_triangle_list(&mut self) {
+        if self.topology == PrimitiveTopology::TriangleList {
+            return;
+        }
+        self.indices = self.triangles().into_iter().flatten().collect();
+        self.topology = PrimitiveTopology::TriangleList;
+    }
+
+    /// 上传到 GPU 时使用的索引宽度
+    #[inline]
+    pub fn index_width(&self) -> IndexWidth {
+        IndexWidth::for_vertex_count(self.vertex_count())
+    }
+
+    /// 按 [`index_width`](Self::index_width) 打包索引
+    pub fn packed_indices(&self) -> IndexData {
+        IndexData::new(&self.indices, self.vertex_count())
     }
 
     /// 验证网格数据的有效性
     ///
     /// 检查：
-    /// - 索引数量是3的倍数（每个三角形3个顶点）
-    /// - 所有索引都在有效范围内
+    /// - 三角形列表的索引数量是3的倍数（每个三角形3个顶点）
+    /// - 所有索引都在有效范围内（三角形带允许图元重启索引）
     /// - 子网格描述符的范围有效
     ///
     /// # 返回
@@ -204,7 +418,7 @@ impl MeshData {
     /// - `Err(String)`: 数据无效，返回错误描述
     pub fn validate(&self) -> Result<(), String> {
         // 检查索引数量
-        if self.indices.len() % 3 != 0 {
+        if self.topology == PrimitiveTopology::TriangleList && !self.indices.len().is_multiple_of(3) {
             return Err(format!(
                 "索引数量必须是3的倍数，当前为: {}",
                 self.indices.len()
@@ -213,8 +427,9 @@ impl MeshData {
 
         // 检查索引范围
         let vertex_count = self.vertices.len() as u32;
+        let restart = self.topology == PrimitiveTopology::TriangleStrip;
         for (i, &index) in self.indices.iter().enumerate() {
-            if index >= vertex_count {
+            if index >= vertex_count && !(restart && index == PRIMITIVE_RESTART_INDEX) {
                 return Err(format!(
                     "索引 {} 处的值 {} 超出顶点范围 (0-{})",
                     i,
@@ -245,11 +460,121 @@ impl MeshData {
         Ok(())
     }
 
+    /// 转换网格的坐标系
+    ///
+    /// 将位置、法线和切线从 `from` 坐标系变换到 `to` 坐标系。
+    /// 当手性发生变化时翻转三角形绕序，避免模型"内外翻转"。
+    ///
+    /// # 参数
+    ///
+    /// - `from`: 模型文件使用的坐标系
+    /// - `to`: 目标坐标系（通常为引擎默认的 Y-up 右手系）
+    pub fn convert_coordinate_system(&mut self, from: CoordinateSystem, to: CoordinateSystem) {
+        if from == to {
+            return;
+        }
+
+        for vertex in &mut self.vertices {
+            vertex.position = from.convert_vector(&to, vertex.position);
+            vertex.normal = from.convert_vector(&to, vertex.normal);
+            vertex.tangent = from.convert_vector(&to, vertex.tangent);
+        }
+
+        if from.flips_winding(&to) {
+            self.to_triangle_list();
+            for triangle in self.indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+    }
+
+    /// 翻转三角形绕序
+    ///
+    /// 交换每个三角形的后两个索引（`[i0, i1, i2] -> [i0, i2, i1]`）并反转法线，
+    /// 用于修正绕序与引擎约定（逆时针为正面）相反的模型。
+    pub fn flip_winding(&mut self) {
+        self.to_triangle_list();
+        for triangle in self.indices.chunks_exact_mut(3) {
+            triangle.swap(1, 2);
+        }
+
+        for vertex in &mut self.vertices {
+            vertex.normal = vertex.normal.map(|n| -n);
+        }
+    }
+
+    /// 合并另一个网格
+    ///
+    /// 将 `other` 的顶点经 `transform` 变换后追加到当前网格，索引按当前顶点数偏移，
+    /// 子网格按顶点和面的偏移追加。法线使用变换矩阵的逆转置矩阵变换，
+    /// 切线使用线性部分变换。若变换包含镜像（行列式为负），追加的三角形会翻转绕序。
+    ///
+    /// 如果 `other` 没有子网格而当前网格有，则为追加部分新建一个覆盖全部的子网格，
+    /// 保证所有三角形都被子网格覆盖。`other` 带材质时材质追加到当前网格，
+    /// 其子网格 ID 按当前材质数偏移。
+    ///
+    /// # 参数
+    ///
+    /// - `other`: 要合并的网格
+    /// - `transform`: 应用到 `other` 的变换矩阵
+    pub fn merge(&mut self, other: &MeshData, transform: &Matrix4) {
+        self.to_triangle_list();
+        let vertex_offset = self.vertices.len() as u32;
+        let face_offset = self.triangle_count() as u32;
+
+        let linear: Matrix3 = transform.fixed_view::<3, 3>(0, 0).into_owned();
+        let normal_matrix = linear.try_inverse().unwrap_or(linear).transpose();
+        let flip_winding = linear.determinant() < 0.0;
+
+        let transform_direction = |matrix: &Matrix3, v: [f32; 3]| -> [f32; 3] {
+            let d = (matrix * Vector3::from(v)).try_normalize(1e-12).unwrap_or_default();
+            [d.x, d.y, d.z]
+        };
+
+        self.vertices.reserve(other.vertices.len());
+        for vertex in &other.vertices {
+            let p = transform.transform_point(&Vector3::from(vertex.position).into());
+            self.vertices.push(Vertex {
+                position: [p.x, p.y, p.z],
+                normal: transform_direction(&normal_matrix, vertex.normal),
+                texcoord: vertex.texcoord,
+                tangent: transform_direction(&linear, vertex.tangent),
+            });
+        }
+
+        self.indices.reserve(other.indices.len());
+        for [a, b, c] in other.triangles() {
+            let triangle = if flip_winding { [a, c, b] } else { [a, b, c] };
+            self.indices.extend(triangle.iter().map(|&i| i + vertex_offset));
+        }
+
+        if !other.subsets.is_empty() {
+            let id_offset = if other.materials.is_empty() { 0 } else { self.materials.len() as u32 };
+            self.subsets.extend(other.subsets.iter().map(|subset| Subset {
+                id: subset.id + id_offset,
+                vertex_start: subset.vertex_start + vertex_offset,
+                face_start: subset.face_start + face_offset,
+                ..subset.clone()
+            }));
+        } else if !self.subsets.is_empty() {
+            self.subsets.push(Subset::new(
+                self.subsets.len() as u32,
+                vertex_offset,
+                other.vertices.len() as u32,
+                face_offset,
+                other.triangle_count() as u32,
+            ));
+        }
+        self.materials.extend_from_slice(&other.materials);
+    }
+
     /// 清空所有数据
     pub fn clear(&mut self) {
         self.vertices.clear();
         self.indices.clear();
+        self.topology = PrimitiveTopology::TriangleList;
         self.subsets.clear();
+        self.materials.clear();
         self.name = None;
     }
 }
@@ -354,6 +679,171 @@ mod tests {
         assert!(result.unwrap_err().contains("超出顶点范围"));
     }
 
+    #[test]
+    fn test_convert_z_up_to_y_up() {
+        use crate::geometry::coordinate::{Handedness, UpAxis};
+
+        let mut mesh = MeshData::new();
+        mesh.vertices.push(Vertex::new([0.0, 0.0, 1.0], [0.0, 0.0, 1.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+
+        mesh.convert_coordinate_system(
+            CoordinateSystem::new(UpAxis::Z, Handedness::Right),
+            CoordinateSystem::default(),
+        );
+
+        assert_eq!(mesh.vertices[0].position, [0.0, 1.0, 0.0]);
+        assert_eq!(mesh.vertices[0].normal, [0.0, 1.0, 0.0]);
+        assert_eq!(mesh.vertices[0].tangent, [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_convert_handedness_flips_winding() {
+        use crate::geometry::coordinate::{Handedness, UpAxis};
+
+        let mut mesh = MeshData::new();
+        mesh.vertices.push(Vertex::default());
+        mesh.vertices.push(Vertex::default());
+        mesh.vertices.push(Vertex::default());
+        mesh.indices.extend_from_slice(&[0, 1, 2]);
+
+        mesh.convert_coordinate_system(
+            CoordinateSystem::new(UpAxis::Y, Handedness::Left),
+            CoordinateSystem::default(),
+        );
+
+        assert_eq!(mesh.indices, vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_scale_shrinks_bounds() {
+        let mut mesh = MeshData::new();
+        assert_eq!(mesh.bounds(), None);
+        for position in [[-50.0, 0.0, 10.0], [50.0, 200.0, -10.0], [0.0, 100.0, 0.0]] {
+            mesh.vertices.push(Vertex::new(position, [0.0, 0.0, 1.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+        }
+        let (min, max) = mesh.bounds().unwrap();
+        assert_eq!((min, max), (Vector3::new(-50.0, 0.0, -10.0), Vector3::new(50.0, 200.0, 10.0)));
+
+        // 厘米转换为米：包围盒缩小 100 倍，法线不变
+        mesh.scale(0.01);
+        let (scaled_min, scaled_max) = mesh.bounds().unwrap();
+        assert!((scaled_max - scaled_min - (max - min) / 100.0).norm() < 1e-5);
+        assert!(mesh.vertices.iter().all(|v| v.normal == [0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_flip_winding() {
+        let mut mesh = MeshData::new();
+        for position in [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 1.0, 0.0]] {
+            mesh.vertices.push(Vertex::new(position, [0.0, 0.0, 1.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+        }
+        mesh.indices.extend_from_slice(&[0, 1, 2, 2, 1, 3]);
+
+        mesh.flip_winding();
+
+        assert_eq!(mesh.indices, vec![0, 2, 1, 2, 3, 1]);
+        assert!(mesh.vertices.iter().all(|v| v.normal == [0.0, 0.0, -1.0]));
+        // 切线不受影响
+        assert!(mesh.vertices.iter().all(|v| v.tangent == [1.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_merge_two_triangles() {
+        use crate::math::matrix;
+
+        let triangle = |name: &str| {
+            let mut mesh = MeshData::with_name(name);
+            mesh.vertices.push(Vertex::new([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+            mesh.vertices.push(Vertex::new([1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0], [1.0, 0.0, 0.0]));
+            mesh.vertices.push(Vertex::new([0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0], [1.0, 0.0, 0.0]));
+            mesh.indices.extend_from_slice(&[0, 1, 2]);
+            mesh.subsets.push(Subset::new(0, 0, 3, 0, 1));
+            mesh
+        };
+
+        let mut mesh = triangle("A");
+        let other = triangle("B");
+        let transform = matrix::translation(5.0, 0.0, 0.0) * matrix::scaling(2.0, 1.0, 1.0);
+        mesh.merge(&other, &transform);
+
+        assert_eq!(mesh.vertex_count(), 6);
+        assert_eq!(mesh.indices, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(mesh.subsets[1], Subset::new(0, 3, 3, 1, 1));
+        assert!(mesh.validate().is_ok());
+
+        // 第一个网格保持不变，第二个网格被变换
+        assert_eq!(mesh.vertices[1].position, [1.0, 0.0, 0.0]);
+        assert_eq!(mesh.vertices[3].position, [5.0, 0.0, 0.0]);
+        assert_eq!(mesh.vertices[4].position, [7.0, 0.0, 0.0]);
+        assert_eq!(mesh.vertices[5].position, [5.0, 1.0, 0.0]);
+        assert_eq!(mesh.vertices[4].normal, [0.0, 0.0, 1.0]);
+        assert_eq!(mesh.name, Some("A".to_string()));
+    }
+
+    #[test]
+    fn test_merge_offsets_material_ids() {
+        let triangle = |roughness: f32| {
+            let mut mesh = MeshData::new();
+            mesh.vertices.push(Vertex::new([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+            mesh.vertices.push(Vertex::new([1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0], [1.0, 0.0, 0.0]));
+            mesh.vertices.push(Vertex::new([0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0], [1.0, 0.0, 0.0]));
+            mesh.indices.extend_from_slice(&[0, 1, 2]);
+            mesh.subsets.push(Subset::new(0, 0, 3, 0, 1));
+            mesh.materials.push(Material {
+                roughness,
+                ..Default::default()
+            });
+            mesh
+        };
+
+        let mut mesh = triangle(0.25);
+        mesh.merge(&triangle(0.75), &Matrix4::identity());
+
+        assert_eq!(mesh.materials.len(), 2);
+        assert_eq!(mesh.subsets[1].id, 1);
+        assert_eq!(mesh.vertex_materials().unwrap()[3].roughness, 0.75);
+    }
+
+    #[test]
+    fn test_merge_non_uniform_scale_normals() {
+        use crate::math::matrix;
+
+        // 斜面法线在非均匀缩放后应使用逆转置变换
+        let mut other = MeshData::new();
+        let n = std::f32::consts::FRAC_1_SQRT_2;
+        other.vertices.push(Vertex::new([0.0, 0.0, 0.0], [n, n, 0.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+
+        let mut mesh = MeshData::new();
+        mesh.merge(&other, &matrix::scaling(2.0, 1.0, 1.0));
+
+        let normal = mesh.vertices[0].normal;
+        let expected = Vector3::new(0.5, 1.0, 0.0).normalize();
+        assert!((normal[0] - expected.x).abs() < 1e-5);
+        assert!((normal[1] - expected.y).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_index_width_selection() {
+        let mut small = MeshData::new();
+        small.vertices.resize(3, Vertex::default());
+        small.indices.extend_from_slice(&[0, 1, 2]);
+        assert_eq!(small.index_width(), IndexWidth::U16);
+
+        let packed = small.packed_indices();
+        assert_eq!(packed, IndexData::U16(vec![0, 1, 2]));
+        assert_eq!(packed.as_bytes().len(), 6);
+
+        let mut large = MeshData::new();
+        large.vertices.resize(70_000, Vertex::default());
+        large.indices.extend_from_slice(&[0, 1, 69_999]);
+        assert_eq!(large.index_width(), IndexWidth::U32);
+        assert_eq!(large.packed_indices().as_bytes().len(), 12);
+
+        // 边界：65535 个顶点（最大索引 65534）仍可使用 16 位
+        assert_eq!(IndexWidth::for_vertex_count(65_535), IndexWidth::U16);
+        assert_eq!(IndexWidth::for_vertex_count(65_536), IndexWidth::U32);
+    }
+
     #[test]
     fn test_mesh_data_clear() {
         let mut mesh = MeshData::with_name("Test");
@@ -0,0 +1,346 @@
+//! 网格缓存
+//!
+//! 大型 FBX 等模型的解析很慢。开启 `mesh_cache` 后，加载器的输出（坐标系转换和缩放之前）
+//! 以二进制形式写入源文件旁的 `<文件名>.distmesh`。之后加载时，如果源文件的路径、
+//! 修改时间和大小与缓存中记录的一致，就直接读取缓存而不再解析源文件；源文件变化后缓存失效，
+//! 重新解析并覆盖缓存。
+//!
+//! 顶点按内存布局原样写入（本机字节序），缓存只在生成它的机器上使用，不适合分发。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use tracing::{debug, info, warn};
+
+use super::material::Material;
+use super::mesh::{MeshData, PrimitiveTopology, Subset};
+use super::vertex::Vertex;
+use crate::core::error::Result;
+
+/// 缓存文件追加的扩展名
+pub const CACHE_EXTENSION: &str = "distmesh";
+
+/// 文件头标识
+const MAGIC: &[u8; 4] = b"DMSH";
+
+/// 格式版本，`MeshData` 或编码方式变化时递增，使旧缓存失效
+const VERSION: u32 = 2;
+
+/// 源文件对应的缓存文件路径（源文件名后追加 `.distmesh`）
+pub fn cache_path(source: &Path) -> PathBuf {
+    let mut path = source.as_os_str().to_os_string();
+    path.push(".");
+    path.push(CACHE_EXTENSION);
+    PathBuf::from(path)
+}
+
+/// 通过缓存加载网格
+///
+/// 缓存有效时直接读取，否则调用 `load` 解析源文件并写入缓存。缓存读写失败不影响加载，
+/// 只输出日志；`load` 的错误原样返回。
+pub fn load_cached(source: &Path, load: impl FnOnce(&Path) -> Result<MeshData>) -> Result<MeshData> {
+    let cache = cache_path(source);
+    let key = CacheKey::of(source);
+
+    if let Some(key) = &key {
+        match fs::read(&cache) {
+            Ok(bytes) => match decode(&bytes, key) {
+                Some(mesh_data) => {
+                    info!("Mesh cache hit: {}", cache.display());
+                    return Ok(mesh_data);
+                }
+                None => info!("Mesh cache is stale, reloading {}", source.display()),
+            },
+            Err(e) => debug!("No mesh cache at {}: {}", cache.display(), e),
+        }
+    }
+
+    let mesh_data = load(source)?;
+    match key {
+        Some(key) => match fs::write(&cache, encode(&mesh_data, &key)) {
+            Ok(()) => info!("Mesh cache written: {}", cache.display()),
+            Err(e) => warn!("Failed to write mesh cache {}: {}", cache.display(), e),
+        },
+        None => warn!("Cannot read the modification time of {}, mesh cache disabled", source.display()),
+    }
+    Ok(mesh_data)
+}
+
+/// 缓存键：源文件路径、修改时间和大小
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CacheKey {
+    path: String,
+    modified_secs: u64,
+    modified_nanos: u32,
+    len: u64,
+}
+
+impl CacheKey {
+    /// 读取源文件的缓存键，文件不存在或平台不支持修改时间时返回 `None`
+    fn of(source: &Path) -> Option<Self> {
+        let metadata = fs::metadata(source).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            path: source.to_string_lossy().into_owned(),
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+            len: metadata.len(),
+        })
+    }
+}
+
+/// 编码网格和缓存键
+fn encode(mesh_data: &MeshData, key: &CacheKey) -> Vec<u8> {
+    let mut writer = Writer(Vec::with_capacity(
+        64 + mesh_data.vertices.len() * std::mem::size_of::<Vertex>() + mesh_data.indices.len() * 4,
+    ));
+    writer.0.extend_from_slice(MAGIC);
+    writer.u32(VERSION);
+    writer.string(Some(&key.path));
+    writer.u64(key.modified_secs);
+    writer.u32(key.modified_nanos);
+    writer.u64(key.len);
+
+    writer.string(mesh_data.name.as_deref());
+    writer.u32(match mesh_data.topology {
+        PrimitiveTopology::TriangleList => 0,
+        PrimitiveTopology::TriangleStrip => 1,
+    });
+    writer.bytes(bytemuck::cast_slice(&mesh_data.vertices));
+    writer.bytes(bytemuck::cast_slice(&mesh_data.indices));
+
+    writer.u32(mesh_data.subsets.len() as u32);
+    for subset in &mesh_data.subsets {
+        for value in [subset.id, subset.vertex_start, subset.vertex_count, subset.face_start, subset.face_count] {
+            writer.u32(value);
+        }
+    }
+
+    writer.u32(mesh_data.materials.len() as u32);
+    for material in &mesh_data.materials {
+        writer.string(material.name.as_deref());
+        for value in material.base_color {
+            writer.f32(value);
+        }
+        writer.f32(material.metallic);
+        writer.f32(material.roughness);
+        writer.0.push(material.transparent as u8);
+    }
+
+    writer.0
+}
+
+/// 解码缓存，格式不对、版本不同或缓存键不匹配时返回 `None`
+fn decode(bytes: &[u8], key: &CacheKey) -> Option<MeshData> {
+    let mut reader = Reader(bytes);
+    if reader.take(MAGIC.len())? != MAGIC || reader.u32()? != VERSION {
+        return None;
+    }
+    let cached_key = CacheKey {
+        path: reader.string()??,
+        modified_secs: reader.u64()?,
+        modified_nanos: reader.u32()?,
+        len: reader.u64()?,
+    };
+    if cached_key != *key {
+        return None;
+    }
+
+    let name = reader.string()?;
+    let topology = match reader.u32()? {
+        0 => PrimitiveTopology::TriangleList,
+        1 => PrimitiveTopology::TriangleStrip,
+        _ => return None,
+    };
+    let vertices = reader.pod_vec::<Vertex>()?;
+    let indices = reader.pod_vec::<u32>()?;
+
+    let subset_count = reader.u32()?;
+    let subsets = (0..subset_count)
+        .map(|_| Some(Subset::new(reader.u32()?, reader.u32()?, reader.u32()?, reader.u32()?, reader.u32()?)))
+        .collect::<Option<Vec<_>>>()?;
+
+    let material_count = reader.u32()?;
+    let materials = (0..material_count)
+        .map(|_| {
+            Some(Material {
+                name: reader.string()?,
+                base_color: [reader.f32()?, reader.f32()?, reader.f32()?, reader.f32()?],
+                metallic: reader.f32()?,
+                roughness: reader.f32()?,
+                transparent: reader.take(1)?[0] != 0,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    reader.0.is_empty().then_some(MeshData {
+        vertices,
+        indices,
+        topology,
+        subsets,
+        materials,
+        name,
+    })
+}
+
+/// 小端序写入
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u32(&mut self, value: u32) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// 长度前缀的字节串
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.u32(bytes.len() as u32);
+        self.0.extend_from_slice(bytes);
+    }
+
+    /// 可选字符串：1 字节的标记后跟字节串
+    fn string(&mut self, value: Option<&str>) {
+        match value {
+            Some(value) => {
+                self.0.push(1);
+                self.bytes(value.as_bytes());
+            }
+            None => self.0.push(0),
+        }
+    }
+}
+
+/// 与 [`Writer`] 对应的读取，数据不足时返回 `None`
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Option<f32> {
+        self.array().map(f32::from_le_bytes)
+    }
+
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    /// 外层 `None` 表示数据损坏，内层为字符串本身是否存在
+    fn string(&mut self) -> Option<Option<String>> {
+        match self.take(1)?[0] {
+            0 => Some(None),
+            1 => String::from_utf8(self.bytes()?.to_vec()).ok().map(Some),
+            _ => None,
+        }
+    }
+
+    /// 按元素大小整除的字节串，复制到对齐的数组中
+    fn pod_vec<T: bytemuck::Pod>(&mut self) -> Option<Vec<T>> {
+        let bytes = self.bytes()?;
+        bytes
+            .len()
+            .is_multiple_of(std::mem::size_of::<T>())
+            .then(|| bytemuck::pod_collect_to_vec(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use crate::geometry::loaders::load_mesh;
+
+    const TRIANGLE_OBJ: &str = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nf 1//1 2//1 3//1\n";
+
+    #[test]
+    fn test_second_load_hits_cache() {
+        let dir = std::env::temp_dir().join(format!("dist_render_mesh_cache_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("triangle.obj");
+        fs::write(&source, TRIANGLE_OBJ).unwrap();
+        let _ = fs::remove_file(cache_path(&source));
+
+        let parses = Cell::new(0);
+        let load = |path: &Path| {
+            parses.set(parses.get() + 1);
+            load_mesh(path)
+        };
+
+        let parsed = load_cached(&source, load).unwrap();
+        let cached = load_cached(&source, load).unwrap();
+        assert_eq!(parses.get(), 1);
+        assert!(cache_path(&source).exists());
+        assert_eq!(bytemuck::cast_slice::<Vertex, u8>(&cached.vertices), bytemuck::cast_slice::<Vertex, u8>(&parsed.vertices));
+        assert_eq!(cached.indices, parsed.indices);
+        assert_eq!(cached.topology, parsed.topology);
+        assert_eq!(cached.subsets, parsed.subsets);
+        assert_eq!(cached.materials, parsed.materials);
+        assert_eq!(cached.name, parsed.name);
+
+        // 源文件变化后缓存失效
+        fs::write(&source, format!("{}v 0 0 1\n", TRIANGLE_OBJ)).unwrap();
+        load_cached(&source, load).unwrap();
+        assert_eq!(parses.get(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_round_trip_with_materials() {
+        let key = CacheKey {
+            path: "model.glb".to_string(),
+            modified_secs: 1,
+            modified_nanos: 2,
+            len: 3,
+        };
+        let mut mesh_data = MeshData::with_name("model");
+        mesh_data.vertices.push(Vertex::new([1.0, 2.0, 3.0], [0.0, 1.0, 0.0], [0.5, 0.5], [1.0, 0.0, 0.0]));
+        mesh_data.indices = vec![0, 0, 0];
+        mesh_data.subsets.push(Subset::new(0, 0, 1, 0, 1));
+        mesh_data.materials.push(Material {
+            name: Some("red".to_string()),
+            base_color: [1.0, 0.0, 0.0, 1.0],
+            metallic: 0.25,
+            roughness: 0.75,
+            transparent: true,
+        });
+
+        let bytes = encode(&mesh_data, &key);
+        let decoded = decode(&bytes, &key).unwrap();
+        assert_eq!(decoded.vertices[0].position, [1.0, 2.0, 3.0]);
+        assert_eq!(decoded.subsets, mesh_data.subsets);
+        assert_eq!(decoded.materials, mesh_data.materials);
+        assert_eq!(decoded.name.as_deref(), Some("model"));
+
+        // 缓存键不同、数据截断时视为无效
+        assert!(decode(&bytes, &CacheKey { len: 4, ..key.clone() }).is_none());
+        assert!(decode(&bytes[..bytes.len() - 1], &key).is_none());
+    }
+}
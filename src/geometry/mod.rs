@@ -7,7 +7,16 @@
 ///
 /// - `vertex`: 顶点数据结构定义
 /// - `mesh`: 网格数据和子网格结构
+/// - `material`: 金属-粗糙度材质
+/// - `coordinate`: 坐标系定义（上轴、手性）及转换
+/// - `simplify`: 基于二次误差度量的网格简化（`MeshData::decimate`）
+/// - `subdivide`: Loop 细分平滑网格（`MeshData::subdivide`）
+/// - `optimize`: 顶点缓存和顶点读取顺序优化（`MeshData::optimize_vertex_cache`）
+/// - `skinning`: 骨骼、骨骼动画片段和蒙皮网格
+/// - `primitives`: 程序化基本几何体（立方体、球、平面、圆柱）
+/// - `uv`: UV 布局检查（重叠和超出 [0, 1] 的纹理坐标）
 /// - `loaders`: 各种格式的模型加载器
+/// - `mesh_cache`: 加载结果的磁盘缓存（`.distmesh`），避免每次启动重新解析大型模型
 ///
 /// # 几何处理
 ///
@@ -41,6 +50,15 @@
 /// ```
 pub mod vertex;
 pub mod mesh;
+pub mod material;
+pub mod coordinate;
 pub mod loaders;
+pub mod mesh_cache;
+pub mod simplify;
+pub mod subdivide;
+pub mod optimize;
+pub mod skinning;
+pub mod primitives;
+pub mod uv;
 
 // 重新导出常用类型
@@ -0,0 +1,352 @@
+//! 顶点缓存与顶点读取优化
+//!
+//! - [`MeshData::optimize_vertex_cache`]：按 Tom Forsyth 的线性速度顶点缓存优化算法
+//!   重排三角形顺序，提高变换后顶点缓存的命中率
+//! - [`MeshData::optimize_vertex_fetch`]：按索引首次引用的顺序重排顶点，提高顶点读取的局部性
+//!
+//! 两者都只改变顺序，不增删三角形或改变三角形的绕序；重排只在子网格内部进行，
+//! 子网格的三角形范围保持不变。缓存效率用 ACMR（每个三角形平均的缓存未命中次数）衡量，
+//! 理想值接近 0.5，最差为 3.0。
+
+use super::mesh::{MeshData, Subset};
+
+/// 计算 ACMR 时模拟的 FIFO 缓存大小（与常见硬件的变换后缓存相当）
+pub const ACMR_CACHE_SIZE: usize = 16;
+
+/// Forsyth 算法模拟的 LRU 缓存大小
+const CACHE_SIZE: usize = 32;
+const CACHE_DECAY_POWER: f32 = 1.5;
+/// 最近一个三角形的三个顶点使用固定分数，避免偏向重复使用同一条边
+const LAST_TRIANGLE_SCORE: f32 = 0.75;
+const VALENCE_BOOST_SCALE: f32 = 2.0;
+const VALENCE_BOOST_POWER: f32 = 0.5;
+
+/// 顶点缓存优化前后的 ACMR
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CacheOptimization {
+    /// 优化前的 ACMR
+    pub acmr_before: f32,
+
+    /// 优化后的 ACMR
+    pub acmr_after: f32,
+}
+
+/// 在 `cache_size` 大小的 FIFO 缓存上计算 ACMR
+///
+/// 没有三角形时返回 0。
+pub fn acmr(indices: &[u32], cache_size: usize) -> f32 {
+    let triangle_count = indices.len() / 3;
+    if triangle_count == 0 {
+        return 0.0;
+    }
+
+    let mut cache: Vec<u32> = Vec::with_capacity(cache_size);
+    let mut head = 0;
+    let mut misses = 0usize;
+    for &index in &indices[..triangle_count * 3] {
+        if cache.contains(&index) {
+            continue;
+        }
+        misses += 1;
+        if cache.len() < cache_size {
+            cache.push(index);
+        } else if cache_size > 0 {
+            cache[head] = index;
+            head = (head + 1) % cache_size;
+        }
+    }
+
+    misses as f32 / triangle_count as f32
+}
+
+impl MeshData {
+    /// 重排三角形以提高变换后顶点缓存的命中率
+    ///
+    /// 每个子网格（没有子网格时为整个网格）独立优化，返回整个索引缓冲优化前后的 ACMR。
+    /// 三角形带先转换为三角形列表。
+    pub fn optimize_vertex_cache(&mut self) -> CacheOptimization {
+        self.to_triangle_list();
+        let acmr_before = acmr(&self.indices, ACMR_CACHE_SIZE);
+
+        let triangle_count = self.triangle_count();
+        for range in face_ranges(&self.subsets, triangle_count) {
+            let indices = &mut self.indices[range.start * 3..range.end * 3];
+            let optimized = forsyth_order(indices, self.vertices.len());
+            indices.copy_from_slice(&optimized);
+        }
+
+        let acmr_after = acmr(&self.indices, ACMR_CACHE_SIZE);
+        CacheOptimization { acmr_before, acmr_after }
+    }
+
+    /// 按索引首次引用的顺序重排顶点
+    ///
+    /// 未被引用的顶点保留并移到末尾。子网格的顶点范围更新为其引用的顶点所在区间。
+    /// 应在 [`optimize_vertex_cache`](Self::optimize_vertex_cache) 之后调用。
+    pub fn optimize_vertex_fetch(&mut self) {
+        self.to_triangle_list();
+        let mut remap = vec![u32::MAX; self.vertices.len()];
+        let mut next = 0u32;
+        for index in &mut self.indices {
+            let slot = &mut remap[*index as usize];
+            if *slot == u32::MAX {
+                *slot = next;
+                next += 1;
+            }
+            *index = *slot;
+        }
+        for slot in remap.iter_mut().filter(|slot| **slot == u32::MAX) {
+            *slot = next;
+            next += 1;
+        }
+
+        let mut vertices = self.vertices.clone();
+        for (old, &new) in remap.iter().enumerate() {
+            vertices[new as usize] = self.vertices[old];
+        }
+        self.vertices = vertices;
+
+        let triangle_count = self.triangle_count();
+        for subset in &mut self.subsets {
+            let end = ((subset.face_start + subset.face_count) as usize).min(triangle_count);
+            let start = (subset.face_start as usize).min(end);
+            let referenced = &self.indices[start * 3..end * 3];
+            if let (Some(&min), Some(&max)) = (referenced.iter().min(), referenced.iter().max()) {
+                subset.vertex_start = min;
+                subset.vertex_count = max - min + 1;
+            }
+        }
+    }
+}
+
+/// 需要独立优化的三角形范围；没有子网格时为整个网格
+fn face_ranges(subsets: &[Subset], triangle_count: usize) -> Vec<std::ops::Range<usize>> {
+    if subsets.is_empty() {
+        return std::iter::once(0..triangle_count).collect();
+    }
+
+    subsets
+        .iter()
+        .map(|subset| {
+            let end = ((subset.face_start + subset.face_count) as usize).min(triangle_count);
+            (subset.face_start as usize).min(end)..end
+        })
+        .collect()
+}
+
+/// 顶点分数：缓存位置越靠前、剩余三角形越少，分数越高
+fn vertex_score(cache_position: Option<usize>, remaining: u32) -> f32 {
+    if remaining == 0 {
+        return -1.0;
+    }
+
+    let cache_score = match cache_position {
+        None => 0.0,
+        Some(position) if position < 3 => LAST_TRIANGLE_SCORE,
+        Some(position) => {
+            let scale = 1.0 / (CACHE_SIZE - 3) as f32;
+            (1.0 - (position - 3) as f32 * scale).powf(CACHE_DECAY_POWER)
+        }
+    };
+    cache_score + VALENCE_BOOST_SCALE * (remaining as f32).powf(-VALENCE_BOOST_POWER)
+}
+
+/// Forsyth 算法：每次输出分数最高的三角形，并模拟 LRU 缓存更新相关顶点的分数
+fn forsyth_order(indices: &[u32], vertex_count: usize) -> Vec<u32> {
+    let triangle_count = indices.len() / 3;
+
+    // 每个顶点相邻的三角形（CSR 形式）
+    let mut offsets = vec![0usize; vertex_count + 1];
+    for &v in indices {
+        offsets[v as usize + 1] += 1;
+    }
+    for i in 0..vertex_count {
+        offsets[i + 1] += offsets[i];
+    }
+    let mut adjacency = vec![0usize; indices.len()];
+    let mut fill = offsets.clone();
+    for (i, &v) in indices.iter().enumerate() {
+        adjacency[fill[v as usize]] = i / 3;
+        fill[v as usize] += 1;
+    }
+
+    let mut remaining: Vec<u32> = (0..vertex_count).map(|v| (offsets[v + 1] - offsets[v]) as u32).collect();
+    let mut cache_position: Vec<Option<usize>> = vec![None; vertex_count];
+    let mut score: Vec<f32> = (0..vertex_count).map(|v| vertex_score(None, remaining[v])).collect();
+    let triangle_score = |score: &[f32], t: usize| -> f32 {
+        indices[t * 3..t * 3 + 3].iter().map(|&v| score[v as usize]).sum()
+    };
+    let mut triangle_scores: Vec<f32> = (0..triangle_count).map(|t| triangle_score(&score, t)).collect();
+    let mut emitted = vec![false; triangle_count];
+
+    let mut output = Vec::with_capacity(indices.len());
+    let mut cache: Vec<u32> = Vec::with_capacity(CACHE_SIZE + 3);
+    let mut best = (0..triangle_count).max_by(|&a, &b| triangle_scores[a].total_cmp(&triangle_scores[b]));
+    let mut scan_cursor = 0;
+
+    while let Some(triangle) = best {
+        emitted[triangle] = true;
+        let corners = &indices[triangle * 3..triangle * 3 + 3];
+        output.extend_from_slice(corners);
+
+        // 三个顶点移到缓存最前面，其余顶点依次后移
+        let mut new_cache: Vec<u32> = corners.to_vec();
+        new_cache.extend(cache.iter().copied().filter(|v| !corners.contains(v)));
+        for &v in corners {
+            remaining[v as usize] -= 1;
+        }
+        for &v in new_cache.iter().skip(CACHE_SIZE) {
+            cache_position[v as usize] = None;
+        }
+        new_cache.truncate(CACHE_SIZE);
+        for (position, &v) in new_cache.iter().enumerate() {
+            cache_position[v as usize] = Some(position);
+        }
+
+        // 更新缓存内外受影响顶点的分数，再更新它们相邻三角形的分数
+        let mut touched: Vec<u32> = new_cache.clone();
+        touched.extend(cache.iter().copied().filter(|v| cache_position[*v as usize].is_none()));
+        for &v in &touched {
+            score[v as usize] = vertex_score(cache_position[v as usize], remaining[v as usize]);
+        }
+
+        best = None;
+        let mut best_score = f32::MIN;
+        for &v in &touched {
+            for &t in &adjacency[offsets[v as usize]..offsets[v as usize + 1]] {
+                if emitted[t] {
+                    continue;
+                }
+                triangle_scores[t] = triangle_score(&score, t);
+                if triangle_scores[t] > best_score {
+                    best_score = triangle_scores[t];
+                    best = Some(t);
+                }
+            }
+        }
+        cache = new_cache;
+
+        // 缓存中没有可用三角形时取下一个未输出的三角形
+        if best.is_none() {
+            while scan_cursor < triangle_count && emitted[scan_cursor] {
+                scan_cursor += 1;
+            }
+            best = (scan_cursor < triangle_count).then_some(scan_cursor);
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::vertex::Vertex;
+
+    /// `n x n` 个格子的平面网格，三角形按行交错打乱
+    fn shuffled_grid(n: u32) -> MeshData {
+        let mut mesh = MeshData::new();
+        for y in 0..=n {
+            for x in 0..=n {
+                let p = [x as f32, y as f32, 0.0];
+                mesh.vertices.push(Vertex::new(p, [0.0, 0.0, 1.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+            }
+        }
+
+        let vertex = |x: u32, y: u32| y * (n + 1) + x;
+        let mut triangles = Vec::new();
+        for y in 0..n {
+            for x in 0..n {
+                triangles.push([vertex(x, y), vertex(x + 1, y), vertex(x, y + 1)]);
+                triangles.push([vertex(x + 1, y), vertex(x + 1, y + 1), vertex(x, y + 1)]);
+            }
+        }
+        // 固定步长跳跃访问，破坏局部性
+        let count = triangles.len();
+        for i in 0..count {
+            mesh.indices.extend_from_slice(&triangles[(i * 97) % count]);
+        }
+        mesh
+    }
+
+    /// 以位置表示的三角形集合，每个三角形旋转到最小顶点在前（保持绕序）
+    fn triangle_set(mesh: &MeshData) -> Vec<[[u32; 3]; 3]> {
+        let mut triangles: Vec<[[u32; 3]; 3]> = mesh
+            .indices
+            .chunks_exact(3)
+            .map(|t| {
+                let corners = [0, 1, 2].map(|k| mesh.vertices[t[k] as usize].position.map(f32::to_bits));
+                let start = (0..3).min_by_key(|&k| corners[k]).unwrap();
+                [0, 1, 2].map(|k| corners[(start + k) % 3])
+            })
+            .collect();
+        triangles.sort();
+        triangles
+    }
+
+    #[test]
+    fn test_acmr_of_simple_strips() {
+        assert_eq!(acmr(&[], ACMR_CACHE_SIZE), 0.0);
+        assert_eq!(acmr(&[0, 1, 2], ACMR_CACHE_SIZE), 3.0);
+        // 两个共享一条边的三角形只多一次未命中
+        assert_eq!(acmr(&[0, 1, 2, 2, 1, 3], ACMR_CACHE_SIZE), 2.0);
+    }
+
+    #[test]
+    fn test_optimize_vertex_cache_preserves_triangles() {
+        let mut mesh = shuffled_grid(16);
+        let original = triangle_set(&mesh);
+
+        let result = mesh.optimize_vertex_cache();
+
+        assert_eq!(triangle_set(&mesh), original);
+        assert!(result.acmr_after < result.acmr_before, "{:?}", result);
+        assert!(result.acmr_after < 1.0, "{:?}", result);
+        assert_eq!(result.acmr_after, acmr(&mesh.indices, ACMR_CACHE_SIZE));
+    }
+
+    #[test]
+    fn test_optimize_keeps_subset_ranges() {
+        let mut mesh = shuffled_grid(8);
+        let half = mesh.triangle_count() as u32 / 2;
+        mesh.subsets = vec![
+            Subset::new(0, 0, mesh.vertex_count() as u32, 0, half),
+            Subset::new(1, 0, mesh.vertex_count() as u32, half, half),
+        ];
+        let first_half: Vec<u32> = mesh.indices[..(half * 3) as usize].to_vec();
+        let original = triangle_set(&mesh);
+
+        mesh.optimize_vertex_cache();
+        mesh.optimize_vertex_fetch();
+
+        assert_eq!(triangle_set(&mesh), original);
+        assert!(mesh.validate().is_ok());
+
+        // 第一个子网格的三角形仍位于其范围内
+        let mut before = MeshData::new();
+        before.vertices = shuffled_grid(8).vertices;
+        before.indices = first_half;
+        let mut after = MeshData::new();
+        after.vertices = mesh.vertices.clone();
+        after.indices = mesh.indices[..(half * 3) as usize].to_vec();
+        assert_eq!(triangle_set(&after), triangle_set(&before));
+    }
+
+    #[test]
+    fn test_optimize_vertex_fetch_orders_by_first_use() {
+        let mut mesh = shuffled_grid(4);
+        mesh.optimize_vertex_cache();
+        let original = triangle_set(&mesh);
+
+        mesh.optimize_vertex_fetch();
+
+        assert_eq!(triangle_set(&mesh), original);
+        let mut next = 0;
+        for &index in &mesh.indices {
+            assert!(index <= next);
+            if index == next {
+                next += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,629 @@
+/// 程序化基本几何体模块
+///
+/// 生成立方体、UV 球、平面、圆柱、圆环和圆锥的 `MeshData`，包含单位法线、
+/// 范围在 [0, 1] 内的 UV、切线和索引。三角形绕序由 [`Winding`] 参数指定（从外侧看），
+/// 以匹配目标后端管线的正面约定；无论哪种绕序，法线都朝外。
+/// 不依赖任何模型文件或渲染后端，可直接用于搭建场景、测试光照和作为模型缺失时的占位网格。
+/// 所有几何体中心都在原点，轴向几何体（圆柱、圆环、圆锥）沿 Y 轴。
+///
+/// # 使用示例
+///
+/// ```rust
+/// use dist_render::geometry::primitives::{self, Winding};
+///
+/// let sphere = primitives::uv_sphere(1.0, 32, 16, Winding::Ccw);
+/// let floor = primitives::plane(10.0, 4, Winding::Ccw);
+/// let torus = primitives::torus(1.0, 0.25, 48, 16, Winding::Cw);
+///
+/// assert!(sphere.validate().is_ok());
+/// assert_eq!(floor.triangle_count(), 4 * 4 * 2);
+/// assert_eq!(torus.triangle_count(), 48 * 16 * 2);
+/// ```
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{PI, TAU};
+
+use crate::geometry::mesh::{MeshData, PrimitiveTopology, PRIMITIVE_RESTART_INDEX};
+use crate::geometry::vertex::Vertex;
+use crate::math::Vector3;
+
+/// 三角形绕序（从几何体外侧看）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Winding {
+    /// 逆时针为正面（引擎约定，wgpu 管线使用 `FrontFace::Ccw`）
+    #[default]
+    Ccw,
+    /// 顺时针为正面（Vulkan 管线使用 `FrontFace::Clockwise`）
+    Cw,
+}
+
+/// 基本几何体类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrimitiveShape {
+    /// 单个三角形
+    Triangle,
+    /// 边长为 1 的立方体
+    #[default]
+    Cube,
+    /// 半径为 0.5 的 UV 球
+    Sphere,
+    /// 边长为 1、朝向 +Y 的平面（三角形带）
+    Plane,
+    /// 半径为 0.5、高为 1 的圆柱
+    Cylinder,
+    /// 外径为 1、管半径为 0.15 的圆环
+    Torus,
+    /// 底面半径为 0.5、高为 1 的圆锥
+    Cone,
+}
+
+impl PrimitiveShape {
+    /// 生成默认尺寸的网格（都位于单位包围盒内，中心在原点）
+    pub fn mesh(self, winding: Winding) -> MeshData {
+        match self {
+            PrimitiveShape::Triangle => triangle(winding),
+            PrimitiveShape::Cube => cube(1.0, 1, winding),
+            PrimitiveShape::Sphere => uv_sphere(0.5, 32, 16, winding),
+            PrimitiveShape::Plane => plane_strip(1.0, 1, winding),
+            PrimitiveShape::Cylinder => cylinder(0.5, 1.0, 32, winding),
+            PrimitiveShape::Torus => torus(0.35, 0.15, 32, 16, winding),
+            PrimitiveShape::Cone => cone(0.5, 1.0, 32, winding),
+        }
+    }
+}
+
+/// XY 平面上朝向 +Z 的三角形，顶点与渲染器的默认三角形相同
+pub fn triangle(winding: Winding) -> MeshData {
+    let mut mesh = MeshData::with_name("Triangle");
+    let normal = [0.0, 0.0, 1.0];
+    let tangent = [1.0, 0.0, 0.0];
+    mesh.vertices = vec![
+        Vertex::new([0.0, 0.5, 0.0], normal, [0.5, 0.0], tangent),
+        Vertex::new([0.5, -0.5, 0.0], normal, [1.0, 1.0], tangent),
+        Vertex::new([-0.5, -0.5, 0.0], normal, [0.0, 1.0], tangent),
+    ];
+    // 顶点按顺时针排列，从 +Z 看逆时针的顺序为 0, 2, 1
+    mesh.indices = vec![0, 2, 1];
+    with_winding(mesh, winding)
+}
+
+/// 边长为 `size` 的立方体，每个面每边细分为 `subdivisions` 段（至少 1）
+///
+/// 每个面使用独立顶点，保证棱边的法线不被平均；不细分时为 24 个顶点、36 个索引。
+///
+/// ```rust
+/// use dist_render::geometry::primitives::{cube, Winding};
+///
+/// let mesh = cube(2.0, 1, Winding::Ccw);
+/// assert_eq!((mesh.vertex_count(), mesh.index_count()), (24, 36));
+/// ```
+pub fn cube(size: f32, subdivisions: u32, winding: Winding) -> MeshData {
+    let mut mesh = MeshData::with_name("Cube");
+    let h = size * 0.5;
+    // (法线, 面内 u 轴, 面内 v 轴)，u x v = 法线
+    let faces = [
+        (Vector3::x(), -Vector3::z(), Vector3::y()),
+        (-Vector3::x(), Vector3::z(), Vector3::y()),
+        (Vector3::y(), Vector3::x(), -Vector3::z()),
+        (-Vector3::y(), Vector3::x(), Vector3::z()),
+        (Vector3::z(), Vector3::x(), Vector3::y()),
+        (-Vector3::z(), -Vector3::x(), Vector3::y()),
+    ];
+    for (normal, u, v) in faces {
+        push_grid(&mut mesh, normal * h, u * h, v * h, subdivisions.max(1));
+    }
+    with_winding(mesh, winding)
+}
+
+/// 边长为 `size`、朝向 +Y 的平面，每边细分为 `subdivisions` 段（至少 1）
+pub fn plane(size: f32, subdivisions: u32, winding: Winding) -> MeshData {
+    let mut mesh = MeshData::with_name("Plane");
+    let h = size * 0.5;
+    push_grid(&mut mesh, Vector3::zeros(), Vector3::x() * h, -Vector3::z() * h, subdivisions.max(1));
+    with_winding(mesh, winding)
+}
+
+/// 与 [`plane`] 相同的平面（地形网格），索引为带图元重启的三角形带
+///
+/// 每行网格一条带，行之间插入重启索引；三角形及其绕序与 [`plane`] 相同，
+/// 索引数量约为三角形列表的三分之一。
+///
+/// ```rust
+/// use dist_render::geometry::primitives::{plane_strip, Winding};
+///
+/// let terrain = plane_strip(100.0, 64, Winding::Ccw);
+/// assert_eq!(terrain.triangle_count(), 64 * 64 * 2);
+/// assert_eq!(terrain.index_count(), 64 * 65 * 2 + 63);
+/// ```
+pub fn plane_strip(size: f32, subdivisions: u32, winding: Winding) -> MeshData {
+    let divisions = subdivisions.max(1);
+    let mut mesh = plane(size, divisions, Winding::Ccw);
+    let stride = divisions + 1;
+
+    mesh.indices.clear();
+    for j in 0..divisions {
+        if j > 0 {
+            mesh.indices.push(PRIMITIVE_RESTART_INDEX);
+        }
+        // 上下两行交替，偶数三角形为 (d, a, c)，奇数三角形为 (a, b, c)；交换两行的顺序即反转绕序
+        for i in 0..stride {
+            let (upper, lower) = ((j + 1) * stride + i, j * stride + i);
+            let pair = match winding {
+                Winding::Ccw => [upper, lower],
+                Winding::Cw => [lower, upper],
+            };
+            mesh.indices.extend_from_slice(&pair);
+        }
+    }
+    mesh.topology = PrimitiveTopology::TriangleStrip;
+    mesh
+}
+
+/// 半径为 `radius` 的 UV 球
+///
+/// 经线方向 `segments` 段（至少 3），纬线方向 `rings` 段（至少 2）。
+/// 两极的顶点按经线重复以保证 UV 连续，两极的退化三角形不生成。
+pub fn uv_sphere(radius: f32, segments: u32, rings: u32, winding: Winding) -> MeshData {
+    let segments = segments.max(3);
+    let rings = rings.max(2);
+    let mut mesh = MeshData::with_name("Sphere");
+
+    for ring in 0..=rings {
+        let theta = PI * ring as f32 / rings as f32;
+        for segment in 0..=segments {
+            let phi = TAU * segment as f32 / segments as f32;
+            let normal = Vector3::new(theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin());
+            let tangent = [-phi.sin(), 0.0, phi.cos()];
+            mesh.vertices.push(Vertex::new(
+                (normal * radius).into(),
+                normal.into(),
+                [segment as f32 / segments as f32, ring as f32 / rings as f32],
+                tangent,
+            ));
+        }
+    }
+
+    let stride = segments + 1;
+    for ring in 0..rings {
+        for segment in 0..segments {
+            let a = ring * stride + segment;
+            let b = a + stride;
+            if ring != 0 {
+                mesh.indices.extend_from_slice(&[a, a + 1, b]);
+            }
+            if ring != rings - 1 {
+                mesh.indices.extend_from_slice(&[a + 1, b + 1, b]);
+            }
+        }
+    }
+    with_winding(mesh, winding)
+}
+
+/// 半径为 `radius`、高为 `height` 的圆柱（含上下底面），中心在原点，轴沿 Y
+///
+/// 圆周分为 `segments` 段（至少 3）。侧面和底面使用独立顶点。
+pub fn cylinder(radius: f32, height: f32, segments: u32, winding: Winding) -> MeshData {
+    let segments = segments.max(3);
+    let h = height * 0.5;
+    let mut mesh = MeshData::with_name("Cylinder");
+
+    // 侧面：上下两圈，接缝处顶点重复
+    for (row, y) in [h, -h].into_iter().enumerate() {
+        for segment in 0..=segments {
+            let phi = TAU * segment as f32 / segments as f32;
+            let (sin, cos) = phi.sin_cos();
+            mesh.vertices.push(Vertex::new(
+                [cos * radius, y, sin * radius],
+                [cos, 0.0, sin],
+                [segment as f32 / segments as f32, row as f32],
+                [-sin, 0.0, cos],
+            ));
+        }
+    }
+    let stride = segments + 1;
+    for segment in 0..segments {
+        let a = segment;
+        let b = a + stride;
+        mesh.indices.extend_from_slice(&[a, a + 1, b, a + 1, b + 1, b]);
+    }
+
+    // 底面：中心点加一圈顶点
+    for (y, normal) in [(h, 1.0f32), (-h, -1.0)] {
+        let center = mesh.vertices.len() as u32;
+        mesh.vertices.push(Vertex::new([0.0, y, 0.0], [0.0, normal, 0.0], [0.5, 0.5], [1.0, 0.0, 0.0]));
+        for segment in 0..segments {
+            let phi = TAU * segment as f32 / segments as f32;
+            let (sin, cos) = phi.sin_cos();
+            mesh.vertices.push(Vertex::new(
+                [cos * radius, y, sin * radius],
+                [0.0, normal, 0.0],
+                [0.5 + cos * 0.5, 0.5 + sin * 0.5],
+                [1.0, 0.0, 0.0],
+            ));
+        }
+        for segment in 0..segments {
+            let current = center + 1 + segment;
+            let next = center + 1 + (segment + 1) % segments;
+            if normal > 0.0 {
+                mesh.indices.extend_from_slice(&[center, next, current]);
+            } else {
+                mesh.indices.extend_from_slice(&[center, current, next]);
+            }
+        }
+    }
+    with_winding(mesh, winding)
+}
+
+/// 圆环，中心线半径为 `major_radius`，管半径为 `minor_radius`，绕 Y 轴
+///
+/// 沿中心线分为 `segments` 段（至少 3），管截面分为 `sides` 段（至少 3），接缝处顶点重复。
+pub fn torus(major_radius: f32, minor_radius: f32, segments: u32, sides: u32, winding: Winding) -> MeshData {
+    let segments = segments.max(3);
+    let sides = sides.max(3);
+    let mut mesh = MeshData::with_name("Torus");
+
+    for segment in 0..=segments {
+        let phi = TAU * segment as f32 / segments as f32;
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        for side in 0..=sides {
+            let theta = TAU * side as f32 / sides as f32;
+            let (sin_theta, cos_theta) = theta.sin_cos();
+            let normal = [cos_theta * cos_phi, sin_theta, cos_theta * sin_phi];
+            let ring = major_radius + minor_radius * cos_theta;
+            mesh.vertices.push(Vertex::new(
+                [ring * cos_phi, minor_radius * sin_theta, ring * sin_phi],
+                normal,
+                [segment as f32 / segments as f32, side as f32 / sides as f32],
+                [-sin_phi, 0.0, cos_phi],
+            ));
+        }
+    }
+
+    let stride = sides + 1;
+    for segment in 0..segments {
+        for side in 0..sides {
+            let a = segment * stride + side;
+            let b = a + stride;
+            let c = b + 1;
+            let d = a + 1;
+            mesh.indices.extend_from_slice(&[a, d, c, a, c, b]);
+        }
+    }
+    with_winding(mesh, winding)
+}
+
+/// 底面半径为 `radius`、高为 `height` 的圆锥（含底面），中心在原点，尖端朝 +Y
+///
+/// 圆周分为 `segments` 段（至少 3）。尖端按段重复顶点，使侧面法线沿各段的斜面方向。
+pub fn cone(radius: f32, height: f32, segments: u32, winding: Winding) -> MeshData {
+    let segments = segments.max(3);
+    let h = height * 0.5;
+    let mut mesh = MeshData::with_name("Cone");
+    // 侧面法线：径向分量与高度成正比，竖直分量与半径成正比
+    let side_normal = |phi: f32| {
+        let (sin, cos) = phi.sin_cos();
+        Vector3::new(cos * height, radius, sin * height).normalize().into()
+    };
+
+    for segment in 0..segments {
+        let phi = TAU * (segment as f32 + 0.5) / segments as f32;
+        let (sin, cos) = phi.sin_cos();
+        mesh.vertices.push(Vertex::new(
+            [0.0, h, 0.0],
+            side_normal(phi),
+            [(segment as f32 + 0.5) / segments as f32, 0.0],
+            [-sin, 0.0, cos],
+        ));
+    }
+    let ring = mesh.vertices.len() as u32;
+    for segment in 0..=segments {
+        let phi = TAU * segment as f32 / segments as f32;
+        let (sin, cos) = phi.sin_cos();
+        mesh.vertices.push(Vertex::new(
+            [cos * radius, -h, sin * radius],
+            side_normal(phi),
+            [segment as f32 / segments as f32, 1.0],
+            [-sin, 0.0, cos],
+        ));
+    }
+    for segment in 0..segments {
+        let base = ring + segment;
+        mesh.indices.extend_from_slice(&[segment, base + 1, base]);
+    }
+
+    let center = mesh.vertices.len() as u32;
+    mesh.vertices.push(Vertex::new([0.0, -h, 0.0], [0.0, -1.0, 0.0], [0.5, 0.5], [1.0, 0.0, 0.0]));
+    for segment in 0..segments {
+        let phi = TAU * segment as f32 / segments as f32;
+        let (sin, cos) = phi.sin_cos();
+        mesh.vertices.push(Vertex::new(
+            [cos * radius, -h, sin * radius],
+            [0.0, -1.0, 0.0],
+            [0.5 + cos * 0.5, 0.5 + sin * 0.5],
+            [1.0, 0.0, 0.0],
+        ));
+    }
+    for segment in 0..segments {
+        let current = center + 1 + segment;
+        let next = center + 1 + (segment + 1) % segments;
+        mesh.indices.extend_from_slice(&[center, current, next]);
+    }
+    with_winding(mesh, winding)
+}
+
+/// 按 `winding` 调整以逆时针生成的三角形列表的绕序，法线保持朝外
+fn with_winding(mut mesh: MeshData, winding: Winding) -> MeshData {
+    if winding == Winding::Cw {
+        for triangle in mesh.indices.chunks_exact_mut(3) {
+            triangle.swap(1, 2);
+        }
+    }
+    mesh
+}
+
+/// 追加一个细分的矩形面
+///
+/// 矩形中心为 `center`，`u`/`v` 为两个方向的半边向量，法线为 `u x v` 方向。
+fn push_grid(mesh: &mut MeshData, center: Vector3, u: Vector3, v: Vector3, divisions: u32) {
+    let base = mesh.vertices.len() as u32;
+    let normal: [f32; 3] = u.cross(&v).normalize().into();
+    let tangent: [f32; 3] = u.normalize().into();
+
+    for j in 0..=divisions {
+        for i in 0..=divisions {
+            let s = i as f32 / divisions as f32;
+            let t = j as f32 / divisions as f32;
+            let position = center + u * (2.0 * s - 1.0) + v * (2.0 * t - 1.0);
+            mesh.vertices.push(Vertex::new(position.into(), normal, [s, 1.0 - t], tangent));
+        }
+    }
+
+    let stride = divisions + 1;
+    for j in 0..divisions {
+        for i in 0..divisions {
+            let a = base + j * stride + i;
+            let b = a + 1;
+            let c = b + stride;
+            let d = a + stride;
+            mesh.indices.extend_from_slice(&[a, b, c, a, c, d]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_unit_normals(mesh: &MeshData) {
+        for vertex in &mesh.vertices {
+            let length = Vector3::from(vertex.normal).norm();
+            assert!((length - 1.0).abs() < 1e-5, "normal length {}", length);
+        }
+    }
+
+    /// 闭合几何体的每个三角形都朝外（几何法线与质心方向同向）且与顶点法线一致
+    fn assert_outward_winding(mesh: &MeshData) {
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| Vector3::from(mesh.vertices[triangle[k] as usize].position));
+            let face_normal = (b - a).cross(&(c - a));
+            assert!(face_normal.norm() > 1e-8, "degenerate triangle {:?}", triangle);
+
+            let centroid = (a + b + c) / 3.0;
+            assert!(face_normal.dot(&centroid) > 0.0, "inward triangle {:?}", triangle);
+            let vertex_normal = Vector3::from(mesh.vertices[triangle[0] as usize].normal);
+            assert!(face_normal.dot(&vertex_normal) > 0.0, "normal mismatch {:?}", triangle);
+        }
+    }
+
+    fn assert_uvs_in_unit_range(mesh: &MeshData) {
+        for vertex in &mesh.vertices {
+            assert!(vertex.texcoord.iter().all(|t| (0.0..=1.0).contains(t)), "uv {:?}", vertex.texcoord);
+        }
+    }
+
+    #[test]
+    fn test_cube() {
+        let mesh = cube(2.0, 1, Winding::Ccw);
+
+        assert_eq!(mesh.vertex_count(), 24);
+        assert_eq!(mesh.index_count(), 36);
+        assert!(mesh.validate().is_ok());
+        assert_unit_normals(&mesh);
+        assert_outward_winding(&mesh);
+        assert!(mesh.vertices.iter().all(|v| v.position.iter().all(|p| p.abs() == 1.0)));
+    }
+
+    #[test]
+    fn test_subdivided_cube() {
+        let mesh = cube(1.0, 3, Winding::Ccw);
+
+        assert_eq!(mesh.vertex_count(), 6 * 4 * 4);
+        assert_eq!(mesh.index_count(), 6 * 3 * 3 * 6);
+        assert_unit_normals(&mesh);
+        assert_outward_winding(&mesh);
+        assert_uvs_in_unit_range(&mesh);
+        // 每个顶点都在某个面上
+        assert!(mesh.vertices.iter().all(|v| v.position.iter().any(|p| (p.abs() - 0.5).abs() < 1e-6)));
+    }
+
+    #[test]
+    fn test_uv_sphere() {
+        let mesh = uv_sphere(0.5, 16, 8, Winding::Ccw);
+
+        assert_eq!(mesh.vertex_count(), 17 * 9);
+        assert_eq!(mesh.index_count(), 16 * (8 - 1) * 6);
+        assert!(mesh.validate().is_ok());
+        assert_unit_normals(&mesh);
+        assert_outward_winding(&mesh);
+        for vertex in &mesh.vertices {
+            assert!((Vector3::from(vertex.position).norm() - 0.5).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_subdivided_sphere_on_radius_with_unit_uvs() {
+        let radius = 2.5;
+        let mesh = uv_sphere(radius, 64, 32, Winding::Ccw);
+
+        assert!(mesh.validate().is_ok());
+        assert_uvs_in_unit_range(&mesh);
+        for vertex in &mesh.vertices {
+            let position = Vector3::from(vertex.position);
+            assert!((position.norm() - radius).abs() < 1e-4 * radius);
+            // 球面法线即归一化的位置
+            assert!((position / radius - Vector3::from(vertex.normal)).norm() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_plane() {
+        let mesh = plane(1.0, 4, Winding::Ccw);
+
+        assert_eq!(mesh.vertex_count(), 25);
+        assert_eq!(mesh.index_count(), 4 * 4 * 6);
+        assert!(mesh.validate().is_ok());
+        assert_unit_normals(&mesh);
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| Vector3::from(mesh.vertices[triangle[k] as usize].position));
+            assert!((b - a).cross(&(c - a)).y > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_plane_strip_matches_plane() {
+        // 三角形按最小索引旋转到首位（不改变绕序）后排序比较
+        let normalized = |mesh: &MeshData| {
+            let mut triangles: Vec<[u32; 3]> = mesh
+                .triangles()
+                .into_iter()
+                .map(|t| {
+                    let first = (0..3).min_by_key(|&k| t[k]).unwrap();
+                    [t[first], t[(first + 1) % 3], t[(first + 2) % 3]]
+                })
+                .collect();
+            triangles.sort();
+            triangles
+        };
+
+        for subdivisions in [1, 3, 8] {
+            let list = plane(2.0, subdivisions, Winding::Ccw);
+            let strip = plane_strip(2.0, subdivisions, Winding::Ccw);
+
+            assert!(strip.validate().is_ok());
+            assert_eq!(strip.vertex_count(), list.vertex_count());
+            assert_eq!(strip.triangle_count(), list.triangle_count());
+            assert_eq!(normalized(&strip), normalized(&list));
+            assert!(strip.index_count() < list.index_count());
+        }
+
+        // 转换为列表后得到同样的三角形
+        let mut converted = plane_strip(2.0, 3, Winding::Ccw);
+        converted.to_triangle_list();
+        assert_eq!(converted.topology, PrimitiveTopology::TriangleList);
+        assert_eq!(normalized(&converted), normalized(&plane(2.0, 3, Winding::Ccw)));
+    }
+
+    #[test]
+    fn test_cylinder() {
+        let mesh = cylinder(0.5, 1.0, 12, Winding::Ccw);
+
+        assert_eq!(mesh.vertex_count(), 4 * 12 + 4);
+        assert_eq!(mesh.index_count(), 12 * 12);
+        assert!(mesh.validate().is_ok());
+        assert_unit_normals(&mesh);
+        assert_outward_winding(&mesh);
+    }
+
+    #[test]
+    fn test_torus() {
+        let (major, minor) = (1.0, 0.25);
+        let mesh = torus(major, minor, 24, 12, Winding::Ccw);
+
+        assert_eq!(mesh.vertex_count(), 25 * 13);
+        assert_eq!(mesh.index_count(), 24 * 12 * 6);
+        assert!(mesh.validate().is_ok());
+        assert_unit_normals(&mesh);
+        assert_uvs_in_unit_range(&mesh);
+        for vertex in &mesh.vertices {
+            // 顶点到中心线的距离等于管半径
+            let position = Vector3::from(vertex.position);
+            let center = Vector3::new(position.x, 0.0, position.z).normalize() * major;
+            assert!(((position - center).norm() - minor).abs() < 1e-5);
+        }
+        // 圆环不是凸体，只检查绕序与顶点法线一致
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| Vector3::from(mesh.vertices[triangle[k] as usize].position));
+            let vertex_normal = Vector3::from(mesh.vertices[triangle[0] as usize].normal);
+            assert!((b - a).cross(&(c - a)).dot(&vertex_normal) > 0.0, "winding {:?}", triangle);
+        }
+    }
+
+    #[test]
+    fn test_cone() {
+        let mesh = cone(0.5, 1.0, 16, Winding::Ccw);
+
+        assert_eq!(mesh.vertex_count(), 3 * 16 + 2);
+        assert_eq!(mesh.index_count(), 16 * 6);
+        assert!(mesh.validate().is_ok());
+        assert_unit_normals(&mesh);
+        assert_outward_winding(&mesh);
+        assert_uvs_in_unit_range(&mesh);
+    }
+
+    #[test]
+    fn test_both_windings_keep_outward_normals() {
+        for shape in [
+            PrimitiveShape::Triangle,
+            PrimitiveShape::Cube,
+            PrimitiveShape::Sphere,
+            PrimitiveShape::Plane,
+            PrimitiveShape::Cylinder,
+            PrimitiveShape::Torus,
+            PrimitiveShape::Cone,
+        ] {
+            let ccw = shape.mesh(Winding::Ccw);
+            let cw = shape.mesh(Winding::Cw);
+            assert!(cw.validate().is_ok(), "{:?}", shape);
+            assert_eq!(cw.topology, ccw.topology);
+            assert_eq!(cw.triangle_count(), ccw.triangle_count());
+            // 顶点（含法线）不变，只有绕序相反
+            assert!(
+                cw.vertices.iter().zip(&ccw.vertices).all(|(a, b)| a.position == b.position && a.normal == b.normal),
+                "{:?}",
+                shape
+            );
+
+            for (mesh, sign) in [(&ccw, 1.0), (&cw, -1.0)] {
+                for triangle in mesh.triangles() {
+                    let [a, b, c] = triangle.map(|i| Vector3::from(mesh.vertices[i as usize].position));
+                    let vertex_normal = Vector3::from(mesh.vertices[triangle[0] as usize].normal);
+                    assert!(sign * (b - a).cross(&(c - a)).dot(&vertex_normal) > 0.0, "{:?} {:?}", shape, triangle);
+                }
+            }
+        }
+
+        // 闭合几何体在两种绕序下法线都朝外
+        let cube = cube(1.0, 2, Winding::Cw);
+        for vertex in &cube.vertices {
+            assert!(Vector3::from(vertex.normal).dot(&Vector3::from(vertex.position)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_shapes_fit_unit_box() {
+        for shape in [
+            PrimitiveShape::Triangle,
+            PrimitiveShape::Cube,
+            PrimitiveShape::Sphere,
+            PrimitiveShape::Plane,
+            PrimitiveShape::Cylinder,
+            PrimitiveShape::Torus,
+            PrimitiveShape::Cone,
+        ] {
+            let mesh = shape.mesh(Winding::Ccw);
+            assert!(mesh.validate().is_ok(), "{:?}", shape);
+            assert!(mesh.vertices.iter().all(|v| v.position.iter().all(|p| p.abs() <= 0.5 + 1e-6)));
+        }
+    }
+}
@@ -0,0 +1,616 @@
+//! 网格简化
+//!
+//! 基于二次误差度量（Quadric Error Metrics，Garland & Heckbert 1997）的边折叠简化。
+//! 每个顶点累积相邻三角形平面的二次误差矩阵，每次折叠误差最小的边，
+//! 直到三角形数量降到目标值或没有可以安全折叠的边。
+//!
+//! # 拓扑与属性
+//!
+//! - 折叠前检查链接条件（两端点的公共邻点只能是共享三角形的对顶点），避免产生非流形边
+//! - 折叠后任何相邻三角形翻转或退化时放弃该折叠
+//! - 开放边界附加垂直于边界的约束平面，边界形状基本保持不变
+//! - UV 接缝等位置重合的顶点不会移动，只允许其他顶点折叠到它们上面，接缝不会开裂
+//! - 折叠位置的法线、UV、切线按其在边上的投影参数插值
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use super::mesh::{edge_key, MeshData, Subset};
+use super::vertex::Vertex;
+use crate::math::{Mat3, Vec3};
+
+/// 开放边界约束平面的权重
+const BOUNDARY_WEIGHT: f64 = 100.0;
+
+/// 折叠后相邻三角形法线与原法线夹角余弦的下限，小于该值视为翻转
+const MIN_NORMAL_DOT: f64 = 0.2;
+
+/// 对称 4x4 二次误差矩阵，只存储上三角的 10 个元素
+#[derive(Debug, Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    /// 平面 `n·p + d = 0` 的二次误差矩阵
+    fn from_plane(n: Vec3<f64>, d: f64, weight: f64) -> Self {
+        let (a, b, c) = (n.x, n.y, n.z);
+        Self(
+            [a * a, a * b, a * c, a * d, b * b, b * c, b * d, c * c, c * d, d * d]
+                .map(|q| q * weight),
+        )
+    }
+
+    fn add(&mut self, other: &Quadric) {
+        for (q, o) in self.0.iter_mut().zip(other.0) {
+            *q += o;
+        }
+    }
+
+    /// 点 `p` 到累积平面的距离平方和
+    fn error(&self, p: Vec3<f64>) -> f64 {
+        let q = &self.0;
+        let (x, y, z) = (p.x, p.y, p.z);
+        q[0] * x * x + 2.0 * q[1] * x * y + 2.0 * q[2] * x * z + 2.0 * q[3] * x
+            + q[4] * y * y + 2.0 * q[5] * y * z + 2.0 * q[6] * y
+            + q[7] * z * z + 2.0 * q[8] * z
+            + q[9]
+    }
+
+    /// 误差最小的位置，矩阵奇异时返回 `None`
+    fn optimal_point(&self) -> Option<Vec3<f64>> {
+        let q = &self.0;
+        let a = Mat3::new(q[0], q[1], q[2], q[1], q[4], q[5], q[2], q[5], q[7]);
+        if a.determinant().abs() < 1e-12 {
+            return None;
+        }
+        a.try_inverse().map(|inv| -(inv * Vec3::new(q[3], q[6], q[8])))
+    }
+}
+
+/// 待折叠的边
+#[derive(Debug, Clone, Copy)]
+struct Collapse {
+    cost: f64,
+    v0: u32,
+    v1: u32,
+    /// 入堆时两端点的版本号，任一端点之后被修改则该条目失效
+    stamp: (u32, u32),
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    /// 反向比较，使 `BinaryHeap` 成为按代价排序的最小堆
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| (other.v0, other.v1).cmp(&(self.v0, self.v1)))
+    }
+}
+
+/// 折叠目标
+struct Target {
+    position: Vec3<f64>,
+    /// 在边 v0→v1 上的插值参数
+    t: f32,
+    cost: f64,
+}
+
+/// 简化过程中的工作数据
+struct Simplifier {
+    positions: Vec<Vec3<f64>>,
+    vertices: Vec<Vertex>,
+    triangles: Vec<[u32; 3]>,
+    triangle_alive: Vec<bool>,
+    /// 每个顶点所在的存活三角形
+    vertex_triangles: Vec<Vec<usize>>,
+    quadrics: Vec<Quadric>,
+    /// 位置与其他顶点重合（UV 接缝等）的顶点，不允许移动
+    locked: Vec<bool>,
+    versions: Vec<u32>,
+    heap: BinaryHeap<Collapse>,
+    alive_triangles: usize,
+}
+
+impl Simplifier {
+    fn new(mesh: &MeshData) -> Self {
+        let positions: Vec<Vec3<f64>> = mesh
+            .vertices
+            .iter()
+            .map(|v| Vec3::from(v.position).cast::<f64>())
+            .collect();
+        let triangles: Vec<[u32; 3]> = mesh
+            .indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .collect();
+
+        let vertex_count = positions.len();
+        let mut vertex_triangles = vec![Vec::new(); vertex_count];
+        let mut quadrics = vec![Quadric::default(); vertex_count];
+        let mut edge_faces: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+
+        for (face, triangle) in triangles.iter().enumerate() {
+            for (k, &v) in triangle.iter().enumerate() {
+                vertex_triangles[v as usize].push(face);
+                let w = triangle[(k + 1) % 3];
+                edge_faces.entry(edge_key(v, w)).or_default().push(face);
+            }
+
+            let Some((normal, _)) = face_normal(&positions, triangle) else {
+                continue;
+            };
+            let d = -normal.dot(&positions[triangle[0] as usize]);
+            let quadric = Quadric::from_plane(normal, d, 1.0);
+            for &v in triangle {
+                quadrics[v as usize].add(&quadric);
+            }
+        }
+
+        // 开放边界：添加经过边且垂直于三角形的约束平面
+        for (&(a, b), faces) in &edge_faces {
+            if faces.len() != 1 {
+                continue;
+            }
+            let Some((normal, _)) = face_normal(&positions, &triangles[faces[0]]) else {
+                continue;
+            };
+            let edge = positions[b as usize] - positions[a as usize];
+            let Some(plane_normal) = edge.cross(&normal).try_normalize(1e-12) else {
+                continue;
+            };
+            let d = -plane_normal.dot(&positions[a as usize]);
+            let quadric = Quadric::from_plane(plane_normal, d, BOUNDARY_WEIGHT * edge.norm_squared());
+            quadrics[a as usize].add(&quadric);
+            quadrics[b as usize].add(&quadric);
+        }
+
+        let mut by_position: HashMap<[u32; 3], u32> = HashMap::new();
+        for vertex in &mesh.vertices {
+            *by_position.entry(vertex.position.map(f32::to_bits)).or_default() += 1;
+        }
+        let locked = mesh
+            .vertices
+            .iter()
+            .map(|v| by_position[&v.position.map(f32::to_bits)] > 1)
+            .collect();
+
+        let alive_triangles = triangles.len();
+        let mut simplifier = Self {
+            positions,
+            vertices: mesh.vertices.clone(),
+            triangle_alive: vec![true; triangles.len()],
+            triangles,
+            vertex_triangles,
+            quadrics,
+            locked,
+            versions: vec![0; vertex_count],
+            heap: BinaryHeap::new(),
+            alive_triangles,
+        };
+
+        let mut edges: Vec<(u32, u32)> = edge_faces.into_keys().collect();
+        edges.sort_unstable();
+        for (v0, v1) in edges {
+            simplifier.push_edge(v0, v1);
+        }
+
+        simplifier
+    }
+
+    /// 计算边的折叠目标并入堆
+    fn push_edge(&mut self, v0: u32, v1: u32) {
+        if let Some(target) = self.target(v0, v1) {
+            self.heap.push(Collapse {
+                cost: target.cost,
+                v0,
+                v1,
+                stamp: (self.versions[v0 as usize], self.versions[v1 as usize]),
+            });
+        }
+    }
+
+    /// 选择折叠位置：最优点、两端点和中点中误差最小者
+    fn target(&self, v0: u32, v1: u32) -> Option<Target> {
+        let (i0, i1) = (v0 as usize, v1 as usize);
+        let (p0, p1) = (self.positions[i0], self.positions[i1]);
+        let mut quadric = self.quadrics[i0];
+        quadric.add(&self.quadrics[i1]);
+
+        let candidate = |position: Vec3<f64>, t: f32| Target {
+            position,
+            t,
+            cost: quadric.error(position),
+        };
+
+        match (self.locked[i0], self.locked[i1]) {
+            (true, true) => return None,
+            (true, false) => return Some(candidate(p0, 0.0)),
+            (false, true) => return Some(candidate(p1, 1.0)),
+            (false, false) => {}
+        }
+
+        let mut candidates = vec![candidate(p0, 0.0), candidate(p1, 1.0), candidate((p0 + p1) * 0.5, 0.5)];
+        if let Some(optimal) = quadric.optimal_point() {
+            let edge = p1 - p0;
+            let t = (optimal - p0).dot(&edge) / edge.norm_squared().max(1e-24);
+            candidates.push(candidate(optimal, t.clamp(0.0, 1.0) as f32));
+        }
+
+        candidates.into_iter().min_by(|a, b| a.cost.total_cmp(&b.cost))
+    }
+
+    fn neighbors(&self, v: u32) -> HashSet<u32> {
+        self.vertex_triangles[v as usize]
+            .iter()
+            .flat_map(|&face| self.triangles[face])
+            .filter(|&w| w != v)
+            .collect()
+    }
+
+    /// 检查折叠是否会产生非流形结构或翻转三角形
+    fn can_collapse(&self, v0: u32, v1: u32, position: Vec3<f64>) -> bool {
+        let shared_faces = self.vertex_triangles[v0 as usize]
+            .iter()
+            .filter(|&&face| self.triangles[face].contains(&v1))
+            .count();
+        if shared_faces == 0 {
+            return false;
+        }
+
+        // 链接条件
+        let n0 = self.neighbors(v0);
+        let n1 = self.neighbors(v1);
+        if n0.intersection(&n1).count() != shared_faces {
+            return false;
+        }
+
+        for (moved, other) in [(v0, v1), (v1, v0)] {
+            for &face in &self.vertex_triangles[moved as usize] {
+                let triangle = self.triangles[face];
+                if triangle.contains(&other) {
+                    continue;
+                }
+                let Some((before, _)) = face_normal(&self.positions, &triangle) else {
+                    continue;
+                };
+                let mut moved_positions = triangle.map(|v| self.positions[v as usize]);
+                for (p, &v) in moved_positions.iter_mut().zip(&triangle) {
+                    if v == moved {
+                        *p = position;
+                    }
+                }
+                let after = (moved_positions[1] - moved_positions[0]).cross(&(moved_positions[2] - moved_positions[0]));
+                match after.try_normalize(1e-18) {
+                    Some(after) if after.dot(&before) >= MIN_NORMAL_DOT => {}
+                    _ => return false,
+                }
+            }
+        }
+
+        true
+    }
+
+    /// 将 v1 折叠到 v0，v0 移动到目标位置
+    fn collapse(&mut self, v0: u32, v1: u32, target: &Target) {
+        let (i0, i1) = (v0 as usize, v1 as usize);
+
+        let a = self.vertices[i0];
+        let b = self.vertices[i1];
+        let t = target.t;
+        self.vertices[i0] = Vertex {
+            position: [target.position.x as f32, target.position.y as f32, target.position.z as f32],
+            normal: lerp_direction(a.normal, b.normal, t),
+            texcoord: [a.texcoord[0] + (b.texcoord[0] - a.texcoord[0]) * t, a.texcoord[1] + (b.texcoord[1] - a.texcoord[1]) * t],
+            tangent: lerp_direction(a.tangent, b.tangent, t),
+        };
+        self.positions[i0] = target.position;
+        self.locked[i0] |= self.locked[i1];
+
+        let quadric = self.quadrics[i1];
+        self.quadrics[i0].add(&quadric);
+
+        let faces = std::mem::take(&mut self.vertex_triangles[i1]);
+        for face in faces {
+            if self.triangles[face].contains(&v0) {
+                self.triangle_alive[face] = false;
+                self.alive_triangles -= 1;
+                for v in self.triangles[face] {
+                    if v != v1 {
+                        self.vertex_triangles[v as usize].retain(|&f| f != face);
+                    }
+                }
+            } else {
+                for v in &mut self.triangles[face] {
+                    if *v == v1 {
+                        *v = v0;
+                    }
+                }
+                self.vertex_triangles[i0].push(face);
+            }
+        }
+
+        self.versions[i0] += 1;
+        self.versions[i1] += 1;
+
+        // 与 v0 相连的边代价已变化，旧条目因版本号不匹配自动失效
+        let mut neighbors: Vec<u32> = self.neighbors(v0).into_iter().collect();
+        neighbors.sort_unstable();
+        for neighbor in neighbors {
+            self.push_edge(v0, neighbor);
+        }
+    }
+
+    fn run(&mut self, target_triangles: usize) {
+        while self.alive_triangles > target_triangles {
+            let Some(collapse) = self.heap.pop() else {
+                break;
+            };
+            let (v0, v1) = (collapse.v0, collapse.v1);
+            if collapse.stamp != (self.versions[v0 as usize], self.versions[v1 as usize]) {
+                continue;
+            }
+
+            let Some(target) = self.target(v0, v1) else {
+                continue;
+            };
+            if !self.can_collapse(v0, v1, target.position) {
+                continue;
+            }
+
+            // 保留未锁定一侧以外的顶点，锁定顶点始终作为保留端
+            if self.locked[v1 as usize] {
+                let flipped = Target { t: 1.0 - target.t, ..target };
+                self.collapse(v1, v0, &flipped);
+            } else {
+                self.collapse(v0, v1, &target);
+            }
+        }
+    }
+
+    /// 写回网格：丢弃未使用的顶点，保持顶点和三角形的原有顺序
+    fn write_back(self, mesh: &mut MeshData) {
+        let old_triangle_count = self.triangles.len();
+
+        let mut used = vec![false; self.vertices.len()];
+        for (triangle, _) in self.triangles.iter().zip(&self.triangle_alive).filter(|(_, &alive)| alive) {
+            for &v in triangle {
+                used[v as usize] = true;
+            }
+        }
+
+        let mut remap = vec![u32::MAX; self.vertices.len()];
+        let mut vertices = Vec::with_capacity(self.vertices.len());
+        for (i, vertex) in self.vertices.iter().enumerate() {
+            if used[i] {
+                remap[i] = vertices.len() as u32;
+                vertices.push(*vertex);
+            }
+        }
+
+        let mut indices = Vec::with_capacity(self.alive_triangles * 3);
+        for (triangle, _) in self.triangles.iter().zip(&self.triangle_alive).filter(|(_, &alive)| alive) {
+            indices.extend(triangle.iter().map(|&v| remap[v as usize]));
+        }
+
+        // 子网格范围按保留下来的顶点和三角形重新计算
+        let prefix = |flags: &[bool]| -> Vec<u32> {
+            let mut counts = Vec::with_capacity(flags.len() + 1);
+            counts.push(0);
+            for &flag in flags {
+                counts.push(counts.last().copied().unwrap_or(0) + flag as u32);
+            }
+            counts
+        };
+        let vertex_prefix = prefix(&used);
+        let face_prefix = prefix(&self.triangle_alive);
+        let subsets = mesh
+            .subsets
+            .iter()
+            .map(|subset| {
+                let vertex_end = ((subset.vertex_start + subset.vertex_count) as usize).min(used.len());
+                let vertex_start = (subset.vertex_start as usize).min(vertex_end);
+                let face_end = ((subset.face_start + subset.face_count) as usize).min(old_triangle_count);
+                let face_start = (subset.face_start as usize).min(face_end);
+                Subset {
+                    vertex_start: vertex_prefix[vertex_start],
+                    vertex_count: vertex_prefix[vertex_end] - vertex_prefix[vertex_start],
+                    face_start: face_prefix[face_start],
+                    face_count: face_prefix[face_end] - face_prefix[face_start],
+                    ..subset.clone()
+                }
+            })
+            .collect();
+
+        mesh.vertices = vertices;
+        mesh.indices = indices;
+        mesh.subsets = subsets;
+    }
+}
+
+impl MeshData {
+    /// 使用二次误差度量边折叠简化网格
+    ///
+    /// 三角形数量降到原来的约 `target_ratio` 倍（`0.0..=1.0`）。
+    /// 为保持拓扑和接缝，无法继续安全折叠时会提前停止，结果可能多于目标数量。
+    /// 三角形的原有顺序保持不变，子网格范围随之更新。三角形带先转换为三角形列表。
+    pub fn decimate(&mut self, target_ratio: f32) {
+        let target_ratio = target_ratio.clamp(0.0, 1.0);
+        if target_ratio >= 1.0 || self.indices.len() < 3 {
+            return;
+        }
+        self.to_triangle_list();
+
+        let target_triangles = (self.triangle_count() as f32 * target_ratio).ceil() as usize;
+        let mut simplifier = Simplifier::new(self);
+        simplifier.run(target_triangles);
+        simplifier.write_back(self);
+    }
+}
+
+/// 三角形单位法线和面积的两倍，退化三角形返回 `None`
+fn face_normal(positions: &[Vec3<f64>], triangle: &[u32; 3]) -> Option<(Vec3<f64>, f64)> {
+    let [p0, p1, p2] = triangle.map(|v| positions[v as usize]);
+    let cross = (p1 - p0).cross(&(p2 - p0));
+    let length = cross.norm();
+    (length > 1e-18).then(|| (cross / length, length))
+}
+
+fn lerp_direction(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    let v = Vec3::from(a).lerp(&Vec3::from(b), t);
+    let v = v.try_normalize(1e-12).unwrap_or_else(|| Vec3::from(a));
+    [v.x, v.y, v.z]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    /// UV 球，两极各一个顶点
+    ///
+    /// `seam` 为真时经线首尾使用位置相同、UV 不同的两组顶点，否则首尾共用顶点。
+    fn sphere(segments: u32, rings: u32, seam: bool) -> MeshData {
+        let mut mesh = MeshData::new();
+        let vertex = |p: [f32; 3], uv: [f32; 2]| Vertex::new(p, p, uv, [1.0, 0.0, 0.0]);
+        let columns = if seam { segments + 1 } else { segments };
+
+        mesh.vertices.push(vertex([0.0, 1.0, 0.0], [0.5, 0.0]));
+        for ring in 1..rings {
+            let phi = PI * ring as f32 / rings as f32;
+            for segment in 0..columns {
+                let theta = 2.0 * PI * (segment % segments) as f32 / segments as f32;
+                let p = [phi.sin() * theta.cos(), phi.cos(), -phi.sin() * theta.sin()];
+                mesh.vertices.push(vertex(p, [segment as f32 / segments as f32, ring as f32 / rings as f32]));
+            }
+        }
+        mesh.vertices.push(vertex([0.0, -1.0, 0.0], [0.5, 1.0]));
+
+        let south = mesh.vertices.len() as u32 - 1;
+        let ring_vertex = |ring: u32, segment: u32| 1 + (ring - 1) * columns + segment % columns;
+        for segment in 0..segments {
+            mesh.indices.extend_from_slice(&[0, ring_vertex(1, segment), ring_vertex(1, segment + 1)]);
+            mesh.indices.extend_from_slice(&[south, ring_vertex(rings - 1, segment + 1), ring_vertex(rings - 1, segment)]);
+        }
+        for ring in 1..rings - 1 {
+            for segment in 0..segments {
+                let a = ring_vertex(ring, segment);
+                let b = ring_vertex(ring, segment + 1);
+                let c = ring_vertex(ring + 1, segment);
+                let d = ring_vertex(ring + 1, segment + 1);
+                mesh.indices.extend_from_slice(&[a, c, b, b, c, d]);
+            }
+        }
+        mesh
+    }
+
+    /// 按位置合并顶点后，每条边恰好被两个三角形共享
+    fn is_closed_manifold(mesh: &MeshData) -> bool {
+        let mut welded: HashMap<[u32; 3], u32> = HashMap::new();
+        let ids: Vec<u32> = mesh
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, v)| *welded.entry(v.position.map(f32::to_bits)).or_insert(i as u32))
+            .collect();
+
+        let mut edges: HashMap<(u32, u32), u32> = HashMap::new();
+        for triangle in mesh.indices.chunks_exact(3) {
+            let triangle = [ids[triangle[0] as usize], ids[triangle[1] as usize], ids[triangle[2] as usize]];
+            for k in 0..3 {
+                *edges.entry(edge_key(triangle[k], triangle[(k + 1) % 3])).or_default() += 1;
+            }
+        }
+        edges.values().all(|&count| count == 2)
+    }
+
+    #[test]
+    fn test_decimate_sphere_to_quarter() {
+        let mut mesh = sphere(48, 24, false);
+        let original = mesh.triangle_count();
+        assert!(is_closed_manifold(&mesh));
+
+        mesh.decimate(0.25);
+
+        let ratio = mesh.triangle_count() as f32 / original as f32;
+        assert!((0.2..=0.3).contains(&ratio), "ratio = {ratio}");
+        assert!(mesh.validate().is_ok());
+        assert!(is_closed_manifold(&mesh));
+
+        // 顶点仍大致位于单位球面上
+        for vertex in &mesh.vertices {
+            let radius = Vec3::from(vertex.position).norm();
+            assert!((radius - 1.0).abs() < 0.05, "radius = {radius}");
+        }
+    }
+
+    #[test]
+    fn test_decimate_keeps_seam_vertices() {
+        let mut mesh = sphere(32, 16, true);
+        let seam: Vec<[f32; 3]> = mesh
+            .vertices
+            .iter()
+            .filter(|v| v.texcoord[0] == 1.0)
+            .map(|v| v.position)
+            .collect();
+
+        mesh.decimate(0.3);
+
+        // 接缝两侧的顶点都保留在原位，网格没有开裂
+        for position in &seam {
+            let count = mesh.vertices.iter().filter(|v| v.position == *position).count();
+            assert_eq!(count, 2);
+        }
+        assert!(mesh.validate().is_ok());
+        assert!(is_closed_manifold(&mesh));
+        assert!(mesh.triangle_count() < sphere(32, 16, true).triangle_count() / 2);
+    }
+
+    #[test]
+    fn test_decimate_updates_subsets() {
+        let mut mesh = sphere(32, 16, false);
+        mesh.merge(&sphere(32, 16, false), &crate::math::matrix::translation(3.0, 0.0, 0.0));
+        let half = mesh.triangle_count() as u32 / 2;
+        let half_vertices = mesh.vertex_count() as u32 / 2;
+        mesh.subsets = vec![
+            Subset::new(0, 0, half_vertices, 0, half),
+            Subset::new(1, half_vertices, half_vertices, half, half),
+        ];
+
+        mesh.decimate(0.5);
+
+        assert!(mesh.validate().is_ok());
+        let total: u32 = mesh.subsets.iter().map(|s| s.face_count).sum();
+        assert_eq!(total as usize, mesh.triangle_count());
+        assert_eq!(mesh.subsets[1].face_start, mesh.subsets[0].face_count);
+
+        // 第二个子网格的三角形仍全部位于平移后的球上
+        let start = mesh.subsets[1].index_start() as usize;
+        let end = start + mesh.subsets[1].index_count() as usize;
+        for &index in &mesh.indices[start..end] {
+            assert!(mesh.vertices[index as usize].position[0] > 1.5);
+        }
+    }
+
+    #[test]
+    fn test_decimate_full_ratio_is_noop() {
+        let mut mesh = sphere(8, 4, false);
+        let indices = mesh.indices.clone();
+
+        mesh.decimate(1.0);
+
+        assert_eq!(mesh.indices, indices);
+    }
+}
@@ -0,0 +1,534 @@
+//! 骨骼蒙皮与骨骼动画
+//!
+//! - [`Skeleton`]：关节层级、逆绑定矩阵和静止姿态
+//! - [`AnimationClip`]：按关节的平移/旋转/缩放关键帧，线性插值采样（旋转使用球面插值）
+//! - [`SkinnedMesh`]：网格加每顶点最多 4 个关节索引和权重
+//! - [`AnimatedSkeleton`]：骨骼加当前播放的片段，按 `delta_time` 推进并输出关节矩阵
+//!
+//! 关节矩阵为 `根变换 * 关节全局变换 * 逆绑定矩阵`，顶点着色器按权重混合后
+//! 作为模型空间的变换，关节数不超过 [`MAX_JOINTS`]（与着色器中的数组长度一致）。
+
+use crate::geometry::coordinate::CoordinateSystem;
+use crate::geometry::mesh::MeshData;
+use crate::math::{Matrix4, Quaternion, Vector3};
+
+/// 着色器骨骼矩阵数组的长度，超过时模型按静态网格绘制
+pub const MAX_JOINTS: usize = 64;
+
+/// 每个顶点受影响的最大关节数
+pub const JOINTS_PER_VERTEX: usize = 4;
+
+/// 关节的局部变换（相对父关节）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JointPose {
+    /// 平移
+    pub translation: Vector3,
+
+    /// 旋转
+    pub rotation: Quaternion,
+
+    /// 缩放
+    pub scale: Vector3,
+}
+
+impl Default for JointPose {
+    fn default() -> Self {
+        Self {
+            translation: Vector3::zeros(),
+            rotation: Quaternion::identity(),
+            scale: Vector3::new(1.0, 1.0, 1.0),
+        }
+    }
+}
+
+impl JointPose {
+    /// 局部变换矩阵（T * R * S）
+    pub fn to_matrix(&self) -> Matrix4 {
+        Matrix4::new_translation(&self.translation)
+            * self.rotation.to_homogeneous()
+            * Matrix4::new_nonuniform_scaling(&self.scale)
+    }
+}
+
+/// 骨骼中的一个关节
+#[derive(Debug, Clone, PartialEq)]
+pub struct Joint {
+    /// 关节名称
+    pub name: String,
+
+    /// 父关节序号，根关节为 `None`
+    pub parent: Option<usize>,
+
+    /// 逆绑定矩阵（把模型空间变换到关节的绑定空间）
+    pub inverse_bind_matrix: Matrix4,
+
+    /// 静止姿态（动画没有覆盖的分量使用此值）
+    pub rest_pose: JointPose,
+}
+
+/// 骨骼
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skeleton {
+    /// 关节列表，顶点的关节索引指向此列表
+    pub joints: Vec<Joint>,
+
+    /// 作用于根关节的变换（骨骼之外的父节点变换和导入时的坐标系转换）
+    pub root_transform: Matrix4,
+}
+
+impl Skeleton {
+    /// 各关节的静止姿态
+    pub fn rest_pose(&self) -> Vec<JointPose> {
+        self.joints.iter().map(|joint| joint.rest_pose).collect()
+    }
+
+    /// 按给定姿态计算关节矩阵
+    ///
+    /// `pose` 与关节一一对应。父关节可以位于子关节之后；层级中存在环时，
+    /// 环上的关节按根关节处理。
+    pub fn joint_matrices(&self, pose: &[JointPose]) -> Vec<Matrix4> {
+        let mut globals: Vec<Option<Matrix4>> = vec![None; self.joints.len()];
+        for joint in 0..self.joints.len() {
+            self.global_transform(joint, pose, &mut globals, 0);
+        }
+
+        globals
+            .iter()
+            .zip(&self.joints)
+            .map(|(global, joint)| global.unwrap_or_else(Matrix4::identity) * joint.inverse_bind_matrix)
+            .collect()
+    }
+
+    /// 关节的全局变换（含根变换），结果缓存在 `globals` 中
+    fn global_transform(
+        &self,
+        joint: usize,
+        pose: &[JointPose],
+        globals: &mut [Option<Matrix4>],
+        depth: usize,
+    ) -> Matrix4 {
+        if let Some(global) = globals[joint] {
+            return global;
+        }
+
+        let local = pose.get(joint).unwrap_or(&self.joints[joint].rest_pose).to_matrix();
+        let parent = match self.joints[joint].parent {
+            Some(parent) if parent < self.joints.len() && depth < self.joints.len() => {
+                self.global_transform(parent, pose, globals, depth + 1)
+            }
+            _ => self.root_transform,
+        };
+        let global = parent * local;
+        globals[joint] = Some(global);
+        global
+    }
+}
+
+/// 关键帧数值
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelValues {
+    /// 平移关键帧
+    Translation(Vec<Vector3>),
+
+    /// 旋转关键帧
+    Rotation(Vec<Quaternion>),
+
+    /// 缩放关键帧
+    Scale(Vec<Vector3>),
+}
+
+impl ChannelValues {
+    fn len(&self) -> usize {
+        match self {
+            ChannelValues::Translation(values) | ChannelValues::Scale(values) => values.len(),
+            ChannelValues::Rotation(values) => values.len(),
+        }
+    }
+}
+
+/// 作用于单个关节的一条动画通道
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationChannel {
+    /// 目标关节序号
+    pub joint: usize,
+
+    /// 关键帧时间（秒，递增）
+    pub times: Vec<f32>,
+
+    /// 关键帧数值，数量与 `times` 相同
+    pub values: ChannelValues,
+}
+
+impl AnimationChannel {
+    /// 在 `time` 处采样并写入关节姿态
+    ///
+    /// 时间在首尾关键帧之外时使用首尾关键帧的值。
+    fn sample(&self, time: f32, pose: &mut JointPose) {
+        let count = self.times.len().min(self.values.len());
+        if count == 0 {
+            return;
+        }
+
+        let next = self.times[..count].partition_point(|&t| t <= time);
+        let (a, b, t) = if next == 0 {
+            (0, 0, 0.0)
+        } else if next == count {
+            (count - 1, count - 1, 0.0)
+        } else {
+            let span = self.times[next] - self.times[next - 1];
+            let t = if span > 0.0 { (time - self.times[next - 1]) / span } else { 0.0 };
+            (next - 1, next, t)
+        };
+
+        match &self.values {
+            ChannelValues::Translation(values) => pose.translation = values[a].lerp(&values[b], t),
+            ChannelValues::Rotation(values) => {
+                pose.rotation = values[a].try_slerp(&values[b], t, 1e-6).unwrap_or(values[a]);
+            }
+            ChannelValues::Scale(values) => pose.scale = values[a].lerp(&values[b], t),
+        }
+    }
+}
+
+/// 动画片段
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationClip {
+    /// 片段名称
+    pub name: String,
+
+    /// 时长（秒），即所有通道最后一个关键帧的时间
+    pub duration: f32,
+
+    /// 动画通道
+    pub channels: Vec<AnimationChannel>,
+}
+
+impl AnimationClip {
+    /// 创建片段，时长取所有通道的最后一个关键帧时间
+    pub fn new(name: impl Into<String>, channels: Vec<AnimationChannel>) -> Self {
+        let duration = channels
+            .iter()
+            .filter_map(|channel| channel.times.last().copied())
+            .fold(0.0f32, f32::max);
+
+        Self {
+            name: name.into(),
+            duration,
+            channels,
+        }
+    }
+
+    /// 在 `time` 处采样，覆盖 `pose` 中有动画的关节分量
+    pub fn sample(&self, time: f32, pose: &mut [JointPose]) {
+        for channel in &self.channels {
+            if let Some(joint_pose) = pose.get_mut(channel.joint) {
+                channel.sample(time, joint_pose);
+            }
+        }
+    }
+}
+
+/// 带蒙皮数据的网格
+#[derive(Debug, Clone)]
+pub struct SkinnedMesh {
+    /// 绑定姿态下的网格
+    pub mesh: MeshData,
+
+    /// 每个顶点的关节索引，与 `mesh.vertices` 一一对应
+    pub joint_indices: Vec<[u16; JOINTS_PER_VERTEX]>,
+
+    /// 每个顶点的关节权重（和为 1）
+    pub joint_weights: Vec<[f32; JOINTS_PER_VERTEX]>,
+
+    /// 骨骼
+    pub skeleton: Skeleton,
+
+    /// 动画片段（文件中的第一个动画，没有动画时为 `None`）
+    pub animation: Option<AnimationClip>,
+}
+
+impl SkinnedMesh {
+    /// 转换坐标系
+    ///
+    /// 网格按 [`MeshData::convert_coordinate_system`] 转换；骨骼的根变换左乘转换矩阵、
+    /// 逆绑定矩阵右乘其逆矩阵，使蒙皮结果与转换后的网格一致。
+    pub fn convert_coordinate_system(&mut self, from: CoordinateSystem, to: CoordinateSystem) {
+        if from == to {
+            return;
+        }
+
+        self.mesh.convert_coordinate_system(from, to);
+
+        let axis = |v: [f32; 3]| Vector3::from(from.convert_vector(&to, v)).push(0.0);
+        let mut conversion = Matrix4::identity();
+        conversion.set_column(0, &axis([1.0, 0.0, 0.0]));
+        conversion.set_column(1, &axis([0.0, 1.0, 0.0]));
+        conversion.set_column(2, &axis([0.0, 0.0, 1.0]));
+        // 转换矩阵是正交的，逆矩阵即转置
+        let inverse = conversion.transpose();
+
+        self.skeleton.root_transform = conversion * self.skeleton.root_transform;
+        for joint in &mut self.skeleton.joints {
+            joint.inverse_bind_matrix *= inverse;
+        }
+    }
+
+    /// 按 `factor` 均匀缩放
+    ///
+    /// 网格按 [`MeshData::scale`] 缩放；骨骼的根变换左乘缩放矩阵、逆绑定矩阵右乘其逆矩阵，
+    /// 动画中的平移因此也按比例缩放。
+    pub fn scale(&mut self, factor: f32) {
+        if factor == 1.0 {
+            return;
+        }
+
+        self.mesh.scale(factor);
+        self.skeleton.root_transform = Matrix4::new_scaling(factor) * self.skeleton.root_transform;
+        let inverse = Matrix4::new_scaling(1.0 / factor);
+        for joint in &mut self.skeleton.joints {
+            joint.inverse_bind_matrix *= inverse;
+        }
+    }
+
+    /// 归一化关节权重，权重和为 0 的顶点绑定到 0 号关节
+    pub fn normalize_weights(&mut self) {
+        for weights in &mut self.joint_weights {
+            let sum: f32 = weights.iter().sum();
+            if sum > 0.0 {
+                weights.iter_mut().for_each(|w| *w /= sum);
+            } else {
+                *weights = [1.0, 0.0, 0.0, 0.0];
+            }
+        }
+    }
+
+    /// 按关节矩阵在 CPU 上蒙皮，返回变换后的顶点位置
+    pub fn skinned_positions(&self, joint_matrices: &[Matrix4]) -> Vec<Vector3> {
+        self.mesh
+            .vertices
+            .iter()
+            .zip(self.joint_indices.iter().zip(&self.joint_weights))
+            .map(|(vertex, (joints, weights))| {
+                let position = Vector3::from(vertex.position).push(1.0);
+                let skinned = joints
+                    .iter()
+                    .zip(weights)
+                    .filter(|(_, &weight)| weight > 0.0)
+                    .filter_map(|(&joint, &weight)| joint_matrices.get(joint as usize).map(|m| m * position * weight))
+                    .sum::<crate::math::Vector4>();
+                skinned.xyz()
+            })
+            .collect()
+    }
+}
+
+/// 播放中的骨骼动画
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimatedSkeleton {
+    /// 骨骼
+    pub skeleton: Skeleton,
+
+    /// 循环播放的动画片段，`None` 时保持静止姿态
+    pub animation: Option<AnimationClip>,
+
+    /// 当前播放时间（秒）
+    pub time: f32,
+}
+
+impl AnimatedSkeleton {
+    /// 从头开始播放
+    pub fn new(skeleton: Skeleton, animation: Option<AnimationClip>) -> Self {
+        Self {
+            skeleton,
+            animation,
+            time: 0.0,
+        }
+    }
+
+    /// 推进播放时间，到达片段末尾后循环
+    pub fn advance(&mut self, delta_time: f32) {
+        let duration = self.animation.as_ref().map_or(0.0, |clip| clip.duration);
+        self.time = if duration > 0.0 {
+            (self.time + delta_time.max(0.0)).rem_euclid(duration)
+        } else {
+            0.0
+        };
+    }
+
+    /// 当前时间的关节姿态
+    pub fn pose(&self) -> Vec<JointPose> {
+        let mut pose = self.skeleton.rest_pose();
+        if let Some(clip) = &self.animation {
+            clip.sample(self.time, &mut pose);
+        }
+        pose
+    }
+
+    /// 当前时间的关节矩阵
+    pub fn joint_matrices(&self) -> Vec<Matrix4> {
+        self.skeleton.joint_matrices(&self.pose())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::coordinate::{Handedness, UpAxis};
+    use crate::geometry::vertex::Vertex;
+    use std::f32::consts::FRAC_PI_2;
+
+    /// 沿 Y 轴排列的两节骨骼：根关节在原点，子关节在 (0, 1, 0)
+    fn two_bone_skeleton() -> Skeleton {
+        let child_offset = Vector3::new(0.0, 1.0, 0.0);
+        Skeleton {
+            joints: vec![
+                Joint {
+                    name: "root".to_string(),
+                    parent: None,
+                    inverse_bind_matrix: Matrix4::identity(),
+                    rest_pose: JointPose::default(),
+                },
+                Joint {
+                    name: "child".to_string(),
+                    parent: Some(0),
+                    inverse_bind_matrix: Matrix4::new_translation(&-child_offset),
+                    rest_pose: JointPose {
+                        translation: child_offset,
+                        ..Default::default()
+                    },
+                },
+            ],
+            root_transform: Matrix4::identity(),
+        }
+    }
+
+    fn bend_clip() -> AnimationClip {
+        AnimationClip::new(
+            "bend",
+            vec![AnimationChannel {
+                joint: 1,
+                times: vec![0.0, 1.0],
+                values: ChannelValues::Rotation(vec![
+                    Quaternion::identity(),
+                    Quaternion::from_axis_angle(&Vector3::z_axis(), FRAC_PI_2),
+                ]),
+            }],
+        )
+    }
+
+    fn skinned_segment() -> SkinnedMesh {
+        let mut mesh = MeshData::new();
+        for position in [[0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 2.0, 0.0]] {
+            mesh.vertices.push(Vertex::new(position, [0.0, 0.0, 1.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+        }
+        mesh.indices = vec![0, 1, 2];
+
+        SkinnedMesh {
+            mesh,
+            joint_indices: vec![[0, 0, 0, 0], [0, 1, 0, 0], [1, 0, 0, 0]],
+            joint_weights: vec![[1.0, 0.0, 0.0, 0.0], [0.5, 0.5, 0.0, 0.0], [1.0, 0.0, 0.0, 0.0]],
+            skeleton: two_bone_skeleton(),
+            animation: Some(bend_clip()),
+        }
+    }
+
+    #[test]
+    fn test_rest_pose_joint_matrices_are_identity() {
+        let skeleton = two_bone_skeleton();
+        for matrix in skeleton.joint_matrices(&skeleton.rest_pose()) {
+            assert!((matrix - Matrix4::identity()).norm() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_channel_interpolates_linearly() {
+        let channel = AnimationChannel {
+            joint: 0,
+            times: vec![0.0, 1.0, 3.0],
+            values: ChannelValues::Translation(vec![
+                Vector3::zeros(),
+                Vector3::new(2.0, 0.0, 0.0),
+                Vector3::new(2.0, 4.0, 0.0),
+            ]),
+        };
+        let mut pose = JointPose::default();
+
+        channel.sample(0.5, &mut pose);
+        assert!((pose.translation - Vector3::new(1.0, 0.0, 0.0)).norm() < 1e-6);
+        channel.sample(2.0, &mut pose);
+        assert!((pose.translation - Vector3::new(2.0, 2.0, 0.0)).norm() < 1e-6);
+        // 超出范围时使用首尾关键帧
+        channel.sample(-1.0, &mut pose);
+        assert_eq!(pose.translation, Vector3::zeros());
+        channel.sample(10.0, &mut pose);
+        assert_eq!(pose.translation, Vector3::new(2.0, 4.0, 0.0));
+    }
+
+    #[test]
+    fn test_animation_bends_child_joint() {
+        let mesh = skinned_segment();
+        let mut animated = AnimatedSkeleton::new(mesh.skeleton.clone(), mesh.animation.clone());
+        assert_eq!(animated.animation.as_ref().unwrap().duration, 1.0);
+
+        let rest = mesh.skinned_positions(&animated.joint_matrices());
+        assert!((rest[2] - Vector3::new(0.0, 2.0, 0.0)).norm() < 1e-5);
+
+        // 0.5 秒时子关节旋转 45 度，末端顶点绕 (0, 1, 0) 向 -X 方向弯曲
+        animated.advance(0.5);
+        let bent = mesh.skinned_positions(&animated.joint_matrices());
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        assert!((bent[0] - Vector3::zeros()).norm() < 1e-5);
+        assert!((bent[2] - Vector3::new(-half, 1.0 + half, 0.0)).norm() < 1e-4);
+
+        // 播放时间循环
+        animated.advance(0.75);
+        assert!((animated.time - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_convert_coordinate_system_matches_converted_mesh() {
+        let mut mesh = skinned_segment();
+        let mut animated = AnimatedSkeleton::new(mesh.skeleton.clone(), mesh.animation.clone());
+        animated.advance(0.5);
+        let expected = mesh.skinned_positions(&animated.joint_matrices());
+
+        let from = CoordinateSystem::new(UpAxis::Z, Handedness::Left);
+        mesh.convert_coordinate_system(from, CoordinateSystem::default());
+        let mut converted = AnimatedSkeleton::new(mesh.skeleton.clone(), mesh.animation.clone());
+        converted.advance(0.5);
+
+        for (position, original) in mesh.skinned_positions(&converted.joint_matrices()).iter().zip(&expected) {
+            let original = from.convert_vector(&CoordinateSystem::default(), [original.x, original.y, original.z]);
+            assert!((position - Vector3::from(original)).norm() < 1e-4);
+        }
+        // 手性变化时绕序翻转
+        assert_eq!(mesh.mesh.indices, vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_scale_matches_scaled_mesh() {
+        let mut mesh = skinned_segment();
+        let mut animated = AnimatedSkeleton::new(mesh.skeleton.clone(), mesh.animation.clone());
+        animated.advance(0.5);
+        let expected = mesh.skinned_positions(&animated.joint_matrices());
+
+        mesh.scale(0.01);
+        let mut scaled = AnimatedSkeleton::new(mesh.skeleton.clone(), mesh.animation.clone());
+        scaled.advance(0.5);
+
+        for (position, original) in mesh.skinned_positions(&scaled.joint_matrices()).iter().zip(&expected) {
+            assert!((position - original * 0.01).norm() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_normalize_weights() {
+        let mut mesh = skinned_segment();
+        mesh.joint_weights = vec![[2.0, 2.0, 0.0, 0.0], [0.0; 4], [0.2, 0.0, 0.0, 0.0]];
+        mesh.normalize_weights();
+
+        assert_eq!(mesh.joint_weights[0], [0.5, 0.5, 0.0, 0.0]);
+        assert_eq!(mesh.joint_weights[1], [1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(mesh.joint_weights[2], [1.0, 0.0, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,292 @@
+//! 网格细分
+//!
+//! Loop 细分（Loop 1987）：每级把每个三角形按边中点分为四个，并按相邻顶点加权移动新旧顶点，
+//! 多级细分后低多边形网格收敛为光滑曲面。
+//!
+//! # 拓扑与属性
+//!
+//! - 拓扑按位置合并顶点后计算，UV 接缝和硬边两侧位置重合的顶点移动到同一位置，曲面不会开裂
+//! - 开放边界按边界规则细分（只受边界上相邻顶点影响），边界曲线保持为曲线；非流形边按边界处理
+//! - 新顶点的 UV 取所在边两端的平均值，细分完成后重新计算法线和切线
+//! - 三角形的原有顺序保持不变，每个三角形变为连续的四个三角形，子网格的顶点和三角形范围随之更新
+
+use std::collections::HashMap;
+
+use super::mesh::{edge_key, MeshData, Subset};
+use super::vertex::Vertex;
+use crate::math::geometry::{compute_tangent_space, reconstruct_normals, smooth_normals_by_position};
+use crate::math::Vector3;
+
+/// 最大细分级数，每级三角形数量变为 4 倍
+pub const MAX_SUBDIVISION_LEVELS: u32 = 4;
+
+/// 合并细分后接缝两侧法线时使用的位置阈值
+const SEAM_EPSILON: f32 = 1e-5;
+
+impl MeshData {
+    /// 使用 Loop 细分平滑网格 `levels` 级（最多 [`MAX_SUBDIVISION_LEVELS`] 级）
+    ///
+    /// 每级三角形数量变为原来的 4 倍。细分后重新计算法线和切线，位置重合的顶点共用平滑法线。
+    /// 三角形带先转换为三角形列表。
+    pub fn subdivide(&mut self, levels: u32) {
+        let levels = levels.min(MAX_SUBDIVISION_LEVELS);
+        if levels == 0 || self.indices.len() < 3 {
+            return;
+        }
+        self.to_triangle_list();
+
+        for _ in 0..levels {
+            subdivide_once(self);
+        }
+        reconstruct_normals(&mut self.vertices, &self.indices);
+        smooth_normals_by_position(&mut self.vertices, SEAM_EPSILON);
+        compute_tangent_space(&mut self.vertices, &self.indices);
+    }
+}
+
+/// 按位置合并后的拓扑：每个顶点所属的合并顶点，以及每条边对面的顶点
+struct Topology {
+    /// 顶点对应的合并顶点（位置相同的顶点中序号最小的一个）
+    welded: Vec<u32>,
+    /// 合并后的边（较小序号在前）及共享该边的三角形中的对顶点
+    edges: HashMap<(u32, u32), Vec<u32>>,
+}
+
+impl Topology {
+    fn new(vertices: &[Vertex], triangles: &[[u32; 3]]) -> Self {
+        // 加 0.0 把 -0.0 规范为 0.0，两者按位比较时不同
+        let mut first: HashMap<[u32; 3], u32> = HashMap::new();
+        let welded = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, v)| *first.entry(v.position.map(|c| (c + 0.0).to_bits())).or_insert(i as u32))
+            .collect::<Vec<_>>();
+
+        let mut edges: HashMap<(u32, u32), Vec<u32>> = HashMap::new();
+        for triangle in triangles {
+            let [a, b, c] = triangle.map(|v| welded[v as usize]);
+            for (a, b, opposite) in [(a, b, c), (b, c, a), (c, a, b)] {
+                if a != b {
+                    edges.entry(edge_key(a, b)).or_default().push(opposite);
+                }
+            }
+        }
+        Self { welded, edges }
+    }
+
+    /// 内部边（恰好两个三角形共享）上的新顶点：`3/8 (a + b) + 1/8 (c + d)`，其余边取中点
+    fn edge_point(&self, positions: &[Vector3], a: u32, b: u32) -> Vector3 {
+        let (a, b) = (self.welded[a as usize], self.welded[b as usize]);
+        let p = |v: u32| positions[v as usize];
+        match self.edges.get(&edge_key(a, b)).map(Vec::as_slice) {
+            Some(&[c, d]) => (p(a) + p(b)) * 0.375 + (p(c) + p(d)) * 0.125,
+            _ => (p(a) + p(b)) * 0.5,
+        }
+    }
+
+    /// 所有合并顶点的新位置
+    ///
+    /// 内部顶点使用 Loop 的权重 `beta`；恰好有两条边界边的顶点取 `3/4 v + 1/8 (b0 + b1)`；
+    /// 其余（边界角点和非流形顶点）保持不动。
+    fn vertex_points(&self, positions: &[Vector3]) -> Vec<Vector3> {
+        let mut neighbors: HashMap<u32, (Vec<u32>, Vec<u32>)> = HashMap::new();
+        for (&(a, b), opposite) in &self.edges {
+            let boundary = opposite.len() != 2;
+            for (v, other) in [(a, b), (b, a)] {
+                let (all, border) = neighbors.entry(v).or_default();
+                all.push(other);
+                if boundary {
+                    border.push(other);
+                }
+            }
+        }
+
+        let mut points = positions.to_vec();
+        for (&v, (all, border)) in &neighbors {
+            let p = |v: u32| positions[v as usize];
+            let sum = |vs: &[u32]| vs.iter().fold(Vector3::zeros(), |sum, &n| sum + p(n));
+            points[v as usize] = match border.len() {
+                0 => {
+                    let n = all.len() as f32;
+                    let beta = if all.len() == 3 { 3.0 / 16.0 } else { 3.0 / (8.0 * n) };
+                    p(v) * (1.0 - n * beta) + sum(all) * beta
+                }
+                2 => p(v) * 0.75 + sum(border) * 0.125,
+                _ => p(v),
+            };
+        }
+        points
+    }
+}
+
+/// 细分一级
+///
+/// 顶点按子网格分组输出：每组先是子网格原有的顶点，再是其三角形的边上新增的顶点，
+/// 保证每个子网格的顶点范围仍然连续。不属于任何子网格的顶点和三角形最后输出。
+fn subdivide_once(mesh: &mut MeshData) {
+    let triangles: Vec<[u32; 3]> = mesh.indices.chunks_exact(3).map(|t| [t[0], t[1], t[2]]).collect();
+    let topology = Topology::new(&mesh.vertices, &triangles);
+    let positions: Vec<Vector3> = mesh.vertices.iter().map(|v| Vector3::from(v.position)).collect();
+    let vertex_points = topology.vertex_points(&positions);
+
+    // 各组的（原有顶点范围, 三角形范围），最后一组收集剩余的顶点和三角形
+    let clamp = |start: u32, count: u32, len: usize| {
+        let end = ((start + count) as usize).min(len);
+        (start as usize).min(end)..end
+    };
+    let mut groups: Vec<_> = mesh
+        .subsets
+        .iter()
+        .map(|s| {
+            (
+                clamp(s.vertex_start, s.vertex_count, mesh.vertices.len()),
+                clamp(s.face_start, s.face_count, triangles.len()),
+            )
+        })
+        .collect();
+    groups.push((0..mesh.vertices.len(), 0..triangles.len()));
+
+    let mut vertices = Vec::with_capacity(mesh.vertices.len() + triangles.len() * 3 / 2);
+    let mut remap = vec![u32::MAX; mesh.vertices.len()];
+    let mut face_group = vec![usize::MAX; triangles.len()];
+    let mut edge_vertices: HashMap<(usize, u32, u32), u32> = HashMap::new();
+    let mut vertex_ranges = Vec::with_capacity(groups.len());
+
+    for (group, (vertex_range, face_range)) in groups.iter().enumerate() {
+        let start = vertices.len() as u32;
+        for v in vertex_range.clone() {
+            if remap[v] != u32::MAX {
+                continue;
+            }
+            remap[v] = vertices.len() as u32;
+            let mut vertex = mesh.vertices[v];
+            vertex.position = vertex_points[topology.welded[v] as usize].into();
+            vertices.push(vertex);
+        }
+
+        for f in face_range.clone() {
+            if face_group[f] != usize::MAX {
+                continue;
+            }
+            face_group[f] = group;
+            let triangle = triangles[f];
+            // 被三角形引用但不在本组范围内的顶点
+            for &v in &triangle {
+                if remap[v as usize] == u32::MAX {
+                    remap[v as usize] = vertices.len() as u32;
+                    let mut vertex = mesh.vertices[v as usize];
+                    vertex.position = vertex_points[topology.welded[v as usize] as usize].into();
+                    vertices.push(vertex);
+                }
+            }
+            for k in 0..3 {
+                let (a, b) = edge_key(triangle[k], triangle[(k + 1) % 3]);
+                edge_vertices.entry((group, a, b)).or_insert_with(|| {
+                    let (va, vb) = (&mesh.vertices[a as usize], &mesh.vertices[b as usize]);
+                    let mut vertex = *va;
+                    vertex.position = topology.edge_point(&positions, a, b).into();
+                    vertex.texcoord = [0, 1].map(|i| (va.texcoord[i] + vb.texcoord[i]) * 0.5);
+                    vertices.push(vertex);
+                    vertices.len() as u32 - 1
+                });
+            }
+        }
+        vertex_ranges.push((start, vertices.len() as u32 - start));
+    }
+
+    // 三角形按原有顺序输出，每个三角形分为三个角上的三角形和中间的三角形，绕序不变
+    let mut indices = Vec::with_capacity(triangles.len() * 12);
+    for (triangle, &group) in triangles.iter().zip(&face_group) {
+        let [i0, i1, i2] = triangle.map(|v| remap[v as usize]);
+        let [m01, m12, m20] = [(0, 1), (1, 2), (2, 0)].map(|(a, b)| {
+            let (a, b) = edge_key(triangle[a], triangle[b]);
+            edge_vertices[&(group, a, b)]
+        });
+        indices.extend_from_slice(&[i0, m01, m20, m01, i1, m12, m20, m12, i2, m01, m12, m20]);
+    }
+
+    mesh.subsets = mesh
+        .subsets
+        .iter()
+        .zip(&vertex_ranges)
+        .map(|(subset, &(vertex_start, vertex_count))| Subset {
+            vertex_start,
+            vertex_count,
+            face_start: subset.face_start * 4,
+            face_count: subset.face_count * 4,
+            ..subset.clone()
+        })
+        .collect();
+    mesh.vertices = vertices;
+    mesh.indices = indices;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::primitives::{cube, plane, Winding};
+
+    /// 按位置合并顶点后，每条边恰好被两个三角形共享
+    fn is_closed_manifold(mesh: &MeshData) -> bool {
+        let triangles = mesh.triangles();
+        let topology = Topology::new(&mesh.vertices, &triangles);
+        let degenerate = triangles.iter().any(|t| {
+            let [a, b, c] = t.map(|v| topology.welded[v as usize]);
+            a == b || b == c || c == a
+        });
+        !degenerate && topology.edges.values().all(|opposite| opposite.len() == 2)
+    }
+
+    #[test]
+    fn test_subdivide_cube_quadruples_triangles() {
+        let mut mesh = cube(2.0, 1, Winding::Ccw);
+        let original = mesh.triangle_count();
+        assert!(is_closed_manifold(&mesh));
+
+        mesh.subdivide(1);
+
+        assert_eq!(mesh.triangle_count(), original * 4);
+        assert!(mesh.validate().is_ok());
+        assert!(is_closed_manifold(&mesh));
+
+        // 角点向内收缩（原角点到中心的距离为 √3），法线重新计算为单位向量且朝外
+        for vertex in &mesh.vertices {
+            let position = Vector3::from(vertex.position);
+            let normal = Vector3::from(vertex.normal);
+            assert!(position.amax() <= 1.0 && position.norm() < 1.6);
+            assert!((normal.norm() - 1.0).abs() < 1e-4);
+            assert!(normal.dot(&position) > 0.0);
+        }
+
+        mesh.subdivide(2);
+        assert_eq!(mesh.triangle_count(), original * 64);
+        assert!(is_closed_manifold(&mesh));
+    }
+
+    #[test]
+    fn test_subdivide_updates_subsets_and_keeps_flat_boundary() {
+        let mut mesh = plane(2.0, 2, Winding::Ccw);
+        mesh.merge(&plane(2.0, 2, Winding::Ccw), &crate::math::matrix::translation(3.0, 0.0, 0.0));
+        let half_faces = mesh.triangle_count() as u32 / 2;
+        let half_vertices = mesh.vertex_count() as u32 / 2;
+        mesh.subsets = vec![
+            Subset::new(0, 0, half_vertices, 0, half_faces),
+            Subset::new(1, half_vertices, half_vertices, half_faces, half_faces),
+        ];
+
+        mesh.subdivide(1);
+
+        assert!(mesh.validate().is_ok());
+        assert_eq!(mesh.subsets[1].face_start, half_faces * 4);
+        assert_eq!(mesh.subsets[1].face_count, half_faces * 4);
+        // 每个子网格的三角形只引用本子网格范围内的顶点
+        for subset in &mesh.subsets {
+            let vertices = subset.vertex_start..subset.vertex_start + subset.vertex_count;
+            let start = subset.index_start() as usize;
+            let end = start + subset.index_count() as usize;
+            assert!(mesh.indices[start..end].iter().all(|i| vertices.contains(i)));
+        }
+        // 平面细分后仍然是平面
+        assert!(mesh.vertices.iter().all(|v| v.position[1].abs() < 1e-6));
+    }
+}
@@ -0,0 +1,179 @@
+//! UV 布局检查
+//!
+//! 把网格的三角形展开到 UV 空间，标记互相重叠的三角形并统计超出 [0, 1] 的纹理坐标，
+//! 用于排查导入模型的纹理坐标问题。重叠检测先按包围盒把三角形分到均匀网格中，
+//! 只比较同一格子中的三角形，再用分离轴测试判断内部是否相交（只共享边或顶点的相邻三角形不算重叠）。
+
+use std::collections::HashSet;
+
+/// 分离轴测试的容差，投影区间只在边界相接时不算重叠
+const OVERLAP_EPSILON: f32 = 1e-6;
+
+/// 面积小于该值的三角形视为退化，不参与重叠检测
+const DEGENERATE_AREA: f32 = 1e-12;
+
+/// 重叠检测网格每个方向的最大格子数
+const MAX_GRID_CELLS: usize = 256;
+
+/// 网格的 UV 布局
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UvLayout {
+    /// 每个三角形三个顶点的 UV
+    pub triangles: Vec<[[f32; 2]; 3]>,
+
+    /// 与其他三角形重叠的三角形，与 `triangles` 一一对应
+    pub overlapping: Vec<bool>,
+
+    /// 三角形引用的顶点中 UV 超出 [0, 1] 的比例
+    pub outside_fraction: f32,
+}
+
+impl UvLayout {
+    /// 按三角形的顶点索引和每个顶点的 UV 创建布局
+    pub fn new(triangles: &[[u32; 3]], uv: impl Fn(u32) -> [f32; 2]) -> Self {
+        let referenced: HashSet<u32> = triangles.iter().flatten().copied().collect();
+        let outside = referenced
+            .iter()
+            .filter(|&&index| uv(index).iter().any(|c| !(0.0..=1.0).contains(c)))
+            .count();
+        let outside_fraction = if referenced.is_empty() {
+            0.0
+        } else {
+            outside as f32 / referenced.len() as f32
+        };
+
+        let triangles: Vec<[[f32; 2]; 3]> = triangles.iter().map(|t| t.map(&uv)).collect();
+        let overlapping = find_overlaps(&triangles);
+        Self {
+            triangles,
+            overlapping,
+            outside_fraction,
+        }
+    }
+
+    /// 重叠的三角形数量
+    pub fn overlap_count(&self) -> usize {
+        self.overlapping.iter().filter(|&&overlapping| overlapping).count()
+    }
+
+    /// 所有 UV 的包围盒 (最小值, 最大值)，没有三角形时为 `None`
+    pub fn bounds(&self) -> Option<([f32; 2], [f32; 2])> {
+        uv_bounds(&self.triangles)
+    }
+}
+
+fn uv_bounds(triangles: &[[[f32; 2]; 3]]) -> Option<([f32; 2], [f32; 2])> {
+    let mut points = triangles.iter().flatten();
+    let first = *points.next()?;
+    Some(points.fold((first, first), |(min, max), p| {
+        ([min[0].min(p[0]), min[1].min(p[1])], [max[0].max(p[0]), max[1].max(p[1])])
+    }))
+}
+
+/// 标记内部相交的三角形
+fn find_overlaps(triangles: &[[[f32; 2]; 3]]) -> Vec<bool> {
+    let mut overlapping = vec![false; triangles.len()];
+    let Some((min, max)) = uv_bounds(triangles) else {
+        return overlapping;
+    };
+
+    // 格子数随三角形数量增长，平均每个格子几个三角形
+    let cells = ((triangles.len() as f32).sqrt().ceil() as usize).clamp(1, MAX_GRID_CELLS);
+    let extent = [(max[0] - min[0]).max(f32::EPSILON), (max[1] - min[1]).max(f32::EPSILON)];
+    let cell_of = |value: f32, axis: usize| {
+        (((value - min[axis]) / extent[axis] * cells as f32) as usize).min(cells - 1)
+    };
+
+    let mut grid: Vec<Vec<usize>> = vec![Vec::new(); cells * cells];
+    for (i, triangle) in triangles.iter().enumerate() {
+        if signed_area(triangle).abs() < DEGENERATE_AREA {
+            continue;
+        }
+        let (lo, hi) = uv_bounds(std::slice::from_ref(triangle)).unwrap_or((min, max));
+        for y in cell_of(lo[1], 1)..=cell_of(hi[1], 1) {
+            for x in cell_of(lo[0], 0)..=cell_of(hi[0], 0) {
+                grid[y * cells + x].push(i);
+            }
+        }
+    }
+
+    for cell in &grid {
+        for (k, &a) in cell.iter().enumerate() {
+            for &b in &cell[k + 1..] {
+                if overlapping[a] && overlapping[b] {
+                    continue;
+                }
+                if triangles_overlap(&triangles[a], &triangles[b]) {
+                    overlapping[a] = true;
+                    overlapping[b] = true;
+                }
+            }
+        }
+    }
+    overlapping
+}
+
+fn signed_area([a, b, c]: &[[f32; 2]; 3]) -> f32 {
+    0.5 * ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]))
+}
+
+/// 分离轴测试：两个三角形的内部是否相交
+fn triangles_overlap(a: &[[f32; 2]; 3], b: &[[f32; 2]; 3]) -> bool {
+    let project = |triangle: &[[f32; 2]; 3], axis: [f32; 2]| {
+        triangle.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| {
+            let d = p[0] * axis[0] + p[1] * axis[1];
+            (lo.min(d), hi.max(d))
+        })
+    };
+
+    [a, b].iter().all(|triangle| {
+        (0..3).all(|i| {
+            let (p, q) = (triangle[i], triangle[(i + 1) % 3]);
+            let axis = [q[1] - p[1], p[0] - q[0]];
+            let length = (axis[0] * axis[0] + axis[1] * axis[1]).sqrt();
+            if length < f32::EPSILON {
+                return true;
+            }
+            let axis = [axis[0] / length, axis[1] / length];
+            let (a_lo, a_hi) = project(a, axis);
+            let (b_lo, b_hi) = project(b, axis);
+            a_hi.min(b_hi) - a_lo.max(b_lo) > OVERLAP_EPSILON
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlaps_and_out_of_range_uvs() {
+        // 0-1-2 和 0-2-3 组成单位正方形（共享对角线，不重叠）；4-5-6 与第一个三角形重叠；
+        // 7-8-9 在 [0, 1] 之外且与其他三角形分离
+        let uvs = [
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [1.0, 1.0],
+            [0.0, 1.0],
+            [0.5, 0.1],
+            [0.9, 0.1],
+            [0.9, 0.5],
+            [2.0, 2.0],
+            [3.0, 2.0],
+            [3.0, 3.0],
+        ];
+        let triangles = [[0, 1, 2], [0, 2, 3], [4, 5, 6], [7, 8, 9]];
+        let layout = UvLayout::new(&triangles, |i| uvs[i as usize]);
+
+        assert_eq!(layout.overlapping, vec![true, false, true, false]);
+        assert_eq!(layout.overlap_count(), 2);
+        assert!((layout.outside_fraction - 0.3).abs() < 1e-6);
+        assert_eq!(layout.bounds(), Some(([0.0, 0.0], [3.0, 3.0])));
+
+        // 只共享边的相邻三角形不算重叠
+        let quad = UvLayout::new(&triangles[..2], |i| uvs[i as usize]);
+        assert_eq!(quad.overlap_count(), 0);
+        assert_eq!(quad.outside_fraction, 0.0);
+        assert_eq!(UvLayout::new(&[], |i| uvs[i as usize]).bounds(), None);
+    }
+}
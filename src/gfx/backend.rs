@@ -6,6 +6,8 @@
 use winit::window::Window;
 use winit::event_loop::EventLoop;
 use crate::core::Config;
+use crate::core::error::Result;
+use crate::gfx::capabilities::DeviceCapabilities;
 
 /// 图形后端的统一接口
 ///
@@ -51,4 +53,16 @@ pub trait GraphicsBackend {
     ///
     /// 后端名称的字符串切片（如 "Vulkan"、"DirectX 12"）
     fn backend_name(&self) -> &str;
+
+    /// 查询设备能力
+    ///
+    /// 返回设备名称、API 版本、可选特性和关键限制。各后端在初始化完成时
+    /// 调用 [`DeviceCapabilities::log`] 输出一次。
+    fn report_capabilities(&self) -> DeviceCapabilities;
+
+    /// 等待 GPU 完成所有已提交的工作
+    ///
+    /// 在重建交换链、销毁资源或切换后端之前调用。没有进行中的工作时
+    /// 会立即返回，可以安全地重复调用。
+    fn wait_idle(&self) -> Result<()>;
 }
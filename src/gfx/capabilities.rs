@@ -0,0 +1,103 @@
+//! 设备能力报告
+//!
+//! 汇总各后端在初始化时查询到的设备名称、API 版本、可选特性和关键限制。
+//! 初始化完成后以一个结构化日志块输出，用户反馈渲染问题时附上即可定位设备差异。
+
+use std::fmt;
+
+use tracing::info;
+
+/// 设备能力
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceCapabilities {
+    /// 后端名称（如 "Vulkan"）
+    pub backend: String,
+
+    /// 设备（显卡）名称
+    pub device_name: String,
+
+    /// API 或驱动版本
+    pub api_version: String,
+
+    /// 颜色附件支持的最大 MSAA 采样数
+    pub max_msaa_samples: u32,
+
+    /// 最大各向异性过滤等级
+    pub max_anisotropy: f32,
+
+    /// 是否支持时间戳查询
+    pub timestamp_query: bool,
+
+    /// 是否支持线框填充模式
+    pub polygon_mode_line: bool,
+
+    /// 是否支持点填充模式
+    pub polygon_mode_point: bool,
+
+    /// 二维纹理的最大边长
+    pub max_texture_dimension_2d: u32,
+
+    /// 单个 Uniform 缓冲绑定的最大字节数
+    pub max_uniform_buffer_size: u64,
+
+    /// 最大顶点属性数量
+    pub max_vertex_attributes: u32,
+}
+
+impl DeviceCapabilities {
+    /// 以 (名称, 值) 的形式列出所有条目，日志和 GUI 面板共用
+    pub fn entries(&self) -> Vec<(&'static str, String)> {
+        let yes_no = |supported: bool| if supported { "yes" } else { "no" }.to_string();
+
+        vec![
+            ("Backend", self.backend.clone()),
+            ("Device", self.device_name.clone()),
+            ("API Version", self.api_version.clone()),
+            ("Max MSAA Samples", self.max_msaa_samples.to_string()),
+            ("Max Anisotropy", format!("{}", self.max_anisotropy)),
+            ("Timestamp Query", yes_no(self.timestamp_query)),
+            ("Polygon Mode Line", yes_no(self.polygon_mode_line)),
+            ("Polygon Mode Point", yes_no(self.polygon_mode_point)),
+            ("Max Texture 2D", self.max_texture_dimension_2d.to_string()),
+            ("Max Uniform Buffer", format!("{} bytes", self.max_uniform_buffer_size)),
+            ("Max Vertex Attributes", self.max_vertex_attributes.to_string()),
+        ]
+    }
+
+    /// 输出到日志（单个日志块）
+    pub fn log(&self) {
+        info!("Device capabilities:\n{}", self);
+    }
+}
+
+impl fmt::Display for DeviceCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in self.entries() {
+            writeln!(f, "  {:<22} {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_lists_all_entries() {
+        let caps = DeviceCapabilities {
+            backend: "wgpu".to_string(),
+            device_name: "Test GPU".to_string(),
+            max_msaa_samples: 8,
+            timestamp_query: true,
+            ..Default::default()
+        };
+
+        let text = caps.to_string();
+        assert_eq!(text.lines().count(), caps.entries().len());
+        assert!(text.contains("Test GPU"));
+        assert!(text.contains("Max MSAA Samples       8"));
+        assert!(text.contains("Timestamp Query        yes"));
+        assert!(text.contains("Polygon Mode Line      no"));
+    }
+}
@@ -0,0 +1,80 @@
+//! GPU 资源的调试名称
+//!
+//! RenderDoc、PIX 和 Xcode 的 GPU 抓帧中未命名的资源难以辨认。各后端按用途和来源为缓冲、纹理和管线命名，
+//! 例如 "Vertex Buffer: sphere.obj"（wgpu 的标签、DX12 的 `SetName`、Metal 的 `set_label`、
+//! Vulkan 的 `VK_EXT_debug_utils` 对象名称）。
+//!
+//! 命名只在启用图形 API 验证（`graphics.validation`）时进行，发布构建没有额外的字符串分配和 API 调用；
+//! 名称不影响渲染结果。
+
+use std::path::Path;
+
+use crate::core::SceneConfig;
+
+/// 调试名称生成器，未启用时所有名称为 `None`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DebugNames {
+    enabled: bool,
+}
+
+impl DebugNames {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// 是否为资源命名
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// 资源名称 "用途: 来源"，来源为文件路径时只取文件名
+    pub fn name(&self, role: &str, source: &str) -> Option<String> {
+        self.enabled.then(|| format!("{}: {}", role, file_name(source)))
+    }
+
+    /// 场景合并缓冲的名称，来源为场景中所有模型的文件名
+    pub fn scene(&self, role: &str, scene: &SceneConfig) -> Option<String> {
+        self.enabled.then(|| {
+            let sources: Vec<&str> = scene.models.iter().map(|model| file_name(&model.path)).collect();
+            format!("{}: {}", role, sources.join(", "))
+        })
+    }
+
+    /// 场景合并缓冲的标签，未启用时退回到只有用途的固定标签（供创建时必须给出标签的 API 使用）
+    pub fn scene_label(&self, role: &str, scene: &SceneConfig) -> String {
+        self.scene(role, scene).unwrap_or_else(|| role.to_string())
+    }
+}
+
+/// 路径的文件名部分（内嵌纹理的 `#imageN` 后缀保留）
+fn file_name(source: &str) -> &str {
+    Path::new(source).file_name().and_then(|name| name.to_str()).unwrap_or(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::scene::ModelConfig;
+
+    #[test]
+    fn test_names_from_role_and_file_name() {
+        let names = DebugNames::new(true);
+        assert_eq!(names.name("Texture", "assets/textures/brick.png").as_deref(), Some("Texture: brick.png"));
+        assert_eq!(names.name("Texture", "assets/helmet.gltf#image0").as_deref(), Some("Texture: helmet.gltf#image0"));
+
+        let models = ["assets/models/sphere.obj", "cube.fbx"]
+            .into_iter()
+            .map(|path| ModelConfig { path: path.to_string(), ..Default::default() })
+            .collect();
+        let scene = SceneConfig { models, ..Default::default() };
+        assert_eq!(names.scene("Vertex Buffer", &scene).as_deref(), Some("Vertex Buffer: sphere.obj, cube.fbx"));
+    }
+
+    #[test]
+    fn test_disabled_names() {
+        let names = DebugNames::default();
+        assert!(!names.enabled());
+        assert_eq!(names.name("Texture", "brick.png"), None);
+        assert_eq!(names.scene_label("Index Buffer", &SceneConfig::default()), "Index Buffer");
+    }
+}
@@ -19,7 +19,7 @@
 //! 7. 鍒涘缓鍚屾瀵硅薄锛團ence锛?
 
 use std::sync::Arc;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, trace, warn};
 use windows::{
     core::*, Win32::Graphics::Direct3D::*, Win32::Graphics::Direct3D12::*,
     Win32::Graphics::Dxgi::Common::*, Win32::Graphics::Dxgi::*,
@@ -30,7 +30,20 @@ use winit::dpi::LogicalSize;
 use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
 
 use crate::gfx::backend::GraphicsBackend;
+use crate::gfx::capabilities::DeviceCapabilities;
+use crate::gfx::window::place_window;
 use crate::core::Config;
+use crate::core::error::{GraphicsError, Result};
+use crate::core::config::{effective_buffer_count, SurfaceFormat};
+use crate::gfx::surface_format::select_surface_format;
+
+/// 可以作为交换链渲染目标视图的格式，自动选择时优先使用 sRGB 格式
+const SWAP_CHAIN_FORMATS: [DXGI_FORMAT; 4] = [
+    DXGI_FORMAT_R8G8B8A8_UNORM_SRGB,
+    DXGI_FORMAT_B8G8R8A8_UNORM_SRGB,
+    DXGI_FORMAT_R8G8B8A8_UNORM,
+    DXGI_FORMAT_B8G8R8A8_UNORM,
+];
 
 /// DirectX 12 鍥惧舰鍚庣
 ///
@@ -76,6 +89,12 @@ pub struct Dx12Context {
     pub width: u32,
     /// 绐楀彛楂樺害
     pub height: u32,
+    /// 交换链缓冲数（按 DXGI 支持范围钳制后的实际值）
+    pub buffer_count: u32,
+    /// 渲染目标视图格式，管线的颜色目标格式与其一致
+    pub rtv_format: DXGI_FORMAT,
+    /// 调试层的消息队列，未启用调试层时为 `None`
+    info_queue: Option<ID3D12InfoQueue>,
 }
 
 // 涓轰簡鍦ㄥ绾跨▼鐜涓娇鐢紝闇€瑕佸疄鐜?Send 鍜?Sync
@@ -119,31 +138,26 @@ impl Dx12Context {
         let height = config.window.height;
 
         // 鍒涘缓绐楀彛
+        let window_builder = WindowBuilder::new()
+            .with_title(format!("{} [{}]", config.window.title, config.graphics.backend.name()))
+            .with_inner_size(LogicalSize::new(width, height))
+            .with_resizable(config.window.resizable);
         let window = Arc::new(
-            WindowBuilder::new()
-                .with_title(format!("{} [{}]", config.window.title, config.graphics.backend.name()))
-                .with_inner_size(LogicalSize::new(width, height))
-                .with_resizable(config.window.resizable)
+            place_window(window_builder, event_loop, &config.window)
                 .build(event_loop)
                 .expect("Failed to create window")
         );
 
         unsafe {
-            // 1. 鍚敤璋冭瘯灞傦紙浠?Debug 妯″紡锛?
-            #[cfg(debug_assertions)]
-            {
-                let mut debug: Option<ID3D12Debug> = None;
-                if let Ok(_) = D3D12GetDebugInterface(&mut debug) {
-                    let debug = debug.unwrap();
-                    debug.EnableDebugLayer();
-                    debug!("DX12 Debug Layer enabled");
-                } else {
-                    warn!("Failed to enable DX12 Debug Layer");
-                }
-            }
+            // 1. 按配置启用调试层（必须在创建设备之前），未安装时跳过
+            let debug_layer = config.graphics.validation && enable_debug_layer();
 
             // 2. 鍒涘缓 DXGI 宸ュ巶
-            let factory: IDXGIFactory4 = CreateDXGIFactory2(DXGI_CREATE_FACTORY_DEBUG).unwrap();
+            // DXGI 调试版本随调试层一起安装，不可用时退回普通工厂
+            let factory_flags = if debug_layer { DXGI_CREATE_FACTORY_DEBUG } else { DXGI_CREATE_FACTORY_FLAGS(0) };
+            let factory: IDXGIFactory4 = CreateDXGIFactory2(factory_flags)
+                .or_else(|_| CreateDXGIFactory2(DXGI_CREATE_FACTORY_FLAGS(0)))
+                .expect("Failed to create DXGI factory");
 
             // 3. 鍒涘缓 D3D12 璁惧
             let mut device: Option<ID3D12Device> = None;
@@ -154,6 +168,8 @@ impl Dx12Context {
             #[cfg(debug_assertions)]
             debug!("D3D12 Device created successfully");
 
+            let info_queue = if debug_layer { create_info_queue(&device) } else { None };
+
             // 4. 鍒涘缓鍛戒护闃熷垪
             let queue_desc = D3D12_COMMAND_QUEUE_DESC {
                 Type: D3D12_COMMAND_LIST_TYPE_DIRECT,
@@ -171,17 +187,45 @@ impl Dx12Context {
                 }
                 _ => panic!("Expected Win32 window handle on Windows platform"),
             };
+            // FLIP 模型至少需要 2 个缓冲
+            let buffer_count = effective_buffer_count(
+                config.graphics.buffer_count,
+                2,
+                Some(DXGI_MAX_SWAP_CHAIN_BUFFERS),
+            );
+            info!(
+                requested = config.graphics.buffer_count,
+                effective = buffer_count,
+                "Swapchain buffer count selected"
+            );
+
+            let available_formats: Vec<DXGI_FORMAT> = SWAP_CHAIN_FORMATS
+                .into_iter()
+                .filter(|&format| {
+                    supports_format(&device, format, D3D12_FORMAT_SUPPORT1_RENDER_TARGET)
+                        && supports_format(&device, swap_chain_buffer_format(format), D3D12_FORMAT_SUPPORT1_DISPLAY)
+                })
+                .collect();
+            let rtv_format = select_surface_format(
+                &available_formats,
+                config.graphics.surface_format,
+                known_surface_format,
+                is_srgb_format,
+            )
+            .unwrap_or(DXGI_FORMAT_R8G8B8A8_UNORM);
+
             let swap_chain_desc = DXGI_SWAP_CHAIN_DESC1 {
                 Width: width,
                 Height: height,
-                Format: DXGI_FORMAT_R8G8B8A8_UNORM,
+                Format: swap_chain_buffer_format(rtv_format),
                 SampleDesc: DXGI_SAMPLE_DESC {
                     Count: 1,
                     ..Default::default()
                 },
                 BufferUsage: DXGI_USAGE_RENDER_TARGET_OUTPUT,
-                BufferCount: 2,
-                SwapEffect: DXGI_SWAP_EFFECT_FLIP_DISCARD,
+                BufferCount: buffer_count,
+                // SEQUENTIAL 在呈现后保留缓冲内容，关闭每帧清除颜色时才能在上一次的结果上继续绘制
+                SwapEffect: DXGI_SWAP_EFFECT_FLIP_SEQUENTIAL,
                 ..Default::default()
             };
 
@@ -192,11 +236,11 @@ impl Dx12Context {
                 .expect("Failed to cast swap chain to IDXGISwapChain3");
 
             #[cfg(debug_assertions)]
-            info!(width, height, buffers = 2, "Swap chain created");
+            info!(width, height, buffers = buffer_count, "Swap chain created");
 
             // 6. 鍒涘缓鎻忚堪绗﹀爢
             let rtv_heap_desc = D3D12_DESCRIPTOR_HEAP_DESC {
-                NumDescriptors: 2,
+                NumDescriptors: buffer_count,
                 Type: D3D12_DESCRIPTOR_HEAP_TYPE_RTV,
                 Flags: D3D12_DESCRIPTOR_HEAP_FLAG_NONE,
                 NodeMask: 0,
@@ -206,12 +250,12 @@ impl Dx12Context {
 
             // 7. 鍒涘缓娓叉煋鐩爣瑙嗗浘锛圧TV锛?
             let rtv_handle = rtv_heap.GetCPUDescriptorHandleForHeapStart();
-            for i in 0..2 {
+            for i in 0..buffer_count {
                 let surface: ID3D12Resource = swap_chain.GetBuffer(i).unwrap();
                 let handle = D3D12_CPU_DESCRIPTOR_HANDLE {
                     ptr: rtv_handle.ptr + (i as usize * rtv_descriptor_size),
                 };
-                device.CreateRenderTargetView(&surface, None, handle);
+                device.CreateRenderTargetView(&surface, Some(&render_target_view_desc(rtv_format)), handle);
             }
 
             // 8. 鍒涘缓鍚屾瀵硅薄
@@ -228,7 +272,7 @@ impl Dx12Context {
             #[cfg(debug_assertions)]
             info!("DX12 Backend initialization complete");
 
-            Self {
+            let context = Self {
                 device,
                 command_queue,
                 swap_chain,
@@ -241,9 +285,196 @@ impl Dx12Context {
                 window,
                 width,
                 height,
+                buffer_count,
+                rtv_format,
+                info_queue,
+            };
+            context.report_capabilities().log();
+
+            context
+        }
+    }
+
+    /// 查询设备能力
+    ///
+    /// 时间戳查询和线框填充是 D3D12 的基础功能；各向异性和纹理尺寸使用 API 规定的上限。
+    pub fn report_capabilities(&self) -> DeviceCapabilities {
+        unsafe {
+            let device_name = CreateDXGIFactory2::<IDXGIFactory4>(DXGI_CREATE_FACTORY_FLAGS(0))
+                .and_then(|factory| factory.EnumAdapterByLuid::<IDXGIAdapter1>(self.device.GetAdapterLuid()))
+                .and_then(|adapter| adapter.GetDesc1())
+                .map(|desc| {
+                    let len = desc.Description.iter().position(|&c| c == 0).unwrap_or(desc.Description.len());
+                    String::from_utf16_lossy(&desc.Description[..len])
+                })
+                .unwrap_or_else(|_| "Unknown".to_string());
+
+            let max_msaa_samples = [16u32, 8, 4, 2]
+                .into_iter()
+                .find(|&count| {
+                    let mut levels = D3D12_FEATURE_DATA_MULTISAMPLE_QUALITY_LEVELS {
+                        Format: self.rtv_format,
+                        SampleCount: count,
+                        ..Default::default()
+                    };
+                    self.device
+                        .CheckFeatureSupport(
+                            D3D12_FEATURE_MULTISAMPLE_QUALITY_LEVELS,
+                            &mut levels as *mut _ as *mut core::ffi::c_void,
+                            std::mem::size_of::<D3D12_FEATURE_DATA_MULTISAMPLE_QUALITY_LEVELS>() as u32,
+                        )
+                        .is_ok()
+                        && levels.NumQualityLevels > 0
+                })
+                .unwrap_or(1);
+
+            DeviceCapabilities {
+                backend: "DirectX 12".to_string(),
+                device_name,
+                api_version: "Direct3D 12 (feature level 11_0+)".to_string(),
+                max_msaa_samples,
+                max_anisotropy: D3D12_MAX_MAXANISOTROPY as f32,
+                timestamp_query: true,
+                polygon_mode_line: true,
+                // D3D12 只有实心和线框两种填充模式
+                polygon_mode_point: false,
+                max_texture_dimension_2d: D3D12_REQ_TEXTURE2D_U_OR_V_DIMENSION,
+                max_uniform_buffer_size: (D3D12_REQ_CONSTANT_BUFFER_ELEMENT_COUNT * 16) as u64,
+                max_vertex_attributes: D3D12_IA_VERTEX_INPUT_STRUCTURE_ELEMENT_COUNT,
+            }
+        }
+    }
+
+    /// 把调试层积累的消息输出到日志并清空
+    ///
+    /// 每帧提交后调用；在处理失败的 HRESULT 之前调用可以先输出 D3D12 给出的具体原因。
+    /// 未启用调试层时什么也不做。
+    pub fn flush_debug_messages(&self) {
+        let Some(info_queue) = &self.info_queue else {
+            return;
+        };
+
+        unsafe {
+            for index in 0..info_queue.GetNumStoredMessages() {
+                // 先查询消息长度，再按 D3D12_MESSAGE 的对齐分配缓冲
+                let mut length = 0usize;
+                if info_queue.GetMessage(index, None, &mut length).is_err() || length == 0 {
+                    continue;
+                }
+                let mut buffer = vec![0u64; length.div_ceil(std::mem::size_of::<u64>())];
+                let message = buffer.as_mut_ptr() as *mut D3D12_MESSAGE;
+                if info_queue.GetMessage(index, Some(message), &mut length).is_err() {
+                    continue;
+                }
+
+                let message = &*message;
+                let description = message.pDescription.to_string().unwrap_or_default();
+                let id = message.ID.0;
+                match message.Severity {
+                    D3D12_MESSAGE_SEVERITY_CORRUPTION | D3D12_MESSAGE_SEVERITY_ERROR => {
+                        error!(target: "d3d12", id, "{}", description)
+                    }
+                    D3D12_MESSAGE_SEVERITY_WARNING => warn!(target: "d3d12", id, "{}", description),
+                    D3D12_MESSAGE_SEVERITY_INFO => debug!(target: "d3d12", id, "{}", description),
+                    _ => trace!(target: "d3d12", id, "{}", description),
+                }
             }
+            info_queue.ClearStoredMessages();
         }
     }
+
+    /// 把提交或呈现失败的 HRESULT 转换为错误
+    ///
+    /// 设备被移除或重置（驱动更新、TDR 等）时查询 `GetDeviceRemovedReason` 并记录，
+    /// 返回 [`GraphicsError::DeviceLost`]；其他失败返回 `CommandExecution`。
+    pub fn device_error(&self, action: &str, error: Error) -> GraphicsError {
+        let code = error.code();
+        if code != DXGI_ERROR_DEVICE_REMOVED && code != DXGI_ERROR_DEVICE_RESET {
+            return GraphicsError::CommandExecution(format!("{}: {}", action, error));
+        }
+
+        // 设备正常时 GetDeviceRemovedReason 返回 S_OK，否则错误码即移除原因
+        let reason = match unsafe { self.device.GetDeviceRemovedReason() } {
+            Ok(()) => "unknown".to_string(),
+            Err(reason) => format!("{} ({:#010x})", reason.message(), reason.code().0),
+        };
+        error!(code = %format!("{:#010x}", code.0), %reason, "{}: D3D12 device lost", action);
+        GraphicsError::DeviceLost(format!("{}: {}", action, reason))
+    }
+
+    /// 等待命令队列中的所有工作完成
+    ///
+    /// 使用一次性的栅栏，不影响渲染器维护的帧栅栏值。
+    pub fn wait_idle(&self) -> Result<()> {
+        unsafe {
+            let fence: ID3D12Fence = self.device.CreateFence(0, D3D12_FENCE_FLAG_NONE)
+                .map_err(|e| GraphicsError::ResourceCreation(format!("Failed to create idle fence: {}", e)))?;
+            self.command_queue.Signal(&fence, 1)
+                .map_err(|e| GraphicsError::CommandExecution(format!("Failed to signal idle fence: {}", e)))?;
+
+            // 事件句柄为空时 SetEventOnCompletion 会阻塞到栅栏到达目标值
+            if fence.GetCompletedValue() < 1 {
+                fence.SetEventOnCompletion(1, windows::Win32::Foundation::HANDLE::default())
+                    .map_err(|e| GraphicsError::CommandExecution(format!("Failed to wait for idle fence: {}", e)))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 交换链格式对应的配置项
+fn known_surface_format(format: DXGI_FORMAT) -> Option<SurfaceFormat> {
+    match format {
+        DXGI_FORMAT_B8G8R8A8_UNORM_SRGB => Some(SurfaceFormat::Bgra8Srgb),
+        DXGI_FORMAT_R8G8B8A8_UNORM_SRGB => Some(SurfaceFormat::Rgba8Srgb),
+        DXGI_FORMAT_B8G8R8A8_UNORM => Some(SurfaceFormat::Bgra8Unorm),
+        DXGI_FORMAT_R8G8B8A8_UNORM => Some(SurfaceFormat::Rgba8Unorm),
+        _ => None,
+    }
+}
+
+/// 写入时是否由硬件做 sRGB 编码
+pub fn is_srgb_format(format: DXGI_FORMAT) -> bool {
+    matches!(format, DXGI_FORMAT_R8G8B8A8_UNORM_SRGB | DXGI_FORMAT_B8G8R8A8_UNORM_SRGB)
+}
+
+/// 交换链缓冲格式
+///
+/// FLIP 模型的交换链缓冲不能使用 sRGB 格式，sRGB 渲染目标视图创建在对应的 UNORM 缓冲上。
+pub fn swap_chain_buffer_format(rtv_format: DXGI_FORMAT) -> DXGI_FORMAT {
+    match rtv_format {
+        DXGI_FORMAT_R8G8B8A8_UNORM_SRGB => DXGI_FORMAT_R8G8B8A8_UNORM,
+        DXGI_FORMAT_B8G8R8A8_UNORM_SRGB => DXGI_FORMAT_B8G8R8A8_UNORM,
+        format => format,
+    }
+}
+
+/// 交换链缓冲的渲染目标视图描述
+pub fn render_target_view_desc(format: DXGI_FORMAT) -> D3D12_RENDER_TARGET_VIEW_DESC {
+    D3D12_RENDER_TARGET_VIEW_DESC {
+        Format: format,
+        ViewDimension: D3D12_RTV_DIMENSION_TEXTURE2D,
+        ..Default::default()
+    }
+}
+
+/// 查询设备是否以指定用途支持某个格式
+fn supports_format(device: &ID3D12Device, format: DXGI_FORMAT, support: D3D12_FORMAT_SUPPORT1) -> bool {
+    let mut data = D3D12_FEATURE_DATA_FORMAT_SUPPORT {
+        Format: format,
+        ..Default::default()
+    };
+    unsafe {
+        device
+            .CheckFeatureSupport(
+                D3D12_FEATURE_FORMAT_SUPPORT,
+                &mut data as *mut _ as *mut core::ffi::c_void,
+                std::mem::size_of::<D3D12_FEATURE_DATA_FORMAT_SUPPORT>() as u32,
+            )
+            .is_ok()
+            && (data.Support1 & support) == support
+    }
 }
 
 impl GraphicsBackend for Dx12Context {
@@ -258,4 +489,48 @@ impl GraphicsBackend for Dx12Context {
     fn backend_name(&self) -> &str {
         "DirectX 12"
     }
+
+    fn report_capabilities(&self) -> DeviceCapabilities {
+        self.report_capabilities()
+    }
+
+    fn wait_idle(&self) -> Result<()> {
+        self.wait_idle()
+    }
+}
+
+/// 启用 D3D12 调试层
+///
+/// 调试层属于 Windows 的可选功能（Graphics Tools），未安装时输出警告并返回 `false`。
+unsafe fn enable_debug_layer() -> bool {
+    let mut debug: Option<ID3D12Debug> = None;
+    match D3D12GetDebugInterface(&mut debug).map(|_| debug) {
+        Ok(Some(debug)) => {
+            debug.EnableDebugLayer();
+            info!("DX12 debug layer enabled");
+            true
+        }
+        _ => {
+            warn!("DX12 debug layer is not installed (Graphics Tools optional feature), validation is disabled");
+            false
+        }
+    }
+}
+
+/// 获取设备的调试消息队列
+///
+/// 调试构建中附加了调试器时，遇到损坏和错误级别的消息会中断到调试器。
+unsafe fn create_info_queue(device: &ID3D12Device) -> Option<ID3D12InfoQueue> {
+    let info_queue: ID3D12InfoQueue = device
+        .cast()
+        .map_err(|e| warn!("Failed to get ID3D12InfoQueue: {}", e))
+        .ok()?;
+
+    #[cfg(debug_assertions)]
+    if windows::Win32::System::Diagnostics::Debug::IsDebuggerPresent().as_bool() {
+        let _ = info_queue.SetBreakOnSeverity(D3D12_MESSAGE_SEVERITY_CORRUPTION, true);
+        let _ = info_queue.SetBreakOnSeverity(D3D12_MESSAGE_SEVERITY_ERROR, true);
+    }
+
+    Some(info_queue)
 }
@@ -0,0 +1,549 @@
+//! DX12 GUI 绘制器
+//!
+//! 在场景绘制之后、后台缓冲转换回 Present 状态之前绘制内置 GUI 的 egui 网格。
+//! 纹理以 sRGB 格式保存，像素着色器把采样结果转回 gamma 空间后与顶点颜色相乘，
+//! 按 egui 的约定在 gamma 空间做预乘 alpha 混合；渲染目标为 sRGB 格式时输出前转回线性值。
+//!
+//! 上传缓冲、每帧的顶点/索引缓冲以及已释放的纹理按帧资源槽位保留，
+//! 直到渲染器等待该槽位的栅栏后才真正释放。
+
+use std::collections::HashMap;
+use std::mem::ManuallyDrop;
+use std::path::Path;
+
+use windows::core::PCSTR;
+use windows::Win32::Foundation::RECT;
+use windows::Win32::Graphics::Direct3D::Fxc::*;
+use windows::Win32::Graphics::Direct3D::*;
+use windows::Win32::Graphics::Direct3D12::*;
+use windows::Win32::Graphics::Dxgi::Common::*;
+
+use crate::core::error::{DistRenderError, GraphicsError, Result};
+use crate::gfx::dx12::context::is_srgb_format;
+use crate::gfx::dx12::descriptor::Dx12DescriptorHeap;
+use crate::gui::paint::{image_pixels, GuiBatch};
+use crate::gui::GuiFrame;
+use crate::renderer::resources::descriptor::DescriptorHeapDescriptor;
+
+/// 同时存在的 GUI 纹理数量上限（字体图集加上少量用户图片）
+const MAX_GUI_TEXTURES: u32 = 64;
+
+/// egui 顶点大小：位置和纹理坐标各两个 f32，加上 RGBA8 颜色
+const GUI_VERTEX_STRIDE: u32 = std::mem::size_of::<egui::epaint::Vertex>() as u32;
+
+/// 上传到 GPU 的 egui 纹理及其 SRV 所在的描述符槽位
+struct GuiTexture {
+    resource: ID3D12Resource,
+    slot: u32,
+}
+
+/// 某个帧资源槽位上 GPU 可能仍在使用的资源
+#[derive(Default)]
+struct InFlightResources {
+    resources: Vec<ID3D12Resource>,
+    /// 已释放纹理的描述符槽位，资源释放后才能复用
+    slots: Vec<u32>,
+}
+
+/// DX12 GUI 绘制器
+pub struct GuiPainter {
+    root_signature: ID3D12RootSignature,
+    pso: ID3D12PipelineState,
+    /// 着色器可见的 SRV 堆，每个纹理占用一个槽位
+    srv_heap: Dx12DescriptorHeap,
+    free_slots: Vec<u32>,
+    textures: HashMap<egui::TextureId, GuiTexture>,
+    in_flight: Vec<InFlightResources>,
+    frame_index: usize,
+    /// 渲染目标为 UNORM 格式，着色器直接输出 gamma 空间的颜色
+    manual_srgb: bool,
+}
+
+impl GuiPainter {
+    /// 创建绘制器，`rtv_format` 为后台缓冲的渲染目标视图格式，`frame_count` 为帧资源槽位数量
+    pub fn new(device: &ID3D12Device, shader_dir: &Path, rtv_format: DXGI_FORMAT, frame_count: usize) -> Result<Self> {
+        unsafe {
+            let (root_signature, pso) = create_pipeline(device, shader_dir, rtv_format)?;
+            let srv_heap = Dx12DescriptorHeap::new(
+                device,
+                &DescriptorHeapDescriptor::srv_cbv_uav(MAX_GUI_TEXTURES).with_name("GUI SRV Heap"),
+            )?;
+            Ok(Self {
+                root_signature,
+                pso,
+                srv_heap,
+                free_slots: (0..MAX_GUI_TEXTURES).rev().collect(),
+                textures: HashMap::new(),
+                in_flight: (0..frame_count).map(|_| InFlightResources::default()).collect(),
+                frame_index: 0,
+                manual_srgb: !is_srgb_format(rtv_format),
+            })
+        }
+    }
+
+    /// 开始新的一帧，调用前渲染器必须已经等待过 `frame_index` 槽位的栅栏
+    pub fn begin_frame(&mut self, frame_index: usize) {
+        self.frame_index = frame_index;
+        let retired = std::mem::take(&mut self.in_flight[frame_index]);
+        self.free_slots.extend(retired.slots);
+    }
+
+    /// GPU 空闲后按新的帧资源数量重置（交换链重建时调用）
+    pub fn reset_frames(&mut self, frame_count: usize) {
+        for retired in self.in_flight.drain(..) {
+            self.free_slots.extend(retired.slots);
+        }
+        self.in_flight = (0..frame_count).map(|_| InFlightResources::default()).collect();
+        self.frame_index = 0;
+    }
+
+    /// 录制本帧的纹理上传命令，需在绘制之前调用
+    pub fn update_textures(
+        &mut self,
+        device: &ID3D12Device,
+        command_list: &ID3D12GraphicsCommandList,
+        frame: &GuiFrame,
+    ) -> Result<()> {
+        unsafe {
+            for (id, delta) in &frame.textures_delta.set {
+                let [width, height] = delta.image.size();
+                let pixels = image_pixels(&delta.image);
+
+                // 带位置的更新只覆盖已有纹理的一部分，否则重新创建整张纹理
+                let existing = delta.pos.is_some() && self.textures.contains_key(id);
+                let [x, y] = if existing {
+                    let texture = &self.textures[id].resource;
+                    command_list.ResourceBarrier(&[transition(
+                        texture,
+                        D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+                        D3D12_RESOURCE_STATE_COPY_DEST,
+                    )]);
+                    delta.pos.unwrap()
+                } else {
+                    let slot = self.free_slots.pop().ok_or_else(|| DistRenderError::Graphics(
+                        GraphicsError::ResourceCreation(format!("Too many GUI textures (max {})", MAX_GUI_TEXTURES))
+                    ))?;
+                    let resource = create_texture(device, width as u64, height as u32)?;
+                    device.CreateShaderResourceView(
+                        &resource,
+                        None,
+                        self.srv_heap.to_dx12_cpu_handle(self.srv_heap.cpu_handle(slot)),
+                    );
+                    if let Some(old) = self.textures.insert(*id, GuiTexture { resource, slot }) {
+                        self.retire(old);
+                    }
+                    [0, 0]
+                };
+                let texture = &self.textures[id].resource;
+
+                // 上传缓冲的行距需按 256 字节对齐
+                let row_bytes = width as u32 * 4;
+                let row_pitch = row_bytes.next_multiple_of(D3D12_TEXTURE_DATA_PITCH_ALIGNMENT);
+                let upload = create_upload_buffer(device, row_pitch as u64 * height as u64)?;
+                let mut data = std::ptr::null_mut();
+                upload.Map(0, None, Some(&mut data)).map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::ResourceCreation(format!("Failed to map GUI upload buffer: {:?}", e))
+                ))?;
+                for (row, src) in pixels.chunks_exact(row_bytes as usize).enumerate() {
+                    let dst = (data as *mut u8).add(row * row_pitch as usize);
+                    std::ptr::copy_nonoverlapping(src.as_ptr(), dst, src.len());
+                }
+                upload.Unmap(0, None);
+
+                let dst_location = D3D12_TEXTURE_COPY_LOCATION {
+                    pResource: ManuallyDrop::new(Some(texture.clone())),
+                    Type: D3D12_TEXTURE_COPY_TYPE_SUBRESOURCE_INDEX,
+                    Anonymous: D3D12_TEXTURE_COPY_LOCATION_0 { SubresourceIndex: 0 },
+                };
+                let src_location = D3D12_TEXTURE_COPY_LOCATION {
+                    pResource: ManuallyDrop::new(Some(upload.clone())),
+                    Type: D3D12_TEXTURE_COPY_TYPE_PLACED_FOOTPRINT,
+                    Anonymous: D3D12_TEXTURE_COPY_LOCATION_0 {
+                        PlacedFootprint: D3D12_PLACED_SUBRESOURCE_FOOTPRINT {
+                            Offset: 0,
+                            Footprint: D3D12_SUBRESOURCE_FOOTPRINT {
+                                Format: DXGI_FORMAT_R8G8B8A8_UNORM_SRGB,
+                                Width: width as u32,
+                                Height: height as u32,
+                                Depth: 1,
+                                RowPitch: row_pitch,
+                            },
+                        },
+                    },
+                };
+                command_list.CopyTextureRegion(&dst_location, x as u32, y as u32, 0, &src_location, None);
+                command_list.ResourceBarrier(&[transition(
+                    texture,
+                    D3D12_RESOURCE_STATE_COPY_DEST,
+                    D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+                )]);
+                self.in_flight[self.frame_index].resources.push(upload);
+            }
+            Ok(())
+        }
+    }
+
+    /// 在当前绑定的渲染目标上绘制 GUI，`extent` 为后台缓冲的像素尺寸
+    pub fn draw(
+        &mut self,
+        device: &ID3D12Device,
+        command_list: &ID3D12GraphicsCommandList,
+        frame: &GuiFrame,
+        extent: [u32; 2],
+    ) -> Result<()> {
+        let batch = GuiBatch::new(frame, extent);
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        unsafe {
+            let vertex_bytes = batch.vertices.len() * GUI_VERTEX_STRIDE as usize;
+            let index_bytes = batch.indices.len() * std::mem::size_of::<u32>();
+            let vertex_buffer = create_upload_buffer(device, vertex_bytes as u64)?;
+            let index_buffer = create_upload_buffer(device, index_bytes as u64)?;
+            for (buffer, src, len) in [
+                (&vertex_buffer, batch.vertices.as_ptr() as *const u8, vertex_bytes),
+                (&index_buffer, batch.indices.as_ptr() as *const u8, index_bytes),
+            ] {
+                let mut data = std::ptr::null_mut();
+                buffer.Map(0, None, Some(&mut data)).map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::ResourceCreation(format!("Failed to map GUI buffer: {:?}", e))
+                ))?;
+                std::ptr::copy_nonoverlapping(src, data as *mut u8, len);
+                buffer.Unmap(0, None);
+            }
+
+            // GUI 覆盖整个后台缓冲，不使用场景的信箱视口
+            command_list.SetDescriptorHeaps(&[Some(self.srv_heap.heap().clone())]);
+            command_list.SetGraphicsRootSignature(&self.root_signature);
+            command_list.SetPipelineState(&self.pso);
+            command_list.RSSetViewports(&[D3D12_VIEWPORT {
+                TopLeftX: 0.0,
+                TopLeftY: 0.0,
+                Width: extent[0] as f32,
+                Height: extent[1] as f32,
+                MinDepth: 0.0,
+                MaxDepth: 1.0,
+            }]);
+            let constants = [
+                (extent[0] as f32 / frame.pixels_per_point).to_bits(),
+                (extent[1] as f32 / frame.pixels_per_point).to_bits(),
+                self.manual_srgb as u32,
+            ];
+            command_list.SetGraphicsRoot32BitConstants(0, constants.len() as u32, constants.as_ptr() as *const _, 0);
+            command_list.IASetPrimitiveTopology(D3D_PRIMITIVE_TOPOLOGY_TRIANGLELIST);
+            command_list.IASetVertexBuffers(0, Some(&[D3D12_VERTEX_BUFFER_VIEW {
+                BufferLocation: vertex_buffer.GetGPUVirtualAddress(),
+                SizeInBytes: vertex_bytes as u32,
+                StrideInBytes: GUI_VERTEX_STRIDE,
+            }]));
+            command_list.IASetIndexBuffer(Some(&D3D12_INDEX_BUFFER_VIEW {
+                BufferLocation: index_buffer.GetGPUVirtualAddress(),
+                SizeInBytes: index_bytes as u32,
+                Format: DXGI_FORMAT_R32_UINT,
+            }));
+
+            for mesh in &batch.meshes {
+                let Some(texture) = self.textures.get(&mesh.texture_id) else {
+                    continue;
+                };
+                let Some(gpu_handle) = self.srv_heap.gpu_handle(texture.slot) else {
+                    continue;
+                };
+                command_list.RSSetScissorRects(&[RECT {
+                    left: mesh.clip.x as i32,
+                    top: mesh.clip.y as i32,
+                    right: (mesh.clip.x + mesh.clip.width) as i32,
+                    bottom: (mesh.clip.y + mesh.clip.height) as i32,
+                }]);
+                command_list.SetGraphicsRootDescriptorTable(1, self.srv_heap.to_dx12_gpu_handle(gpu_handle));
+                command_list.DrawIndexedInstanced(mesh.index_count, 1, mesh.first_index, mesh.vertex_offset as i32, 0);
+            }
+
+            let in_flight = &mut self.in_flight[self.frame_index];
+            in_flight.resources.push(vertex_buffer);
+            in_flight.resources.push(index_buffer);
+        }
+        Ok(())
+    }
+
+    /// 释放本帧不再使用的纹理，资源保留到该帧的 GPU 工作完成
+    pub fn free_textures(&mut self, frame: &GuiFrame) {
+        for id in &frame.textures_delta.free {
+            if let Some(texture) = self.textures.remove(id) {
+                self.retire(texture);
+            }
+        }
+    }
+
+    fn retire(&mut self, texture: GuiTexture) {
+        let in_flight = &mut self.in_flight[self.frame_index];
+        in_flight.resources.push(texture.resource);
+        in_flight.slots.push(texture.slot);
+    }
+}
+
+/// 创建 GUI 的根签名和 PSO
+///
+/// 根参数 0 为屏幕尺寸的 2 个根常量，根参数 1 为纹理 SRV 的描述符表；
+/// 采样器为静态的线性钳制采样器，egui 纹理选项中的过滤和环绕方式不生效。
+unsafe fn create_pipeline(
+    device: &ID3D12Device,
+    shader_dir: &Path,
+    rtv_format: DXGI_FORMAT,
+) -> Result<(ID3D12RootSignature, ID3D12PipelineState)> {
+    let srv_range = D3D12_DESCRIPTOR_RANGE {
+        RangeType: D3D12_DESCRIPTOR_RANGE_TYPE_SRV,
+        NumDescriptors: 1,
+        BaseShaderRegister: 0,  // t0
+        RegisterSpace: 0,
+        OffsetInDescriptorsFromTableStart: 0,
+    };
+    let root_parameters = [
+        D3D12_ROOT_PARAMETER {
+            ParameterType: D3D12_ROOT_PARAMETER_TYPE_32BIT_CONSTANTS,
+            Anonymous: D3D12_ROOT_PARAMETER_0 {
+                Constants: D3D12_ROOT_CONSTANTS {
+                    ShaderRegister: 0,  // b0
+                    RegisterSpace: 0,
+                    Num32BitValues: 3,
+                },
+            },
+            ShaderVisibility: D3D12_SHADER_VISIBILITY_ALL,
+        },
+        D3D12_ROOT_PARAMETER {
+            ParameterType: D3D12_ROOT_PARAMETER_TYPE_DESCRIPTOR_TABLE,
+            Anonymous: D3D12_ROOT_PARAMETER_0 {
+                DescriptorTable: D3D12_ROOT_DESCRIPTOR_TABLE {
+                    NumDescriptorRanges: 1,
+                    pDescriptorRanges: &srv_range,
+                },
+            },
+            ShaderVisibility: D3D12_SHADER_VISIBILITY_PIXEL,
+        },
+    ];
+    let sampler = D3D12_STATIC_SAMPLER_DESC {
+        Filter: D3D12_FILTER_MIN_MAG_MIP_LINEAR,
+        AddressU: D3D12_TEXTURE_ADDRESS_MODE_CLAMP,
+        AddressV: D3D12_TEXTURE_ADDRESS_MODE_CLAMP,
+        AddressW: D3D12_TEXTURE_ADDRESS_MODE_CLAMP,
+        MipLODBias: 0.0,
+        MaxAnisotropy: 0,
+        ComparisonFunc: D3D12_COMPARISON_FUNC_NEVER,
+        BorderColor: D3D12_STATIC_BORDER_COLOR_TRANSPARENT_BLACK,
+        MinLOD: 0.0,
+        MaxLOD: D3D12_FLOAT32_MAX,
+        ShaderRegister: 0,  // s0
+        RegisterSpace: 0,
+        ShaderVisibility: D3D12_SHADER_VISIBILITY_PIXEL,
+    };
+    let root_desc = D3D12_ROOT_SIGNATURE_DESC {
+        NumParameters: root_parameters.len() as u32,
+        pParameters: root_parameters.as_ptr(),
+        NumStaticSamplers: 1,
+        pStaticSamplers: &sampler,
+        Flags: D3D12_ROOT_SIGNATURE_FLAG_ALLOW_INPUT_ASSEMBLER_INPUT_LAYOUT,
+    };
+
+    let mut signature = None;
+    D3D12SerializeRootSignature(&root_desc, D3D_ROOT_SIGNATURE_VERSION_1, &mut signature, None)
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ResourceCreation(format!("Failed to serialize GUI root signature: {:?}", e))
+        ))?;
+    let signature = signature.unwrap();
+    let root_signature: ID3D12RootSignature = device.CreateRootSignature(
+        0,
+        std::slice::from_raw_parts(signature.GetBufferPointer() as _, signature.GetBufferSize())
+    ).map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to create GUI root signature: {:?}", e))
+    ))?;
+
+    let shader_path = shader_dir.join("gui.hlsl");
+    let hlsl = std::fs::read_to_string(&shader_path)
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ShaderCompilation(format!("Failed to read {}: {}", shader_path.display(), e))
+        ))?;
+    let vs_blob = compile_shader(&hlsl, windows::core::s!("VSMain"), windows::core::s!("vs_5_0"))?;
+    let ps_blob = compile_shader(&hlsl, windows::core::s!("PSMain"), windows::core::s!("ps_5_0"))?;
+
+    // egui::epaint::Vertex: pos (float2), uv (float2), color (RGBA8)
+    let input_element_descs = [
+        (windows::core::s!("POSITION"), DXGI_FORMAT_R32G32_FLOAT, 0),
+        (windows::core::s!("TEXCOORD"), DXGI_FORMAT_R32G32_FLOAT, 8),
+        (windows::core::s!("COLOR"), DXGI_FORMAT_R8G8B8A8_UNORM, 16),
+    ]
+    .map(|(semantic, format, offset)| D3D12_INPUT_ELEMENT_DESC {
+        SemanticName: semantic,
+        SemanticIndex: 0,
+        Format: format,
+        InputSlot: 0,
+        AlignedByteOffset: offset,
+        InputSlotClass: D3D12_INPUT_CLASSIFICATION_PER_VERTEX_DATA,
+        InstanceDataStepRate: 0,
+    });
+
+    let mut pso_desc = D3D12_GRAPHICS_PIPELINE_STATE_DESC::default();
+    pso_desc.pRootSignature = ManuallyDrop::new(Some(root_signature.clone()));
+    pso_desc.VS = D3D12_SHADER_BYTECODE {
+        pShaderBytecode: vs_blob.GetBufferPointer(),
+        BytecodeLength: vs_blob.GetBufferSize(),
+    };
+    pso_desc.PS = D3D12_SHADER_BYTECODE {
+        pShaderBytecode: ps_blob.GetBufferPointer(),
+        BytecodeLength: ps_blob.GetBufferSize(),
+    };
+    // 预乘 alpha 混合，与 egui-wgpu 相同
+    pso_desc.BlendState.RenderTarget[0] = D3D12_RENDER_TARGET_BLEND_DESC {
+        BlendEnable: true.into(),
+        LogicOpEnable: false.into(),
+        SrcBlend: D3D12_BLEND_ONE,
+        DestBlend: D3D12_BLEND_INV_SRC_ALPHA,
+        BlendOp: D3D12_BLEND_OP_ADD,
+        SrcBlendAlpha: D3D12_BLEND_INV_DEST_ALPHA,
+        DestBlendAlpha: D3D12_BLEND_ONE,
+        BlendOpAlpha: D3D12_BLEND_OP_ADD,
+        LogicOp: D3D12_LOGIC_OP_NOOP,
+        RenderTargetWriteMask: D3D12_COLOR_WRITE_ENABLE_ALL.0 as u8,
+    };
+    pso_desc.RasterizerState = D3D12_RASTERIZER_DESC {
+        FillMode: D3D12_FILL_MODE_SOLID,
+        CullMode: D3D12_CULL_MODE_NONE,
+        DepthClipEnable: true.into(),
+        ..Default::default()
+    };
+    // 不绑定深度缓冲，GUI 总是绘制在场景之上
+    pso_desc.DepthStencilState = D3D12_DEPTH_STENCIL_DESC {
+        DepthEnable: false.into(),
+        DepthWriteMask: D3D12_DEPTH_WRITE_MASK_ZERO,
+        DepthFunc: D3D12_COMPARISON_FUNC_ALWAYS,
+        ..Default::default()
+    };
+    pso_desc.SampleMask = 0xFFFFFFFF;
+    pso_desc.DSVFormat = DXGI_FORMAT_UNKNOWN;
+    pso_desc.InputLayout = D3D12_INPUT_LAYOUT_DESC {
+        pInputElementDescs: input_element_descs.as_ptr(),
+        NumElements: input_element_descs.len() as u32,
+    };
+    pso_desc.PrimitiveTopologyType = D3D12_PRIMITIVE_TOPOLOGY_TYPE_TRIANGLE;
+    pso_desc.NumRenderTargets = 1;
+    pso_desc.RTVFormats[0] = rtv_format;
+    pso_desc.SampleDesc.Count = 1;
+
+    let pso: ID3D12PipelineState = device.CreateGraphicsPipelineState(&pso_desc)
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ResourceCreation(format!("Failed to create GUI PSO: {:?}", e))
+        ))?;
+
+    Ok((root_signature, pso))
+}
+
+/// 编译 gui.hlsl 中的一个入口，失败时返回编译器输出
+unsafe fn compile_shader(source: &str, entry_point: PCSTR, target: PCSTR) -> Result<ID3DBlob> {
+    let mut blob = None;
+    let mut error_blob = None;
+    let result = D3DCompile(
+        source.as_ptr() as _,
+        source.len(),
+        None,
+        None,
+        None,
+        entry_point,
+        target,
+        0,
+        0,
+        &mut blob,
+        Some(&mut error_blob),
+    );
+    if let Err(e) = result {
+        let message = error_blob.map_or_else(|| format!("{:?}", e), |error| {
+            String::from_utf8_lossy(std::slice::from_raw_parts(
+                error.GetBufferPointer() as *const u8,
+                error.GetBufferSize(),
+            )).into_owned()
+        });
+        return Err(DistRenderError::Graphics(
+            GraphicsError::ShaderCompilation(format!("GUI shader compile error: {}", message))
+        ));
+    }
+    Ok(blob.unwrap())
+}
+
+/// 创建 CPU 可写的上传缓冲
+unsafe fn create_upload_buffer(device: &ID3D12Device, size: u64) -> Result<ID3D12Resource> {
+    let heap_props = D3D12_HEAP_PROPERTIES {
+        Type: D3D12_HEAP_TYPE_UPLOAD,
+        ..Default::default()
+    };
+    let resource_desc = D3D12_RESOURCE_DESC {
+        Dimension: D3D12_RESOURCE_DIMENSION_BUFFER,
+        Width: size,
+        Height: 1,
+        DepthOrArraySize: 1,
+        MipLevels: 1,
+        SampleDesc: DXGI_SAMPLE_DESC { Count: 1, Quality: 0 },
+        Layout: D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
+        ..Default::default()
+    };
+    let mut buffer: Option<ID3D12Resource> = None;
+    device.CreateCommittedResource(
+        &heap_props,
+        D3D12_HEAP_FLAG_NONE,
+        &resource_desc,
+        D3D12_RESOURCE_STATE_GENERIC_READ,
+        None,
+        &mut buffer,
+    ).map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to create GUI upload buffer: {:?}", e))
+    ))?;
+    Ok(buffer.unwrap())
+}
+
+/// 创建 sRGB 纹理，初始状态为复制目标
+unsafe fn create_texture(device: &ID3D12Device, width: u64, height: u32) -> Result<ID3D12Resource> {
+    let heap_props = D3D12_HEAP_PROPERTIES {
+        Type: D3D12_HEAP_TYPE_DEFAULT,
+        ..Default::default()
+    };
+    let resource_desc = D3D12_RESOURCE_DESC {
+        Dimension: D3D12_RESOURCE_DIMENSION_TEXTURE2D,
+        Width: width,
+        Height: height,
+        DepthOrArraySize: 1,
+        MipLevels: 1,
+        Format: DXGI_FORMAT_R8G8B8A8_UNORM_SRGB,
+        SampleDesc: DXGI_SAMPLE_DESC { Count: 1, Quality: 0 },
+        Layout: D3D12_TEXTURE_LAYOUT_UNKNOWN,
+        ..Default::default()
+    };
+    let mut texture: Option<ID3D12Resource> = None;
+    device.CreateCommittedResource(
+        &heap_props,
+        D3D12_HEAP_FLAG_NONE,
+        &resource_desc,
+        D3D12_RESOURCE_STATE_COPY_DEST,
+        None,
+        &mut texture,
+    ).map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to create GUI texture: {:?}", e))
+    ))?;
+    Ok(texture.unwrap())
+}
+
+fn transition(
+    resource: &ID3D12Resource,
+    before: D3D12_RESOURCE_STATES,
+    after: D3D12_RESOURCE_STATES,
+) -> D3D12_RESOURCE_BARRIER {
+    D3D12_RESOURCE_BARRIER {
+        Type: D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
+        Flags: D3D12_RESOURCE_BARRIER_FLAG_NONE,
+        Anonymous: D3D12_RESOURCE_BARRIER_0 {
+            Transition: ManuallyDrop::new(D3D12_RESOURCE_TRANSITION_BARRIER {
+                pResource: ManuallyDrop::new(Some(resource.clone())),
+                Subresource: D3D12_RESOURCE_BARRIER_ALL_SUBRESOURCES,
+                StateBefore: before,
+                StateAfter: after,
+            }),
+        },
+    }
+}
@@ -4,10 +4,14 @@
 //! - Backend: DX12 设备、命令队列、交换链等基础设施
 //! - Renderer: DX12 渲染器实现
 //! - Descriptor: DX12 描述符管理
+//! - GuiPainter: 内置 GUI 的绘制
+//! - VertexLayout: 顶点布局到输入布局的转换
 
 pub mod context;
 pub mod renderer;
 pub mod descriptor;
+pub mod gui_painter;
+pub mod vertex_layout;
 
 // 重新导出常用类型
 pub use context::Dx12Context;
@@ -1,29 +1,42 @@
 use std::mem::ManuallyDrop;
-use tracing::{trace, debug, info};
+use tracing::{trace, debug, info, warn, error};
 use winit::event_loop::EventLoop;
 use crate::gfx::Dx12Context;
+use crate::gfx::debug_name::DebugNames;
+use crate::gfx::letterbox::{ViewportRect, LETTERBOX_COLOR};
+use crate::gfx::shader_preprocessor::{preprocess, ShaderLanguage};
+use crate::gfx::surface_format::encode_output_color;
 use crate::gfx::backend::GraphicsBackend;
 use crate::core::{Config, SceneConfig};
+use crate::core::config::effective_frames_in_flight;
 use crate::core::error::{Result, DistRenderError, GraphicsError};
-use crate::renderer::resources::vertex::{MyVertex, create_default_triangle, convert_geometry_vertex};
+use crate::renderer::resources::vertex::MyVertex;
+use crate::renderer::resources::vertex_layout::HasVertexLayout;
+use crate::renderer::resources::scene_mesh::{select_lods, ModelMesh, SceneMesh};
+use crate::renderer::stats::{FrameStats, PresentMode, PresentStats};
+use crate::renderer::stencil::{StencilCompare, StencilOp, StencilPass, StencilState, STENCIL_REFERENCE};
 use crate::renderer::resources::resource::FrameResourcePool;
 use crate::renderer::commands::sync::{FenceManager, FenceValue};
+use crate::gfx::dx12::context::{is_srgb_format, render_target_view_desc, swap_chain_buffer_format};
 use crate::gfx::dx12::descriptor::Dx12DescriptorManager;
-use crate::geometry::loaders::{MeshLoader, ObjLoader};
-use crate::component::{Camera, DirectionalLight};
+use crate::gfx::dx12::gui_painter::GuiPainter;
+use crate::gfx::dx12::vertex_layout::Dx12InputLayout;
+use crate::component::{AutoRotate, Camera, DirectionalLight};
 use crate::math::{Vector3, Matrix4};
+use crate::math::matrix::normal_matrix;
+use crate::geometry::mesh::IndexWidth;
 use crate::gui::ipc::GuiStatePacket;
+use crate::gui::{GuiManager, GuiState};
 use std::path::Path;
+use std::time::Instant;
 use std::f32::consts::PI;
-use windows::Win32::Graphics::Dxgi::{DXGI_PRESENT, DXGI_SWAP_CHAIN_FLAG, Common::*};
+use windows::Win32::Graphics::Dxgi::{DXGI_FRAME_STATISTICS, DXGI_PRESENT, DXGI_SWAP_CHAIN_FLAG, Common::*};
 use windows::Win32::Graphics::Direct3D12::*;
 use windows::Win32::Graphics::Direct3D::Fxc::*;
 use windows::Win32::Graphics::Direct3D::*;
 use windows::Win32::Foundation::RECT;
 use windows::Win32::System::Threading::WaitForSingleObject;
 
-const FRAME_COUNT: usize = 2;
-
 /// Uniform Buffer Object - MVP 閻晠妯€閺佺増宓?
 ///
 /// D3D12 鐟曚焦鐪扮敮鎼佸櫤缂傛挸鍟块崠?256 鐎涙濡€靛綊缍?
@@ -36,10 +49,15 @@ struct UniformBufferObject {
     light_dir: [f32; 4],
     light_color: [f32; 4],
     camera_pos: [f32; 4],
+    ambient: [f32; 4],
+    /// 模型颜色色调，乘以最终颜色
+    tint: [f32; 4],
+    /// 法线矩阵（模型矩阵的逆转置），非均匀缩放时保持法线垂直于表面
+    normal_matrix: [[f32; 4]; 4],
 }
 
 impl UniformBufferObject {
-    fn new(model: &Matrix4, view: &Matrix4, projection: &Matrix4, light_dir:[f32;3], light_color:[f32;4], camera_pos:[f32;3]) -> Self {
+    fn new(model: &Matrix4, view: &Matrix4, projection: &Matrix4, light_dir:[f32;3], light_color:[f32;4], camera_pos:[f32;3], ambient:[f32;4]) -> Self {
         Self {
             model: *model.as_ref(),
             view: *view.as_ref(),
@@ -47,6 +65,9 @@ impl UniformBufferObject {
             light_dir: [light_dir[0],light_dir[1],light_dir[2],0.0],
             light_color,
             camera_pos: [camera_pos[0],camera_pos[1],camera_pos[2],0.0],
+            ambient,
+            tint: [1.0; 4],
+            normal_matrix: *normal_matrix(model).as_ref(),
         }
     }
 }
@@ -62,18 +83,34 @@ pub struct Renderer {
     #[allow(dead_code)]  // 娣囨繄鏆€娓氭稑鐨㈤弶銉ゅ▏閻?
     index_buffer: ID3D12Resource,
     index_buffer_view: D3D12_INDEX_BUFFER_VIEW,
-    index_count: u32,
+    // 各模型在合并索引缓冲中的范围
+    models: Vec<ModelMesh>,
+    // 加载失败、使用占位几何体的模型序号，重新加载时用于判断是否失败
+    fallback_models: Vec<usize>,
+    // 加载模型时是否使用磁盘缓存
+    mesh_cache: bool,
     viewport: D3D12_VIEWPORT,
     scissor_rect: RECT,
-    command_allocators: [ID3D12CommandAllocator; FRAME_COUNT],
+    // 固定的画面宽高比，视口按该比例居中（信箱模式）
+    target_aspect: Option<f32>,
+    // 每个交换链缓冲对应一个命令分配器
+    command_allocators: Vec<ID3D12CommandAllocator>,
     command_list: ID3D12GraphicsCommandList,
 
     // 濞ｅ崬瀹?濡剝婢樼紓鎾冲暱
     depth_stencil_heap: ID3D12DescriptorHeap,
     depth_stencil_buffer: ID3D12Resource,
+    // 深度缓冲格式，启用模板遮罩时带模板分量
+    depth_format: DXGI_FORMAT,
+    // 模板遮罩的根签名和 PSO，只在启用模板遮罩时创建
+    stencil_mask_pipeline: Option<(ID3D12RootSignature, ID3D12PipelineState)>,
 
     // 娴ｈ法鏁ら弬鎵畱鐢嗙カ濠ф劗顓搁悶鍡欓兇缂佺噦绱欓弴澶稿敩fence_values閿?
     frame_resource_pool: FrameResourcePool,
+    // 同时在 GPU 上执行的最大帧数，小于缓冲数时每帧提交后等待更早的帧完成（1 为低延迟模式）
+    frames_in_flight: u32,
+    // 关闭低延迟模式时的帧数（配置值，未配置或为 1 时等于缓冲数）
+    pipelined_frames: u32,
     // 娴ｈ法鏁ら弬鎵畱Fence缁狅紕鎮婇崳?
     fence_manager: FenceManager,
     // 閹诲繗鍫粭锔绢吀閻炲棗娅?
@@ -81,12 +118,33 @@ pub struct Renderer {
     // 鐢悂鍣虹紓鎾冲暱閸栫尨绱橫VP 閻晠妯€閿?
     constant_buffer: ID3D12Resource,
     constant_buffer_data: *mut u8,
+    // 每个模型常量数据的间隔（256 字节对齐）
+    constant_buffer_stride: u64,
     // 閸︾儤娅欓柊宥囩枂
     scene: SceneConfig,
+    // 还需强制清除颜色的帧数（创建和尺寸变化后每个后台缓冲清除一次）
+    pending_color_clears: u32,
+    // 窗口尺寸变化后在下一帧绘制前重建交换链
+    resize_pending: bool,
+    /// 运行时间（秒），驱动清除颜色循环
+    total_time: f32,
     // 閻╁憡婧€缂佸嫪娆?
     camera: Camera,
     // 閺傜懓鎮滈崗澶岀矋娴?
     directional_light: DirectionalLight,
+    /// 模型自动旋转，叠加在 GUI 设置的旋转之上
+    auto_rotate: AutoRotate,
+    /// 内置 GUI（egui），在场景之后绘制到同一个后台缓冲
+    gui_manager: GuiManager,
+    gui_painter: GuiPainter,
+    /// 是否绘制内置 GUI 并处理其输入事件
+    gui_enabled: bool,
+    /// 呈现统计（跨帧累计）
+    present_stats: PresentStats,
+    /// 上一次查询到的交换链帧统计，用于计算两次查询之间的丢帧数
+    frame_statistics: Option<DXGI_FRAME_STATISTICS>,
+    /// PIX 抓帧中的资源调试名称（启用验证时）
+    debug_names: DebugNames,
 }
 
 impl Renderer {
@@ -146,6 +204,9 @@ impl Renderer {
                 .unwrap_or_else(|e| panic!("Failed to read vertex.hlsl at {}: {}", vs_path.display(), e));
             let ps_hlsl = fs::read_to_string(&ps_path)
                 .unwrap_or_else(|e| panic!("Failed to read fragment.hlsl at {}: {}", ps_path.display(), e));
+            // 展开共享的光照函数
+            let ps_hlsl = preprocess(&ps_hlsl, ShaderLanguage::Hlsl)
+                .unwrap_or_else(|e| panic!("Failed to preprocess fragment.hlsl: {}", e));
 
             let mut vs_blob = None;
             let mut ps_blob = None;
@@ -201,36 +262,13 @@ impl Renderer {
             let vs_blob = vs_blob.unwrap();
             let ps_blob = ps_blob.unwrap();
 
-            // 3. Input Layout (POSITION/NORMAL/COLOR)
-            let input_element_descs = [
-                D3D12_INPUT_ELEMENT_DESC {
-                    SemanticName: windows::core::s!("POSITION"),
-                    SemanticIndex: 0,
-                    Format: DXGI_FORMAT_R32G32B32_FLOAT,
-                    InputSlot: 0,
-                    AlignedByteOffset: 0,
-                    InputSlotClass: D3D12_INPUT_CLASSIFICATION_PER_VERTEX_DATA,
-                    InstanceDataStepRate: 0,
-                },
-                D3D12_INPUT_ELEMENT_DESC {
-                    SemanticName: windows::core::s!("NORMAL"),
-                    SemanticIndex: 0,
-                    Format: DXGI_FORMAT_R32G32B32_FLOAT,
-                    InputSlot: 0,
-                    AlignedByteOffset: 12,
-                    InputSlotClass: D3D12_INPUT_CLASSIFICATION_PER_VERTEX_DATA,
-                    InstanceDataStepRate: 0,
-                },
-                D3D12_INPUT_ELEMENT_DESC {
-                    SemanticName: windows::core::s!("COLOR"),
-                    SemanticIndex: 0,
-                    Format: DXGI_FORMAT_R32G32B32_FLOAT,
-                    InputSlot: 0,
-                    AlignedByteOffset: 24,
-                    InputSlotClass: D3D12_INPUT_CLASSIFICATION_PER_VERTEX_DATA,
-                    InstanceDataStepRate: 0,
-                },
-            ];
+            // 启用模板遮罩时使用带模板分量的深度格式，场景 PSO 只在模板值等于参考值处绘制
+            let stencil_enabled = scene.stencil_mask.is_some();
+            let depth_format = if stencil_enabled { DXGI_FORMAT_D24_UNORM_S8_UINT } else { DXGI_FORMAT_D32_FLOAT };
+            let restrict = StencilPass::Restrict.state();
+
+            // 3. Input Layout（由 MyVertex 的顶点布局生成）
+            let input_layout = Dx12InputLayout::new(0, &MyVertex::vertex_layout());
 
             // 4. PSO
             let mut pso_desc = D3D12_GRAPHICS_PIPELINE_STATE_DESC::default();
@@ -270,6 +308,9 @@ impl Renderer {
             pso_desc.RasterizerState = D3D12_RASTERIZER_DESC {
                 FillMode: D3D12_FILL_MODE_SOLID,
                 CullMode: D3D12_CULL_MODE_BACK,  // 閼冲矂娼伴崜鏃堟珟
+                DepthBias: config.graphics.depth_bias.constant,
+                DepthBiasClamp: config.graphics.depth_bias.clamp,
+                SlopeScaledDepthBias: config.graphics.depth_bias.slope_scale,
                 ..Default::default()
             };
             // 閸氼垳鏁ゅǎ鍗炲濞村鐦?
@@ -277,138 +318,49 @@ impl Renderer {
                 DepthEnable: true.into(),
                 DepthWriteMask: D3D12_DEPTH_WRITE_MASK_ALL,
                 DepthFunc: D3D12_COMPARISON_FUNC_LESS,  // 濞ｅ崬瀹抽崐鐓庣毈閻ㄥ嫰鈧俺绻冮敍鍫熸纯鏉╂垹娈戦悧鈺€缍嬮敍?
-                StencilEnable: false.into(),
+                StencilEnable: stencil_enabled.into(),
                 StencilReadMask: 0xFF,
-                StencilWriteMask: 0xFF,
-                FrontFace: D3D12_DEPTH_STENCILOP_DESC::default(),
-                BackFace: D3D12_DEPTH_STENCILOP_DESC::default(),
+                StencilWriteMask: restrict.write_mask as u8,
+                FrontFace: stencil_op_desc(restrict),
+                BackFace: stencil_op_desc(restrict),
             };
             pso_desc.SampleMask = 0xFFFFFFFF;
-            pso_desc.DSVFormat = DXGI_FORMAT_D32_FLOAT;  // 32娴ｅ秵璇為悙瑙勭箒鎼达附鐗稿?
-            pso_desc.InputLayout = D3D12_INPUT_LAYOUT_DESC {
-                pInputElementDescs: input_element_descs.as_ptr(),
-                NumElements: input_element_descs.len() as u32,
-            };
+            pso_desc.DSVFormat = depth_format;  // 32娴ｅ秵璇為悙瑙勭箒鎼达附鐗稿?
+            pso_desc.InputLayout = input_layout.desc();
             pso_desc.PrimitiveTopologyType = D3D12_PRIMITIVE_TOPOLOGY_TYPE_TRIANGLE;
             pso_desc.NumRenderTargets = 1;
-            pso_desc.RTVFormats[0] = DXGI_FORMAT_R8G8B8A8_UNORM;
+            pso_desc.RTVFormats[0] = gfx.rtv_format;
             pso_desc.SampleDesc.Count = 1;
 
             let pso: ID3D12PipelineState = gfx.device.CreateGraphicsPipelineState(&pso_desc).expect("Failed to create PSO");
-
-            // 5. MyVertex Buffer - 閸旂姾娴?OBJ 濡€崇€烽弬鍥︽
-            let obj_path = Path::new("assets/models/sphere.obj");
-            let (vertices, indices) = if obj_path.exists() {
-                info!("Loading mesh from: {}", obj_path.display());
-                match ObjLoader::load_from_file(obj_path) {
-                    Ok(mesh_data) => {
-                        info!(
-                            "Mesh loaded successfully: {} vertices, {} indices",
-                            mesh_data.vertex_count(),
-                            mesh_data.index_count()
-                        );
-                        // 鏉烆剚宕?GeometryVertex 娑?MyVertex
-                        let verts = mesh_data
-                            .vertices
-                            .iter()
-                            .map(|v| convert_geometry_vertex(v))
-                            .collect::<Vec<_>>();
-                        let inds = mesh_data.indices.clone();
-                        (verts, inds)
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to load OBJ file: {}, using default triangle", e);
-                        (create_default_triangle().to_vec(), vec![0, 1, 2])
-                    }
-                }
+            let debug_names = DebugNames::new(config.graphics.validation);
+            set_debug_name(&pso, debug_names.name("Scene Pipeline", "Solid"));
+
+            let stencil_mask_pipeline = if stencil_enabled {
+                let (root_signature, pso) =
+                    create_stencil_mask_pipeline(&gfx.device, &shader_dir, gfx.rtv_format, depth_format)?;
+                set_debug_name(&pso, debug_names.name("Pipeline", "Stencil Mask"));
+                Some((root_signature, pso))
             } else {
-                tracing::warn!("OBJ file not found: {}, using default triangle", obj_path.display());
-                (create_default_triangle().to_vec(), vec![0, 1, 2])
-            };
-            let vertex_data_size = (std::mem::size_of::<MyVertex>() * vertices.len()) as u64;
-
-            let heap_props = D3D12_HEAP_PROPERTIES {
-                Type: D3D12_HEAP_TYPE_UPLOAD,
-                ..Default::default()
-            };
-            let resource_desc = D3D12_RESOURCE_DESC {
-                Dimension: D3D12_RESOURCE_DIMENSION_BUFFER,
-                Width: vertex_data_size,
-                Height: 1,
-                DepthOrArraySize: 1,
-                MipLevels: 1,
-                SampleDesc: DXGI_SAMPLE_DESC { Count: 1, Quality: 0 },
-                Layout: D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
-                ..Default::default()
-            };
-
-            let mut vertex_buffer: Option<ID3D12Resource> = None;
-            gfx.device.CreateCommittedResource(
-                &heap_props,
-                D3D12_HEAP_FLAG_NONE,
-                &resource_desc,
-                D3D12_RESOURCE_STATE_GENERIC_READ,
-                None,
-                &mut vertex_buffer,
-            ).expect("Failed to create VB");
-            let vertex_buffer = vertex_buffer.unwrap();
-
-            // Copy data
-            let mut data = std::ptr::null_mut();
-            vertex_buffer.Map(0, None, Some(&mut data)).unwrap();
-            std::ptr::copy_nonoverlapping(vertices.as_ptr(), data as *mut MyVertex, vertices.len());
-            vertex_buffer.Unmap(0, None);
-
-            let vertex_buffer_view = D3D12_VERTEX_BUFFER_VIEW {
-                BufferLocation: vertex_buffer.GetGPUVirtualAddress(),
-                SizeInBytes: vertex_data_size as u32,
-                StrideInBytes: std::mem::size_of::<MyVertex>() as u32,
-            };
-
-            let vertex_count = vertices.len() as u32;
-
-            // 5.5. 閸掓稑缂撶槐銏犵穿缂傛挸鍟块崠鐚寸礄Index Buffer閿?
-            let index_data_size = (std::mem::size_of::<u32>() * indices.len()) as u64;
-            let index_count = indices.len() as u32;
-
-            let ib_resource_desc = D3D12_RESOURCE_DESC {
-                Dimension: D3D12_RESOURCE_DIMENSION_BUFFER,
-                Width: index_data_size,
-                Height: 1,
-                DepthOrArraySize: 1,
-                MipLevels: 1,
-                SampleDesc: DXGI_SAMPLE_DESC { Count: 1, Quality: 0 },
-                Layout: D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
-                ..Default::default()
+                None
             };
 
-            let mut index_buffer: Option<ID3D12Resource> = None;
-            gfx.device.CreateCommittedResource(
-                &heap_props,
-                D3D12_HEAP_FLAG_NONE,
-                &ib_resource_desc,
-                D3D12_RESOURCE_STATE_GENERIC_READ,
-                None,
-                &mut index_buffer,
-            ).expect("Failed to create IB");
-            let index_buffer = index_buffer.unwrap();
-
-            // Copy index data
-            let mut ib_data = std::ptr::null_mut();
-            index_buffer.Map(0, None, Some(&mut ib_data)).unwrap();
-            std::ptr::copy_nonoverlapping(indices.as_ptr(), ib_data as *mut u32, indices.len());
-            index_buffer.Unmap(0, None);
-
-            let index_buffer_view = D3D12_INDEX_BUFFER_VIEW {
-                BufferLocation: index_buffer.GetGPUVirtualAddress(),
-                SizeInBytes: index_data_size as u32,
-                Format: DXGI_FORMAT_R32_UINT,
-            };
-
-            info!("Index buffer created: {} indices", index_count);
+            // 5. MyVertex Buffer - 閸旂姾娴?OBJ 濡€崇€烽弬鍥︽
+            // PSO 只使用三角形列表，三角形带在加载时转换
+            let scene_mesh = SceneMesh::load(&scene.models, scene.fallback_primitive, false, config.graphics.mesh_cache);
+            let scene_bounds = scene.camera.fit_clip_planes.then(|| scene_mesh.world_bounds(&scene.models)).flatten();
+            let MeshBuffers {
+                vertex_buffer,
+                vertex_buffer_view,
+                vertex_count,
+                index_buffer,
+                index_buffer_view,
+            } = create_mesh_buffers(&gfx.device, &scene_mesh, debug_names, scene)?;
 
             // 5.6. 閸掓稑缂撶敮鎼佸櫤缂傛挸鍟块崠鐚寸礄Constant Buffer for MVP matrices閿?
-            let constant_buffer_size = std::mem::size_of::<UniformBufferObject>() as u64;
+            // 每个模型占用一段按 256 字节对齐的常量缓冲
+            let constant_buffer_stride = (std::mem::size_of::<UniformBufferObject>() as u64 + 255) & !255;
+            let constant_buffer_size = constant_buffer_stride * scene_mesh.models.len().max(1) as u64;
 
             let cb_heap_props = D3D12_HEAP_PROPERTIES {
                 Type: D3D12_HEAP_TYPE_UPLOAD,
@@ -435,6 +387,7 @@ impl Renderer {
                 &mut constant_buffer,
             ).expect("Failed to create constant buffer");
             let constant_buffer = constant_buffer.unwrap();
+            set_debug_name(&constant_buffer, debug_names.scene("Constant Buffer", scene));
 
             // Map 鐢悂鍣虹紓鎾冲暱閸栬桨浜掗懢宄板絿 CPU 閹稿洭鎷?
             let mut constant_buffer_data = std::ptr::null_mut();
@@ -443,32 +396,22 @@ impl Renderer {
             info!("Constant buffer created and mapped (size: {} bytes)", constant_buffer_size);
 
             // 6. Viewport/Scissor
-             let viewport = D3D12_VIEWPORT {
-                TopLeftX: 0.0,
-                TopLeftY: 0.0,
-                Width: gfx.width as f32,
-                Height: gfx.height as f32,
-                MinDepth: 0.0,
-                MaxDepth: 1.0,
-            };
+            let (viewport, scissor_rect) =
+                viewport_and_scissor(ViewportRect::letterbox(gfx.width, gfx.height, config.graphics.target_aspect));
 
-            let scissor_rect = RECT {
-                left: 0,
-                top: 0,
-                right: gfx.width as i32,
-                bottom: gfx.height as i32,
-            };
+            // 帧资源数量与交换链缓冲数保持一致
+            let frame_count = gfx.buffer_count as usize;
 
             // 7. 閸掓稑缂撻崨鎴掓姢鐎电钖勯敍鍫濆蓟缂傛挸鍟块敍?
             #[cfg(debug_assertions)]
-            debug!(frame_count = FRAME_COUNT, "Creating command allocators for frame buffering");
+            debug!(frame_count, "Creating command allocators for frame buffering");
 
-            let command_allocators: [ID3D12CommandAllocator; FRAME_COUNT] = [
-                gfx.device.CreateCommandAllocator(D3D12_COMMAND_LIST_TYPE_DIRECT)
-                    .expect("Failed to create CommandAllocator 0"),
-                gfx.device.CreateCommandAllocator(D3D12_COMMAND_LIST_TYPE_DIRECT)
-                    .expect("Failed to create CommandAllocator 1"),
-            ];
+            let command_allocators: Vec<ID3D12CommandAllocator> = (0..frame_count)
+                .map(|i| {
+                    gfx.device.CreateCommandAllocator(D3D12_COMMAND_LIST_TYPE_DIRECT)
+                        .unwrap_or_else(|_| panic!("Failed to create CommandAllocator {}", i))
+                })
+                .collect();
 
             let command_list: ID3D12GraphicsCommandList =
                 gfx.device.CreateCommandList(
@@ -482,7 +425,7 @@ impl Renderer {
             command_list.Close().expect("Failed to close initial CommandList");
 
             // 閸掓繂顫愰崠鏍ф姎鐠у嫭绨Ч鐙呯礄閸欏瞼绱﹂崘璇х礉娑撳锭RAME_COUNT閸栧綊鍘ら敍?
-            let frame_resource_pool = FrameResourcePool::double_buffering();
+            let frame_resource_pool = FrameResourcePool::new(frame_count);
 
             // 閸掓繂顫愰崠鏈廵nce缁狅紕鎮婇崳?
             let fence_manager = FenceManager::new();
@@ -491,7 +434,7 @@ impl Renderer {
             let mut descriptor_manager = Dx12DescriptorManager::new();
 
             // 閸掓繂顫愰崠?RTV 閸棴绱欐禍銈嗗床闁惧墽绱﹂崘鍙夋殶闁插骏绱?
-            descriptor_manager.init_rtv_heap(&gfx.device, FRAME_COUNT as u32)?;
+            descriptor_manager.init_rtv_heap(&gfx.device, frame_count as u32)?;
 
             // 閸掓繂顫愰崠?DSV 閸棴绱欓懛鍐茬毌1娑擃亝绻佹惔锔剧处閸愯绱?
             descriptor_manager.init_dsv_heap(&gfx.device, 1)?;
@@ -521,7 +464,7 @@ impl Renderer {
                 Height: gfx.height,
                 DepthOrArraySize: 1,
                 MipLevels: 1,
-                Format: DXGI_FORMAT_D32_FLOAT,
+                Format: depth_format,
                 SampleDesc: DXGI_SAMPLE_DESC { Count: 1, Quality: 0 },
                 Layout: D3D12_TEXTURE_LAYOUT_UNKNOWN,
                 Flags: D3D12_RESOURCE_FLAG_ALLOW_DEPTH_STENCIL,
@@ -529,7 +472,7 @@ impl Renderer {
             };
 
             let clear_value = D3D12_CLEAR_VALUE {
-                Format: DXGI_FORMAT_D32_FLOAT,
+                Format: depth_format,
                 Anonymous: D3D12_CLEAR_VALUE_0 {
                     DepthStencil: D3D12_DEPTH_STENCIL_VALUE {
                         Depth: 1.0,
@@ -558,9 +501,9 @@ impl Renderer {
 
             #[cfg(debug_assertions)]
             {
-                info!("DX12 Renderer initialized successfully with double buffering");
+                info!(frame_count, "DX12 Renderer initialized successfully");
                 debug!("Descriptor heaps initialized: RTV={}, DSV={}, SRV/CBV/UAV={}",
-                    FRAME_COUNT, 1, 128);
+                    frame_count, 1, 128);
                 info!("Depth stencil buffer created: {}x{}", gfx.width, gfx.height);
             }
 
@@ -572,8 +515,9 @@ impl Renderer {
                 scene.camera.transform.position[2],
             ));
             let aspect_ratio = viewport.Width / viewport.Height;
-            camera.set_lens(
+            camera.set_perspective(
                 scene.camera.fov * PI / 180.0,
+                scene.camera.fov_axis,
                 aspect_ratio,
                 scene.camera.near_clip,
                 scene.camera.far_clip,
@@ -592,6 +536,12 @@ impl Renderer {
 
             info!("Camera component initialized at position {:?}", camera.position());
 
+            // 按场景包围盒收紧近远裁剪面
+            if let Some(bounds) = &scene_bounds {
+                camera.fit_clip_planes(bounds);
+                info!("Camera clip planes fitted to scene bounds: near={}, far={}", camera.near_z(), camera.far_z());
+            }
+
             // 閸掓繂顫愰崠鏍ㄦ煙閸氭垵鍘滅紒鍕
             let directional_light = scene.light.to_directional_light("MainLight");
             info!(
@@ -601,6 +551,12 @@ impl Renderer {
                 directional_light.direction
             );
 
+            let gui_painter = GuiPainter::new(&gfx.device, &shader_dir, gfx.rtv_format, frame_count)?;
+            let mut gui_state = GuiState::new(config, scene);
+            gui_state.camera_near = camera.near_z();
+            gui_state.camera_far = camera.far_z();
+            let gui_manager = GuiManager::without_renderer(gfx.window(), gui_state);
+
             Ok(Self {
                 gfx,
                 root_signature,
@@ -610,21 +566,43 @@ impl Renderer {
                 vertex_count,
                 index_buffer,
                 index_buffer_view,
-                index_count,
+                models: scene_mesh.models,
+                fallback_models: scene_mesh.fallback_models,
+                mesh_cache: config.graphics.mesh_cache,
                 viewport,
                 scissor_rect,
+                target_aspect: config.graphics.target_aspect,
                 command_allocators,
                 command_list,
                 depth_stencil_heap,
                 depth_stencil_buffer,
+                depth_format,
+                stencil_mask_pipeline,
                 frame_resource_pool,
+                frames_in_flight: effective_frames_in_flight(config.graphics.frames_in_flight, frame_count as u32),
+                pipelined_frames: effective_frames_in_flight(
+                    config.graphics.frames_in_flight.filter(|&n| n > 1),
+                    frame_count as u32,
+                ),
                 fence_manager,
                 descriptor_manager,
                 constant_buffer,
                 constant_buffer_data: constant_buffer_data as *mut u8,
+                constant_buffer_stride,
                 scene: scene.clone(),
+                pending_color_clears: frame_count as u32,
+                resize_pending: false,
+                total_time: 0.0,
                 camera,
                 directional_light,
+                auto_rotate: AutoRotate::default(),
+                gui_manager,
+                gui_painter,
+                gui_enabled: true,
+                // 以同步间隔 1 呈现，等价于 FIFO
+                present_stats: PresentStats { present_mode: PresentMode::Fifo, ..Default::default() },
+                frame_statistics: None,
+                debug_names,
             })
         }
     }
@@ -641,7 +619,7 @@ impl Renderer {
             // Signal娑撯偓娑擃亝鏌婇惃鍒nce閸?
             let flush_fence = self.fence_manager.next_value();
             self.gfx.command_queue.Signal(&self.gfx.fence, flush_fence.value())
-                .expect("Failed to signal fence");
+                .map_err(|e| self.gfx.device_error("Failed to signal fence", e))?;
 
             // 缁涘绶熺拠顧琫nce閸婄厧鐣幋?
             if self.gfx.fence.GetCompletedValue() < flush_fence.value() {
@@ -663,49 +641,66 @@ impl Renderer {
         }
     }
 
+    /// 标记交换链需要重建，与 Vulkan 后端一样推迟到下一帧绘制前执行
     pub fn resize(&mut self) {
+        #[cfg(debug_assertions)]
+        debug!("Swapchain resize requested");
+
+        self.resize_pending = true;
+    }
+
+    /// 按当前窗口尺寸重建交换链缓冲和深度缓冲
+    ///
+    /// 窗口最小化（尺寸为 0）时保留请求，恢复后再重建；尺寸没有变化时不做任何事，
+    /// 避免无谓地等待 GPU 空闲和重建深度缓冲。
+    fn recreate_swap_chain(&mut self) {
+        let size = self.gfx.window.inner_size();
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+        self.resize_pending = false;
+        if size.width == self.gfx.width && size.height == self.gfx.height {
+            return;
+        }
+
         unsafe {
             #[cfg(debug_assertions)]
             debug!("Resizing swapchain...");
 
-            // 缁涘绶?GPU 鐎瑰本鍨氶幍鈧張澶婁紣娴?
-            let fence_value = self.gfx.fence_value;
-            self.gfx.command_queue.Signal(&self.gfx.fence, fence_value)
-                .expect("Failed to signal fence for resize");
-            self.gfx.fence_value += 1;
-
-            if self.gfx.fence.GetCompletedValue() < fence_value {
-                self.gfx.fence.SetEventOnCompletion(fence_value, self.gfx.fence_event)
-                    .expect("Failed to set fence event for resize");
-                WaitForSingleObject(self.gfx.fence_event, windows::Win32::System::Threading::INFINITE);
+            // 等待 GPU 完成所有工作后才能重建交换链缓冲
+            if let Err(e) = self.gfx.wait_idle() {
+                error!("Failed to wait for GPU before resize: {}", e);
+                return;
             }
 
             #[cfg(debug_assertions)]
             debug!("GPU idle, resizing swap chain buffers...");
 
-            // 閼惧嘲褰囬弬鎵畱缁愭褰涙径褍鐨?
-            let size = self.gfx.window.inner_size();
             self.gfx.width = size.width;
             self.gfx.height = size.height;
 
             // 鐠嬪啯鏆ｆ禍銈嗗床闁炬儳銇囩亸蹇ョ礄娴兼俺鍤滈崝銊╁櫞閺€鐐＋閻ㄥ嫮绱﹂崘鎻掑隘閿?
             self.gfx.swap_chain.ResizeBuffers(
-                FRAME_COUNT as u32,
+                self.gfx.buffer_count,
                 size.width,
                 size.height,
-                DXGI_FORMAT_R8G8B8A8_UNORM,
+                swap_chain_buffer_format(self.gfx.rtv_format),
                 DXGI_SWAP_CHAIN_FLAG(0),
             ).expect("Failed to resize swap chain buffers");
 
             // 闁插秵鏌婇崚娑樼紦 RTV
             let rtv_handle = self.gfx.rtv_heap.GetCPUDescriptorHandleForHeapStart();
-            for i in 0..FRAME_COUNT {
+            for i in 0..self.gfx.buffer_count as usize {
                 let surface: ID3D12Resource = self.gfx.swap_chain.GetBuffer(i as u32)
                     .expect("Failed to get swap chain buffer");
                 let handle = D3D12_CPU_DESCRIPTOR_HANDLE {
                     ptr: rtv_handle.ptr + (i * self.gfx.rtv_descriptor_size),
                 };
-                self.gfx.device.CreateRenderTargetView(&surface, None, handle);
+                self.gfx.device.CreateRenderTargetView(
+                    &surface,
+                    Some(&render_target_view_desc(self.gfx.rtv_format)),
+                    handle,
+                );
             }
 
             // 闁插秵鏌婇崚娑樼紦濞ｅ崬瀹冲Ο鈩冩緲缂傛挸鍟?
@@ -719,7 +714,7 @@ impl Renderer {
                 Height: size.height,
                 DepthOrArraySize: 1,
                 MipLevels: 1,
-                Format: DXGI_FORMAT_D32_FLOAT,
+                Format: self.depth_format,
                 SampleDesc: DXGI_SAMPLE_DESC { Count: 1, Quality: 0 },
                 Layout: D3D12_TEXTURE_LAYOUT_UNKNOWN,
                 Flags: D3D12_RESOURCE_FLAG_ALLOW_DEPTH_STENCIL,
@@ -727,7 +722,7 @@ impl Renderer {
             };
 
             let clear_value = D3D12_CLEAR_VALUE {
-                Format: DXGI_FORMAT_D32_FLOAT,
+                Format: self.depth_format,
                 Anonymous: D3D12_CLEAR_VALUE_0 {
                     DepthStencil: D3D12_DEPTH_STENCIL_VALUE {
                         Depth: 1.0,
@@ -755,17 +750,17 @@ impl Renderer {
             );
 
             // 閺囧瓨鏌?viewport 閸?scissor rect
-            self.viewport.Width = size.width as f32;
-            self.viewport.Height = size.height as f32;
-            self.scissor_rect.right = size.width as i32;
-            self.scissor_rect.bottom = size.height as i32;
+            (self.viewport, self.scissor_rect) =
+                viewport_and_scissor(ViewportRect::letterbox(size.width, size.height, self.target_aspect));
 
             // 闁插秶鐤?frame index
             self.gfx.frame_index = self.gfx.swap_chain.GetCurrentBackBufferIndex() as usize;
 
             // 濞撳懘娅?fence 閸婄》绱欓崶鐘辫礋閹存垳婊戠粵澶婄窡娴滃棙澧嶉張澶婃姎鐎瑰本鍨氶敍?
             // 闁插秶鐤嗙敮褑绁┃鎰潨
-            self.frame_resource_pool = FrameResourcePool::double_buffering();
+            self.frame_resource_pool = FrameResourcePool::new(self.gfx.buffer_count as usize);
+            self.gui_painter.reset_frames(self.gfx.buffer_count as usize);
+            self.pending_color_clears = self.gfx.buffer_count;
             self.fence_manager.reset();
 
             #[cfg(debug_assertions)]
@@ -773,7 +768,14 @@ impl Renderer {
         }
     }
 
-    pub fn draw(&mut self) -> Result<()> {
+    pub fn draw(&mut self) -> Result<FrameStats> {
+        if self.resize_pending {
+            self.recreate_swap_chain();
+        }
+
+        let start = Instant::now();
+        let mut stats = FrameStats::default();
+
         unsafe {
             let frame_index = self.gfx.frame_index;
 
@@ -794,9 +796,11 @@ impl Renderer {
 
                 // 缁涘绶熺拠銉ユ姎鐠у嫭绨€瑰本鍨?
                 if self.gfx.fence.GetCompletedValue() < fence_value {
+                    let wait_start = Instant::now();
                     self.gfx.fence.SetEventOnCompletion(fence_value, self.gfx.fence_event)
                         .expect("Failed to set fence event");
                     WaitForSingleObject(self.gfx.fence_event, windows::Win32::System::Threading::INFINITE);
+                    stats.record_wait(wait_start);
 
                     #[cfg(debug_assertions)]
                     debug!(frame_index, "GPU wait completed");
@@ -813,12 +817,22 @@ impl Renderer {
             self.command_list.Reset(allocator, Some(&self.pso))
                 .expect("Failed to reset CommandList");
 
+            // 该帧资源已空闲，GUI 上一次在此槽位使用的上传缓冲可以释放；纹理上传需在绘制之前录制
+            self.gui_painter.begin_frame(frame_index);
+            let gui_frame = if self.gui_enabled {
+                self.gui_manager.update(self.gfx.window());
+                let frame = self.gui_manager.end_frame(self.gfx.window());
+                self.gui_painter.update_textures(&self.gfx.device, &self.command_list, &frame)?;
+                Some(frame)
+            } else {
+                None
+            };
+
             // 閺囧瓨鏌婇惄鍛婃簚閻ㄥ嫬顔旀妯荤槷閿涘牆顩ч弸婊呯崶閸欙絽銇囩亸蹇旀暭閸欐﹫绱?
             let aspect_ratio = self.viewport.Width / self.viewport.Height;
             self.camera.set_aspect(aspect_ratio);
 
             // 鐠侊紕鐣?MVP 閻晠妯€閿涘牅濞囬悽?Camera 缂佸嫪娆㈤敍?
-            let model = self.scene.model.transform.to_matrix();
             let view = self.camera.view_matrix();
             let mut projection = self.camera.proj_matrix();
             projection[(1, 1)] *= -1.0;
@@ -828,21 +842,31 @@ impl Renderer {
             let light_color_intensity = self.directional_light.color.with_intensity(self.directional_light.intensity);
 
             let camera_pos = self.camera.position();
-            let ubo = UniformBufferObject::new(
-                &model,
-                &view,
-                &projection,
-                [light_direction.x, light_direction.y, light_direction.z],
-                [light_color_intensity[0], light_color_intensity[1], light_color_intensity[2], self.directional_light.intensity],
-                [camera_pos.x, camera_pos.y, camera_pos.z],
-            );
+            let manual_srgb = !is_srgb_format(self.gfx.rtv_format);
+
+            // 按模型写入常量缓冲的对应位置
+            for (i, model_config) in self.scene.models.iter().take(self.models.len()).enumerate() {
+                let model = model_config.transform.to_matrix();
+                let mut ubo = UniformBufferObject {
+                    tint: model_config.tint,
+                    ..UniformBufferObject::new(
+                        &model,
+                        &view,
+                        &projection,
+                        [light_direction.x, light_direction.y, light_direction.z],
+                        [light_color_intensity[0], light_color_intensity[1], light_color_intensity[2], self.directional_light.intensity],
+                        [camera_pos.x, camera_pos.y, camera_pos.z],
+                        self.scene.ambient_light(),
+                    )
+                };
+                ubo.camera_pos[3] = manual_srgb as u32 as f32;
 
-            // 閺囧瓨鏌婄敮鎼佸櫤缂傛挸鍟块崠鐑樻殶閹?
-            std::ptr::copy_nonoverlapping(
-                &ubo as *const UniformBufferObject as *const u8,
-                self.constant_buffer_data,
-                std::mem::size_of::<UniformBufferObject>()
-            );
+                std::ptr::copy_nonoverlapping(
+                    &ubo as *const UniformBufferObject as *const u8,
+                    self.constant_buffer_data.add(i * self.constant_buffer_stride as usize),
+                    std::mem::size_of::<UniformBufferObject>()
+                );
+            }
 
             // Get render target resource
             let render_target: ID3D12Resource = self.gfx.swap_chain.GetBuffer(self.gfx.frame_index as u32)
@@ -872,10 +896,29 @@ impl Renderer {
             self.command_list.OMSetRenderTargets(1, Some(&rtv_handle), false, Some(&dsv_handle));
 
             // 濞撳懐鈹栧〒鍙夌厠閻╊喗鐖ｉ崪灞剧箒鎼达妇绱﹂崘?
-            self.command_list.ClearRenderTargetView(rtv_handle, &self.scene.clear_color, None);
+            // 关闭每帧清除时跳过颜色清除，保留后台缓冲原有内容
+            // 信箱模式下先把整个后台缓冲清除为黑色，再只把视口区域清除为场景的清除颜色
+            if self.scene.clear_color_enabled || self.pending_color_clears > 0 {
+                let letterboxed = !ViewportRect::letterbox(self.gfx.width, self.gfx.height, self.target_aspect)
+                    .covers(self.gfx.width, self.gfx.height);
+                let clear_color = encode_output_color(self.scene.clear_color_at(self.total_time), manual_srgb);
+                if letterboxed {
+                    let letterbox_color = encode_output_color(LETTERBOX_COLOR, manual_srgb);
+                    self.command_list.ClearRenderTargetView(rtv_handle, &letterbox_color, None);
+                    self.command_list.ClearRenderTargetView(rtv_handle, &clear_color, Some(&[self.scissor_rect]));
+                } else {
+                    self.command_list.ClearRenderTargetView(rtv_handle, &clear_color, None);
+                }
+            }
+            self.pending_color_clears = self.pending_color_clears.saturating_sub(1);
+            let clear_flags = if self.stencil_mask_pipeline.is_some() {
+                D3D12_CLEAR_FLAG_DEPTH | D3D12_CLEAR_FLAG_STENCIL
+            } else {
+                D3D12_CLEAR_FLAG_DEPTH
+            };
             self.command_list.ClearDepthStencilView(
                 dsv_handle,
-                D3D12_CLEAR_FLAG_DEPTH,
+                clear_flags,
                 1.0,  // 濞ｅ崬瀹冲〒鍛敄娑?.0閿涘牊娓舵潻婊愮礆
                 0,
                 None,
@@ -888,16 +931,47 @@ impl Renderer {
             self.command_list.RSSetScissorRects(&[self.scissor_rect]);
 
             // 鐠佸墽鐤嗙敮鎼佸櫤缂傛挸鍟块崠鐚寸礄Root Parameter 0閿?
-            self.command_list.SetGraphicsRootConstantBufferView(
-                0,  // Root parameter index
-                self.constant_buffer.GetGPUVirtualAddress()
-            );
-
-            self.command_list.OMSetRenderTargets(1, Some(&rtv_handle), false, None);
+            // 模板遮罩需要绑定深度模板缓冲
+            if self.stencil_mask_pipeline.is_some() {
+                self.command_list.OMSetRenderTargets(1, Some(&rtv_handle), false, Some(&dsv_handle));
+            } else {
+                self.command_list.OMSetRenderTargets(1, Some(&rtv_handle), false, None);
+            }
             self.command_list.IASetPrimitiveTopology(D3D_PRIMITIVE_TOPOLOGY_TRIANGLELIST);
+
+            // 第一遍把遮罩矩形写入模板缓冲，之后的场景 PSO 只在矩形内绘制
+            if let (Some((root_signature, pso)), Some(mask)) = (&self.stencil_mask_pipeline, self.scene.stencil_mask) {
+                let bounds = mask.ndc_bounds(false);
+                self.command_list.SetGraphicsRootSignature(root_signature);
+                self.command_list.SetPipelineState(pso);
+                self.command_list.SetGraphicsRoot32BitConstants(0, 4, bounds.as_ptr() as *const _, 0);
+                self.command_list.DrawInstanced(6, 1, 0, 0);
+                self.command_list.OMSetStencilRef(STENCIL_REFERENCE);
+
+                self.command_list.SetGraphicsRootSignature(&self.root_signature);
+                self.command_list.SetPipelineState(&self.pso);
+            }
             self.command_list.IASetVertexBuffers(0, Some(&[self.vertex_buffer_view]));
             self.command_list.IASetIndexBuffer(Some(&self.index_buffer_view));
-            self.command_list.DrawIndexedInstanced(self.index_count, 1, 0, 0, 0);
+
+            // 每个模型绑定各自的常量缓冲区段（Root Parameter 0）后按距离选择的 LOD 绘制
+            let constant_buffer_address = self.constant_buffer.GetGPUVirtualAddress();
+            let lod_levels = select_lods(&self.models, &self.scene.models, &self.camera.position());
+            for (i, model) in self.models.iter().enumerate() {
+                self.command_list.SetGraphicsRootConstantBufferView(
+                    0,
+                    constant_buffer_address + i as u64 * self.constant_buffer_stride,
+                );
+                let indices = model.lod_range(lod_levels.get(i).copied().unwrap_or(0));
+                self.command_list.DrawIndexedInstanced(indices.len() as u32, 1, indices.start, 0, 0);
+                stats.record_draw(indices.len() as u32);
+            }
+
+            // GUI 不做深度测试，只绑定渲染目标
+            if let Some(frame) = &gui_frame {
+                self.command_list.OMSetRenderTargets(1, Some(&rtv_handle), false, None);
+                self.gui_painter.draw(&self.gfx.device, &self.command_list, frame, [self.gfx.width, self.gfx.height])?;
+            }
 
             // Transition Barrier RenderTarget -> Present
             let barrier_back = D3D12_RESOURCE_BARRIER {
@@ -928,8 +1002,12 @@ impl Renderer {
             self.gfx.command_queue.ExecuteCommandLists(&command_lists);
 
             // Present
-            self.gfx.swap_chain.Present(1, DXGI_PRESENT(0)).ok()
-                .expect("Failed to present");
+            // 先输出调试层消息，失败时日志中有具体原因
+            // 设备丢失（驱动更新、TDR）时返回可恢复的错误而不是崩溃
+            let present = self.gfx.swap_chain.Present(1, DXGI_PRESENT(0)).ok();
+            self.gfx.flush_debug_messages();
+            present.map_err(|e| self.gfx.device_error("Failed to present", e))?;
+            self.update_present_stats();
 
             #[cfg(debug_assertions)]
             trace!(frame_index, "Presented");
@@ -937,7 +1015,7 @@ impl Renderer {
             // 娴ｈ法鏁ら弬鎵畱 Fence 缁狅紕鎮婇崳銊﹀絹娴溿倓淇婇崣?
             let fence_value = self.fence_manager.next_value();
             self.gfx.command_queue.Signal(&self.gfx.fence, fence_value.value())
-                .expect("Failed to signal fence");
+                .map_err(|e| self.gfx.device_error("Failed to signal fence", e))?;
 
             #[cfg(debug_assertions)]
             trace!(frame_index, fence_value = fence_value.value(), "Fence signaled");
@@ -947,6 +1025,22 @@ impl Renderer {
                 frame_resource.mark_in_use(fence_value.value());
             }
 
+            // 帧资源池按后台缓冲分配，限制在途帧数需要额外等待：
+            // 等到只剩 frames_in_flight - 1 帧未完成（低延迟模式下即等待刚提交的这一帧）
+            if self.frames_in_flight < self.gfx.buffer_count {
+                let wait_value = fence_value.value().saturating_sub(self.frames_in_flight as u64 - 1);
+                if self.gfx.fence.GetCompletedValue() < wait_value {
+                    let wait_start = Instant::now();
+                    self.gfx.fence.SetEventOnCompletion(wait_value, self.gfx.fence_event)
+                        .map_err(|e| self.gfx.device_error("Failed to set fence event", e))?;
+                    WaitForSingleObject(self.gfx.fence_event, windows::Win32::System::Threading::INFINITE);
+                    stats.record_wait(wait_start);
+                }
+                let completed = self.gfx.fence.GetCompletedValue();
+                self.fence_manager.update_completed_value(FenceValue::new(completed));
+                self.frame_resource_pool.update_availability(completed);
+            }
+
             // 娣囨繃瀵旀稉?gfx.fence_value 閻ㄥ嫬鎮撳銉礄娑撹桨绨￠崗鐓庮啇閹嶇礆
             self.gfx.fence_value = fence_value.value() + 1;
 
@@ -956,22 +1050,71 @@ impl Renderer {
             #[cfg(debug_assertions)]
             trace!(frame_index, next_frame = self.gfx.frame_index, "Frame completed");
 
-            Ok(())
+            stats.finish(start);
+
+            if let Some(frame) = &gui_frame {
+                self.gui_painter.free_textures(frame);
+                self.gui_manager.state_mut().update_frame_stats(&stats);
+                self.gui_manager.state_mut().present_stats = Some(self.present_stats);
+                let packet = self.gui_manager.state().to_packet();
+                self.apply_gui_packet(&packet);
+            }
+            Ok(stats)
+        }
+    }
+
+    /// 呈现之后更新呈现统计
+    ///
+    /// 交换链尚未产生帧统计（刚创建或全屏切换后）时查询会失败，此时只累计呈现次数。
+    unsafe fn update_present_stats(&mut self) {
+        self.present_stats.presented_frames += 1;
+        let mut current = DXGI_FRAME_STATISTICS::default();
+        if self.gfx.swap_chain.GetFrameStatistics(&mut current).is_err() {
+            return;
         }
+        if let Some(previous) = &self.frame_statistics {
+            self.present_stats.dropped_frames += dropped_frames_between(previous, &current);
+        }
+        self.frame_statistics = Some(current);
     }
 
     /// Update camera based on input system state
     ///
     /// Called every frame before draw() to apply user input to camera
     pub fn update(&mut self, input_system: &mut crate::core::input::InputSystem, delta_time: f32) {
+        self.auto_rotate.advance(delta_time);
+        self.gui_manager.state_mut().mouse_captured = Some(input_system.is_captured());
+        if input_system.update_light(&mut self.directional_light, delta_time) {
+            // 同步到 GUI 状态，否则每帧应用 GUI 参数时会被覆盖
+            let direction = self.directional_light.direction;
+            self.gui_manager.state_mut().light_direction = [direction.x, direction.y, direction.z];
+        }
         input_system.update_camera(&mut self.camera, delta_time);
     }
 
+    /// 仅更新相机（暂停时使用）
+    pub fn update_camera(&mut self, input_system: &mut crate::core::input::InputSystem, delta_time: f32) {
+        self.gui_manager.state_mut().mouse_captured = Some(input_system.is_captured());
+        input_system.update_camera(&mut self.camera, delta_time);
+    }
+
+    /// 处理 GUI 事件，返回 true 表示事件被 GUI 消费
+    pub fn handle_gui_event(&mut self, event: &winit::event::WindowEvent) -> bool {
+        self.gui_enabled && self.gui_manager.handle_event(self.gfx.window(), event)
+    }
+
     pub fn apply_gui_packet(&mut self, packet: &GuiStatePacket) {
         self.scene.clear_color = packet.clear_color;
-        self.scene.model.transform.position = packet.model_position;
-        self.scene.model.transform.rotation = packet.model_rotation;
-        self.scene.model.transform.scale = packet.model_scale;
+        self.scene.clear_color_enabled = packet.clear_color_enabled != 0;
+        self.scene.clear_color_cycle = packet.clear_color_cycle != 0;
+        self.scene.ambient_color = packet.ambient_color;
+        self.scene.ambient_intensity = packet.ambient_intensity;
+        self.scene.exposure = packet.exposure;
+        packet.apply_model_transforms(&mut self.scene.models);
+        self.auto_rotate.enabled = packet.auto_rotate != 0;
+        self.auto_rotate.speed = packet.auto_rotate_speed;
+        self.auto_rotate.apply(&mut self.scene.models);
+        packet.apply_model_tints(&mut self.scene.models);
 
         self.directional_light.intensity = packet.light_intensity;
         self.directional_light.direction = Vector3::new(
@@ -981,20 +1124,33 @@ impl Renderer {
         )
         .normalize();
 
-        if (self.camera.fov_x() - packet.camera_fov * PI / 180.0).abs() > 0.01 {
-            self.camera.set_lens(
-                packet.camera_fov * PI / 180.0,
-                self.camera.aspect(),
-                packet.camera_near,
-                packet.camera_far,
-            );
-        }
+        packet.apply_camera_lens(&mut self.camera);
+        self.frames_in_flight = packet.frames_in_flight(self.pipelined_frames);
     }
 
     /// Get a reference to the window for cursor control
     pub fn window(&self) -> &winit::window::Window {
         self.gfx.window()
     }
+
+    /// 从磁盘重新加载场景模型并替换顶点和索引缓冲，之前加载成功的模型失败时保留当前网格
+    ///
+    /// 旧缓冲可能仍被在途的帧使用，替换前等待 GPU 空闲。
+    pub fn reload_models(&mut self) -> Result<()> {
+        let scene_mesh = SceneMesh::load(&self.scene.models, self.scene.fallback_primitive, false, self.mesh_cache);
+        scene_mesh.check_reload(&self.fallback_models, &self.scene.models)?;
+
+        let buffers = unsafe { create_mesh_buffers(&self.gfx.device, &scene_mesh, self.debug_names, &self.scene)? };
+        self.gfx.wait_idle()?;
+        self.vertex_buffer = buffers.vertex_buffer;
+        self.vertex_buffer_view = buffers.vertex_buffer_view;
+        self.vertex_count = buffers.vertex_count;
+        self.index_buffer = buffers.index_buffer;
+        self.index_buffer_view = buffers.index_buffer_view;
+        self.models = scene_mesh.models;
+        self.fallback_models = scene_mesh.fallback_models;
+        Ok(())
+    }
 }
 
 /// 鐎圭偟骞囩紒鐔剁閻ㄥ嫭瑕嗛弻鎾虫倵缁旑垱甯撮崣?
@@ -1009,6 +1165,10 @@ impl crate::renderer::backend_trait::RenderBackend for Renderer {
     }
 
     fn draw(&mut self) -> crate::core::error::Result<()> {
+        self.draw().map(|_| ())
+    }
+
+    fn draw_with_stats(&mut self) -> crate::core::error::Result<FrameStats> {
         self.draw()
     }
 
@@ -1016,11 +1176,41 @@ impl crate::renderer::backend_trait::RenderBackend for Renderer {
         self.update(input_system, delta_time)
     }
 
+    fn tick(&mut self, event: &crate::core::event::TickEvent) {
+        self.total_time = event.total_time;
+    }
+
+    fn update_camera(&mut self, input_system: &mut crate::core::input::InputSystem, delta_time: f32) {
+        self.update_camera(input_system, delta_time)
+    }
+
+    fn gui_packet(&self) -> Option<GuiStatePacket> {
+        self.gui_enabled.then(|| self.gui_manager.state().to_packet())
+    }
+
     fn apply_gui_packet(&mut self, packet: &GuiStatePacket) {
         self.apply_gui_packet(packet)
     }
 
-    // handle_gui_event 娴ｈ法鏁ゆ妯款吇鐎圭偟骞囬敍鍫ｇ箲閸?false閿?
+    fn present_stats(&self) -> PresentStats {
+        self.present_stats
+    }
+
+    fn handle_gui_event(&mut self, event: &winit::event::WindowEvent) -> bool {
+        self.handle_gui_event(event)
+    }
+
+    fn set_gui_enabled(&mut self, enabled: bool) {
+        self.gui_enabled = enabled;
+    }
+
+    fn reload_models(&mut self) -> crate::core::error::Result<()> {
+        self.reload_models()
+    }
+
+    fn wait_idle(&self) -> crate::core::error::Result<()> {
+        self.gfx.wait_idle()
+    }
 }
 
 impl Drop for Renderer {
@@ -1032,3 +1222,264 @@ impl Drop for Renderer {
         }
     }
 }
+
+/// 两次帧统计之间显示器刷新时没有新帧可显示的次数
+fn dropped_frames_between(previous: &DXGI_FRAME_STATISTICS, current: &DXGI_FRAME_STATISTICS) -> u64 {
+    let refreshes = current.PresentRefreshCount.wrapping_sub(previous.PresentRefreshCount);
+    let presents = current.PresentCount.wrapping_sub(previous.PresentCount);
+    refreshes.saturating_sub(presents) as u64
+}
+
+/// 视口矩形对应的 D3D12 视口和裁剪矩形
+fn viewport_and_scissor(rect: ViewportRect) -> (D3D12_VIEWPORT, RECT) {
+    let viewport = D3D12_VIEWPORT {
+        TopLeftX: rect.x as f32,
+        TopLeftY: rect.y as f32,
+        Width: rect.width as f32,
+        Height: rect.height as f32,
+        MinDepth: 0.0,
+        MaxDepth: 1.0,
+    };
+    let scissor_rect = RECT {
+        left: rect.x as i32,
+        top: rect.y as i32,
+        right: (rect.x + rect.width) as i32,
+        bottom: (rect.y + rect.height) as i32,
+    };
+    (viewport, scissor_rect)
+}
+
+/// 把后端无关的模板状态转换为 D3D12 模板操作（写入掩码和参考值在 PSO 和命令列表上单独设置）
+fn stencil_op_desc(state: StencilState) -> D3D12_DEPTH_STENCILOP_DESC {
+    D3D12_DEPTH_STENCILOP_DESC {
+        StencilFailOp: D3D12_STENCIL_OP_KEEP,
+        StencilDepthFailOp: D3D12_STENCIL_OP_KEEP,
+        StencilPassOp: match state.pass_op {
+            StencilOp::Keep => D3D12_STENCIL_OP_KEEP,
+            StencilOp::Replace => D3D12_STENCIL_OP_REPLACE,
+        },
+        StencilFunc: match state.compare {
+            StencilCompare::Always => D3D12_COMPARISON_FUNC_ALWAYS,
+            StencilCompare::Equal => D3D12_COMPARISON_FUNC_EQUAL,
+        },
+    }
+}
+
+/// 场景网格的顶点和索引缓冲
+struct MeshBuffers {
+    vertex_buffer: ID3D12Resource,
+    vertex_buffer_view: D3D12_VERTEX_BUFFER_VIEW,
+    vertex_count: u32,
+    index_buffer: ID3D12Resource,
+    index_buffer_view: D3D12_INDEX_BUFFER_VIEW,
+}
+
+/// 在上传堆上为场景网格创建顶点和索引缓冲，顶点数不超过 65535 时使用 16 位索引
+unsafe fn create_mesh_buffers(
+    device: &ID3D12Device,
+    scene_mesh: &SceneMesh,
+    debug_names: DebugNames,
+    scene: &SceneConfig,
+) -> Result<MeshBuffers> {
+    let vertex_bytes: &[u8] = bytemuck::cast_slice(&scene_mesh.vertices);
+    let vertex_buffer = create_upload_buffer(device, vertex_bytes, "vertex")?;
+    let vertex_buffer_view = D3D12_VERTEX_BUFFER_VIEW {
+        BufferLocation: vertex_buffer.GetGPUVirtualAddress(),
+        SizeInBytes: vertex_bytes.len() as u32,
+        StrideInBytes: std::mem::size_of::<MyVertex>() as u32,
+    };
+
+    let index_data = scene_mesh.index_data();
+    let index_bytes = index_data.as_bytes();
+    let index_buffer = create_upload_buffer(device, index_bytes, "index")?;
+    let index_buffer_view = D3D12_INDEX_BUFFER_VIEW {
+        BufferLocation: index_buffer.GetGPUVirtualAddress(),
+        SizeInBytes: index_bytes.len() as u32,
+        Format: match index_data.width() {
+            IndexWidth::U16 => DXGI_FORMAT_R16_UINT,
+            IndexWidth::U32 => DXGI_FORMAT_R32_UINT,
+        },
+    };
+    info!("Index buffer created: {} indices", scene_mesh.indices.len());
+    set_debug_name(&vertex_buffer, debug_names.scene("Vertex Buffer", scene));
+    set_debug_name(&index_buffer, debug_names.scene("Index Buffer", scene));
+
+    Ok(MeshBuffers {
+        vertex_buffer,
+        vertex_buffer_view,
+        vertex_count: scene_mesh.vertices.len() as u32,
+        index_buffer,
+        index_buffer_view,
+    })
+}
+
+/// 设置 D3D12 对象在 PIX 抓帧中显示的调试名称，`name` 为 `None`（未启用调试名称）时不做任何事
+unsafe fn set_debug_name(object: &ID3D12Object, name: Option<String>) {
+    if let Some(name) = name {
+        let wide_name: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+        if let Err(e) = object.SetName(windows::core::PCWSTR(wide_name.as_ptr())) {
+            warn!("Failed to set debug name {}: {:?}", name, e);
+        }
+    }
+}
+
+/// 在上传堆上创建缓冲并写入 `bytes`，`kind` 只用于错误信息
+unsafe fn create_upload_buffer(device: &ID3D12Device, bytes: &[u8], kind: &str) -> Result<ID3D12Resource> {
+    let heap_props = D3D12_HEAP_PROPERTIES {
+        Type: D3D12_HEAP_TYPE_UPLOAD,
+        ..Default::default()
+    };
+    let resource_desc = D3D12_RESOURCE_DESC {
+        Dimension: D3D12_RESOURCE_DIMENSION_BUFFER,
+        Width: bytes.len() as u64,
+        Height: 1,
+        DepthOrArraySize: 1,
+        MipLevels: 1,
+        SampleDesc: DXGI_SAMPLE_DESC { Count: 1, Quality: 0 },
+        Layout: D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
+        ..Default::default()
+    };
+
+    let mut buffer: Option<ID3D12Resource> = None;
+    device
+        .CreateCommittedResource(
+            &heap_props,
+            D3D12_HEAP_FLAG_NONE,
+            &resource_desc,
+            D3D12_RESOURCE_STATE_GENERIC_READ,
+            None,
+            &mut buffer,
+        )
+        .map_err(|e| DistRenderError::Graphics(GraphicsError::ResourceCreation(
+            format!("Failed to create {} buffer: {:?}", kind, e)
+        )))?;
+    let buffer = buffer.ok_or_else(|| DistRenderError::Graphics(GraphicsError::ResourceCreation(
+        format!("Failed to create {} buffer", kind)
+    )))?;
+
+    let mut data = std::ptr::null_mut();
+    buffer.Map(0, None, Some(&mut data)).map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to map {} buffer: {:?}", kind, e))
+    ))?;
+    std::ptr::copy_nonoverlapping(bytes.as_ptr(), data as *mut u8, bytes.len());
+    buffer.Unmap(0, None);
+    Ok(buffer)
+}
+
+/// 创建模板遮罩的根签名和 PSO
+///
+/// 矩形范围通过 4 个根常量传入，顶点由 SV_VertexID 生成；不绑定像素着色器，
+/// 不做深度测试，也不写颜色和深度，只把参考值写入模板缓冲。
+unsafe fn create_stencil_mask_pipeline(
+    device: &ID3D12Device,
+    shader_dir: &Path,
+    rtv_format: DXGI_FORMAT,
+    depth_format: DXGI_FORMAT,
+) -> Result<(ID3D12RootSignature, ID3D12PipelineState)> {
+    let root_parameters = [D3D12_ROOT_PARAMETER {
+        ParameterType: D3D12_ROOT_PARAMETER_TYPE_32BIT_CONSTANTS,
+        Anonymous: D3D12_ROOT_PARAMETER_0 {
+            Constants: D3D12_ROOT_CONSTANTS {
+                ShaderRegister: 0,  // b0
+                RegisterSpace: 0,
+                Num32BitValues: 4,
+            },
+        },
+        ShaderVisibility: D3D12_SHADER_VISIBILITY_VERTEX,
+    }];
+    let root_desc = D3D12_ROOT_SIGNATURE_DESC {
+        NumParameters: root_parameters.len() as u32,
+        pParameters: root_parameters.as_ptr(),
+        NumStaticSamplers: 0,
+        pStaticSamplers: std::ptr::null(),
+        Flags: D3D12_ROOT_SIGNATURE_FLAG_NONE,
+    };
+
+    let mut signature = None;
+    D3D12SerializeRootSignature(&root_desc, D3D_ROOT_SIGNATURE_VERSION_1, &mut signature, None)
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ResourceCreation(format!("Failed to serialize stencil mask root signature: {:?}", e))
+        ))?;
+    let signature = signature.unwrap();
+    let root_signature: ID3D12RootSignature = device.CreateRootSignature(
+        0,
+        std::slice::from_raw_parts(signature.GetBufferPointer() as _, signature.GetBufferSize())
+    ).map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to create stencil mask root signature: {:?}", e))
+    ))?;
+
+    let vs_path = shader_dir.join("stencil_mask.hlsl");
+    let vs_hlsl = std::fs::read_to_string(&vs_path)
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ShaderCompilation(format!("Failed to read {}: {}", vs_path.display(), e))
+        ))?;
+    let mut vs_blob = None;
+    let mut error_blob = None;
+    let result = D3DCompile(
+        vs_hlsl.as_ptr() as _,
+        vs_hlsl.len(),
+        None,
+        None,
+        None,
+        windows::core::s!("VSMain"),
+        windows::core::s!("vs_5_0"),
+        0,
+        0,
+        &mut vs_blob,
+        Some(&mut error_blob),
+    );
+    if let Err(e) = result {
+        let message = error_blob.map_or_else(|| format!("{:?}", e), |error| {
+            String::from_utf8_lossy(std::slice::from_raw_parts(
+                error.GetBufferPointer() as *const u8,
+                error.GetBufferSize(),
+            )).into_owned()
+        });
+        return Err(DistRenderError::Graphics(
+            GraphicsError::ShaderCompilation(format!("Stencil mask VS compile error: {}", message))
+        ));
+    }
+    let vs_blob = vs_blob.unwrap();
+
+    let mark = StencilPass::Mark.state();
+    let mut pso_desc = D3D12_GRAPHICS_PIPELINE_STATE_DESC::default();
+    pso_desc.pRootSignature = ManuallyDrop::new(Some(root_signature.clone()));
+    pso_desc.VS = D3D12_SHADER_BYTECODE {
+        pShaderBytecode: vs_blob.GetBufferPointer(),
+        BytecodeLength: vs_blob.GetBufferSize(),
+    };
+    // 不写颜色
+    pso_desc.BlendState.RenderTarget[0] = D3D12_RENDER_TARGET_BLEND_DESC {
+        RenderTargetWriteMask: 0,
+        ..Default::default()
+    };
+    pso_desc.RasterizerState = D3D12_RASTERIZER_DESC {
+        FillMode: D3D12_FILL_MODE_SOLID,
+        CullMode: D3D12_CULL_MODE_NONE,
+        DepthClipEnable: true.into(),
+        ..Default::default()
+    };
+    pso_desc.DepthStencilState = D3D12_DEPTH_STENCIL_DESC {
+        DepthEnable: false.into(),
+        DepthWriteMask: D3D12_DEPTH_WRITE_MASK_ZERO,
+        DepthFunc: D3D12_COMPARISON_FUNC_ALWAYS,
+        StencilEnable: true.into(),
+        StencilReadMask: 0xFF,
+        StencilWriteMask: mark.write_mask as u8,
+        FrontFace: stencil_op_desc(mark),
+        BackFace: stencil_op_desc(mark),
+    };
+    pso_desc.SampleMask = 0xFFFFFFFF;
+    pso_desc.DSVFormat = depth_format;
+    pso_desc.PrimitiveTopologyType = D3D12_PRIMITIVE_TOPOLOGY_TYPE_TRIANGLE;
+    pso_desc.NumRenderTargets = 1;
+    pso_desc.RTVFormats[0] = rtv_format;
+    pso_desc.SampleDesc.Count = 1;
+
+    let pso: ID3D12PipelineState = device.CreateGraphicsPipelineState(&pso_desc)
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ResourceCreation(format!("Failed to create stencil mask PSO: {:?}", e))
+        ))?;
+
+    Ok((root_signature, pso))
+}
@@ -6,7 +6,10 @@ cbuffer UniformBufferObject : register(b0)
     float4x4 projection;
     float4   lightDir;   // xyz 方向
     float4   lightColor; // rgb*强度
-    float4   cameraPos;
+    float4   cameraPos;  // xyz 位置, w: 非 0 时交换链为 UNORM，需要手动 sRGB 编码
+    float4   ambient;    // rgb: 环境光颜色*强度, a: 曝光
+    float4   tint;       // 模型颜色色调
+    float4x4 normalMatrix; // 模型矩阵的逆转置
 };
 
 struct PSInput
@@ -17,20 +20,27 @@ struct PSInput
     float3 color    : COLOR0;
 };
 
+// sRGB 渲染目标写入时自动编码，UNORM 渲染目标在这里编码
+float3 encodeOutput(float3 color)
+{
+    if (cameraPos.w == 0.0)
+    {
+        return color;
+    }
+    float3 c = saturate(color);
+    return c <= 0.0031308 ? c * 12.92 : 1.055 * pow(c, 1.0 / 2.4) - 0.055;
+}
+
+// 方向光 Blinn-Phong 光照（与其他后端共享，加载时展开）
+#include "../../shaders/directional_light.inc"
+
 float4 PSMain(PSInput IN) : SV_TARGET
 {
     float3 N = normalize(IN.normal);
-    float3 L = normalize(-lightDir.xyz);
     float3 V = normalize(cameraPos.xyz - IN.fragPos);
-    float3 H = normalize(L + V);
-
-    float diff = max(dot(N, L), 0.0);
-    float spec = diff > 0.0 ? pow(max(dot(N, H), 0.0), 32.0) : 0.0;
-
-    float3 ambient  = 0.1 * lightColor.rgb;
-    float3 diffuse  = diff * lightColor.rgb;
-    float3 specular = spec * lightColor.rgb;
+    float3 lighting = directional_light(N, V, lightDir.xyz, lightColor.rgb, ambient.rgb);
 
-    float3 finalColor = (ambient + diffuse + specular) * IN.color;
-    return float4(finalColor, 1.0);
+    // 乘以曝光，没有色调映射，超过 1 的分量在输出时被截断
+    float3 finalColor = lighting * IN.color * ambient.a * tint.rgb;
+    return float4(encodeOutput(finalColor), 1.0);
 }
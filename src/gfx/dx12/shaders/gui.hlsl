@@ -0,0 +1,57 @@
+// ================== 内置 GUI (VSMain / PSMain) ==================
+// egui 顶点以逻辑点为单位，原点在左上角。混合在 gamma 空间进行，与 egui 的约定一致；
+// 渲染目标为 sRGB 格式时写入会再编码一次，因此输出前先转回线性值
+cbuffer GuiConstants : register(b0)
+{
+    float2 screenSize;  // 逻辑点单位的屏幕尺寸
+    uint   manualSrgb;  // 非 0 时渲染目标为 UNORM 格式
+};
+
+Texture2D guiTexture : register(t0);
+SamplerState guiSampler : register(s0);
+
+struct VSInput
+{
+    float2 pos   : POSITION;
+    float2 uv    : TEXCOORD0;
+    float4 color : COLOR0;  // sRGB gamma，预乘 alpha
+};
+
+struct PSInput
+{
+    float4 pos   : SV_POSITION;
+    float2 uv    : TEXCOORD0;
+    float4 color : COLOR0;
+};
+
+PSInput VSMain(VSInput IN)
+{
+    PSInput OUT;
+    float2 ndc = 2.0 * IN.pos / screenSize - 1.0;
+    OUT.pos = float4(ndc.x, -ndc.y, 0.0, 1.0);
+    OUT.uv = IN.uv;
+    OUT.color = IN.color;
+    return OUT;
+}
+
+float3 gamma_from_linear(float3 rgb)
+{
+    float3 lower = rgb * 12.92;
+    float3 higher = 1.055 * pow(rgb, 1.0 / 2.4) - 0.055;
+    return rgb < 0.0031308 ? lower : higher;
+}
+
+float3 linear_from_gamma(float3 srgb)
+{
+    float3 lower = srgb / 12.92;
+    float3 higher = pow((srgb + 0.055) / 1.055, 2.4);
+    return srgb < 0.04045 ? lower : higher;
+}
+
+float4 PSMain(PSInput IN) : SV_TARGET
+{
+    // 纹理为 sRGB 格式，采样结果是线性值，需要转回 gamma 空间
+    float4 texel = guiTexture.Sample(guiSampler, IN.uv);
+    float4 color = IN.color * float4(gamma_from_linear(texel.rgb), texel.a);
+    return manualSrgb != 0 ? color : float4(linear_from_gamma(color.rgb), color.a);
+}
@@ -0,0 +1,17 @@
+// ================== 模板遮罩 (VSMain) ==================
+// 只写模板缓冲的矩形，两个三角形的顶点由 SV_VertexID 生成，不需要像素着色器
+cbuffer MaskRect : register(b0)
+{
+    float4 bounds;  // NDC 最小 x, 最小 y, 最大 x, 最大 y
+};
+
+static const float2 corners[6] =
+{
+    float2(0.0, 0.0), float2(1.0, 0.0), float2(1.0, 1.0),
+    float2(0.0, 0.0), float2(1.0, 1.0), float2(0.0, 1.0),
+};
+
+float4 VSMain(uint vertexId : SV_VertexID) : SV_POSITION
+{
+    return float4(lerp(bounds.xy, bounds.zw, corners[vertexId]), 0.0, 1.0);
+}
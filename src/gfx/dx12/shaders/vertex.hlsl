@@ -7,6 +7,9 @@ cbuffer UniformBufferObject : register(b0)
     float4   lightDir;   // xyz 方向
     float4   lightColor; // rgb*强度
     float4   cameraPos;
+    float4   ambient;    // rgb: 环境光颜色*强度, a: 曝光
+    float4   tint;       // 模型颜色色调
+    float4x4 normalMatrix; // 模型矩阵的逆转置
 };
 
 struct VSInput
@@ -29,7 +32,8 @@ VSOutput VSMain(VSInput IN)
     VSOutput OUT;
     float4 worldPos = mul(model, float4(IN.position, 1.0));
     OUT.fragPos = worldPos.xyz;
-    OUT.normal  = mul((float3x3)model, IN.normal);
+    // 法线矩阵在非均匀缩放时保持法线垂直于表面，像素着色器中重新归一化
+    OUT.normal  = mul((float3x3)normalMatrix, IN.normal);
     OUT.color   = IN.color;
     OUT.pos = mul(projection, mul(view, worldPos));
     return OUT;
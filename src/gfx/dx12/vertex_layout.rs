@@ -0,0 +1,62 @@
+//! 顶点布局到 DX12 输入布局的转换
+
+use std::ffi::CString;
+
+use windows::core::PCSTR;
+use windows::Win32::Graphics::Direct3D12::*;
+use windows::Win32::Graphics::Dxgi::Common::*;
+
+use crate::renderer::resources::vertex_layout::{VertexFormat, VertexLayout};
+
+/// 由 `VertexLayout` 生成的输入元素，持有语义名称字符串，创建 PSO 期间需保持存活
+pub struct Dx12InputLayout {
+    _semantics: Vec<CString>,
+    elements: Vec<D3D12_INPUT_ELEMENT_DESC>,
+}
+
+impl Dx12InputLayout {
+    /// 按布局生成从输入槽 `slot` 读取的逐顶点输入元素，语义为属性名称的大写形式
+    pub fn new(slot: u32, layout: &VertexLayout) -> Self {
+        let semantics: Vec<CString> = layout
+            .attributes()
+            .iter()
+            .map(|attribute| CString::new(attribute.semantic()).expect("Vertex attribute name contains NUL"))
+            .collect();
+        let elements = layout
+            .attributes()
+            .iter()
+            .zip(&semantics)
+            .map(|(attribute, semantic)| D3D12_INPUT_ELEMENT_DESC {
+                SemanticName: PCSTR(semantic.as_ptr() as *const u8),
+                SemanticIndex: 0,
+                Format: vertex_format(attribute.format),
+                InputSlot: slot,
+                AlignedByteOffset: attribute.offset,
+                InputSlotClass: D3D12_INPUT_CLASSIFICATION_PER_VERTEX_DATA,
+                InstanceDataStepRate: 0,
+            })
+            .collect();
+        Self {
+            _semantics: semantics,
+            elements,
+        }
+    }
+
+    pub fn desc(&self) -> D3D12_INPUT_LAYOUT_DESC {
+        D3D12_INPUT_LAYOUT_DESC {
+            pInputElementDescs: self.elements.as_ptr(),
+            NumElements: self.elements.len() as u32,
+        }
+    }
+}
+
+fn vertex_format(format: VertexFormat) -> DXGI_FORMAT {
+    match format {
+        VertexFormat::Float32 => DXGI_FORMAT_R32_FLOAT,
+        VertexFormat::Float32x2 => DXGI_FORMAT_R32G32_FLOAT,
+        VertexFormat::Float32x3 => DXGI_FORMAT_R32G32B32_FLOAT,
+        VertexFormat::Float32x4 => DXGI_FORMAT_R32G32B32A32_FLOAT,
+        VertexFormat::Uint16x4 => DXGI_FORMAT_R16G16B16A16_UINT,
+        VertexFormat::Unorm8x4 => DXGI_FORMAT_R8G8B8A8_UNORM,
+    }
+}
@@ -0,0 +1,93 @@
+//! 固定宽高比视口（信箱模式）
+//!
+//! 配置了目标宽高比时，视口取窗口内按该宽高比能放下的最大居中矩形：窗口更宽时左右留黑边，
+//! 更高时上下留黑边。各后端用同一个矩形设置视口和裁剪矩形，黑边在清除颜色时填充为黑色，
+//! 保证截图和演示时的画面构图与窗口形状无关。
+
+/// 黑边的颜色
+pub const LETTERBOX_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+/// 视口矩形（物理像素，原点在窗口左上角）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewportRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ViewportRect {
+    /// 覆盖整个窗口的视口
+    pub fn full(width: u32, height: u32) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        }
+    }
+
+    /// 窗口内按 `target_aspect`（宽 / 高）居中的最大视口，`None` 时覆盖整个窗口
+    pub fn letterbox(width: u32, height: u32, target_aspect: Option<f32>) -> Self {
+        let Some(aspect) = target_aspect.filter(|aspect| aspect.is_finite() && *aspect > 0.0) else {
+            return Self::full(width, height);
+        };
+
+        let fitted_width = (height as f32 * aspect).round() as u32;
+        if fitted_width < width {
+            // 窗口比目标更宽：左右留黑边
+            let fitted_width = fitted_width.max(1);
+            Self {
+                x: (width - fitted_width) / 2,
+                y: 0,
+                width: fitted_width,
+                height,
+            }
+        } else {
+            // 窗口比目标更高：上下留黑边
+            let fitted_height = ((width as f32 / aspect).round() as u32).clamp(1, height.max(1));
+            Self {
+                x: 0,
+                y: height.saturating_sub(fitted_height) / 2,
+                width,
+                height: fitted_height,
+            }
+        }
+    }
+
+    /// 视口的宽高比，用于相机投影
+    pub fn aspect(&self) -> f32 {
+        self.width.max(1) as f32 / self.height.max(1) as f32
+    }
+
+    /// 是否覆盖宽 `width`、高 `height` 的整个窗口（没有黑边）
+    pub fn covers(&self, width: u32, height: u32) -> bool {
+        *self == Self::full(width, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_letterbox_16_9_in_square_window() {
+        let viewport = ViewportRect::letterbox(900, 900, Some(16.0 / 9.0));
+        // 水平方向铺满，垂直方向居中
+        assert_eq!(viewport, ViewportRect { x: 0, y: 197, width: 900, height: 506 });
+        assert!((viewport.aspect() - 16.0 / 9.0).abs() < 0.01);
+        assert!(!viewport.covers(900, 900));
+    }
+
+    #[test]
+    fn test_pillarbox_and_full_window() {
+        // 窗口比目标更宽时左右留黑边
+        let viewport = ViewportRect::letterbox(1920, 1080, Some(4.0 / 3.0));
+        assert_eq!(viewport, ViewportRect { x: 240, y: 0, width: 1440, height: 1080 });
+
+        // 宽高比一致或未配置时覆盖整个窗口
+        assert!(ViewportRect::letterbox(1920, 1080, Some(16.0 / 9.0)).covers(1920, 1080));
+        assert!(ViewportRect::letterbox(800, 600, None).covers(800, 600));
+        assert!(ViewportRect::letterbox(800, 600, Some(0.0)).covers(800, 600));
+    }
+}
@@ -7,12 +7,20 @@ use winit::window::{Window, WindowBuilder};
 use winit::dpi::LogicalSize;
 use raw_window_handle::{HasWindowHandle, RawWindowHandle};
 
-use metal::{Device, CommandQueue, MetalLayer, MTLPixelFormat};
+use metal::{Device, CommandQueue, MetalLayer, MTLCounterSamplingPoint, MTLPixelFormat};
 use objc::runtime::{YES};
 use core_graphics_types::geometry::CGSize;
 
 use crate::gfx::backend::GraphicsBackend;
+use crate::gfx::capabilities::DeviceCapabilities;
+use crate::gfx::window::place_window;
 use crate::core::Config;
+use crate::core::error::Result;
+use crate::core::config::{effective_buffer_count, SurfaceFormat};
+use crate::gfx::surface_format::select_surface_format;
+
+/// CAMetalLayer 支持的 8 位颜色格式，自动选择时优先使用 sRGB 格式
+const LAYER_FORMATS: [MTLPixelFormat; 2] = [MTLPixelFormat::BGRA8Unorm_sRGB, MTLPixelFormat::BGRA8Unorm];
 
 /// Metal 鍥惧舰鍚庣
 pub struct MetalContext {
@@ -20,6 +28,8 @@ pub struct MetalContext {
     pub device: Device,
     pub command_queue: CommandQueue,
     pub layer: MetalLayer,
+    /// drawable 的像素格式，管线的颜色附件格式与其一致
+    pub pixel_format: MTLPixelFormat,
 }
 
 impl GraphicsBackend for MetalContext {
@@ -31,6 +41,7 @@ impl GraphicsBackend for MetalContext {
             .with_inner_size(LogicalSize::new(config.window.width, config.window.height))
             .with_resizable(config.window.resizable);
 
+        let window_builder = place_window(window_builder, event_loop, &config.window);
         let window = Arc::new(window_builder.build(event_loop).expect("鏃犳硶鍒涘缓绐楀彛"));
 
         // 鑾峰彇绯荤粺榛樿 Metal 璁惧
@@ -42,12 +53,28 @@ impl GraphicsBackend for MetalContext {
         // 鍒涘缓骞堕厤缃?CAMetalLayer
         let layer = MetalLayer::new();
         layer.set_device(&device);
-        layer.set_pixel_format(MTLPixelFormat::BGRA8Unorm);
+        let pixel_format = select_surface_format(
+            &LAYER_FORMATS,
+            config.graphics.surface_format,
+            |format| match format {
+                MTLPixelFormat::BGRA8Unorm_sRGB => Some(SurfaceFormat::Bgra8Srgb),
+                MTLPixelFormat::BGRA8Unorm => Some(SurfaceFormat::Bgra8Unorm),
+                _ => None,
+            },
+            is_srgb_format,
+        )
+        .unwrap_or(MTLPixelFormat::BGRA8Unorm);
+        layer.set_pixel_format(pixel_format);
         layer.set_presents_with_transaction(false);
-        
-        // Enable triple buffering for better performance
-        // This prevents next_drawable() from blocking when drawing faster than display refresh
-        layer.set_maximum_drawable_count(3);
+
+        // CAMetalLayer 只支持 2 或 3 个 drawable
+        let buffer_count = effective_buffer_count(config.graphics.buffer_count, 2, Some(3));
+        info!(
+            requested = config.graphics.buffer_count,
+            effective = buffer_count,
+            "Swapchain buffer count selected"
+        );
+        layer.set_maximum_drawable_count(buffer_count as u64);
 
         // 灏?Layer 缁戝畾鍒扮獥鍙?
         // 杩欓噷闇€瑕佷娇鐢?raw-window-handle 鏉ヨ幏鍙栧簳灞?NSView
@@ -70,12 +97,16 @@ impl GraphicsBackend for MetalContext {
         
         info!("Metal 鍚庣鍒濆鍖栧畬鎴?);
 
-        Self {
+        let context = Self {
             window,
             device,
             command_queue,
             layer,
-        }
+            pixel_format,
+        };
+        context.report_capabilities().log();
+
+        context
     }
 
     fn window(&self) -> &Window {
@@ -85,4 +116,40 @@ impl GraphicsBackend for MetalContext {
     fn backend_name(&self) -> &str {
         "Metal"
     }
+
+    /// 查询设备能力
+    ///
+    /// Metal 没有纹理尺寸和顶点属性数量的查询接口，使用 macOS GPU 家族规定的上限。
+    fn report_capabilities(&self) -> DeviceCapabilities {
+        DeviceCapabilities {
+            backend: "Metal".to_string(),
+            device_name: self.device.name().to_string(),
+            api_version: "Metal".to_string(),
+            max_msaa_samples: [8u64, 4, 2]
+                .into_iter()
+                .find(|&count| self.device.supports_texture_sample_count(count))
+                .unwrap_or(1) as u32,
+            max_anisotropy: 16.0,
+            timestamp_query: self.device.supports_counter_sampling(MTLCounterSamplingPoint::AtStageBoundary),
+            polygon_mode_line: true,
+            // Metal 的三角形填充模式只有实心和线框
+            polygon_mode_point: false,
+            max_texture_dimension_2d: 16384,
+            max_uniform_buffer_size: self.device.max_buffer_length(),
+            max_vertex_attributes: 31,
+        }
+    }
+
+    /// 提交一个空命令缓冲并等待完成；命令队列按顺序执行，此时之前的工作均已结束
+    fn wait_idle(&self) -> Result<()> {
+        let command_buffer = self.command_queue.new_command_buffer();
+        command_buffer.commit();
+        command_buffer.wait_until_completed();
+        Ok(())
+    }
+}
+
+/// 写入时是否由硬件做 sRGB 编码
+pub fn is_srgb_format(format: MTLPixelFormat) -> bool {
+    format == MTLPixelFormat::BGRA8Unorm_sRGB
 }
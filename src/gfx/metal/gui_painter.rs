@@ -0,0 +1,230 @@
+//! Metal GUI 绘制器
+//!
+//! 在场景的渲染命令编码器结束之前绘制内置 GUI 的 egui 网格。纹理以 sRGB 格式保存，
+//! 片段着色器把采样结果转回 gamma 空间后与顶点颜色相乘，与 egui 的混合约定一致；
+//! drawable 为 sRGB 格式时输出前转回线性值。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use metal::*;
+
+use crate::core::error::{DistRenderError, Result};
+use crate::gfx::metal::context::is_srgb_format;
+use crate::gui::paint::{image_pixels, GuiBatch};
+use crate::gui::GuiFrame;
+
+/// egui 顶点大小：位置和纹理坐标各两个 f32，加上 RGBA8 颜色
+const GUI_VERTEX_STRIDE: u64 = std::mem::size_of::<egui::epaint::Vertex>() as u64;
+
+/// 上传到 GPU 的 egui 纹理及其采样器
+struct GuiTexture {
+    texture: Texture,
+    sampler: SamplerState,
+}
+
+/// Metal GUI 绘制器
+pub struct GuiPainter {
+    pipeline_state: RenderPipelineState,
+    /// 关闭深度测试和写入，GUI 总是绘制在场景之上
+    depth_stencil_state: DepthStencilState,
+    textures: HashMap<egui::TextureId, GuiTexture>,
+    /// drawable 为 UNORM 格式，着色器直接输出 gamma 空间的颜色
+    manual_srgb: bool,
+}
+
+/// 顶点和片段着色器共用的常量，与 gui.metal 中的 `GuiConstants` 一致
+#[repr(C)]
+struct GuiConstants {
+    screen_size: [f32; 2],
+    manual_srgb: u32,
+}
+
+impl GuiPainter {
+    /// 创建绘制器，颜色和深度格式与场景的渲染通道一致
+    pub fn new(device: &Device, pixel_format: MTLPixelFormat) -> Result<Self> {
+        let shader_path = Path::new("src/gfx/metal/shaders/gui.metal");
+        let shader_source = std::fs::read_to_string(shader_path)
+            .map_err(|e| DistRenderError::Initialization(format!("Failed to load Metal GUI shader file: {}", e)))?;
+        let library = device.new_library_with_source(&shader_source, &CompileOptions::new())
+            .map_err(|e| DistRenderError::Initialization(format!("GUI shader compilation failed: {}", e)))?;
+        let vertex_function = library.get_function("gui_vertex_main", None)
+            .map_err(|_| DistRenderError::Initialization("GUI vertex function not found".into()))?;
+        let fragment_function = library.get_function("gui_fragment_main", None)
+            .map_err(|_| DistRenderError::Initialization("GUI fragment function not found".into()))?;
+
+        // egui::epaint::Vertex: pos (float2), uv (float2), color (RGBA8)
+        let vertex_descriptor = VertexDescriptor::new();
+        let attributes = [
+            (MTLVertexFormat::Float2, 0),
+            (MTLVertexFormat::Float2, 8),
+            (MTLVertexFormat::UChar4Normalized, 16),
+        ];
+        for (index, (format, offset)) in attributes.into_iter().enumerate() {
+            let attribute = vertex_descriptor.attributes().object_at(index as u64).unwrap();
+            attribute.set_format(format);
+            attribute.set_offset(offset);
+            attribute.set_buffer_index(0);
+        }
+        let layout = vertex_descriptor.layouts().object_at(0).unwrap();
+        layout.set_stride(GUI_VERTEX_STRIDE);
+        layout.set_step_rate(1);
+        layout.set_step_function(MTLVertexStepFunction::PerVertex);
+
+        let pipeline_descriptor = RenderPipelineDescriptor::new();
+        pipeline_descriptor.set_vertex_function(Some(&vertex_function));
+        pipeline_descriptor.set_fragment_function(Some(&fragment_function));
+        pipeline_descriptor.set_vertex_descriptor(Some(&vertex_descriptor));
+        pipeline_descriptor.set_depth_attachment_pixel_format(MTLPixelFormat::Depth32Float);
+
+        // 预乘 alpha 混合，与 egui-wgpu 相同
+        let color_attachment = pipeline_descriptor.color_attachments().object_at(0).unwrap();
+        color_attachment.set_pixel_format(pixel_format);
+        color_attachment.set_blending_enabled(true);
+        color_attachment.set_rgb_blend_operation(MTLBlendOperation::Add);
+        color_attachment.set_source_rgb_blend_factor(MTLBlendFactor::One);
+        color_attachment.set_destination_rgb_blend_factor(MTLBlendFactor::OneMinusSourceAlpha);
+        color_attachment.set_alpha_blend_operation(MTLBlendOperation::Add);
+        color_attachment.set_source_alpha_blend_factor(MTLBlendFactor::OneMinusDestinationAlpha);
+        color_attachment.set_destination_alpha_blend_factor(MTLBlendFactor::One);
+
+        let pipeline_state = device.new_render_pipeline_state(&pipeline_descriptor)
+            .map_err(|e| DistRenderError::Initialization(format!("GUI pipeline state creation failed: {}", e)))?;
+
+        let depth_stencil_desc = DepthStencilDescriptor::new();
+        depth_stencil_desc.set_depth_compare_function(MTLCompareFunction::Always);
+        depth_stencil_desc.set_depth_write_enabled(false);
+        let depth_stencil_state = device.new_depth_stencil_state(&depth_stencil_desc);
+
+        Ok(Self {
+            pipeline_state,
+            depth_stencil_state,
+            textures: HashMap::new(),
+            manual_srgb: !is_srgb_format(pixel_format),
+        })
+    }
+
+    /// 创建或更新本帧的纹理
+    pub fn update_textures(&mut self, device: &Device, frame: &GuiFrame) {
+        for (id, delta) in &frame.textures_delta.set {
+            let [width, height] = delta.image.size();
+            let pixels = image_pixels(&delta.image);
+
+            // 带位置的更新只覆盖已有纹理的一部分，否则重新创建整张纹理
+            let [x, y] = match delta.pos {
+                Some(pos) if self.textures.contains_key(id) => pos,
+                _ => {
+                    self.textures.insert(*id, create_texture(device, [width, height], delta.options));
+                    [0, 0]
+                }
+            };
+            self.textures[id].texture.replace_region(
+                MTLRegion::new_2d(x as u64, y as u64, width as u64, height as u64),
+                0,
+                pixels.as_ptr() as *const _,
+                (width * 4) as u64,
+            );
+        }
+    }
+
+    /// 在场景的编码器上绘制 GUI，`extent` 为 drawable 的像素尺寸
+    pub fn draw(&self, device: &Device, encoder: &RenderCommandEncoderRef, frame: &GuiFrame, extent: [u32; 2]) {
+        let batch = GuiBatch::new(frame, extent);
+        if batch.is_empty() {
+            return;
+        }
+
+        let vertex_buffer = device.new_buffer_with_data(
+            batch.vertices.as_ptr() as *const _,
+            batch.vertices.len() as u64 * GUI_VERTEX_STRIDE,
+            MTLResourceOptions::CPUCacheModeDefaultCache,
+        );
+        let index_buffer = device.new_buffer_with_data(
+            batch.indices.as_ptr() as *const _,
+            (batch.indices.len() * std::mem::size_of::<u32>()) as u64,
+            MTLResourceOptions::CPUCacheModeDefaultCache,
+        );
+
+        // GUI 覆盖整个 drawable，不使用场景的信箱视口
+        encoder.set_render_pipeline_state(&self.pipeline_state);
+        encoder.set_depth_stencil_state(&self.depth_stencil_state);
+        encoder.set_cull_mode(MTLCullMode::None);
+        encoder.set_depth_bias(0.0, 0.0, 0.0);
+        encoder.set_viewport(MTLViewport {
+            originX: 0.0,
+            originY: 0.0,
+            width: extent[0] as f64,
+            height: extent[1] as f64,
+            znear: 0.0,
+            zfar: 1.0,
+        });
+        let constants = GuiConstants {
+            screen_size: [
+                extent[0] as f32 / frame.pixels_per_point,
+                extent[1] as f32 / frame.pixels_per_point,
+            ],
+            manual_srgb: self.manual_srgb as u32,
+        };
+        let constants_size = std::mem::size_of::<GuiConstants>() as u64;
+        let constants_ptr = &constants as *const GuiConstants as *const _;
+        encoder.set_vertex_bytes(1, constants_size, constants_ptr);
+        encoder.set_fragment_bytes(0, constants_size, constants_ptr);
+
+        for mesh in &batch.meshes {
+            let Some(texture) = self.textures.get(&mesh.texture_id) else {
+                continue;
+            };
+            encoder.set_scissor_rect(MTLScissorRect {
+                x: mesh.clip.x as u64,
+                y: mesh.clip.y as u64,
+                width: mesh.clip.width as u64,
+                height: mesh.clip.height as u64,
+            });
+            encoder.set_fragment_texture(0, Some(&texture.texture));
+            encoder.set_fragment_sampler_state(0, Some(&texture.sampler));
+            encoder.set_vertex_buffer(0, Some(&vertex_buffer), mesh.vertex_offset as u64 * GUI_VERTEX_STRIDE);
+            encoder.draw_indexed_primitives(
+                MTLPrimitiveType::Triangle,
+                mesh.index_count as u64,
+                MTLIndexType::UInt32,
+                &index_buffer,
+                (mesh.first_index as usize * std::mem::size_of::<u32>()) as u64,
+            );
+        }
+    }
+
+    /// 释放本帧不再使用的纹理（已提交的命令缓冲持有引用，GPU 用完后才真正销毁）
+    pub fn free_textures(&mut self, frame: &GuiFrame) {
+        for id in &frame.textures_delta.free {
+            self.textures.remove(id);
+        }
+    }
+}
+
+/// 创建 sRGB 纹理及其采样器
+fn create_texture(device: &Device, [width, height]: [usize; 2], options: egui::TextureOptions) -> GuiTexture {
+    let texture_desc = TextureDescriptor::new();
+    texture_desc.set_pixel_format(MTLPixelFormat::RGBA8Unorm_sRGB);
+    texture_desc.set_width(width as u64);
+    texture_desc.set_height(height as u64);
+    texture_desc.set_usage(MTLTextureUsage::ShaderRead);
+    let texture = device.new_texture(&texture_desc);
+
+    let filter = |filter: egui::TextureFilter| match filter {
+        egui::TextureFilter::Nearest => MTLSamplerMinMagFilter::Nearest,
+        egui::TextureFilter::Linear => MTLSamplerMinMagFilter::Linear,
+    };
+    let address_mode = match options.wrap_mode {
+        egui::TextureWrapMode::ClampToEdge => MTLSamplerAddressMode::ClampToEdge,
+        egui::TextureWrapMode::Repeat => MTLSamplerAddressMode::Repeat,
+        egui::TextureWrapMode::MirroredRepeat => MTLSamplerAddressMode::MirrorRepeat,
+    };
+    let sampler_desc = SamplerDescriptor::new();
+    sampler_desc.set_mag_filter(filter(options.magnification));
+    sampler_desc.set_min_filter(filter(options.minification));
+    sampler_desc.set_address_mode_s(address_mode);
+    sampler_desc.set_address_mode_t(address_mode);
+    let sampler = device.new_sampler(&sampler_desc);
+
+    GuiTexture { texture, sampler }
+}
@@ -1,12 +1,16 @@
 //! Metal 图形后端模块
 //!
-//! 本模块提供了基于 Apple Metal API 的图形后端实现。
+//! 本模块提供了基于 Apple Metal API 的图形后端实现，包括渲染器和内置 GUI 的绘制器。
 //! 仅在 macOS/iOS 平台上可用。
 
 #[cfg(target_os = "macos")]
 pub mod context;
 #[cfg(target_os = "macos")]
 pub mod renderer;
+#[cfg(target_os = "macos")]
+pub mod gui_painter;
+#[cfg(target_os = "macos")]
+pub mod vertex_layout;
 
 #[cfg(target_os = "macos")]
 pub use context::MetalContext;
@@ -1,26 +1,41 @@
 //! Metal 娓叉煋鍣ㄥ疄鐜?
 
 use crate::core::{Config, SceneConfig};
+use crate::core::config::{effective_frames_in_flight, DepthBias};
 use crate::core::error::{Result, DistRenderError};
-use crate::gfx::metal::context::MetalContext;
+use crate::gfx::debug_name::DebugNames;
+use crate::gfx::metal::context::{is_srgb_format, MetalContext};
+use crate::gfx::metal::gui_painter::GuiPainter;
+use crate::gfx::metal::vertex_layout::vertex_descriptor;
 use crate::gfx::GraphicsBackend;
-use crate::renderer::resources::vertex::{MyVertex, convert_geometry_vertex, create_default_triangle};
-use crate::geometry::loaders::ObjLoader;
-use crate::component::{Camera, DirectionalLight};
+use crate::gfx::letterbox::{ViewportRect, LETTERBOX_COLOR};
+use crate::gfx::shader_preprocessor::{preprocess, ShaderLanguage};
+use crate::gfx::surface_format::encode_output_color;
+use crate::renderer::resources::vertex::MyVertex;
+use crate::renderer::resources::vertex_layout::HasVertexLayout;
+use crate::renderer::resources::scene_mesh::{select_lods, ModelMesh, SceneMesh};
+use crate::renderer::stats::{FrameStats, PresentMode, PresentStats};
+use crate::component::{AutoRotate, Camera, DirectionalLight};
 use crate::math::{Matrix4, Vector3};
+use crate::math::matrix::normal_matrix;
+use crate::geometry::mesh::IndexWidth;
 use crate::core::input::InputSystem;
 use winit::window::Window;
 use crate::gui::ipc::GuiStatePacket;
+use crate::gui::{GuiManager, GuiState};
 
+use std::collections::VecDeque;
 use std::path::Path;
+use std::time::Instant;
 use std::f32::consts::PI;
-use tracing::{info, warn};
+use tracing::info;
 use winit::event_loop::EventLoop;
 use metal::*;
 use objc::rc::autoreleasepool;
 use core_graphics_types::geometry::CGSize;
 
-use crate::geometry::loaders::MeshLoader;
+/// CAMetalLayer 最多轮换的 drawable 数量
+const MAX_DRAWABLE_COUNT: u32 = 3;
 
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -31,6 +46,10 @@ struct Uniforms {
     light_dir: [f32; 4],
     light_color: [f32; 4],
     camera_pos: [f32; 4],
+    ambient: [f32; 4],
+    tint: [f32; 4],
+    // 法线矩阵（模型矩阵的逆转置），非均匀缩放时保持法线垂直于表面
+    normal_matrix: Matrix4,
 }
 
 pub struct Renderer {
@@ -39,11 +58,41 @@ pub struct Renderer {
     depth_stencil_state: DepthStencilState,
     vertex_buffer: Buffer,
     index_buffer: Buffer,
+    index_type: MTLIndexType,
     depth_texture: Texture,
-    index_count: u64,
+    models: Vec<ModelMesh>,
+    /// 加载失败、使用占位几何体的模型序号，重新加载时用于判断是否失败
+    fallback_models: Vec<usize>,
+    /// 加载模型时是否使用磁盘缓存
+    mesh_cache: bool,
     camera: Camera,
     directional_light: DirectionalLight,
+    // 模型自动旋转，叠加在 GUI 设置的旋转之上
+    auto_rotate: AutoRotate,
     scene: SceneConfig,
+    // 主通道深度偏移，Metal 在编码器上设置
+    depth_bias: DepthBias,
+    // 固定的画面宽高比，视口按该比例居中（信箱模式）
+    target_aspect: Option<f32>,
+    // 同时在 GPU 上执行的最大帧数，提交后等到未完成的帧少于该值（为 1 时即低延迟模式）
+    frames_in_flight: u32,
+    // 关闭低延迟模式时的帧数（配置值，未配置或为 1 时等于 drawable 数）
+    pipelined_frames: u32,
+    // 已提交、可能尚未执行完的命令缓冲（按提交顺序），用于限制在途帧数
+    in_flight_command_buffers: VecDeque<CommandBuffer>,
+    // 还需强制清除颜色的帧数（创建和尺寸变化后每个 drawable 清除一次）
+    pending_color_clears: u32,
+    /// 运行时间（秒），驱动清除颜色循环
+    total_time: f32,
+    /// 内置 GUI（egui），在场景之后用同一个编码器绘制
+    gui_manager: GuiManager,
+    gui_painter: GuiPainter,
+    /// 是否绘制内置 GUI 并处理其输入事件
+    gui_enabled: bool,
+    /// 呈现统计（跨帧累计）
+    present_stats: PresentStats,
+    /// Xcode 抓帧中的资源调试名称（启用验证时）
+    debug_names: DebugNames,
 }
 
 impl Renderer {
@@ -54,6 +103,7 @@ impl Renderer {
         let shader_path = Path::new("src/gfx/metal/shaders/shader.metal");
         let shader_source = std::fs::read_to_string(shader_path)
             .map_err(|e| DistRenderError::Initialization(format!("Failed to load Metal shader file: {}", e)))?;
+        let shader_source = preprocess(&shader_source, ShaderLanguage::Msl)?;
         
         let device = &backend.device;
         let library = device.new_library_with_source(&shader_source, &CompileOptions::new())
@@ -65,34 +115,19 @@ impl Renderer {
             .map_err(|_| DistRenderError::Initialization("Fragment function not found".into()))?;
 
         // 2. Vertex Descriptor
-        let vertex_descriptor = VertexDescriptor::new();
-        
-        // Position
-        vertex_descriptor.attributes().object_at(0).unwrap().set_format(MTLVertexFormat::Float3);
-        vertex_descriptor.attributes().object_at(0).unwrap().set_offset(0);
-        vertex_descriptor.attributes().object_at(0).unwrap().set_buffer_index(0);
-        
-        // Normal
-        vertex_descriptor.attributes().object_at(1).unwrap().set_format(MTLVertexFormat::Float3);
-        vertex_descriptor.attributes().object_at(1).unwrap().set_offset(12);
-        vertex_descriptor.attributes().object_at(1).unwrap().set_buffer_index(0);
-
-        // Color
-        vertex_descriptor.attributes().object_at(2).unwrap().set_format(MTLVertexFormat::Float3);
-        vertex_descriptor.attributes().object_at(2).unwrap().set_offset(24);
-        vertex_descriptor.attributes().object_at(2).unwrap().set_buffer_index(0);
-
-        vertex_descriptor.layouts().object_at(0).unwrap().set_stride(36); 
-        vertex_descriptor.layouts().object_at(0).unwrap().set_step_rate(1);
-        vertex_descriptor.layouts().object_at(0).unwrap().set_step_function(MTLVertexStepFunction::PerVertex);
+        let vertex_descriptor = vertex_descriptor(0, &MyVertex::vertex_layout());
 
         // 3. Pipeline State
         let pipeline_descriptor = RenderPipelineDescriptor::new();
         pipeline_descriptor.set_vertex_function(Some(&vertex_function));
         pipeline_descriptor.set_fragment_function(Some(&fragment_function));
         pipeline_descriptor.set_vertex_descriptor(Some(&vertex_descriptor));
-        pipeline_descriptor.color_attachments().object_at(0).unwrap().set_pixel_format(MTLPixelFormat::BGRA8Unorm);
+        pipeline_descriptor.color_attachments().object_at(0).unwrap().set_pixel_format(backend.pixel_format);
         pipeline_descriptor.set_depth_attachment_pixel_format(MTLPixelFormat::Depth32Float);
+        let debug_names = DebugNames::new(config.graphics.validation);
+        if let Some(label) = debug_names.name("Scene Pipeline", "Solid") {
+            pipeline_descriptor.set_label(&label);
+        }
 
         let pipeline_state = device.new_render_pipeline_state(&pipeline_descriptor)
             .map_err(|e| DistRenderError::Initialization(format!("Pipeline state creation failed: {}", e)))?;
@@ -103,37 +138,10 @@ impl Renderer {
         depth_stencil_desc.set_depth_write_enabled(true);
         let depth_stencil_state = device.new_depth_stencil_state(&depth_stencil_desc);
 
-        // 4. Load Mesh
-        let obj_path = Path::new(&scene.model.path);
-        let (vertices, indices) = if obj_path.exists() {
-            info!("Loading mesh from: {}", obj_path.display());
-            match ObjLoader::load_from_file(obj_path) {
-                Ok(mesh_data) => {
-                     let verts = mesh_data.vertices.iter().map(|v| convert_geometry_vertex(v)).collect::<Vec<_>>();
-                     let inds = mesh_data.indices.clone();
-                     (verts, inds)
-                }
-                Err(e) => {
-                    warn!("Failed to load OBJ: {}, using default triangle", e);
-                    (create_default_triangle().to_vec(), vec![0, 1, 2])
-                }
-            }
-        } else {
-             warn!("OBJ file not found, using default triangle");
-             (create_default_triangle().to_vec(), vec![0, 1, 2])
-        };
-
-        let vertex_buffer = device.new_buffer_with_data(
-            vertices.as_ptr() as *const _,
-            (vertices.len() * std::mem::size_of::<MyVertex>()) as u64,
-            MTLResourceOptions::CPUCacheModeDefaultCache,
-        );
-        
-        let index_buffer = device.new_buffer_with_data(
-            indices.as_ptr() as *const _,
-            (indices.len() * std::mem::size_of::<u32>()) as u64,
-            MTLResourceOptions::CPUCacheModeDefaultCache,
-        );
+        // 4. Load Mesh (triangle strips are converted to lists, the pipeline only draws lists)
+        let scene_mesh = SceneMesh::load(&scene.models, scene.fallback_primitive, false, config.graphics.mesh_cache);
+        let scene_bounds = scene.camera.fit_clip_planes.then(|| scene_mesh.world_bounds(&scene.models)).flatten();
+        let (vertex_buffer, index_buffer, index_type) = create_mesh_buffers(device, &scene_mesh, debug_names, scene);
 
         // 5. Depth Texture
         let size = backend.window().inner_size();
@@ -150,9 +158,10 @@ impl Renderer {
         let cam_pos = Vector3::new(pos[0], pos[1], pos[2]);
         
         // Set camera lens parameters
-        let aspect_ratio = size.width as f32 / size.height as f32;
-        camera.set_lens(
+        let aspect_ratio = ViewportRect::letterbox(size.width, size.height, config.graphics.target_aspect).aspect();
+        camera.set_perspective(
             scene.camera.fov * PI / 180.0,
+            scene.camera.fov_axis,
             aspect_ratio,
             scene.camera.near_clip,
             scene.camera.far_clip,
@@ -162,6 +171,12 @@ impl Renderer {
         camera.look_at(cam_pos, Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
         
         info!("Camera initialized at position {:?}", cam_pos);
+
+        // 按场景包围盒收紧近远裁剪面
+        if let Some(bounds) = &scene_bounds {
+            camera.fit_clip_planes(bounds);
+            info!("Camera clip planes fitted to scene bounds: near={}, far={}", camera.near_z(), camera.far_z());
+        }
         
         // 7. Initialize directional light
         let directional_light = scene.light.to_directional_light("MainLight");
@@ -172,17 +187,46 @@ impl Renderer {
             directional_light.direction
         );
         
+        let gui_painter = GuiPainter::new(device, backend.pixel_format)?;
+        let mut gui_state = GuiState::new(config, scene);
+        gui_state.camera_near = camera.near_z();
+        gui_state.camera_far = camera.far_z();
+        let gui_manager = GuiManager::without_renderer(backend.window(), gui_state);
+
+        // 在途帧数不超过 drawable 数
+        let drawable_count = backend.layer.maximum_drawable_count() as u32;
+        let frames_in_flight = effective_frames_in_flight(config.graphics.frames_in_flight, drawable_count);
+        let pipelined_frames =
+            effective_frames_in_flight(config.graphics.frames_in_flight.filter(|&n| n > 1), drawable_count);
+
         Ok(Self {
             backend,
             pipeline_state,
             depth_stencil_state,
             vertex_buffer,
             index_buffer,
+            index_type,
             depth_texture,
-            index_count: indices.len() as u64,
+            models: scene_mesh.models,
+            fallback_models: scene_mesh.fallback_models,
+            mesh_cache: config.graphics.mesh_cache,
             camera,
             directional_light,
+            auto_rotate: AutoRotate::default(),
             scene: scene.clone(),
+            depth_bias: config.graphics.depth_bias,
+            target_aspect: config.graphics.target_aspect,
+            frames_in_flight,
+            pipelined_frames,
+            in_flight_command_buffers: VecDeque::new(),
+            pending_color_clears: MAX_DRAWABLE_COUNT,
+            total_time: 0.0,
+            gui_manager,
+            gui_painter,
+            gui_enabled: true,
+            // CAMetalLayer 默认开启 displaySyncEnabled，按垂直同步逐帧显示，等价于 FIFO
+            present_stats: PresentStats { present_mode: PresentMode::Fifo, ..Default::default() },
+            debug_names,
         })
     }
 
@@ -193,7 +237,10 @@ impl Renderer {
             window_size.height as f64
         ));
         
-        self.camera.set_aspect(window_size.width as f32 / window_size.height as f32);
+        self.camera.set_aspect(
+            ViewportRect::letterbox(window_size.width, window_size.height, self.target_aspect).aspect(),
+        );
+        self.pending_color_clears = MAX_DRAWABLE_COUNT;
 
         // Recreate depth texture
         let depth_desc = TextureDescriptor::new();
@@ -204,16 +251,49 @@ impl Renderer {
         self.depth_texture = self.backend.device.new_texture(&depth_desc);
     }
 
-    pub fn draw(&mut self) -> Result<()> {
+    pub fn draw(&mut self) -> Result<FrameStats> {
+        let start = Instant::now();
+        let mut stats = FrameStats::default();
+
+        // 关闭每帧清除时加载 drawable 原有内容（Metal 不保证保留，通常为上一次呈现的结果）
+        let color_load_action = if self.scene.clear_color_enabled || self.pending_color_clears > 0 {
+            MTLLoadAction::Clear
+        } else {
+            MTLLoadAction::Load
+        };
+        self.pending_color_clears = self.pending_color_clears.saturating_sub(1);
+        let manual_srgb = !is_srgb_format(self.backend.pixel_format);
+
+        let gui_frame = if self.gui_enabled {
+            self.gui_manager.update(self.backend.window());
+            let frame = self.gui_manager.end_frame(self.backend.window());
+            self.gui_painter.update_textures(&self.backend.device, &frame);
+            Some(frame)
+        } else {
+            None
+        };
+
         autoreleasepool(|| {
-            if let Some(drawable) = self.backend.layer.next_drawable() {
+            // 没有空闲的 drawable 时阻塞到 GPU 显示完之前的帧，计入 GPU 等待
+            let wait_start = Instant::now();
+            let drawable = self.backend.layer.next_drawable();
+            stats.record_wait(wait_start);
+            if let Some(drawable) = drawable {
                 let render_pass_descriptor = RenderPassDescriptor::new();
                 
                 // Color Attachment - use scene clear color
                 let color_attachment = render_pass_descriptor.color_attachments().object_at(0).unwrap();
                 color_attachment.set_texture(Some(drawable.texture()));
-                color_attachment.set_load_action(MTLLoadAction::Clear);
-                let cc = self.scene.clear_color;
+                color_attachment.set_load_action(color_load_action);
+                // Metal 无法只清除视口区域，信箱模式下整个 drawable 清除为黑色
+                let window_size = self.backend.window().inner_size();
+                let viewport_rect = ViewportRect::letterbox(window_size.width, window_size.height, self.target_aspect);
+                let cc = if viewport_rect.covers(window_size.width, window_size.height) {
+                    self.scene.clear_color_at(self.total_time)
+                } else {
+                    LETTERBOX_COLOR
+                };
+                let cc = encode_output_color(cc, manual_srgb);
                 color_attachment.set_clear_color(MTLClearColor::new(cc[0] as f64, cc[1] as f64, cc[2] as f64, cc[3] as f64));
                 color_attachment.set_store_action(MTLStoreAction::Store);
 
@@ -230,7 +310,6 @@ impl Renderer {
                 encoder.set_render_pipeline_state(&self.pipeline_state);
                 
                 // Create Uniforms - following Vulkan implementation
-                let model = self.scene.model.transform.to_matrix();
                 let view = self.camera.view_matrix();
                 let projection_gl = self.camera.proj_matrix();
 
@@ -243,34 +322,22 @@ impl Renderer {
                 let light_color_intensity = self.directional_light.color.with_intensity(self.directional_light.intensity);
                 let cam_pos = self.camera.transform().position;
                 
-                let uniforms = Uniforms {
-                    model,
-                    view,
-                    projection,
-                    light_dir: [light_direction.x, light_direction.y, light_direction.z, 0.0],
-                    light_color: [
-                        light_color_intensity[0],
-                        light_color_intensity[1],
-                        light_color_intensity[2],
-                        self.directional_light.intensity,
-                    ],
-                    camera_pos: [cam_pos.x, cam_pos.y, cam_pos.z, 1.0],
-                };
-
-                encoder.set_vertex_bytes(1, std::mem::size_of::<Uniforms>() as u64, &uniforms as *const _ as *const _);
-                encoder.set_fragment_bytes(1, std::mem::size_of::<Uniforms>() as u64, &uniforms as *const _ as *const _);
-
                 // Viewport is critical!
-                let window_size = self.backend.window().inner_size();
                 let viewport = MTLViewport {
-                    originX: 0.0,
-                    originY: 0.0,
-                    width: window_size.width as f64,
-                    height: window_size.height as f64,
+                    originX: viewport_rect.x as f64,
+                    originY: viewport_rect.y as f64,
+                    width: viewport_rect.width as f64,
+                    height: viewport_rect.height as f64,
                     znear: 0.0,
                     zfar: 1.0,
                 };
                 encoder.set_viewport(viewport);
+                encoder.set_scissor_rect(MTLScissorRect {
+                    x: viewport_rect.x as u64,
+                    y: viewport_rect.y as u64,
+                    width: viewport_rect.width as u64,
+                    height: viewport_rect.height as u64,
+                });
 
                 // Culling and Winding
                 encoder.set_cull_mode(MTLCullMode::Back);
@@ -280,40 +347,151 @@ impl Renderer {
                 
                 // Set Depth Stencil State (created once during initialization)
                 encoder.set_depth_stencil_state(&self.depth_stencil_state);
+                if self.depth_bias.is_enabled() {
+                    encoder.set_depth_bias(
+                        self.depth_bias.constant as f32,
+                        self.depth_bias.slope_scale,
+                        self.depth_bias.clamp,
+                    );
+                }
+
+                // Draw Indexed - 每个模型设置各自的 Uniforms 后绘制按距离选择的 LOD 索引范围
+                let index_size = match self.index_type {
+                    MTLIndexType::UInt16 => std::mem::size_of::<u16>(),
+                    MTLIndexType::UInt32 => std::mem::size_of::<u32>(),
+                } as u64;
+                let lod_levels = select_lods(&self.models, &self.scene.models, &cam_pos);
+                for ((model_mesh, model_config), &lod_level) in self.models.iter().zip(&self.scene.models).zip(&lod_levels) {
+                    let model = model_config.transform.to_matrix();
+                    let uniforms = Uniforms {
+                        model,
+                        view,
+                        projection,
+                        light_dir: [light_direction.x, light_direction.y, light_direction.z, 0.0],
+                        light_color: [
+                            light_color_intensity[0],
+                            light_color_intensity[1],
+                            light_color_intensity[2],
+                            self.directional_light.intensity,
+                        ],
+                        camera_pos: [cam_pos.x, cam_pos.y, cam_pos.z, manual_srgb as u32 as f32],
+                        ambient: self.scene.ambient_light(),
+                        tint: model_config.tint,
+                        normal_matrix: normal_matrix(&model),
+                    };
+
+                    encoder.set_vertex_bytes(1, std::mem::size_of::<Uniforms>() as u64, &uniforms as *const _ as *const _);
+                    encoder.set_fragment_bytes(1, std::mem::size_of::<Uniforms>() as u64, &uniforms as *const _ as *const _);
+
+                    let indices = model_mesh.lod_range(lod_level);
+                    encoder.draw_indexed_primitives(
+                        MTLPrimitiveType::Triangle,
+                        indices.len() as u64,
+                        self.index_type,
+                        &self.index_buffer,
+                        indices.start as u64 * index_size,
+                    );
+                    stats.record_draw(indices.len() as u32);
+                }
 
-                // Draw Indexed
-                encoder.draw_indexed_primitives(
-                    MTLPrimitiveType::Triangle,
-                    self.index_count,
-                    MTLIndexType::UInt32,
-                    &self.index_buffer,
-                    0
-                );
+                if let Some(frame) = &gui_frame {
+                    self.gui_painter.draw(
+                        &self.backend.device,
+                        encoder,
+                        frame,
+                        [window_size.width, window_size.height],
+                    );
+                }
 
                 encoder.end_encoding();
 
                 command_buffer.present_drawable(drawable);
                 command_buffer.commit();
+                self.present_stats.presented_frames += 1;
+                // 限制在途帧数：等到只剩 frames_in_flight - 1 帧未完成（低延迟模式下即等待刚提交的这一帧）；
+                // 同一队列的命令缓冲按顺序完成，只需等待需要完成的最后一个
+                self.in_flight_command_buffers.push_back(command_buffer.to_owned());
+                let excess =
+                    (self.in_flight_command_buffers.len() + 1).saturating_sub(self.frames_in_flight as usize);
+                if let Some(wait_for) = self.in_flight_command_buffers.drain(..excess).next_back() {
+                    let wait_start = Instant::now();
+                    wait_for.wait_until_completed();
+                    stats.record_wait(wait_start);
+                }
+            } else {
+                // 等待 drawable 超时，这一帧没有呈现
+                self.present_stats.dropped_frames += 1;
             }
         });
-        Ok(())
+        stats.finish(start);
+
+        if let Some(frame) = &gui_frame {
+            self.gui_painter.free_textures(frame);
+            self.gui_manager.state_mut().update_frame_stats(&stats);
+            self.gui_manager.state_mut().present_stats = Some(self.present_stats);
+            let packet = self.gui_manager.state().to_packet();
+            self.apply_gui_packet(&packet);
+        }
+        Ok(stats)
     }
 
     pub fn update(&mut self, input_system: &mut InputSystem, delta_time: f32) {
-        // Update camera based on input system state
+        self.auto_rotate.advance(delta_time);
+        self.gui_manager.state_mut().mouse_captured = Some(input_system.is_captured());
+        // Update light and camera based on input system state
+        if input_system.update_light(&mut self.directional_light, delta_time) {
+            // 同步到 GUI 状态，否则每帧应用 GUI 参数时会被覆盖
+            let direction = self.directional_light.direction;
+            self.gui_manager.state_mut().light_direction = [direction.x, direction.y, direction.z];
+        }
         input_system.update_camera(&mut self.camera, delta_time);
     }
 
+    /// 仅更新相机（暂停时使用）
+    pub fn update_camera(&mut self, input_system: &mut InputSystem, delta_time: f32) {
+        self.gui_manager.state_mut().mouse_captured = Some(input_system.is_captured());
+        input_system.update_camera(&mut self.camera, delta_time);
+    }
+
+    /// 处理 GUI 事件，返回 true 表示事件被 GUI 消费
+    pub fn handle_gui_event(&mut self, event: &winit::event::WindowEvent) -> bool {
+        self.gui_enabled && self.gui_manager.handle_event(self.backend.window(), event)
+    }
+
     pub fn window(&self) -> &Window {
         self.backend.window()
     }
 
+    /// 从磁盘重新加载场景模型并替换顶点和索引缓冲，之前加载成功的模型失败时保留当前网格
+    ///
+    /// 已提交的命令缓冲持有旧缓冲的引用，GPU 用完后才释放。
+    pub fn reload_models(&mut self) -> Result<()> {
+        let scene_mesh = SceneMesh::load(&self.scene.models, self.scene.fallback_primitive, false, self.mesh_cache);
+        scene_mesh.check_reload(&self.fallback_models, &self.scene.models)?;
+
+        let (vertex_buffer, index_buffer, index_type) = create_mesh_buffers(&self.backend.device, &scene_mesh, self.debug_names, &self.scene);
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        self.index_type = index_type;
+        self.models = scene_mesh.models;
+        self.fallback_models = scene_mesh.fallback_models;
+        Ok(())
+    }
+
     pub fn apply_gui_packet(&mut self, packet: &GuiStatePacket) {
         // Update scene configuration from GUI
         self.scene.clear_color = packet.clear_color;
-        self.scene.model.transform.position = packet.model_position;
-        self.scene.model.transform.rotation = packet.model_rotation;
-        self.scene.model.transform.scale = packet.model_scale;
+        self.scene.clear_color_enabled = packet.clear_color_enabled != 0;
+        self.scene.clear_color_cycle = packet.clear_color_cycle != 0;
+        self.scene.ambient_color = packet.ambient_color;
+        self.scene.ambient_intensity = packet.ambient_intensity;
+        self.scene.exposure = packet.exposure;
+        packet.apply_model_transforms(&mut self.scene.models);
+        self.auto_rotate.enabled = packet.auto_rotate != 0;
+        self.auto_rotate.speed = packet.auto_rotate_speed;
+        self.auto_rotate.apply(&mut self.scene.models);
+        packet.apply_model_tints(&mut self.scene.models);
+        self.frames_in_flight = packet.frames_in_flight(self.pipelined_frames);
 
         // Update light parameters
         self.directional_light.intensity = packet.light_intensity;
@@ -324,15 +502,7 @@ impl Renderer {
         )
         .normalize();
 
-        // Update camera FOV if changed
-        if (self.camera.fov_y() - packet.camera_fov * PI / 180.0).abs() > 0.01 {
-            self.camera.set_lens(
-                packet.camera_fov * PI / 180.0,
-                self.camera.aspect(),
-                packet.camera_near,
-                packet.camera_far,
-            );
-        }
+        packet.apply_camera_lens(&mut self.camera);
     }
 }
 
@@ -348,6 +518,10 @@ impl crate::renderer::backend_trait::RenderBackend for Renderer {
     }
 
     fn draw(&mut self) -> crate::core::error::Result<()> {
+        self.draw().map(|_| ())
+    }
+
+    fn draw_with_stats(&mut self) -> crate::core::error::Result<FrameStats> {
         self.draw()
     }
 
@@ -355,9 +529,73 @@ impl crate::renderer::backend_trait::RenderBackend for Renderer {
         self.update(input_system, delta_time)
     }
 
+    fn tick(&mut self, event: &crate::core::event::TickEvent) {
+        self.total_time = event.total_time;
+    }
+
+    fn update_camera(&mut self, input_system: &mut InputSystem, delta_time: f32) {
+        self.update_camera(input_system, delta_time)
+    }
+
+    fn gui_packet(&self) -> Option<GuiStatePacket> {
+        self.gui_enabled.then(|| self.gui_manager.state().to_packet())
+    }
+
     fn apply_gui_packet(&mut self, packet: &GuiStatePacket) {
         self.apply_gui_packet(packet)
     }
 
-    // handle_gui_event 浣跨敤榛樿瀹炵幇锛堣繑鍥?false锛?
+    fn present_stats(&self) -> PresentStats {
+        self.present_stats
+    }
+
+    fn handle_gui_event(&mut self, event: &winit::event::WindowEvent) -> bool {
+        self.handle_gui_event(event)
+    }
+
+    fn set_gui_enabled(&mut self, enabled: bool) {
+        self.gui_enabled = enabled;
+    }
+
+    fn reload_models(&mut self) -> crate::core::error::Result<()> {
+        self.reload_models()
+    }
+
+    fn wait_idle(&self) -> crate::core::error::Result<()> {
+        self.backend.wait_idle()
+    }
+}
+
+/// 为场景网格创建顶点和索引缓冲，顶点数不超过 65535 时使用 16 位索引
+fn create_mesh_buffers(
+    device: &Device,
+    scene_mesh: &SceneMesh,
+    debug_names: DebugNames,
+    scene: &SceneConfig,
+) -> (Buffer, Buffer, MTLIndexType) {
+    let vertices = &scene_mesh.vertices;
+    let vertex_buffer = device.new_buffer_with_data(
+        vertices.as_ptr() as *const _,
+        (vertices.len() * std::mem::size_of::<MyVertex>()) as u64,
+        MTLResourceOptions::CPUCacheModeDefaultCache,
+    );
+
+    let index_data = scene_mesh.index_data();
+    let index_type = match index_data.width() {
+        IndexWidth::U16 => MTLIndexType::UInt16,
+        IndexWidth::U32 => MTLIndexType::UInt32,
+    };
+    let index_buffer = device.new_buffer_with_data(
+        index_data.as_bytes().as_ptr() as *const _,
+        index_data.as_bytes().len() as u64,
+        MTLResourceOptions::CPUCacheModeDefaultCache,
+    );
+
+    if let Some(label) = debug_names.scene("Vertex Buffer", scene) {
+        vertex_buffer.set_label(&label);
+    }
+    if let Some(label) = debug_names.scene("Index Buffer", scene) {
+        index_buffer.set_label(&label);
+    }
+    (vertex_buffer, index_buffer, index_type)
 }
@@ -0,0 +1,55 @@
+#include <metal_stdlib>
+using namespace metal;
+
+// GUI pass: egui vertices are in logical points with the origin at the top left.
+// Blending happens in gamma space as egui expects; sRGB drawables encode on write,
+// so the output is converted back to linear for them.
+
+struct GuiConstants {
+    float2 screenSize;
+    uint manualSrgb;    // non-zero when the drawable is UNORM
+};
+
+struct GuiVertexIn {
+    float2 position [[attribute(0)]];
+    float2 uv [[attribute(1)]];
+    float4 color [[attribute(2)]];  // sRGB gamma, premultiplied alpha
+};
+
+struct GuiVertexOut {
+    float4 position [[position]];
+    float2 uv;
+    float4 color;
+};
+
+vertex GuiVertexOut gui_vertex_main(GuiVertexIn in [[stage_in]],
+                                    constant GuiConstants &constants [[buffer(1)]]) {
+    GuiVertexOut out;
+    float2 ndc = 2.0 * in.position / constants.screenSize - 1.0;
+    out.position = float4(ndc.x, -ndc.y, 0.0, 1.0);
+    out.uv = in.uv;
+    out.color = in.color;
+    return out;
+}
+
+float3 gamma_from_linear(float3 rgb) {
+    float3 lower = rgb * 12.92;
+    float3 higher = 1.055 * pow(rgb, float3(1.0 / 2.4)) - 0.055;
+    return select(higher, lower, rgb < float3(0.0031308));
+}
+
+float3 linear_from_gamma(float3 srgb) {
+    float3 lower = srgb / 12.92;
+    float3 higher = pow((srgb + 0.055) / 1.055, float3(2.4));
+    return select(higher, lower, srgb < float3(0.04045));
+}
+
+fragment float4 gui_fragment_main(GuiVertexOut in [[stage_in]],
+                                  texture2d<float> guiTexture [[texture(0)]],
+                                  sampler guiSampler [[sampler(0)]],
+                                  constant GuiConstants &constants [[buffer(0)]]) {
+    // The texture is sRGB, so the sample is linear and has to be converted back to gamma
+    float4 texel = guiTexture.sample(guiSampler, in.uv);
+    float4 color = in.color * float4(gamma_from_linear(texel.rgb), texel.a);
+    return constants.manualSrgb != 0 ? color : float4(linear_from_gamma(color.rgb), color.a);
+}
@@ -20,7 +20,10 @@ struct Uniforms {
     float4x4 projection;
     float4 lightDir;
     float4 lightColor;
-    float4 cameraPos;
+    float4 cameraPos;   // xyz: position, w: non-zero when the drawable is UNORM
+    float4 ambient;     // rgb: ambient color * intensity, a: exposure
+    float4 tint;        // per-model color tint
+    float4x4 normalMatrix; // inverse-transpose of the model matrix
 };
 
 vertex VertexOut vertex_main(VertexIn in [[stage_in]],
@@ -29,19 +32,32 @@ vertex VertexOut vertex_main(VertexIn in [[stage_in]],
     float4 worldPos = uniforms.model * float4(in.position, 1.0);
     out.position = uniforms.projection * uniforms.view * worldPos;
     out.worldPos = worldPos.xyz;
-    out.normal = (uniforms.model * float4(in.normal, 0.0)).xyz;
+    // The normal matrix keeps normals perpendicular under non-uniform scale; renormalized per fragment
+    out.normal = (uniforms.normalMatrix * float4(in.normal, 0.0)).xyz;
     out.color = float4(in.color, 1.0);
     return out;
 }
 
+// sRGB drawables encode on write; UNORM drawables are encoded here
+float3 encodeOutput(float3 color, float manualSrgb) {
+    if (manualSrgb == 0.0) {
+        return color;
+    }
+    float3 c = saturate(color);
+    return select(1.055 * pow(c, float3(1.0 / 2.4)) - 0.055, c * 12.92, c <= float3(0.0031308));
+}
+
+// Directional Blinn-Phong lighting shared with the other backends, expanded on load
+#include "../../shaders/directional_light.inc"
+
 fragment float4 fragment_main(VertexOut in [[stage_in]],
                               constant Uniforms &uniforms [[buffer(1)]]) {
     float3 N = normalize(in.normal);
-    float3 L = normalize(-uniforms.lightDir.xyz);  // Negate: lightDir points FROM light, we need direction TO light
-    
-    // Simple Lambertian
-    float diff = max(dot(N, L), 0.2);
-    float3 diffuse = diff * uniforms.lightColor.rgb * in.color.rgb;
+    float3 V = normalize(uniforms.cameraPos.xyz - in.worldPos);
+    float3 lighting = directional_light(N, V, uniforms.lightDir.xyz, uniforms.lightColor.rgb, uniforms.ambient.rgb);
+
+    // Exposure scales the lit color; without tonemapping values above 1 are clamped on output
+    float3 finalColor = lighting * in.color.rgb * uniforms.ambient.a * uniforms.tint.rgb;
     
-    return float4(diffuse, 1.0);
+    return float4(encodeOutput(finalColor, uniforms.cameraPos.w), 1.0);
 }
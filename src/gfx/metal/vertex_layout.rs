@@ -0,0 +1,32 @@
+//! 顶点布局到 Metal 顶点描述符的转换
+
+use metal::{MTLVertexFormat, MTLVertexStepFunction, VertexDescriptor, VertexDescriptorRef};
+
+use crate::renderer::resources::vertex_layout::{VertexFormat, VertexLayout};
+
+/// 按布局生成从顶点缓冲 `buffer_index` 读取的顶点描述符，属性序号与布局中的着色器位置一致
+pub fn vertex_descriptor(buffer_index: u64, layout: &VertexLayout) -> &'static VertexDescriptorRef {
+    let descriptor = VertexDescriptor::new();
+    for attribute in layout.attributes() {
+        let desc = descriptor.attributes().object_at(attribute.location as u64).unwrap();
+        desc.set_format(vertex_format(attribute.format));
+        desc.set_offset(attribute.offset as u64);
+        desc.set_buffer_index(buffer_index);
+    }
+    let buffer_layout = descriptor.layouts().object_at(buffer_index).unwrap();
+    buffer_layout.set_stride(layout.stride() as u64);
+    buffer_layout.set_step_rate(1);
+    buffer_layout.set_step_function(MTLVertexStepFunction::PerVertex);
+    descriptor
+}
+
+fn vertex_format(format: VertexFormat) -> MTLVertexFormat {
+    match format {
+        VertexFormat::Float32 => MTLVertexFormat::Float,
+        VertexFormat::Float32x2 => MTLVertexFormat::Float2,
+        VertexFormat::Float32x3 => MTLVertexFormat::Float3,
+        VertexFormat::Float32x4 => MTLVertexFormat::Float4,
+        VertexFormat::Uint16x4 => MTLVertexFormat::UShort4,
+        VertexFormat::Unorm8x4 => MTLVertexFormat::UChar4Normalized,
+    }
+}
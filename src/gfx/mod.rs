@@ -6,9 +6,16 @@
 //! - wgpu：跨平台的高层图形抽象（支持 Vulkan、Metal、DX12、OpenGL）
 //!
 //! 所有后端都实现了统一的 `GraphicsBackend` trait，
-//! 确保可以在不同的图形 API 之间无缝切换。
+//! 确保可以在不同的图形 API 之间无缝切换。各后端场景着色器共用的光照函数放在 `shaders/` 下，
+//! 由 `shader_preprocessor` 展开。
 
 pub mod backend;
+pub mod capabilities;
+pub mod debug_name;
+pub mod letterbox;
+pub mod shader_preprocessor;
+pub mod surface_format;
+pub mod window;
 pub mod vulkan;
 #[cfg(target_os = "windows")]
 pub mod dx12;
@@ -16,6 +23,7 @@ pub mod wgpu;
 pub mod metal;
 
 pub use backend::GraphicsBackend;
+pub use capabilities::DeviceCapabilities;
 pub use vulkan::VulkanContext;
 #[cfg(target_os = "windows")]
 pub use dx12::Dx12Context;
@@ -0,0 +1,163 @@
+//! 着色器预处理器
+//!
+//! 各后端着色器共用的光照函数只写一份，放在 `src/gfx/shaders/` 下，着色器中用
+//! `#include "../../shaders/<文件名>"` 引用（路径相对着色器文件，按文件名匹配），加载时由 [`preprocess`]
+//! 展开。共享片段用 HLSL / MSL 的 C 风格子集书写，HLSL 和 MSL 原样插入，WGSL 逐行转换函数定义、
+//! 局部常量声明和类型名。每个片段在一个着色器中只展开一次，尖括号形式的 `#include <...>`（如 MSL 标准库）保持不变。
+//!
+//! Vulkan 的 GLSL 在构建时由 shaderc 编译，同样用 `#include` 引用共享片段，
+//! 并在包含前用 `#define` 把 float2/3/4 映射为 vec2/3/4。
+
+use crate::core::error::{GraphicsError, Result};
+
+/// 共享片段（文件名, 内容）
+const SNIPPETS: [(&str, &str); 1] = [("directional_light.inc", include_str!("shaders/directional_light.inc"))];
+
+/// 共享片段中的类型名及其 WGSL 写法
+const WGSL_TYPES: [(&str, &str); 4] = [
+    ("float", "f32"),
+    ("float2", "vec2<f32>"),
+    ("float3", "vec3<f32>"),
+    ("float4", "vec4<f32>"),
+];
+
+/// 着色器语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaderLanguage {
+    Hlsl,
+    Msl,
+    Wgsl,
+}
+
+/// 展开着色器源码中的 `#include "..."`，引用未知片段时返回错误
+pub fn preprocess(source: &str, language: ShaderLanguage) -> Result<String> {
+    let mut output = String::with_capacity(source.len());
+    let mut included = Vec::new();
+    expand(source, language, &mut included, &mut output)?;
+    Ok(output)
+}
+
+/// 逐行复制源码，遇到包含指令时插入转换后的片段（片段中的包含递归展开）
+fn expand(source: &str, language: ShaderLanguage, included: &mut Vec<&'static str>, output: &mut String) -> Result<()> {
+    for line in source.lines() {
+        let Some(path) = include_path(line) else {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        };
+
+        let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+        let &(name, snippet) = SNIPPETS
+            .iter()
+            .find(|(name, _)| *name == file_name)
+            .ok_or_else(|| GraphicsError::ShaderCompilation(format!("Unknown shader include \"{}\"", path)))?;
+        if included.contains(&name) {
+            continue;
+        }
+        included.push(name);
+        expand(&translate(snippet, language), language, included, output)?;
+    }
+    Ok(())
+}
+
+/// 引号形式的包含指令中的路径
+fn include_path(line: &str) -> Option<&str> {
+    let path = line.trim().strip_prefix("#include")?.trim();
+    path.strip_prefix('"')?.strip_suffix('"')
+}
+
+/// 把共享片段转换为目标语言
+fn translate(snippet: &str, language: ShaderLanguage) -> String {
+    match language {
+        ShaderLanguage::Hlsl | ShaderLanguage::Msl => snippet.to_string(),
+        ShaderLanguage::Wgsl => snippet.lines().map(wgsl_line).collect::<Vec<_>>().join("\n"),
+    }
+}
+
+/// 转换一行到 WGSL：`T name(T a, ...) {` 变为 `fn name(a: T, ...) -> T {`，
+/// `T name = expr;` 变为 `let name = expr;`，其余行只替换类型名（构造函数和类型转换）
+fn wgsl_line(line: &str) -> String {
+    let body = line.trim_start();
+    let indent = &line[..line.len() - body.len()];
+    if body.starts_with("//") {
+        return line.to_string();
+    }
+
+    if let Some((ty, rest)) = body.split_once(' ').and_then(|(ty, rest)| Some((wgsl_type(ty)?, rest))) {
+        if let Some((name, params)) = rest.strip_suffix(") {").and_then(|rest| rest.split_once('(')) {
+            let params = params
+                .split(',')
+                .filter_map(|param| {
+                    let (param_ty, param_name) = param.trim().split_once(' ')?;
+                    Some(format!("{}: {}", param_name.trim(), wgsl_type(param_ty).unwrap_or(param_ty)))
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            return format!("{}fn {}({}) -> {} {{", indent, name, params, ty);
+        }
+        if let Some((name, value)) = rest.split_once(" = ") {
+            return format!("{}let {} = {}", indent, name, replace_types(value));
+        }
+    }
+
+    replace_types(line)
+}
+
+fn wgsl_type(name: &str) -> Option<&'static str> {
+    WGSL_TYPES.iter().find(|(ty, _)| *ty == name).map(|&(_, wgsl)| wgsl)
+}
+
+/// 按标识符边界替换类型名（数字字面量整体跳过）
+fn replace_types(text: &str) -> String {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(is_word) {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let number = rest.starts_with(|c: char| c.is_ascii_digit());
+        let end = rest.find(|c: char| !(is_word(c) || number && c == '.')).unwrap_or(rest.len());
+        let token = &rest[..end];
+        output.push_str(wgsl_type(token).unwrap_or(token));
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wgsl_translation() {
+        let source = "#include \"../../shaders/directional_light.inc\"\n#include \"directional_light.inc\"\nfn main() {}\n";
+        let wgsl = preprocess(source, ShaderLanguage::Wgsl).unwrap();
+
+        assert!(wgsl.contains(
+            "fn blinn_phong(N: vec3<f32>, L: vec3<f32>, V: vec3<f32>, shininess: f32) -> vec2<f32> {"
+        ));
+        assert!(wgsl.contains("    let H = normalize(L + V);"));
+        assert!(wgsl.contains("* f32(diff > 0.0);"));
+        assert!(wgsl.contains("return vec2<f32>(diff, spec);"));
+        // 每个片段只展开一次，宿主源码保留
+        assert_eq!(wgsl.matches("fn directional_light(").count(), 1);
+        assert!(wgsl.ends_with("fn main() {}\n"));
+        assert!(wgsl.lines().all(|line| include_path(line).is_none()));
+        // 只替换完整的类型名，不影响数字和其他标识符
+        assert_eq!(replace_types("float3(1.0, x_float3, float)"), "vec3<f32>(1.0, x_float3, f32)");
+    }
+
+    #[test]
+    fn test_hlsl_and_msl_keep_snippet_and_system_includes() {
+        let source = "#include <metal_stdlib>\n#include \"../../shaders/directional_light.inc\"\n";
+        let msl = preprocess(source, ShaderLanguage::Msl).unwrap();
+        assert!(msl.starts_with("#include <metal_stdlib>\n"));
+        assert!(msl.contains("float2 blinn_phong(float3 N, float3 L, float3 V, float shininess) {"));
+
+        let hlsl = preprocess(source, ShaderLanguage::Hlsl).unwrap();
+        assert_eq!(hlsl, msl);
+
+        assert!(preprocess("#include \"missing.inc\"\n", ShaderLanguage::Wgsl).is_err());
+    }
+}
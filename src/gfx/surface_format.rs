@@ -0,0 +1,107 @@
+//! 交换链格式选择
+//!
+//! 光照统一在线性空间计算。交换链优先使用 sRGB 格式，写入时由硬件自动编码；
+//! 选中 UNORM 格式时（配置指定或表面不支持 sRGB）由着色器和 CPU 端手动编码，
+//! 保证不同平台上的颜色一致。
+
+use std::fmt::Debug;
+
+use tracing::{info, warn};
+
+use crate::core::config::SurfaceFormat;
+
+/// 按配置从表面支持的格式中选择交换链格式
+///
+/// `known` 把后端格式映射为配置中可以指定的格式（其他格式返回 `None`），
+/// `is_srgb` 判断格式写入时是否自动做 sRGB 编码。指定的格式不可用时列出表面支持的格式并回退到自动选择：
+/// 第一个 sRGB 格式，没有时使用表面的首选（第一个）格式。没有可用格式时返回 `None`。
+pub fn select_surface_format<F: Copy + Debug>(
+    available: &[F],
+    requested: SurfaceFormat,
+    known: impl Fn(F) -> Option<SurfaceFormat>,
+    is_srgb: impl Fn(F) -> bool,
+) -> Option<F> {
+    let requested_format = match requested {
+        SurfaceFormat::Auto => None,
+        requested => {
+            let format = available.iter().copied().find(|&format| known(format) == Some(requested));
+            if format.is_none() {
+                warn!(
+                    "Surface format {:?} is not supported (available: {:?}), selecting automatically",
+                    requested, available
+                );
+            }
+            format
+        }
+    };
+    let format = requested_format
+        .or_else(|| available.iter().copied().find(|&format| is_srgb(format)))
+        .or_else(|| available.first().copied())?;
+
+    info!(
+        "Surface format selected: {:?} ({})",
+        format,
+        if is_srgb(format) { "hardware sRGB encoding" } else { "shader sRGB encoding" }
+    );
+    Some(format)
+}
+
+/// 线性值编码为 sRGB（输入先截断到 [0, 1]）
+pub fn linear_to_srgb(value: f32) -> f32 {
+    let value = value.clamp(0.0, 1.0);
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// 写入 UNORM 交换链的颜色需要手动编码（alpha 不变）
+pub fn encode_output_color(color: [f32; 4], manual_srgb: bool) -> [f32; 4] {
+    if manual_srgb {
+        [linear_to_srgb(color[0]), linear_to_srgb(color[1]), linear_to_srgb(color[2]), color[3]]
+    } else {
+        color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known(format: &'static str) -> Option<SurfaceFormat> {
+        match format {
+            "bgra8_unorm" => Some(SurfaceFormat::Bgra8Unorm),
+            "bgra8_srgb" => Some(SurfaceFormat::Bgra8Srgb),
+            _ => None,
+        }
+    }
+
+    fn is_srgb(format: &'static str) -> bool {
+        format.ends_with("srgb")
+    }
+
+    #[test]
+    fn test_srgb_format_preferred_unless_overridden() {
+        let available = ["rgb10a2_unorm", "bgra8_unorm", "bgra8_srgb"];
+        let select = |requested| select_surface_format(&available, requested, known, is_srgb);
+
+        assert_eq!(select(SurfaceFormat::Auto), Some("bgra8_srgb"));
+        assert_eq!(select(SurfaceFormat::Bgra8Unorm), Some("bgra8_unorm"));
+        // 不支持的格式回退到自动选择
+        assert_eq!(select(SurfaceFormat::Rgba8Srgb), Some("bgra8_srgb"));
+        // 没有 sRGB 格式时使用首选格式
+        assert_eq!(select_surface_format(&available[..2], SurfaceFormat::Auto, known, is_srgb), Some("rgb10a2_unorm"));
+        assert_eq!(select_surface_format(&[], SurfaceFormat::Auto, known, is_srgb), None);
+    }
+
+    #[test]
+    fn test_linear_to_srgb() {
+        assert_eq!(linear_to_srgb(0.0), 0.0);
+        assert!((linear_to_srgb(1.0) - 1.0).abs() < 1e-6);
+        assert!((linear_to_srgb(0.5) - 0.7354).abs() < 1e-3);
+        assert_eq!(linear_to_srgb(2.0), linear_to_srgb(1.0));
+        assert_eq!(encode_output_color([0.5, 0.5, 0.5, 0.5], false), [0.5; 4]);
+        assert_eq!(encode_output_color([0.0, 0.0, 0.0, 0.5], true), [0.0, 0.0, 0.0, 0.5]);
+    }
+}
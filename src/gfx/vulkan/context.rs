@@ -16,13 +16,17 @@
 //! 5. 鍒涘缓鍐呭瓨鍜屽懡浠ょ紦鍐插垎閰嶅櫒
 
 use std::sync::Arc;
-use tracing::{debug, info};
+use tracing::{debug, error, info, trace, warn};
 use vulkano::command_buffer::allocator::{
     StandardCommandBufferAllocator, StandardCommandBufferAllocatorCreateInfo,
 };
 use vulkano::descriptor_set::allocator::StandardDescriptorSetAllocator;
 use vulkano::device::physical::PhysicalDeviceType;
-use vulkano::device::{Device, DeviceCreateInfo, DeviceExtensions, Queue, QueueCreateInfo, QueueFlags};
+use vulkano::device::{Device, DeviceCreateInfo, DeviceExtensions, Features, Queue, QueueCreateInfo, QueueFlags};
+use vulkano::instance::debug::{
+    DebugUtilsMessageSeverity, DebugUtilsMessageType, DebugUtilsMessenger, DebugUtilsMessengerCallback,
+    DebugUtilsMessengerCreateInfo,
+};
 use vulkano::instance::{Instance, InstanceCreateInfo, InstanceExtensions};
 use vulkano::memory::allocator::StandardMemoryAllocator;
 use vulkano::swapchain::Surface;
@@ -32,7 +36,10 @@ use winit::window::{Window, WindowBuilder};
 use winit::dpi::LogicalSize;
 
 use crate::gfx::backend::GraphicsBackend;
+use crate::gfx::capabilities::DeviceCapabilities;
+use crate::gfx::window::place_window;
 use crate::core::Config;
+use crate::core::error::{GraphicsError, Result};
 
 /// Vulkan 鍥惧舰鍚庣
 ///
@@ -65,8 +72,13 @@ pub struct VulkanContext {
     pub command_buffer_allocator: StandardCommandBufferAllocator,
     /// 鎻忚堪绗﹂泦鍒嗛厤鍣?
     pub descriptor_allocator: StandardDescriptorSetAllocator,
+    /// 验证层消息回调，未启用验证层时为 `None`（需要与实例保持同样长的生命周期）
+    _debug_messenger: Option<DebugUtilsMessenger>,
 }
 
+/// Khronos 验证层名称
+const VALIDATION_LAYER: &str = "VK_LAYER_KHRONOS_validation";
+
 impl VulkanContext {
     /// 鍒涘缓鏂扮殑 Vulkan 鍚庣
     ///
@@ -102,6 +114,8 @@ impl VulkanContext {
         let library = VulkanLibrary::new().expect("Failed to load Vulkan library");
 
         // 2. 鍒涘缓 Vulkan 瀹炰緥锛坴ulkano_win 浼氳嚜鍔ㄥ鐞嗘墍闇€鐨勮〃闈㈡墿灞曪級
+        // 按配置启用验证层，未安装时跳过
+        let validation = config.graphics.validation && validation_layer_available(&library);
         let instance = Instance::new(
             library,
             InstanceCreateInfo {
@@ -111,8 +125,10 @@ impl VulkanContext {
                     khr_xlib_surface: cfg!(target_os = "linux"),
                     khr_wayland_surface: cfg!(target_os = "linux"),
                     mvk_macos_surface: cfg!(target_os = "macos"),
+                    ext_debug_utils: validation,
                     ..InstanceExtensions::empty()
                 },
+                enabled_layers: if validation { vec![VALIDATION_LAYER.to_string()] } else { Vec::new() },
                 ..Default::default()
             },
         )
@@ -122,11 +138,12 @@ impl VulkanContext {
         debug!("Vulkan instance created");
 
         // 3. 鍒涘缓绐楀彛鍜岃〃闈紙浣跨敤閰嶇疆涓殑绐楀彛鍙傛暟锛?
+        let window_builder = WindowBuilder::new()
+            .with_title(format!("{} [{}]", config.window.title, config.graphics.backend.name()))
+            .with_inner_size(LogicalSize::new(config.window.width, config.window.height))
+            .with_resizable(config.window.resizable);
         let window = Arc::new(
-            WindowBuilder::new()
-                .with_title(format!("{} [{}]", config.window.title, config.graphics.backend.name()))
-                .with_inner_size(LogicalSize::new(config.window.width, config.window.height))
-                .with_resizable(config.window.resizable)
+            place_window(window_builder, event_loop, &config.window)
                 .build(event_loop)
                 .expect("Failed to create window")
         );
@@ -271,10 +288,16 @@ impl VulkanContext {
         );
 
         // 6. 鍒涘缓閫昏緫璁惧鍜岄槦鍒?
+        // 线框和点填充模式需要 fill_mode_non_solid，设备支持时启用
+        let fill_mode_non_solid = physical_device.supported_features().fill_mode_non_solid;
         let (device, mut queues) = Device::new(
             physical_device,
             DeviceCreateInfo {
                 enabled_extensions: device_extensions,
+                enabled_features: Features {
+                    fill_mode_non_solid,
+                    ..Features::empty()
+                },
                 queue_create_infos: vec![QueueCreateInfo {
                     queue_family_index,
                     ..Default::default()
@@ -305,7 +328,9 @@ impl VulkanContext {
             info!("Vulkan Backend initialization complete");
         }
 
-        Self {
+        let debug_messenger = if validation { create_debug_messenger(instance.clone()) } else { None };
+
+        let context = Self {
             instance,
             device,
             queue,
@@ -314,8 +339,45 @@ impl VulkanContext {
             memory_allocator,
             command_buffer_allocator,
             descriptor_allocator,
+            _debug_messenger: debug_messenger,
+        };
+        context.report_capabilities().log();
+
+        context
+    }
+
+    /// 查询物理设备能力
+    pub fn report_capabilities(&self) -> DeviceCapabilities {
+        let physical_device = self.device.physical_device();
+        let properties = physical_device.properties();
+        let sample_counts = properties
+            .framebuffer_color_sample_counts
+            .intersection(properties.framebuffer_depth_sample_counts);
+
+        DeviceCapabilities {
+            backend: "Vulkan".to_string(),
+            device_name: properties.device_name.clone(),
+            api_version: match &properties.driver_info {
+                Some(driver) => format!("Vulkan {} ({})", properties.api_version, driver),
+                None => format!("Vulkan {}", properties.api_version),
+            },
+            max_msaa_samples: u32::from(sample_counts.max_count()),
+            max_anisotropy: properties.max_sampler_anisotropy,
+            timestamp_query: properties.timestamp_compute_and_graphics,
+            polygon_mode_line: self.device.enabled_features().fill_mode_non_solid,
+            polygon_mode_point: self.device.enabled_features().fill_mode_non_solid,
+            max_texture_dimension_2d: properties.max_image_dimension2_d,
+            max_uniform_buffer_size: properties.max_uniform_buffer_range as u64,
+            max_vertex_attributes: properties.max_vertex_input_attributes,
         }
     }
+
+    /// 等待设备上所有队列空闲
+    pub fn wait_idle(&self) -> Result<()> {
+        // SAFETY: 渲染器只在主线程提交命令，等待期间没有其他线程访问队列
+        unsafe { self.device.wait_idle() }
+            .map_err(|e| GraphicsError::CommandExecution(format!("Failed to wait for device idle: {:?}", e)).into())
+    }
 }
 
 impl GraphicsBackend for VulkanContext {
@@ -330,4 +392,67 @@ impl GraphicsBackend for VulkanContext {
     fn backend_name(&self) -> &str {
         "Vulkan"
     }
+
+    fn report_capabilities(&self) -> DeviceCapabilities {
+        self.report_capabilities()
+    }
+
+    fn wait_idle(&self) -> Result<()> {
+        self.wait_idle()
+    }
+}
+
+/// 验证层和 `VK_EXT_debug_utils` 扩展是否可用，不可用时输出警告
+fn validation_layer_available(library: &VulkanLibrary) -> bool {
+    let layer_installed = library
+        .layer_properties()
+        .map(|mut layers| layers.any(|layer| layer.name() == VALIDATION_LAYER))
+        .unwrap_or(false);
+    if !layer_installed {
+        warn!("{} is not installed, Vulkan validation is disabled", VALIDATION_LAYER);
+        return false;
+    }
+    if !library.supported_extensions().ext_debug_utils {
+        warn!("VK_EXT_debug_utils is not supported, Vulkan validation is disabled");
+        return false;
+    }
+
+    info!("Vulkan validation layer enabled");
+    true
+}
+
+/// 创建把验证层消息转发到日志的回调
+///
+/// 错误和警告分别输出为 error 和 warn，信息和详细消息降为 debug 和 trace。
+fn create_debug_messenger(instance: Arc<Instance>) -> Option<DebugUtilsMessenger> {
+    // SAFETY: 回调只写日志，不调用 Vulkan API
+    let callback = unsafe {
+        DebugUtilsMessengerCallback::new(|severity, message_type, data| {
+            let id = data.message_id_name.unwrap_or_default();
+            if severity.intersects(DebugUtilsMessageSeverity::ERROR) {
+                error!(target: "vulkan", ?message_type, id, "{}", data.message);
+            } else if severity.intersects(DebugUtilsMessageSeverity::WARNING) {
+                warn!(target: "vulkan", ?message_type, id, "{}", data.message);
+            } else if severity.intersects(DebugUtilsMessageSeverity::INFO) {
+                debug!(target: "vulkan", ?message_type, id, "{}", data.message);
+            } else {
+                trace!(target: "vulkan", ?message_type, id, "{}", data.message);
+            }
+        })
+    };
+
+    let create_info = DebugUtilsMessengerCreateInfo {
+        message_severity: DebugUtilsMessageSeverity::ERROR
+            | DebugUtilsMessageSeverity::WARNING
+            | DebugUtilsMessageSeverity::INFO
+            | DebugUtilsMessageSeverity::VERBOSE,
+        message_type: DebugUtilsMessageType::GENERAL
+            | DebugUtilsMessageType::VALIDATION
+            | DebugUtilsMessageType::PERFORMANCE,
+        ..DebugUtilsMessengerCreateInfo::user_callback(callback)
+    };
+
+    DebugUtilsMessenger::new(instance, create_info)
+        .map_err(|e| warn!("Failed to create Vulkan debug messenger: {:?}", e))
+        .ok()
 }
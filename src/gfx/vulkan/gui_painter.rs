@@ -0,0 +1,398 @@
+//! Vulkan GUI 绘制器
+//!
+//! 把 [`GuiManager::end_frame`](crate::gui::GuiManager::end_frame) 输出的 egui 网格绘制到场景所在的渲染通道中，
+//! 使 Vulkan 后端与 wgpu 一样显示内置控制面板。纹理以 sRGB 格式保存，片段着色器按 egui 的约定在 gamma
+//! 空间混合；交换链为 UNORM 格式时直接输出 gamma 颜色。
+//!
+//! 纹理上传使用复制命令，必须在渲染通道开始之前记录；网格绘制在场景绘制之后、渲染通道结束之前记录。
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use vulkano::buffer::{Buffer, BufferContents, BufferCreateInfo, BufferUsage};
+use vulkano::command_buffer::{
+    AutoCommandBufferBuilder, BufferImageCopy, CopyBufferToImageInfo, PrimaryAutoCommandBuffer,
+};
+use vulkano::descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet};
+use vulkano::format::Format;
+use vulkano::image::sampler::{Filter, Sampler, SamplerAddressMode, SamplerCreateInfo};
+use vulkano::image::view::ImageView;
+use vulkano::image::{Image, ImageCreateInfo, ImageType, ImageUsage};
+use vulkano::memory::allocator::{AllocationCreateInfo, MemoryTypeFilter};
+use vulkano::pipeline::graphics::color_blend::{
+    AttachmentBlend, BlendFactor, BlendOp, ColorBlendAttachmentState, ColorBlendState,
+};
+use vulkano::pipeline::graphics::depth_stencil::DepthStencilState;
+use vulkano::pipeline::graphics::input_assembly::InputAssemblyState;
+use vulkano::pipeline::graphics::rasterization::RasterizationState;
+use vulkano::pipeline::graphics::vertex_input::{Vertex, VertexDefinition};
+use vulkano::pipeline::graphics::viewport::{Scissor, Viewport, ViewportState};
+use vulkano::pipeline::graphics::GraphicsPipelineCreateInfo;
+use vulkano::pipeline::layout::PipelineDescriptorSetLayoutCreateInfo;
+use vulkano::pipeline::{
+    DynamicState, GraphicsPipeline, Pipeline, PipelineBindPoint, PipelineLayout, PipelineShaderStageCreateInfo,
+};
+use vulkano::render_pass::{RenderPass, Subpass};
+
+use crate::core::error::{DistRenderError, GraphicsError, Result};
+use crate::gfx::vulkan::shaders::{gui_fs, gui_vs};
+use crate::gfx::VulkanContext as GfxDevice;
+use crate::gui::paint::{image_pixels, GuiBatch};
+use crate::gui::GuiFrame;
+
+/// GUI 顶点，与 `egui::epaint::Vertex` 的布局一致
+#[derive(BufferContents, Vertex, Clone, Copy)]
+#[repr(C)]
+struct GuiVertex {
+    #[format(R32G32_SFLOAT)]
+    pos: [f32; 2],
+    #[format(R32G32_SFLOAT)]
+    uv: [f32; 2],
+    /// sRGB gamma 颜色，预乘 alpha
+    #[format(R8G8B8A8_UNORM)]
+    color: [u8; 4],
+}
+
+/// 上传到 GPU 的 egui 纹理
+struct GuiTexture {
+    image: Arc<Image>,
+    descriptor_set: Arc<PersistentDescriptorSet>,
+}
+
+/// Vulkan GUI 绘制器
+pub struct GuiPainter {
+    pipeline: Arc<GraphicsPipeline>,
+    textures: HashMap<egui::TextureId, GuiTexture>,
+    /// 交换链为 UNORM 格式时为 true，着色器直接输出 gamma 颜色
+    manual_srgb: bool,
+}
+
+impl GuiPainter {
+    /// 创建绘制器，`render_pass` 的第一个子通道需与场景绘制共用
+    pub fn new(gfx: &GfxDevice, render_pass: &Arc<RenderPass>, manual_srgb: bool) -> Result<Self> {
+        let vs = gui_vs::load(gfx.device.clone())
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::ShaderCompilation(format!("Failed to load GUI vertex shader: {:?}", e))
+            ))?;
+        let fs = gui_fs::load(gfx.device.clone())
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::ShaderCompilation(format!("Failed to load GUI fragment shader: {:?}", e))
+            ))?;
+        let entry_not_found = || DistRenderError::Graphics(
+            GraphicsError::ShaderCompilation("GUI shader 'main' entry point not found".to_string())
+        );
+        let vs_entry = vs.entry_point("main").ok_or_else(entry_not_found)?;
+        let fs_entry = fs.entry_point("main").ok_or_else(entry_not_found)?;
+
+        let vertex_input_state = GuiVertex::per_vertex()
+            .definition(&vs_entry.info().input_interface)
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::ResourceCreation(format!("Failed to create GUI vertex input state: {:?}", e))
+            ))?;
+        let stages = [
+            PipelineShaderStageCreateInfo::new(vs_entry),
+            PipelineShaderStageCreateInfo::new(fs_entry),
+        ];
+
+        let layout = PipelineLayout::new(
+            gfx.device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages(&stages)
+                .into_pipeline_layout_create_info(gfx.device.clone())
+                .map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::ResourceCreation(format!("Failed to create GUI pipeline layout info: {:?}", e))
+                ))?,
+        )
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ResourceCreation(format!("Failed to create GUI pipeline layout: {:?}", e))
+        ))?;
+
+        let subpass = Subpass::from(render_pass.clone(), 0)
+            .ok_or_else(|| DistRenderError::Graphics(
+                GraphicsError::ResourceCreation("Failed to create subpass".to_string())
+            ))?;
+
+        // 预乘 alpha 混合，与 egui-wgpu 相同；不做深度和模板测试，GUI 总是绘制在场景之上
+        let pipeline = GraphicsPipeline::new(
+            gfx.device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: stages.into_iter().collect(),
+                vertex_input_state: Some(vertex_input_state),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                viewport_state: Some(ViewportState::default()),
+                rasterization_state: Some(RasterizationState::default()),
+                depth_stencil_state: Some(DepthStencilState::default()),
+                multisample_state: Some(Default::default()),
+                color_blend_state: Some(ColorBlendState::with_attachment_states(
+                    1,
+                    ColorBlendAttachmentState {
+                        blend: Some(AttachmentBlend {
+                            src_color_blend_factor: BlendFactor::One,
+                            dst_color_blend_factor: BlendFactor::OneMinusSrcAlpha,
+                            color_blend_op: BlendOp::Add,
+                            src_alpha_blend_factor: BlendFactor::OneMinusDstAlpha,
+                            dst_alpha_blend_factor: BlendFactor::One,
+                            alpha_blend_op: BlendOp::Add,
+                        }),
+                        ..Default::default()
+                    },
+                )),
+                dynamic_state: [DynamicState::Viewport, DynamicState::Scissor].into_iter().collect(),
+                subpass: Some(subpass.into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ResourceCreation(format!("Failed to create GUI pipeline: {:?}", e))
+        ))?;
+
+        Ok(Self {
+            pipeline,
+            textures: HashMap::new(),
+            manual_srgb,
+        })
+    }
+
+    /// 记录本帧的纹理创建和更新，必须在渲染通道开始之前调用
+    pub fn update_textures(
+        &mut self,
+        gfx: &GfxDevice,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        frame: &GuiFrame,
+    ) -> Result<()> {
+        for (id, delta) in &frame.textures_delta.set {
+            let [width, height] = delta.image.size();
+            let pixels = image_pixels(&delta.image);
+
+            // 带位置的更新只覆盖已有纹理的一部分，否则重新创建整张纹理
+            let (image, offset) = match (delta.pos, self.textures.get(id)) {
+                (Some([x, y]), Some(texture)) => (texture.image.clone(), [x as u32, y as u32, 0]),
+                _ => {
+                    let texture = create_texture(gfx, [width as u32, height as u32], delta.options, self.layout())?;
+                    let image = texture.image.clone();
+                    self.textures.insert(*id, texture);
+                    (image, [0, 0, 0])
+                }
+            };
+
+            let staging = Buffer::from_iter(
+                gfx.memory_allocator.clone(),
+                BufferCreateInfo {
+                    usage: BufferUsage::TRANSFER_SRC,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_HOST | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                    ..Default::default()
+                },
+                pixels,
+            )
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::ResourceCreation(format!("Failed to create GUI staging buffer: {:?}", e))
+            ))?;
+
+            builder
+                .copy_buffer_to_image(CopyBufferToImageInfo {
+                    regions: [BufferImageCopy {
+                        image_subresource: image.subresource_layers(),
+                        image_offset: offset,
+                        image_extent: [width as u32, height as u32, 1],
+                        ..Default::default()
+                    }]
+                    .into_iter()
+                    .collect(),
+                    ..CopyBufferToImageInfo::buffer_image(staging, image)
+                })
+                .map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::CommandExecution(format!("Failed to upload GUI texture: {:?}", e))
+                ))?;
+        }
+        Ok(())
+    }
+
+    /// 在当前渲染通道中绘制 GUI，`extent` 为帧缓冲的像素尺寸
+    pub fn draw(
+        &self,
+        gfx: &GfxDevice,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        frame: &GuiFrame,
+        extent: [u32; 2],
+    ) -> Result<()> {
+        // 所有网格合并到同一组缓冲，按各自的起始位置绘制
+        let batch = GuiBatch::new(frame, extent);
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let vertices = batch.vertices.iter().map(|vertex| GuiVertex {
+            pos: [vertex.pos.x, vertex.pos.y],
+            uv: [vertex.uv.x, vertex.uv.y],
+            color: vertex.color.to_array(),
+        });
+
+        let allocation_info = AllocationCreateInfo {
+            memory_type_filter: MemoryTypeFilter::PREFER_DEVICE | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+            ..Default::default()
+        };
+        let vertex_buffer = Buffer::from_iter(
+            gfx.memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            allocation_info.clone(),
+            vertices,
+        )
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ResourceCreation(format!("Failed to create GUI vertex buffer: {:?}", e))
+        ))?;
+        let index_buffer = Buffer::from_iter(
+            gfx.memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::INDEX_BUFFER,
+                ..Default::default()
+            },
+            allocation_info,
+            batch.indices.iter().copied(),
+        )
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ResourceCreation(format!("Failed to create GUI index buffer: {:?}", e))
+        ))?;
+
+        // GUI 覆盖整个窗口，不使用场景的信箱视口
+        let viewport = Viewport {
+            offset: [0.0, 0.0],
+            extent: [extent[0] as f32, extent[1] as f32],
+            depth_range: 0.0..=1.0,
+        };
+        let constants = gui_vs::GuiConstants {
+            screen_size: [
+                extent[0] as f32 / frame.pixels_per_point,
+                extent[1] as f32 / frame.pixels_per_point,
+            ],
+            manual_srgb: self.manual_srgb as u32,
+        };
+
+        builder
+            .bind_pipeline_graphics(self.pipeline.clone())
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::CommandExecution(format!("Failed to bind GUI pipeline: {:?}", e))
+            ))?
+            .set_viewport(0, [viewport].into_iter().collect())
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::CommandExecution(format!("Failed to set GUI viewport: {:?}", e))
+            ))?
+            .push_constants(self.pipeline.layout().clone(), 0, constants)
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::CommandExecution(format!("Failed to push GUI constants: {:?}", e))
+            ))?
+            .bind_vertex_buffers(0, vertex_buffer)
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::CommandExecution(format!("Failed to bind GUI vertex buffer: {:?}", e))
+            ))?
+            .bind_index_buffer(index_buffer)
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::CommandExecution(format!("Failed to bind GUI index buffer: {:?}", e))
+            ))?;
+
+        for mesh in &batch.meshes {
+            let Some(texture) = self.textures.get(&mesh.texture_id) else {
+                continue;
+            };
+            let scissor = Scissor {
+                offset: [mesh.clip.x, mesh.clip.y],
+                extent: [mesh.clip.width, mesh.clip.height],
+            };
+            builder
+                .set_scissor(0, [scissor].into_iter().collect())
+                .map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::CommandExecution(format!("Failed to set GUI scissor: {:?}", e))
+                ))?
+                .bind_descriptor_sets(
+                    PipelineBindPoint::Graphics,
+                    self.pipeline.layout().clone(),
+                    0,
+                    texture.descriptor_set.clone(),
+                )
+                .map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::CommandExecution(format!("Failed to bind GUI descriptor set: {:?}", e))
+                ))?
+                .draw_indexed(mesh.index_count, 1, mesh.first_index, mesh.vertex_offset as i32, 0)
+                .map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::CommandExecution(format!("Failed to draw GUI mesh: {:?}", e))
+                ))?;
+        }
+        Ok(())
+    }
+
+    /// 释放本帧不再使用的纹理（已记录的命令缓冲仍持有引用，提交完成后才真正销毁）
+    pub fn free_textures(&mut self, frame: &GuiFrame) {
+        for id in &frame.textures_delta.free {
+            self.textures.remove(id);
+        }
+    }
+
+    fn layout(&self) -> &Arc<vulkano::descriptor_set::layout::DescriptorSetLayout> {
+        &self.pipeline.layout().set_layouts()[0]
+    }
+}
+
+/// 创建 sRGB 纹理及其采样描述符集
+fn create_texture(
+    gfx: &GfxDevice,
+    [width, height]: [u32; 2],
+    options: egui::TextureOptions,
+    layout: &Arc<vulkano::descriptor_set::layout::DescriptorSetLayout>,
+) -> Result<GuiTexture> {
+    let image = Image::new(
+        gfx.memory_allocator.clone(),
+        ImageCreateInfo {
+            image_type: ImageType::Dim2d,
+            format: Format::R8G8B8A8_SRGB,
+            extent: [width, height, 1],
+            usage: ImageUsage::TRANSFER_DST | ImageUsage::SAMPLED,
+            ..Default::default()
+        },
+        AllocationCreateInfo::default(),
+    )
+    .map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to create GUI texture: {:?}", e))
+    ))?;
+    let view = ImageView::new_default(image.clone())
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ResourceCreation(format!("Failed to create GUI texture view: {:?}", e))
+        ))?;
+
+    let filter = |filter: egui::TextureFilter| match filter {
+        egui::TextureFilter::Nearest => Filter::Nearest,
+        egui::TextureFilter::Linear => Filter::Linear,
+    };
+    let address_mode = match options.wrap_mode {
+        egui::TextureWrapMode::ClampToEdge => SamplerAddressMode::ClampToEdge,
+        egui::TextureWrapMode::Repeat => SamplerAddressMode::Repeat,
+        egui::TextureWrapMode::MirroredRepeat => SamplerAddressMode::MirroredRepeat,
+    };
+    let sampler = Sampler::new(
+        gfx.device.clone(),
+        SamplerCreateInfo {
+            mag_filter: filter(options.magnification),
+            min_filter: filter(options.minification),
+            address_mode: [address_mode; 3],
+            ..Default::default()
+        },
+    )
+    .map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to create GUI sampler: {:?}", e))
+    ))?;
+
+    let descriptor_set = PersistentDescriptorSet::new(
+        &gfx.descriptor_allocator,
+        layout.clone(),
+        [WriteDescriptorSet::image_view_sampler(0, view, sampler)],
+        [],
+    )
+    .map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to create GUI descriptor set: {:?}", e))
+    ))?;
+
+    Ok(GuiTexture { image, descriptor_set })
+}
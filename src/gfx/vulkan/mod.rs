@@ -5,11 +5,15 @@
 //! - Renderer: Vulkan 渲染器实现
 //! - Descriptor: Vulkan 描述符管理
 //! - Shaders: Vulkan shader 加载
+//! - GuiPainter: 内置 GUI 的 egui 网格绘制
+//! - VertexLayout: 顶点布局到顶点输入状态的转换
 
 pub mod context;
 pub mod renderer;
 pub mod descriptor;
 pub mod shaders;
+pub mod gui_painter;
+pub mod vertex_layout;
 
 // 重新导出常用类型
 pub use context::VulkanContext;
@@ -1,45 +1,66 @@
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing::{trace, debug, info, warn, error};
-use vulkano::buffer::{Buffer, BufferUsage, BufferCreateInfo, Subbuffer};
+use vulkano::buffer::{Buffer, BufferUsage, BufferCreateInfo, IndexBuffer, Subbuffer};
 use vulkano::command_buffer::{
-    AutoCommandBufferBuilder, CommandBufferUsage, RenderPassBeginInfo, SubpassBeginInfo, SubpassEndInfo,
+    AutoCommandBufferBuilder, ClearAttachment, ClearRect, CommandBufferExecFuture, CommandBufferUsage,
+    RenderPassBeginInfo, SubpassBeginInfo, SubpassEndInfo,
 };
 use vulkano::descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet};
+use vulkano::device::DeviceOwnedVulkanObject;
 use vulkano::image::view::ImageView;
-use vulkano::image::{Image, ImageUsage};
-use vulkano::format::Format;
+use vulkano::image::{Image, ImageAspects, ImageUsage};
+use vulkano::format::{ClearColorValue, ClearValue, Format, FormatFeatures, NumericFormat};
 use vulkano::pipeline::graphics::input_assembly::InputAssemblyState;
-use vulkano::pipeline::graphics::vertex_input::{Vertex, VertexInputState, VertexInputBindingDescription, VertexInputAttributeDescription};
-use vulkano::pipeline::graphics::viewport::{Viewport, ViewportState};
-use vulkano::pipeline::graphics::rasterization::{RasterizationState, CullMode, FrontFace};
-use vulkano::pipeline::graphics::depth_stencil::{DepthStencilState, DepthState};
-use vulkano::pipeline::graphics::color_blend::{ColorBlendState, ColorBlendAttachmentState};
+use vulkano::pipeline::graphics::vertex_input::VertexInputState;
+use vulkano::pipeline::graphics::viewport::{Scissor, Viewport, ViewportState};
+use vulkano::pipeline::graphics::rasterization::{RasterizationState, CullMode, DepthBiasState, FrontFace, PolygonMode};
+use vulkano::pipeline::graphics::depth_stencil::{
+    CompareOp, DepthStencilState, DepthState, StencilOp as VkStencilOp, StencilOpState, StencilOps,
+    StencilState as VkStencilState,
+};
+use vulkano::pipeline::graphics::color_blend::{ColorBlendState, ColorBlendAttachmentState, ColorComponents};
 use vulkano::pipeline::{GraphicsPipeline, Pipeline, PipelineBindPoint, PipelineLayout, PipelineShaderStageCreateInfo};
 use vulkano::pipeline::layout::PipelineDescriptorSetLayoutCreateInfo;
 use vulkano::render_pass::{Framebuffer, FramebufferCreateInfo, RenderPass, Subpass};
 use vulkano::swapchain::{
-    acquire_next_image, PresentMode, Swapchain, SwapchainCreateInfo,
+    acquire_next_image, PresentFuture, PresentMode, Swapchain, SwapchainAcquireFuture, SwapchainCreateInfo,
     SwapchainPresentInfo,
 };
+use vulkano::sync::future::{FenceSignalFuture, JoinFuture};
 use vulkano::sync::{self, GpuFuture};
 use vulkano::memory::allocator::{AllocationCreateInfo, MemoryTypeFilter};
 use winit::event_loop::EventLoop;
 use winit::window::Window;
 use bytemuck::{Pod, Zeroable};
 
-use crate::renderer::resources::vertex::{MyVertex, create_default_triangle, convert_geometry_vertex};
-use crate::gfx::vulkan::shaders::{vs, fs};
+use crate::renderer::resources::vertex::MyVertex;
+use crate::renderer::resources::vertex_layout::HasVertexLayout;
+use crate::renderer::resources::scene_mesh::{select_lods, ModelMesh, SceneMesh};
+use crate::renderer::stats::{self, FrameStats, PresentStats};
+use crate::renderer::fill_mode::FillMode;
+use crate::renderer::stencil::{StencilCompare, StencilOp, StencilPass, StencilState};
+use crate::gfx::debug_name::DebugNames;
+use crate::gfx::surface_format::{encode_output_color, select_surface_format};
+use crate::gfx::letterbox::{ViewportRect, LETTERBOX_COLOR};
+use crate::core::config::SurfaceFormat;
+use crate::gfx::vulkan::shaders::{vs, fs, stencil_mask_vs, stencil_mask_fs};
 use crate::renderer::resources::resource::FrameResourcePool;
 use crate::renderer::commands::sync::FenceManager;
 use crate::gfx::vulkan::descriptor::VulkanDescriptorManager;
+use crate::gfx::vulkan::gui_painter::GuiPainter;
+use crate::gfx::vulkan::vertex_layout::vertex_input_state;
 use crate::gfx::{GraphicsBackend, VulkanContext as GfxDevice};
 use crate::core::{Config, SceneConfig};
+use crate::core::config::{effective_buffer_count, effective_frames_in_flight, DepthBias};
 use crate::core::error::{Result, DistRenderError, GraphicsError};
-use crate::geometry::loaders::{MeshLoader, ObjLoader};
-use crate::component::{Camera, DirectionalLight};
+use crate::component::{AutoRotate, Camera, DirectionalLight};
 use crate::math::{Vector3, Matrix4};
+use crate::math::matrix::normal_matrix;
+use crate::geometry::mesh::IndexData;
 use crate::gui::ipc::GuiStatePacket;
-use std::path::Path;
+use crate::gui::{GuiManager, GuiState};
 use std::f32::consts::PI;
 
 /// Uniform Buffer Object - MVP 鐭╅樀鏁版嵁
@@ -54,11 +75,17 @@ struct UniformBufferObject {
     projection: [[f32; 4]; 4],
     light_dir: [f32; 4],
     light_color: [f32; 4],
+    /// xyz 为相机位置，w 非零时着色器手动做 sRGB 编码（交换链为 UNORM 格式）
     camera_pos: [f32; 4],
+    ambient: [f32; 4],
+    /// 模型颜色色调，乘以最终颜色
+    tint: [f32; 4],
+    /// 法线矩阵（模型矩阵的逆转置），非均匀缩放时保持法线垂直于表面
+    normal_matrix: [[f32; 4]; 4],
 }
 
 impl UniformBufferObject {
-    fn new(model: &Matrix4, view: &Matrix4, projection: &Matrix4, light_dir: [f32;3], light_color_intensity: [f32;4], camera_pos: [f32;3]) -> Self {
+    fn new(model: &Matrix4, view: &Matrix4, projection: &Matrix4, light_dir: [f32;3], light_color_intensity: [f32;4], camera_pos: [f32;3], ambient: [f32;4]) -> Self {
         Self {
             model: *model.as_ref(),
             view: *view.as_ref(),
@@ -66,6 +93,9 @@ impl UniformBufferObject {
             light_dir: [light_dir[0], light_dir[1], light_dir[2], 0.0],
             light_color: light_color_intensity,
             camera_pos: [camera_pos[0], camera_pos[1], camera_pos[2], 0.0],
+            ambient,
+            tint: [1.0; 4],
+            normal_matrix: *normal_matrix(model).as_ref(),
         }
     }
 }
@@ -74,17 +104,44 @@ pub struct Renderer {
     gfx: GfxDevice,
     swapchain: Arc<Swapchain>,
     render_pass: Arc<RenderPass>,
-    pipeline: Arc<GraphicsPipeline>,
+    /// 颜色附件使用 Load 的兼容渲染通道，关闭每帧清除时使用
+    load_render_pass: Arc<RenderPass>,
+    /// 还需强制清除颜色的帧数（创建和交换链重建后每个图像清除一次）
+    pending_color_clears: u32,
+    /// 运行时间（秒），驱动清除颜色循环
+    total_time: f32,
+    /// 设备支持的每种填充模式一个管线（第一个为实心填充）
+    pipelines: Vec<(FillMode, Arc<GraphicsPipeline>)>,
+    /// 当前的三角形填充模式
+    fill_mode: FillMode,
     framebuffers: Vec<Arc<Framebuffer>>,
     vertex_buffer: Subbuffer<[MyVertex]>,
-    index_buffer: Subbuffer<[u32]>,
+    index_buffer: IndexBuffer,
+    /// 各模型在合并缓冲中的范围
+    models: Vec<ModelMesh>,
+    /// 加载失败、使用占位几何体的模型序号，重新加载时用于判断是否失败
+    fallback_models: Vec<usize>,
+    /// 加载模型时是否使用磁盘缓存
+    mesh_cache: bool,
     viewport: Viewport,
+    /// 固定的画面宽高比，视口按该比例居中（信箱模式）
+    target_aspect: Option<f32>,
     recreate_swapchain: bool,
     previous_frame_end: Option<Box<dyn GpuFuture>>,
+    /// 已提交、可能尚未执行完的帧的栅栏（按提交顺序），用于限制在途帧数
+    in_flight_fences: VecDeque<FrameFence>,
     depth_image: Arc<Image>,
+    /// 深度缓冲格式，启用模板遮罩时带模板分量
+    depth_format: Format,
+    /// 模板遮罩管线，只在启用模板遮罩时创建
+    stencil_mask_pipeline: Option<Arc<GraphicsPipeline>>,
 
     // 鏂板锛氬抚璧勬簮绠＄悊
     frame_resource_pool: FrameResourcePool,
+    /// 同时在 GPU 上执行的最大帧数，提交后等到未完成的帧少于该值（为 1 时即低延迟模式）
+    frames_in_flight: u32,
+    /// 关闭低延迟模式时的帧数（配置值，未配置或为 1 时等于交换链缓冲数）
+    pipelined_frames: u32,
     // 鏂板锛欶ence鍚屾绠＄悊
     fence_manager: FenceManager,
     // 鏂板锛氭弿杩扮绠＄悊
@@ -95,13 +152,25 @@ pub struct Renderer {
     camera: Camera,
     // 鏂板锛氭柟鍚戝厜缁勪欢
     directional_light: DirectionalLight,
+    /// 模型自动旋转，叠加在 GUI 设置的旋转之上
+    auto_rotate: AutoRotate,
+
+    /// 内置 GUI（egui），绘制在场景之后的同一个渲染通道中
+    gui_manager: GuiManager,
+    gui_painter: GuiPainter,
+    /// 是否绘制内置 GUI 并处理其输入事件
+    gui_enabled: bool,
+    /// 呈现统计（跨帧累计）
+    present_stats: PresentStats,
+    /// GPU 抓帧中的资源调试名称（启用验证层时）
+    debug_names: DebugNames,
 }
 
 impl Renderer {
     pub fn new(event_loop: &EventLoop<()>, config: &Config, scene: &SceneConfig) -> Result<Self> {
         let gfx = GfxDevice::new(event_loop, config);
 
-        let (swapchain, images) = {
+        let (swapchain, images, buffer_count) = {
             let surface_capabilities = gfx.device
                 .physical_device()
                 .surface_capabilities(&gfx.surface, Default::default())
@@ -116,11 +185,22 @@ impl Renderer {
                     GraphicsError::DeviceCreation(format!("Failed to get surface formats: {:?}", e))
                 ))?;
 
-            let image_format = surface_formats.get(0)
-                .ok_or_else(|| DistRenderError::Graphics(
-                    GraphicsError::DeviceCreation("No surface formats available".to_string())
-                ))?
-                .0;
+            let surface_formats: Vec<Format> = surface_formats.into_iter().map(|(format, _)| format).collect();
+            let image_format = select_surface_format(
+                &surface_formats,
+                config.graphics.surface_format,
+                |format| match format {
+                    Format::B8G8R8A8_SRGB => Some(SurfaceFormat::Bgra8Srgb),
+                    Format::R8G8B8A8_SRGB => Some(SurfaceFormat::Rgba8Srgb),
+                    Format::B8G8R8A8_UNORM => Some(SurfaceFormat::Bgra8Unorm),
+                    Format::R8G8B8A8_UNORM => Some(SurfaceFormat::Rgba8Unorm),
+                    _ => None,
+                },
+                is_srgb_format,
+            )
+            .ok_or_else(|| DistRenderError::Graphics(
+                GraphicsError::DeviceCreation("No surface formats available".to_string())
+            ))?;
 
             let window = gfx.window();
 
@@ -132,11 +212,22 @@ impl Renderer {
                     GraphicsError::SwapchainError("No supported composite alpha modes".to_string())
                 ))?;
 
-            Swapchain::new(
+            let buffer_count = effective_buffer_count(
+                config.graphics.buffer_count,
+                surface_capabilities.min_image_count,
+                surface_capabilities.max_image_count,
+            );
+            info!(
+                requested = config.graphics.buffer_count,
+                effective = buffer_count,
+                "Swapchain buffer count selected"
+            );
+
+            let (swapchain, images) = Swapchain::new(
                 gfx.device.clone(),
                 gfx.surface.clone(),
                 SwapchainCreateInfo {
-                    min_image_count: surface_capabilities.min_image_count.max(2),
+                    min_image_count: buffer_count,
                     image_format,
                     image_extent: window.inner_size().into(),
                     image_usage: ImageUsage::COLOR_ATTACHMENT,
@@ -149,7 +240,9 @@ impl Renderer {
             )
             .map_err(|e| DistRenderError::Graphics(
                 GraphicsError::SwapchainError(format!("Failed to create swapchain: {:?}", e))
-            ))?
+            ))?;
+
+            (swapchain, images, buffer_count)
         };
 
         #[cfg(debug_assertions)]
@@ -160,69 +253,12 @@ impl Renderer {
             "Swapchain created"
         );
 
-        // 鍔犺浇 OBJ 妯″瀷鏂囦欢
-        let obj_path = Path::new(&scene.model.path);
-        let (vertices, indices) = if obj_path.exists() {
-            info!("Loading mesh from: {}", obj_path.display());
-            match ObjLoader::load_from_file(obj_path) {
-                Ok(mesh_data) => {
-                    info!(
-                        "Mesh loaded successfully: {} vertices, {} indices",
-                        mesh_data.vertex_count(),
-                        mesh_data.index_count()
-                    );
-                    // 杞崲 GeometryVertex 涓?MyVertex
-                    let verts = mesh_data
-                        .vertices
-                        .iter()
-                        .map(|v| convert_geometry_vertex(v))
-                        .collect::<Vec<_>>();
-                    let inds = mesh_data.indices.clone();
-                    (verts, inds)
-                }
-                Err(e) => {
-                    warn!("Failed to load OBJ file: {}, using default triangle", e);
-                    (create_default_triangle().to_vec(), vec![0, 1, 2])
-                }
-            }
-        } else {
-            warn!("OBJ file not found: {}, using default triangle", obj_path.display());
-            (create_default_triangle().to_vec(), vec![0, 1, 2])
-        };
-
-        let vertex_buffer = Buffer::from_iter(
-            gfx.memory_allocator.clone(),
-            BufferCreateInfo {
-                usage: BufferUsage::VERTEX_BUFFER,
-                ..Default::default()
-            },
-            AllocationCreateInfo {
-                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
-                ..Default::default()
-            },
-            vertices.into_iter(),
-        )
-        .map_err(|e| DistRenderError::Graphics(
-            GraphicsError::ResourceCreation(format!("Failed to create vertex buffer: {:?}", e))
-        ))?;
-
-        let index_buffer = Buffer::from_iter(
-            gfx.memory_allocator.clone(),
-            BufferCreateInfo {
-                usage: BufferUsage::INDEX_BUFFER,
-                ..Default::default()
-            },
-            AllocationCreateInfo {
-                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
-                ..Default::default()
-            },
-            indices.into_iter(),
-        )
-        .map_err(|e| DistRenderError::Graphics(
-            GraphicsError::ResourceCreation(format!("Failed to create index buffer: {:?}", e))
-        ))?;
-
-        info!("Index buffer created: {} indices", index_buffer.len());
+        // 加载场景中的所有模型（合并到同一组缓冲），管线只使用三角形列表，三角形带在加载时转换
+        let scene_mesh = SceneMesh::load(&scene.models, scene.fallback_primitive, false, config.graphics.mesh_cache);
+        let scene_bounds = scene.camera.fit_clip_planes.then(|| scene_mesh.world_bounds(&scene.models)).flatten();
+        // 调试名称需要 VK_EXT_debug_utils，只在验证层实际启用时设置
+        let debug_names = DebugNames::new(gfx.instance.enabled_extensions().ext_debug_utils);
+        let (vertex_buffer, index_buffer) = create_mesh_buffers(&gfx, &scene_mesh, debug_names, scene)?;
 
         let vs = vs::load(gfx.device.clone())
             .map_err(|e| DistRenderError::Graphics(
@@ -236,6 +272,16 @@ impl Renderer {
         #[cfg(debug_assertions)]
         debug!("Shaders loaded successfully");
 
+        // 启用模板遮罩时深度缓冲需要带模板分量，设备不支持时关闭遮罩
+        let stencil_format = scene.stencil_mask.and_then(|_| {
+            let format = select_depth_stencil_format(&gfx);
+            if format.is_none() {
+                warn!("No depth-stencil format supported, disabling stencil mask");
+            }
+            format
+        });
+        let depth_format = stencil_format.unwrap_or(Format::D32_SFLOAT);
+
         let render_pass = vulkano::single_pass_renderpass!(
             gfx.device.clone(),
             attachments: {
@@ -246,7 +292,33 @@ impl Renderer {
                     store_op: Store,
                 },
                 depth: {
-                    format: Format::D32_SFLOAT,
+                    format: depth_format,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {depth}
+            }
+        )
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ResourceCreation(format!("Failed to create render pass: {:?}", e))
+        ))?;
+
+        // 与上面的渲染通道兼容（只有加载操作不同），可共用帧缓冲和管线
+        let load_render_pass = vulkano::single_pass_renderpass!(
+            gfx.device.clone(),
+            attachments: {
+                color: {
+                    format: swapchain.image_format(),
+                    samples: 1,
+                    load_op: Load,
+                    store_op: Store,
+                },
+                depth: {
+                    format: depth_format,
                     samples: 1,
                     load_op: Clear,
                     store_op: DontCare,
@@ -264,7 +336,7 @@ impl Renderer {
         #[cfg(debug_assertions)]
         debug!("Render pass created");
 
-        let pipeline = {
+        let pipelines = {
             let vs_entry = vs.entry_point("main")
                 .ok_or_else(|| DistRenderError::Graphics(
                     GraphicsError::ShaderCompilation("Vertex shader 'main' entry point not found".to_string())
@@ -297,40 +369,26 @@ impl Renderer {
                     GraphicsError::ResourceCreation("Failed to create subpass".to_string())
                 ))?;
 
-            GraphicsPipeline::new(
+            // 设备支持的每种填充模式一个管线，共享同一个管线布局以复用描述符集
+            let capabilities = gfx.report_capabilities();
+            let create_pipeline = |polygon_mode: PolygonMode| GraphicsPipeline::new(
                 gfx.device.clone(),
                 None,
                 vulkano::pipeline::graphics::GraphicsPipelineCreateInfo {
-                    stages: stages.into_iter().collect(),
-                    vertex_input_state: Some({
-                        let desc = MyVertex::per_vertex();
-                        let binding_desc = VertexInputBindingDescription {
-                            stride: desc.stride,
-                            input_rate: desc.input_rate,
-                        };
-                        let attr_descs: Vec<(u32, VertexInputAttributeDescription)> = desc.members.iter().enumerate().map(|(location, (_name, member))| {
-                            (location as u32, VertexInputAttributeDescription {
-                                binding: 0,
-                                format: member.format,
-                                offset: member.offset as u32,
-                            })
-                        }).collect();
-                        
-                        let mut state = VertexInputState::new().binding(0, binding_desc);
-                        for (location, attr) in attr_descs {
-                            state = state.attribute(location, attr);
-                        }
-                        state
-                    }),
+                    stages: stages.iter().cloned().collect(),
+                    vertex_input_state: Some(vertex_input_state(0, &MyVertex::vertex_layout())),
                     input_assembly_state: Some(InputAssemblyState::default()),
                     viewport_state: Some(ViewportState::default()),
                     rasterization_state: Some(RasterizationState {
+                        polygon_mode,
                         cull_mode: CullMode::Back,
                         front_face: FrontFace::Clockwise,
+                        depth_bias: depth_bias_state(&config.graphics.depth_bias, &gfx),
                         ..Default::default()
                     }),
                     depth_stencil_state: Some(DepthStencilState {
                         depth: Some(DepthState::simple()),
+                        stencil: stencil_format.map(|_| stencil_state(StencilPass::Restrict.state())),
                         ..Default::default()
                     }),
                     multisample_state: Some(Default::default()),
@@ -338,16 +396,43 @@ impl Renderer {
                         1,  // 娓叉煋閫氶亾涓湁 1 涓?color attachment
                         ColorBlendAttachmentState::default(),
                     )),
-                    dynamic_state: [vulkano::pipeline::DynamicState::Viewport].into_iter().collect(),
-                    subpass: Some(subpass.into()),
-                    ..vulkano::pipeline::graphics::GraphicsPipelineCreateInfo::layout(layout)
+                    dynamic_state: [vulkano::pipeline::DynamicState::Viewport, vulkano::pipeline::DynamicState::Scissor]
+                .into_iter()
+                .collect(),
+                    subpass: Some(subpass.clone().into()),
+                    ..vulkano::pipeline::graphics::GraphicsPipelineCreateInfo::layout(layout.clone())
                 },
             )
             .map_err(|e| DistRenderError::Graphics(
                 GraphicsError::ResourceCreation(format!("Failed to create graphics pipeline: {:?}", e))
-            ))?
+            ));
+
+            let mut pipelines = Vec::new();
+            for mode in FillMode::ALL.into_iter().filter(|mode| mode.is_supported(&capabilities)) {
+                let polygon_mode = match mode {
+                    FillMode::Solid => PolygonMode::Fill,
+                    FillMode::Wireframe => PolygonMode::Line,
+                    FillMode::Point => PolygonMode::Point,
+                };
+                let pipeline = create_pipeline(polygon_mode)?;
+                set_debug_name(&pipeline, debug_names.name("Scene Pipeline", mode.label()));
+                pipelines.push((mode, pipeline));
+            }
+            pipelines
         };
 
+        let stencil_mask_pipeline = match stencil_format {
+            Some(_) => {
+                let pipeline = create_stencil_mask_pipeline(&gfx, &render_pass)?;
+                set_debug_name(&pipeline, debug_names.name("Pipeline", "Stencil Mask"));
+                Some(pipeline)
+            }
+            None => None,
+        };
+
+        let gui_painter = GuiPainter::new(&gfx, &render_pass, !is_srgb_format(swapchain.image_format()))?;
+        let mut gui_manager = GuiManager::without_renderer(gfx.window(), GuiState::new(config, scene));
+
         #[cfg(debug_assertions)]
         debug!("Graphics pipeline created");
 
@@ -363,7 +448,7 @@ impl Renderer {
             gfx.memory_allocator.clone(),
             vulkano::image::ImageCreateInfo {
                 image_type: vulkano::image::ImageType::Dim2d,
-                format: Format::D32_SFLOAT,
+                format: depth_format,
                 extent: [dimensions[0], dimensions[1], 1],
                 usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT,
                 ..Default::default()
@@ -374,12 +459,21 @@ impl Renderer {
             GraphicsError::ResourceCreation(format!("Failed to create depth image: {:?}", e))
         ))?;
 
-        let framebuffers = window_size_dependent_setup(&images, render_pass.clone(), depth_image.clone(), &mut viewport)?;
+        let framebuffers = window_size_dependent_setup(
+            &images,
+            render_pass.clone(),
+            depth_image.clone(),
+            &mut viewport,
+            config.graphics.target_aspect,
+        )?;
 
         let previous_frame_end = Some(sync::now(gfx.device.clone()).boxed());
 
-        // 鍒濆鍖栧抚璧勬簮姹狅紙涓夌紦鍐诧級
-        let frame_resource_pool = FrameResourcePool::triple_buffering();
+        // 帧资源池的大小即同时在 GPU 上执行的最大帧数
+        let frames_in_flight = effective_frames_in_flight(config.graphics.frames_in_flight, buffer_count);
+        let pipelined_frames =
+            effective_frames_in_flight(config.graphics.frames_in_flight.filter(|&n| n > 1), buffer_count);
+        let frame_resource_pool = FrameResourcePool::new(frames_in_flight as usize);
 
         // 鍒濆鍖朏ence绠＄悊鍣?
         let fence_manager = FenceManager::new();
@@ -389,7 +483,7 @@ impl Renderer {
 
         #[cfg(debug_assertions)]
         {
-            info!("Vulkan Renderer initialized successfully with triple buffering");
+            info!(buffer_count, frames_in_flight, "Vulkan Renderer initialized successfully");
             debug!("Descriptor manager initialized");
         }
 
@@ -401,8 +495,9 @@ impl Renderer {
             scene.camera.transform.position[2],
         ));
         let aspect_ratio = viewport.extent[0] / viewport.extent[1];
-        camera.set_lens(
+        camera.set_perspective(
             scene.camera.fov * PI / 180.0,
+            scene.camera.fov_axis,
             aspect_ratio,
             scene.camera.near_clip,
             scene.camera.far_clip,
@@ -421,6 +516,14 @@ impl Renderer {
 
         info!("Camera component initialized at position {:?}", camera.position());
 
+        // 按场景包围盒收紧近远裁剪面
+        if let Some(bounds) = &scene_bounds {
+            camera.fit_clip_planes(bounds);
+            info!("Camera clip planes fitted to scene bounds: near={}, far={}", camera.near_z(), camera.far_z());
+            gui_manager.state_mut().camera_near = camera.near_z();
+            gui_manager.state_mut().camera_far = camera.far_z();
+        }
+
         // 鍒濆鍖栨柟鍚戝厜缁勪欢
         let directional_light = scene.light.to_directional_light("MainLight");
         info!(
@@ -430,24 +533,48 @@ impl Renderer {
             directional_light.direction
         );
 
+        let present_mode = present_mode_kind(swapchain.present_mode());
+
         Ok(Self {
             gfx,
             swapchain,
             render_pass,
-            pipeline,
+            load_render_pass,
+            pending_color_clears: framebuffers.len() as u32,
+            total_time: 0.0,
+            pipelines,
+            fill_mode: FillMode::default(),
             framebuffers,
             vertex_buffer,
             index_buffer,
+            models: scene_mesh.models,
+            fallback_models: scene_mesh.fallback_models,
+            mesh_cache: config.graphics.mesh_cache,
             viewport,
+            target_aspect: config.graphics.target_aspect,
             recreate_swapchain: false,
             previous_frame_end,
+            in_flight_fences: VecDeque::new(),
             depth_image,
+            depth_format,
+            stencil_mask_pipeline,
             frame_resource_pool,
+            frames_in_flight,
+            pipelined_frames,
             fence_manager,
             descriptor_manager,
             scene: scene.clone(),
             camera,
             directional_light,
+            auto_rotate: AutoRotate::default(),
+            gui_manager,
+            gui_painter,
+            gui_enabled: true,
+            present_stats: PresentStats {
+                present_mode,
+                ..Default::default()
+            },
+            debug_names,
         })
     }
 
@@ -455,6 +582,21 @@ impl Renderer {
         self.gfx.window()
     }
 
+    /// 从磁盘重新加载场景模型并替换顶点和索引缓冲，之前加载成功的模型失败时保留当前网格
+    ///
+    /// 旧缓冲由仍在执行的命令缓冲持有引用，GPU 用完后才释放。
+    pub fn reload_models(&mut self) -> Result<()> {
+        let scene_mesh = SceneMesh::load(&self.scene.models, self.scene.fallback_primitive, false, self.mesh_cache);
+        scene_mesh.check_reload(&self.fallback_models, &self.scene.models)?;
+
+        let (vertex_buffer, index_buffer) = create_mesh_buffers(&self.gfx, &scene_mesh, self.debug_names, &self.scene)?;
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        self.models = scene_mesh.models;
+        self.fallback_models = scene_mesh.fallback_models;
+        Ok(())
+    }
+
     pub fn resize(&mut self) {
         #[cfg(debug_assertions)]
         debug!("Swapchain resize requested");
@@ -488,7 +630,10 @@ impl Renderer {
         Ok(())
     }
 
-    pub fn draw(&mut self) -> Result<()> {
+    pub fn draw(&mut self) -> Result<FrameStats> {
+        let start = Instant::now();
+        let mut stats = FrameStats::default();
+
         // 鑾峰彇褰撳墠甯ц祫婧愪俊鎭?
         let current_frame = self.frame_resource_pool.current_index();
 
@@ -498,7 +643,7 @@ impl Renderer {
         let window = self.window();
         let dimensions = window.inner_size();
         if dimensions.width == 0 || dimensions.height == 0 {
-            return Ok(());
+            return Ok(stats);
         }
 
         self.previous_frame_end.as_mut()
@@ -527,7 +672,7 @@ impl Renderer {
                     if err_string.contains("ImageExtentNotSupported") {
                         #[cfg(debug_assertions)]
                         warn!("Swapchain recreation skipped: extent not supported");
-                        return Ok(());
+                        return Ok(stats);
                     }
                     error!("Failed to recreate swapchain: {:?}", e);
                     return Err(DistRenderError::Graphics(
@@ -552,7 +697,7 @@ impl Renderer {
                 self.gfx.memory_allocator.clone(),
                 vulkano::image::ImageCreateInfo {
                     image_type: vulkano::image::ImageType::Dim2d,
-                    format: Format::D32_SFLOAT,
+                    format: self.depth_format,
                     extent: [new_dimensions[0], new_dimensions[1], 1],
                     usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT,
                     ..Default::default()
@@ -568,8 +713,10 @@ impl Renderer {
                 self.render_pass.clone(),
                 self.depth_image.clone(),
                 &mut self.viewport,
+                self.target_aspect,
             )?;
             self.recreate_swapchain = false;
+            self.pending_color_clears = self.framebuffers.len() as u32;
 
             // 閲嶇疆 previous_frame_end 浠ョ‘淇濆共鍑€鐨勫悓姝ョ姸鎬?
             self.previous_frame_end = Some(sync::now(self.gfx.device.clone()).boxed());
@@ -578,7 +725,10 @@ impl Renderer {
             debug!("Framebuffers rebuilt, synchronization reset");
         }
 
+        // 没有空闲的交换链图像时阻塞到 GPU 显示完之前的帧，计入 GPU 等待
+        let wait_start = Instant::now();
         let acquire_result = acquire_next_image(self.swapchain.clone(), None);
+        stats.record_wait(wait_start);
 
         let (image_index, suboptimal, acquire_future) =
             match acquire_result {
@@ -594,7 +744,8 @@ impl Renderer {
                         #[cfg(debug_assertions)]
                         warn!("Swapchain out of date, will recreate");
                         self.recreate_swapchain = true;
-                        return Ok(());
+                        self.present_stats.dropped_frames += 1;
+                        return Ok(stats);
                     }
                     error!("Failed to acquire next image: {:?}", e);
                     return Err(DistRenderError::Graphics(
@@ -607,6 +758,7 @@ impl Renderer {
             #[cfg(debug_assertions)]
             debug!("Swapchain suboptimal, will recreate next frame");
             self.recreate_swapchain = true;
+            self.present_stats.dropped_frames += 1;
         }
 
         #[cfg(debug_assertions)]
@@ -617,7 +769,6 @@ impl Renderer {
         self.camera.set_aspect(aspect_ratio);
 
         // 璁＄畻 MVP 鐭╅樀锛堜娇鐢?Camera 缁勪欢锛?
-        let model = self.scene.model.transform.to_matrix();
         let view = self.camera.view_matrix();
         let mut projection = self.camera.proj_matrix();
 
@@ -635,47 +786,61 @@ impl Renderer {
             self.directional_light.intensity,
         ];
         let camera_pos = self.camera.position();
-        let ubo = UniformBufferObject::new(
-            &model,
-            &view,
-            &projection,
-            [light_direction.x, light_direction.y, light_direction.z],
-            light_col_int,
-            [camera_pos.x, camera_pos.y, camera_pos.z],
-        );
+        let manual_srgb = !is_srgb_format(self.swapchain.image_format());
 
-        // 鍒涘缓 uniform buffer
-        let uniform_subbuffer = Buffer::from_data(
-            self.gfx.memory_allocator.clone(),
-            BufferCreateInfo {
-                usage: BufferUsage::UNIFORM_BUFFER,
-                ..Default::default()
-            },
-            AllocationCreateInfo {
-                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
-                ..Default::default()
-            },
-            ubo,
-        )
-        .map_err(|e| DistRenderError::Graphics(
-            GraphicsError::ResourceCreation(format!("Failed to create uniform buffer: {:?}", e))
-        ))?;
-
-        // 鍒涘缓鎻忚堪绗﹂泦
-        let layout = self.pipeline.layout().set_layouts().get(0)
+        let layout = self.pipeline().layout().set_layouts().get(0)
             .ok_or_else(|| DistRenderError::Graphics(
                 GraphicsError::ResourceCreation("Pipeline has no descriptor set layouts".to_string())
             ))?;
 
-        let descriptor_set = PersistentDescriptorSet::new(
-            &self.gfx.descriptor_allocator,
-            layout.clone(),
-            [WriteDescriptorSet::buffer(0, uniform_subbuffer)],
-            []
-        )
-        .map_err(|e| DistRenderError::Graphics(
-            GraphicsError::ResourceCreation(format!("Failed to create descriptor set: {:?}", e))
-        ))?;
+        // 每个模型使用各自的 uniform buffer 和描述符集，按距离选择 LOD
+        let lod_levels = select_lods(&self.models, &self.scene.models, &camera_pos);
+        let mut model_draws = Vec::with_capacity(self.models.len());
+        for ((model_mesh, model_config), &lod_level) in self.models.iter().zip(&self.scene.models).zip(&lod_levels) {
+            let model = model_config.transform.to_matrix();
+            let mut ubo = UniformBufferObject {
+                tint: model_config.tint,
+                ..UniformBufferObject::new(
+                    &model,
+                    &view,
+                    &projection,
+                    [light_direction.x, light_direction.y, light_direction.z],
+                    light_col_int,
+                    [camera_pos.x, camera_pos.y, camera_pos.z],
+                    self.scene.ambient_light(),
+                )
+            };
+            ubo.camera_pos[3] = manual_srgb as u32 as f32;
+
+            let uniform_subbuffer = Buffer::from_data(
+                self.gfx.memory_allocator.clone(),
+                BufferCreateInfo {
+                    usage: BufferUsage::UNIFORM_BUFFER,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_DEVICE | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                    ..Default::default()
+                },
+                ubo,
+            )
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::ResourceCreation(format!("Failed to create uniform buffer: {:?}", e))
+            ))?;
+
+            let descriptor_set = PersistentDescriptorSet::new(
+                &self.gfx.descriptor_allocator,
+                layout.clone(),
+                [WriteDescriptorSet::buffer(0, uniform_subbuffer)],
+                []
+            )
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::ResourceCreation(format!("Failed to create descriptor set: {:?}", e))
+            ))?;
+
+            let indices = model_mesh.lod_range(lod_level);
+            model_draws.push((descriptor_set, indices.start, indices.len() as u32));
+        }
 
         let mut builder = AutoCommandBufferBuilder::primary(
             &self.gfx.command_buffer_allocator,
@@ -686,12 +851,43 @@ impl Renderer {
             GraphicsError::CommandExecution(format!("Failed to create command buffer builder: {:?}", e))
         ))?;
 
+        // 关闭每帧清除时改用 Load 渲染通道，保留交换链图像原有内容
+        let clear_color = self.scene.clear_color_enabled || self.pending_color_clears > 0;
+        self.pending_color_clears = self.pending_color_clears.saturating_sub(1);
+        let scene_clear_color = encode_output_color(self.scene.clear_color_at(self.total_time), manual_srgb);
+
+        // 信箱模式下先把整个图像清除为黑色，再只把视口区域清除为场景的清除颜色
+        let framebuffer_extent = self.framebuffers[image_index as usize].extent();
+        let viewport_rect = ViewportRect {
+            x: self.viewport.offset[0] as u32,
+            y: self.viewport.offset[1] as u32,
+            width: self.viewport.extent[0] as u32,
+            height: self.viewport.extent[1] as u32,
+        };
+        let letterboxed = !viewport_rect.covers(framebuffer_extent[0], framebuffer_extent[1]);
+        // GUI 纹理上传是复制命令，需在渲染通道开始之前记录
+        let gui_frame = if self.gui_enabled {
+            self.gui_manager.update(self.gfx.window());
+            let frame = self.gui_manager.end_frame(self.gfx.window());
+            self.gui_painter.update_textures(&self.gfx, &mut builder, &frame)?;
+            Some(frame)
+        } else {
+            None
+        };
+
+        let (render_pass, color_clear_value) = match (clear_color, letterboxed) {
+            (true, true) => (self.render_pass.clone(), Some(encode_output_color(LETTERBOX_COLOR, manual_srgb).into())),
+            (true, false) => (self.render_pass.clone(), Some(scene_clear_color.into())),
+            (false, _) => (self.load_render_pass.clone(), None),
+        };
+
         builder
             .begin_render_pass(
                 RenderPassBeginInfo {
+                    render_pass,
                     clear_values: vec![
-                        Some(self.scene.clear_color.into()),
-                        Some(1.0f32.into()),  // 娣卞害缂撳啿娓呯┖涓?.0锛堟渶杩滐級
+                        color_clear_value,
+                        Some(depth_clear_value(self.depth_format)),
                     ],
                     ..RenderPassBeginInfo::framebuffer(
                         self.framebuffers[image_index as usize].clone(),
@@ -709,18 +905,57 @@ impl Renderer {
             .map_err(|e| DistRenderError::Graphics(
                 GraphicsError::CommandExecution(format!("Failed to set viewport: {:?}", e))
             ))?
-            .bind_pipeline_graphics(self.pipeline.clone())
+            .set_scissor(0, [Scissor {
+                offset: [viewport_rect.x, viewport_rect.y],
+                extent: [viewport_rect.width, viewport_rect.height],
+            }].into_iter().collect())
             .map_err(|e| DistRenderError::Graphics(
-                GraphicsError::CommandExecution(format!("Failed to bind pipeline: {:?}", e))
-            ))?
-            .bind_descriptor_sets(
-                PipelineBindPoint::Graphics,
-                self.pipeline.layout().clone(),
-                0,
-                descriptor_set,
-            )
+                GraphicsError::CommandExecution(format!("Failed to set scissor: {:?}", e))
+            ))?;
+
+        if clear_color && letterboxed {
+            builder
+                .clear_attachments(
+                    [ClearAttachment::Color {
+                        color_attachment: 0,
+                        clear_value: ClearColorValue::Float(scene_clear_color),
+                    }].into_iter().collect(),
+                    [ClearRect {
+                        offset: [viewport_rect.x, viewport_rect.y],
+                        extent: [viewport_rect.width, viewport_rect.height],
+                        array_layers: 0..1,
+                    }].into_iter().collect(),
+                )
+                .map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::CommandExecution(format!("Failed to clear viewport: {:?}", e))
+                ))?;
+        }
+
+        // 第一遍把遮罩矩形写入模板缓冲，之后的场景管线只在矩形内绘制
+        if let (Some(pipeline), Some(mask)) = (&self.stencil_mask_pipeline, self.scene.stencil_mask) {
+            builder
+                .bind_pipeline_graphics(pipeline.clone())
+                .map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::CommandExecution(format!("Failed to bind stencil mask pipeline: {:?}", e))
+                ))?
+                .push_constants(
+                    pipeline.layout().clone(),
+                    0,
+                    stencil_mask_vs::MaskRect { bounds: mask.ndc_bounds(true) },
+                )
+                .map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::CommandExecution(format!("Failed to push stencil mask rect: {:?}", e))
+                ))?
+                .draw(6, 1, 0, 0)
+                .map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::CommandExecution(format!("Failed to draw stencil mask: {:?}", e))
+                ))?;
+        }
+
+        builder
+            .bind_pipeline_graphics(self.pipeline().clone())
             .map_err(|e| DistRenderError::Graphics(
-                GraphicsError::CommandExecution(format!("Failed to bind descriptor sets: {:?}", e))
+                GraphicsError::CommandExecution(format!("Failed to bind pipeline: {:?}", e))
             ))?
             .bind_vertex_buffers(0, self.vertex_buffer.clone())
             .map_err(|e| DistRenderError::Graphics(
@@ -729,11 +964,31 @@ impl Renderer {
             .bind_index_buffer(self.index_buffer.clone())
             .map_err(|e| DistRenderError::Graphics(
                 GraphicsError::CommandExecution(format!("Failed to bind index buffer: {:?}", e))
-            ))?
-            .draw_indexed(self.index_buffer.len() as u32, 1, 0, 0, 0)
-            .map_err(|e| DistRenderError::Graphics(
-                GraphicsError::CommandExecution(format!("Failed to record draw command: {:?}", e))
-            ))?
+            ))?;
+
+        for (descriptor_set, index_start, index_count) in model_draws {
+            builder
+                .bind_descriptor_sets(
+                    PipelineBindPoint::Graphics,
+                    self.pipeline().layout().clone(),
+                    0,
+                    descriptor_set,
+                )
+                .map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::CommandExecution(format!("Failed to bind descriptor sets: {:?}", e))
+                ))?
+                .draw_indexed(index_count, 1, index_start, 0, 0)
+                .map_err(|e| DistRenderError::Graphics(
+                    GraphicsError::CommandExecution(format!("Failed to record draw command: {:?}", e))
+                ))?;
+            stats.record_draw(index_count);
+        }
+
+        if let Some(frame) = &gui_frame {
+            self.gui_painter.draw(&self.gfx, &mut builder, frame, framebuffer_extent)?;
+        }
+
+        builder
             .end_render_pass(SubpassEndInfo::default())
             .map_err(|e| DistRenderError::Graphics(
                 GraphicsError::CommandExecution(format!("Failed to end render pass: {:?}", e))
@@ -743,6 +998,9 @@ impl Renderer {
             .map_err(|e| DistRenderError::Graphics(
                 GraphicsError::CommandExecution(format!("Failed to build command buffer: {:?}", e))
             ))?;
+        if let Some(frame) = &gui_frame {
+            self.gui_painter.free_textures(frame);
+        }
 
         #[cfg(debug_assertions)]
         trace!("Command buffer built, submitting to queue");
@@ -766,6 +1024,23 @@ impl Renderer {
             Ok(future) => {
                 #[cfg(debug_assertions)]
                 trace!("Frame presented successfully");
+                self.present_stats.presented_frames += 1;
+                // 限制在途帧数：等到只剩 frames_in_flight - 1 帧未完成
+                // （低延迟模式下即等待刚提交的这一帧，下一帧的输入处理与录制不再与 GPU 并行）
+                // vulkano 只为 Arc<FenceSignalFuture> 实现共享的 GpuFuture，帧 future 不跨线程使用
+                #[allow(clippy::arc_with_non_send_sync)]
+                let future = Arc::new(future);
+                self.in_flight_fences.push_back(future.clone());
+                if self.in_flight_fences.len() >= self.frames_in_flight as usize {
+                    let wait_start = Instant::now();
+                    while self.in_flight_fences.len() >= self.frames_in_flight as usize {
+                        let Some(fence) = self.in_flight_fences.pop_front() else { break };
+                        if let Err(e) = fence.wait(None) {
+                            error!("Failed to wait for frame fence: {:?}", e);
+                        }
+                    }
+                    stats.record_wait(wait_start);
+                }
                 self.previous_frame_end = Some(future.boxed());
             }
             Err(e) => {
@@ -775,6 +1050,7 @@ impl Renderer {
                     #[cfg(debug_assertions)]
                     debug!("Flush error: swapchain out of date");
                     self.recreate_swapchain = true;
+                    self.present_stats.dropped_frames += 1;
                 } else {
                     error!("Failed to flush future: {:?}", e);
                 }
@@ -794,21 +1070,53 @@ impl Renderer {
         // 鎺ㄨ繘鍒颁笅涓€甯?
         self.frame_resource_pool.advance();
 
-        Ok(())
+        stats.finish(start);
+        if self.gui_enabled {
+            self.gui_manager.state_mut().update_frame_stats(&stats);
+            self.gui_manager.state_mut().present_stats = Some(self.present_stats);
+            let packet = self.gui_manager.state().to_packet();
+            self.apply_gui_packet(&packet);
+        }
+        Ok(stats)
     }
 
     /// Update camera based on input system state
     ///
     /// Called every frame before draw() to apply user input to camera
     pub fn update(&mut self, input_system: &mut crate::core::input::InputSystem, delta_time: f32) {
+        self.auto_rotate.advance(delta_time);
+        self.gui_manager.state_mut().mouse_captured = Some(input_system.is_captured());
+        if input_system.update_light(&mut self.directional_light, delta_time) {
+            // 同步到 GUI 状态，否则每帧应用 GUI 参数时会被覆盖
+            let direction = self.directional_light.direction;
+            self.gui_manager.state_mut().light_direction = [direction.x, direction.y, direction.z];
+        }
+        input_system.update_camera(&mut self.camera, delta_time);
+    }
+
+    /// 仅更新相机（暂停时使用）
+    pub fn update_camera(&mut self, input_system: &mut crate::core::input::InputSystem, delta_time: f32) {
+        self.gui_manager.state_mut().mouse_captured = Some(input_system.is_captured());
         input_system.update_camera(&mut self.camera, delta_time);
     }
 
+    /// 处理 GUI 事件，返回 true 表示事件被 GUI 消费
+    pub fn handle_gui_event(&mut self, event: &winit::event::WindowEvent) -> bool {
+        self.gui_enabled && self.gui_manager.handle_event(self.gfx.window(), event)
+    }
+
     pub fn apply_gui_packet(&mut self, packet: &GuiStatePacket) {
         self.scene.clear_color = packet.clear_color;
-        self.scene.model.transform.position = packet.model_position;
-        self.scene.model.transform.rotation = packet.model_rotation;
-        self.scene.model.transform.scale = packet.model_scale;
+        self.scene.clear_color_enabled = packet.clear_color_enabled != 0;
+        self.scene.clear_color_cycle = packet.clear_color_cycle != 0;
+        self.scene.ambient_color = packet.ambient_color;
+        self.scene.ambient_intensity = packet.ambient_intensity;
+        self.scene.exposure = packet.exposure;
+        packet.apply_model_transforms(&mut self.scene.models);
+        self.auto_rotate.enabled = packet.auto_rotate != 0;
+        self.auto_rotate.speed = packet.auto_rotate_speed;
+        self.auto_rotate.apply(&mut self.scene.models);
+        packet.apply_model_tints(&mut self.scene.models);
 
         self.directional_light.intensity = packet.light_intensity;
         self.directional_light.direction = Vector3::new(
@@ -818,15 +1126,26 @@ impl Renderer {
         )
         .normalize();
 
-        if (self.camera.fov_x() - packet.camera_fov * PI / 180.0).abs() > 0.01 {
-            self.camera.set_lens(
-                packet.camera_fov * PI / 180.0,
-                self.camera.aspect(),
-                packet.camera_near,
-                packet.camera_far,
-            );
+        packet.apply_camera_lens(&mut self.camera);
+        self.fill_mode = FillMode::from_index(packet.fill_mode);
+
+        // 帧数变化时按新的帧数重建帧资源池（池只记录逻辑 fence 值，不持有 GPU 资源）
+        let frames_in_flight = packet.frames_in_flight(self.pipelined_frames);
+        if frames_in_flight != self.frames_in_flight {
+            self.frames_in_flight = frames_in_flight;
+            self.frame_resource_pool = FrameResourcePool::new(frames_in_flight as usize);
         }
     }
+
+    /// 当前填充模式的管线，不受支持时使用实心填充
+    fn pipeline(&self) -> &Arc<GraphicsPipeline> {
+        let (_, pipeline) = self
+            .pipelines
+            .iter()
+            .find(|(mode, _)| *mode == self.fill_mode)
+            .unwrap_or(&self.pipelines[0]);
+        pipeline
+    }
 }
 
 /// 瀹炵幇缁熶竴鐨勬覆鏌撳悗绔帴鍙?
@@ -840,6 +1159,10 @@ impl crate::renderer::backend_trait::RenderBackend for Renderer {
     }
 
     fn draw(&mut self) -> crate::core::error::Result<()> {
+        self.draw().map(|_| ())
+    }
+
+    fn draw_with_stats(&mut self) -> crate::core::error::Result<FrameStats> {
         self.draw()
     }
 
@@ -847,11 +1170,41 @@ impl crate::renderer::backend_trait::RenderBackend for Renderer {
         self.update(input_system, delta_time)
     }
 
+    fn tick(&mut self, event: &crate::core::event::TickEvent) {
+        self.total_time = event.total_time;
+    }
+
+    fn update_camera(&mut self, input_system: &mut crate::core::input::InputSystem, delta_time: f32) {
+        self.update_camera(input_system, delta_time)
+    }
+
+    fn gui_packet(&self) -> Option<GuiStatePacket> {
+        self.gui_enabled.then(|| self.gui_manager.state().to_packet())
+    }
+
     fn apply_gui_packet(&mut self, packet: &GuiStatePacket) {
         self.apply_gui_packet(packet)
     }
 
-    // handle_gui_event 浣跨敤榛樿瀹炵幇锛堣繑鍥?false锛?
+    fn present_stats(&self) -> PresentStats {
+        self.present_stats
+    }
+
+    fn handle_gui_event(&mut self, event: &winit::event::WindowEvent) -> bool {
+        self.handle_gui_event(event)
+    }
+
+    fn set_gui_enabled(&mut self, enabled: bool) {
+        self.gui_enabled = enabled;
+    }
+
+    fn reload_models(&mut self) -> crate::core::error::Result<()> {
+        self.reload_models()
+    }
+
+    fn wait_idle(&self) -> crate::core::error::Result<()> {
+        self.gfx.wait_idle()
+    }
 }
 
 impl Drop for Renderer {
@@ -869,14 +1222,241 @@ impl Drop for Renderer {
     }
 }
 
+/// 一帧提交后的栅栏 future（等待前一帧、获取交换链图像、执行命令缓冲并呈现）
+type FrameFence =
+    Arc<FenceSignalFuture<PresentFuture<CommandBufferExecFuture<JoinFuture<Box<dyn GpuFuture>, SwapchainAcquireFuture>>>>>;
+
+/// 为场景网格创建顶点和索引缓冲
+fn create_mesh_buffers(
+    gfx: &GfxDevice,
+    scene_mesh: &SceneMesh,
+    debug_names: DebugNames,
+    scene: &SceneConfig,
+) -> Result<(Subbuffer<[MyVertex]>, IndexBuffer)> {
+    let vertex_buffer = Buffer::from_iter(
+        gfx.memory_allocator.clone(),
+        BufferCreateInfo {
+            usage: BufferUsage::VERTEX_BUFFER,
+            ..Default::default()
+        },
+        AllocationCreateInfo {
+            memory_type_filter: MemoryTypeFilter::PREFER_DEVICE | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+            ..Default::default()
+        },
+        scene_mesh.vertices.iter().copied(),
+    )
+    .map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to create vertex buffer: {:?}", e))
+    ))?;
+
+    // 顶点数不超过 65535 时使用 16 位索引
+    let index_buffer_info = BufferCreateInfo {
+        usage: BufferUsage::INDEX_BUFFER,
+        ..Default::default()
+    };
+    let index_allocation_info = AllocationCreateInfo {
+        memory_type_filter: MemoryTypeFilter::PREFER_DEVICE | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+        ..Default::default()
+    };
+    let index_buffer: IndexBuffer = match scene_mesh.index_data() {
+        IndexData::U16(indices) => Buffer::from_iter(
+            gfx.memory_allocator.clone(),
+            index_buffer_info,
+            index_allocation_info,
+            indices,
+        )
+        .map(IndexBuffer::from),
+        IndexData::U32(indices) => Buffer::from_iter(
+            gfx.memory_allocator.clone(),
+            index_buffer_info,
+            index_allocation_info,
+            indices,
+        )
+        .map(IndexBuffer::from),
+    }
+    .map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to create index buffer: {:?}", e))
+    ))?;
+
+    info!("Index buffer created: {} indices ({:?})", index_buffer.len(), index_buffer.index_type());
+    set_debug_name(vertex_buffer.buffer(), debug_names.scene("Vertex Buffer", scene));
+    set_debug_name(index_buffer.as_bytes().buffer(), debug_names.scene("Index Buffer", scene));
+    Ok((vertex_buffer, index_buffer))
+}
+
+/// 设置 Vulkan 对象在 GPU 抓帧中显示的调试名称，`name` 为 `None`（未启用调试名称）时不做任何事
+fn set_debug_name<T: DeviceOwnedVulkanObject>(object: &T, name: Option<String>) {
+    if let Some(name) = name {
+        if let Err(e) = object.set_debug_utils_object_name(Some(&name)) {
+            warn!("Failed to set debug name {}: {:?}", name, e);
+        }
+    }
+}
+
+/// 把交换链的呈现模式转换为呈现统计中的模式
+fn present_mode_kind(mode: PresentMode) -> stats::PresentMode {
+    match mode {
+        PresentMode::Fifo | PresentMode::FifoRelaxed => stats::PresentMode::Fifo,
+        PresentMode::Mailbox => stats::PresentMode::Mailbox,
+        PresentMode::Immediate => stats::PresentMode::Immediate,
+        _ => stats::PresentMode::Unknown,
+    }
+}
+
+/// 格式写入时是否由硬件做 sRGB 编码
+fn is_srgb_format(format: Format) -> bool {
+    format.numeric_format_color() == Some(NumericFormat::SRGB)
+}
+
+/// 把配置中的深度偏移转换为光栅化状态；偏移为 0 时不启用
+///
+/// 非 0 的 `clamp` 需要设备启用 `depth_bias_clamp` 特性，未启用时忽略并给出警告。
+fn depth_bias_state(bias: &DepthBias, gfx: &GfxDevice) -> Option<DepthBiasState> {
+    if !bias.is_enabled() {
+        return None;
+    }
+
+    let clamp = if bias.clamp != 0.0 && !gfx.device.enabled_features().depth_bias_clamp {
+        warn!("depth_bias_clamp feature not enabled, ignoring depth bias clamp");
+        0.0
+    } else {
+        bias.clamp
+    };
+
+    Some(DepthBiasState {
+        constant_factor: bias.constant as f32,
+        clamp,
+        slope_factor: bias.slope_scale,
+    })
+}
+
+/// 设备支持的带模板分量的深度格式
+fn select_depth_stencil_format(gfx: &GfxDevice) -> Option<Format> {
+    [Format::D32_SFLOAT_S8_UINT, Format::D24_UNORM_S8_UINT]
+        .into_iter()
+        .find(|&format| {
+            gfx.device
+                .physical_device()
+                .format_properties(format)
+                .is_ok_and(|properties| {
+                    properties.optimal_tiling_features.intersects(FormatFeatures::DEPTH_STENCIL_ATTACHMENT)
+                })
+        })
+}
+
+/// 深度缓冲的清除值：深度清除为 1.0（最远），模板清除为 0
+fn depth_clear_value(format: Format) -> ClearValue {
+    if format.aspects().intersects(ImageAspects::STENCIL) {
+        ClearValue::DepthStencil((1.0, 0))
+    } else {
+        ClearValue::Depth(1.0)
+    }
+}
+
+/// 把后端无关的模板状态转换为 Vulkan 模板状态（正反面相同）
+fn stencil_state(state: StencilState) -> VkStencilState {
+    let op_state = StencilOpState {
+        ops: StencilOps {
+            fail_op: VkStencilOp::Keep,
+            pass_op: match state.pass_op {
+                StencilOp::Keep => VkStencilOp::Keep,
+                StencilOp::Replace => VkStencilOp::Replace,
+            },
+            depth_fail_op: VkStencilOp::Keep,
+            compare_op: match state.compare {
+                StencilCompare::Always => CompareOp::Always,
+                StencilCompare::Equal => CompareOp::Equal,
+            },
+        },
+        compare_mask: 0xFF,
+        write_mask: state.write_mask,
+        reference: state.reference,
+    };
+    VkStencilState {
+        front: op_state,
+        back: op_state,
+    }
+}
+
+/// 创建模板遮罩管线：用顶点序号生成矩形，只写模板缓冲，不做深度测试也不写颜色
+fn create_stencil_mask_pipeline(gfx: &GfxDevice, render_pass: &Arc<RenderPass>) -> Result<Arc<GraphicsPipeline>> {
+    let vs = stencil_mask_vs::load(gfx.device.clone())
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ShaderCompilation(format!("Failed to load stencil mask vertex shader: {:?}", e))
+        ))?;
+    let fs = stencil_mask_fs::load(gfx.device.clone())
+        .map_err(|e| DistRenderError::Graphics(
+            GraphicsError::ShaderCompilation(format!("Failed to load stencil mask fragment shader: {:?}", e))
+        ))?;
+    let entry_not_found = || DistRenderError::Graphics(
+        GraphicsError::ShaderCompilation("Stencil mask shader 'main' entry point not found".to_string())
+    );
+    let stages = [
+        PipelineShaderStageCreateInfo::new(vs.entry_point("main").ok_or_else(entry_not_found)?),
+        PipelineShaderStageCreateInfo::new(fs.entry_point("main").ok_or_else(entry_not_found)?),
+    ];
+
+    let layout = PipelineLayout::new(
+        gfx.device.clone(),
+        PipelineDescriptorSetLayoutCreateInfo::from_stages(&stages)
+            .into_pipeline_layout_create_info(gfx.device.clone())
+            .map_err(|e| DistRenderError::Graphics(
+                GraphicsError::ResourceCreation(format!("Failed to create stencil mask pipeline layout info: {:?}", e))
+            ))?,
+    )
+    .map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to create stencil mask pipeline layout: {:?}", e))
+    ))?;
+
+    let subpass = Subpass::from(render_pass.clone(), 0)
+        .ok_or_else(|| DistRenderError::Graphics(
+            GraphicsError::ResourceCreation("Failed to create subpass".to_string())
+        ))?;
+
+    GraphicsPipeline::new(
+        gfx.device.clone(),
+        None,
+        vulkano::pipeline::graphics::GraphicsPipelineCreateInfo {
+            stages: stages.into_iter().collect(),
+            vertex_input_state: Some(VertexInputState::new()),
+            input_assembly_state: Some(InputAssemblyState::default()),
+            viewport_state: Some(ViewportState::default()),
+            rasterization_state: Some(RasterizationState::default()),
+            depth_stencil_state: Some(DepthStencilState {
+                stencil: Some(stencil_state(StencilPass::Mark.state())),
+                ..Default::default()
+            }),
+            multisample_state: Some(Default::default()),
+            color_blend_state: Some(ColorBlendState::with_attachment_states(
+                1,
+                ColorBlendAttachmentState {
+                    color_write_mask: ColorComponents::empty(),
+                    ..Default::default()
+                },
+            )),
+            dynamic_state: [vulkano::pipeline::DynamicState::Viewport, vulkano::pipeline::DynamicState::Scissor]
+                .into_iter()
+                .collect(),
+            subpass: Some(subpass.into()),
+            ..vulkano::pipeline::graphics::GraphicsPipelineCreateInfo::layout(layout)
+        },
+    )
+    .map_err(|e| DistRenderError::Graphics(
+        GraphicsError::ResourceCreation(format!("Failed to create stencil mask pipeline: {:?}", e))
+    ))
+}
+
 fn window_size_dependent_setup(
     images: &[Arc<Image>],
     render_pass: Arc<RenderPass>,
     depth_image: Arc<Image>,
     viewport: &mut Viewport,
+    target_aspect: Option<f32>,
 ) -> Result<Vec<Arc<Framebuffer>>> {
     let dimensions = images[0].extent();
-    viewport.extent = [dimensions[0] as f32, dimensions[1] as f32];
+    let rect = ViewportRect::letterbox(dimensions[0], dimensions[1], target_aspect);
+    viewport.offset = [rect.x as f32, rect.y as f32];
+    viewport.extent = [rect.width as f32, rect.height as f32];
 
     let depth_view = ImageView::new_default(depth_image)
         .map_err(|e| DistRenderError::Graphics(
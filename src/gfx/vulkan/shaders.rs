@@ -14,3 +14,31 @@ pub mod fs {
         path: "src/gfx/vulkan/shaders/fragment.glsl",
     }
 }
+
+pub mod stencil_mask_vs {
+    vulkano_shaders::shader! {
+        ty: "vertex",
+        path: "src/gfx/vulkan/shaders/stencil_mask_vertex.glsl",
+    }
+}
+
+pub mod stencil_mask_fs {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        path: "src/gfx/vulkan/shaders/stencil_mask_fragment.glsl",
+    }
+}
+
+pub mod gui_vs {
+    vulkano_shaders::shader! {
+        ty: "vertex",
+        path: "src/gfx/vulkan/shaders/gui_vertex.glsl",
+    }
+}
+
+pub mod gui_fs {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        path: "src/gfx/vulkan/shaders/gui_fragment.glsl",
+    }
+}
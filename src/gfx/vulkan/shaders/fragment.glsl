@@ -7,7 +7,10 @@ layout(binding = 0) uniform UniformBufferObject {
     mat4 projection;
     vec4 lightDir;      // xyz direction
     vec4 lightColor;    // rgb * intensity
-    vec4 cameraPos;
+    vec4 cameraPos;     // xyz position, w: non-zero when output needs manual sRGB encoding
+    vec4 ambient;       // rgb: ambient color * intensity, a: exposure
+    vec4 tint;          // per-model color tint
+    mat4 normalMatrix;  // inverse-transpose of the model matrix
 } ubo;
 
 // Fragment Input
@@ -18,19 +21,28 @@ layout(location = 2) in vec3 fragColor;
 // Fragment Output
 layout(location = 0) out vec4 outColor;
 
+// sRGB swapchains encode on write; UNORM swapchains are encoded here
+vec3 encodeOutput(vec3 color) {
+    if (ubo.cameraPos.w == 0.0) {
+        return color;
+    }
+    vec3 c = clamp(color, 0.0, 1.0);
+    return mix(1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055, c * 12.92, lessThanEqual(c, vec3(0.0031308)));
+}
+
+// Directional Blinn-Phong lighting shared with the other backends; the shared
+// snippet is written with HLSL vector type names
+#define float2 vec2
+#define float3 vec3
+#define float4 vec4
+#include "../../shaders/directional_light.inc"
+
 void main() {
     vec3 N = normalize(fragNormal);
-    vec3 L = normalize(-ubo.lightDir.xyz);
     vec3 V = normalize(ubo.cameraPos.xyz - fragPos);
-    vec3 H = normalize(L + V);
-
-    float diff = max(dot(N, L), 0.0);
-    float spec = diff > 0.0 ? pow(max(dot(N, H), 0.0), 32.0) : 0.0;
-
-    vec3 ambient  = 0.1 * ubo.lightColor.rgb;
-    vec3 diffuse  = diff * ubo.lightColor.rgb;
-    vec3 specular = spec * ubo.lightColor.rgb;
+    vec3 lighting = directional_light(N, V, ubo.lightDir.xyz, ubo.lightColor.rgb, ubo.ambient.rgb);
 
-    vec3 finalColor = (ambient + diffuse + specular) * fragColor;
-    outColor = vec4(finalColor, 1.0);
+    // Exposure scales the lit color; without tonemapping values above 1 are clamped on output
+    vec3 finalColor = lighting * fragColor * ubo.ambient.a * ubo.tint.rgb;
+    outColor = vec4(encodeOutput(finalColor), 1.0);
 }
@@ -0,0 +1,35 @@
+#version 450
+
+// GUI pass: egui blends in gamma space, so the linear texture sample is converted
+// to gamma, modulated by the vertex color, and converted back for sRGB swapchains.
+layout(push_constant) uniform GuiConstants {
+    vec2 screen_size;
+    uint manual_srgb;
+} constants;
+
+layout(set = 0, binding = 0) uniform sampler2D gui_texture;
+
+layout(location = 0) in vec2 frag_uv;
+layout(location = 1) in vec4 frag_color;
+
+layout(location = 0) out vec4 out_color;
+
+vec3 linear_from_gamma(vec3 srgb) {
+    bvec3 cutoff = lessThan(srgb, vec3(0.04045));
+    vec3 lower = srgb / 12.92;
+    vec3 higher = pow((srgb + 0.055) / 1.055, vec3(2.4));
+    return mix(higher, lower, cutoff);
+}
+
+vec3 gamma_from_linear(vec3 rgb) {
+    bvec3 cutoff = lessThan(rgb, vec3(0.0031308));
+    vec3 lower = rgb * 12.92;
+    vec3 higher = 1.055 * pow(rgb, vec3(1.0 / 2.4)) - 0.055;
+    return mix(higher, lower, cutoff);
+}
+
+void main() {
+    vec4 texel = texture(gui_texture, frag_uv);
+    vec4 color = frag_color * vec4(gamma_from_linear(texel.rgb), texel.a);
+    out_color = constants.manual_srgb != 0 ? color : vec4(linear_from_gamma(color.rgb), color.a);
+}
@@ -0,0 +1,21 @@
+#version 450
+
+// GUI pass: egui vertices are in logical points with the origin at the top left,
+// which maps directly onto Vulkan's Y-down clip space.
+layout(push_constant) uniform GuiConstants {
+    vec2 screen_size;   // framebuffer size in points
+    uint manual_srgb;   // non-zero when the swapchain is UNORM
+} constants;
+
+layout(location = 0) in vec2 pos;
+layout(location = 1) in vec2 uv;
+layout(location = 2) in vec4 color;  // sRGB gamma, premultiplied alpha
+
+layout(location = 0) out vec2 frag_uv;
+layout(location = 1) out vec4 frag_color;
+
+void main() {
+    gl_Position = vec4(2.0 * pos / constants.screen_size - 1.0, 0.0, 1.0);
+    frag_uv = uv;
+    frag_color = color;
+}
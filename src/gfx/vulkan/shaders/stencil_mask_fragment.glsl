@@ -0,0 +1,5 @@
+#version 450
+
+// Stencil mask pass: no color output, the pipeline disables color writes.
+void main() {
+}
@@ -0,0 +1,16 @@
+#version 450
+
+// Stencil mask pass: draws a screen-space rectangle (two triangles generated
+// from gl_VertexIndex) that only writes the stencil buffer.
+layout(push_constant) uniform MaskRect {
+    vec4 bounds;    // NDC min x, min y, max x, max y
+} mask;
+
+void main() {
+    const vec2 corners[6] = vec2[](
+        vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(1.0, 1.0),
+        vec2(0.0, 0.0), vec2(1.0, 1.0), vec2(0.0, 1.0)
+    );
+    vec2 corner = corners[gl_VertexIndex];
+    gl_Position = vec4(mix(mask.bounds.xy, mask.bounds.zw, corner), 0.0, 1.0);
+}
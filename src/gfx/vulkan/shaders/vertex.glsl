@@ -8,6 +8,9 @@ layout(binding = 0) uniform UniformBufferObject {
     vec4 lightDir;      // xyz direction
     vec4 lightColor;    // rgb * intensity
     vec4 cameraPos;
+    vec4 ambient;       // rgb: ambient color * intensity, a: exposure
+    vec4 tint;          // per-model color tint
+    mat4 normalMatrix;  // inverse-transpose of the model matrix
 } ubo;
 
 // Vertex Input
@@ -23,7 +26,8 @@ layout(location = 2) out vec3 fragColor;
 void main() {
     vec4 worldPos = ubo.model * vec4(position, 1.0);
     fragPos = worldPos.xyz;
-    fragNormal = mat3(ubo.model) * normal;
+    // The normal matrix keeps normals perpendicular under non-uniform scale; renormalized per fragment
+    fragNormal = mat3(ubo.normalMatrix) * normal;
     fragColor = color;
     gl_Position = ubo.projection * ubo.view * worldPos;
 }
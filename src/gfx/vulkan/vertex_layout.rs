@@ -0,0 +1,50 @@
+//! 顶点布局到 Vulkan 顶点输入状态的转换
+
+use vulkano::format::Format;
+use vulkano::pipeline::graphics::vertex_input::{
+    VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputRate, VertexInputState,
+};
+
+use crate::renderer::resources::vertex_layout::{VertexFormat, VertexLayout};
+
+/// 按布局生成绑定到 `binding` 的逐顶点输入状态，属性位置与布局中的着色器位置一致
+pub fn vertex_input_state(binding: u32, layout: &VertexLayout) -> VertexInputState {
+    let state = VertexInputState::new().binding(binding, VertexInputBindingDescription {
+        stride: layout.stride(),
+        input_rate: VertexInputRate::Vertex,
+    });
+    layout.attributes().iter().fold(state, |state, attribute| {
+        state.attribute(attribute.location, VertexInputAttributeDescription {
+            binding,
+            format: vertex_format(attribute.format),
+            offset: attribute.offset,
+        })
+    })
+}
+
+fn vertex_format(format: VertexFormat) -> Format {
+    match format {
+        VertexFormat::Float32 => Format::R32_SFLOAT,
+        VertexFormat::Float32x2 => Format::R32G32_SFLOAT,
+        VertexFormat::Float32x3 => Format::R32G32B32_SFLOAT,
+        VertexFormat::Float32x4 => Format::R32G32B32A32_SFLOAT,
+        VertexFormat::Uint16x4 => Format::R16G16B16A16_UINT,
+        VertexFormat::Unorm8x4 => Format::R8G8B8A8_UNORM,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::resources::vertex::MyVertex;
+    use crate::renderer::resources::vertex_layout::HasVertexLayout;
+
+    #[test]
+    fn test_attributes_use_layout_locations() {
+        let state = vertex_input_state(0, &MyVertex::vertex_layout());
+        assert_eq!(state.bindings[&0].stride, 36);
+        let offsets: Vec<_> = (0..3).map(|location| state.attributes[&location].offset).collect();
+        assert_eq!(offsets, vec![0, 12, 24]);
+        assert_eq!(state.attributes[&2].format, Format::R32G32B32_SFLOAT);
+    }
+}
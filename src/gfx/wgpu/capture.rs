@@ -0,0 +1,160 @@
+//! 帧截取
+//!
+//! 把渲染目标纹理复制到可映射的缓冲区并同步读回 CPU，转换为 RGBA8 图像。
+//! 纹理需带有 `COPY_SRC` 用途，行字节数按 wgpu 要求对齐到 256 字节。
+//! 截取时可以指定裁剪矩形，只保留模型所在的区域，用于生成紧凑的缩略图。
+//! [`read_texture`] 也用于深度缓冲读回。
+
+use image::{imageops, RgbaImage};
+
+use crate::core::error::{GraphicsError, Result};
+
+/// 把裁剪矩形 `(x, y, width, height)` 限制在 `width` x `height` 的帧缓冲内
+///
+/// 超出边界的部分被截掉，矩形与帧缓冲没有交集时返回 `None`。
+pub fn clamp_region(rect: (u32, u32, u32, u32), width: u32, height: u32) -> Option<(u32, u32, u32, u32)> {
+    let (x, y, w, h) = rect;
+    if x >= width || y >= height {
+        return None;
+    }
+    let w = w.min(width - x);
+    let h = h.min(height - y);
+    (w > 0 && h > 0).then_some((x, y, w, h))
+}
+
+/// 读回纹理内容
+///
+/// 支持 RGBA8 和 BGRA8（含 sRGB 变体）格式，BGRA 会交换红蓝通道。
+/// `region` 为像素单位的裁剪矩形 `(x, y, width, height)`，会被限制在纹理范围内，为 `None` 时返回整帧。
+/// 该调用会等待 GPU 完成之前提交的所有工作。
+pub fn capture_frame(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    region: Option<(u32, u32, u32, u32)>,
+) -> Result<RgbaImage> {
+    let region = match region {
+        Some(rect) => Some(clamp_region(rect, texture.width(), texture.height()).ok_or_else(|| {
+            GraphicsError::ResourceCreation(format!(
+                "Capture region {:?} is outside the {}x{} frame",
+                rect,
+                texture.width(),
+                texture.height()
+            ))
+        })?),
+        None => None,
+    };
+
+    let swap_red_blue = match texture.format() {
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+        format => {
+            return Err(GraphicsError::ResourceCreation(format!("Unsupported capture format: {:?}", format)).into());
+        }
+    };
+
+    let mut pixels = read_texture(device, queue, texture, 4)?;
+
+    if swap_red_blue {
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+
+    let image = RgbaImage::from_raw(texture.width(), texture.height(), pixels)
+        .ok_or_else(|| GraphicsError::ResourceCreation("Capture buffer size mismatch".to_string()))?;
+
+    Ok(match region {
+        Some((x, y, width, height)) => imageops::crop_imm(&image, x, y, width, height).to_image(),
+        None => image,
+    })
+}
+
+/// 把单采样纹理的第 0 层 mip 读回 CPU，返回紧密排列的行数据
+///
+/// `bytes_per_texel` 为每个纹素的字节数。行字节数按 wgpu 要求对齐到 256 字节，读回后去掉填充。
+/// 该调用会等待 GPU 完成之前提交的所有工作。
+pub fn read_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    bytes_per_texel: u32,
+) -> Result<Vec<u8>> {
+    let width = texture.width();
+    let height = texture.height();
+    let unpadded_bytes_per_row = width * bytes_per_texel;
+    let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+        * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Readback Buffer"),
+        size: padded_bytes_per_row as u64 * height as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Readback Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(height),
+            },
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device.poll(wgpu::Maintain::Wait);
+    receiver
+        .recv()
+        .map_err(|e| GraphicsError::CommandExecution(format!("Readback was dropped: {}", e)))?
+        .map_err(|e| GraphicsError::CommandExecution(format!("Failed to map readback buffer: {}", e)))?;
+
+    let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+    {
+        let data = slice.get_mapped_range();
+        for row in data.chunks_exact(padded_bytes_per_row as usize) {
+            pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+        }
+    }
+    buffer.unmap();
+
+    Ok(pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_inside_frame_is_unchanged() {
+        assert_eq!(clamp_region((10, 20, 30, 40), 100, 100), Some((10, 20, 30, 40)));
+    }
+
+    #[test]
+    fn test_region_is_clamped_to_frame() {
+        assert_eq!(clamp_region((80, 90, 50, 50), 100, 100), Some((80, 90, 20, 10)));
+        assert_eq!(clamp_region((0, 0, u32::MAX, u32::MAX), 64, 32), Some((0, 0, 64, 32)));
+    }
+
+    #[test]
+    fn test_region_outside_frame_is_rejected() {
+        assert_eq!(clamp_region((100, 0, 10, 10), 100, 100), None);
+        assert_eq!(clamp_region((0, 0, 0, 10), 100, 100), None);
+    }
+}
@@ -8,13 +8,16 @@
 //! - 閰嶇疆浜ゆ崲閾?
 
 use std::sync::Arc;
-use tracing::{info, debug};
+use tracing::{info, debug, warn};
 use winit::event_loop::EventLoop;
 use winit::window::{Window, WindowBuilder};
 use wgpu;
 
-use crate::gfx::GraphicsBackend;
+use crate::gfx::{DeviceCapabilities, GraphicsBackend};
 use crate::core::Config;
+use crate::core::config::{effective_buffer_count, effective_msaa_samples, SurfaceFormat};
+use crate::gfx::surface_format::select_surface_format;
+use crate::gfx::window::place_window;
 use crate::core::error::{Result, GraphicsError};
 
 /// wgpu 鍥惧舰鍚庣
@@ -63,13 +66,14 @@ impl WgpuContext {
         // 2. 鍒涘缓绐楀彛
         debug!("Creating window");
         let title = format!("{} [{}]", config.window.title, config.graphics.backend.name());
-        let window = WindowBuilder::new()
+        let window_builder = WindowBuilder::new()
             .with_title(title)
             .with_inner_size(winit::dpi::LogicalSize::new(
                 config.window.width,
                 config.window.height,
             ))
-            .with_resizable(config.window.resizable)
+            .with_resizable(config.window.resizable);
+        let window = place_window(window_builder, event_loop, &config.window)
             .build(event_loop)
             .map_err(|e| GraphicsError::DeviceCreation(format!("Failed to create window: {}", e)))?;
 
@@ -96,7 +100,11 @@ impl WgpuContext {
         let (device, queue) = pollster::block_on(adapter.request_device(
             &wgpu::DeviceDescriptor {
                 label: Some("Main Device"),
-                required_features: wgpu::Features::empty(),
+                // 线框和点填充模式在适配器支持时启用；适配器特定的格式特性用于 4 以外的 MSAA 采样数
+                required_features: adapter.features()
+                    & (wgpu::Features::POLYGON_MODE_LINE
+                        | wgpu::Features::POLYGON_MODE_POINT
+                        | wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES),
                 required_limits: wgpu::Limits::default(),
             },
             None,  // 涓嶈窡韪?API 璋冪敤
@@ -105,14 +113,19 @@ impl WgpuContext {
 
         // 6. 閰嶇疆琛ㄩ潰
         let surface_caps = surface.get_capabilities(&adapter);
-        let surface_format = surface_caps
-            .formats
-            .iter()
-            .copied()
-            .find(|f| matches!(f, wgpu::TextureFormat::Bgra8UnormSrgb | wgpu::TextureFormat::Rgba8UnormSrgb))  // 浼樺厛閫夋嫨 sRGB 鏍煎紡
-            .unwrap_or(surface_caps.formats[0]);
-
-        debug!("Surface format: {:?}", surface_format);
+        let surface_format = select_surface_format(
+            &surface_caps.formats,
+            config.graphics.surface_format,
+            |format| match format {
+                wgpu::TextureFormat::Bgra8UnormSrgb => Some(SurfaceFormat::Bgra8Srgb),
+                wgpu::TextureFormat::Rgba8UnormSrgb => Some(SurfaceFormat::Rgba8Srgb),
+                wgpu::TextureFormat::Bgra8Unorm => Some(SurfaceFormat::Bgra8Unorm),
+                wgpu::TextureFormat::Rgba8Unorm => Some(SurfaceFormat::Rgba8Unorm),
+                _ => None,
+            },
+            |format| format.is_srgb(),
+        )
+        .ok_or_else(|| GraphicsError::DeviceCreation("Surface reports no supported formats".to_string()))?;
 
         let present_mode = if config.graphics.vsync {
             wgpu::PresentMode::Fifo  // 鍨傜洿鍚屾
@@ -120,23 +133,36 @@ impl WgpuContext {
             wgpu::PresentMode::Immediate  // 绔嬪嵆鍛堢幇
         };
 
+        // 表面支持时允许复制交换链纹理，用于帧截取
+        let usage = wgpu::TextureUsages::RENDER_ATTACHMENT
+            | (surface_caps.usages & wgpu::TextureUsages::COPY_SRC);
+
+        // wgpu 不直接暴露交换链图像数，帧延迟 N 对应 N + 1 个图像，
+        // 超出表面支持范围时由 wgpu 自行钳制
+        let buffer_count = effective_buffer_count(config.graphics.buffer_count, 2, None);
+        info!(
+            requested = config.graphics.buffer_count,
+            effective = buffer_count,
+            "Swapchain buffer count selected"
+        );
+
         let size = window.inner_size();
         let surface_config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage,
             format: surface_format,
             width: size.width,
             height: size.height,
             present_mode,
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
-            desired_maximum_frame_latency: 2,
+            desired_maximum_frame_latency: buffer_count - 1,
         };
 
         surface.configure(&device, &surface_config);
 
         info!("wgpu backend initialized successfully");
 
-        Ok(Self {
+        let context = Self {
             instance,
             surface,
             adapter,
@@ -144,7 +170,69 @@ impl WgpuContext {
             queue,
             surface_config,
             window,
-        })
+        };
+        context.report_capabilities().log();
+
+        Ok(context)
+    }
+
+    /// 查询适配器能力
+    pub fn report_capabilities(&self) -> DeviceCapabilities {
+        let info = self.adapter.get_info();
+        let features = self.adapter.features();
+        let limits = self.adapter.limits();
+        let format_flags = self.adapter.get_texture_format_features(self.surface_config.format).flags;
+
+        DeviceCapabilities {
+            backend: format!("wgpu ({:?})", info.backend),
+            device_name: info.name,
+            api_version: format!("{} {}", info.driver, info.driver_info).trim().to_string(),
+            max_msaa_samples: [16, 8, 4, 2]
+                .into_iter()
+                .find(|&count| format_flags.sample_count_supported(count))
+                .unwrap_or(1),
+            // wgpu 的 anisotropy_clamp 最大为 16，不支持时由后端忽略
+            max_anisotropy: if self
+                .adapter
+                .get_downlevel_capabilities()
+                .flags
+                .contains(wgpu::DownlevelFlags::ANISOTROPIC_FILTERING)
+            {
+                16.0
+            } else {
+                1.0
+            },
+            timestamp_query: features.contains(wgpu::Features::TIMESTAMP_QUERY),
+            polygon_mode_line: features.contains(wgpu::Features::POLYGON_MODE_LINE),
+            polygon_mode_point: features.contains(wgpu::Features::POLYGON_MODE_POINT),
+            max_texture_dimension_2d: limits.max_texture_dimension_2d,
+            max_uniform_buffer_size: limits.max_uniform_buffer_binding_size as u64,
+            max_vertex_attributes: limits.max_vertex_attributes,
+        }
+    }
+
+    /// 主通道使用的 MSAA 采样数
+    ///
+    /// 取交换链格式和深度格式都支持的、不超过配置值的最大采样数。没有启用适配器特定的
+    /// 格式特性时 wgpu 只保证 4 倍采样可用。
+    pub fn msaa_sample_count(&self, requested: u32, depth_format: wgpu::TextureFormat) -> u32 {
+        let adapter_specific = self
+            .device
+            .features()
+            .contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES);
+        let color_flags = self.adapter.get_texture_format_features(self.surface_config.format).flags;
+        let depth_flags = self.adapter.get_texture_format_features(depth_format).flags;
+
+        let samples = effective_msaa_samples(requested, |count| {
+            (adapter_specific || count == 4)
+                && color_flags.sample_count_supported(count)
+                && depth_flags.sample_count_supported(count)
+        });
+        if samples != requested.max(1) {
+            warn!("MSAA {}x is not supported, using {}x", requested, samples);
+        }
+        info!("MSAA samples: {}", samples);
+        samples
     }
 
     /// 鑾峰彇绐楀彛寮曠敤
@@ -152,6 +240,22 @@ impl WgpuContext {
         &self.window
     }
 
+    /// 等待 GPU 完成所有已提交的工作
+    pub fn wait_idle(&self) -> Result<()> {
+        self.device.poll(wgpu::Maintain::Wait);
+        Ok(())
+    }
+
+    /// 交换链格式不自动做 sRGB 编码，输出颜色需要手动编码
+    pub fn manual_srgb_encoding(&self) -> bool {
+        !self.surface_config.format.is_srgb()
+    }
+
+    /// 交换链缓冲数
+    pub fn buffer_count(&self) -> u32 {
+        self.surface_config.desired_maximum_frame_latency + 1
+    }
+
     /// 閲嶆柊閰嶇疆琛ㄩ潰锛堢敤浜庣獥鍙ｈ皟鏁达級
     pub fn reconfigure_surface(&mut self, width: u32, height: u32) {
         self.surface_config.width = width;
@@ -175,4 +279,50 @@ impl GraphicsBackend for WgpuContext {
     fn backend_name(&self) -> &str {
         "wgpu"
     }
+
+    fn report_capabilities(&self) -> DeviceCapabilities {
+        self.report_capabilities()
+    }
+
+    fn wait_idle(&self) -> Result<()> {
+        self.wait_idle()
+    }
+}
+
+/// 获取交换链图像失败时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceRecovery {
+    /// 表面与窗口尺寸不再匹配或已丢失：按当前窗口尺寸重新配置并跳过这一帧
+    Reconfigure,
+    /// 暂时取不到图像：直接跳过这一帧
+    Skip,
+    /// 无法恢复，返回错误
+    Fail,
+}
+
+impl SurfaceRecovery {
+    /// 按错误类型选择处理方式
+    ///
+    /// 尺寸变化去抖期间表面仍是旧尺寸，部分驱动（Windows 和 X11 上的 Vulkan）会报告 `Outdated`，
+    /// 需要立即重新配置而不是结束渲染循环。
+    pub fn for_error(error: &wgpu::SurfaceError) -> Self {
+        match error {
+            wgpu::SurfaceError::Outdated | wgpu::SurfaceError::Lost => SurfaceRecovery::Reconfigure,
+            wgpu::SurfaceError::Timeout => SurfaceRecovery::Skip,
+            wgpu::SurfaceError::OutOfMemory => SurfaceRecovery::Fail,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outdated_surface_is_reconfigured_instead_of_failing() {
+        assert_eq!(SurfaceRecovery::for_error(&wgpu::SurfaceError::Outdated), SurfaceRecovery::Reconfigure);
+        assert_eq!(SurfaceRecovery::for_error(&wgpu::SurfaceError::Lost), SurfaceRecovery::Reconfigure);
+        assert_eq!(SurfaceRecovery::for_error(&wgpu::SurfaceError::Timeout), SurfaceRecovery::Skip);
+        assert_eq!(SurfaceRecovery::for_error(&wgpu::SurfaceError::OutOfMemory), SurfaceRecovery::Fail);
+    }
 }
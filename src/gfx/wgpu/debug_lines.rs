@@ -0,0 +1,125 @@
+//! 调试线段通道
+//!
+//! 绘制 [`DebugDraw`](crate::renderer::debug_draw::DebugDraw) 生成的三角形几何。
+//! 线段用于 gizmo 等编辑辅助，不做深度测试，总是显示在场景之上。
+
+use wgpu::util::DeviceExt;
+
+use crate::renderer::debug_draw::DebugVertex;
+use crate::renderer::stats::FrameStats;
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// 本帧上传的几何
+struct DebugGeometry {
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+}
+
+/// 调试线段通道
+pub struct DebugLinePass {
+    pipeline: wgpu::RenderPipeline,
+    geometry: Option<DebugGeometry>,
+}
+
+impl DebugLinePass {
+    /// 创建调试线段通道
+    ///
+    /// # 参数
+    ///
+    /// - `color_format`: 主通道颜色目标格式
+    /// - `multisample`: 主通道的多重采样状态
+    pub fn new(device: &wgpu::Device, color_format: wgpu::TextureFormat, multisample: wgpu::MultisampleState) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Debug Line Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/debug_lines.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Debug Line Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Debug Line Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<DebugVertex>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x4, 1 => Float32x3],
+                }],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            // 线段四边形的朝向取决于屏幕方向，不做背面剔除
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            // 主通道带有深度附件，管线需声明相同格式；始终通过且不写入
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample,
+            multiview: None,
+        });
+
+        Self {
+            pipeline,
+            geometry: None,
+        }
+    }
+
+    /// 上传本帧的线段几何，为空时本帧不绘制
+    pub fn prepare(&mut self, device: &wgpu::Device, vertices: &[DebugVertex], indices: &[u32]) {
+        if indices.is_empty() {
+            self.geometry = None;
+            return;
+        }
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Debug Line Vertex Buffer"),
+            contents: bytemuck::cast_slice(vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Debug Line Index Buffer"),
+            contents: bytemuck::cast_slice(indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        self.geometry = Some(DebugGeometry {
+            vertex_buffer,
+            index_buffer,
+            index_count: indices.len() as u32,
+        });
+    }
+
+    /// 在主通道中绘制已上传的线段，必须在场景几何之后调用，绘制调用计入 `stats`
+    pub fn draw<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>, stats: &mut FrameStats) {
+        let Some(geometry) = &self.geometry else {
+            return;
+        };
+
+        pass.set_pipeline(&self.pipeline);
+        pass.set_vertex_buffer(0, geometry.vertex_buffer.slice(..));
+        pass.set_index_buffer(geometry.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        pass.draw_indexed(0..geometry.index_count, 0, 0..1);
+        stats.record_draw(geometry.index_count);
+    }
+}
@@ -0,0 +1,173 @@
+//! 深度缓冲读回
+//!
+//! 调试和测试用：把主通道的深度缓冲读回 CPU 并转换为线性深度，用于验证深度预通道和阴影等功能。
+//! 多重采样纹理不能直接复制，深度格式的复制也有额外限制，因此先用全屏三角形把深度
+//! （多重采样时取第 0 个采样）写入 R32Float 纹理，再按普通颜色纹理读回。
+
+use crate::core::error::Result;
+use crate::gfx::wgpu::capture::read_texture;
+
+/// 中间纹理格式
+const READBACK_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Float;
+const READBACK_TEXEL_SIZE: u32 = 4;
+
+/// 深度读回通道
+pub struct DepthReadback {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+}
+
+impl DepthReadback {
+    /// 创建读回通道，`sample_count` 为深度缓冲的采样数
+    pub fn new(device: &wgpu::Device, sample_count: u32) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Depth Readback Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Depth,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: sample_count > 1,
+                },
+                count: None,
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Depth Readback Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/depth_readback.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Depth Readback Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Depth Readback Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: if sample_count > 1 { "fs_multisampled" } else { "fs_single" },
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: READBACK_FORMAT,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+        }
+    }
+
+    /// 读回深度缓冲并转换为线性深度（视空间距离，按行优先排列）
+    ///
+    /// `depth_view` 需带有 `TEXTURE_BINDING` 用途，`near`/`far` 为绘制时相机的裁剪面。
+    /// 该调用会等待 GPU 完成之前提交的所有工作。
+    pub fn read(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        depth_view: &wgpu::TextureView,
+        (width, height): (u32, u32),
+        (near, far): (f32, f32),
+    ) -> Result<Vec<f32>> {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Depth Readback Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: READBACK_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Depth Readback Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(depth_view),
+            }],
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Depth Readback Encoder"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Depth Readback Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let bytes = read_texture(device, queue, &texture, READBACK_TEXEL_SIZE)?;
+        Ok(bytes
+            .chunks_exact(READBACK_TEXEL_SIZE as usize)
+            .map(|texel| linearize_depth(f32::from_le_bytes([texel[0], texel[1], texel[2], texel[3]]), near, far))
+            .collect())
+    }
+}
+
+/// 深度缓冲中的值转换为视空间距离
+///
+/// 投影矩阵为 OpenGL 风格（NDC 深度 [-1, 1]），深度缓冲直接保存 NDC 深度，
+/// 清除值 1.0 对应远裁剪面。
+pub fn linearize_depth(depth: f32, near: f32, far: f32) -> f32 {
+    2.0 * far * near / ((far + near) - depth * (far - near))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{Matrix4, Vector4};
+
+    #[test]
+    fn test_linearize_depth_matches_projection() {
+        let (near, far) = (0.1, 100.0);
+        let proj = Matrix4::new_perspective(16.0 / 9.0, 1.0, near, far);
+
+        for distance in [0.5, 2.0, 10.0, 75.0] {
+            // 右手坐标系视空间中相机看向 -z
+            let clip = proj * Vector4::new(0.0, 0.0, -distance, 1.0);
+            let depth = clip.z / clip.w;
+            assert!((linearize_depth(depth, near, far) - distance).abs() < distance * 1e-3);
+        }
+        assert!((linearize_depth(1.0, near, far) - far).abs() < far * 1e-3);
+    }
+}
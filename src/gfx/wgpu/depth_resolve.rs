@@ -0,0 +1,178 @@
+//! 多重采样深度解析
+//!
+//! 开启 MSAA 时主通道的深度缓冲是多重采样的，之后的通道无法直接按普通深度纹理采样。
+//! 该通道用全屏三角形读取每个像素的全部采样，按配置取最小值、最大值或平均值，
+//! 写入单采样的深度纹理。取最小值时几何边缘的像素保留前景深度，不会得到前景和背景之间并不存在的深度，
+//! 因此推荐使用。
+
+use crate::core::config::DepthResolveMode;
+use crate::gfx::wgpu::targets::{MainTargets, DEPTH_FORMAT};
+use crate::gfx::wgpu::texture::texture_byte_size;
+use crate::renderer::resources::resource_stats::{ResourceKind, ResourceStats, Tracked};
+use crate::renderer::stats::FrameStats;
+
+/// 深度解析通道
+pub struct DepthResolvePass {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    /// 绑定主通道多重采样深度的绑定组，随目标尺寸重建
+    bind_group: wgpu::BindGroup,
+    /// 解析后的单采样深度
+    resolved_view: Tracked<wgpu::TextureView>,
+}
+
+impl DepthResolvePass {
+    /// 创建解析通道，`targets` 需为多重采样目标
+    pub fn new(device: &wgpu::Device, stats: &ResourceStats, mode: DepthResolveMode, targets: &MainTargets) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Depth Resolve Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Depth,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: true,
+                },
+                count: None,
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Depth Resolve Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/depth_resolve.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Depth Resolve Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Depth Resolve Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            // 只写深度，没有颜色目标
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: entry_point(mode),
+                targets: &[],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
+        let bind_group = create_bind_group(device, &bind_group_layout, targets);
+        let resolved_view = create_resolved_view(device, stats, targets.size());
+        Self {
+            pipeline,
+            bind_group_layout,
+            bind_group,
+            resolved_view,
+        }
+    }
+
+    /// 主通道目标重建后重建解析目标和绑定组
+    pub fn resize(&mut self, device: &wgpu::Device, stats: &ResourceStats, targets: &MainTargets) {
+        self.bind_group = create_bind_group(device, &self.bind_group_layout, targets);
+        self.resolved_view = create_resolved_view(device, stats, targets.size());
+    }
+
+    /// 解析后的单采样深度（`TEXTURE_BINDING` 用途，可供之后的通道采样）
+    pub fn resolved_view(&self) -> &wgpu::TextureView {
+        &self.resolved_view
+    }
+
+    /// 编码解析通道，必须在主通道之后调用
+    pub fn encode(&self, encoder: &mut wgpu::CommandEncoder, stats: &mut FrameStats) {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Depth Resolve Pass"),
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.resolved_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+        stats.record_draw(3);
+    }
+}
+
+/// 解析方式对应的片段着色器入口
+fn entry_point(mode: DepthResolveMode) -> &'static str {
+    match mode {
+        DepthResolveMode::Min => "fs_min",
+        DepthResolveMode::Max => "fs_max",
+        DepthResolveMode::Average => "fs_average",
+    }
+}
+
+fn create_bind_group(device: &wgpu::Device, layout: &wgpu::BindGroupLayout, targets: &MainTargets) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Depth Resolve Bind Group"),
+        layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::TextureView(targets.depth_view()),
+        }],
+    })
+}
+
+fn create_resolved_view(
+    device: &wgpu::Device,
+    stats: &ResourceStats,
+    (width, height): (u32, u32),
+) -> Tracked<wgpu::TextureView> {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Resolved Depth Texture"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: DEPTH_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    stats.track(view, ResourceKind::Texture, texture_byte_size(&texture))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_points_exist_in_shader() {
+        let source = include_str!("shaders/depth_resolve.wgsl");
+        for mode in [DepthResolveMode::Min, DepthResolveMode::Max, DepthResolveMode::Average] {
+            assert!(source.contains(&format!("fn {}(", entry_point(mode))));
+        }
+    }
+}
@@ -0,0 +1,730 @@
+//! 图像光照（IBL）
+//!
+//! 启动时用计算着色器从等距柱状投影的环境贴图预计算三张贴图：
+//! - 辐照度立方体贴图：法线半球内余弦加权的积分，用于环境漫反射
+//! - 预过滤环境立方体贴图：按 GGX 分布加权，每级 mip 对应一个粗糙度，用于环境镜面反射
+//! - BRDF 积分查找表：按 (N·V, 粗糙度) 存放 Fresnel 项的缩放和偏移
+//!
+//! 预计算结果读回后按环境贴图内容、预计算着色器和贴图尺寸的哈希缓存到 [`CACHE_DIR`]，
+//! 环境不变时下次启动直接上传缓存。没有配置环境贴图或加载失败时 PBR 着色使用常量环境光。
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use bytemuck::{Pod, Zeroable};
+use tracing::{info, warn};
+use wgpu::util::DeviceExt;
+
+use crate::core::error::{DistRenderError, GraphicsError, Result};
+
+/// 辐照度贴图每个面的边长
+pub const IRRADIANCE_SIZE: u32 = 32;
+
+/// 预过滤环境贴图第 0 级每个面的边长及 mip 级数（最后一级对应粗糙度 1）
+pub const PREFILTER_SIZE: u32 = 128;
+pub const PREFILTER_MIP_LEVELS: u32 = 5;
+
+/// BRDF 查找表的边长
+pub const BRDF_LUT_SIZE: u32 = 128;
+
+/// 预计算结果的缓存目录
+pub const CACHE_DIR: &str = "cache/ibl";
+
+/// 上传前把环境贴图缩小到的最大宽度，降低预计算的耗时和欠采样噪声
+const MAX_ENVIRONMENT_WIDTH: u32 = 1024;
+
+const MAP_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+const MAP_TEXEL_SIZE: u32 = 8;
+const WORKGROUP_SIZE: u32 = 8;
+const CACHE_MAGIC: &[u8; 4] = b"IBL1";
+const SHADER_SOURCE: &str = include_str!("shaders/ibl.wgsl");
+
+/// IBL Uniform（布局与 shader.wgsl 中的 IblParams 一致）
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+struct IblUniform {
+    intensity: f32,
+    max_lod: f32,
+    enabled: u32,
+    _padding: u32,
+}
+
+/// 预过滤参数（布局与 ibl.wgsl 中的 PrefilterParams 一致）
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+struct PrefilterUniform {
+    roughness: f32,
+    _padding: [f32; 3],
+}
+
+/// 预计算的三张贴图
+struct IblTextures {
+    irradiance: wgpu::Texture,
+    prefiltered: wgpu::Texture,
+    brdf_lut: wgpu::Texture,
+}
+
+impl IblTextures {
+    fn new(device: &wgpu::Device, irradiance_size: u32, prefilter_size: u32, mip_levels: u32, lut_size: u32) -> Self {
+        let create = |label: &str, size: u32, layers: u32, mip_level_count: u32| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width: size,
+                    height: size,
+                    depth_or_array_layers: layers,
+                },
+                mip_level_count,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: MAP_FORMAT,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::STORAGE_BINDING
+                    | wgpu::TextureUsages::COPY_SRC
+                    | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            })
+        };
+
+        Self {
+            irradiance: create("IBL Irradiance Map", irradiance_size, 6, 1),
+            prefiltered: create("IBL Prefiltered Map", prefilter_size, 6, mip_levels),
+            brdf_lut: create("IBL BRDF LUT", lut_size, 1, 1),
+        }
+    }
+
+    /// 所有贴图的所有 mip 级别，顺序与缓存数据一致
+    fn levels(&self) -> Vec<(&wgpu::Texture, u32)> {
+        let mut levels = vec![(&self.irradiance, 0)];
+        levels.extend((0..self.prefiltered.mip_level_count()).map(|mip| (&self.prefiltered, mip)));
+        levels.push((&self.brdf_lut, 0));
+        levels
+    }
+
+    /// 缓存数据的总字节数
+    fn byte_size(&self) -> usize {
+        self.levels()
+            .into_iter()
+            .map(|(texture, mip)| {
+                let extent = level_extent(texture, mip);
+                (extent.width * extent.height * extent.depth_or_array_layers * MAP_TEXEL_SIZE) as usize
+            })
+            .sum()
+    }
+
+    /// 上传缓存数据（各级别紧密排列）
+    fn upload(&self, queue: &wgpu::Queue, data: &[u8]) {
+        let mut offset = 0;
+        for (texture, mip) in self.levels() {
+            let extent = level_extent(texture, mip);
+            let size = (extent.width * extent.height * extent.depth_or_array_layers * MAP_TEXEL_SIZE) as usize;
+            queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    texture,
+                    mip_level: mip,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &data[offset..offset + size],
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(extent.width * MAP_TEXEL_SIZE),
+                    rows_per_image: Some(extent.height),
+                },
+                extent,
+            );
+            offset += size;
+        }
+    }
+
+    /// 读回所有级别（各级别紧密排列），会等待 GPU 完成之前提交的所有工作
+    fn read_back(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Result<Vec<u8>> {
+        let levels: Vec<(&wgpu::Texture, u32, wgpu::Extent3d, u32)> = self
+            .levels()
+            .into_iter()
+            .map(|(texture, mip)| {
+                let extent = level_extent(texture, mip);
+                let padded_bytes_per_row = (extent.width * MAP_TEXEL_SIZE)
+                    .div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+                    * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+                (texture, mip, extent, padded_bytes_per_row)
+            })
+            .collect();
+        let buffer_size: u64 = levels
+            .iter()
+            .map(|(_, _, extent, row)| u64::from(*row) * u64::from(extent.height * extent.depth_or_array_layers))
+            .sum();
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("IBL Readback Buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("IBL Readback Encoder"),
+        });
+        let mut offset = 0;
+        for &(texture, mip, extent, padded_bytes_per_row) in &levels {
+            encoder.copy_texture_to_buffer(
+                wgpu::ImageCopyTexture {
+                    texture,
+                    mip_level: mip,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::ImageCopyBuffer {
+                    buffer: &buffer,
+                    layout: wgpu::ImageDataLayout {
+                        offset,
+                        bytes_per_row: Some(padded_bytes_per_row),
+                        rows_per_image: Some(extent.height),
+                    },
+                },
+                extent,
+            );
+            offset += u64::from(padded_bytes_per_row) * u64::from(extent.height * extent.depth_or_array_layers);
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .map_err(|e| GraphicsError::CommandExecution(format!("IBL readback was dropped: {}", e)))?
+            .map_err(|e| GraphicsError::CommandExecution(format!("Failed to map IBL readback buffer: {}", e)))?;
+
+        let mut data = Vec::with_capacity(self.byte_size());
+        {
+            let mapped = slice.get_mapped_range();
+            let mut rows = mapped.as_ref();
+            for &(_, _, extent, padded_bytes_per_row) in &levels {
+                let row_count = (extent.height * extent.depth_or_array_layers) as usize;
+                let (level, rest) = rows.split_at(padded_bytes_per_row as usize * row_count);
+                for row in level.chunks_exact(padded_bytes_per_row as usize) {
+                    data.extend_from_slice(&row[..(extent.width * MAP_TEXEL_SIZE) as usize]);
+                }
+                rows = rest;
+            }
+        }
+        buffer.unmap();
+
+        Ok(data)
+    }
+}
+
+/// 图像光照资源：预计算的贴图及 PBR 着色使用的绑定组
+pub struct IblMaps {
+    layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    /// 辐照度、预过滤环境和 BRDF 查找表的视图（切换过滤模式时重建绑定组）
+    views: [wgpu::TextureView; 3],
+    uniform_buffer: wgpu::Buffer,
+    max_lod: f32,
+    enabled: bool,
+}
+
+impl IblMaps {
+    /// 创建图像光照资源
+    ///
+    /// `environment` 为环境贴图路径。未配置或加载失败时使用 1x1 的空贴图并关闭图像光照。
+    /// `sampler` 为采样三张贴图使用的采样器。
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        environment: Option<&str>,
+        sampler: &wgpu::Sampler,
+    ) -> Self {
+        let textures = environment.and_then(|path| match load_or_precompute(device, queue, Path::new(path)) {
+            Ok(textures) => Some(textures),
+            Err(e) => {
+                warn!("Failed to prepare IBL from {}: {}, using constant ambient light", path, e);
+                None
+            }
+        });
+        let enabled = textures.is_some();
+        let textures = textures.unwrap_or_else(|| IblTextures::new(device, 1, 1, 1, 1));
+        let max_lod = (textures.prefiltered.mip_level_count() - 1) as f32;
+
+        let texture_entry = |binding: u32, view_dimension: wgpu::TextureViewDimension| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("IBL Bind Group Layout"),
+            entries: &[
+                texture_entry(0, wgpu::TextureViewDimension::Cube),
+                texture_entry(1, wgpu::TextureViewDimension::Cube),
+                texture_entry(2, wgpu::TextureViewDimension::D2),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("IBL Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[IblUniform {
+                intensity: 1.0,
+                max_lod,
+                enabled: enabled as u32,
+                _padding: 0,
+            }]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let cube_view = |texture: &wgpu::Texture| {
+            texture.create_view(&wgpu::TextureViewDescriptor {
+                dimension: Some(wgpu::TextureViewDimension::Cube),
+                ..Default::default()
+            })
+        };
+        let views = [
+            cube_view(&textures.irradiance),
+            cube_view(&textures.prefiltered),
+            textures.brdf_lut.create_view(&wgpu::TextureViewDescriptor::default()),
+        ];
+        let bind_group = create_bind_group(device, &layout, &views, &uniform_buffer, sampler);
+
+        Self {
+            layout,
+            bind_group,
+            views,
+            uniform_buffer,
+            max_lod,
+            enabled,
+        }
+    }
+
+    /// 主通道使用的绑定组布局
+    pub fn layout(&self) -> &wgpu::BindGroupLayout {
+        &self.layout
+    }
+
+    /// 主通道使用的绑定组（三张贴图 + 采样器 + 参数）
+    pub fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.bind_group
+    }
+
+    /// 是否有可用的环境贴图
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// 切换采样器（过滤模式变化时），只重建绑定组
+    pub fn set_sampler(&mut self, device: &wgpu::Device, sampler: &wgpu::Sampler) {
+        self.bind_group = create_bind_group(device, &self.layout, &self.views, &self.uniform_buffer, sampler);
+    }
+
+    /// 更新 IBL 强度
+    pub fn update(&self, queue: &wgpu::Queue, intensity: f32) {
+        let uniform = IblUniform {
+            intensity,
+            max_lod: self.max_lod,
+            enabled: self.enabled as u32,
+            _padding: 0,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniform]));
+    }
+}
+
+/// 创建主通道使用的绑定组（三张贴图 + 采样器 + 参数）
+fn create_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    views: &[wgpu::TextureView; 3],
+    uniform_buffer: &wgpu::Buffer,
+    sampler: &wgpu::Sampler,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("IBL Bind Group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&views[0]),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(&views[1]),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::TextureView(&views[2]),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+            wgpu::BindGroupEntry {
+                binding: 4,
+                resource: uniform_buffer.as_entire_binding(),
+            },
+        ],
+    })
+}
+
+/// 读取缓存，缓存不存在或已失效时预计算并写入缓存
+fn load_or_precompute(device: &wgpu::Device, queue: &wgpu::Queue, path: &Path) -> Result<IblTextures> {
+    let source = std::fs::read(path)?;
+    let key = cache_key(&source);
+    let cache_path = cache_path(key);
+    let textures = IblTextures::new(device, IRRADIANCE_SIZE, PREFILTER_SIZE, PREFILTER_MIP_LEVELS, BRDF_LUT_SIZE);
+
+    if let Ok(cached) = std::fs::read(&cache_path) {
+        if let Some(data) = decode_cache(&cached, key, textures.byte_size()) {
+            textures.upload(queue, data);
+            info!("Loaded IBL maps from cache {}", cache_path.display());
+            return Ok(textures);
+        }
+        warn!("IBL cache {} is invalid, recomputing", cache_path.display());
+    }
+
+    let start = Instant::now();
+    let image = image::load_from_memory(&source).map_err(|e| DistRenderError::Runtime(e.to_string()))?;
+    let environment = create_environment_texture(device, queue, image);
+    precompute(device, queue, &environment, &textures);
+
+    match textures.read_back(device, queue) {
+        Ok(data) => {
+            info!("Precomputed IBL maps in {:.1} ms", start.elapsed().as_secs_f32() * 1000.0);
+            let written = std::fs::create_dir_all(CACHE_DIR)
+                .and_then(|_| std::fs::write(&cache_path, encode_cache(key, &data)));
+            if let Err(e) = written {
+                warn!("Failed to write IBL cache {}: {}", cache_path.display(), e);
+            }
+        }
+        Err(e) => warn!("Failed to read back IBL maps, they will not be cached: {}", e),
+    }
+
+    Ok(textures)
+}
+
+/// 上传环境贴图（线性空间 RGBA32F），LDR 图像从 sRGB 转换到线性空间，过宽时先缩小
+fn create_environment_texture(device: &wgpu::Device, queue: &wgpu::Queue, image: image::DynamicImage) -> wgpu::Texture {
+    let pixels = environment_pixels(image);
+    let (width, height) = pixels.dimensions();
+
+    device.create_texture_with_data(
+        queue,
+        &wgpu::TextureDescriptor {
+            label: Some("IBL Environment Map"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba32Float,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        },
+        wgpu::util::TextureDataOrder::LayerMajor,
+        bytemuck::cast_slice(pixels.as_raw()),
+    )
+}
+
+/// 转换为线性空间的 RGBA32F 像素
+fn environment_pixels(image: image::DynamicImage) -> image::Rgba32FImage {
+    let is_hdr = matches!(image.color(), image::ColorType::Rgb32F | image::ColorType::Rgba32F);
+    let mut pixels = image.into_rgba32f();
+    if !is_hdr {
+        for pixel in pixels.pixels_mut() {
+            for channel in &mut pixel.0[..3] {
+                *channel = srgb_to_linear(*channel);
+            }
+        }
+    }
+
+    if pixels.width() > MAX_ENVIRONMENT_WIDTH {
+        let height = (pixels.height() * MAX_ENVIRONMENT_WIDTH / pixels.width()).max(1);
+        pixels = image::imageops::resize(&pixels, MAX_ENVIRONMENT_WIDTH, height, image::imageops::FilterType::Triangle);
+    }
+    pixels
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// 录制并提交三个预计算通道
+fn precompute(device: &wgpu::Device, queue: &wgpu::Queue, environment: &wgpu::Texture, textures: &IblTextures) {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("IBL Shader"),
+        source: wgpu::ShaderSource::Wgsl(SHADER_SOURCE.into()),
+    });
+
+    // 显式声明绑定布局：环境贴图为 RGBA32F，着色器只用 textureLoad 读取，声明为不可过滤，
+    // 不依赖设备对 32 位浮点纹理过滤的支持（自动推导的布局要求可过滤）
+    let environment_entry = wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+        },
+        count: None,
+    };
+    let storage_entry = |binding: u32, view_dimension: wgpu::TextureViewDimension| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::StorageTexture {
+            access: wgpu::StorageTextureAccess::WriteOnly,
+            format: wgpu::TextureFormat::Rgba16Float,
+            view_dimension,
+        },
+        count: None,
+    };
+    let cube_output_entry = storage_entry(1, wgpu::TextureViewDimension::D2Array);
+    let prefilter_entry = wgpu::BindGroupLayoutEntry {
+        binding: 2,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    };
+    let brdf_output_entry = storage_entry(3, wgpu::TextureViewDimension::D2);
+
+    let pipeline = |label: &str, entry_point: &str, entries: &[wgpu::BindGroupLayoutEntry]| {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(label),
+            entries,
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(label),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some(label),
+            layout: Some(&layout),
+            module: &shader,
+            entry_point,
+        });
+        (pipeline, bind_group_layout)
+    };
+    let (irradiance_pipeline, irradiance_layout) =
+        pipeline("IBL Irradiance Pipeline", "cs_irradiance", &[environment_entry, cube_output_entry]);
+    let (prefilter_pipeline, prefilter_layout) = pipeline(
+        "IBL Prefilter Pipeline",
+        "cs_prefilter",
+        &[environment_entry, cube_output_entry, prefilter_entry],
+    );
+    let (brdf_pipeline, brdf_layout) = pipeline("IBL BRDF Pipeline", "cs_brdf", &[brdf_output_entry]);
+
+    let environment_view = environment.create_view(&wgpu::TextureViewDescriptor::default());
+    let storage_view = |texture: &wgpu::Texture, mip: u32, dimension: wgpu::TextureViewDimension| {
+        texture.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(dimension),
+            base_mip_level: mip,
+            mip_level_count: Some(1),
+            ..Default::default()
+        })
+    };
+
+    // 每个通道一组绑定和调度尺寸
+    let mut dispatches: Vec<(&wgpu::ComputePipeline, wgpu::BindGroup, wgpu::Extent3d)> = Vec::new();
+    let irradiance_view = storage_view(&textures.irradiance, 0, wgpu::TextureViewDimension::D2Array);
+    dispatches.push((
+        &irradiance_pipeline,
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("IBL Irradiance Bind Group"),
+            layout: &irradiance_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&environment_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&irradiance_view),
+                },
+            ],
+        }),
+        level_extent(&textures.irradiance, 0),
+    ));
+
+    let mip_levels = textures.prefiltered.mip_level_count();
+    for mip in 0..mip_levels {
+        let roughness = mip as f32 / (mip_levels - 1).max(1) as f32;
+        let params = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("IBL Prefilter Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[PrefilterUniform {
+                roughness,
+                _padding: [0.0; 3],
+            }]),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let view = storage_view(&textures.prefiltered, mip, wgpu::TextureViewDimension::D2Array);
+        dispatches.push((
+            &prefilter_pipeline,
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("IBL Prefilter Bind Group"),
+                layout: &prefilter_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&environment_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: params.as_entire_binding(),
+                    },
+                ],
+            }),
+            level_extent(&textures.prefiltered, mip),
+        ));
+    }
+
+    let brdf_view = storage_view(&textures.brdf_lut, 0, wgpu::TextureViewDimension::D2);
+    dispatches.push((
+        &brdf_pipeline,
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("IBL BRDF Bind Group"),
+            layout: &brdf_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::TextureView(&brdf_view),
+            }],
+        }),
+        level_extent(&textures.brdf_lut, 0),
+    ));
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("IBL Precompute Encoder"),
+    });
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("IBL Precompute Pass"),
+            timestamp_writes: None,
+        });
+        for (pipeline, bind_group, extent) in &dispatches {
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.dispatch_workgroups(
+                extent.width.div_ceil(WORKGROUP_SIZE),
+                extent.height.div_ceil(WORKGROUP_SIZE),
+                extent.depth_or_array_layers,
+            );
+        }
+    }
+    queue.submit(std::iter::once(encoder.finish()));
+}
+
+/// 贴图某一级 mip 的尺寸（含数组层数）
+fn level_extent(texture: &wgpu::Texture, mip: u32) -> wgpu::Extent3d {
+    wgpu::Extent3d {
+        width: (texture.width() >> mip).max(1),
+        height: (texture.height() >> mip).max(1),
+        depth_or_array_layers: texture.depth_or_array_layers(),
+    }
+}
+
+/// 缓存键：环境贴图内容、预计算着色器和贴图尺寸的哈希
+fn cache_key(source: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    SHADER_SOURCE.hash(&mut hasher);
+    (IRRADIANCE_SIZE, PREFILTER_SIZE, PREFILTER_MIP_LEVELS, BRDF_LUT_SIZE, MAX_ENVIRONMENT_WIDTH).hash(&mut hasher);
+    hasher.finish()
+}
+
+fn cache_path(key: u64) -> PathBuf {
+    Path::new(CACHE_DIR).join(format!("{:016x}.bin", key))
+}
+
+/// 缓存文件：魔数、缓存键和贴图数据
+fn encode_cache(key: u64, data: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(CACHE_MAGIC.len() + 8 + data.len());
+    bytes.extend_from_slice(CACHE_MAGIC);
+    bytes.extend_from_slice(&key.to_le_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+/// 校验缓存文件并返回贴图数据，魔数、缓存键或长度不符时返回 `None`
+fn decode_cache(bytes: &[u8], key: u64, expected_len: usize) -> Option<&[u8]> {
+    let rest = bytes.strip_prefix(CACHE_MAGIC)?;
+    let (stored_key, data) = rest.split_at_checked(8)?;
+    (stored_key == key.to_le_bytes() && data.len() == expected_len).then_some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_round_trip() {
+        let data = vec![1u8, 2, 3, 4, 5, 6, 7, 8];
+        let bytes = encode_cache(42, &data);
+
+        assert_eq!(decode_cache(&bytes, 42, data.len()), Some(data.as_slice()));
+        // 环境贴图变化（键不同）或尺寸变化时缓存失效
+        assert_eq!(decode_cache(&bytes, 43, data.len()), None);
+        assert_eq!(decode_cache(&bytes, 42, data.len() + 8), None);
+        assert_eq!(decode_cache(&bytes[..6], 42, 0), None);
+    }
+
+    #[test]
+    fn test_cache_key_depends_on_source() {
+        assert_eq!(cache_key(b"sky"), cache_key(b"sky"));
+        assert_ne!(cache_key(b"sky"), cache_key(b"sky2"));
+        assert!(cache_path(cache_key(b"sky")).starts_with(CACHE_DIR));
+    }
+
+    #[test]
+    fn test_ldr_environment_is_linearized_and_downscaled() {
+        let ldr = image::RgbaImage::from_pixel(1280, 640, image::Rgba([255, 188, 0, 255]));
+        let pixels = environment_pixels(image::DynamicImage::ImageRgba8(ldr));
+
+        assert_eq!(pixels.dimensions(), (MAX_ENVIRONMENT_WIDTH, MAX_ENVIRONMENT_WIDTH / 2));
+        let pixel = pixels.get_pixel(10, 10).0;
+        assert!((pixel[0] - 1.0).abs() < 1e-4);
+        assert!((pixel[1] - 0.5).abs() < 0.01);
+        assert_eq!(pixel[2], 0.0);
+        assert_eq!(pixel[3], 1.0);
+
+        // HDR 图像本身就是线性空间
+        let hdr = image::Rgba32FImage::from_pixel(4, 2, image::Rgba([4.0, 0.5, 0.0, 1.0]));
+        let pixels = environment_pixels(image::DynamicImage::ImageRgba32F(hdr));
+        assert_eq!(pixels.get_pixel(0, 0).0, [4.0, 0.5, 0.0, 1.0]);
+    }
+}
@@ -7,9 +7,37 @@
 //!
 //! - `backend` - WgpuBackend 结构（设备初始化和管理）
 //! - `renderer` - Renderer 结构（渲染逻辑实现）
+//! - `render_graph` - 每帧通道的依赖排序
+//! - `ssao` - SSAO 屏幕空间环境光遮蔽通道
+//! - `ibl` - 基于环境贴图的图像光照预计算
+//! - `background` - 渐变背景
+//! - `sampler` - 按纹理过滤模式创建采样器
+//! - `texture` - 模型纹理加载
+//! - `outline` - 选中子网格的轮廓高亮
+//! - `wireframe` - 实心着色之上的线框叠加
+//! - `debug_lines` - 调试线段（平移 gizmo 等）
+//! - `targets` - 主通道的深度和 MSAA 颜色目标
+//! - `capture` - 帧截取（转台录制）
+//! - `depth_readback` - 深度缓冲读回（调试和测试）
+//! - `depth_resolve` - 多重采样深度解析
+//! - `vertex_layout` - 顶点布局到 wgpu 顶点缓冲布局的转换
 
+mod background;
+mod capture;
 mod context;
+mod debug_lines;
+mod depth_readback;
+mod depth_resolve;
+mod ibl;
+mod outline;
+mod render_graph;
 mod renderer;
+mod sampler;
+mod ssao;
+mod targets;
+mod texture;
+mod vertex_layout;
+mod wireframe;
 
 pub use context::WgpuContext;
 pub use renderer::Renderer;
@@ -0,0 +1,178 @@
+//! 选中轮廓
+//!
+//! 使用反向外壳（inverted hull）绘制选中子网格的轮廓：在主通道之后再绘制一次选中的子网格，
+//! 顶点沿法线在屏幕空间外扩固定像素宽度，只保留背面并以纯色输出。
+//! 深度测试使用主通道的深度缓冲，外壳只在物体轮廓外侧可见。
+
+use std::ops::Range;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use crate::gfx::wgpu::vertex_layout::WgpuVertexLayout;
+use crate::renderer::resources::vertex::MyVertex;
+
+/// 轮廓宽度（像素）
+pub const OUTLINE_WIDTH: f32 = 3.0;
+
+/// 默认轮廓颜色（橙色）
+pub const DEFAULT_OUTLINE_COLOR: [f32; 4] = [1.0, 0.6, 0.0, 1.0];
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// 轮廓 Uniform（布局与 outline.wgsl 中的 OutlineParams 一致）
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+struct OutlineUniform {
+    color: [f32; 4],
+    viewport: [f32; 2],
+    width: f32,
+    _padding: f32,
+}
+
+/// 选中轮廓通道
+pub struct OutlinePass {
+    pipeline: wgpu::RenderPipeline,
+    /// 三角形带使用的管线
+    strip_pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+impl OutlinePass {
+    /// 创建轮廓通道
+    ///
+    /// # 参数
+    ///
+    /// - `uniform_layout`: 主通道的 UBO 绑定组布局
+    /// - `color_format`: 主通道颜色目标格式
+    /// - `multisample`: 主通道的多重采样状态
+    /// - `depth_bias`: 外壳与主通道表面比较深度时使用的偏移
+    pub fn new(
+        device: &wgpu::Device,
+        uniform_layout: &wgpu::BindGroupLayout,
+        color_format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+        depth_bias: wgpu::DepthBiasState,
+    ) -> Self {
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Outline Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[OutlineUniform {
+                color: DEFAULT_OUTLINE_COLOR,
+                viewport: [1.0, 1.0],
+                width: OUTLINE_WIDTH,
+                _padding: 0.0,
+            }]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Outline Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Outline Bind Group"),
+            layout: &layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Outline Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/outline.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Outline Pipeline Layout"),
+            bind_group_layouts: &[uniform_layout, &layout],
+            push_constant_ranges: &[],
+        });
+        let mesh_layout = WgpuVertexLayout::of::<MyVertex>();
+        let create_pipeline = |label: &str, topology: wgpu::PrimitiveTopology| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vs_main",
+                    buffers: &[mesh_layout.buffer_layout()],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: "fs_main",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: color_format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                // 只绘制外壳背面，正面被物体本身遮挡
+                primitive: wgpu::PrimitiveState {
+                    topology,
+                    // 三角形带只出现在 32 位索引缓冲中
+                    strip_index_format: (topology == wgpu::PrimitiveTopology::TriangleStrip)
+                        .then_some(wgpu::IndexFormat::Uint32),
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: Some(wgpu::Face::Front),
+                    ..Default::default()
+                },
+                // 与主通道深度比较但不写入，避免遮挡之后绘制的内容
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: DEPTH_FORMAT,
+                    depth_write_enabled: false,
+                    depth_compare: wgpu::CompareFunction::LessEqual,
+                    stencil: wgpu::StencilState::default(),
+                    bias: depth_bias,
+                }),
+                multisample,
+                multiview: None,
+            })
+        };
+        let pipeline = create_pipeline("Outline Pipeline", wgpu::PrimitiveTopology::TriangleList);
+        let strip_pipeline = create_pipeline("Outline Strip Pipeline", wgpu::PrimitiveTopology::TriangleStrip);
+
+        Self {
+            pipeline,
+            strip_pipeline,
+            uniform_buffer,
+            bind_group,
+        }
+    }
+
+    /// 更新轮廓颜色和视口尺寸
+    pub fn update(&self, queue: &wgpu::Queue, color: [f32; 4], viewport: [f32; 2]) {
+        let uniform = OutlineUniform {
+            color,
+            viewport,
+            width: OUTLINE_WIDTH,
+            _padding: 0.0,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniform]));
+    }
+
+    /// 在主通道中绘制选中范围的轮廓
+    ///
+    /// 调用前需已设置好顶点和索引缓冲，必须在主几何体之后调用。`strip` 表示索引为三角形带。
+    pub fn draw<'a>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        uniform_bind_group: &'a wgpu::BindGroup,
+        indices: Range<u32>,
+        strip: bool,
+    ) {
+        pass.set_pipeline(if strip { &self.strip_pipeline } else { &self.pipeline });
+        pass.set_bind_group(0, uniform_bind_group, &[]);
+        pass.set_bind_group(1, &self.bind_group, &[]);
+        pass.draw_indexed(indices, 0, 0..1);
+    }
+}
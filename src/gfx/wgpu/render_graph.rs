@@ -0,0 +1,265 @@
+//! 渲染图
+//!
+//! 把一帧拆成若干命名通道，每个通道声明读取和写入的纹理资源（只是名称，不持有 GPU 对象）。
+//! 创建时按"写入者先于读取者"做拓扑排序，之后每帧只需按顺序执行启用的通道；
+//! 关闭的通道被跳过，但其输出仍视为存在（例如关闭 SSAO 后主通道仍绑定之前的遮蔽纹理）。
+//!
+//! 图本身不执行任何命令，渲染器按返回的通道标识依次编码，避免闭包同时借用渲染器的多个字段。
+//!
+//! 使用深度缓冲的通道显式声明清除还是保留深度（[`DepthAccess`]）：保留深度的通道之前必须有启用的
+//! 通道写入该深度，否则返回错误，避免调整通道顺序时主通道悄悄在未初始化的深度上测试。
+
+use crate::core::error::{DistRenderError, GraphicsError, Result};
+
+/// 通道对深度缓冲的使用方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthAccess {
+    /// 清除后做深度测试并写入（没有深度预通道时的主通道）
+    Clear,
+    /// 保留之前通道写入的深度，只绘制深度相等的片段且不再写入（深度预通道之后的主通道）
+    LoadEqual,
+}
+
+impl DepthAccess {
+    /// 通道开始时深度附件的加载操作
+    pub fn load_op(self) -> wgpu::LoadOp<f32> {
+        match self {
+            DepthAccess::Clear => wgpu::LoadOp::Clear(1.0),
+            DepthAccess::LoadEqual => wgpu::LoadOp::Load,
+        }
+    }
+
+    /// 管线的深度比较函数
+    pub fn compare(self) -> wgpu::CompareFunction {
+        match self {
+            DepthAccess::Clear => wgpu::CompareFunction::Less,
+            DepthAccess::LoadEqual => wgpu::CompareFunction::Equal,
+        }
+    }
+
+    /// 管线是否写入深度
+    pub fn writes_depth(self) -> bool {
+        self == DepthAccess::Clear
+    }
+}
+
+/// 通道声明
+#[derive(Debug, Clone)]
+pub struct PassDesc<P> {
+    id: P,
+    name: &'static str,
+    reads: Vec<&'static str>,
+    writes: Vec<&'static str>,
+    /// 使用的深度资源及使用方式
+    depth: Option<(&'static str, DepthAccess)>,
+}
+
+impl<P> PassDesc<P> {
+    pub fn new(id: P, name: &'static str) -> Self {
+        Self {
+            id,
+            name,
+            reads: Vec::new(),
+            writes: Vec::new(),
+            depth: None,
+        }
+    }
+
+    /// 设置读取的资源
+    pub fn reads(mut self, resources: &[&'static str]) -> Self {
+        self.reads = resources.to_vec();
+        self
+    }
+
+    /// 设置写入的资源（每个资源只能有一个写入者）
+    pub fn writes(mut self, resources: &[&'static str]) -> Self {
+        self.writes = resources.to_vec();
+        self
+    }
+
+    /// 设置使用的深度资源：清除时视为写入该资源，保留时视为读取
+    pub fn depth(mut self, resource: &'static str, access: DepthAccess) -> Self {
+        match access {
+            DepthAccess::Clear => self.writes.push(resource),
+            DepthAccess::LoadEqual => self.reads.push(resource),
+        }
+        self.depth = Some((resource, access));
+        self
+    }
+}
+
+/// 按依赖排序后的通道集合
+#[derive(Debug)]
+pub struct RenderGraph<P> {
+    /// 按执行顺序排列的通道及其启用状态
+    passes: Vec<(PassDesc<P>, bool)>,
+}
+
+impl<P: Copy + PartialEq> RenderGraph<P> {
+    /// 按资源依赖排序通道，所有通道默认启用
+    ///
+    /// 没有依赖关系的通道保持声明顺序。资源有多个写入者或依赖成环时返回错误。
+    pub fn new(passes: Vec<PassDesc<P>>) -> Result<Self> {
+        let graph_error = |message: String| {
+            DistRenderError::Graphics(GraphicsError::ResourceCreation(format!("Render graph: {}", message)))
+        };
+
+        // 每个资源的写入者
+        let mut writers: Vec<(&'static str, usize)> = Vec::new();
+        for (i, pass) in passes.iter().enumerate() {
+            for &resource in &pass.writes {
+                if let Some(&(_, other)) = writers.iter().find(|(name, _)| *name == resource) {
+                    return Err(graph_error(format!(
+                        "resource '{}' is written by both '{}' and '{}'",
+                        resource, passes[other].name, pass.name
+                    )));
+                }
+                writers.push((resource, i));
+            }
+        }
+
+        // 每个通道依赖的通道（读取资源的写入者，没有写入者的资源视为外部输入）
+        let dependencies: Vec<Vec<usize>> = passes
+            .iter()
+            .map(|pass| {
+                pass.reads
+                    .iter()
+                    .filter_map(|resource| writers.iter().find(|(name, _)| name == resource).map(|&(_, i)| i))
+                    .collect()
+            })
+            .collect();
+
+        // 每次取声明顺序最靠前、依赖都已排好的通道
+        let mut order = Vec::with_capacity(passes.len());
+        let mut scheduled = vec![false; passes.len()];
+        while order.len() < passes.len() {
+            let next = (0..passes.len())
+                .find(|&i| !scheduled[i] && dependencies[i].iter().all(|&d| scheduled[d]))
+                .ok_or_else(|| {
+                    let remaining: Vec<&str> =
+                        (0..passes.len()).filter(|&i| !scheduled[i]).map(|i| passes[i].name).collect();
+                    graph_error(format!("dependency cycle between {}", remaining.join(", ")))
+                })?;
+            scheduled[next] = true;
+            order.push(next);
+        }
+
+        let mut passes: Vec<Option<PassDesc<P>>> = passes.into_iter().map(Some).collect();
+        let graph = Self {
+            passes: order.into_iter().filter_map(|i| passes[i].take()).map(|pass| (pass, true)).collect(),
+        };
+        graph.validate_depth()?;
+        Ok(graph)
+    }
+
+    /// 启用或关闭通道
+    pub fn set_enabled(&mut self, id: P, enabled: bool) {
+        for (pass, pass_enabled) in &mut self.passes {
+            if pass.id == id {
+                *pass_enabled = enabled;
+            }
+        }
+    }
+
+    /// 按执行顺序排列的启用通道
+    ///
+    /// 保留深度的通道之前没有启用的通道写入该深度时（例如关闭了深度预通道）返回错误。
+    pub fn enabled_passes(&self) -> Result<Vec<P>> {
+        self.validate_depth()?;
+        Ok(self
+            .passes
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(pass, _)| pass.id)
+            .collect())
+    }
+
+    /// 通道的深度使用方式，不使用深度时为 `None`
+    pub fn depth_access(&self, id: P) -> Option<DepthAccess> {
+        self.passes
+            .iter()
+            .find(|(pass, _)| pass.id == id)
+            .and_then(|(pass, _)| pass.depth)
+            .map(|(_, access)| access)
+    }
+
+    /// 检查每个保留深度的启用通道之前都有启用的通道写入该深度
+    fn validate_depth(&self) -> Result<()> {
+        let mut written: Vec<&'static str> = Vec::new();
+        for (pass, _) in self.passes.iter().filter(|(_, enabled)| *enabled) {
+            if let Some((resource, DepthAccess::LoadEqual)) = pass.depth {
+                if !written.contains(&resource) {
+                    return Err(DistRenderError::Graphics(GraphicsError::ResourceCreation(format!(
+                        "Render graph: pass '{}' loads depth '{}' that no earlier enabled pass writes",
+                        pass.name, resource
+                    ))));
+                }
+            }
+            written.extend(&pass.writes);
+        }
+        Ok(())
+    }
+
+    /// 按执行顺序排列的所有通道名称
+    pub fn pass_names(&self) -> Vec<&'static str> {
+        self.passes.iter().map(|(pass, _)| pass.name).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passes_run_after_their_inputs() {
+        // 声明顺序与依赖相反
+        let mut graph = RenderGraph::new(vec![
+            PassDesc::new(3, "gui").reads(&["scene_color"]).writes(&["frame"]),
+            PassDesc::new(2, "main").reads(&["ao"]).writes(&["scene_color", "depth"]),
+            PassDesc::new(1, "ssao").writes(&["ao"]),
+            PassDesc::new(4, "stats"),
+        ])
+        .unwrap();
+        assert_eq!(graph.pass_names(), vec!["ssao", "main", "gui", "stats"]);
+        assert_eq!(graph.enabled_passes().unwrap(), vec![1, 2, 3, 4]);
+
+        // 关闭的通道被跳过，依赖它的通道仍然执行
+        graph.set_enabled(1, false);
+        assert_eq!(graph.enabled_passes().unwrap(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_depth_load_requires_an_earlier_writer() {
+        let mut graph = RenderGraph::new(vec![
+            PassDesc::new(1, "main").writes(&["color"]).depth("depth", DepthAccess::LoadEqual),
+            PassDesc::new(0, "prepass").depth("depth", DepthAccess::Clear),
+        ])
+        .unwrap();
+        assert_eq!(graph.pass_names(), vec!["prepass", "main"]);
+        assert_eq!(graph.depth_access(1), Some(DepthAccess::LoadEqual));
+        assert_eq!(graph.depth_access(0).map(DepthAccess::compare), Some(wgpu::CompareFunction::Less));
+
+        // 关闭深度预通道后主通道会在未写入的深度上测试
+        graph.set_enabled(0, false);
+        assert!(graph.enabled_passes().unwrap_err().to_string().contains("loads depth"));
+
+        // 没有任何通道写入深度
+        let missing = RenderGraph::new(vec![PassDesc::new(0, "main").depth("depth", DepthAccess::LoadEqual)]);
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_invalid_graphs_are_rejected() {
+        let cycle = RenderGraph::new(vec![
+            PassDesc::new(0, "a").reads(&["y"]).writes(&["x"]),
+            PassDesc::new(1, "b").reads(&["x"]).writes(&["y"]),
+        ]);
+        assert!(cycle.unwrap_err().to_string().contains("cycle"));
+
+        let shared_output = RenderGraph::new(vec![
+            PassDesc::new(0, "a").writes(&["x"]),
+            PassDesc::new(1, "b").writes(&["x"]),
+        ]);
+        assert!(shared_output.is_err());
+    }
+}
@@ -8,22 +8,60 @@
 
 use tracing::{debug, info, warn};
 use bytemuck::{Pod, Zeroable};
+use image::RgbaImage;
 use wgpu::util::DeviceExt;
 
-use crate::gfx::wgpu::context::WgpuContext;
-use crate::renderer::resources::vertex::{MyVertex, create_default_triangle, convert_geometry_vertex};
+use crate::gfx::debug_name::DebugNames;
+use crate::gfx::surface_format::{encode_output_color, linear_to_srgb};
+use crate::gfx::letterbox::{ViewportRect, LETTERBOX_COLOR};
+use crate::gfx::shader_preprocessor::{preprocess, ShaderLanguage};
+use crate::gfx::wgpu::background::BackgroundPass;
+use crate::gfx::wgpu::capture::capture_frame;
+use crate::gfx::wgpu::context::{SurfaceRecovery, WgpuContext};
+use crate::gfx::wgpu::debug_lines::DebugLinePass;
+use crate::gfx::wgpu::depth_readback::DepthReadback;
+use crate::gfx::wgpu::depth_resolve::DepthResolvePass;
+use crate::gfx::wgpu::ibl::IblMaps;
+use crate::gfx::wgpu::outline::{OutlinePass, DEFAULT_OUTLINE_COLOR};
+use crate::gfx::wgpu::wireframe::{WireframeOverlayPass, DEFAULT_WIREFRAME_COLOR};
+use crate::gfx::wgpu::render_graph::{DepthAccess, PassDesc, RenderGraph};
+use crate::gfx::wgpu::sampler::SamplerCache;
+use crate::gfx::wgpu::ssao::{SsaoDraw, SsaoGeometry, SsaoPass, SsaoSettings};
+use crate::gfx::wgpu::targets::{MainTargets, DEPTH_FORMAT};
+use crate::gfx::wgpu::texture::{create_white_texture, load_texture, texture_byte_size};
+use crate::renderer::clip_planes::{ClipPlanes, MAX_CLIP_PLANES};
+use crate::renderer::resources::vertex::{MaterialVertex, MyVertex, SkinVertex};
+use crate::gfx::wgpu::vertex_layout::WgpuVertexLayout;
+use crate::renderer::resources::scene_mesh::{
+    select_lods, subset_count, subset_materials, subset_range, transparent_draws, SceneMesh,
+};
 use crate::renderer::resources::resource::FrameResourcePool;
+use crate::renderer::resources::resource_stats::{ResourceKind, ResourceStats, Tracked};
+use crate::renderer::resources::sampler::TextureFilter;
 use crate::renderer::commands::sync::FenceManager;
 use crate::core::{Config, SceneConfig};
-use crate::core::error::{Result, GraphicsError};
-use crate::geometry::loaders::{MeshLoader, ObjLoader};
-use crate::component::{Camera, DirectionalLight};
+use crate::core::config::{effective_frames_in_flight, effective_max_texture_size, DepthBias};
+use crate::core::scene::BackgroundMode;
+use crate::core::error::{Result, DistRenderError, GraphicsError};
+use crate::component::{halton_jitter, AutoRotate, Camera, DirectionalLight, TranslateGizmo, Turntable, SHAKE_TRAUMA};
 use crate::core::input::InputSystem;
-use crate::math::{Vector3, Matrix4};
-use crate::gui::{GuiManager, GuiState};
+use crate::math::{Vector2, Vector3, Matrix4};
+use crate::math::matrix::normal_matrix;
+use crate::geometry::material::Material;
+use crate::geometry::mesh::IndexWidth;
+use crate::geometry::skinning::MAX_JOINTS;
+use crate::renderer::culling::{cull_instance_indices, InstanceData};
+use crate::renderer::axis_indicator::{self, DEFAULT_AXIS_INDICATOR_SIZE};
+use crate::renderer::debug_draw::{DebugDraw, NormalLines};
+use crate::renderer::fill_mode::FillMode;
+use crate::renderer::stats::{self, FrameStats, PresentStats};
+use crate::gui::{GuiManager, GuiState, SubsetState};
 use crate::gui::ipc::GuiStatePacket;
-use std::path::Path;
+use std::collections::{HashMap, VecDeque};
 use std::f32::consts::PI;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Instant;
 
 /// Uniform Buffer Object - MVP 鐭╅樀鍜屽厜鐓ф暟鎹?
 ///
@@ -37,7 +75,15 @@ struct UniformBufferObject {
     projection: [[f32; 4]; 4],
     light_dir: [f32; 4],
     light_color: [f32; 4],
+    /// xyz 为相机位置，w 非零时着色器手动做 sRGB 编码（交换链为 UNORM 格式）
     camera_pos: [f32; 4],
+    ambient: [f32; 4],
+    /// 模型颜色色调，乘以最终颜色
+    tint: [f32; 4],
+    /// 剖切平面系数，未启用的平面为 (0, 0, 0, 1)
+    clip_planes: [[f32; 4]; MAX_CLIP_PLANES],
+    /// 法线矩阵（模型矩阵的逆转置），非均匀缩放时保持法线垂直于表面
+    normal_matrix: [[f32; 4]; 4],
 }
 
 impl UniformBufferObject {
@@ -48,6 +94,7 @@ impl UniformBufferObject {
         light_dir: [f32; 3],
         light_color_intensity: [f32; 4],
         camera_pos: [f32; 3],
+        ambient: [f32; 4],
     ) -> Self {
         Self {
             model: *model.as_ref(),
@@ -56,37 +103,197 @@ impl UniformBufferObject {
             light_dir: [light_dir[0], light_dir[1], light_dir[2], 0.0],
             light_color: light_color_intensity,
             camera_pos: [camera_pos[0], camera_pos[1], camera_pos[2], 0.0],
+            ambient,
+            tint: [1.0; 4],
+            clip_planes: ClipPlanes::default().to_uniform(),
+            normal_matrix: *normal_matrix(model).as_ref(),
         }
     }
 }
 
+/// 每帧的渲染通道（渲染图中的节点）
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FramePass {
+    /// SSAO 预通道：几何、遮蔽和模糊
+    Ssao,
+    /// 主通道：背景、场景、选中轮廓和调试线段
+    Main,
+    /// 开启 MSAA 时把多重采样深度解析为单采样深度
+    DepthResolve,
+    /// 内置 GUI
+    Gui,
+}
+
+/// 一帧的截图请求
+struct CaptureRequest {
+    /// 裁剪矩形 `(x, y, width, height)`，为空时截取整帧
+    region: Option<(u32, u32, u32, u32)>,
+}
+
+/// 单个模型的 UBO 和绑定组
+struct ModelUniform {
+    buffer: Tracked<wgpu::Buffer>,
+    bind_group: wgpu::BindGroup,
+}
+
+/// 一种填充模式下的场景绘制管线
+struct ScenePipelines {
+    simple: wgpu::RenderPipeline,
+    /// 带材质的模型使用的 PBR 管线（片段阶段与简单着色不同）
+    pbr: wgpu::RenderPipeline,
+    /// 蒙皮模型使用的管线
+    skinned: wgpu::RenderPipeline,
+    skinned_pbr: wgpu::RenderPipeline,
+    /// 三角形带（带图元重启）使用的管线，蒙皮模型总是三角形列表
+    strip: wgpu::RenderPipeline,
+    strip_pbr: wgpu::RenderPipeline,
+    /// 半透明子网格使用的管线：按不透明度混合，只做深度测试不写深度。
+    /// 半透明材质只来自带材质的网格，总是三角形列表
+    transparent: wgpu::RenderPipeline,
+    transparent_pbr: wgpu::RenderPipeline,
+    transparent_skinned: wgpu::RenderPipeline,
+    transparent_skinned_pbr: wgpu::RenderPipeline,
+}
+
 /// wgpu 娓叉煋鍣?
 pub struct Renderer {
     gfx: WgpuContext,
 
     // 娓叉煋绠＄嚎鍜岃祫婧?
-    render_pipeline: wgpu::RenderPipeline,
-    vertex_buffer: wgpu::Buffer,
-    index_buffer: wgpu::Buffer,
-    uniform_buffer: wgpu::Buffer,
-    bind_group: wgpu::BindGroup,
-    depth_texture: wgpu::Texture,
-    depth_view: wgpu::TextureView,
+    /// 设备支持的每种填充模式一组场景管线（第一组为实心填充）
+    scene_pipelines: Vec<(FillMode, ScenePipelines)>,
+    /// 当前的三角形填充模式
+    fill_mode: FillMode,
+    /// 蒙皮模型的关节数据顶点缓冲
+    skin_buffer: Tracked<wgpu::Buffer>,
+    /// 金属度、粗糙度和纹理坐标顶点缓冲
+    material_buffer: Tracked<wgpu::Buffer>,
+    /// 带材质的模型是否使用 PBR 着色
+    pbr_shading: bool,
+    vertex_buffer: Tracked<wgpu::Buffer>,
+    index_buffer: Tracked<wgpu::Buffer>,
+    index_format: wgpu::IndexFormat,
+    /// 每个模型一份 UBO（模型矩阵不同），绑定组同时绑定模型纹理和采样器
+    uniform_layout: wgpu::BindGroupLayout,
+    model_uniforms: Vec<ModelUniform>,
+    /// 各模型的基础颜色贴图（引用同一贴图的模型共享），没有纹理或加载失败的模型使用白色纹理
+    model_textures: Vec<Option<Arc<Tracked<wgpu::TextureView>>>>,
+    white_texture: Tracked<wgpu::TextureView>,
+    model_sampler: Arc<wgpu::Sampler>,
+    /// 按 (过滤模式, 寻址模式, 各向异性等级) 共享的采样器
+    samplers: SamplerCache,
+    /// 每个蒙皮模型一份关节矩阵 Uniform，静态模型为 `None`（蒙皮管线的 group 3）
+    joint_layout: wgpu::BindGroupLayout,
+    joint_uniforms: Vec<Option<ModelUniform>>,
+    /// 主通道的深度和 MSAA 颜色目标
+    main_targets: MainTargets,
+    /// 深度缓冲读回（调试用）
+    depth_readback: DepthReadback,
+    /// 多重采样深度解析（未开启 MSAA 时为 `None`，主通道深度本身就是单采样的）
+    depth_resolve: Option<DepthResolvePass>,
+    /// 缓冲和纹理的占用统计（与 GUI 共享）
+    resource_stats: ResourceStats,
 
     // 鍦烘櫙瀵硅薄
     camera: Camera,
+    /// 视锥体可视化时的观察者相机（主相机保持不动，用于剔除和 LOD）
+    observer: Option<Camera>,
+    /// 顶点法线可视化设置，未开启时为 `None`
+    normal_lines: Option<NormalLines>,
+    /// 剖切平面
+    clip_planes: ClipPlanes,
     directional_light: DirectionalLight,
+    /// 模型自动旋转，叠加在 GUI 设置的旋转之上
+    auto_rotate: AutoRotate,
     scene: SceneConfig,
+    /// 还需强制清除颜色的帧数（创建和尺寸变化后每个交换链图像清除一次）
+    pending_color_clears: u32,
+    /// 运行时间（秒），驱动清除颜色循环
+    total_time: f32,
+
+    /// 每帧通道的执行顺序
+    render_graph: RenderGraph<FramePass>,
 
     // 閫氱敤绠＄悊鍣?
     frame_resource_pool: FrameResourcePool,
     fence_manager: FenceManager,
+    /// 同时在 GPU 上执行的最大帧数，提交后等到未完成的帧少于该值（为 1 时即低延迟模式）
+    frames_in_flight: u32,
+    /// 已提交、可能尚未执行完的帧的提交序号（按提交顺序），用于限制在途帧数
+    in_flight_submissions: VecDeque<wgpu::SubmissionIndex>,
+    /// 关闭低延迟模式时的帧数（配置值，未配置或为 1 时等于交换链缓冲数）
+    pipelined_frames: u32,
 
     // GUI 绠＄悊鍣?
     gui_manager: GuiManager,
+    /// 是否绘制内置 GUI 并处理其事件（基准测试时关闭）
+    gui_enabled: bool,
+    /// 呈现统计（跨帧累计）
+    present_stats: PresentStats,
+    /// GPU 抓帧中的资源调试名称（启用验证时）
+    debug_names: DebugNames,
+
+    /// 固定的画面宽高比，视口按该比例居中（信箱模式）
+    target_aspect: Option<f32>,
+
+    /// SSAO 通道和参数
+    ssao: SsaoPass,
+    ssao_settings: SsaoSettings,
+
+    /// 图像光照贴图（所有场景管线的 group 2，只在 PBR 着色中使用）
+    ibl: IblMaps,
+    /// 当前的纹理过滤模式
+    texture_filter: TextureFilter,
+
+    /// 渐变背景通道（纯色背景时不绘制）
+    background: BackgroundPass,
+
+    /// 选中轮廓通道和参数
+    outline: OutlinePass,
+    selected_subset: Option<usize>,
+    outline_color: [f32; 4],
+
+    /// 线框叠加通道（设备不支持线框填充时为 `None`）和参数
+    wireframe: Option<WireframeOverlayPass>,
+    wireframe_overlay: bool,
+    wireframe_color: [f32; 4],
+
+    /// 选中模型的平移 gizmo 及其绘制
+    gizmo: TranslateGizmo,
+    gizmo_model: Option<usize>,
+    debug_draw: DebugDraw,
+    debug_lines: DebugLinePass,
+    /// 坐标轴指示器的边长（像素），关闭时为 `None`；在独立的小视口中绘制，几何与调试线段分开
+    axis_indicator: Option<u32>,
+    axis_draw: DebugDraw,
+    axis_lines: DebugLinePass,
 
     // 娓叉煋鐘舵€?
-    num_indices: u32,
+    /// 合并后的场景网格（CPU 副本，修改子网格材质后重新上传顶点）
+    scene_mesh: SceneMesh,
+    /// 按全局序号排列的子网格材质（没有 PBR 材质的为 `None`）和可见性
+    subset_materials: Vec<Option<Material>>,
+    hidden_subsets: Vec<bool>,
+    /// 最近一次处理的相机抖动请求，以及下一次时钟事件要加到相机上的抖动创伤值
+    camera_shake_id: u32,
+    pending_shake_trauma: f32,
+    /// 最近一次处理的 LOD 生成和细分请求，以及当前网格使用的简化比例和细分级数
+    lod_id: u32,
+    subdivision_id: u32,
+    lod_ratio: f32,
+    subdivision_levels: u32,
+
+    /// 转台录制状态、最近一次处理的录制请求和录制前的相机位置与朝向
+    turntable: Option<Turntable>,
+    turntable_id: u32,
+    turntable_camera: Option<(Vector3, Vector3)>,
+
+    /// 截图时是否隐藏 GUI
+    capture_hides_gui: bool,
+
+    /// 是否启用投影抖动，以及下一帧使用的 Halton 序列位置
+    projection_jitter: bool,
+    jitter_frame: u32,
 }
 
 impl Renderer {
@@ -100,31 +307,104 @@ impl Renderer {
 
         // 1. 鍒涘缓 wgpu 鍚庣
         let gfx = WgpuContext::new(event_loop, config)?;
+        let debug_names = DebugNames::new(config.graphics.validation);
 
         // 2. 鍔犺浇鐫€鑹插櫒妯″潡
         debug!("Loading shaders");
-        let shader_source = include_str!("shaders/shader.wgsl");
+        let shader_source = preprocess(include_str!("shaders/shader.wgsl"), ShaderLanguage::Wgsl)?;
         let shader_module = gfx.device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Main Shader"),
             source: wgpu::ShaderSource::Wgsl(shader_source.into()),
         });
 
-        // 3. 鍒涘缓 Uniform Buffer
-        debug!("Creating uniform buffer");
-        let uniform_buffer = gfx.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Uniform Buffer"),
-            size: std::mem::size_of::<UniformBufferObject>() as u64,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
-        // 4. 鍒涘缓 Bind Group Layout
+        // 3. 鍒涘缓 Bind Group Layout
         debug!("Creating bind group layout");
         let bind_group_layout = gfx.device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Uniform Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // 模型纹理
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        // 4. 为每个模型加载纹理，创建 Uniform Buffer 和 Bind Group
+        debug!("Creating uniform buffers for {} models", scene.models.len());
+        let capabilities = gfx.report_capabilities();
+        let texture_filter = TextureFilter::preferred(capabilities.max_anisotropy);
+        let mut samplers = SamplerCache::new(config.graphics.max_anisotropy.min(capabilities.max_anisotropy as u16));
+        let model_sampler = samplers.get(&gfx.device, texture_filter, wgpu::AddressMode::Repeat);
+        let resource_stats = ResourceStats::new();
+        let white_texture = track_texture_view(&resource_stats, &create_white_texture(&gfx.device, &gfx.queue));
+        // 多个模型引用同一张贴图时只加载一次（加载失败的也只尝试一次），超过最大尺寸的贴图加载时缩小
+        let max_texture_size =
+            effective_max_texture_size(config.graphics.max_texture_size, capabilities.max_texture_dimension_2d);
+        let mut texture_cache: HashMap<String, Option<Arc<Tracked<wgpu::TextureView>>>> = HashMap::new();
+        let model_textures: Vec<Option<Arc<Tracked<wgpu::TextureView>>>> = scene
+            .models
+            .iter()
+            .map(|model| {
+                let source = model.base_color_texture()?;
+                texture_cache
+                    .entry(source.key())
+                    .or_insert_with(|| {
+                        let texture = load_texture(&gfx.device, &gfx.queue, &source, max_texture_size, debug_names)?;
+                        Some(Arc::new(track_texture_view(&resource_stats, &texture)))
+                    })
+                    .clone()
+            })
+            .collect();
+        let model_uniforms: Vec<ModelUniform> = model_textures
+            .iter()
+            .map(|texture| {
+                let buffer = gfx.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Uniform Buffer"),
+                    size: std::mem::size_of::<UniformBufferObject>() as u64,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+                let buffer = track_buffer(&resource_stats, ResourceKind::Uniform, buffer);
+                let bind_group = create_model_bind_group(
+                    &gfx.device,
+                    &bind_group_layout,
+                    &buffer,
+                    texture.as_deref().unwrap_or(&white_texture),
+                    &model_sampler,
+                );
+                ModelUniform { buffer, bind_group }
+            })
+            .collect();
+
+        // 5. 关节矩阵的 Bind Group Layout（蒙皮管线的 group 3）
+        let joint_layout = gfx.device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Joint Bind Group Layout"),
             entries: &[wgpu::BindGroupLayoutEntry {
                 binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                visibility: wgpu::ShaderStages::VERTEX,
                 ty: wgpu::BindingType::Buffer {
                     ty: wgpu::BufferBindingType::Uniform,
                     has_dynamic_offset: false,
@@ -134,155 +414,118 @@ impl Renderer {
             }],
         });
 
-        // 5. 鍒涘缓 Bind Group
-        let bind_group = gfx.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Uniform Bind Group"),
-            layout: &bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: uniform_buffer.as_entire_binding(),
-            }],
+        // 6. 创建 SSAO 通道（主通道的 group 1 绑定其输出）
+        debug!("Creating SSAO pass");
+        let size = gfx.window().inner_size();
+        let ssao = SsaoPass::new(&gfx.device, &bind_group_layout, size.width, size.height);
+
+        // 7. 创建主通道的深度和 MSAA 颜色目标
+        debug!("Creating main pass targets");
+        let main_targets = MainTargets::new(
+            &gfx.device,
+            &resource_stats,
+            gfx.surface_config.format,
+            gfx.msaa_sample_count(config.graphics.msaa_samples, DEPTH_FORMAT),
+            size.width,
+            size.height,
+        );
+        let outline = OutlinePass::new(
+            &gfx.device,
+            &bind_group_layout,
+            main_targets.format,
+            main_targets.multisample(),
+            depth_bias_state(&config.graphics.overlay_depth_bias),
+        );
+        let wireframe = capabilities.polygon_mode_line.then(|| {
+            WireframeOverlayPass::new(
+                &gfx.device,
+                &bind_group_layout,
+                &joint_layout,
+                main_targets.format,
+                main_targets.multisample(),
+                depth_bias_state(&config.graphics.overlay_depth_bias),
+            )
+        });
+        let debug_lines = DebugLinePass::new(&gfx.device, main_targets.format, main_targets.multisample());
+        let axis_lines = DebugLinePass::new(&gfx.device, main_targets.format, main_targets.multisample());
+        let background = BackgroundPass::new(&gfx.device, main_targets.format, main_targets.multisample());
+        let depth_resolve = (main_targets.sample_count > 1).then(|| {
+            DepthResolvePass::new(&gfx.device, &resource_stats, config.graphics.depth_resolve, &main_targets)
         });
+        // 开启 MSAA 时读回解析后的单采样深度
+        let readback_samples = if depth_resolve.is_some() { 1 } else { main_targets.sample_count };
+        let depth_readback = DepthReadback::new(&gfx.device, readback_samples);
 
-        // 6. 鍒涘缓娓叉煋绠＄嚎甯冨眬
+        // 图像光照：预计算或从缓存读取环境贴图的辐照度、预过滤贴图和 BRDF 查找表
+        let ibl_sampler = samplers.get(&gfx.device, texture_filter, wgpu::AddressMode::ClampToEdge);
+        let ibl = IblMaps::new(&gfx.device, &gfx.queue, scene.environment.map.as_deref(), &ibl_sampler);
+
+        // 鍒涘缓娓叉煋绠＄嚎甯冨眬
         let pipeline_layout = gfx.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Render Pipeline Layout"),
-            bind_group_layouts: &[&bind_group_layout],
+            bind_group_layouts: &[&bind_group_layout, ssao.output_layout(), ibl.layout()],
             push_constant_ranges: &[],
         });
 
-        // 7. 鍒涘缓娣卞害绾圭悊
-        debug!("Creating depth texture");
-        let size = gfx.window().inner_size();
-        let depth_texture = gfx.device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Depth Texture"),
-            size: wgpu::Extent3d {
-                width: size.width,
-                height: size.height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Depth32Float,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-        let depth_view = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
-
         // 8. 鍒涘缓娓叉煋绠＄嚎
         debug!("Creating render pipeline");
-        let render_pipeline = gfx.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader_module,
-                entry_point: "vs_main",
-                buffers: &[wgpu::VertexBufferLayout {
-                    array_stride: std::mem::size_of::<MyVertex>() as wgpu::BufferAddress,
-                    step_mode: wgpu::VertexStepMode::Vertex,
-                    attributes: &[
-                        // position
-                        wgpu::VertexAttribute {
-                            offset: 0,
-                            shader_location: 0,
-                            format: wgpu::VertexFormat::Float32x3,
-                        },
-                        // normal
-                        wgpu::VertexAttribute {
-                            offset: std::mem::size_of::<[f32; 3]>() as wgpu::BufferAddress,
-                            shader_location: 1,
-                            format: wgpu::VertexFormat::Float32x3,
-                        },
-                        // color
-                        wgpu::VertexAttribute {
-                            offset: (std::mem::size_of::<[f32; 3]>() * 2) as wgpu::BufferAddress,
-                            shader_location: 2,
-                            format: wgpu::VertexFormat::Float32x3,
-                        },
-                    ],
-                }],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader_module,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: gfx.surface_config.format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: Some(wgpu::Face::Back),
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: Some(wgpu::DepthStencilState {
-                format: wgpu::TextureFormat::Depth32Float,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less,
-                stencil: wgpu::StencilState::default(),
-                bias: wgpu::DepthBiasState::default(),
-            }),
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            multiview: None,
+        // 蒙皮管线：额外输入关节索引和权重，group 3 绑定关节矩阵
+        let skinned_pipeline_layout = gfx.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Skinned Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout, ssao.output_layout(), ibl.layout(), &joint_layout],
+            push_constant_ranges: &[],
         });
 
+        // 按资源依赖排列每帧的通道；主通道输出（开启 MSAA 时为解析后）的单采样颜色，GUI 在其上绘制。
+        // 没有深度预通道，主通道清除深度；场景管线的深度比较按主通道的深度使用方式创建。
+        // 开启 MSAA 时深度解析通道在主通道之后把多重采样深度解析为单采样深度
+        let render_graph = RenderGraph::new(vec![
+            PassDesc::new(FramePass::Ssao, "ssao").writes(&["ao"]),
+            PassDesc::new(FramePass::Main, "main")
+                .reads(&["ao"])
+                .writes(&["resolved_color"])
+                .depth("depth", DepthAccess::Clear),
+            PassDesc::new(FramePass::DepthResolve, "depth_resolve").reads(&["depth"]).writes(&["resolved_depth"]),
+            PassDesc::new(FramePass::Gui, "gui").reads(&["resolved_color"]).writes(&["frame"]),
+        ])?;
+        debug!("Render graph: {}", render_graph.pass_names().join(" -> "));
+        let main_depth = render_graph.depth_access(FramePass::Main).unwrap_or(DepthAccess::Clear);
+
+        // 每种设备支持的填充模式一组管线，绘制时按 GUI 选择的模式切换
+        let scene_pipelines: Vec<(FillMode, ScenePipelines)> = FillMode::ALL
+            .into_iter()
+            .filter(|mode| mode.is_supported(&capabilities))
+            .map(|mode| {
+                let pipelines = create_scene_pipelines(
+                    &gfx.device,
+                    &shader_module,
+                    &pipeline_layout,
+                    &skinned_pipeline_layout,
+                    &main_targets,
+                    &config.graphics.depth_bias,
+                    main_depth,
+                    mode,
+                );
+                (mode, pipelines)
+            })
+            .collect();
+
         // 9. 鍔犺浇妯″瀷鏁版嵁鎴栦娇鐢ㄩ粯璁や笁瑙掑舰
         debug!("Loading mesh data");
-        let obj_path = Path::new(&scene.model.path);
-        let (vertices, indices) = if obj_path.exists() {
-            info!("Loading model from: {}", scene.model.path);
-            match ObjLoader::load_from_file(obj_path) {
-                Ok(mesh_data) => {
-                    let vertices: Vec<MyVertex> = mesh_data
-                        .vertices
-                        .iter()
-                        .map(convert_geometry_vertex)
-                        .collect();
-                    let indices = mesh_data.indices;
-                    info!("Model loaded: {} vertices, {} indices", vertices.len(), indices.len());
-                    (vertices, indices)
-                }
-                Err(e) => {
-                    warn!("Failed to load model: {}, using default triangle", e);
-                    let vertices = create_default_triangle().to_vec();
-                    let indices = vec![0, 1, 2];
-                    (vertices, indices)
-                }
-            }
-        } else {
-            warn!("Model file not found: {}, using default triangle", scene.model.path);
-            let vertices = create_default_triangle().to_vec();
-            let indices = vec![0, 1, 2];
-            (vertices, indices)
-        };
-
-        let num_indices = indices.len() as u32;
-
-        // 10. 鍒涘缓椤剁偣缂撳啿
-        debug!("Creating vertex buffer");
-        let vertex_buffer = gfx.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Vertex Buffer"),
-            contents: bytemuck::cast_slice(&vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
+        let scene_mesh = SceneMesh::load(
+            &scene.models,
+            scene.fallback_primitive,
+            config.graphics.primitive_restart,
+            config.graphics.mesh_cache,
+        );
+        let scene_bounds = scene.camera.fit_clip_planes.then(|| scene_mesh.world_bounds(&scene.models)).flatten();
 
-        // 11. 鍒涘缓绱㈠紩缂撳啿
-        debug!("Creating index buffer");
-        let index_buffer = gfx.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Index Buffer"),
-            contents: bytemuck::cast_slice(&indices),
-            usage: wgpu::BufferUsages::INDEX,
-        });
+        // 10-11. 创建顶点和索引缓冲
+        debug!("Creating vertex and index buffers");
+        let (vertex_buffer, index_buffer, index_format) = create_mesh_buffers(&gfx.device, &resource_stats, &scene_mesh, debug_names, scene);
+        let skin_buffer = create_skin_buffer(&gfx.device, &resource_stats, &scene_mesh, debug_names, scene);
+        let material_buffer = create_material_buffer(&gfx.device, &resource_stats, &scene_mesh, debug_names, scene);
+        let joint_uniforms = create_joint_uniforms(&gfx.device, &resource_stats, &joint_layout, &scene_mesh);
 
         // 12. 鍒濆鍖栫浉鏈?
         debug!("Initializing camera");
@@ -293,9 +536,10 @@ impl Renderer {
             scene.camera.transform.position[2],
         ));
 
-        let aspect_ratio = size.width as f32 / size.height as f32;
-        camera.set_lens(
+        let aspect_ratio = ViewportRect::letterbox(size.width, size.height, config.graphics.target_aspect).aspect();
+        camera.set_perspective(
             scene.camera.fov * PI / 180.0,
+            scene.camera.fov_axis,
             aspect_ratio,
             scene.camera.near_clip,
             scene.camera.far_clip,
@@ -314,6 +558,12 @@ impl Renderer {
 
         info!("Camera component initialized at position {:?}", camera.position());
 
+        // 按场景包围盒收紧近远裁剪面
+        if let Some(bounds) = &scene_bounds {
+            camera.fit_clip_planes(bounds);
+            info!("Camera clip planes fitted to scene bounds: near={}, far={}", camera.near_z(), camera.far_z());
+        }
+
         // 13. 鍒濆鍖栧厜鐓?
         debug!("Initializing lights");
         let directional_light = scene.light.to_directional_light("MainLight");
@@ -325,12 +575,25 @@ impl Renderer {
         );
 
         // 14. 鍒濆鍖栧抚璧勬簮绠＄悊
-        let frame_resource_pool = FrameResourcePool::triple_buffering();
+        let frame_resource_pool = FrameResourcePool::new(gfx.buffer_count() as usize);
+        let frames_in_flight = effective_frames_in_flight(config.graphics.frames_in_flight, gfx.buffer_count());
+        let pipelined_frames =
+            effective_frames_in_flight(config.graphics.frames_in_flight.filter(|&n| n > 1), gfx.buffer_count());
         let fence_manager = FenceManager::new();
 
         // 15. 鍒濆鍖?GUI
         debug!("Initializing GUI");
-        let gui_state = GuiState::new(config, scene);
+        let mut gui_state = GuiState::new(config, scene);
+        gui_state.camera_near = camera.near_z();
+        gui_state.camera_far = camera.far_z();
+        gui_state.device_capabilities = Some(capabilities);
+        gui_state.resource_stats = Some(resource_stats.clone());
+        gui_state.subset_count = subset_count(&scene_mesh.models);
+        let subset_materials = subset_materials(&scene_mesh.models);
+        let hidden_subsets = vec![false; subset_materials.len()];
+        gui_state.subsets = subset_states(&subset_materials);
+        gui_state.ibl_available = ibl.is_enabled();
+        gui_state.texture_filter = texture_filter;
         let gui_manager = GuiManager::new(
             &gfx.device,
             gfx.surface_config.format,
@@ -341,43 +604,204 @@ impl Renderer {
 
         info!("wgpu renderer created successfully");
 
+        let pending_color_clears = gfx.buffer_count();
+        let present_mode = present_mode_kind(gfx.surface_config.present_mode);
+
         Ok(Self {
             gfx,
-            render_pipeline,
+            scene_pipelines,
+            fill_mode: FillMode::default(),
+            skin_buffer,
+            material_buffer,
+            pbr_shading: true,
             vertex_buffer,
             index_buffer,
-            uniform_buffer,
-            bind_group,
-            depth_texture,
-            depth_view,
+            index_format,
+            uniform_layout: bind_group_layout,
+            model_uniforms,
+            model_textures,
+            white_texture,
+            model_sampler,
+            samplers,
+            joint_layout,
+            joint_uniforms,
+            main_targets,
+            depth_readback,
+            depth_resolve,
+            resource_stats,
             camera,
+            observer: None,
+            normal_lines: None,
+            clip_planes: ClipPlanes::default(),
             directional_light,
+            auto_rotate: AutoRotate::default(),
             scene: scene.clone(),
+            pending_color_clears,
+            total_time: 0.0,
+            render_graph,
             frame_resource_pool,
             fence_manager,
+            frames_in_flight,
+            in_flight_submissions: VecDeque::new(),
+            pipelined_frames,
             gui_manager,
-            num_indices,
+            gui_enabled: true,
+            present_stats: PresentStats {
+                present_mode,
+                ..Default::default()
+            },
+            debug_names,
+            target_aspect: config.graphics.target_aspect,
+            ssao,
+            ssao_settings: SsaoSettings::default(),
+            ibl,
+            texture_filter,
+            background,
+            outline,
+            selected_subset: None,
+            outline_color: DEFAULT_OUTLINE_COLOR,
+            wireframe,
+            wireframe_overlay: false,
+            wireframe_color: DEFAULT_WIREFRAME_COLOR,
+            gizmo: TranslateGizmo::new(),
+            gizmo_model: None,
+            debug_draw: DebugDraw::new(),
+            debug_lines,
+            axis_indicator: Some(DEFAULT_AXIS_INDICATOR_SIZE),
+            axis_draw: DebugDraw::new(),
+            axis_lines,
+            subset_materials,
+            hidden_subsets,
+            scene_mesh,
+            camera_shake_id: 0,
+            pending_shake_trauma: 0.0,
+            lod_id: 0,
+            subdivision_id: 0,
+            lod_ratio: 1.0,
+            subdivision_levels: 0,
+            turntable: None,
+            turntable_id: 0,
+            turntable_camera: None,
+            capture_hides_gui: true,
+            projection_jitter: false,
+            jitter_frame: 0,
         })
     }
 
     /// 缁樺埗涓€甯?
-    pub fn draw(&mut self) -> Result<()> {
+    pub fn draw(&mut self) -> Result<FrameStats> {
+        self.render_frame(None).map(|(stats, _)| stats)
+    }
+
+    /// 绘制一帧并截取画面
+    ///
+    /// `rect` 为像素单位的裁剪矩形 `(x, y, width, height)`，会被限制在帧缓冲范围内，为 `None` 时截取整帧。
+    /// 开启 [`set_capture_hides_gui`](Self::set_capture_hides_gui) 时这一帧不绘制 GUI。
+    pub fn capture_region(&mut self, rect: Option<(u32, u32, u32, u32)>) -> Result<RgbaImage> {
+        if !self.gfx.surface_config.usage.contains(wgpu::TextureUsages::COPY_SRC) {
+            return Err(GraphicsError::CommandExecution("Surface does not support COPY_SRC".to_string()).into());
+        }
+        let (_, image) = self.render_frame(Some(CaptureRequest { region: rect }))?;
+        image.ok_or_else(|| GraphicsError::CommandExecution("Frame was not captured".to_string()).into())
+    }
+
+    /// 设置截图时是否隐藏 GUI
+    pub fn set_capture_hides_gui(&mut self, hide: bool) {
+        self.capture_hides_gui = hide;
+    }
+
+    /// 绘制一帧，`capture` 不为空时在呈现前截取交换链图像
+    fn render_frame(&mut self, capture: Option<CaptureRequest>) -> Result<(FrameStats, Option<RgbaImage>)> {
+        let start = Instant::now();
+        let mut stats = FrameStats::default();
+
         // 1. 鑾峰彇浜ゆ崲閾剧汗鐞?
-        let output = self.gfx.surface.get_current_texture()
-            .map_err(|e| GraphicsError::SwapchainError(format!("Failed to acquire next image: {}", e)))?;
+        // 没有空闲的交换链图像时阻塞到 GPU 显示完之前的帧，计入 GPU 等待
+        let wait_start = Instant::now();
+        let output = match self.gfx.surface.get_current_texture() {
+            Ok(output) => output,
+            Err(e) => {
+                self.present_stats.dropped_frames += 1;
+                match SurfaceRecovery::for_error(&e) {
+                    // 与 Vulkan 后端一致：重建交换链并跳过这一帧（尺寸变化去抖期间可能发生）
+                    SurfaceRecovery::Reconfigure => {
+                        debug!("Surface {}, reconfiguring", e);
+                        self.resize();
+                        return Ok((stats, None));
+                    }
+                    SurfaceRecovery::Skip => return Ok((stats, None)),
+                    SurfaceRecovery::Fail => {
+                        return Err(GraphicsError::SwapchainError(format!("Failed to acquire next image: {}", e)).into());
+                    }
+                }
+            }
+        };
+        stats.record_wait(wait_start);
+        if output.suboptimal {
+            self.present_stats.dropped_frames += 1;
+        }
 
         let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
 
+        // 转台录制时由录制状态控制相机，并隐藏 GUI、选中轮廓和 gizmo
+        if let Some(turntable) = &self.turntable {
+            turntable.apply(&mut self.camera);
+        }
+        let recording = self.turntable.is_some();
+
         // 2. 鍒涘缓鍛戒护缂栫爜鍣?
         let mut encoder = self.gfx.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Render Encoder"),
         });
 
         // 3. 鏇存柊 MVP 鐭╅樀
-        let model = self.scene.model.transform.to_matrix();
-        let view_matrix = self.camera.view_matrix();
-        let mut proj_matrix = self.camera.proj_matrix();
+        let model_matrices: Vec<Matrix4> = self
+            .scene
+            .models
+            .iter()
+            .map(|model| model.transform.to_matrix())
+            .collect();
+        // 视锥体剔除（使用翻转 Y 之前的投影矩阵）
+        let frustum = self.camera.frustum();
+        let instances: Vec<InstanceData> = self
+            .scene_mesh
+            .models
+            .iter()
+            .zip(&model_matrices)
+            .map(|(mesh, model)| InstanceData::new(*model, mesh.bounding_radius))
+            .collect();
+        let (visible_models, culling_stats) = cull_instance_indices(&frustum, &instances);
+        self.gui_manager
+            .state_mut()
+            .update_culling_stats(culling_stats.drawn, culling_stats.culled);
+
+        // 按到相机的距离选择 LOD
+        let lod_levels = select_lods(&self.scene_mesh.models, &self.scene.models, &self.camera.position());
+        self.gui_manager.state_mut().update_lod_levels(
+            self.scene_mesh.models
+                .iter()
+                .zip(&lod_levels)
+                .map(|(model, &level)| (level, model.lod_count())),
+        );
+
+        // 投影抖动只作用于主相机，录制转台时关闭以保证帧间画面稳定
+        let jitter = if self.projection_jitter && !recording {
+            let [width, height] = self.viewport_size();
+            self.jitter_frame = self.jitter_frame.wrapping_add(1);
+            halton_jitter(self.jitter_frame, (width, height))
+        } else {
+            Vector2::zeros()
+        };
+        self.camera.set_jitter(jitter);
+        self.camera.update_view_proj();
+
+        // 视锥体可视化时从观察者相机绘制，剔除和 LOD 仍使用主相机
+        let render_camera = self.render_camera();
+        let view_matrix = render_camera.view_matrix();
+        let mut proj_matrix = render_camera.proj_matrix();
+        let camera_pos = render_camera.position();
         proj_matrix[(1, 1)] *= -1.0;
+        let view_proj = proj_matrix * view_matrix;
 
         // 4. 鍑嗗鍏夌収鍙傛暟
         let light_dir = self.directional_light.direction;
@@ -391,70 +815,338 @@ impl Renderer {
             1.0,
         ];
 
-        let camera_pos = self.camera.position();
         let camera_pos_array = [camera_pos.x, camera_pos.y, camera_pos.z];
+        let manual_srgb = self.gfx.manual_srgb_encoding();
+        let clip_planes = self.clip_planes.to_uniform();
 
         // 5. 鍒涘缓 UBO 骞跺啓鍏ョ紦鍐?
-        let ubo = UniformBufferObject::new(
-            &model,
-            &view_matrix,
-            &proj_matrix,
-            light_dir_array,
-            light_color_intensity,
-            camera_pos_array,
-        );
-
-        self.gfx.queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[ubo]));
-
-        // 6. 寮€濮嬫覆鏌撻€氶亾
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: self.scene.clear_color[0] as f64,
-                            g: self.scene.clear_color[1] as f64,
-                            b: self.scene.clear_color[2] as f64,
-                            a: self.scene.clear_color[3] as f64,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
-                    view: &self.depth_view,
-                    depth_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(1.0),
-                        store: wgpu::StoreOp::Store,
-                    }),
-                    stencil_ops: None,
-                }),
-                occlusion_query_set: None,
-                timestamp_writes: None,
-            });
+        for ((model, config), uniform) in model_matrices.iter().zip(&self.scene.models).zip(&self.model_uniforms) {
+            let mut ubo = UniformBufferObject {
+                tint: config.tint,
+                clip_planes,
+                ..UniformBufferObject::new(
+                    model,
+                    &view_matrix,
+                    &proj_matrix,
+                    light_dir_array,
+                    light_color_intensity,
+                    camera_pos_array,
+                    self.scene.ambient_light(),
+                )
+            };
+            ubo.camera_pos[3] = manual_srgb as u32 as f32;
+            self.gfx.queue.write_buffer(&uniform.buffer, 0, bytemuck::cast_slice(&[ubo]));
+        }
+        for (mesh, uniform) in self.scene_mesh.models.iter().zip(&self.joint_uniforms) {
+            if let (Some(skeleton), Some(uniform)) = (&mesh.skeleton, uniform) {
+                let matrices: Vec<[[f32; 4]; 4]> = skeleton
+                    .joint_matrices()
+                    .iter()
+                    .take(MAX_JOINTS)
+                    .map(|matrix| *matrix.as_ref())
+                    .collect();
+                self.gfx.queue.write_buffer(&uniform.buffer, 0, bytemuck::cast_slice(&matrices));
+            }
+        }
 
-            render_pass.set_pipeline(&self.render_pipeline);
-            render_pass.set_bind_group(0, &self.bind_group, &[]);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-            render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
+        // SSAO 和图像光照参数（SSAO 预通道与主通道使用相同的投影矩阵）
+        self.ssao.update(&self.gfx.queue, &proj_matrix, &self.ssao_settings);
+        self.ibl.update(&self.gfx.queue, self.scene.environment.ibl_intensity);
+
+        // 选中子网格的轮廓（所属模型被剔除时不绘制）
+        let outline_target = self
+            .selected_subset
+            .filter(|_| !recording)
+            .and_then(|selected| subset_range(&self.scene_mesh.models, selected))
+            .filter(|(model_index, _)| visible_models.contains(model_index));
+        if outline_target.is_some() {
+            let color = encode_output_color(self.outline_color, manual_srgb);
+            self.outline.update(&self.gfx.queue, color, self.viewport_size());
+        }
+        let wireframe = self.wireframe.as_ref().filter(|_| self.wireframe_overlay);
+        if let Some(wireframe) = wireframe {
+            wireframe.update(&self.gfx.queue, encode_output_color(self.wireframe_color, manual_srgb));
         }
 
-        // 7. 鏇存柊鍜屾覆鏌?GUI
-        self.gui_manager.update(self.gfx.window());
-        self.gui_manager.render(
-            &self.gfx.device,
-            &self.gfx.queue,
-            &mut encoder,
-            &view,
-            self.gfx.window(),
-        )?;
+        // 选中模型的平移 gizmo
+        self.debug_draw.clear();
+        if let Some(index) = self.selected_model().filter(|_| !recording) {
+            let position = Vector3::from(self.scene.models[index].transform.position);
+            self.gizmo.draw(&mut self.debug_draw, position, &view_proj);
+        }
+        if self.observer.is_some() && !recording {
+            self.debug_draw.frustum(&frustum);
+        }
+        // 顶点法线（只绘制可见模型的原始网格，蒙皮模型按绑定姿态）
+        if let Some(settings) = self.normal_lines.filter(|_| !recording) {
+            for &i in &visible_models {
+                let mesh = &self.scene_mesh.models[i];
+                let vertices = mesh.vertex_start as usize..(mesh.vertex_start + mesh.vertex_count) as usize;
+                self.debug_draw.vertex_normals(
+                    &model_matrices[i],
+                    &self.scene_mesh.vertices[vertices.clone()],
+                    &self.scene_mesh.tangents[vertices],
+                    &settings,
+                );
+            }
+        }
+        let (mut debug_vertices, debug_indices) = self.debug_draw.build_geometry(&view_proj, self.viewport_size());
+        if manual_srgb {
+            for vertex in &mut debug_vertices {
+                vertex.color = vertex.color.map(linear_to_srgb);
+            }
+        }
+        self.debug_lines.prepare(&self.gfx.device, &debug_vertices, &debug_indices);
+
+        // 坐标轴指示器只使用相机的旋转，在场景视口左下角的小视口中绘制
+        let axis_viewport = self
+            .axis_indicator
+            .filter(|_| !recording)
+            .and_then(|size| axis_indicator::viewport(self.viewport_rect(), size));
+        self.axis_draw.clear();
+        let mut axis_vertices = Vec::new();
+        let mut axis_indices = Vec::new();
+        if let Some(rect) = axis_viewport {
+            axis_indicator::draw(&mut self.axis_draw, &view_matrix);
+            let mut axis_view_proj = axis_indicator::view_proj(&view_matrix);
+            // 与场景的投影矩阵一样翻转 Y
+            axis_view_proj.row_mut(1).neg_mut();
+            (axis_vertices, axis_indices) =
+                self.axis_draw.build_geometry(&axis_view_proj, [rect.width as f32, rect.height as f32]);
+            if manual_srgb {
+                for vertex in &mut axis_vertices {
+                    vertex.color = vertex.color.map(linear_to_srgb);
+                }
+            }
+        }
+        self.axis_lines.prepare(&self.gfx.device, &axis_vertices, &axis_indices);
+
+        // 关闭每帧清除时保留交换链图像原有内容，渐变背景也只在清除的帧绘制
+        let clear_color = self.scene.clear_color_enabled || self.pending_color_clears > 0;
+        let clear_value = self.scene.clear_color_at(self.total_time);
+        // 信箱模式下把整个图像清除为黑色，视口内的纯色背景改用背景通道绘制（只覆盖视口）
+        let viewport = self.viewport_rect();
+        let (target_width, target_height) = self.main_targets.size();
+        let letterboxed = !viewport.covers(target_width, target_height);
+        let gradient = match self.scene.background {
+            BackgroundMode::Gradient { top, bottom } if clear_color => Some((top, bottom)),
+            BackgroundMode::Solid if clear_color && letterboxed => Some((clear_value, clear_value)),
+            _ => None,
+        };
+        if let Some((top, bottom)) = gradient {
+            self.background.update(
+                &self.gfx.queue,
+                encode_output_color(top, manual_srgb),
+                encode_output_color(bottom, manual_srgb),
+            );
+        }
+        let color_load = if clear_color {
+            let [r, g, b, a] = encode_output_color(if letterboxed { LETTERBOX_COLOR } else { clear_value }, manual_srgb);
+            wgpu::LoadOp::Clear(wgpu::Color {
+                r: r as f64,
+                g: g as f64,
+                b: b as f64,
+                a: a as f64,
+            })
+        } else {
+            wgpu::LoadOp::Load
+        };
+        self.pending_color_clears = self.pending_color_clears.saturating_sub(1);
+
+        // 6. 按渲染图的顺序编码各通道（关闭 SSAO 或录制时跳过对应通道）
+        self.render_graph.set_enabled(FramePass::Ssao, self.ssao_settings.enabled);
+        self.render_graph.set_enabled(FramePass::DepthResolve, self.depth_resolve.is_some());
+        let hide_gui = recording || (capture.is_some() && self.capture_hides_gui);
+        self.render_graph.set_enabled(FramePass::Gui, self.gui_enabled && !hide_gui);
+        let main_depth = self.render_graph.depth_access(FramePass::Main).unwrap_or(DepthAccess::Clear);
+        for pass in self.render_graph.enabled_passes()? {
+            match pass {
+                FramePass::Ssao => {
+                    self.ssao.encode(
+                        &mut encoder,
+                        &SsaoGeometry {
+                            vertex_buffer: &self.vertex_buffer,
+                            index_buffer: &self.index_buffer,
+                            index_format: self.index_format,
+                            viewport,
+                            draws: visible_models
+                                .iter()
+                                .flat_map(|&i| {
+                                    let uniform_bind_group = &self.model_uniforms[i].bind_group;
+                                    let strip = self.scene_mesh.models[i].is_strip(lod_levels[i]);
+                                    self.draw_ranges(i, lod_levels[i])
+                                        .into_iter()
+                                        .map(move |indices| SsaoDraw { uniform_bind_group, indices, strip })
+                                })
+                                .collect(),
+                        },
+                        &mut stats,
+                    );
+                }
+                FramePass::Main => {
+                    let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                        label: Some("Render Pass"),
+                        // 开启 MSAA 时在主通道结束时解析到交换链图像
+                        color_attachments: &[Some(self.main_targets.color_attachment(&view, color_load))],
+                        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                            view: self.main_targets.depth_view(),
+                            depth_ops: Some(wgpu::Operations {
+                                load: main_depth.load_op(),
+                                store: wgpu::StoreOp::Store,
+                            }),
+                            stencil_ops: None,
+                        }),
+                        occlusion_query_set: None,
+                        timestamp_writes: None,
+                    });
+
+                    render_pass.set_viewport(
+                        viewport.x as f32,
+                        viewport.y as f32,
+                        viewport.width as f32,
+                        viewport.height as f32,
+                        0.0,
+                        1.0,
+                    );
+                    render_pass.set_scissor_rect(viewport.x, viewport.y, viewport.width, viewport.height);
+
+                    if gradient.is_some() {
+                        self.background.draw(&mut render_pass, &mut stats);
+                    }
+
+                    render_pass.set_bind_group(1, self.ssao.output_bind_group(), &[]);
+                    render_pass.set_bind_group(2, self.ibl.bind_group(), &[]);
+                    render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+                    render_pass.set_vertex_buffer(1, self.material_buffer.slice(..));
+                    render_pass.set_vertex_buffer(2, self.skin_buffer.slice(..));
+                    render_pass.set_index_buffer(self.index_buffer.slice(..), self.index_format);
+
+                    // 按（是否蒙皮, 是否 PBR, 是否三角形带）分组绘制，减少管线切换；
+                    // 蒙皮模型不使用 LOD（SSAO 预通道和选中轮廓仍使用绑定姿态）
+                    let mut draws: Vec<((bool, bool, bool), usize)> = visible_models
+                        .iter()
+                        .map(|&i| {
+                            let model = &self.scene_mesh.models[i];
+                            let skinned = self.joint_uniforms[i].is_some();
+                            ((skinned, self.pbr_shading && model.pbr, model.is_strip(lod_levels[i])), i)
+                        })
+                        .collect();
+                    draws.sort_by_key(|&(key, _)| key);
+                    let mut current_pipeline = None;
+                    for (key, i) in draws {
+                        if current_pipeline != Some(key) {
+                            render_pass.set_pipeline(self.scene_pipeline(key.0, key.1, key.2));
+                            current_pipeline = Some(key);
+                        }
+                        render_pass.set_bind_group(0, &self.model_uniforms[i].bind_group, &[]);
+                        let level = match &self.joint_uniforms[i] {
+                            Some(joints) => {
+                                render_pass.set_bind_group(3, &joints.bind_group, &[]);
+                                0
+                            }
+                            None => lod_levels[i],
+                        };
+                        // 隐藏的子网格直接跳过
+                        for indices in self.draw_ranges(i, level) {
+                            stats.record_draw(indices.len() as u32);
+                            render_pass.draw_indexed(indices, 0, 0..1);
+                        }
+                    }
+
+                    // 半透明子网格在不透明几何之后从远到近绘制（原始网格，不使用 LOD）
+                    let transparent = transparent_draws(
+                        &self.scene_mesh.models,
+                        &model_matrices,
+                        &visible_models,
+                        &self.hidden_subsets,
+                        &camera_pos,
+                    );
+                    for draw in transparent {
+                        let model = &self.scene_mesh.models[draw.model];
+                        let joints = self.joint_uniforms[draw.model].as_ref();
+                        render_pass.set_pipeline(self.transparent_pipeline(joints.is_some(), self.pbr_shading && model.pbr));
+                        render_pass.set_bind_group(0, &self.model_uniforms[draw.model].bind_group, &[]);
+                        if let Some(joints) = joints {
+                            render_pass.set_bind_group(3, &joints.bind_group, &[]);
+                        }
+                        stats.record_draw(draw.indices.len() as u32);
+                        render_pass.draw_indexed(draw.indices, 0, 0..1);
+                    }
+
+                    // 线框叠加覆盖与主通道相同的子网格和 LOD 级别（半透明子网格除外）
+                    if let Some(wireframe) = wireframe {
+                        for &i in &visible_models {
+                            let joints = self.joint_uniforms[i].as_ref().map(|joints| &joints.bind_group);
+                            let level = if joints.is_some() { 0 } else { lod_levels[i] };
+                            let ranges = self.draw_ranges(i, level);
+                            for indices in &ranges {
+                                stats.record_draw(indices.len() as u32);
+                            }
+                            let strip = self.scene_mesh.models[i].is_strip(level);
+                            let bind_group = &self.model_uniforms[i].bind_group;
+                            wireframe.draw(&mut render_pass, bind_group, joints, ranges, strip);
+                        }
+                    }
+
+                    if let Some((model_index, range)) = outline_target.clone() {
+                        stats.record_draw(range.len() as u32);
+                        let strip = self.scene_mesh.models[model_index].is_strip(0);
+                        let bind_group = &self.model_uniforms[model_index].bind_group;
+                        self.outline.draw(&mut render_pass, bind_group, range, strip);
+                    }
+
+                    self.debug_lines.draw(&mut render_pass, &mut stats);
+
+                    // 坐标轴指示器放在最后，之后不再恢复场景视口
+                    if let Some(rect) = axis_viewport {
+                        let (x, y, size) = (rect.x as f32, rect.y as f32, rect.width as f32);
+                        render_pass.set_viewport(x, y, size, size, 0.0, 1.0);
+                        render_pass.set_scissor_rect(rect.x, rect.y, rect.width, rect.height);
+                        self.axis_lines.draw(&mut render_pass, &mut stats);
+                    }
+                }
+                FramePass::DepthResolve => {
+                    if let Some(depth_resolve) = &self.depth_resolve {
+                        depth_resolve.encode(&mut encoder, &mut stats);
+                    }
+                }
+                FramePass::Gui => {
+                    // GUI 绘制在主通道解析后的单采样交换链图像上，不经过多重采样
+                    self.gui_manager.update(self.gfx.window());
+                    self.gui_manager.render(
+                        &self.gfx.device,
+                        &self.gfx.queue,
+                        &mut encoder,
+                        &view,
+                        self.gfx.window(),
+                    )?;
+                }
+            }
+        }
 
         // 8. 鎻愪氦鍛戒护
-        self.gfx.queue.submit(std::iter::once(encoder.finish()));
+        let submission = self.gfx.queue.submit(std::iter::once(encoder.finish()));
+        // 限制在途帧数：等到只剩 frames_in_flight - 1 帧未完成（低延迟模式下即等待刚提交的这一帧）；
+        // 提交按顺序完成，只需等待需要完成的最后一个提交
+        self.in_flight_submissions.push_back(submission);
+        let excess = (self.in_flight_submissions.len() + 1).saturating_sub(self.frames_in_flight as usize);
+        if let Some(wait_for) = self.in_flight_submissions.drain(..excess).next_back() {
+            let wait_start = Instant::now();
+            self.gfx.device.poll(wgpu::Maintain::WaitForSubmissionIndex(wait_for));
+            stats.record_wait(wait_start);
+        }
+        stats.finish(start);
+        self.gui_manager.state_mut().update_frame_stats(&stats);
+        if recording {
+            self.capture_turntable_frame(&output.texture);
+        }
+        let captured = match capture {
+            Some(request) => Some(capture_frame(&self.gfx.device, &self.gfx.queue, &output.texture, request.region)?),
+            None => None,
+        };
         output.present();
+        self.present_stats.presented_frames += 1;
+        self.gui_manager.state_mut().present_stats = Some(self.present_stats);
 
         // 9. 搴旂敤 GUI 鐘舵€佸埌鍦烘櫙
         self.apply_gui_state();
@@ -464,7 +1156,7 @@ impl Renderer {
         self.frame_resource_pool.current_mut().mark_in_use(self.fence_manager.current_value().value());
         self.frame_resource_pool.advance();
 
-        Ok(())
+        Ok((stats, captured))
     }
 
     /// 澶勭悊绐楀彛澶у皬璋冩暣
@@ -477,39 +1169,233 @@ impl Renderer {
             // 閲嶆柊閰嶇疆琛ㄩ潰
             self.gfx.reconfigure_surface(size.width, size.height);
 
-            // 閲嶅缓娣卞害绾圭悊
-            self.depth_texture = self.gfx.device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("Depth Texture"),
-                size: wgpu::Extent3d {
-                    width: size.width,
-                    height: size.height,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Depth32Float,
-                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            });
-            self.depth_view = self.depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+            // 重建深度和 MSAA 颜色目标
+            self.main_targets.resize(&self.gfx.device, &self.resource_stats, size.width, size.height);
+            if let Some(depth_resolve) = &mut self.depth_resolve {
+                depth_resolve.resize(&self.gfx.device, &self.resource_stats, &self.main_targets);
+            }
+
+            // 重建 SSAO 渲染目标
+            self.ssao.resize(&self.gfx.device, size.width, size.height);
+
+            // 新的交换链图像内容未定义，需要先清除
+            self.pending_color_clears = self.gfx.buffer_count();
 
             // 鏇存柊鐩告満瀹介珮姣?
-            let aspect = size.width as f32 / size.height as f32;
+            let aspect = self.viewport_rect().aspect();
             self.camera.set_aspect(aspect);
+            if let Some(observer) = &mut self.observer {
+                observer.set_aspect(aspect);
+            }
         }
     }
 
     /// 鏇存柊鐩告満锛堝熀浜庤緭鍏ョ郴缁燂級
     pub fn update(&mut self, input_system: &mut InputSystem, delta_time: f32) {
-        input_system.update_camera(&mut self.camera, delta_time);
+        self.auto_rotate.advance(delta_time);
+        self.gui_manager.state_mut().mouse_captured = Some(input_system.is_captured());
+        self.update_gizmo(input_system);
+        if input_system.update_light(&mut self.directional_light, delta_time) {
+            // 同步到 GUI 状态，否则每帧应用 GUI 参数时会被覆盖
+            let direction = self.directional_light.direction;
+            self.gui_manager.state_mut().light_direction = [direction.x, direction.y, direction.z];
+        }
+        self.pending_shake_trauma += input_system.take_shake_trauma();
+        input_system.update_camera(self.render_camera(), delta_time);
+    }
+
+    /// 按固定步长推进骨骼动画
+    pub fn fixed_update(&mut self, delta_time: f32) {
+        for skeleton in self.scene_mesh.models.iter_mut().filter_map(|model| model.skeleton.as_mut()) {
+            skeleton.advance(delta_time);
+        }
+    }
+
+    /// 处理选中模型的 gizmo 拖拽
+    fn update_gizmo(&mut self, input_system: &InputSystem) {
+        let selected = self.selected_model();
+        if selected != self.gizmo_model {
+            self.gizmo.reset();
+            self.gizmo_model = selected;
+        }
+        let Some(index) = selected else {
+            return;
+        };
+
+        let render_camera = self.render_camera();
+        let mut proj_matrix = render_camera.proj_matrix();
+        proj_matrix[(1, 1)] *= -1.0;
+        let view_proj = proj_matrix * render_camera.view_matrix();
+
+        let position = Vector3::from(self.scene.models[index].transform.position);
+        // 鼠标位置换算到视口内
+        let viewport = self.viewport_rect();
+        let mut gizmo_input = input_system.gizmo_input();
+        gizmo_input.cursor -= Vector2::new(viewport.x as f32, viewport.y as f32);
+        let Some(new_position) = self.gizmo.update(
+            gizmo_input,
+            position,
+            &view_proj,
+            self.viewport_size(),
+        ) else {
+            return;
+        };
+
+        let new_position = [new_position.x, new_position.y, new_position.z];
+        self.scene.models[index].transform.position = new_position;
+        // 同步到 GUI 状态，否则每帧应用 GUI 参数时会被覆盖
+        if let Some(model) = self.gui_manager.state_mut().models.get_mut(index) {
+            model.position = new_position;
+        }
+    }
+
+    /// 选中子网格所属的模型
+    fn selected_model(&self) -> Option<usize> {
+        self.selected_subset
+            .and_then(|selected| subset_range(&self.scene_mesh.models, selected))
+            .map(|(model_index, _)| model_index)
+    }
+
+    /// 模型在 `level` 级别下要绘制的不透明索引范围（跳过隐藏的子网格，半透明子网格另行排序绘制）
+    fn draw_ranges(&self, model_index: usize, level: usize) -> Vec<Range<u32>> {
+        let model = &self.scene_mesh.models[model_index];
+        let first = subset_count(&self.scene_mesh.models[..model_index]);
+        let count = model.subsets.len().max(1);
+        let hidden: Vec<bool> = (0..count)
+            .map(|s| self.hidden_subsets.get(first + s).copied().unwrap_or(false) || model.is_transparent(s))
+            .collect();
+        model.visible_ranges(level, &hidden)
+    }
+
+    /// 场景绘制管线，当前填充模式不受支持时使用实心填充
+    fn scene_pipeline(&self, skinned: bool, pbr: bool, strip: bool) -> &wgpu::RenderPipeline {
+        let (_, pipelines) = self
+            .scene_pipelines
+            .iter()
+            .find(|(mode, _)| *mode == self.fill_mode)
+            .unwrap_or(&self.scene_pipelines[0]);
+        match (skinned, pbr, strip) {
+            (false, false, false) => &pipelines.simple,
+            (false, true, false) => &pipelines.pbr,
+            (false, false, true) => &pipelines.strip,
+            (false, true, true) => &pipelines.strip_pbr,
+            (true, false, _) => &pipelines.skinned,
+            (true, true, _) => &pipelines.skinned_pbr,
+        }
+    }
+
+    /// 半透明子网格的绘制管线，当前填充模式不受支持时使用实心填充
+    fn transparent_pipeline(&self, skinned: bool, pbr: bool) -> &wgpu::RenderPipeline {
+        let (_, pipelines) = self
+            .scene_pipelines
+            .iter()
+            .find(|(mode, _)| *mode == self.fill_mode)
+            .unwrap_or(&self.scene_pipelines[0]);
+        match (skinned, pbr) {
+            (false, false) => &pipelines.transparent,
+            (false, true) => &pipelines.transparent_pbr,
+            (true, false) => &pipelines.transparent_skinned,
+            (true, true) => &pipelines.transparent_skinned_pbr,
+        }
+    }
+
+    /// 绘制和接收输入的相机：视锥体可视化时为观察者相机（转台录制期间除外），否则为主相机
+    fn render_camera(&mut self) -> &mut Camera {
+        match &mut self.observer {
+            Some(observer) if self.turntable.is_none() => observer,
+            _ => &mut self.camera,
+        }
+    }
+
+    /// 视口尺寸（像素）
+    fn viewport_size(&self) -> [f32; 2] {
+        let rect = self.viewport_rect();
+        [rect.width.max(1) as f32, rect.height.max(1) as f32]
+    }
+
+    /// 场景视口，配置了目标宽高比时为窗口内居中的矩形
+    fn viewport_rect(&self) -> ViewportRect {
+        let (width, height) = self.main_targets.size();
+        ViewportRect::letterbox(width, height, self.target_aspect)
+    }
+
+    /// 仅更新相机（暂停时使用）
+    pub fn update_camera(&mut self, input_system: &mut InputSystem, delta_time: f32) {
+        self.gui_manager.state_mut().mouse_captured = Some(input_system.is_captured());
+        self.pending_shake_trauma += input_system.take_shake_trauma();
+        input_system.update_camera(self.render_camera(), delta_time);
+    }
+
+    /// 读回上一帧的线性深度（按行优先排列）
+    pub fn read_depth(&mut self) -> Result<Vec<f32>> {
+        let camera = self.render_camera();
+        let clip_planes = (camera.near_z(), camera.far_z());
+        let depth_view = match &self.depth_resolve {
+            Some(depth_resolve) => depth_resolve.resolved_view(),
+            None => self.main_targets.depth_view(),
+        };
+        self.depth_readback.read(
+            &self.gfx.device,
+            &self.gfx.queue,
+            depth_view,
+            self.main_targets.size(),
+            clip_planes,
+        )
+    }
+
+    /// 获取内置 GUI 的参数包
+    pub fn gui_packet(&self) -> GuiStatePacket {
+        self.gui_manager.state().to_packet()
     }
 
     pub fn apply_gui_packet(&mut self, packet: &GuiStatePacket) {
         self.scene.clear_color = packet.clear_color;
-        self.scene.model.transform.position = packet.model_position;
-        self.scene.model.transform.rotation = packet.model_rotation;
-        self.scene.model.transform.scale = packet.model_scale;
+        self.scene.clear_color_enabled = packet.clear_color_enabled != 0;
+        self.scene.clear_color_cycle = packet.clear_color_cycle != 0;
+        self.scene.ambient_color = packet.ambient_color;
+        self.scene.ambient_intensity = packet.ambient_intensity;
+        self.scene.exposure = packet.exposure;
+        self.scene.background = if packet.background_gradient != 0 {
+            BackgroundMode::Gradient {
+                top: packet.background_top,
+                bottom: packet.background_bottom,
+            }
+        } else {
+            BackgroundMode::Solid
+        };
+        self.pbr_shading = packet.pbr_shading != 0;
+
+        // 子网格材质有变化时重写对应顶点并重新上传
+        packet.apply_subset_visibility(&mut self.hidden_subsets);
+        let changed = packet.apply_subset_materials(&mut self.subset_materials);
+        for &i in &changed {
+            if let Some(material) = &self.subset_materials[i] {
+                self.scene_mesh.set_subset_material(i, material);
+            }
+        }
+        if !changed.is_empty() {
+            self.gfx.queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&self.scene_mesh.vertices));
+            self.gfx
+                .queue
+                .write_buffer(&self.material_buffer, 0, bytemuck::cast_slice(&self.scene_mesh.material_vertices));
+        }
+        self.scene.environment.ibl_intensity = packet.ibl_intensity;
+
+        // 切换过滤模式只更换（缓存的）采样器并重建绑定组
+        let texture_filter = TextureFilter::from_index(packet.texture_filter);
+        if texture_filter != self.texture_filter {
+            let ibl_sampler = self.samplers.get(&self.gfx.device, texture_filter, wgpu::AddressMode::ClampToEdge);
+            self.ibl.set_sampler(&self.gfx.device, &ibl_sampler);
+            self.set_model_filter(texture_filter);
+            self.texture_filter = texture_filter;
+        }
+        // 每种填充模式的管线已预先创建，切换只改变绘制时选择的管线
+        self.fill_mode = FillMode::from_index(packet.fill_mode);
+        packet.apply_model_transforms(&mut self.scene.models);
+        self.auto_rotate.enabled = packet.auto_rotate != 0;
+        self.auto_rotate.speed = packet.auto_rotate_speed;
+        self.auto_rotate.apply(&mut self.scene.models);
+        packet.apply_model_tints(&mut self.scene.models);
 
         self.directional_light.intensity = packet.light_intensity;
         self.directional_light.direction = Vector3::new(
@@ -519,39 +1405,233 @@ impl Renderer {
         )
         .normalize();
 
-        if (self.camera.fov_x() - packet.camera_fov * PI / 180.0).abs() > 0.01 {
-            self.camera.set_lens(
-                packet.camera_fov * PI / 180.0,
-                self.camera.aspect(),
-                packet.camera_near,
-                packet.camera_far,
+        self.ssao_settings = SsaoSettings {
+            enabled: packet.ssao_enabled != 0,
+            radius: packet.ssao_radius,
+            intensity: packet.ssao_intensity,
+        };
+        self.projection_jitter = packet.projection_jitter != 0;
+        self.frames_in_flight = packet.frames_in_flight(self.pipelined_frames);
+
+        self.selected_subset = (packet.selection_enabled != 0).then_some(packet.selected_subset as usize);
+        self.outline_color = packet.outline_color;
+        self.wireframe_overlay = packet.wireframe_overlay != 0;
+        self.wireframe_color = packet.wireframe_color;
+
+        packet.apply_camera_lens(&mut self.camera);
+
+        // 开启视锥体可视化时冻结主相机，观察者从主相机后方看向其视锥体
+        let frustum_debug = packet.frustum_debug != 0;
+        if frustum_debug != self.observer.is_some() {
+            self.observer = frustum_debug.then(|| self.camera.observer());
+        }
+        self.normal_lines = packet.normal_lines();
+        self.axis_indicator = packet.axis_indicator();
+        self.clip_planes = packet.clip_planes();
+
+        if packet.camera_shake_id != self.camera_shake_id {
+            self.camera_shake_id = packet.camera_shake_id;
+            self.pending_shake_trauma += SHAKE_TRAUMA;
+        }
+
+        if packet.lod_id != self.lod_id {
+            self.lod_id = packet.lod_id;
+            self.lod_ratio = packet.lod_ratio;
+            self.rebuild_scene_mesh();
+        }
+        if packet.subdivision_id != self.subdivision_id {
+            self.subdivision_id = packet.subdivision_id;
+            self.subdivision_levels = packet.subdivision_levels;
+            self.rebuild_scene_mesh();
+        }
+
+        if packet.turntable_id != self.turntable_id {
+            self.turntable_id = packet.turntable_id;
+            self.start_turntable(packet.turntable_frames);
+        }
+    }
+
+    /// 按过滤模式更换模型采样器并重建各模型的绑定组
+    fn set_model_filter(&mut self, filter: TextureFilter) {
+        self.model_sampler = self.samplers.get(&self.gfx.device, filter, wgpu::AddressMode::Repeat);
+        for (uniform, texture) in self.model_uniforms.iter_mut().zip(&self.model_textures) {
+            uniform.bind_group = create_model_bind_group(
+                &self.gfx.device,
+                &self.uniform_layout,
+                &uniform.buffer,
+                texture.as_deref().unwrap_or(&self.white_texture),
+                &self.model_sampler,
             );
         }
     }
 
-    /// 搴旂敤 GUI 鐘舵€佸埌鍦烘櫙
-    fn apply_gui_state(&mut self) {
-        let state = self.gui_manager.state();
-
-        let packet = GuiStatePacket {
-            clear_color: state.clear_color,
-            light_intensity: state.light_intensity,
-            light_direction: state.light_direction,
-            model_position: state.model_position,
-            model_rotation: state.model_rotation,
-            model_scale: state.model_scale,
-            camera_fov: state.camera_fov,
-            camera_near: state.camera_near,
-            camera_far: state.camera_far,
+    /// 开始转台录制
+    ///
+    /// 旋转中心为选中的模型，未选中时为整个场景。录制期间忽略新的录制请求。
+    fn start_turntable(&mut self, frame_count: u32) {
+        if self.turntable.is_some() {
+            return;
+        }
+        if !self.gfx.surface_config.usage.contains(wgpu::TextureUsages::COPY_SRC) {
+            warn!("Surface does not support COPY_SRC, turntable recording is unavailable");
+            return;
+        }
+
+        let selected = self.selected_model();
+        let spheres: Vec<(Vector3, f32)> = self
+            .scene_mesh
+            .models
+            .iter()
+            .zip(&self.scene.models)
+            .enumerate()
+            .filter(|(i, _)| selected.is_none_or(|selected| selected == *i))
+            .map(|(_, (mesh, config))| {
+                let scale = config.transform.scale.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+                (Vector3::from(config.transform.position), mesh.bounding_radius * scale)
+            })
+            .collect();
+        if spheres.is_empty() {
+            warn!("No models to record");
+            return;
+        }
+        let center = spheres.iter().map(|(c, _)| c).sum::<Vector3>() / spheres.len() as f32;
+        let radius = spheres
+            .iter()
+            .map(|(c, r)| (c - center).norm() + r)
+            .fold(0.0f32, f32::max);
+
+        let output_dir = self.gui_manager.state().turntable_output_dir.clone();
+        if let Err(e) = std::fs::create_dir_all(&output_dir) {
+            warn!("Failed to create turntable output directory {}: {}", output_dir, e);
+            return;
+        }
+
+        self.turntable_camera = Some((self.camera.position(), self.camera.look()));
+        let turntable = Turntable::new(&mut self.camera, center, radius, frame_count, output_dir);
+        info!(
+            "Recording turntable: {} frames to {}",
+            turntable.frame_count(),
+            turntable.output_dir().display()
+        );
+        self.gui_manager.state_mut().turntable_progress = Some((0, turntable.frame_count()));
+        self.turntable = Some(turntable);
+    }
+
+    /// 截取并保存转台的当前帧，录完或出错时结束录制并恢复相机
+    fn capture_turntable_frame(&mut self, texture: &wgpu::Texture) {
+        let Some(turntable) = self.turntable.as_mut() else {
+            return;
         };
 
+        let path = turntable.frame_path();
+        let saved = capture_frame(&self.gfx.device, &self.gfx.queue, texture, None)
+            .and_then(|image| image.save(&path).map_err(|e| DistRenderError::Runtime(e.to_string())));
+        if let Err(e) = &saved {
+            warn!("Failed to save turntable frame {}: {}", path.display(), e);
+        }
+
+        turntable.advance();
+        let progress = (turntable.frame(), turntable.frame_count());
+        if saved.is_err() || turntable.is_finished() {
+            info!("Turntable recording finished: {} frames", progress.0);
+            self.turntable = None;
+            self.gui_manager.state_mut().turntable_progress = None;
+            if let Some((position, look)) = self.turntable_camera.take() {
+                self.camera.look_at(position, position + look, Vector3::new(0.0, 1.0, 0.0));
+            }
+        } else {
+            self.gui_manager.state_mut().turntable_progress = Some(progress);
+        }
+    }
+
+    /// 重新加载场景模型，按当前的 LOD 比例简化并细分，替换顶点和索引缓冲
+    fn rebuild_scene_mesh(&mut self) {
+        info!(
+            "Rebuilding scene meshes with triangle ratio {:.2} and {} subdivision levels",
+            self.lod_ratio, self.subdivision_levels
+        );
+        let mut scene_mesh = self.load_scene_mesh();
+        self.apply_edited_materials(&mut scene_mesh);
+        self.upload_scene_mesh(scene_mesh);
+    }
+
+    /// 从磁盘重新加载场景模型，之前加载成功的模型失败时保留当前网格
+    ///
+    /// 子网格数量变化（模型在外部被修改）时丢弃编辑器中的材质修改和隐藏状态。
+    fn reload_scene_mesh(&mut self) -> Result<()> {
+        let mut scene_mesh = self.load_scene_mesh();
+        scene_mesh.check_reload(&self.scene_mesh.fallback_models, &self.scene.models)?;
+
+        if subset_count(&scene_mesh.models) == self.subset_materials.len() {
+            self.apply_edited_materials(&mut scene_mesh);
+        } else {
+            self.subset_materials = subset_materials(&scene_mesh.models);
+            self.hidden_subsets = vec![false; self.subset_materials.len()];
+            self.gui_manager.state_mut().subsets = subset_states(&self.subset_materials);
+        }
+        self.upload_scene_mesh(scene_mesh);
+        Ok(())
+    }
+
+    /// 按当前的 LOD 比例从磁盘加载场景模型
+    fn load_scene_mesh(&self) -> SceneMesh {
+        SceneMesh::load_with_lod(
+            &self.scene.models,
+            self.scene.fallback_primitive,
+            self.lod_ratio,
+            self.subdivision_levels,
+            self.scene_mesh.primitive_restart,
+            self.scene_mesh.mesh_cache,
+        )
+    }
+
+    /// 重新加载的网格使用导入时的材质，套用之前在编辑器中的修改
+    fn apply_edited_materials(&self, scene_mesh: &mut SceneMesh) {
+        let imported = subset_materials(&scene_mesh.models);
+        for (i, (material, imported)) in self.subset_materials.iter().zip(&imported).enumerate() {
+            if let Some(material) = material.as_ref().filter(|&m| Some(m) != imported.as_ref()) {
+                scene_mesh.set_subset_material(i, material);
+            }
+        }
+    }
+
+    /// 为加载好的场景网格创建顶点、索引和材质等缓冲并替换当前的网格
+    fn upload_scene_mesh(&mut self, scene_mesh: SceneMesh) {
+        let stats = &self.resource_stats;
+        let (names, scene) = (self.debug_names, &self.scene);
+        let (vertex_buffer, index_buffer, index_format) =
+            create_mesh_buffers(&self.gfx.device, stats, &scene_mesh, names, scene);
+
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        self.index_format = index_format;
+        self.skin_buffer = create_skin_buffer(&self.gfx.device, stats, &scene_mesh, names, scene);
+        self.material_buffer = create_material_buffer(&self.gfx.device, stats, &scene_mesh, names, scene);
+        self.joint_uniforms = create_joint_uniforms(&self.gfx.device, stats, &self.joint_layout, &scene_mesh);
+        self.gui_manager.state_mut().subset_count = subset_count(&scene_mesh.models);
+        // 网格重新加载后重新计算 UV 布局
+        self.gui_manager.state_mut().uv_layout = None;
+        self.scene_mesh = scene_mesh;
+    }
+
+    /// 搴旂敤 GUI 鐘舵€佸埌鍦烘櫙
+    fn apply_gui_state(&mut self) {
+        let packet = self.gui_manager.state().to_packet();
         self.apply_gui_packet(&packet);
+
+        // 选中的模型变化时重新计算 UV 布局
+        let state = self.gui_manager.state_mut();
+        if state.uv_model != state.uv_layout.as_ref().map(|(model, _)| *model) {
+            state.uv_layout = state
+                .uv_model
+                .and_then(|model| self.scene_mesh.uv_layout(model).map(|layout| (model, layout)));
+        }
     }
 
     /// 澶勭悊 GUI 浜嬩欢
     /// 杩斿洖 true 濡傛灉浜嬩欢琚?GUI 娑堣垂
     pub fn handle_gui_event(&mut self, event: &winit::event::WindowEvent) -> bool {
-        self.gui_manager.handle_event(self.gfx.window(), event)
+        self.gui_enabled && self.gui_manager.handle_event(self.gfx.window(), event)
     }
 
     /// 鑾峰彇绐楀彛寮曠敤
@@ -571,6 +1651,10 @@ impl crate::renderer::backend_trait::RenderBackend for Renderer {
     }
 
     fn draw(&mut self) -> crate::core::error::Result<()> {
+        self.draw().map(|_| ())
+    }
+
+    fn draw_with_stats(&mut self) -> crate::core::error::Result<FrameStats> {
         self.draw()
     }
 
@@ -578,11 +1662,329 @@ impl crate::renderer::backend_trait::RenderBackend for Renderer {
         self.update(input_system, delta_time)
     }
 
+    fn fixed_update(&mut self, delta_time: f32) {
+        self.fixed_update(delta_time)
+    }
+
+    fn tick(&mut self, event: &crate::core::event::TickEvent) {
+        self.total_time = event.total_time;
+        // 抖动按帧间隔衰减，叠加在主相机的视图矩阵上
+        self.camera.apply_shake(std::mem::take(&mut self.pending_shake_trauma), event.delta_time);
+    }
+
+    fn update_camera(&mut self, input_system: &mut InputSystem, delta_time: f32) {
+        self.update_camera(input_system, delta_time)
+    }
+
+    fn gui_packet(&self) -> Option<GuiStatePacket> {
+        Some(self.gui_packet())
+    }
+
     fn apply_gui_packet(&mut self, packet: &GuiStatePacket) {
         self.apply_gui_packet(packet)
     }
 
+    fn present_stats(&self) -> PresentStats {
+        self.present_stats
+    }
+
     fn handle_gui_event(&mut self, event: &winit::event::WindowEvent) -> bool {
         self.handle_gui_event(event)
     }
+
+    fn set_gui_enabled(&mut self, enabled: bool) {
+        self.gui_enabled = enabled;
+    }
+
+    fn read_depth(&mut self) -> Result<Vec<f32>> {
+        self.read_depth()
+    }
+
+    fn capture_region(&mut self, rect: Option<(u32, u32, u32, u32)>) -> Result<RgbaImage> {
+        self.capture_region(rect)
+    }
+
+    fn set_capture_hides_gui(&mut self, hide: bool) {
+        self.set_capture_hides_gui(hide)
+    }
+
+    fn reload_models(&mut self) -> crate::core::error::Result<()> {
+        self.reload_scene_mesh()
+    }
+
+    fn wait_idle(&self) -> crate::core::error::Result<()> {
+        self.gfx.wait_idle()
+    }
+}
+
+/// 把表面的呈现模式转换为呈现统计中的模式
+fn present_mode_kind(mode: wgpu::PresentMode) -> stats::PresentMode {
+    match mode {
+        wgpu::PresentMode::Fifo | wgpu::PresentMode::FifoRelaxed | wgpu::PresentMode::AutoVsync => {
+            stats::PresentMode::Fifo
+        }
+        wgpu::PresentMode::Mailbox => stats::PresentMode::Mailbox,
+        wgpu::PresentMode::Immediate | wgpu::PresentMode::AutoNoVsync => stats::PresentMode::Immediate,
+    }
+}
+
+/// 把配置中的深度偏移转换为 wgpu 的深度偏移状态
+fn depth_bias_state(bias: &DepthBias) -> wgpu::DepthBiasState {
+    wgpu::DepthBiasState {
+        constant: bias.constant,
+        slope_scale: bias.slope_scale,
+        clamp: bias.clamp,
+    }
+}
+
+/// 创建一种填充模式下的场景绘制管线，静态和蒙皮管线只有顶点阶段不同，简单着色和 PBR 管线只有片段入口不同，
+/// 三角形带管线只有图元拓扑不同，半透明管线只有混合和深度写入不同
+fn create_scene_pipelines(
+    device: &wgpu::Device,
+    shader_module: &wgpu::ShaderModule,
+    static_layout: &wgpu::PipelineLayout,
+    skinned_layout: &wgpu::PipelineLayout,
+    targets: &MainTargets,
+    depth_bias: &DepthBias,
+    depth_access: DepthAccess,
+    fill_mode: FillMode,
+) -> ScenePipelines {
+    let polygon_mode = match fill_mode {
+        FillMode::Solid => wgpu::PolygonMode::Fill,
+        FillMode::Wireframe => wgpu::PolygonMode::Line,
+        FillMode::Point => wgpu::PolygonMode::Point,
+    };
+    let create = |name: &str,
+                  layout: &wgpu::PipelineLayout,
+                  vertex: wgpu::VertexState,
+                  fragment_entry: &str,
+                  topology: wgpu::PrimitiveTopology,
+                  transparent: bool| {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(&format!("{} ({})", name, fill_mode.label())),
+            layout: Some(layout),
+            fragment: Some(wgpu::FragmentState {
+                module: shader_module,
+                entry_point: fragment_entry,
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: targets.format,
+                    blend: Some(if transparent {
+                        wgpu::BlendState::ALPHA_BLENDING
+                    } else {
+                        wgpu::BlendState::REPLACE
+                    }),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            vertex,
+            primitive: wgpu::PrimitiveState {
+                topology,
+                // 场景网格包含三角形带时总是使用 32 位索引
+                strip_index_format: (topology == wgpu::PrimitiveTopology::TriangleStrip)
+                    .then_some(wgpu::IndexFormat::Uint32),
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: depth_access.writes_depth() && !transparent,
+                depth_compare: if transparent { wgpu::CompareFunction::LessEqual } else { depth_access.compare() },
+                stencil: wgpu::StencilState::default(),
+                bias: depth_bias_state(depth_bias),
+            }),
+            multisample: targets.multisample(),
+            multiview: None,
+        })
+    };
+
+    let mesh_layout = WgpuVertexLayout::of::<MyVertex>();
+    let material_layout = WgpuVertexLayout::of::<MaterialVertex>();
+    let skin_layout = WgpuVertexLayout::of::<SkinVertex>();
+    let static_vertex = wgpu::VertexState {
+        module: shader_module,
+        entry_point: "vs_main",
+        buffers: &[mesh_layout.buffer_layout(), material_layout.buffer_layout()],
+    };
+    // 蒙皮管线额外输入关节索引和权重
+    let skinned_vertex = wgpu::VertexState {
+        module: shader_module,
+        entry_point: "vs_skinned",
+        buffers: &[mesh_layout.buffer_layout(), material_layout.buffer_layout(), skin_layout.buffer_layout()],
+    };
+    let list = wgpu::PrimitiveTopology::TriangleList;
+    let strip = wgpu::PrimitiveTopology::TriangleStrip;
+    ScenePipelines {
+        simple: create("Render Pipeline", static_layout, static_vertex.clone(), "fs_main", list, false),
+        pbr: create("PBR Render Pipeline", static_layout, static_vertex.clone(), "fs_pbr", list, false),
+        skinned: create("Skinned Render Pipeline", skinned_layout, skinned_vertex.clone(), "fs_main", list, false),
+        skinned_pbr: create("Skinned PBR Render Pipeline", skinned_layout, skinned_vertex.clone(), "fs_pbr", list, false),
+        strip: create("Strip Render Pipeline", static_layout, static_vertex.clone(), "fs_main", strip, false),
+        strip_pbr: create("Strip PBR Render Pipeline", static_layout, static_vertex.clone(), "fs_pbr", strip, false),
+        transparent: create("Transparent Render Pipeline", static_layout, static_vertex.clone(), "fs_main", list, true),
+        transparent_pbr: create("Transparent PBR Render Pipeline", static_layout, static_vertex, "fs_pbr", list, true),
+        transparent_skinned: create(
+            "Transparent Skinned Render Pipeline",
+            skinned_layout,
+            skinned_vertex.clone(),
+            "fs_main",
+            list,
+            true,
+        ),
+        transparent_skinned_pbr: create(
+            "Transparent Skinned PBR Render Pipeline",
+            skinned_layout,
+            skinned_vertex,
+            "fs_pbr",
+            list,
+            true,
+        ),
+    }
+}
+
+/// 把缓冲计入资源统计
+fn track_buffer(stats: &ResourceStats, kind: ResourceKind, buffer: wgpu::Buffer) -> Tracked<wgpu::Buffer> {
+    let size = buffer.size();
+    stats.track(buffer, kind, size)
+}
+
+/// 创建纹理视图并按纹理大小计入资源统计（视图保持纹理存活）
+fn track_texture_view(stats: &ResourceStats, texture: &wgpu::Texture) -> Tracked<wgpu::TextureView> {
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    stats.track(view, ResourceKind::Texture, texture_byte_size(texture))
+}
+
+/// 上传场景网格的蒙皮数据
+fn create_skin_buffer(
+    device: &wgpu::Device,
+    stats: &ResourceStats,
+    scene_mesh: &SceneMesh,
+    debug_names: DebugNames,
+    scene: &SceneConfig,
+) -> Tracked<wgpu::Buffer> {
+    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(&debug_names.scene_label("Skin Vertex Buffer", scene)),
+        contents: bytemuck::cast_slice(&scene_mesh.skin_vertices),
+        usage: wgpu::BufferUsages::VERTEX,
+    });
+    track_buffer(stats, ResourceKind::Vertex, buffer)
+}
+
+/// 上传场景网格的材质数据
+fn create_material_buffer(
+    device: &wgpu::Device,
+    stats: &ResourceStats,
+    scene_mesh: &SceneMesh,
+    debug_names: DebugNames,
+    scene: &SceneConfig,
+) -> Tracked<wgpu::Buffer> {
+    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(&debug_names.scene_label("Material Vertex Buffer", scene)),
+        contents: bytemuck::cast_slice(&scene_mesh.material_vertices),
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+    });
+    track_buffer(stats, ResourceKind::Vertex, buffer)
+}
+
+/// 创建模型的绑定组（UBO、纹理和采样器）
+fn create_model_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    buffer: &wgpu::Buffer,
+    texture: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Uniform Bind Group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(texture),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
+}
+
+/// 子网格编辑器的初始状态
+fn subset_states(materials: &[Option<Material>]) -> Vec<SubsetState> {
+    materials
+        .iter()
+        .enumerate()
+        .map(|(i, material)| SubsetState::new(i, material.as_ref()))
+        .collect()
+}
+
+/// 为每个蒙皮模型创建关节矩阵 Uniform（初始为单位矩阵）
+fn create_joint_uniforms(
+    device: &wgpu::Device,
+    stats: &ResourceStats,
+    layout: &wgpu::BindGroupLayout,
+    scene_mesh: &SceneMesh,
+) -> Vec<Option<ModelUniform>> {
+    let identity: [[f32; 4]; 4] = *Matrix4::identity().as_ref();
+    scene_mesh
+        .models
+        .iter()
+        .map(|model| {
+            model.skeleton.as_ref()?;
+            let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Joint Uniform Buffer"),
+                contents: bytemuck::cast_slice(&[identity; MAX_JOINTS]),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            });
+            let buffer = track_buffer(stats, ResourceKind::Uniform, buffer);
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Joint Bind Group"),
+                layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+            });
+            Some(ModelUniform { buffer, bind_group })
+        })
+        .collect()
+}
+
+/// 上传场景网格，返回顶点缓冲、索引缓冲和索引格式
+fn create_mesh_buffers(
+    device: &wgpu::Device,
+    stats: &ResourceStats,
+    scene_mesh: &SceneMesh,
+    debug_names: DebugNames,
+    scene: &SceneConfig,
+) -> (Tracked<wgpu::Buffer>, Tracked<wgpu::Buffer>, wgpu::IndexFormat) {
+    let index_data = scene_mesh.index_data();
+    let index_format = match index_data.width() {
+        IndexWidth::U16 => wgpu::IndexFormat::Uint16,
+        IndexWidth::U32 => wgpu::IndexFormat::Uint32,
+    };
+
+    let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(&debug_names.scene_label("Vertex Buffer", scene)),
+        contents: bytemuck::cast_slice(&scene_mesh.vertices),
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+    });
+    let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(&debug_names.scene_label("Index Buffer", scene)),
+        contents: index_data.as_bytes(),
+        usage: wgpu::BufferUsages::INDEX,
+    });
+
+    (
+        track_buffer(stats, ResourceKind::Vertex, vertex_buffer),
+        track_buffer(stats, ResourceKind::Index, index_buffer),
+        index_format,
+    )
 }
@@ -0,0 +1,137 @@
+//! 采样器创建与缓存
+//!
+//! 把后端无关的 [`TextureFilter`] 转换为 wgpu 采样器。相同的 (过滤模式, 寻址模式, 各向异性等级)
+//! 只创建一次，模型纹理和图像光照等共享同一个采样器。
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tracing::debug;
+
+use crate::renderer::resources::sampler::TextureFilter;
+
+/// 采样器缓存的键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SamplerKey {
+    pub filter: TextureFilter,
+    /// 三个方向使用相同的寻址模式
+    pub address_mode: wgpu::AddressMode,
+    /// 各向异性等级（非各向异性模式为 1）
+    pub anisotropy: u16,
+}
+
+/// 采样器缓存
+///
+/// 各向异性等级统一钳制到创建时给出的上限（配置和设备能力中较小的一个）。
+#[derive(Debug)]
+pub struct SamplerCache<S = wgpu::Sampler> {
+    max_anisotropy: u16,
+    samplers: HashMap<SamplerKey, Arc<S>>,
+}
+
+impl<S> SamplerCache<S> {
+    pub fn new(max_anisotropy: u16) -> Self {
+        Self {
+            max_anisotropy: max_anisotropy.max(1),
+            samplers: HashMap::new(),
+        }
+    }
+
+    /// 过滤模式和寻址模式对应的键
+    pub fn key(&self, filter: TextureFilter, address_mode: wgpu::AddressMode) -> SamplerKey {
+        // 各向异性过滤要求三个过滤器都是线性的，只在各向异性模式下启用
+        let anisotropy = if filter == TextureFilter::Anisotropic { self.max_anisotropy } else { 1 };
+        SamplerKey {
+            filter,
+            address_mode,
+            anisotropy,
+        }
+    }
+
+    /// 已缓存的采样器数量
+    pub fn len(&self) -> usize {
+        self.samplers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samplers.is_empty()
+    }
+
+    /// 取出缓存的采样器，没有时用 `create` 创建
+    fn get_or_create(&mut self, key: SamplerKey, create: impl FnOnce(&SamplerKey) -> S) -> Arc<S> {
+        self.samplers
+            .entry(key)
+            .or_insert_with(|| {
+                debug!("Creating sampler {:?}", key);
+                Arc::new(create(&key))
+            })
+            .clone()
+    }
+}
+
+impl SamplerCache<wgpu::Sampler> {
+    /// 按过滤模式和寻址模式取得采样器
+    pub fn get(
+        &mut self,
+        device: &wgpu::Device,
+        filter: TextureFilter,
+        address_mode: wgpu::AddressMode,
+    ) -> Arc<wgpu::Sampler> {
+        let key = self.key(filter, address_mode);
+        self.get_or_create(key, |key| create_sampler(device, key))
+    }
+}
+
+/// 按键创建采样器
+fn create_sampler(device: &wgpu::Device, key: &SamplerKey) -> wgpu::Sampler {
+    let (filter_mode, mipmap_filter) = match key.filter {
+        TextureFilter::Nearest => (wgpu::FilterMode::Nearest, wgpu::FilterMode::Nearest),
+        TextureFilter::Bilinear => (wgpu::FilterMode::Linear, wgpu::FilterMode::Nearest),
+        TextureFilter::Trilinear | TextureFilter::Anisotropic => (wgpu::FilterMode::Linear, wgpu::FilterMode::Linear),
+    };
+
+    device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some(&format!("{} Sampler ({:?}, {}x)", key.filter.label(), key.address_mode, key.anisotropy)),
+        address_mode_u: key.address_mode,
+        address_mode_v: key.address_mode,
+        address_mode_w: key.address_mode,
+        mag_filter: filter_mode,
+        min_filter: filter_mode,
+        mipmap_filter,
+        anisotropy_clamp: key.anisotropy,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_sampler_config_is_cached() {
+        let mut cache: SamplerCache<u32> = SamplerCache::new(8);
+        let mut created = 0;
+        let mut get = |cache: &mut SamplerCache<u32>, filter, address_mode| {
+            let key = cache.key(filter, address_mode);
+            cache.get_or_create(key, |_| {
+                created += 1;
+                created
+            })
+        };
+
+        let first = get(&mut cache, TextureFilter::Anisotropic, wgpu::AddressMode::Repeat);
+        let second = get(&mut cache, TextureFilter::Anisotropic, wgpu::AddressMode::Repeat);
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // 寻址模式或过滤模式不同时创建新的采样器
+        let clamped = get(&mut cache, TextureFilter::Anisotropic, wgpu::AddressMode::ClampToEdge);
+        let trilinear = get(&mut cache, TextureFilter::Trilinear, wgpu::AddressMode::Repeat);
+        assert!(!Arc::ptr_eq(&first, &clamped));
+        assert!(!Arc::ptr_eq(&first, &trilinear));
+        assert_eq!(cache.len(), 3);
+
+        // 各向异性等级钳制到缓存的上限
+        assert_eq!(cache.key(TextureFilter::Anisotropic, wgpu::AddressMode::Repeat).anisotropy, 8);
+        assert_eq!(cache.key(TextureFilter::Trilinear, wgpu::AddressMode::Repeat).anisotropy, 1);
+    }
+}
@@ -0,0 +1,31 @@
+// 渐变背景
+// 用一个覆盖整个屏幕的三角形绘制自上而下的两色渐变
+
+struct BackgroundParams {
+    top: vec4<f32>,
+    bottom: vec4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> params: BackgroundParams;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // 0 为屏幕顶部，1 为屏幕底部
+    @location(0) t: f32,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    // 顶点 (-1, -1)、(3, -1)、(-1, 3) 覆盖整个裁剪空间
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(uv * 2.0 - 1.0, 1.0, 1.0);
+    out.t = 1.0 - uv.y;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return mix(params.top, params.bottom, clamp(in.t, 0.0, 1.0));
+}
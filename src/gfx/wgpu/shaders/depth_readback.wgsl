@@ -0,0 +1,25 @@
+// 深度读回
+// 用全屏三角形把深度缓冲逐像素写入 R32Float 纹理，多重采样时取第 0 个采样
+
+@group(0) @binding(0)
+var depth_texture: texture_depth_2d;
+
+@group(0) @binding(0)
+var depth_texture_ms: texture_depth_multisampled_2d;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    // 顶点 (-1, -1)、(3, -1)、(-1, 3) 覆盖整个裁剪空间
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_single(@builtin(position) position: vec4<f32>) -> @location(0) f32 {
+    return textureLoad(depth_texture, vec2<i32>(position.xy), 0);
+}
+
+@fragment
+fn fs_multisampled(@builtin(position) position: vec4<f32>) -> @location(0) f32 {
+    return textureLoad(depth_texture_ms, vec2<i32>(position.xy), 0);
+}
@@ -0,0 +1,46 @@
+// 多重采样深度解析
+// 用全屏三角形把多重采样深度缓冲的每个像素合并为一个深度值，写入单采样深度纹理
+
+@group(0) @binding(0)
+var depth_texture: texture_depth_multisampled_2d;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    // 顶点 (-1, -1)、(3, -1)、(-1, 3) 覆盖整个裁剪空间
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+// 最近的采样（边缘像素取前景深度，推荐）
+@fragment
+fn fs_min(@builtin(position) position: vec4<f32>) -> @builtin(frag_depth) f32 {
+    let pixel = vec2<i32>(position.xy);
+    var depth = 1.0;
+    for (var i = 0u; i < textureNumSamples(depth_texture); i++) {
+        depth = min(depth, textureLoad(depth_texture, pixel, i32(i)));
+    }
+    return depth;
+}
+
+// 最远的采样
+@fragment
+fn fs_max(@builtin(position) position: vec4<f32>) -> @builtin(frag_depth) f32 {
+    let pixel = vec2<i32>(position.xy);
+    var depth = 0.0;
+    for (var i = 0u; i < textureNumSamples(depth_texture); i++) {
+        depth = max(depth, textureLoad(depth_texture, pixel, i32(i)));
+    }
+    return depth;
+}
+
+// 所有采样的平均值（边缘像素得到前景和背景之间并不存在的深度）
+@fragment
+fn fs_average(@builtin(position) position: vec4<f32>) -> @builtin(frag_depth) f32 {
+    let pixel = vec2<i32>(position.xy);
+    let count = textureNumSamples(depth_texture);
+    var depth = 0.0;
+    for (var i = 0u; i < count; i++) {
+        depth += textureLoad(depth_texture, pixel, i32(i));
+    }
+    return depth / f32(count);
+}
@@ -0,0 +1,164 @@
+// 图像光照（IBL）预计算
+// 从等距柱状投影的环境贴图生成辐照度立方体贴图、预过滤环境立方体贴图和 BRDF 积分查找表
+
+const PI: f32 = 3.14159265359;
+
+// 辐照度积分的角度步长（弧度）
+const IRRADIANCE_SAMPLE_DELTA: f32 = 0.05;
+// 预过滤和 BRDF 积分的重要性采样数
+const PREFILTER_SAMPLE_COUNT: u32 = 256u;
+const BRDF_SAMPLE_COUNT: u32 = 512u;
+
+// 等距柱状投影的环境贴图（线性空间）
+@group(0) @binding(0)
+var environment: texture_2d<f32>;
+
+// 立方体贴图输出（6 层二维数组，依次为 +X、-X、+Y、-Y、+Z、-Z）
+@group(0) @binding(1)
+var cube_output: texture_storage_2d_array<rgba16float, write>;
+
+// 预过滤参数（每级 mip 一份）
+struct PrefilterParams {
+    roughness: f32,
+    _padding: vec3<f32>,
+}
+
+@group(0) @binding(2)
+var<uniform> prefilter: PrefilterParams;
+
+// BRDF 积分查找表输出：x 为 N·V，y 为粗糙度
+@group(0) @binding(3)
+var brdf_output: texture_storage_2d<rgba16float, write>;
+
+// 立方体贴图某一面上像素中心对应的方向
+fn cube_direction(pixel: vec2<u32>, size: u32, face: u32) -> vec3<f32> {
+    let uv = (vec2<f32>(pixel) + 0.5) / f32(size) * 2.0 - 1.0;
+    var direction: vec3<f32>;
+    switch face {
+        case 0u: { direction = vec3<f32>(1.0, -uv.y, -uv.x); }
+        case 1u: { direction = vec3<f32>(-1.0, -uv.y, uv.x); }
+        case 2u: { direction = vec3<f32>(uv.x, 1.0, uv.y); }
+        case 3u: { direction = vec3<f32>(uv.x, -1.0, -uv.y); }
+        case 4u: { direction = vec3<f32>(uv.x, -uv.y, 1.0); }
+        default: { direction = vec3<f32>(-uv.x, -uv.y, -1.0); }
+    }
+    return normalize(direction);
+}
+
+// 按方向读取环境贴图（Y 轴向上，最近点采样）
+fn sample_environment(direction: vec3<f32>) -> vec3<f32> {
+    let size = textureDimensions(environment);
+    let u = atan2(direction.z, direction.x) / (2.0 * PI) + 0.5;
+    let v = acos(clamp(direction.y, -1.0, 1.0)) / PI;
+    let texel = min(vec2<u32>(vec2<f32>(u, v) * vec2<f32>(size)), size - 1u);
+    return textureLoad(environment, texel, 0).rgb;
+}
+
+// 以 `normal` 为 Z 轴的切线空间基
+fn tangent_basis(normal: vec3<f32>) -> mat3x3<f32> {
+    var up = vec3<f32>(0.0, 1.0, 0.0);
+    if (abs(normal.y) > 0.999) {
+        up = vec3<f32>(0.0, 0.0, 1.0);
+    }
+    let tangent = normalize(cross(up, normal));
+    let bitangent = cross(normal, tangent);
+    return mat3x3<f32>(tangent, bitangent, normal);
+}
+
+// Hammersley 低差异序列
+fn hammersley(i: u32, count: u32) -> vec2<f32> {
+    return vec2<f32>(f32(i) / f32(count), f32(reverseBits(i)) * 2.3283064365386963e-10);
+}
+
+// 按 GGX 分布对半向量重要性采样
+fn importance_sample_ggx(xi: vec2<f32>, normal: vec3<f32>, roughness: f32) -> vec3<f32> {
+    let a = roughness * roughness;
+    let phi = 2.0 * PI * xi.x;
+    let cos_theta = sqrt((1.0 - xi.y) / (1.0 + (a * a - 1.0) * xi.y));
+    let sin_theta = sqrt(1.0 - cos_theta * cos_theta);
+    let h = vec3<f32>(cos(phi) * sin_theta, sin(phi) * sin_theta, cos_theta);
+    return normalize(tangent_basis(normal) * h);
+}
+
+// 辐照度：法线半球内余弦加权的环境光积分
+@compute @workgroup_size(8, 8, 1)
+fn cs_irradiance(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(cube_output);
+    if (id.x >= size.x || id.y >= size.y) {
+        return;
+    }
+
+    let normal = cube_direction(id.xy, size.x, id.z);
+    let basis = tangent_basis(normal);
+    var irradiance = vec3<f32>(0.0);
+    var count = 0.0;
+    for (var phi = 0.0; phi < 2.0 * PI; phi += IRRADIANCE_SAMPLE_DELTA) {
+        for (var theta = 0.0; theta < 0.5 * PI; theta += IRRADIANCE_SAMPLE_DELTA) {
+            let local = vec3<f32>(sin(theta) * cos(phi), sin(theta) * sin(phi), cos(theta));
+            irradiance += sample_environment(basis * local) * cos(theta) * sin(theta);
+            count += 1.0;
+        }
+    }
+
+    textureStore(cube_output, id.xy, id.z, vec4<f32>(PI * irradiance / count, 1.0));
+}
+
+// 预过滤环境贴图：假设 N = V = R，按 GGX 分布对环境光加权平均
+@compute @workgroup_size(8, 8, 1)
+fn cs_prefilter(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(cube_output);
+    if (id.x >= size.x || id.y >= size.y) {
+        return;
+    }
+
+    let normal = cube_direction(id.xy, size.x, id.z);
+    var color = vec3<f32>(0.0);
+    var weight = 0.0;
+    for (var i = 0u; i < PREFILTER_SAMPLE_COUNT; i++) {
+        let h = importance_sample_ggx(hammersley(i, PREFILTER_SAMPLE_COUNT), normal, prefilter.roughness);
+        let l = normalize(2.0 * dot(normal, h) * h - normal);
+        let n_dot_l = dot(normal, l);
+        if (n_dot_l > 0.0) {
+            color += sample_environment(l) * n_dot_l;
+            weight += n_dot_l;
+        }
+    }
+
+    textureStore(cube_output, id.xy, id.z, vec4<f32>(color / max(weight, 1e-4), 1.0));
+}
+
+// BRDF 积分：Fresnel 项 F0 的缩放（x）和偏移（y）
+@compute @workgroup_size(8, 8, 1)
+fn cs_brdf(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(brdf_output);
+    if (id.x >= size.x || id.y >= size.y) {
+        return;
+    }
+
+    let n_dot_v = (f32(id.x) + 0.5) / f32(size.x);
+    let roughness = (f32(id.y) + 0.5) / f32(size.y);
+    let v = vec3<f32>(sqrt(1.0 - n_dot_v * n_dot_v), 0.0, n_dot_v);
+    let normal = vec3<f32>(0.0, 0.0, 1.0);
+    // 图像光照的 Smith 几何项使用 k = a^2 / 2
+    let k = roughness * roughness / 2.0;
+
+    var scale = 0.0;
+    var bias = 0.0;
+    for (var i = 0u; i < BRDF_SAMPLE_COUNT; i++) {
+        let h = importance_sample_ggx(hammersley(i, BRDF_SAMPLE_COUNT), normal, roughness);
+        let l = normalize(2.0 * dot(v, h) * h - v);
+        let n_dot_l = max(l.z, 0.0);
+        if (n_dot_l > 0.0) {
+            let n_dot_h = max(h.z, 0.0);
+            let v_dot_h = max(dot(v, h), 0.0);
+            let g = (n_dot_v / (n_dot_v * (1.0 - k) + k)) * (n_dot_l / (n_dot_l * (1.0 - k) + k));
+            let g_vis = g * v_dot_h / max(n_dot_h * n_dot_v, 1e-4);
+            let fc = pow(1.0 - v_dot_h, 5.0);
+            scale += (1.0 - fc) * g_vis;
+            bias += fc * g_vis;
+        }
+    }
+
+    let count = f32(BRDF_SAMPLE_COUNT);
+    textureStore(brdf_output, id.xy, vec4<f32>(scale / count, bias / count, 0.0, 1.0));
+}
@@ -0,0 +1,52 @@
+// 选中轮廓通道
+// 沿法线方向在屏幕空间外扩固定像素宽度，只绘制背面（反向外壳），以纯色输出
+
+struct UniformBufferObject {
+    model: mat4x4<f32>,
+    view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    light_dir: vec4<f32>,
+    light_color: vec4<f32>,
+    camera_pos: vec4<f32>,
+    ambient: vec4<f32>,
+    tint: vec4<f32>,
+}
+
+struct OutlineParams {
+    color: vec4<f32>,
+    viewport: vec2<f32>,    // 视口尺寸（像素）
+    width: f32,             // 轮廓宽度（像素）
+    _padding: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> ubo: UniformBufferObject;
+
+@group(1) @binding(0)
+var<uniform> params: OutlineParams;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> @builtin(position) vec4<f32> {
+    let mvp = ubo.projection * ubo.view * ubo.model;
+    var clip = mvp * vec4<f32>(input.position, 1.0);
+
+    // 法线投影到裁剪空间后取屏幕方向，偏移量乘以 w 使透视除法后宽度恒定
+    let clip_normal = (mvp * vec4<f32>(input.normal, 0.0)).xy;
+    if (length(clip_normal) > 1e-6) {
+        let offset = normalize(clip_normal) * params.width * 2.0 / params.viewport;
+        clip = vec4<f32>(clip.xy + offset * clip.w, clip.zw);
+    }
+
+    return clip;
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return params.color;
+}
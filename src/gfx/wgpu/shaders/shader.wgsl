@@ -1,5 +1,7 @@
 // WGSL Shader for wgpu backend
-// 实现 Blinn-Phong 光照模型
+// 实现 Blinn-Phong 光照模型（简单着色）和 Cook-Torrance 金属-粗糙度 PBR 光照模型
+
+const PI: f32 = 3.14159265359;
 
 // Uniform Buffer Object - MVP 矩阵和光照数据
 struct UniformBufferObject {
@@ -8,17 +10,92 @@ struct UniformBufferObject {
     projection: mat4x4<f32>,
     light_dir: vec4<f32>,      // xyz: 方向, w: 保留
     light_color: vec4<f32>,    // rgb: 颜色 * 强度, a: 保留
-    camera_pos: vec4<f32>,     // xyz: 位置, w: 保留
+    camera_pos: vec4<f32>,     // xyz: 位置, w: 非零时手动做 sRGB 编码
+    ambient: vec4<f32>,        // rgb: 环境光颜色 * 强度, a: 曝光
+    tint: vec4<f32>,           // 模型颜色色调，乘以最终颜色
+    clip_planes: array<vec4<f32>, 2>, // 剖切平面 (法线, d)，未启用时为 (0, 0, 0, 1)
+    normal_matrix: mat4x4<f32>,       // 模型矩阵的逆转置，非均匀缩放时保持法线垂直于表面
 }
 
 @group(0) @binding(0)
 var<uniform> ubo: UniformBufferObject;
 
+// 模型纹理（基础颜色贴图，没有纹理的模型为白色）
+@group(0) @binding(1)
+var model_texture: texture_2d<f32>;
+
+@group(0) @binding(2)
+var model_sampler: sampler;
+
+// SSAO 参数（布局与 ssao.wgsl 一致）
+struct SsaoParams {
+    projection: mat4x4<f32>,
+    kernel: array<vec4<f32>, 16>,
+    radius: f32,
+    intensity: f32,
+    bias: f32,
+    enabled: u32,
+}
+
+// 模糊后的环境光遮蔽纹理
+@group(1) @binding(0)
+var ao_texture: texture_2d<f32>;
+
+@group(1) @binding(1)
+var<uniform> ssao: SsaoParams;
+
+// 图像光照参数（布局与 ibl.rs 中的 IblUniform 一致）
+struct IblParams {
+    intensity: f32,
+    max_lod: f32,      // 预过滤环境贴图的最高 mip 级别（对应粗糙度 1）
+    enabled: u32,
+    _padding: u32,
+}
+
+// 图像光照贴图（只在 PBR 着色中使用）
+@group(2) @binding(0)
+var irradiance_map: texture_cube<f32>;
+
+@group(2) @binding(1)
+var prefiltered_map: texture_cube<f32>;
+
+@group(2) @binding(2)
+var brdf_lut: texture_2d<f32>;
+
+@group(2) @binding(3)
+var ibl_sampler: sampler;
+
+@group(2) @binding(4)
+var<uniform> ibl: IblParams;
+
 // 顶点输入结构
 struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,       // 基础颜色
+    @location(5) material: vec2<f32>,    // x: 金属度, y: 粗糙度
+    @location(6) tex_coord: vec2<f32>,
+    @location(7) opacity: f32,           // 不透明度，只有半透明材质小于 1
+}
+
+// 关节矩阵（长度与 geometry::skinning::MAX_JOINTS 一致）
+struct JointMatrices {
+    matrices: array<mat4x4<f32>, 64>,
+}
+
+@group(3) @binding(0)
+var<uniform> joints: JointMatrices;
+
+// 蒙皮顶点输入结构
+struct SkinnedVertexInput {
     @location(0) position: vec3<f32>,
     @location(1) normal: vec3<f32>,
     @location(2) color: vec3<f32>,
+    @location(3) joint_indices: vec4<u32>,
+    @location(4) joint_weights: vec4<f32>,
+    @location(5) material: vec2<f32>,
+    @location(6) tex_coord: vec2<f32>,
+    @location(7) opacity: f32,
 }
 
 // 顶点输出 / 片段输入结构
@@ -27,22 +104,33 @@ struct VertexOutput {
     @location(0) frag_pos: vec3<f32>,
     @location(1) frag_normal: vec3<f32>,
     @location(2) frag_color: vec3<f32>,
+    @location(3) frag_material: vec2<f32>,
+    @location(4) frag_tex_coord: vec2<f32>,
+    @location(5) frag_opacity: f32,
 }
 
 // 顶点着色器
 @vertex
 fn vs_main(input: VertexInput) -> VertexOutput {
+    return transform_vertex(input);
+}
+
+// 模型空间顶点变换到世界空间和裁剪空间
+fn transform_vertex(input: VertexInput) -> VertexOutput {
     var output: VertexOutput;
 
     // 计算世界坐标
     let world_pos = ubo.model * vec4<f32>(input.position, 1.0);
     output.frag_pos = world_pos.xyz;
 
-    // 变换法向量到世界空间（忽略平移）
-    output.frag_normal = (ubo.model * vec4<f32>(input.normal, 0.0)).xyz;
+    // 用法线矩阵变换法向量到世界空间（忽略平移，片段着色器中重新归一化）
+    output.frag_normal = (ubo.normal_matrix * vec4<f32>(input.normal, 0.0)).xyz;
 
-    // 传递顶点颜色
+    // 传递顶点颜色、材质参数、纹理坐标和不透明度
     output.frag_color = input.color;
+    output.frag_material = input.material;
+    output.frag_tex_coord = input.tex_coord;
+    output.frag_opacity = input.opacity;
 
     // 计算裁剪空间坐标 (MVP 变换)
     output.clip_position = ubo.projection * ubo.view * world_pos;
@@ -50,41 +138,159 @@ fn vs_main(input: VertexInput) -> VertexOutput {
     return output;
 }
 
+// 蒙皮顶点着色器：按权重混合最多 4 个关节矩阵后再做模型变换
+@vertex
+fn vs_skinned(input: SkinnedVertexInput) -> VertexOutput {
+    let skin = joints.matrices[input.joint_indices.x] * input.joint_weights.x
+        + joints.matrices[input.joint_indices.y] * input.joint_weights.y
+        + joints.matrices[input.joint_indices.z] * input.joint_weights.z
+        + joints.matrices[input.joint_indices.w] * input.joint_weights.w;
+
+    var vertex: VertexInput;
+    vertex.position = (skin * vec4<f32>(input.position, 1.0)).xyz;
+    vertex.normal = (skin * vec4<f32>(input.normal, 0.0)).xyz;
+    vertex.color = input.color;
+    vertex.material = input.material;
+    vertex.tex_coord = input.tex_coord;
+    vertex.opacity = input.opacity;
+    return transform_vertex(vertex);
+}
+
+// 丢弃位于剖切平面负侧的片段
+fn clip_fragment(world_pos: vec3<f32>) {
+    for (var i = 0; i < 2; i++) {
+        let plane = ubo.clip_planes[i];
+        if dot(plane.xyz, world_pos) + plane.w < 0.0 {
+            discard;
+        }
+    }
+}
+
+// 方向光 Blinn-Phong 光照（与其他后端共享，加载时展开）
+#include "../../shaders/directional_light.inc"
+
 // 片段着色器 - Blinn-Phong 光照模型
 @fragment
 fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    clip_fragment(input.frag_pos);
+    let base_color = input.frag_color * textureSample(model_texture, model_sampler, input.frag_tex_coord).rgb;
+
     // 归一化法向量
     let N = normalize(input.frag_normal);
 
-    // 光照方向（从表面指向光源）
-    let L = normalize(-ubo.light_dir.xyz);
-
     // 视线方向（从表面指向相机）
     let V = normalize(ubo.camera_pos.xyz - input.frag_pos);
 
-    // 半向量（Blinn-Phong）
-    let H = normalize(L + V);
+    // 环境光分量
+    let ambient = ubo.ambient.rgb * ambient_occlusion(input.clip_position.xy);
+
+    // 环境光 + 漫反射 + 镜面反射
+    let lighting = directional_light(N, V, ubo.light_dir.xyz, ubo.light_color.rgb, ambient);
+
+    // 最终颜色 = 光照 * 材质颜色 * 曝光 * 色调
+    // 没有色调映射，乘以曝光后超过 1 的分量在写入交换链时被截断
+    let final_color = lighting * base_color * ubo.ambient.a * ubo.tint.rgb;
+
+    return vec4<f32>(encode_output(final_color), input.frag_opacity);
+}
 
-    // 漫反射系数
-    let diff = max(dot(N, L), 0.0);
+// 线性颜色写入交换链前的编码：sRGB 交换链由硬件编码，UNORM 交换链在这里手动编码
+fn encode_output(color: vec3<f32>) -> vec3<f32> {
+    if (ubo.camera_pos.w == 0.0) {
+        return color;
+    }
+    let c = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0));
+    return select(1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055, c * 12.92, c <= vec3<f32>(0.0031308));
+}
 
-    // 镜面反射系数（只有在光照可见时计算）
-    var spec = 0.0;
-    if (diff > 0.0) {
-        spec = pow(max(dot(N, H), 0.0), 32.0);  // 32 是高光指数
+// 环境光遮蔽（未启用 SSAO 时为 1）
+fn ambient_occlusion(pixel: vec2<f32>) -> f32 {
+    if (ssao.enabled == 0u) {
+        return 1.0;
     }
+    let ao_sample = textureLoad(ao_texture, vec2<i32>(pixel), 0).r;
+    return pow(ao_sample, ssao.intensity);
+}
 
-    // 环境光分量
-    let ambient = 0.1 * ubo.light_color.rgb;
+// GGX / Trowbridge-Reitz 法线分布函数
+fn distribution_ggx(n_dot_h: f32, roughness: f32) -> f32 {
+    let a = roughness * roughness;
+    let a2 = a * a;
+    let d = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+    return a2 / (PI * d * d);
+}
+
+// Smith 几何遮蔽函数（Schlick-GGX 近似，直接光照的 k = (r + 1)^2 / 8）
+fn geometry_smith(n_dot_v: f32, n_dot_l: f32, roughness: f32) -> f32 {
+    let r = roughness + 1.0;
+    let k = r * r / 8.0;
+    let g_v = n_dot_v / (n_dot_v * (1.0 - k) + k);
+    let g_l = n_dot_l / (n_dot_l * (1.0 - k) + k);
+    return g_v * g_l;
+}
 
-    // 漫反射分量
-    let diffuse = diff * ubo.light_color.rgb;
+// Schlick 菲涅尔近似
+fn fresnel_schlick(cos_theta: f32, f0: vec3<f32>) -> vec3<f32> {
+    return f0 + (vec3<f32>(1.0) - f0) * pow(1.0 - cos_theta, 5.0);
+}
+
+// 考虑粗糙度的 Schlick 菲涅尔近似（环境光照没有单一的半向量，用 N·V 代替）
+fn fresnel_schlick_roughness(cos_theta: f32, f0: vec3<f32>, roughness: f32) -> vec3<f32> {
+    return f0 + (max(vec3<f32>(1.0 - roughness), f0) - f0) * pow(1.0 - cos_theta, 5.0);
+}
+
+// 片段着色器 - Cook-Torrance 金属-粗糙度 PBR 光照模型
+@fragment
+fn fs_pbr(input: VertexOutput) -> @location(0) vec4<f32> {
+    clip_fragment(input.frag_pos);
+    let texture_color = textureSample(model_texture, model_sampler, input.frag_tex_coord).rgb;
+
+    let N = normalize(input.frag_normal);
+    let L = normalize(-ubo.light_dir.xyz);
+    let V = normalize(ubo.camera_pos.xyz - input.frag_pos);
+    let H = normalize(L + V);
+
+    let n_dot_l = max(dot(N, L), 0.0);
+    let n_dot_v = max(dot(N, V), 1e-4);
+    let n_dot_h = max(dot(N, H), 0.0);
+    let h_dot_v = max(dot(H, V), 0.0);
+
+    let albedo = input.frag_color * texture_color;
+    let metallic = clamp(input.frag_material.x, 0.0, 1.0);
+    // 粗糙度过小时高光退化为极亮的点，限制下限
+    let roughness = clamp(input.frag_material.y, 0.04, 1.0);
 
-    // 镜面反射分量
-    let specular = spec * ubo.light_color.rgb;
+    // 电介质的基础反射率取 0.04，金属使用基础颜色
+    let f0 = mix(vec3<f32>(0.04), albedo, metallic);
+    let F = fresnel_schlick(h_dot_v, f0);
+    let D = distribution_ggx(n_dot_h, roughness);
+    let G = geometry_smith(n_dot_v, n_dot_l, roughness);
+    let specular = D * G * F / max(4.0 * n_dot_v * n_dot_l, 1e-4);
+
+    // 被反射的能量不再参与漫反射，金属没有漫反射
+    let k_d = (vec3<f32>(1.0) - F) * (1.0 - metallic);
+    let diffuse = k_d * albedo / PI;
+
+    // 光源颜色按与简单着色相同的亮度解释，乘以 π 抵消 Lambert 项的 1/π
+    let direct = (diffuse + specular) * ubo.light_color.rgb * n_dot_l * PI;
+
+    // 环境光：有环境贴图时使用图像光照，否则为常量环境光
+    var ambient = ubo.ambient.rgb * albedo;
+    if (ibl.enabled != 0u) {
+        let R = reflect(-V, N);
+        let F_ambient = fresnel_schlick_roughness(n_dot_v, f0, roughness);
+        let k_d_ambient = (vec3<f32>(1.0) - F_ambient) * (1.0 - metallic);
+        let irradiance = textureSampleLevel(irradiance_map, ibl_sampler, N, 0.0).rgb;
+        let prefiltered = textureSampleLevel(prefiltered_map, ibl_sampler, R, roughness * ibl.max_lod).rgb;
+        let brdf = textureSampleLevel(brdf_lut, ibl_sampler, vec2<f32>(n_dot_v, roughness), 0.0).rg;
+        let diffuse_ibl = k_d_ambient * irradiance * albedo;
+        let specular_ibl = prefiltered * (F_ambient * brdf.x + brdf.y);
+        ambient = (diffuse_ibl + specular_ibl) * ibl.intensity;
+    }
+    ambient *= ambient_occlusion(input.clip_position.xy);
 
-    // 最终颜色 = (环境光 + 漫反射 + 镜面反射) * 材质颜色
-    let final_color = (ambient + diffuse + specular) * input.frag_color;
+    // 没有色调映射，乘以曝光后超过 1 的分量在写入交换链时被截断
+    let final_color = (ambient + direct) * ubo.ambient.a * ubo.tint.rgb;
 
-    return vec4<f32>(final_color, 1.0);
+    return vec4<f32>(encode_output(final_color), input.frag_opacity);
 }
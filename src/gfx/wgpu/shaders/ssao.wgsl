@@ -0,0 +1,113 @@
+// SSAO 通道和模糊通道
+// 使用全屏三角形，在半球核内采样视空间位置估计遮蔽
+
+const KERNEL_SIZE: i32 = 16;
+
+struct SsaoParams {
+    projection: mat4x4<f32>,
+    kernel: array<vec4<f32>, 16>,   // 切线空间半球采样核
+    radius: f32,
+    intensity: f32,
+    bias: f32,
+    enabled: u32,
+}
+
+@group(0) @binding(0)
+var position_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var normal_texture: texture_2d<f32>;
+
+@group(0) @binding(2)
+var<uniform> params: SsaoParams;
+
+@group(0) @binding(3)
+var ao_input: texture_2d<f32>;
+
+// 全屏三角形顶点着色器
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+// 屏幕空间伪随机数，用于旋转采样核
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(12.9898, 78.233))) * 43758.5453);
+}
+
+@fragment
+fn fs_ssao(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
+    let size = vec2<i32>(textureDimensions(position_texture));
+    let coord = vec2<i32>(frag_coord.xy);
+
+    // w 为 0 表示背景，没有遮蔽
+    let position_sample = textureLoad(position_texture, coord, 0);
+    if (position_sample.w == 0.0) {
+        return vec4<f32>(1.0);
+    }
+
+    let position = position_sample.xyz;
+    let normal = normalize(textureLoad(normal_texture, coord, 0).xyz);
+
+    // 随机旋转向量 + Gram-Schmidt 构建 TBN
+    let random_vec = vec3<f32>(
+        hash(frag_coord.xy) * 2.0 - 1.0,
+        hash(frag_coord.yx + vec2<f32>(17.0, 31.0)) * 2.0 - 1.0,
+        hash(frag_coord.xy + vec2<f32>(53.0, 7.0)) * 2.0 - 1.0,
+    );
+    var tangent = random_vec - normal * dot(random_vec, normal);
+    if (length(tangent) < 1e-3) {
+        tangent = select(vec3<f32>(0.0, 1.0, 0.0), vec3<f32>(1.0, 0.0, 0.0), abs(normal.y) > 0.9);
+        tangent = tangent - normal * dot(tangent, normal);
+    }
+    tangent = normalize(tangent);
+    let bitangent = cross(normal, tangent);
+    let tbn = mat3x3<f32>(tangent, bitangent, normal);
+
+    var occlusion = 0.0;
+    for (var i = 0; i < KERNEL_SIZE; i = i + 1) {
+        let sample_pos = position + (tbn * params.kernel[i].xyz) * params.radius;
+
+        // 投影到屏幕，与主通道使用相同的投影矩阵
+        let offset = params.projection * vec4<f32>(sample_pos, 1.0);
+        if (offset.w <= 0.0) {
+            continue;
+        }
+        let ndc = offset.xy / offset.w;
+        let uv = vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+        let sample_coord = clamp(vec2<i32>(uv * vec2<f32>(size)), vec2<i32>(0), size - vec2<i32>(1));
+
+        let scene_sample = textureLoad(position_texture, sample_coord, 0);
+        if (scene_sample.w == 0.0) {
+            continue;
+        }
+
+        // 距离过远的遮挡物贡献衰减，避免轮廓处出现黑边
+        let range_check = smoothstep(0.0, 1.0, params.radius / abs(position.z - scene_sample.z));
+        if (scene_sample.z >= sample_pos.z + params.bias) {
+            occlusion = occlusion + range_check;
+        }
+    }
+
+    let ao = 1.0 - occlusion / f32(KERNEL_SIZE);
+    return vec4<f32>(ao, ao, ao, 1.0);
+}
+
+// 4x4 盒式模糊，消除随机旋转带来的噪声
+@fragment
+fn fs_blur(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
+    let size = vec2<i32>(textureDimensions(ao_input));
+    let coord = vec2<i32>(frag_coord.xy);
+
+    var result = 0.0;
+    for (var x = -2; x < 2; x = x + 1) {
+        for (var y = -2; y < 2; y = y + 1) {
+            let c = clamp(coord + vec2<i32>(x, y), vec2<i32>(0), size - vec2<i32>(1));
+            result = result + textureLoad(ao_input, c, 0).r;
+        }
+    }
+
+    let ao = result / 16.0;
+    return vec4<f32>(ao, ao, ao, 1.0);
+}
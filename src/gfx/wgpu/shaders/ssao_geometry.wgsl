@@ -0,0 +1,56 @@
+// SSAO 几何预通道
+// 输出视空间位置和法线，供 SSAO 通道采样
+
+struct UniformBufferObject {
+    model: mat4x4<f32>,
+    view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    light_dir: vec4<f32>,
+    light_color: vec4<f32>,
+    camera_pos: vec4<f32>,
+    ambient: vec4<f32>,
+    tint: vec4<f32>,
+    clip_planes: array<vec4<f32>, 2>,
+    normal_matrix: mat4x4<f32>,   // 模型矩阵的逆转置
+}
+
+@group(0) @binding(0)
+var<uniform> ubo: UniformBufferObject;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) view_pos: vec3<f32>,
+    @location(1) view_normal: vec3<f32>,
+}
+
+// 多渲染目标输出
+struct GeometryTargets {
+    @location(0) position: vec4<f32>,   // xyz: 视空间位置, w: 1 表示有几何体
+    @location(1) normal: vec4<f32>,     // xyz: 视空间法线
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+
+    let view_pos = ubo.view * ubo.model * vec4<f32>(input.position, 1.0);
+    output.view_pos = view_pos.xyz;
+    output.view_normal = (ubo.view * ubo.normal_matrix * vec4<f32>(input.normal, 0.0)).xyz;
+    output.clip_position = ubo.projection * view_pos;
+
+    return output;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> GeometryTargets {
+    var targets: GeometryTargets;
+    targets.position = vec4<f32>(input.view_pos, 1.0);
+    targets.normal = vec4<f32>(normalize(input.view_normal), 0.0);
+    return targets;
+}
@@ -0,0 +1,76 @@
+// 线框叠加通道
+// 在实心着色之上以线框填充再绘制一次网格，输出纯色边线；深度偏移把边线拉到表面之前
+
+struct UniformBufferObject {
+    model: mat4x4<f32>,
+    view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    light_dir: vec4<f32>,
+    light_color: vec4<f32>,
+    camera_pos: vec4<f32>,
+    ambient: vec4<f32>,
+    tint: vec4<f32>,
+    clip_planes: array<vec4<f32>, 2>, // 剖切平面 (法线, d)，未启用时为 (0, 0, 0, 1)
+}
+
+struct WireframeParams {
+    color: vec4<f32>,   // 边线颜色，a 为不透明度
+}
+
+// 关节矩阵（长度与 geometry::skinning::MAX_JOINTS 一致）
+struct JointMatrices {
+    matrices: array<mat4x4<f32>, 64>,
+}
+
+@group(0) @binding(0)
+var<uniform> ubo: UniformBufferObject;
+
+@group(1) @binding(0)
+var<uniform> params: WireframeParams;
+
+@group(2) @binding(0)
+var<uniform> joints: JointMatrices;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_pos: vec3<f32>,
+}
+
+fn transform_position(position: vec3<f32>) -> VertexOutput {
+    var output: VertexOutput;
+    let world_pos = ubo.model * vec4<f32>(position, 1.0);
+    output.world_pos = world_pos.xyz;
+    output.clip_position = ubo.projection * ubo.view * world_pos;
+    return output;
+}
+
+@vertex
+fn vs_main(@location(0) position: vec3<f32>) -> VertexOutput {
+    return transform_position(position);
+}
+
+// 蒙皮顶点与主通道使用相同的关节矩阵混合，边线跟随动画
+@vertex
+fn vs_skinned(
+    @location(0) position: vec3<f32>,
+    @location(3) joint_indices: vec4<u32>,
+    @location(4) joint_weights: vec4<f32>,
+) -> VertexOutput {
+    let skin = joints.matrices[joint_indices.x] * joint_weights.x
+        + joints.matrices[joint_indices.y] * joint_weights.y
+        + joints.matrices[joint_indices.z] * joint_weights.z
+        + joints.matrices[joint_indices.w] * joint_weights.w;
+    return transform_position((skin * vec4<f32>(position, 1.0)).xyz);
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    // 与主通道一致，剖切掉的部分不绘制边线
+    for (var i = 0; i < 2; i++) {
+        let plane = ubo.clip_planes[i];
+        if dot(plane.xyz, input.world_pos) + plane.w < 0.0 {
+            discard;
+        }
+    }
+    return params.color;
+}
@@ -0,0 +1,567 @@
+//! SSAO（屏幕空间环境光遮蔽）
+//!
+//! 由三个通道组成：
+//! - 几何预通道：输出视空间位置和法线
+//! - SSAO 通道：在法线朝向的半球内采样，估计遮蔽程度
+//! - 模糊通道：消除随机旋转带来的噪声
+//!
+//! 输出的遮蔽纹理在主光照通道中调制环境光项。关闭时主通道使用原来的常量环境光。
+
+use std::ops::Range;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use crate::gfx::letterbox::ViewportRect;
+use crate::math::{Matrix4, Vector3};
+use crate::gfx::wgpu::vertex_layout::WgpuVertexLayout;
+use crate::renderer::resources::vertex::MyVertex;
+use crate::renderer::stats::FrameStats;
+
+/// 采样核大小（需与 ssao.wgsl 保持一致）
+pub const KERNEL_SIZE: usize = 16;
+
+const POSITION_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+const NORMAL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+const AO_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R8Unorm;
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// 深度比较偏移，避免平面自遮蔽
+const SSAO_BIAS: f32 = 0.025;
+
+/// SSAO 参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SsaoSettings {
+    /// 是否启用
+    pub enabled: bool,
+
+    /// 采样半径（视空间单位）
+    pub radius: f32,
+
+    /// 遮蔽强度（对遮蔽因子取幂）
+    pub intensity: f32,
+}
+
+impl Default for SsaoSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            radius: 0.5,
+            intensity: 1.0,
+        }
+    }
+}
+
+/// SSAO Uniform（布局与 ssao.wgsl 中的 SsaoParams 一致）
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+struct SsaoUniform {
+    projection: [[f32; 4]; 4],
+    kernel: [[f32; 4]; KERNEL_SIZE],
+    radius: f32,
+    intensity: f32,
+    bias: f32,
+    enabled: u32,
+}
+
+/// 几何预通道的一次绘制（一个模型）
+pub struct SsaoDraw<'a> {
+    /// 该模型的 UBO 绑定组
+    pub uniform_bind_group: &'a wgpu::BindGroup,
+    pub indices: Range<u32>,
+    /// 索引是否为带图元重启的三角形带
+    pub strip: bool,
+}
+
+/// 几何预通道绘制的网格
+pub struct SsaoGeometry<'a> {
+    pub vertex_buffer: &'a wgpu::Buffer,
+    pub index_buffer: &'a wgpu::Buffer,
+    pub index_format: wgpu::IndexFormat,
+    /// 与主通道相同的视口，使预通道的像素与主通道对齐
+    pub viewport: ViewportRect,
+    pub draws: Vec<SsaoDraw<'a>>,
+}
+
+/// 随窗口尺寸变化的渲染目标
+struct SsaoTargets {
+    position: wgpu::TextureView,
+    normal: wgpu::TextureView,
+    depth: wgpu::TextureView,
+    ao: wgpu::TextureView,
+    blurred: wgpu::TextureView,
+}
+
+impl SsaoTargets {
+    fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let create = |label: &str, format: wgpu::TextureFormat| {
+            device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some(label),
+                    size: wgpu::Extent3d {
+                        width: width.max(1),
+                        height: height.max(1),
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        };
+
+        Self {
+            position: create("SSAO Position Texture", POSITION_FORMAT),
+            normal: create("SSAO Normal Texture", NORMAL_FORMAT),
+            depth: create("SSAO Depth Texture", DEPTH_FORMAT),
+            ao: create("SSAO Texture", AO_FORMAT),
+            blurred: create("SSAO Blur Texture", AO_FORMAT),
+        }
+    }
+}
+
+/// SSAO 渲染通道
+pub struct SsaoPass {
+    geometry_pipeline: wgpu::RenderPipeline,
+    /// 三角形带使用的几何预通道管线
+    geometry_strip_pipeline: wgpu::RenderPipeline,
+    ssao_pipeline: wgpu::RenderPipeline,
+    blur_pipeline: wgpu::RenderPipeline,
+
+    ssao_layout: wgpu::BindGroupLayout,
+    blur_layout: wgpu::BindGroupLayout,
+    output_layout: wgpu::BindGroupLayout,
+
+    uniform_buffer: wgpu::Buffer,
+    kernel: [[f32; 4]; KERNEL_SIZE],
+
+    targets: SsaoTargets,
+    ssao_bind_group: wgpu::BindGroup,
+    blur_bind_group: wgpu::BindGroup,
+    output_bind_group: wgpu::BindGroup,
+}
+
+impl SsaoPass {
+    /// 创建 SSAO 通道
+    ///
+    /// # 参数
+    ///
+    /// - `uniform_layout`: 主通道的 UBO 绑定组布局（几何预通道复用）
+    /// - `width`/`height`: 渲染目标尺寸
+    pub fn new(
+        device: &wgpu::Device,
+        uniform_layout: &wgpu::BindGroupLayout,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let kernel = generate_kernel();
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("SSAO Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[SsaoUniform {
+                projection: *Matrix4::identity().as_ref(),
+                kernel,
+                radius: SsaoSettings::default().radius,
+                intensity: SsaoSettings::default().intensity,
+                bias: SSAO_BIAS,
+                enabled: 0,
+            }]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let texture_entry = |binding: u32| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let uniform_entry = |binding: u32| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+        let ssao_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("SSAO Bind Group Layout"),
+            entries: &[texture_entry(0), texture_entry(1), uniform_entry(2)],
+        });
+        let blur_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("SSAO Blur Bind Group Layout"),
+            entries: &[texture_entry(3)],
+        });
+        // 主通道使用：遮蔽纹理 + SSAO 参数
+        let output_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("SSAO Output Bind Group Layout"),
+            entries: &[texture_entry(0), uniform_entry(1)],
+        });
+
+        // 几何预通道
+        let geometry_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("SSAO Geometry Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/ssao_geometry.wgsl").into()),
+        });
+        let geometry_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("SSAO Geometry Pipeline Layout"),
+            bind_group_layouts: &[uniform_layout],
+            push_constant_ranges: &[],
+        });
+        let mesh_layout = WgpuVertexLayout::of::<MyVertex>();
+        let create_geometry_pipeline = |label: &str, topology: wgpu::PrimitiveTopology| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&geometry_layout),
+                vertex: wgpu::VertexState {
+                    module: &geometry_shader,
+                    entry_point: "vs_main",
+                    buffers: &[mesh_layout.buffer_layout()],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &geometry_shader,
+                    entry_point: "fs_main",
+                    targets: &[
+                        Some(wgpu::ColorTargetState {
+                            format: POSITION_FORMAT,
+                            blend: None,
+                            write_mask: wgpu::ColorWrites::ALL,
+                        }),
+                        Some(wgpu::ColorTargetState {
+                            format: NORMAL_FORMAT,
+                            blend: None,
+                            write_mask: wgpu::ColorWrites::ALL,
+                        }),
+                    ],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology,
+                    // 三角形带只出现在 32 位索引缓冲中
+                    strip_index_format: (topology == wgpu::PrimitiveTopology::TriangleStrip)
+                        .then_some(wgpu::IndexFormat::Uint32),
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: Some(wgpu::Face::Back),
+                    ..Default::default()
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: DEPTH_FORMAT,
+                    depth_write_enabled: true,
+                    depth_compare: wgpu::CompareFunction::Less,
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+            })
+        };
+        let geometry_pipeline = create_geometry_pipeline("SSAO Geometry Pipeline", wgpu::PrimitiveTopology::TriangleList);
+        let geometry_strip_pipeline =
+            create_geometry_pipeline("SSAO Geometry Strip Pipeline", wgpu::PrimitiveTopology::TriangleStrip);
+
+        // SSAO 和模糊通道（全屏三角形）
+        let ssao_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("SSAO Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/ssao.wgsl").into()),
+        });
+        let fullscreen_pipeline = |label: &str, layout: &wgpu::BindGroupLayout, entry_point: &str| {
+            let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some(label),
+                bind_group_layouts: &[layout],
+                push_constant_ranges: &[],
+            });
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &ssao_shader,
+                    entry_point: "vs_fullscreen",
+                    buffers: &[],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &ssao_shader,
+                    entry_point,
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: AO_FORMAT,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+            })
+        };
+        let ssao_pipeline = fullscreen_pipeline("SSAO Pipeline", &ssao_layout, "fs_ssao");
+        let blur_pipeline = fullscreen_pipeline("SSAO Blur Pipeline", &blur_layout, "fs_blur");
+
+        let targets = SsaoTargets::new(device, width, height);
+        let (ssao_bind_group, blur_bind_group, output_bind_group) = create_bind_groups(
+            device,
+            &targets,
+            &uniform_buffer,
+            &ssao_layout,
+            &blur_layout,
+            &output_layout,
+        );
+
+        Self {
+            geometry_pipeline,
+            geometry_strip_pipeline,
+            ssao_pipeline,
+            blur_pipeline,
+            ssao_layout,
+            blur_layout,
+            output_layout,
+            uniform_buffer,
+            kernel,
+            targets,
+            ssao_bind_group,
+            blur_bind_group,
+            output_bind_group,
+        }
+    }
+
+    /// 窗口尺寸变化时重建渲染目标和绑定组
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        self.targets = SsaoTargets::new(device, width, height);
+        let (ssao_bind_group, blur_bind_group, output_bind_group) = create_bind_groups(
+            device,
+            &self.targets,
+            &self.uniform_buffer,
+            &self.ssao_layout,
+            &self.blur_layout,
+            &self.output_layout,
+        );
+        self.ssao_bind_group = ssao_bind_group;
+        self.blur_bind_group = blur_bind_group;
+        self.output_bind_group = output_bind_group;
+    }
+
+    /// 主通道使用的绑定组布局
+    pub fn output_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.output_layout
+    }
+
+    /// 主通道使用的绑定组（遮蔽纹理 + 参数）
+    pub fn output_bind_group(&self) -> &wgpu::BindGroup {
+        &self.output_bind_group
+    }
+
+    /// 更新 SSAO 参数
+    ///
+    /// `projection` 必须与主通道使用的投影矩阵一致。
+    pub fn update(&self, queue: &wgpu::Queue, projection: &Matrix4, settings: &SsaoSettings) {
+        let uniform = SsaoUniform {
+            projection: *projection.as_ref(),
+            kernel: self.kernel,
+            radius: settings.radius,
+            intensity: settings.intensity,
+            bias: SSAO_BIAS,
+            enabled: settings.enabled as u32,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniform]));
+    }
+
+    /// 录制 SSAO 的三个通道
+    ///
+    /// 必须在主通道之前调用，`draws` 为空时只清空目标。绘制调用计入 `stats`。
+    pub fn encode(&self, encoder: &mut wgpu::CommandEncoder, geometry: &SsaoGeometry, stats: &mut FrameStats) {
+        // 1. 几何预通道
+        {
+            let clear = wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                store: wgpu::StoreOp::Store,
+            };
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("SSAO Geometry Pass"),
+                color_attachments: &[
+                    Some(wgpu::RenderPassColorAttachment {
+                        view: &self.targets.position,
+                        resolve_target: None,
+                        ops: clear,
+                    }),
+                    Some(wgpu::RenderPassColorAttachment {
+                        view: &self.targets.normal,
+                        resolve_target: None,
+                        ops: clear,
+                    }),
+                ],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.targets.depth,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            if !geometry.draws.is_empty() {
+                let viewport = geometry.viewport;
+                pass.set_viewport(
+                    viewport.x as f32,
+                    viewport.y as f32,
+                    viewport.width as f32,
+                    viewport.height as f32,
+                    0.0,
+                    1.0,
+                );
+                pass.set_scissor_rect(viewport.x, viewport.y, viewport.width, viewport.height);
+                pass.set_vertex_buffer(0, geometry.vertex_buffer.slice(..));
+                pass.set_index_buffer(geometry.index_buffer.slice(..), geometry.index_format);
+                for draw in &geometry.draws {
+                    pass.set_pipeline(if draw.strip { &self.geometry_strip_pipeline } else { &self.geometry_pipeline });
+                    pass.set_bind_group(0, draw.uniform_bind_group, &[]);
+                    pass.draw_indexed(draw.indices.clone(), 0, 0..1);
+                    stats.record_draw(draw.indices.len() as u32);
+                }
+            }
+        }
+
+        // 2. SSAO 通道
+        Self::fullscreen_pass(encoder, "SSAO Pass", &self.targets.ao, &self.ssao_pipeline, &self.ssao_bind_group);
+        stats.record_draw(3);
+
+        // 3. 模糊通道
+        Self::fullscreen_pass(encoder, "SSAO Blur Pass", &self.targets.blurred, &self.blur_pipeline, &self.blur_bind_group);
+        stats.record_draw(3);
+    }
+
+    fn fullscreen_pass(
+        encoder: &mut wgpu::CommandEncoder,
+        label: &str,
+        target: &wgpu::TextureView,
+        pipeline: &wgpu::RenderPipeline,
+        bind_group: &wgpu::BindGroup,
+    ) {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
+
+/// 创建依赖渲染目标的绑定组
+fn create_bind_groups(
+    device: &wgpu::Device,
+    targets: &SsaoTargets,
+    uniform_buffer: &wgpu::Buffer,
+    ssao_layout: &wgpu::BindGroupLayout,
+    blur_layout: &wgpu::BindGroupLayout,
+    output_layout: &wgpu::BindGroupLayout,
+) -> (wgpu::BindGroup, wgpu::BindGroup, wgpu::BindGroup) {
+    let ssao_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("SSAO Bind Group"),
+        layout: ssao_layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&targets.position),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(&targets.normal),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: uniform_buffer.as_entire_binding(),
+            },
+        ],
+    });
+
+    let blur_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("SSAO Blur Bind Group"),
+        layout: blur_layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 3,
+            resource: wgpu::BindingResource::TextureView(&targets.ao),
+        }],
+    });
+
+    let output_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("SSAO Output Bind Group"),
+        layout: output_layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&targets.blurred),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: uniform_buffer.as_entire_binding(),
+            },
+        ],
+    });
+
+    (ssao_bind_group, blur_bind_group, output_bind_group)
+}
+
+/// 生成切线空间的半球采样核
+///
+/// 使用确定性的 xorshift 序列，样本向中心聚集以强调近处遮蔽。
+fn generate_kernel() -> [[f32; 4]; KERNEL_SIZE] {
+    let mut state: u32 = 0x9E37_79B9;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as f32 / u32::MAX as f32
+    };
+
+    let mut kernel = [[0.0; 4]; KERNEL_SIZE];
+    for (i, sample) in kernel.iter_mut().enumerate() {
+        let direction = Vector3::new(next() * 2.0 - 1.0, next() * 2.0 - 1.0, next())
+            .try_normalize(1e-6)
+            .unwrap_or_else(Vector3::z);
+        let t = i as f32 / KERNEL_SIZE as f32;
+        let scale = 0.1 + 0.9 * t * t;
+        let v = direction * next() * scale;
+        *sample = [v.x, v.y, v.z, 0.0];
+    }
+    kernel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernel_in_hemisphere() {
+        for sample in generate_kernel() {
+            let length = (sample[0] * sample[0] + sample[1] * sample[1] + sample[2] * sample[2]).sqrt();
+            assert!(sample[2] >= 0.0);
+            assert!(length <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_uniform_size_matches_shader() {
+        // mat4x4 + array<vec4, 16> + 4 个标量
+        assert_eq!(std::mem::size_of::<SsaoUniform>(), 64 + 16 * KERNEL_SIZE + 16);
+    }
+}
@@ -0,0 +1,113 @@
+//! 主通道渲染目标
+//!
+//! 主通道的深度缓冲，以及开启 MSAA 时的多重采样颜色缓冲。多重采样颜色在主通道结束时
+//! 解析（resolve）到交换链图像，之后的 GUI 通道直接绘制在解析后的单采样图像上，
+//! 界面文字不经过多重采样，保持清晰。
+
+use crate::gfx::wgpu::texture::texture_byte_size;
+use crate::renderer::resources::resource_stats::{ResourceKind, ResourceStats, Tracked};
+
+/// 深度缓冲格式
+pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// 主通道的颜色和深度目标
+pub struct MainTargets {
+    /// 颜色格式（与交换链相同）
+    pub format: wgpu::TextureFormat,
+    /// 采样数，1 表示不使用 MSAA
+    pub sample_count: u32,
+    /// 目标尺寸（像素）
+    size: (u32, u32),
+    depth_view: Tracked<wgpu::TextureView>,
+    /// 多重采样颜色缓冲（`sample_count` 为 1 时为 `None`，直接绘制到交换链图像）
+    msaa_view: Option<Tracked<wgpu::TextureView>>,
+}
+
+impl MainTargets {
+    pub fn new(
+        device: &wgpu::Device,
+        stats: &ResourceStats,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let create = |label: &str, format: wgpu::TextureFormat, usage: wgpu::TextureUsages| {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            stats.track(view, ResourceKind::Texture, texture_byte_size(&texture))
+        };
+
+        Self {
+            format,
+            sample_count,
+            size: (width, height),
+            // 深度缓冲可作为纹理绑定，供深度读回使用
+            depth_view: create(
+                "Depth Texture",
+                DEPTH_FORMAT,
+                wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            ),
+            msaa_view: (sample_count > 1)
+                .then(|| create("MSAA Color Texture", format, wgpu::TextureUsages::RENDER_ATTACHMENT)),
+        }
+    }
+
+    /// 按新的窗口大小重建
+    pub fn resize(&mut self, device: &wgpu::Device, stats: &ResourceStats, width: u32, height: u32) {
+        *self = Self::new(device, stats, self.format, self.sample_count, width, height);
+    }
+
+    /// 主通道管线的多重采样状态
+    pub fn multisample(&self) -> wgpu::MultisampleState {
+        wgpu::MultisampleState {
+            count: self.sample_count,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        }
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    pub fn depth_view(&self) -> &wgpu::TextureView {
+        &self.depth_view
+    }
+
+    /// 主通道的颜色附件
+    ///
+    /// 开启 MSAA 时绘制到多重采样缓冲并解析到 `resolved`，否则直接绘制到 `resolved`。
+    /// 多重采样缓冲的内容同样保留（`Store`），关闭每帧清除时下一帧仍能在其上继续绘制。
+    pub fn color_attachment<'a>(
+        &'a self,
+        resolved: &'a wgpu::TextureView,
+        load: wgpu::LoadOp<wgpu::Color>,
+    ) -> wgpu::RenderPassColorAttachment<'a> {
+        let (view, resolve_target) = match &self.msaa_view {
+            Some(msaa_view) => (&**msaa_view, Some(resolved)),
+            None => (resolved, None),
+        };
+        wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target,
+            ops: wgpu::Operations {
+                load,
+                store: wgpu::StoreOp::Store,
+            },
+        }
+    }
+}
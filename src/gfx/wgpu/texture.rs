@@ -0,0 +1,191 @@
+//! 模型纹理
+//!
+//! 从图片文件或模型内嵌的图片加载模型的基础颜色贴图（sRGB），在 CPU 上生成完整的 mip 链后上传。
+//! 超过最大尺寸的图片先用盒式滤波按比例缩小，限制单张纹理占用的显存。
+//! 没有纹理的模型绑定 1×1 白色纹理，着色器统一采样。
+
+use tracing::{info, warn};
+
+use crate::geometry::material::TextureSource;
+use crate::gfx::debug_name::DebugNames;
+
+/// 模型纹理格式（采样时自动转换到线性空间）
+const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// 1×1 白色纹理（乘以基础颜色后不改变颜色）
+pub fn create_white_texture(device: &wgpu::Device, queue: &wgpu::Queue) -> wgpu::Texture {
+    let white = image::RgbaImage::from_pixel(1, 1, image::Rgba([255; 4]));
+    create_texture(device, queue, "White Texture", &[white])
+}
+
+/// 加载纹理，最长边超过 `max_size` 时先缩小；图片文件读取失败时记录警告并返回 `None`
+///
+/// 纹理标签默认为来源路径，启用调试名称时为 "Base Color Texture: 文件名"。
+pub fn load_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    source: &TextureSource,
+    max_size: u32,
+    debug_names: DebugNames,
+) -> Option<wgpu::Texture> {
+    let label = source.key();
+    let image = match source {
+        TextureSource::File(path) => match image::open(path) {
+            Ok(image) => image.to_rgba8(),
+            Err(e) => {
+                warn!("Failed to load texture {}: {}, using white texture", label, e);
+                return None;
+            }
+        },
+        TextureSource::Embedded { image, .. } => image.clone(),
+    };
+    let (original_width, original_height) = image.dimensions();
+    let image = downscale_to_fit(image, max_size);
+    let (width, height) = image.dimensions();
+    if (width, height) != (original_width, original_height) {
+        info!(
+            "Texture {} downscaled from {}x{} to {}x{} (max texture size {})",
+            label, original_width, original_height, width, height, max_size
+        );
+    }
+    let levels = mip_chain(image);
+    info!("Texture loaded: {} ({}x{}, {} mip levels)", label, width, height, levels.len());
+    let texture_label = debug_names.name("Base Color Texture", &label).unwrap_or(label);
+    Some(create_texture(device, queue, &texture_label, &levels))
+}
+
+/// 纹理所有 mip 级别占用的字节数（按格式的拷贝块大小估算，多重采样纹理乘以采样数）
+pub fn texture_byte_size(texture: &wgpu::Texture) -> u64 {
+    let size = texture.size();
+    let texel_size = texture.format().block_copy_size(None).unwrap_or(4) as u64;
+    (0..texture.mip_level_count())
+        .map(|mip| {
+            let width = (size.width >> mip).max(1) as u64;
+            let height = (size.height >> mip).max(1) as u64;
+            width * height * size.depth_or_array_layers as u64 * texel_size
+        })
+        .sum::<u64>()
+        * texture.sample_count() as u64
+}
+
+/// 创建纹理并上传各级 mip
+fn create_texture(device: &wgpu::Device, queue: &wgpu::Queue, label: &str, levels: &[image::RgbaImage]) -> wgpu::Texture {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width: levels[0].width(),
+            height: levels[0].height(),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: levels.len() as u32,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TEXTURE_FORMAT,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+
+    for (mip, level) in levels.iter().enumerate() {
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: mip as u32,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            level.as_raw(),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(level.width() * 4),
+                rows_per_image: Some(level.height()),
+            },
+            wgpu::Extent3d {
+                width: level.width(),
+                height: level.height(),
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    texture
+}
+
+/// 按比例缩小到最长边不超过 `max_size`（不需要缩小时原样返回）
+///
+/// 使用盒式滤波：每个目标像素取其覆盖的源像素矩形的平均值。
+fn downscale_to_fit(image: image::RgbaImage, max_size: u32) -> image::RgbaImage {
+    let (width, height) = image.dimensions();
+    let longest = width.max(height);
+    if longest <= max_size {
+        return image;
+    }
+
+    let scale = max_size as f64 / longest as f64;
+    let target_width = ((width as f64 * scale).round() as u32).clamp(1, max_size);
+    let target_height = ((height as f64 * scale).round() as u32).clamp(1, max_size);
+    // 目标像素 i 覆盖源像素 [i * source / target, (i + 1) * source / target)，至少一个
+    let span = |i: u32, source: u32, target: u32| {
+        let start = (i as u64 * source as u64 / target as u64) as u32;
+        let end = ((i as u64 + 1) * source as u64 / target as u64) as u32;
+        start..end.max(start + 1)
+    };
+
+    image::RgbaImage::from_fn(target_width, target_height, |x, y| {
+        let mut sum = [0u32; 4];
+        let mut count = 0;
+        for sy in span(y, height, target_height) {
+            for sx in span(x, width, target_width) {
+                let pixel = image.get_pixel(sx, sy);
+                for (total, &channel) in sum.iter_mut().zip(&pixel.0) {
+                    *total += channel as u32;
+                }
+                count += 1;
+            }
+        }
+        image::Rgba(sum.map(|total| ((total + count / 2) / count) as u8))
+    })
+}
+
+/// 完整的 mip 链：从原始图像开始每级宽高减半（不小于 1），直到 1×1
+fn mip_chain(image: image::RgbaImage) -> Vec<image::RgbaImage> {
+    let mut levels = vec![image];
+    loop {
+        let last = &levels[levels.len() - 1];
+        if last.width() == 1 && last.height() == 1 {
+            return levels;
+        }
+        let width = (last.width() / 2).max(1);
+        let height = (last.height() / 2).max(1);
+        let next = image::imageops::resize(last, width, height, image::imageops::FilterType::Triangle);
+        levels.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mip_chain_halves_down_to_one_texel() {
+        let image = image::RgbaImage::from_pixel(8, 2, image::Rgba([255, 128, 0, 255]));
+        let levels = mip_chain(image);
+
+        let sizes: Vec<(u32, u32)> = levels.iter().map(|level| level.dimensions()).collect();
+        assert_eq!(sizes, vec![(8, 2), (4, 1), (2, 1), (1, 1)]);
+        // 纯色图像缩小后颜色不变
+        assert_eq!(levels[3].get_pixel(0, 0), &image::Rgba([255, 128, 0, 255]));
+    }
+
+    #[test]
+    fn test_downscale_to_max_texture_size() {
+        // 4096 宽的纹理在 2048 的上限下按比例缩小到 2048，隔列黑白的条纹平均为灰色
+        let image = image::RgbaImage::from_fn(4096, 64, |x, _| image::Rgba([if x % 2 == 0 { 0 } else { 255 }; 4]));
+        let scaled = downscale_to_fit(image, 2048);
+        assert_eq!(scaled.dimensions(), (2048, 32));
+        assert_eq!(scaled.get_pixel(100, 10), &image::Rgba([128; 4]));
+
+        // 不超过上限时保持原样
+        let small = image::RgbaImage::from_pixel(512, 256, image::Rgba([10, 20, 30, 255]));
+        assert_eq!(downscale_to_fit(small.clone(), 2048), small);
+    }
+}
@@ -0,0 +1,73 @@
+//! 顶点布局到 wgpu 顶点缓冲布局的转换
+
+use crate::renderer::resources::vertex_layout::{HasVertexLayout, VertexFormat, VertexLayout};
+
+/// 由 `VertexLayout` 生成的 wgpu 顶点属性，`buffer_layout` 借用其中的属性数组
+pub struct WgpuVertexLayout {
+    stride: wgpu::BufferAddress,
+    attributes: Vec<wgpu::VertexAttribute>,
+}
+
+impl WgpuVertexLayout {
+    pub fn new(layout: &VertexLayout) -> Self {
+        Self {
+            stride: layout.stride() as wgpu::BufferAddress,
+            attributes: layout
+                .attributes()
+                .iter()
+                .map(|attribute| wgpu::VertexAttribute {
+                    format: vertex_format(attribute.format),
+                    offset: attribute.offset as wgpu::BufferAddress,
+                    shader_location: attribute.location,
+                })
+                .collect(),
+        }
+    }
+
+    /// 顶点类型 `T` 的布局
+    pub fn of<T: HasVertexLayout>() -> Self {
+        Self::new(&T::vertex_layout())
+    }
+
+    /// 逐顶点步进的顶点缓冲布局
+    pub fn buffer_layout(&self) -> wgpu::VertexBufferLayout<'_> {
+        wgpu::VertexBufferLayout {
+            array_stride: self.stride,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &self.attributes,
+        }
+    }
+}
+
+fn vertex_format(format: VertexFormat) -> wgpu::VertexFormat {
+    match format {
+        VertexFormat::Float32 => wgpu::VertexFormat::Float32,
+        VertexFormat::Float32x2 => wgpu::VertexFormat::Float32x2,
+        VertexFormat::Float32x3 => wgpu::VertexFormat::Float32x3,
+        VertexFormat::Float32x4 => wgpu::VertexFormat::Float32x4,
+        VertexFormat::Uint16x4 => wgpu::VertexFormat::Uint16x4,
+        VertexFormat::Unorm8x4 => wgpu::VertexFormat::Unorm8x4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::resources::vertex::{MaterialVertex, MyVertex, SkinVertex};
+
+    #[test]
+    fn test_scene_layouts_match_shader_locations() {
+        let mesh = WgpuVertexLayout::of::<MyVertex>();
+        assert_eq!(
+            mesh.buffer_layout().attributes,
+            &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Float32x3]
+        );
+        let skin = WgpuVertexLayout::of::<SkinVertex>();
+        assert_eq!(skin.buffer_layout().attributes, &wgpu::vertex_attr_array![3 => Uint16x4, 4 => Float32x4]);
+        let material = WgpuVertexLayout::of::<MaterialVertex>();
+        assert_eq!(
+            material.buffer_layout().attributes,
+            &wgpu::vertex_attr_array![5 => Float32x2, 6 => Float32x2, 7 => Float32]
+        );
+    }
+}
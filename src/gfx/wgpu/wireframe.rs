@@ -0,0 +1,199 @@
+//! 线框叠加
+//!
+//! 实心着色之后以线框填充再绘制一次可见模型，以纯色（可半透明）输出三角形的边，
+//! 同时观察形体和拓扑。边线与主通道表面共面，使用叠加通道的深度偏移把它拉近相机以避免 z-fighting；
+//! 深度测试使用主通道的深度缓冲但不写入，被遮挡的边线不可见。需要设备支持线框填充。
+
+use std::ops::Range;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use crate::gfx::wgpu::vertex_layout::WgpuVertexLayout;
+use crate::renderer::resources::vertex::{MaterialVertex, MyVertex, SkinVertex};
+
+/// 默认边线颜色（半透明黑色）
+pub const DEFAULT_WIREFRAME_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.5];
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// 线框 Uniform（布局与 wireframe.wgsl 中的 WireframeParams 一致）
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+struct WireframeUniform {
+    color: [f32; 4],
+}
+
+/// 线框叠加通道
+pub struct WireframeOverlayPass {
+    pipeline: wgpu::RenderPipeline,
+    /// 三角形带使用的管线
+    strip_pipeline: wgpu::RenderPipeline,
+    /// 蒙皮模型使用的管线
+    skinned_pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+impl WireframeOverlayPass {
+    /// 创建线框叠加通道
+    ///
+    /// # 参数
+    ///
+    /// - `uniform_layout`: 主通道的 UBO 绑定组布局
+    /// - `joint_layout`: 蒙皮模型的关节矩阵绑定组布局
+    /// - `color_format`: 主通道颜色目标格式
+    /// - `multisample`: 主通道的多重采样状态
+    /// - `depth_bias`: 边线与主通道表面比较深度时使用的偏移
+    pub fn new(
+        device: &wgpu::Device,
+        uniform_layout: &wgpu::BindGroupLayout,
+        joint_layout: &wgpu::BindGroupLayout,
+        color_format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+        depth_bias: wgpu::DepthBiasState,
+    ) -> Self {
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Wireframe Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[WireframeUniform { color: DEFAULT_WIREFRAME_COLOR }]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Wireframe Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Wireframe Bind Group"),
+            layout: &layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Wireframe Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/wireframe.wgsl").into()),
+        });
+        let static_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Wireframe Pipeline Layout"),
+            bind_group_layouts: &[uniform_layout, &layout],
+            push_constant_ranges: &[],
+        });
+        // 关节矩阵绑定在 group 2（主通道中为 group 3）
+        let skinned_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Skinned Wireframe Pipeline Layout"),
+            bind_group_layouts: &[uniform_layout, &layout, joint_layout],
+            push_constant_ranges: &[],
+        });
+
+        let mesh_layout = WgpuVertexLayout::of::<MyVertex>();
+        let material_layout = WgpuVertexLayout::of::<MaterialVertex>();
+        let skin_layout = WgpuVertexLayout::of::<SkinVertex>();
+        let create_pipeline = |label: &str,
+                               layout: &wgpu::PipelineLayout,
+                               vertex: wgpu::VertexState,
+                               topology: wgpu::PrimitiveTopology| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(layout),
+                vertex,
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: "fs_main",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: color_format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                // 与主通道相同的背面剔除，只显示朝向相机的边
+                primitive: wgpu::PrimitiveState {
+                    topology,
+                    // 三角形带只出现在 32 位索引缓冲中
+                    strip_index_format: (topology == wgpu::PrimitiveTopology::TriangleStrip)
+                        .then_some(wgpu::IndexFormat::Uint32),
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: Some(wgpu::Face::Back),
+                    polygon_mode: wgpu::PolygonMode::Line,
+                    ..Default::default()
+                },
+                // 与主通道深度比较但不写入，避免遮挡之后绘制的内容
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: DEPTH_FORMAT,
+                    depth_write_enabled: false,
+                    depth_compare: wgpu::CompareFunction::LessEqual,
+                    stencil: wgpu::StencilState::default(),
+                    bias: depth_bias,
+                }),
+                multisample,
+                multiview: None,
+            })
+        };
+        let static_vertex = wgpu::VertexState {
+            module: &shader,
+            entry_point: "vs_main",
+            buffers: &[mesh_layout.buffer_layout()],
+        };
+        // 顶点缓冲槽位与主通道一致（材质顶点占用槽位 1，着色器不读取）
+        let skinned_vertex = wgpu::VertexState {
+            module: &shader,
+            entry_point: "vs_skinned",
+            buffers: &[mesh_layout.buffer_layout(), material_layout.buffer_layout(), skin_layout.buffer_layout()],
+        };
+        let list = wgpu::PrimitiveTopology::TriangleList;
+        let strip = wgpu::PrimitiveTopology::TriangleStrip;
+        let pipeline = create_pipeline("Wireframe Pipeline", &static_layout, static_vertex.clone(), list);
+        let strip_pipeline = create_pipeline("Wireframe Strip Pipeline", &static_layout, static_vertex, strip);
+        let skinned_pipeline = create_pipeline("Skinned Wireframe Pipeline", &skinned_layout, skinned_vertex, list);
+
+        Self {
+            pipeline,
+            strip_pipeline,
+            skinned_pipeline,
+            uniform_buffer,
+            bind_group,
+        }
+    }
+
+    /// 更新边线颜色（a 为不透明度）
+    pub fn update(&self, queue: &wgpu::Queue, color: [f32; 4]) {
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[WireframeUniform { color }]));
+    }
+
+    /// 在主通道中绘制一个模型的边线
+    ///
+    /// 调用前需已设置好主通道的顶点和索引缓冲，必须在主几何体之后调用。
+    /// `joints` 为蒙皮模型的关节矩阵绑定组，`strip` 表示索引为三角形带（蒙皮模型不使用三角形带）。
+    pub fn draw<'a>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        uniform_bind_group: &'a wgpu::BindGroup,
+        joints: Option<&'a wgpu::BindGroup>,
+        ranges: impl IntoIterator<Item = Range<u32>>,
+        strip: bool,
+    ) {
+        match joints {
+            Some(joints) => {
+                pass.set_pipeline(&self.skinned_pipeline);
+                pass.set_bind_group(2, joints, &[]);
+            }
+            None => pass.set_pipeline(if strip { &self.strip_pipeline } else { &self.pipeline }),
+        }
+        pass.set_bind_group(0, uniform_bind_group, &[]);
+        pass.set_bind_group(1, &self.bind_group, &[]);
+        for indices in ranges {
+            pass.draw_indexed(indices, 0, 0..1);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! 窗口初始位置
+//!
+//! 按配置选择窗口所在的显示器和位置，各后端创建窗口时共用。指定的显示器序号超出范围时
+//! 回退到主显示器；指定了显示器但没有指定位置时，窗口在该显示器上居中。
+
+use tracing::{info, warn};
+use winit::dpi::{LogicalSize, PhysicalPosition};
+use winit::event_loop::EventLoop;
+use winit::monitor::MonitorHandle;
+use winit::window::WindowBuilder;
+
+use crate::core::config::WindowConfig;
+
+/// 按配置设置窗口的初始位置
+///
+/// 位置相对所选显示器的左上角，单位为物理像素。两项都没有配置时由系统决定窗口位置。
+pub fn place_window(builder: WindowBuilder, event_loop: &EventLoop<()>, window: &WindowConfig) -> WindowBuilder {
+    if window.position.is_none() && window.monitor.is_none() {
+        return builder;
+    }
+
+    let monitors: Vec<MonitorHandle> = event_loop.available_monitors().collect();
+    let requested = window.monitor.and_then(|index| {
+        let monitor = monitors.get(index).cloned();
+        if monitor.is_none() {
+            warn!("Monitor {} not found ({} available), using the primary monitor", index, monitors.len());
+        }
+        monitor
+    });
+    // Wayland 等平台无法查询主显示器，此时使用第一个显示器
+    let Some(monitor) = requested
+        .or_else(|| event_loop.primary_monitor())
+        .or_else(|| monitors.first().cloned())
+    else {
+        warn!("No monitor available, ignoring the configured window position");
+        return builder;
+    };
+
+    let size = monitor.size();
+    info!(
+        "Window monitor: {} ({}x{})",
+        monitor.name().unwrap_or_else(|| "unknown".to_string()),
+        size.width,
+        size.height
+    );
+
+    let window_size = LogicalSize::new(window.width, window.height).to_physical::<u32>(monitor.scale_factor());
+    let origin = monitor.position();
+    let (x, y) = window_origin(
+        (origin.x, origin.y),
+        (size.width, size.height),
+        (window_size.width, window_size.height),
+        window.position,
+    );
+    builder.with_position(PhysicalPosition::new(x, y))
+}
+
+/// 窗口左上角的屏幕坐标：有偏移时相对显示器左上角，否则在显示器上居中
+fn window_origin(
+    monitor_position: (i32, i32),
+    monitor_size: (u32, u32),
+    window_size: (u32, u32),
+    offset: Option<[i32; 2]>,
+) -> (i32, i32) {
+    let [dx, dy] = offset.unwrap_or_else(|| {
+        let center = |monitor: u32, window: u32| (monitor.saturating_sub(window) / 2) as i32;
+        [center(monitor_size.0, window_size.0), center(monitor_size.1, window_size.1)]
+    });
+    (monitor_position.0 + dx, monitor_position.1 + dy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_origin() {
+        // 副显示器在主显示器右侧
+        let monitor = ((1920, 0), (2560, 1440));
+        assert_eq!(window_origin(monitor.0, monitor.1, (1280, 720), Some([100, 50])), (2020, 50));
+        assert_eq!(window_origin(monitor.0, monitor.1, (1280, 720), None), (2560, 360));
+        // 窗口比显示器大时贴齐左上角
+        assert_eq!(window_origin((0, 0), (800, 600), (1280, 720), None), (0, 0));
+    }
+}
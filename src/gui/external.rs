@@ -5,6 +5,7 @@ use shared_memory::{Shmem, ShmemConf};
 
 use crate::core::{Config, SceneConfig};
 use crate::gui::ipc::{GuiStatePacket, SharedGuiState, DEFAULT_SHM_NAME};
+use crate::gui::GuiState;
 
 pub struct ExternalGui {
     pub shmem: Shmem,
@@ -23,17 +24,7 @@ impl ExternalGui {
             std::thread::sleep(std::time::Duration::from_millis(100));
         }
         
-        let packet0 = GuiStatePacket {
-            clear_color: scene.clear_color,
-            light_intensity: scene.light.intensity,
-            light_direction: scene.light.transform.rotation,
-            model_position: scene.model.transform.position,
-            model_rotation: scene.model.transform.rotation,
-            model_scale: scene.model.transform.scale,
-            camera_fov: scene.camera.fov,
-            camera_near: scene.camera.near_clip,
-            camera_far: scene.camera.far_clip,
-        };
+        let packet0 = GuiState::new(config, scene).to_packet();
 
         let size = SharedGuiState::MAGIC_SIZE;
         
@@ -1,21 +1,278 @@
 use std::mem;
 use std::sync::atomic::{AtomicU32, Ordering};
 
+use crate::component::{Camera, FovAxis};
+use crate::core::input::CameraConstraints;
+use crate::core::scene::ModelConfig;
+use crate::geometry::material::Material;
+use crate::renderer::clip_planes::{ClipPlane, ClipPlanes, MAX_CLIP_PLANES};
+use crate::renderer::debug_draw::NormalLines;
+
+/// 参数包中可编辑变换的最大模型数量
+pub const MAX_GUI_MODELS: usize = 16;
+
+/// 参数包中可编辑材质和可见性的最大子网格数量
+pub const MAX_GUI_SUBSETS: usize = 32;
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default)]
 pub struct GuiStatePacket {
     pub clear_color: [f32; 4],
+    /// 非零表示每帧清除颜色缓冲
+    pub clear_color_enabled: u32,
+    /// 非零表示清除颜色随时间循环色相
+    pub clear_color_cycle: u32,
+    /// 非零表示使用渐变背景（纯色背景使用清除颜色）
+    pub background_gradient: u32,
+    pub background_top: [f32; 4],
+    pub background_bottom: [f32; 4],
 
     pub light_intensity: f32,
     pub light_direction: [f32; 3],
 
-    pub model_position: [f32; 3],
-    pub model_rotation: [f32; 3],
-    pub model_scale: [f32; 3],
+    pub ambient_color: [f32; 3],
+    pub ambient_intensity: f32,
+    /// 曝光，光照结果在输出前乘以该值
+    pub exposure: f32,
+    /// 非零表示带材质的模型使用 PBR 着色，否则全部使用简单着色
+    pub pbr_shading: u32,
+    /// 图像光照强度（只影响 PBR 着色）
+    pub ibl_intensity: f32,
+    /// 纹理过滤模式在 `TextureFilter::ALL` 中的序号
+    pub texture_filter: u32,
+    /// 三角形填充模式在 `FillMode::ALL` 中的序号
+    pub fill_mode: u32,
+    /// 非零表示在实心着色之上叠加线框
+    pub wireframe_overlay: u32,
+    /// 叠加线框的颜色，a 为不透明度
+    pub wireframe_color: [f32; 4],
+
+    /// 有效的模型变换数量（不超过 `MAX_GUI_MODELS`）
+    pub model_count: u32,
+    pub model_positions: [[f32; 3]; MAX_GUI_MODELS],
+    pub model_rotations: [[f32; 3]; MAX_GUI_MODELS],
+    pub model_scales: [[f32; 3]; MAX_GUI_MODELS],
+    /// 模型的颜色色调（RGBA）
+    pub model_tints: [[f32; 4]; MAX_GUI_MODELS],
+    /// 非零表示模型绕 Y 轴自动旋转
+    pub auto_rotate: u32,
+    /// 自动旋转速度（度/秒）
+    pub auto_rotate_speed: f32,
 
+    /// 相机 FOV（度数），对应 `camera_fov_horizontal` 指定的轴
     pub camera_fov: f32,
+    /// 非零表示 `camera_fov` 为水平 FOV
+    pub camera_fov_horizontal: u32,
     pub camera_near: f32,
     pub camera_far: f32,
+
+    /// 非零表示暂停更新
+    pub paused: u32,
+    /// 单步计数，每请求一次单步加一
+    pub step_id: u32,
+    /// 非零表示暂停时仍允许相机输入
+    pub camera_input_while_paused: u32,
+    /// 非零表示从观察者相机显示主相机的视锥体
+    pub frustum_debug: u32,
+    /// 非零表示绘制顶点法线
+    pub normal_lines: u32,
+    pub normal_line_length: f32,
+    /// 每隔多少个顶点绘制一条法线
+    pub normal_line_stride: u32,
+    /// 非零表示同时绘制切线
+    pub normal_line_tangents: u32,
+    /// 非零表示显示坐标轴指示器
+    pub axis_indicator: u32,
+    /// 坐标轴指示器边长（像素）
+    pub axis_indicator_size: u32,
+
+    /// 剖切平面：非零表示启用、保留一侧的方向和沿法线的偏移
+    pub clip_plane_enabled: [u32; MAX_CLIP_PLANES],
+    pub clip_plane_normals: [[f32; 3]; MAX_CLIP_PLANES],
+    pub clip_plane_offsets: [f32; MAX_CLIP_PLANES],
+
+    /// 非零表示启用相机移动平滑
+    pub camera_smoothing: u32,
+    pub camera_smoothing_strength: f32,
+    /// 相机基础移动速度（单位/秒）和按住 Shift 时的倍数
+    pub camera_move_speed: f32,
+    pub camera_boost_multiplier: f32,
+    /// 非零表示移动时保持相机高度
+    pub camera_lock_y: u32,
+    /// 非零表示保持相机水平（无翻滚）
+    pub camera_lock_roll: u32,
+    /// 允许的俯仰范围（度数）
+    pub camera_pitch_min: f32,
+    pub camera_pitch_max: f32,
+
+    /// 非零表示启用 SSAO
+    pub ssao_enabled: u32,
+    pub ssao_radius: f32,
+    pub ssao_intensity: f32,
+
+    /// 非零表示启用投影矩阵的亚像素抖动
+    pub projection_jitter: u32,
+
+    /// 非零表示低延迟模式（同时只有一帧在 GPU 上执行）
+    pub low_latency: u32,
+
+    /// 非零表示有选中的子网格
+    pub selection_enabled: u32,
+    pub selected_subset: u32,
+    pub outline_color: [f32; 4],
+
+    /// 有效的子网格条目数量（不超过 `MAX_GUI_SUBSETS`），按全局子网格序号排列
+    pub subset_entry_count: u32,
+    pub subset_base_colors: [[f32; 3]; MAX_GUI_SUBSETS],
+    pub subset_metallic: [f32; MAX_GUI_SUBSETS],
+    pub subset_roughness: [f32; MAX_GUI_SUBSETS],
+    /// 非零表示绘制该子网格
+    pub subset_visible: [u32; MAX_GUI_SUBSETS],
+
+    /// LOD 目标三角形比例
+    pub lod_ratio: f32,
+    /// LOD 生成计数，每请求一次生成加一
+    pub lod_id: u32,
+
+    /// 网格细分级数
+    pub subdivision_levels: u32,
+    /// 网格细分计数，每请求一次细分加一
+    pub subdivision_id: u32,
+
+    /// 转台录制的帧数
+    pub turntable_frames: u32,
+    /// 转台录制计数，每请求一次录制加一
+    pub turntable_id: u32,
+
+    /// 相机抖动计数，每请求一次抖动加一
+    pub camera_shake_id: u32,
+}
+
+impl GuiStatePacket {
+    /// 参数包中的相机移动和旋转限制
+    pub fn camera_constraints(&self) -> CameraConstraints {
+        CameraConstraints {
+            lock_y: self.camera_lock_y != 0,
+            lock_pitch_range: (self.camera_pitch_min, self.camera_pitch_max),
+            lock_roll: self.camera_lock_roll != 0,
+        }
+    }
+
+    /// 参数包中的法线可视化设置，未开启时为 `None`
+    pub fn normal_lines(&self) -> Option<NormalLines> {
+        (self.normal_lines != 0).then_some(NormalLines {
+            length: self.normal_line_length,
+            stride: self.normal_line_stride,
+            tangents: self.normal_line_tangents != 0,
+        })
+    }
+
+    /// 坐标轴指示器的边长（像素），未开启时为 `None`
+    pub fn axis_indicator(&self) -> Option<u32> {
+        (self.axis_indicator != 0).then_some(self.axis_indicator_size)
+    }
+
+    /// 参数包中的剖切平面
+    pub fn clip_planes(&self) -> ClipPlanes {
+        ClipPlanes {
+            planes: std::array::from_fn(|i| ClipPlane {
+                enabled: self.clip_plane_enabled[i] != 0,
+                normal: self.clip_plane_normals[i],
+                offset: self.clip_plane_offsets[i],
+            }),
+        }
+    }
+
+    /// 把参数包中的模型变换写回场景模型
+    ///
+    /// 超出 `model_count` 的模型保持原有变换。
+    pub fn apply_model_transforms(&self, models: &mut [ModelConfig]) {
+        let count = (self.model_count as usize).min(MAX_GUI_MODELS);
+        for (i, model) in models.iter_mut().take(count).enumerate() {
+            model.transform.position = self.model_positions[i];
+            model.transform.rotation = self.model_rotations[i];
+            model.transform.scale = self.model_scales[i];
+        }
+    }
+
+    /// 把参数包中的模型色调写回场景模型
+    ///
+    /// 超出 `model_count` 的模型保持原有色调。
+    pub fn apply_model_tints(&self, models: &mut [ModelConfig]) {
+        let count = (self.model_count as usize).min(MAX_GUI_MODELS);
+        for (model, &tint) in models.iter_mut().take(count).zip(&self.model_tints) {
+            model.tint = tint;
+        }
+    }
+
+    /// 把参数包中的子网格材质写入 `materials`，返回有变化的子网格序号
+    ///
+    /// 超出 `subset_entry_count` 的子网格和没有材质（`None`）的子网格保持不变。
+    pub fn apply_subset_materials(&self, materials: &mut [Option<Material>]) -> Vec<usize> {
+        let count = (self.subset_entry_count as usize).min(MAX_GUI_SUBSETS);
+        let mut changed = Vec::new();
+        for (i, material) in materials.iter_mut().take(count).enumerate() {
+            let Some(material) = material else {
+                continue;
+            };
+            let [r, g, b] = self.subset_base_colors[i];
+            let base_color = [r, g, b, material.base_color[3]];
+            if material.base_color != base_color
+                || material.metallic != self.subset_metallic[i]
+                || material.roughness != self.subset_roughness[i]
+            {
+                material.base_color = base_color;
+                material.metallic = self.subset_metallic[i];
+                material.roughness = self.subset_roughness[i];
+                changed.push(i);
+            }
+        }
+        changed
+    }
+
+    /// 把参数包中的子网格可见性写入 `hidden`（`true` 表示隐藏）
+    ///
+    /// 超出 `subset_entry_count` 的子网格保持不变。
+    pub fn apply_subset_visibility(&self, hidden: &mut [bool]) {
+        let count = (self.subset_entry_count as usize).min(MAX_GUI_SUBSETS);
+        for (i, hidden) in hidden.iter_mut().take(count).enumerate() {
+            *hidden = self.subset_visible[i] == 0;
+        }
+    }
+
+    /// FOV 所对应的轴
+    pub fn camera_fov_axis(&self) -> FovAxis {
+        if self.camera_fov_horizontal != 0 {
+            FovAxis::Horizontal
+        } else {
+            FovAxis::Vertical
+        }
+    }
+
+    /// 同时在 GPU 上执行的最大帧数：低延迟模式为 1，否则为 `pipelined`
+    pub fn frames_in_flight(&self, pipelined: u32) -> u32 {
+        if self.low_latency != 0 {
+            1
+        } else {
+            pipelined
+        }
+    }
+
+    /// 把参数包中的 FOV 和裁剪面应用到相机
+    ///
+    /// 参数没有变化时不重建投影矩阵，宽高比保持相机当前值。
+    pub fn apply_camera_lens(&self, camera: &mut Camera) {
+        let fov = self.camera_fov.to_radians();
+        let fov_axis = self.camera_fov_axis();
+
+        let changed = (camera.fov() - fov).abs() > 1e-4
+            || camera.fov_axis() != fov_axis
+            || camera.near_z() != self.camera_near
+            || camera.far_z() != self.camera_far;
+        if changed {
+            camera.set_perspective(fov, fov_axis, camera.aspect(), self.camera_near, self.camera_far);
+        }
+    }
 }
 
 #[repr(C)]
@@ -2,6 +2,8 @@
 //!
 //! GuiManager 是 GUI 系统的核心，负责集成 egui 和 wgpu，
 //! 处理输入事件，更新 UI 状态，并渲染 GUI。
+//! 非 wgpu 后端使用 [`GuiManager::without_renderer`] 创建，通过 [`GuiManager::end_frame`]
+//! 取得三角形化的绘制数据后由各自的绘制器渲染。
 
 use egui;
 use egui_wgpu::Renderer as EguiRenderer;
@@ -13,12 +15,23 @@ use crate::gui::metrics::PerformanceMetrics;
 use crate::gui::panels;
 use crate::core::error::Result;
 
+/// 一帧 GUI 的绘制数据
+pub struct GuiFrame {
+    /// 按裁剪矩形分组的三角形网格（坐标单位为逻辑点）
+    pub paint_jobs: Vec<egui::ClippedPrimitive>,
+    /// 本帧需要创建、更新或释放的纹理
+    pub textures_delta: egui::TexturesDelta,
+    /// 每个逻辑点对应的像素数
+    pub pixels_per_point: f32,
+}
+
 /// GUI 管理器（使用 egui + wgpu）
 pub struct GuiManager {
     // egui 核心组件
     context: egui::Context,
     state: EguiState,
-    renderer: EguiRenderer,
+    /// egui-wgpu 渲染器，非 wgpu 后端为 `None`
+    renderer: Option<EguiRenderer>,
 
     // GUI 状态和统计
     gui_state: GuiState,
@@ -33,6 +46,16 @@ impl GuiManager {
         window: &Window,
         gui_state: GuiState,
     ) -> Result<Self> {
+        let mut manager = Self::without_renderer(window, gui_state);
+
+        // 创建 egui-wgpu renderer
+        manager.renderer = Some(EguiRenderer::new(device, surface_format, None, 1));
+
+        Ok(manager)
+    }
+
+    /// 创建不带 wgpu 渲染器的 GUI 管理器，由调用方用 [`end_frame`](Self::end_frame) 的输出自行绘制
+    pub fn without_renderer(window: &Window, gui_state: GuiState) -> Self {
         // 创建 egui context
         let context = egui::Context::default();
 
@@ -40,18 +63,15 @@ impl GuiManager {
         let viewport_id = context.viewport_id();
         let state = EguiState::new(context.clone(), viewport_id, window, None, None);
 
-        // 创建 egui-wgpu renderer
-        let renderer = EguiRenderer::new(device, surface_format, None, 1);
-
         let metrics = PerformanceMetrics::new();
 
-        Ok(Self {
+        Self {
             context,
             state,
-            renderer,
+            renderer: None,
             gui_state,
             metrics,
-        })
+        }
     }
 
     /// 处理输入事件
@@ -70,6 +90,11 @@ impl GuiManager {
             self.metrics.frame_time_ms()
         );
 
+        // GUI 缩放：egui 的每点像素数为系统缩放乘以缩放倍数，需在读取输入前设置
+        let system_scale = window.scale_factor() as f32;
+        let gui_scale = self.gui_state.effective_gui_scale(system_scale);
+        self.context.set_zoom_factor(gui_scale / system_scale);
+
         // 开始新帧
         let raw_input = self.state.take_egui_input(window);
         self.context.begin_frame(raw_input);
@@ -93,46 +118,86 @@ impl GuiManager {
                 panels::scene::render(ui, &mut self.gui_state);
                 ui.separator();
 
+                // 子网格材质面板
+                panels::materials::render(ui, &mut self.gui_state);
+                ui.separator();
+
+                // UV 布局面板
+                panels::uv::render(ui, &mut self.gui_state);
+                ui.separator();
+
                 // 后端切换面板
                 panels::backend::render(ui, &mut self.gui_state);
+                ui.separator();
+
+                // 调试控制面板
+                panels::debug::render(ui, &mut self.gui_state);
+                ui.separator();
+
+                // 后处理面板
+                panels::post::render(ui, &mut self.gui_state);
+                ui.separator();
+
+                // 截取面板
+                panels::capture::render(ui, &mut self.gui_state);
+                ui.separator();
+
+                // 控制台面板
+                panels::console::render(ui, &mut self.gui_state);
             });
     }
 
+    /// 结束帧，返回三角形化的绘制数据和纹理更新
+    ///
+    /// 同时处理平台输出（光标、复制粘贴等），每帧在 [`update`](Self::update) 之后调用一次。
+    pub fn end_frame(&mut self, window: &Window) -> GuiFrame {
+        let full_output = self.context.end_frame();
+        self.state.handle_platform_output(window, full_output.platform_output);
+
+        let pixels_per_point = full_output.pixels_per_point;
+        GuiFrame {
+            paint_jobs: self.context.tessellate(full_output.shapes, pixels_per_point),
+            textures_delta: full_output.textures_delta,
+            pixels_per_point,
+        }
+    }
+
     /// 渲染 GUI（绘制到 wgpu）
+    ///
+    /// `target` 必须是单采样的颜色目标：场景开启 MSAA 时传入主通道解析后的图像，
+    /// 而不是多重采样缓冲（GUI 渲染器按单采样创建，文字也不需要多重采样）。
     pub fn render(
         &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         encoder: &mut wgpu::CommandEncoder,
-        view: &wgpu::TextureView,
+        target: &wgpu::TextureView,
         window: &Window,
     ) -> Result<()> {
         // 结束帧，获取输出
-        let full_output = self.context.end_frame();
-
-        // 处理平台输出（光标、复制粘贴等）
-        self.state.handle_platform_output(window, full_output.platform_output);
+        let frame = self.end_frame(window);
+        let Some(renderer) = self.renderer.as_mut() else {
+            return Ok(());
+        };
 
         // 更新纹理和缓冲
-        let pixels_per_point = window.scale_factor() as f32;
-        let paint_jobs = self.context.tessellate(full_output.shapes, pixels_per_point);
         let screen_descriptor = egui_wgpu::ScreenDescriptor {
             size_in_pixels: [window.inner_size().width, window.inner_size().height],
-            pixels_per_point: window.scale_factor() as f32,
+            pixels_per_point: frame.pixels_per_point,
         };
 
-        for (id, image_delta) in &full_output.textures_delta.set {
-            self.renderer.update_texture(device, queue, *id, image_delta);
+        for (id, image_delta) in &frame.textures_delta.set {
+            renderer.update_texture(device, queue, *id, image_delta);
         }
 
-        self.renderer.update_buffers(device, queue, encoder, &paint_jobs, &screen_descriptor);
+        renderer.update_buffers(device, queue, encoder, &frame.paint_jobs, &screen_descriptor);
 
         // 渲染
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("GUI Render Pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view,
+                    view: target,
                     resolve_target: None,
                     ops: wgpu::Operations {
                         load: wgpu::LoadOp::Load,  // 保留场景渲染结果
@@ -144,12 +209,12 @@ impl GuiManager {
                 timestamp_writes: None,
             });
 
-            self.renderer.render(&mut render_pass, &paint_jobs, &screen_descriptor);
+            renderer.render(&mut render_pass, &frame.paint_jobs, &screen_descriptor);
         }
 
         // 清理释放的纹理
-        for id in &full_output.textures_delta.free {
-            self.renderer.free_texture(id);
+        for id in &frame.textures_delta.free {
+            renderer.free_texture(id);
         }
 
         Ok(())
@@ -6,10 +6,11 @@ mod manager;
 mod state;
 mod metrics;
 pub mod panels;
+pub mod paint;
 
 pub mod ipc;
 mod external;
 
 pub use external::ExternalGui;
-pub use manager::GuiManager;
-pub use state::GuiState;
+pub use manager::{GuiFrame, GuiManager};
+pub use state::{GuiState, SubsetState};
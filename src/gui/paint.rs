@@ -0,0 +1,146 @@
+//! GUI 绘制数据的后端无关处理
+//!
+//! 没有 egui 官方渲染器的后端（Vulkan、DX12、Metal）共用这里的逻辑：把一帧的网格合并到一组顶点和索引中、
+//! 把逻辑点单位的裁剪矩形转换为像素，以及把纹理更新转换为 RGBA8 像素。各后端的绘制器只负责上传和绘制。
+
+use crate::gfx::letterbox::ViewportRect;
+use crate::gui::GuiFrame;
+
+/// 合并后的一个网格
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuiMesh {
+    pub texture_id: egui::TextureId,
+    /// 裁剪区域（帧缓冲像素）
+    pub clip: ViewportRect,
+    /// 在合并索引中的起始位置
+    pub first_index: u32,
+    pub index_count: u32,
+    /// 加到索引上的顶点偏移
+    pub vertex_offset: u32,
+}
+
+/// 一帧 GUI 合并后的绘制数据
+pub struct GuiBatch {
+    /// egui 顶点：位置和纹理坐标各两个 f32，颜色为预乘 alpha 的 sRGB RGBA8，共 20 字节
+    pub vertices: Vec<egui::epaint::Vertex>,
+    pub indices: Vec<u32>,
+    pub meshes: Vec<GuiMesh>,
+}
+
+impl GuiBatch {
+    /// 合并一帧的网格，跳过空网格和完全被裁剪的网格
+    ///
+    /// `extent` 为帧缓冲的像素尺寸。
+    pub fn new(frame: &GuiFrame, extent: [u32; 2]) -> Self {
+        let mut batch = Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            meshes: Vec::new(),
+        };
+        for job in &frame.paint_jobs {
+            let egui::epaint::Primitive::Mesh(mesh) = &job.primitive else {
+                continue;
+            };
+            if mesh.indices.is_empty() {
+                continue;
+            }
+            let Some(clip) = clip_rect_pixels(job.clip_rect, frame.pixels_per_point, extent) else {
+                continue;
+            };
+
+            batch.meshes.push(GuiMesh {
+                texture_id: mesh.texture_id,
+                clip,
+                first_index: batch.indices.len() as u32,
+                index_count: mesh.indices.len() as u32,
+                vertex_offset: batch.vertices.len() as u32,
+            });
+            batch.indices.extend_from_slice(&mesh.indices);
+            batch.vertices.extend_from_slice(&mesh.vertices);
+        }
+        batch
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.meshes.is_empty()
+    }
+}
+
+/// 把逻辑点单位的裁剪矩形转换为帧缓冲内的像素区域，区域为空时返回 `None`
+pub fn clip_rect_pixels(
+    clip_rect: egui::Rect,
+    pixels_per_point: f32,
+    [width, height]: [u32; 2],
+) -> Option<ViewportRect> {
+    let to_pixels = |value: f32, max: u32| ((value * pixels_per_point).round().max(0.0) as u32).min(max);
+    let min_x = to_pixels(clip_rect.min.x, width);
+    let min_y = to_pixels(clip_rect.min.y, height);
+    let max_x = to_pixels(clip_rect.max.x, width);
+    let max_y = to_pixels(clip_rect.max.y, height);
+    if max_x <= min_x || max_y <= min_y {
+        return None;
+    }
+    Some(ViewportRect {
+        x: min_x,
+        y: min_y,
+        width: max_x - min_x,
+        height: max_y - min_y,
+    })
+}
+
+/// 纹理更新的像素数据：按行优先排列的 sRGB RGBA8，预乘 alpha
+pub fn image_pixels(image: &egui::ImageData) -> Vec<u8> {
+    match image {
+        egui::ImageData::Color(image) => image.pixels.iter().flat_map(|color| color.to_array()).collect(),
+        egui::ImageData::Font(image) => image.srgba_pixels(None).flat_map(|color| color.to_array()).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mesh_job(clip_rect: egui::Rect, vertex_count: usize, indices: Vec<u32>) -> egui::ClippedPrimitive {
+        let mesh = egui::epaint::Mesh {
+            vertices: vec![egui::epaint::Vertex::default(); vertex_count],
+            indices,
+            ..Default::default()
+        };
+        egui::ClippedPrimitive {
+            clip_rect,
+            primitive: egui::epaint::Primitive::Mesh(mesh),
+        }
+    }
+
+    #[test]
+    fn test_clip_rect_scales_and_clamps_to_framebuffer() {
+        let rect = egui::Rect::from_min_max(egui::pos2(-10.0, 5.0), egui::pos2(100.0, 400.0));
+        let clip = clip_rect_pixels(rect, 2.0, [150, 600]).unwrap();
+        assert_eq!(clip, ViewportRect { x: 0, y: 10, width: 150, height: 590 });
+
+        let outside = egui::Rect::from_min_max(egui::pos2(200.0, 0.0), egui::pos2(300.0, 10.0));
+        assert!(clip_rect_pixels(outside, 1.0, [150, 600]).is_none());
+    }
+
+    #[test]
+    fn test_batch_offsets_meshes_and_skips_clipped_ones() {
+        let visible = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(50.0, 50.0));
+        let hidden = egui::Rect::from_min_max(egui::pos2(500.0, 0.0), egui::pos2(600.0, 50.0));
+        let frame = GuiFrame {
+            paint_jobs: vec![
+                mesh_job(visible, 3, vec![0, 1, 2]),
+                mesh_job(hidden, 3, vec![0, 1, 2]),
+                mesh_job(visible, 4, vec![0, 1, 2, 0, 2, 3]),
+            ],
+            textures_delta: Default::default(),
+            pixels_per_point: 1.0,
+        };
+
+        let batch = GuiBatch::new(&frame, [100, 100]);
+        assert_eq!(batch.meshes.len(), 2);
+        assert_eq!(batch.vertices.len(), 7);
+        assert_eq!(batch.indices.len(), 9);
+        assert_eq!((batch.meshes[1].first_index, batch.meshes[1].vertex_offset), (3, 3));
+        assert_eq!(batch.meshes[1].index_count, 6);
+    }
+}
@@ -1,9 +1,10 @@
 //! 后端切换面板
 //!
-//! 提供图形后端切换功能（需要重启应用）。
+//! 提供图形后端切换功能（需要重启应用），并显示当前设备能力、呈现统计和 GPU 资源占用。
 
 use egui;
 use crate::gui::state::GuiState;
+use crate::renderer::resources::resource_stats::{ResourceKind, ResourceUsage};
 
 /// 渲染后端切换面板
 pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
@@ -25,5 +26,52 @@ pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
                 state.backend_changed = true;
             }
         }
+
+        ui.checkbox(&mut state.low_latency, "Low Latency (1 frame in flight)")
+            .on_hover_text("Wait for the previous frame before starting the next: lower input latency, lower frame rate");
+
+        ui.collapsing("Presentation", |ui| match &state.present_stats {
+            Some(present) => {
+                ui.label(format!("Present Mode: {}", present.present_mode));
+                ui.label(format!(
+                    "Frames: {} presented / {} dropped",
+                    present.presented_frames, present.dropped_frames
+                ));
+            }
+            None => {
+                ui.label("Present statistics unsupported by this backend");
+            }
+        });
+
+        if let Some(capabilities) = &state.device_capabilities {
+            ui.collapsing("Device Capabilities", |ui| {
+                egui::Grid::new("device_capabilities").striped(true).show(ui, |ui| {
+                    for (name, value) in capabilities.entries() {
+                        ui.label(name);
+                        ui.label(value);
+                        ui.end_row();
+                    }
+                });
+            });
+        }
+
+        if let Some(stats) = &state.resource_stats {
+            ui.collapsing("Resource Usage", |ui| {
+                egui::Grid::new("resource_usage").striped(true).show(ui, |ui| {
+                    for kind in ResourceKind::ALL {
+                        usage_row(ui, kind.label(), stats.usage(kind));
+                    }
+                    usage_row(ui, "Total", stats.total());
+                });
+            });
+        }
     });
 }
+
+/// 资源占用表格中的一行：名称、数量和大小
+fn usage_row(ui: &mut egui::Ui, name: &str, usage: ResourceUsage) {
+    ui.label(name);
+    ui.label(usage.count.to_string());
+    ui.label(format!("{:.2} MB", usage.bytes as f64 / (1024.0 * 1024.0)));
+    ui.end_row();
+}
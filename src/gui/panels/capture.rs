@@ -0,0 +1,32 @@
+//! 截取面板
+//!
+//! 提供转台录制：相机绕选中模型（未选中时为整个场景）旋转一周并保存 PNG 序列（仅 wgpu 后端）。
+
+use egui;
+use crate::gui::state::GuiState;
+
+/// 渲染截取面板
+pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
+    ui.collapsing("Capture", |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Frames:");
+            ui.add(egui::DragValue::new(&mut state.turntable_frames).clamp_range(1..=720));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Output:");
+            ui.text_edit_singleline(&mut state.turntable_output_dir);
+        });
+
+        match state.turntable_progress {
+            Some((frame, frame_count)) => {
+                ui.label(format!("Recording frame {} / {}", frame, frame_count));
+            }
+            None => {
+                if ui.button("Record Turntable").clicked() {
+                    state.request_turntable();
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,59 @@
+//! 控制台面板
+//!
+//! 显示日志缓冲中的最近日志，按级别过滤并着色，可清空缓冲。
+
+use egui;
+use tracing::Level;
+use crate::core::config::LogLevel;
+use crate::core::log::log_buffer;
+use crate::gui::state::GuiState;
+
+/// 可选的最低显示级别
+const LEVELS: [LogLevel; 5] = [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug, LogLevel::Trace];
+
+/// 渲染控制台面板
+pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
+    ui.collapsing("Console", |ui| {
+        let buffer = log_buffer();
+
+        ui.horizontal(|ui| {
+            ui.label("Level:");
+            egui::ComboBox::from_id_source("console_level")
+                .selected_text(format!("{:?}", state.console_level))
+                .show_ui(ui, |ui| {
+                    for level in LEVELS {
+                        ui.selectable_value(&mut state.console_level, level, format!("{:?}", level));
+                    }
+                });
+            if ui.button("Clear").clicked() {
+                buffer.clear();
+            }
+        });
+
+        let records = buffer.records(state.console_level.into());
+        ui.label(format!("{} messages", records.len()));
+
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for record in &records {
+                    let text = egui::RichText::new(format!("{:5} {}", record.level, record.message))
+                        .monospace()
+                        .color(level_color(record.level));
+                    ui.label(text).on_hover_text(&record.target);
+                }
+            });
+    });
+}
+
+/// 日志级别对应的文字颜色
+fn level_color(level: Level) -> egui::Color32 {
+    match level {
+        Level::ERROR => egui::Color32::from_rgb(255, 96, 96),
+        Level::WARN => egui::Color32::from_rgb(255, 200, 64),
+        Level::INFO => egui::Color32::from_rgb(220, 220, 220),
+        Level::DEBUG => egui::Color32::from_rgb(140, 170, 220),
+        _ => egui::Color32::GRAY,
+    }
+}
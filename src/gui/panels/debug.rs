@@ -0,0 +1,54 @@
+//! 调试控制面板
+//!
+//! 提供暂停渲染循环、单步执行一帧、视锥体可视化、顶点法线可视化、坐标轴指示器（仅 wgpu 后端）、剖切平面等调试功能。
+
+use egui;
+use crate::gui::state::GuiState;
+
+/// 渲染调试控制面板
+pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
+    ui.collapsing("Debug", |ui| {
+        ui.checkbox(&mut state.paused, "Pause Updates");
+
+        ui.add_enabled_ui(state.paused, |ui| {
+            if ui.button("Step One Frame").clicked() {
+                state.request_step();
+            }
+        });
+
+        ui.checkbox(&mut state.camera_input_while_paused, "Camera Input While Paused");
+
+        ui.checkbox(&mut state.frustum_debug, "Visualize Camera Frustum");
+
+        ui.checkbox(&mut state.normal_lines, "Visualize Vertex Normals");
+        ui.add_enabled_ui(state.normal_lines, |ui| {
+            let settings = &mut state.normal_lines_settings;
+            ui.label("Line Length:");
+            ui.add(egui::Slider::new(&mut settings.length, 0.001..=1.0).logarithmic(true));
+            ui.label("Every Nth Vertex:");
+            ui.add(egui::Slider::new(&mut settings.stride, 1..=64).logarithmic(true));
+            ui.checkbox(&mut settings.tangents, "Show Tangents");
+        });
+
+        ui.checkbox(&mut state.axis_indicator, "Show Axis Indicator");
+        ui.add_enabled_ui(state.axis_indicator, |ui| {
+            ui.label("Indicator Size:");
+            ui.add(egui::Slider::new(&mut state.axis_indicator_size, 48..=256).suffix(" px"));
+        });
+
+        // 剖切平面：保留法线一侧，丢弃另一侧的片段
+        for (i, plane) in state.clip_planes.planes.iter_mut().enumerate() {
+            ui.checkbox(&mut plane.enabled, format!("Clip Plane {}", i + 1));
+            ui.add_enabled_ui(plane.enabled, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Normal:");
+                    for component in &mut plane.normal {
+                        ui.add(egui::DragValue::new(component).speed(0.01).clamp_range(-1.0..=1.0));
+                    }
+                });
+                ui.label("Offset:");
+                ui.add(egui::Slider::new(&mut plane.offset, -10.0..=10.0));
+            });
+        }
+    });
+}
@@ -0,0 +1,39 @@
+//! 子网格材质面板
+//!
+//! 列出已加载的子网格，可切换每个子网格的可见性；带 PBR 材质的子网格可编辑基础颜色、
+//! 金属度和粗糙度，修改实时写回顶点数据。最多列出 MAX_GUI_SUBSETS 个子网格。
+
+use egui;
+use crate::gui::ipc::MAX_GUI_SUBSETS;
+use crate::gui::state::GuiState;
+
+/// 渲染子网格材质面板
+pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
+    ui.collapsing("Materials", |ui| {
+        if state.subsets.is_empty() {
+            ui.label("No subsets loaded");
+            return;
+        }
+
+        for subset in state.subsets.iter_mut().take(MAX_GUI_SUBSETS) {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut subset.visible, "");
+                ui.label(&subset.name);
+            });
+            if subset.editable {
+                ui.indent(&subset.name, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Base Color:");
+                        ui.color_edit_button_rgb(&mut subset.base_color);
+                    });
+                    ui.add(egui::Slider::new(&mut subset.metallic, 0.0..=1.0).text("Metallic"));
+                    ui.add(egui::Slider::new(&mut subset.roughness, 0.0..=1.0).text("Roughness"));
+                });
+            }
+        }
+
+        if state.subsets.len() > MAX_GUI_SUBSETS {
+            ui.label(format!("{} more subsets not shown", state.subsets.len() - MAX_GUI_SUBSETS));
+        }
+    });
+}
@@ -5,4 +5,10 @@
 pub mod performance;
 pub mod rendering;
 pub mod scene;
+pub mod materials;
+pub mod uv;
 pub mod backend;
+pub mod debug;
+pub mod post;
+pub mod capture;
+pub mod console;
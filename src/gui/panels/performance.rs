@@ -1,15 +1,41 @@
 //! 性能监控面板
 //!
-//! 显示 FPS、帧时间等性能指标。
+//! 显示 FPS、帧时间、实例剔除数量、绘制调用和三角形数量等性能指标，
+//! 以及 CPU 帧耗时中等待 GPU 的比例和由此得出的瓶颈判断。
 
 use egui;
 use crate::gui::state::GuiState;
+use crate::renderer::stats::Bottleneck;
 
 /// 渲染性能面板
 pub fn render(ui: &mut egui::Ui, state: &GuiState) {
     ui.collapsing("Performance", |ui| {
         ui.label(format!("FPS: {:.1}", state.fps));
         ui.label(format!("Frame Time: {:.2} ms", state.frame_time_ms));
+        ui.label(format!(
+            "Instances: {} drawn / {} culled",
+            state.instances_drawn, state.instances_culled
+        ));
+        ui.label(format!("Draw Calls: {}", state.frame_stats.draw_calls));
+        ui.label(format!("Triangles: {}", state.frame_stats.triangles));
+        ui.label(format!("CPU Time: {:.2} ms", state.frame_stats.cpu_time_ms));
+
+        // 等待 GPU 与录制命令的占比（平滑后），等待多说明 GPU 是瓶颈
+        let stats = &state.frame_stats;
+        let waiting = state.gpu_wait_fraction * 100.0;
+        ui.label(format!(
+            "GPU Wait: {:.2} ms ({:.0}% waiting / {:.0}% recording)",
+            stats.gpu_wait_ms,
+            waiting,
+            100.0 - waiting
+        ));
+        let bottleneck = Bottleneck::from_wait_fraction(state.gpu_wait_fraction);
+        let color = match bottleneck {
+            Bottleneck::Cpu => egui::Color32::LIGHT_BLUE,
+            Bottleneck::Gpu => egui::Color32::LIGHT_RED,
+            Bottleneck::Balanced => egui::Color32::LIGHT_GREEN,
+        };
+        ui.colored_label(color, bottleneck.to_string());
 
         if state.frame_time_ms > 0.0 {
             let target_60fps = 1000.0 / 60.0;
@@ -0,0 +1,21 @@
+//! 后处理面板
+//!
+//! 提供 SSAO 等后处理效果的开关和参数调节，以及为 TAA 准备的投影抖动开关。
+
+use egui;
+use crate::gui::state::GuiState;
+
+/// 渲染后处理面板
+pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
+    ui.collapsing("Post Processing", |ui| {
+        ui.checkbox(&mut state.ssao_enabled, "SSAO");
+
+        ui.add_enabled_ui(state.ssao_enabled, |ui| {
+            ui.add(egui::Slider::new(&mut state.ssao_radius, 0.05..=2.0).text("Radius"));
+            ui.add(egui::Slider::new(&mut state.ssao_intensity, 0.0..=4.0).text("Intensity"));
+        });
+
+        ui.checkbox(&mut state.projection_jitter, "Projection Jitter")
+            .on_hover_text("Offset the projection by a sub-pixel Halton sample every frame (TAA groundwork)");
+    });
+}
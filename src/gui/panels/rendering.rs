@@ -1,9 +1,12 @@
 //! 渲染设置面板
 //!
-//! 提供清除颜色、光照强度、光照方向、相机 FOV 等渲染参数的调整。
+//! 提供清除颜色（可关闭每帧清除，可循环色相以确认渲染循环在运行）、背景模式（纯色或渐变）、光照强度、光照方向、环境光、曝光、着色模型（PBR 或简单着色）、图像光照强度、纹理过滤模式、三角形填充模式、线框叠加（仅 wgpu 后端）、相机 FOV（可选水平或垂直轴）、相机移动速度、平滑和移动限制、GUI 缩放等渲染参数的调整。
 
 use egui;
-use crate::gui::state::GuiState;
+use crate::component::FovAxis;
+use crate::gui::state::{GuiState, GUI_SCALE_RANGE};
+use crate::renderer::fill_mode::FillMode;
+use crate::renderer::resources::sampler::TextureFilter;
 
 /// 渲染渲染设置面板
 pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
@@ -11,7 +14,24 @@ pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
         ui.label("Clear Color:");
         ui.horizontal(|ui| {
             ui.color_edit_button_rgba_unmultiplied(&mut state.clear_color);
+            ui.checkbox(&mut state.clear_color_enabled, "Clear Every Frame");
         });
+        ui.checkbox(&mut state.clear_color_cycle, "Cycle Clear Color")
+            .on_hover_text("Animate the clear color hue to confirm the render loop is presenting");
+
+        ui.label("Background:");
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut state.background_gradient, false, "Solid");
+            ui.radio_value(&mut state.background_gradient, true, "Gradient");
+        });
+        if state.background_gradient {
+            ui.horizontal(|ui| {
+                ui.label("Top:");
+                ui.color_edit_button_rgba_unmultiplied(&mut state.background_top);
+                ui.label("Bottom:");
+                ui.color_edit_button_rgba_unmultiplied(&mut state.background_bottom);
+            });
+        }
 
         ui.label("Light Intensity:");
         ui.add(egui::Slider::new(&mut state.light_intensity, 0.0..=5.0));
@@ -26,7 +46,135 @@ pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
             ui.add(egui::DragValue::new(&mut state.light_direction[2]).speed(0.1));
         });
 
+        ui.label("Ambient Color:");
+        ui.horizontal(|ui| {
+            ui.color_edit_button_rgb(&mut state.ambient_color);
+        });
+
+        ui.label("Ambient Intensity:");
+        ui.add(egui::Slider::new(&mut state.ambient_intensity, 0.0..=1.0));
+
+        ui.label("Exposure:");
+        ui.add(egui::Slider::new(&mut state.exposure, 0.0..=4.0));
+
+        ui.label("Shading:");
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut state.pbr_shading, true, "PBR");
+            ui.radio_value(&mut state.pbr_shading, false, "Simple");
+        });
+
+        // 图像光照只在加载了环境贴图且使用 PBR 着色时生效
+        ui.add_enabled_ui(state.ibl_available && state.pbr_shading, |ui| {
+            ui.label("IBL Intensity:");
+            ui.add(egui::Slider::new(&mut state.ibl_intensity, 0.0..=4.0));
+        });
+
+        ui.label("Texture Filtering:");
+        egui::ComboBox::from_id_source("texture_filter")
+            .selected_text(state.texture_filter.label())
+            .show_ui(ui, |ui| {
+                for filter in TextureFilter::ALL {
+                    ui.selectable_value(&mut state.texture_filter, filter, filter.label());
+                }
+            });
+
+        ui.label("Fill Mode:");
+        egui::ComboBox::from_id_source("fill_mode")
+            .selected_text(state.fill_mode.label())
+            .show_ui(ui, |ui| {
+                for mode in FillMode::ALL {
+                    // 能力未知（外部 GUI 进程）时不禁用，后端自行退回实心填充
+                    let supported = match &state.device_capabilities {
+                        Some(capabilities) => mode.is_supported(capabilities),
+                        None => true,
+                    };
+                    let label = egui::SelectableLabel::new(state.fill_mode == mode, mode.label());
+                    if ui.add_enabled(supported, label).clicked() {
+                        state.fill_mode = mode;
+                    }
+                }
+            });
+
+        let overlay_supported = state.device_capabilities.as_ref().is_none_or(|c| c.polygon_mode_line);
+        ui.add_enabled_ui(overlay_supported, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut state.wireframe_overlay, "Wireframe Overlay")
+                    .on_hover_text("Draw triangle edges over the shaded mesh");
+                ui.color_edit_button_rgba_unmultiplied(&mut state.wireframe_color);
+            });
+        });
+
+        if let Some(captured) = state.mouse_captured {
+            if captured {
+                ui.colored_label(egui::Color32::LIGHT_GREEN, format!("Mouse captured ({} to release)", state.capture_key));
+            } else {
+                ui.label(format!("Mouse free ({} to capture)", state.capture_key));
+            }
+        }
+
         ui.label("Camera FOV:");
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut state.camera_fov_axis, FovAxis::Vertical, "Vertical");
+            ui.radio_value(&mut state.camera_fov_axis, FovAxis::Horizontal, "Horizontal");
+        });
         ui.add(egui::Slider::new(&mut state.camera_fov, 30.0..=120.0).suffix("°"));
+
+        ui.label("Camera Speed:");
+        ui.add(egui::Slider::new(&mut state.camera_move_speed, 0.1..=100.0).logarithmic(true));
+        ui.label("Boost Multiplier (Shift):");
+        ui.add(egui::Slider::new(&mut state.camera_boost_multiplier, 1.0..=20.0));
+
+        ui.checkbox(&mut state.camera_smoothing, "Camera Smoothing");
+        ui.add_enabled_ui(state.camera_smoothing, |ui| {
+            ui.label("Smoothing Strength:");
+            ui.add(egui::Slider::new(&mut state.camera_smoothing_strength, 0.0..=1.0));
+        });
+
+        ui.label("Camera Constraints:");
+        let constraints = &mut state.camera_constraints;
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut constraints.lock_y, "Lock Height");
+            ui.checkbox(&mut constraints.lock_roll, "Lock Roll");
+        });
+        let (min, max) = &mut constraints.lock_pitch_range;
+        ui.horizontal(|ui| {
+            ui.label("Pitch:");
+            ui.add(egui::DragValue::new(min).clamp_range(-90.0..=*max).suffix("°"));
+            ui.label("to");
+            ui.add(egui::DragValue::new(max).clamp_range(*min..=90.0).suffix("°"));
+        });
+
+        if ui.button("Shake Camera (K)").clicked() {
+            state.request_camera_shake();
+        }
+
+        gui_scale(ui, state);
+    });
+}
+
+/// GUI 缩放滑块
+///
+/// 拖动时界面随缩放变化会使滑块在光标下移动，因此拖动中的值暂存在 egui 内存里，
+/// 松开后才写入 `gui_scale`。
+fn gui_scale(ui: &mut egui::Ui, state: &mut GuiState) {
+    let system_scale = ui.ctx().native_pixels_per_point().unwrap_or(1.0);
+    let current = state.effective_gui_scale(system_scale);
+    let id = ui.make_persistent_id("gui_scale_drag");
+
+    ui.label("GUI Scale:");
+    ui.horizontal(|ui| {
+        let mut scale = ui.data(|data| data.get_temp::<f32>(id)).unwrap_or(current);
+        let response = ui.add(egui::Slider::new(&mut scale, GUI_SCALE_RANGE).step_by(0.05));
+        if response.dragged() {
+            ui.data_mut(|data| data.insert_temp(id, scale));
+        } else {
+            ui.data_mut(|data| data.remove::<f32>(id));
+            if scale != current {
+                state.gui_scale = Some(scale);
+            }
+        }
+        if ui.add_enabled(state.gui_scale.is_some(), egui::Button::new("System")).clicked() {
+            state.gui_scale = None;
+        }
     });
 }
@@ -1,47 +1,120 @@
 //! 场景控制面板
 //!
-//! 提供模型位置、旋转、缩放等场景参数的调整。
+//! 列出场景中的模型，提供每个模型位置、旋转、缩放的调整、展示用的自动旋转，以及子网格选中高亮。
+//! 选中子网格所属的模型会显示平移 gizmo（仅 wgpu 后端）。
+//! 可按三角形比例重新生成简化后的场景网格（仅 wgpu 后端）。
 
 use egui;
+use crate::geometry::subdivide::MAX_SUBDIVISION_LEVELS;
 use crate::gui::state::GuiState;
 
 /// 渲染场景控制面板
 pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
     ui.collapsing("Scene", |ui| {
-        ui.label("Model Position:");
-        ui.horizontal(|ui| {
-            ui.label("X:");
-            ui.add(egui::DragValue::new(&mut state.model_position[0]).speed(0.1));
-            ui.label("Y:");
-            ui.add(egui::DragValue::new(&mut state.model_position[1]).speed(0.1));
-            ui.label("Z:");
-            ui.add(egui::DragValue::new(&mut state.model_position[2]).speed(0.1));
-        });
+        if state.models.is_empty() {
+            ui.label("No models in scene");
+        }
+
+        for (i, model) in state.models.iter_mut().enumerate() {
+            egui::CollapsingHeader::new(format!("{}: {}", i, model.name))
+                .id_source(("scene_model", i))
+                .default_open(i == 0)
+                .show(ui, |ui| {
+                    if model.lod_count > 1 {
+                        ui.label(format!("LOD: {} / {}", model.active_lod, model.lod_count - 1));
+                    }
+
+                    ui.label("Position:");
+                    ui.horizontal(|ui| {
+                        ui.label("X:");
+                        ui.add(egui::DragValue::new(&mut model.position[0]).speed(0.1));
+                        ui.label("Y:");
+                        ui.add(egui::DragValue::new(&mut model.position[1]).speed(0.1));
+                        ui.label("Z:");
+                        ui.add(egui::DragValue::new(&mut model.position[2]).speed(0.1));
+                    });
+
+                    ui.label("Rotation (deg):");
+                    ui.horizontal(|ui| {
+                        ui.label("X:");
+                        ui.add(egui::DragValue::new(&mut model.rotation[0]).speed(1.0));
+                        ui.label("Y:");
+                        ui.add(egui::DragValue::new(&mut model.rotation[1]).speed(1.0));
+                        ui.label("Z:");
+                        ui.add(egui::DragValue::new(&mut model.rotation[2]).speed(1.0));
+                    });
+
+                    ui.label("Scale:");
+                    ui.horizontal(|ui| {
+                        ui.label("X:");
+                        ui.add(egui::DragValue::new(&mut model.scale[0]).speed(0.1));
+                        ui.label("Y:");
+                        ui.add(egui::DragValue::new(&mut model.scale[1]).speed(0.1));
+                        ui.label("Z:");
+                        ui.add(egui::DragValue::new(&mut model.scale[2]).speed(0.1));
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Tint:");
+                        ui.color_edit_button_rgba_unmultiplied(&mut model.tint);
+                    });
+
+                    if ui.button("Reset Transform").clicked() {
+                        model.position = [0.0, 0.0, 0.0];
+                        model.rotation = [0.0, 0.0, 0.0];
+                        model.scale = [1.0, 1.0, 1.0];
+                    }
+                });
+        }
 
-        ui.label("Model Rotation (deg):");
         ui.horizontal(|ui| {
-            ui.label("X:");
-            ui.add(egui::DragValue::new(&mut state.model_rotation[0]).speed(1.0));
-            ui.label("Y:");
-            ui.add(egui::DragValue::new(&mut state.model_rotation[1]).speed(1.0));
-            ui.label("Z:");
-            ui.add(egui::DragValue::new(&mut state.model_rotation[2]).speed(1.0));
+            ui.checkbox(&mut state.auto_rotate, "Auto-Rotate")
+                .on_hover_text("Spin the models around their Y axis on top of the rotation above");
+            ui.add(egui::DragValue::new(&mut state.auto_rotate_speed).speed(1.0).suffix(" deg/s"));
         });
 
-        ui.label("Model Scale:");
+        ui.separator();
+
+        let [x, y, z] = state.light_direction;
+        ui.label(format!("Light Direction: ({:.2}, {:.2}, {:.2})", x, y, z));
+        ui.label("Hold L and use the arrow keys to rotate the light");
+
+        ui.separator();
+
+        let mut highlight = state.selected_subset.is_some();
+        if ui.checkbox(&mut highlight, "Highlight Subset").changed() {
+            state.selected_subset = highlight.then_some(0);
+        }
+
+        if let Some(selected) = state.selected_subset.as_mut() {
+            ui.horizontal(|ui| {
+                ui.label("Subset:");
+                let mut drag = egui::DragValue::new(selected).speed(0.1);
+                if state.subset_count > 0 {
+                    drag = drag.clamp_range(0..=state.subset_count - 1);
+                }
+                ui.add(drag);
+            });
+            ui.label("Drag a gizmo axis to move the model (hold Ctrl to snap)");
+        }
+
+        ui.label("Outline Color:");
         ui.horizontal(|ui| {
-            ui.label("X:");
-            ui.add(egui::DragValue::new(&mut state.model_scale[0]).speed(0.1));
-            ui.label("Y:");
-            ui.add(egui::DragValue::new(&mut state.model_scale[1]).speed(0.1));
-            ui.label("Z:");
-            ui.add(egui::DragValue::new(&mut state.model_scale[2]).speed(0.1));
+            ui.color_edit_button_rgba_unmultiplied(&mut state.outline_color);
         });
 
-        if ui.button("Reset Transform").clicked() {
-            state.model_position = [0.0, 0.0, 0.0];
-            state.model_rotation = [0.0, 0.0, 0.0];
-            state.model_scale = [1.0, 1.0, 1.0];
+        ui.separator();
+
+        ui.label("LOD Triangle Ratio:");
+        ui.add(egui::Slider::new(&mut state.lod_ratio, 0.05..=1.0));
+        if ui.button("Generate LOD").clicked() {
+            state.request_lod();
+        }
+
+        ui.label("Subdivision Levels:");
+        ui.add(egui::Slider::new(&mut state.subdivision_levels, 0..=MAX_SUBDIVISION_LEVELS));
+        if ui.button("Subdivide").clicked() {
+            state.request_subdivision();
         }
     });
 }
@@ -0,0 +1,93 @@
+//! UV 布局面板
+//!
+//! 选择一个模型，把它的三角形按 UV 坐标画成二维线框，与其他三角形重叠的三角形以红色标出，
+//! 并显示超出 [0, 1] 的纹理坐标比例。方框为 [0, 1] 范围，超出的部分一并显示。
+
+use egui;
+use crate::geometry::uv::UvLayout;
+use crate::gui::state::GuiState;
+
+/// 最多绘制的三角形数量，超出的部分只参与统计
+const MAX_DRAWN_TRIANGLES: usize = 20_000;
+
+/// 线框区域的边长（点）
+const VIEW_SIZE: f32 = 260.0;
+
+/// 渲染 UV 布局面板
+pub fn render(ui: &mut egui::Ui, state: &mut GuiState) {
+    ui.collapsing("UV Layout", |ui| {
+        let selected_text = state
+            .uv_model
+            .and_then(|i| state.models.get(i))
+            .map_or("None".to_string(), |model| model.name.clone());
+        ui.horizontal(|ui| {
+            ui.label("Model:");
+            egui::ComboBox::from_id_source("uv_model")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut state.uv_model, None, "None");
+                    for (i, model) in state.models.iter().enumerate() {
+                        ui.selectable_value(&mut state.uv_model, Some(i), &model.name);
+                    }
+                });
+        });
+
+        let Some((_, layout)) = state.uv_layout.as_ref().filter(|(model, _)| Some(*model) == state.uv_model) else {
+            return;
+        };
+
+        ui.label(format!("{} triangles", layout.triangles.len()));
+        let overlaps = layout.overlap_count();
+        let overlap_text = format!("Overlapping: {} triangles", overlaps);
+        if overlaps > 0 {
+            ui.colored_label(egui::Color32::from_rgb(255, 96, 96), overlap_text);
+        } else {
+            ui.label(overlap_text);
+        }
+        let outside_text = format!("Outside [0, 1]: {:.1}% of UVs", layout.outside_fraction * 100.0);
+        if layout.outside_fraction > 0.0 {
+            ui.colored_label(egui::Color32::from_rgb(255, 200, 64), outside_text);
+        } else {
+            ui.label(outside_text);
+        }
+        if layout.triangles.len() > MAX_DRAWN_TRIANGLES {
+            ui.label(format!("Showing the first {} triangles", MAX_DRAWN_TRIANGLES));
+        }
+
+        paint_layout(ui, layout);
+    });
+}
+
+/// 在正方形区域内绘制 UV 线框
+fn paint_layout(ui: &mut egui::Ui, layout: &UvLayout) {
+    let (response, painter) = ui.allocate_painter(egui::vec2(VIEW_SIZE, VIEW_SIZE), egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 0.0, egui::Color32::from_gray(24));
+
+    // 显示范围包含 [0, 1] 和所有 UV，保持纵横比
+    let (min, max) = layout.bounds().unwrap_or(([0.0, 0.0], [1.0, 1.0]));
+    let min = [min[0].min(0.0), min[1].min(0.0)];
+    let max = [max[0].max(1.0), max[1].max(1.0)];
+    let span = (max[0] - min[0]).max(max[1] - min[1]);
+    // 纹理坐标原点在图像左上角，v 向下与屏幕方向一致
+    let to_screen = |uv: [f32; 2]| {
+        rect.min + egui::vec2((uv[0] - min[0]) / span * rect.width(), (uv[1] - min[1]) / span * rect.height())
+    };
+
+    let unit = egui::Rect::from_two_pos(to_screen([0.0, 0.0]), to_screen([1.0, 1.0]));
+    painter.rect_stroke(unit, 0.0, egui::Stroke::new(1.0, egui::Color32::from_gray(120)));
+
+    let normal = egui::Stroke::new(1.0, egui::Color32::from_gray(200));
+    let overlapping = egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 96, 96));
+    let shapes = layout
+        .triangles
+        .iter()
+        .zip(&layout.overlapping)
+        .take(MAX_DRAWN_TRIANGLES)
+        .flat_map(|(triangle, &overlap)| {
+            let stroke = if overlap { overlapping } else { normal };
+            let points = triangle.map(to_screen);
+            (0..3).map(move |i| egui::Shape::line_segment([points[i], points[(i + 1) % 3]], stroke))
+        });
+    painter.extend(shapes);
+}
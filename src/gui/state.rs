@@ -2,8 +2,77 @@
 //!
 //! GuiState 保存所有 GUI 相关的状态数据，与具体的图形后端无关。
 
+use crate::component::{FovAxis, DEFAULT_AUTO_ROTATE_SPEED};
+use crate::core::config::LogLevel;
+use crate::core::input::CameraConstraints;
 use crate::core::Config;
 use crate::core::SceneConfig;
+use crate::core::scene::BackgroundMode;
+use crate::gfx::capabilities::DeviceCapabilities;
+use crate::geometry::material::Material;
+use crate::geometry::uv::UvLayout;
+use crate::gui::ipc::{GuiStatePacket, MAX_GUI_MODELS, MAX_GUI_SUBSETS};
+use crate::renderer::axis_indicator::DEFAULT_AXIS_INDICATOR_SIZE;
+use crate::renderer::clip_planes::ClipPlanes;
+use crate::renderer::debug_draw::NormalLines;
+use crate::renderer::fill_mode::FillMode;
+use crate::renderer::resources::resource_stats::ResourceStats;
+use crate::renderer::resources::sampler::TextureFilter;
+use crate::renderer::stats::{FrameStats, PresentStats};
+
+/// GUI 缩放系数的允许范围
+pub const GUI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+
+/// GPU 等待占比的平滑系数（每帧向新值靠近的比例）
+const WAIT_FRACTION_SMOOTHING: f32 = 0.1;
+
+/// 单个模型的 GUI 状态
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelState {
+    /// 显示名称（模型文件名）
+    pub name: String,
+    pub position: [f32; 3],
+    pub rotation: [f32; 3],
+    pub scale: [f32; 3],
+    /// 颜色色调（乘以最终颜色）
+    pub tint: [f32; 4],
+    /// 当前使用的 LOD 级别和级别总数（由内置 GUI 的后端填充，总数为 0 表示未知）
+    pub active_lod: usize,
+    pub lod_count: usize,
+}
+
+/// 子网格编辑器中的一项
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubsetState {
+    /// 显示名称（子网格序号和材质名称）
+    pub name: String,
+    /// 是否带可编辑的 PBR 材质
+    pub editable: bool,
+    pub base_color: [f32; 3],
+    pub metallic: f32,
+    pub roughness: f32,
+    pub visible: bool,
+}
+
+impl SubsetState {
+    /// 从子网格的材质创建，没有材质时只能切换可见性
+    pub fn new(index: usize, material: Option<&Material>) -> Self {
+        let name = match material.and_then(|m| m.name.as_deref()) {
+            Some(name) => format!("#{} {}", index, name),
+            None => format!("#{}", index),
+        };
+        let default = Material::default();
+        let source = material.unwrap_or(&default);
+        Self {
+            name,
+            editable: material.is_some(),
+            base_color: [source.base_color[0], source.base_color[1], source.base_color[2]],
+            metallic: source.metallic,
+            roughness: source.roughness,
+            visible: true,
+        }
+    }
+}
 
 /// GUI 状态（与后端无关）
 pub struct GuiState {
@@ -11,51 +80,242 @@ pub struct GuiState {
     pub show_fps: bool,
     pub fps: f32,
     pub frame_time_ms: f32,
+    pub instances_drawn: u32,
+    pub instances_culled: u32,
+    /// 上一帧的绘制统计（由内置 GUI 的后端填充）
+    pub frame_stats: FrameStats,
+    /// 等待 GPU 的耗时占比（指数平滑，避免瓶颈判断逐帧跳动）
+    pub gpu_wait_fraction: f32,
+    /// 呈现统计（由提供呈现统计的后端填充）
+    pub present_stats: Option<PresentStats>,
 
     // 渲染设置
     pub clear_color: [f32; 4],
+    pub clear_color_enabled: bool,
+    /// 清除颜色随时间循环色相（调试渲染循环是否在运行）
+    pub clear_color_cycle: bool,
+    pub background_gradient: bool,
+    pub background_top: [f32; 4],
+    pub background_bottom: [f32; 4],
     pub light_intensity: f32,
     pub light_direction: [f32; 3],
+    pub ambient_color: [f32; 3],
+    pub ambient_intensity: f32,
+    pub exposure: f32,
+    pub pbr_shading: bool,
+    pub ibl_intensity: f32,
+    /// 后端是否加载了环境贴图（图像光照可用）
+    pub ibl_available: bool,
+    /// 纹理过滤模式（后端初始化后设为设备支持的最佳模式）
+    pub texture_filter: TextureFilter,
+    /// 三角形填充模式（设备不支持的模式在面板中禁用）
+    pub fill_mode: FillMode,
+    /// 在实心着色之上叠加线框（需要设备支持线框填充，仅 wgpu 后端）
+    pub wireframe_overlay: bool,
+    /// 叠加线框的颜色，a 为不透明度
+    pub wireframe_color: [f32; 4],
+    /// GUI 缩放系数（egui 每点像素数），`None` 表示跟随系统缩放
+    pub gui_scale: Option<f32>,
 
-    // 场景控制
-    pub model_position: [f32; 3],
-    pub model_rotation: [f32; 3],
-    pub model_scale: [f32; 3],
+    // 场景控制（最多 MAX_GUI_MODELS 个模型可编辑）
+    pub models: Vec<ModelState>,
+    /// 模型绕 Y 轴自动旋转（叠加在上面的旋转之上）及其速度（度/秒）
+    pub auto_rotate: bool,
+    pub auto_rotate_speed: f32,
 
     // 相机参数
     pub camera_fov: f32,
+    pub camera_fov_axis: FovAxis,
     pub camera_near: f32,
     pub camera_far: f32,
+    pub camera_smoothing: bool,
+    pub camera_smoothing_strength: f32,
+    pub camera_move_speed: f32,
+    pub camera_boost_multiplier: f32,
+    /// 相机移动和旋转限制
+    pub camera_constraints: CameraConstraints,
+    /// 相机抖动计数，每请求一次抖动加一
+    pub camera_shake_id: u32,
+    /// 鼠标是否被相机捕获（由内置 GUI 的后端每帧同步，只用于显示），`None` 表示未知
+    pub mouse_captured: Option<bool>,
+    /// 切换鼠标捕获的按键名称
+    pub capture_key: String,
+
+    // 调试控制
+    pub paused: bool,
+    pub step_id: u32,
+    pub camera_input_while_paused: bool,
+    /// 从观察者相机显示主相机的视锥体（由内置 GUI 的后端实现）
+    pub frustum_debug: bool,
+    /// 绘制顶点法线（由内置 GUI 的后端实现）
+    pub normal_lines: bool,
+    pub normal_lines_settings: NormalLines,
+    /// 在视口角落显示随相机旋转的坐标轴指示器及其边长（像素，由内置 GUI 的后端实现）
+    pub axis_indicator: bool,
+    pub axis_indicator_size: u32,
+    /// 剖切平面（由内置 GUI 的后端实现）
+    pub clip_planes: ClipPlanes,
+
+    // 后处理
+    pub ssao_enabled: bool,
+    pub ssao_radius: f32,
+    pub ssao_intensity: f32,
+    /// 投影矩阵的亚像素抖动，为之后的 TAA 做准备（由内置 GUI 的后端实现）
+    pub projection_jitter: bool,
+
+    // 选中高亮
+    pub selected_subset: Option<usize>,
+    pub outline_color: [f32; 4],
+    /// 所有模型的子网格总数（由内置 GUI 的后端填充，0 表示未知）
+    pub subset_count: usize,
+    /// 子网格材质和可见性（由内置 GUI 的后端填充，最多 MAX_GUI_SUBSETS 个可编辑）
+    pub subsets: Vec<SubsetState>,
+
+    // LOD 生成
+    pub lod_ratio: f32,
+    pub lod_id: u32,
+
+    // 网格细分
+    pub subdivision_levels: u32,
+    pub subdivision_id: u32,
+
+    // 转台录制
+    pub turntable_frames: u32,
+    pub turntable_output_dir: String,
+    pub turntable_id: u32,
+    /// 录制进度（已录帧数, 总帧数），未在录制时为 `None`（由内置 GUI 的后端填充）
+    pub turntable_progress: Option<(u32, u32)>,
+
+    // 控制台
+    /// 控制台显示的最详细日志级别
+    pub console_level: LogLevel,
+
+    // UV 布局
+    /// 要查看 UV 布局的模型，`None` 表示不查看
+    pub uv_model: Option<usize>,
+    /// 已计算的 UV 布局及其模型序号（由内置 GUI 的后端按 `uv_model` 填充）
+    pub uv_layout: Option<(usize, UvLayout)>,
 
     // 后端信息
     pub current_backend: String,
     pub selected_backend: String,
     pub backend_changed: bool,
+    /// 低延迟模式：同时只有一帧在 GPU 上执行，以吞吐量换取更低的输入延迟
+    pub low_latency: bool,
+    /// 当前设备能力（由内置 GUI 的后端填充）
+    pub device_capabilities: Option<DeviceCapabilities>,
+    /// GPU 缓冲和纹理占用（由内置 GUI 的后端填充，与后端共享同一份统计）
+    pub resource_stats: Option<ResourceStats>,
 }
 
 impl GuiState {
     /// 从配置和场景创建 GUI 状态
     pub fn new(config: &Config, scene: &SceneConfig) -> Self {
+        let (background_top, background_bottom) = scene.background.gradient_colors();
         Self {
             show_fps: true,
             fps: 0.0,
             frame_time_ms: 0.0,
+            instances_drawn: 0,
+            instances_culled: 0,
+            frame_stats: FrameStats::default(),
+            gpu_wait_fraction: 0.0,
+            present_stats: None,
 
             clear_color: scene.clear_color,
+            clear_color_enabled: scene.clear_color_enabled,
+            clear_color_cycle: scene.clear_color_cycle,
+            background_gradient: matches!(scene.background, BackgroundMode::Gradient { .. }),
+            background_top,
+            background_bottom,
             light_intensity: scene.light.intensity,
             light_direction: scene.light.transform.rotation,
+            ambient_color: scene.ambient_color,
+            ambient_intensity: scene.ambient_intensity,
+            exposure: scene.exposure,
+            pbr_shading: true,
+            ibl_intensity: scene.environment.ibl_intensity,
+            ibl_available: false,
+            texture_filter: TextureFilter::default(),
+            fill_mode: FillMode::default(),
+            wireframe_overlay: false,
+            wireframe_color: [0.0, 0.0, 0.0, 0.5],
+            gui_scale: config.window.gui_scale,
 
-            model_position: scene.model.transform.position,
-            model_rotation: scene.model.transform.rotation,
-            model_scale: scene.model.transform.scale,
+            models: scene
+                .models
+                .iter()
+                .take(MAX_GUI_MODELS)
+                .map(|model| ModelState {
+                    name: std::path::Path::new(&model.path)
+                        .file_name()
+                        .map_or_else(|| model.path.clone(), |name| name.to_string_lossy().into_owned()),
+                    position: model.transform.position,
+                    rotation: model.transform.rotation,
+                    scale: model.transform.scale,
+                    tint: model.tint,
+                    active_lod: 0,
+                    lod_count: 0,
+                })
+                .collect(),
+            auto_rotate: false,
+            auto_rotate_speed: DEFAULT_AUTO_ROTATE_SPEED,
 
             camera_fov: scene.camera.fov,
+            camera_fov_axis: scene.camera.fov_axis,
             camera_near: scene.camera.near_clip,
             camera_far: scene.camera.far_clip,
+            camera_smoothing: false,
+            camera_smoothing_strength: 0.5,
+            camera_move_speed: scene.camera.move_speed,
+            camera_boost_multiplier: scene.camera.boost_multiplier,
+            camera_constraints: scene.camera.constraints,
+            camera_shake_id: 0,
+            mouse_captured: None,
+            capture_key: config.window.capture_key.clone(),
+
+            paused: false,
+            step_id: 0,
+            camera_input_while_paused: true,
+            frustum_debug: false,
+            normal_lines: false,
+            normal_lines_settings: NormalLines::default(),
+            axis_indicator: true,
+            axis_indicator_size: DEFAULT_AXIS_INDICATOR_SIZE,
+            clip_planes: ClipPlanes::default(),
+
+            ssao_enabled: false,
+            ssao_radius: 0.5,
+            ssao_intensity: 1.0,
+            projection_jitter: false,
+
+            selected_subset: None,
+            outline_color: [1.0, 0.6, 0.0, 1.0],
+            subset_count: 0,
+            subsets: Vec::new(),
+
+            lod_ratio: 0.5,
+            lod_id: 0,
+
+            subdivision_levels: 1,
+            subdivision_id: 0,
+
+            turntable_frames: 36,
+            turntable_output_dir: "captures/turntable".to_string(),
+            turntable_id: 0,
+            turntable_progress: None,
+
+            console_level: LogLevel::Info,
+
+            uv_model: None,
+            uv_layout: None,
 
             current_backend: config.graphics.backend.name().to_string(),
             selected_backend: config.graphics.backend.name().to_string(),
             backend_changed: false,
+            low_latency: config.graphics.frames_in_flight == Some(1),
+            device_capabilities: None,
+            resource_stats: None,
         }
     }
 
@@ -65,6 +325,155 @@ impl GuiState {
         self.frame_time_ms = frame_time_ms;
     }
 
+    /// 更新实例剔除统计
+    pub fn update_culling_stats(&mut self, drawn: u32, culled: u32) {
+        self.instances_drawn = drawn;
+        self.instances_culled = culled;
+    }
+
+    /// 更新帧统计
+    pub fn update_frame_stats(&mut self, stats: &FrameStats) {
+        self.frame_stats = *stats;
+        self.gpu_wait_fraction += (stats.gpu_wait_fraction() - self.gpu_wait_fraction) * WAIT_FRACTION_SMOOTHING;
+    }
+
+    /// 请求单步执行一帧（仅在暂停时有效）
+    pub fn request_step(&mut self) {
+        self.step_id = self.step_id.wrapping_add(1);
+    }
+
+    /// 更新各模型当前使用的 LOD 级别
+    ///
+    /// `levels` 中每项为（当前级别, 级别总数），按模型序号对应。
+    pub fn update_lod_levels(&mut self, levels: impl IntoIterator<Item = (usize, usize)>) {
+        for (model, (active_lod, lod_count)) in self.models.iter_mut().zip(levels) {
+            model.active_lod = active_lod;
+            model.lod_count = lod_count;
+        }
+    }
+
+    /// 请求按 `lod_ratio` 重新生成场景网格
+    pub fn request_lod(&mut self) {
+        self.lod_id = self.lod_id.wrapping_add(1);
+    }
+
+    /// 请求按 `subdivision_levels` 细分场景网格
+    pub fn request_subdivision(&mut self) {
+        self.subdivision_id = self.subdivision_id.wrapping_add(1);
+    }
+
+    /// 请求抖动相机
+    pub fn request_camera_shake(&mut self) {
+        self.camera_shake_id = self.camera_shake_id.wrapping_add(1);
+    }
+
+    /// 请求录制转台序列
+    pub fn request_turntable(&mut self) {
+        self.turntable_id = self.turntable_id.wrapping_add(1);
+    }
+
+    /// 生成用于渲染器的参数包
+    pub fn to_packet(&self) -> GuiStatePacket {
+        let mut model_positions = [[0.0; 3]; MAX_GUI_MODELS];
+        let mut model_rotations = [[0.0; 3]; MAX_GUI_MODELS];
+        let mut model_scales = [[1.0; 3]; MAX_GUI_MODELS];
+        let mut model_tints = [[1.0; 4]; MAX_GUI_MODELS];
+        for (i, model) in self.models.iter().take(MAX_GUI_MODELS).enumerate() {
+            model_positions[i] = model.position;
+            model_rotations[i] = model.rotation;
+            model_scales[i] = model.scale;
+            model_tints[i] = model.tint;
+        }
+        let mut subset_base_colors = [[1.0; 3]; MAX_GUI_SUBSETS];
+        let mut subset_metallic = [1.0; MAX_GUI_SUBSETS];
+        let mut subset_roughness = [1.0; MAX_GUI_SUBSETS];
+        let mut subset_visible = [1; MAX_GUI_SUBSETS];
+        for (i, subset) in self.subsets.iter().take(MAX_GUI_SUBSETS).enumerate() {
+            subset_base_colors[i] = subset.base_color;
+            subset_metallic[i] = subset.metallic;
+            subset_roughness[i] = subset.roughness;
+            subset_visible[i] = subset.visible as u32;
+        }
+
+        GuiStatePacket {
+            clear_color: self.clear_color,
+            clear_color_enabled: self.clear_color_enabled as u32,
+            clear_color_cycle: self.clear_color_cycle as u32,
+            background_gradient: self.background_gradient as u32,
+            background_top: self.background_top,
+            background_bottom: self.background_bottom,
+            light_intensity: self.light_intensity,
+            light_direction: self.light_direction,
+            ambient_color: self.ambient_color,
+            ambient_intensity: self.ambient_intensity,
+            exposure: self.exposure,
+            pbr_shading: self.pbr_shading as u32,
+            ibl_intensity: self.ibl_intensity,
+            texture_filter: self.texture_filter.index(),
+            fill_mode: self.fill_mode.index(),
+            wireframe_overlay: self.wireframe_overlay as u32,
+            wireframe_color: self.wireframe_color,
+            model_count: self.models.len().min(MAX_GUI_MODELS) as u32,
+            model_positions,
+            model_rotations,
+            model_scales,
+            model_tints,
+            auto_rotate: self.auto_rotate as u32,
+            auto_rotate_speed: self.auto_rotate_speed,
+            camera_fov: self.camera_fov,
+            camera_fov_horizontal: (self.camera_fov_axis == FovAxis::Horizontal) as u32,
+            camera_near: self.camera_near,
+            camera_far: self.camera_far,
+            paused: self.paused as u32,
+            step_id: self.step_id,
+            camera_input_while_paused: self.camera_input_while_paused as u32,
+            frustum_debug: self.frustum_debug as u32,
+            normal_lines: self.normal_lines as u32,
+            normal_line_length: self.normal_lines_settings.length,
+            normal_line_stride: self.normal_lines_settings.stride,
+            normal_line_tangents: self.normal_lines_settings.tangents as u32,
+            axis_indicator: self.axis_indicator as u32,
+            axis_indicator_size: self.axis_indicator_size,
+            clip_plane_enabled: self.clip_planes.planes.map(|plane| plane.enabled as u32),
+            clip_plane_normals: self.clip_planes.planes.map(|plane| plane.normal),
+            clip_plane_offsets: self.clip_planes.planes.map(|plane| plane.offset),
+            camera_smoothing: self.camera_smoothing as u32,
+            camera_smoothing_strength: self.camera_smoothing_strength,
+            camera_move_speed: self.camera_move_speed,
+            camera_boost_multiplier: self.camera_boost_multiplier,
+            camera_lock_y: self.camera_constraints.lock_y as u32,
+            camera_lock_roll: self.camera_constraints.lock_roll as u32,
+            camera_pitch_min: self.camera_constraints.lock_pitch_range.0,
+            camera_pitch_max: self.camera_constraints.lock_pitch_range.1,
+            ssao_enabled: self.ssao_enabled as u32,
+            ssao_radius: self.ssao_radius,
+            ssao_intensity: self.ssao_intensity,
+            projection_jitter: self.projection_jitter as u32,
+            low_latency: self.low_latency as u32,
+            selection_enabled: self.selected_subset.is_some() as u32,
+            selected_subset: self.selected_subset.unwrap_or(0) as u32,
+            outline_color: self.outline_color,
+            subset_entry_count: self.subsets.len().min(MAX_GUI_SUBSETS) as u32,
+            subset_base_colors,
+            subset_metallic,
+            subset_roughness,
+            subset_visible,
+            lod_ratio: self.lod_ratio,
+            lod_id: self.lod_id,
+            subdivision_levels: self.subdivision_levels,
+            subdivision_id: self.subdivision_id,
+            turntable_frames: self.turntable_frames,
+            turntable_id: self.turntable_id,
+            camera_shake_id: self.camera_shake_id,
+        }
+    }
+
+    /// 实际使用的 GUI 缩放系数：设置的值限制在 `GUI_SCALE_RANGE` 内，未设置时为系统缩放
+    pub fn effective_gui_scale(&self, system_scale: f32) -> f32 {
+        self.gui_scale
+            .map_or(system_scale, |scale| scale.clamp(*GUI_SCALE_RANGE.start(), *GUI_SCALE_RANGE.end()))
+    }
+
     /// 检查后端是否改变
     pub fn check_backend_change(&mut self) -> bool {
         if self.selected_backend != self.current_backend {
@@ -75,3 +484,98 @@ impl GuiState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::scene::ModelConfig;
+
+    #[test]
+    fn test_model_transforms_round_trip_through_packet() {
+        let mut scene = SceneConfig::default();
+        scene.models.push(ModelConfig {
+            path: "assets/models/cube.obj".to_string(),
+            ..Default::default()
+        });
+
+        let mut state = GuiState::new(&Config::default(), &scene);
+        assert_eq!(state.models.len(), 2);
+        assert_eq!(state.models[1].name, "cube.obj");
+
+        state.models[1].position = [1.0, 2.0, 3.0];
+        state.models[1].scale = [2.0, 2.0, 2.0];
+        state.models[1].tint = [1.0, 0.0, 0.0, 1.0];
+        let packet = state.to_packet();
+        packet.apply_model_transforms(&mut scene.models);
+        packet.apply_model_tints(&mut scene.models);
+
+        assert_eq!(scene.models[0].transform.position, [0.0, 0.0, 0.0]);
+        assert_eq!(scene.models[1].transform.position, [1.0, 2.0, 3.0]);
+        assert_eq!(scene.models[1].transform.scale, [2.0, 2.0, 2.0]);
+        assert_eq!(scene.models[0].tint, [1.0; 4]);
+        assert_eq!(scene.models[1].tint, [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_subset_edits_round_trip_through_packet() {
+        let gold = Material {
+            name: Some("gold".to_string()),
+            roughness: 0.25,
+            ..Default::default()
+        };
+        let mut materials = vec![None, Some(gold.clone())];
+
+        let mut state = GuiState::new(&Config::default(), &SceneConfig::default());
+        state.subsets = materials
+            .iter()
+            .enumerate()
+            .map(|(i, material)| SubsetState::new(i, material.as_ref()))
+            .collect();
+        assert_eq!(state.subsets[1].name, "#1 gold");
+        assert!(!state.subsets[0].editable);
+
+        // 没有修改时不报告变化
+        assert!(state.to_packet().apply_subset_materials(&mut materials).is_empty());
+
+        state.subsets[1].base_color = [1.0, 0.8, 0.2];
+        state.subsets[0].visible = false;
+        let packet = state.to_packet();
+        assert_eq!(packet.apply_subset_materials(&mut materials), vec![1]);
+        assert_eq!(materials[1].as_ref().unwrap().base_color, [1.0, 0.8, 0.2, 1.0]);
+        assert!(materials[0].is_none());
+
+        let mut hidden = vec![false; 3];
+        packet.apply_subset_visibility(&mut hidden);
+        assert_eq!(hidden, vec![true, false, false]);
+    }
+
+    #[test]
+    fn test_camera_fov_axis_applies_to_camera() {
+        let mut state = GuiState::new(&Config::default(), &SceneConfig::default());
+        state.camera_fov = 90.0;
+        state.camera_fov_axis = FovAxis::Horizontal;
+
+        let mut camera = crate::component::Camera::main_camera();
+        camera.set_aspect(2.0);
+        state.to_packet().apply_camera_lens(&mut camera);
+
+        assert_eq!(camera.fov_axis(), FovAxis::Horizontal);
+        assert!((camera.fov_x() - 90f32.to_radians()).abs() < 1e-5);
+        assert_eq!(camera.aspect(), 2.0);
+    }
+
+    #[test]
+    fn test_gui_scale_defaults_to_system_and_clamps() {
+        let mut config = Config::default();
+        let state = GuiState::new(&config, &SceneConfig::default());
+        assert_eq!(state.effective_gui_scale(1.25), 1.25);
+
+        config.window.gui_scale = Some(10.0);
+        let mut state = GuiState::new(&config, &SceneConfig::default());
+        assert_eq!(state.effective_gui_scale(1.0), 3.0);
+        state.gui_scale = Some(0.1);
+        assert_eq!(state.effective_gui_scale(1.0), 0.5);
+        state.gui_scale = Some(1.5);
+        assert_eq!(state.effective_gui_scale(2.0), 1.5);
+    }
+}
@@ -4,24 +4,42 @@
 //! 可以通过配置文件或命令行参数选择使用的图形后端。
 
 use dist_render::core::{self, log, Config, SceneConfig};
-use dist_render::core::config::GraphicsBackend;
-use dist_render::core::input::InputSystem;
+use dist_render::core::error::{DistRenderError, GraphicsError};
+use dist_render::core::error_dialog::show_error_dialog;
+use dist_render::core::input::{parse_capture_key, InputConfig, InputSystem, DEFAULT_CAPTURE_KEY};
+use dist_render::core::frame_limiter::FrameLimiter;
+use dist_render::core::fixed_timestep::FixedTimestep;
+use dist_render::core::resize_debounce::ResizeDebounce;
+use dist_render::core::file_watch::FileWatcher;
+use dist_render::core::event::TickEvent;
+use dist_render::core::benchmark::{self, Benchmark};
+use dist_render::math::Vector3;
 use dist_render::renderer::Renderer;
 use dist_render::gui::ExternalGui;
 
 use tracing::{debug, error, info};
-use winit::event::{Event, WindowEvent};
+use winit::event::{DeviceEvent, ElementState, Event, WindowEvent};
 use winit::event_loop::EventLoop;
+use winit::keyboard::KeyCode;
 
 use std::time::Instant;
 
+/// 手动重新加载场景模型的按键
+const RELOAD_MODELS_KEY: KeyCode = KeyCode::F6;
+
+/// 查询模型文件修改时间的间隔（毫秒）
+const MODEL_WATCH_INTERVAL_MS: u64 = 500;
+
 fn main() {
-    let mut config = Config::from_file_or_default("config.toml");
+    let mut config = Config::from_file_or_default(core::config::DEFAULT_CONFIG_PATH);
     let args: Vec<String> = std::env::args().collect();
     config.apply_args(args.iter());
 
     if let Err(e) = config.validate() {
         eprintln!("Invalid configuration: {}", e);
+        if config.window.show_error_dialog {
+            show_error_dialog("DistRender", &format!("Invalid configuration:\n{}", e));
+        }
         std::process::exit(1);
     }
 
@@ -49,7 +67,7 @@ fn main() {
     info!(
         camera_pos = ?scene.camera.transform.position,
         camera_fov = scene.camera.fov,
-        model_path = %scene.model.path,
+        model_count = scene.models.len(),
         "Scene configuration"
     );
 
@@ -60,19 +78,32 @@ fn main() {
         Err(e) => {
             error!("Failed to initialize renderer: {}", e);
             eprintln!("Failed to initialize renderer: {}", e);
+            if config.window.show_error_dialog {
+                show_error_dialog("DistRender", &format!("Failed to initialize renderer:\n{}", e));
+            }
             std::process::exit(1);
         }
     };
 
     info!("Renderer initialized successfully");
 
-    let mut input_system = InputSystem::new();
+    // 基准测试模式：关闭 GUI 和帧率限制，相机沿固定路径运动，渲染完指定帧数后输出统计并退出
+    let mut benchmark = benchmark::parse_args(&args).map(|frames| {
+        info!(frames, "Benchmark mode enabled");
+        renderer.set_gui_enabled(false);
+        Benchmark::new(frames, Vector3::from(scene.camera.transform.position))
+    });
 
-    let no_external_gui = args.iter().any(|a| a == "--no-external-gui");
-    let force_external_gui = args.iter().any(|a| a == "--external-gui");
+    let mut input_system = InputSystem::with_config(InputConfig {
+        move_speed: scene.camera.move_speed,
+        boost_multiplier: scene.camera.boost_multiplier,
+        constraints: scene.camera.constraints,
+        capture_key: parse_capture_key(&config.window.capture_key).unwrap_or(DEFAULT_CAPTURE_KEY),
+        ..Default::default()
+    });
 
-    let default_external_gui = matches!(config.graphics.backend, GraphicsBackend::Vulkan | GraphicsBackend::Dx12 | GraphicsBackend::Metal);
-    let use_external_gui = !no_external_gui && (force_external_gui || default_external_gui);
+    // 所有后端都有内置 GUI，外部 GUI 进程只在显式传入 --external-gui 时启动
+    let use_external_gui = benchmark.is_none() && args.iter().any(|a| a == "--external-gui");
 
     let external_gui = if use_external_gui && !config.graphics.backend.is_wgpu() {
         ExternalGui::try_start(&config, &scene)
@@ -83,8 +114,33 @@ fn main() {
     if use_external_gui && external_gui.is_none() {
         warn_external_gui_disabled();
     }
+    // 外部 GUI 运行时关闭内置 GUI，避免两份参数互相覆盖
+    if external_gui.is_some() {
+        renderer.set_gui_enabled(false);
+    }
+
+    let max_fps = config.graphics.max_fps.filter(|_| benchmark.is_none());
+    let mut frame_limiter = FrameLimiter::new(max_fps);
+    match max_fps {
+        Some(fps) => info!(max_fps = fps, "Frame rate cap enabled"),
+        None => info!("Frame rate cap disabled"),
+    }
+
+    let mut fixed_timestep = FixedTimestep::new(config.graphics.fixed_update_rate);
+    match fixed_timestep.step() {
+        Some(_) => info!(rate = config.graphics.fixed_update_rate, "Fixed-timestep logic update enabled"),
+        None => info!("Variable-timestep logic update"),
+    }
+
+    let mut resize_debounce = ResizeDebounce::new(config.window.resize_debounce_ms);
+
+    // 外部工具保存模型文件后自动重新加载，基准测试时不监视
+    let watch_interval = if benchmark.is_some() { 0 } else { MODEL_WATCH_INTERVAL_MS };
+    let mut model_watcher = FileWatcher::new(scene.models.iter().map(|model| &model.path), watch_interval);
 
     let mut last_frame = Instant::now();
+    let start_time = last_frame;
+    let mut last_step_id = 0u32;
 
     let _ = event_loop.run(move |event, elwt| {
         elwt.set_control_flow(winit::event_loop::ControlFlow::Poll);
@@ -101,8 +157,8 @@ fn main() {
                 event: ref window_event,
                 ..
             } => {
-                // wgpu 后端需要先处理 GUI 事件
-                let gui_consumed = if config.graphics.backend.is_wgpu() {
+                // 有内置 GUI 的后端需要先处理 GUI 事件（鼠标捕获时输入全部交给相机）
+                let gui_consumed = if !input_system.is_captured() {
                     renderer.handle_gui_event(window_event)
                 } else {
                     false
@@ -112,12 +168,29 @@ fn main() {
                 if !gui_consumed {
                     match window_event {
                         WindowEvent::Resized(_) => {
-                            renderer.resize();
+                            // 启用去抖时只记录变化，由下一次尺寸稳定后的绘制前重建
+                            let immediate = resize_debounce.request(Instant::now());
+                            if immediate {
+                                renderer.resize();
+                            }
                         }
                         WindowEvent::KeyboardInput {
                             event: key_event, ..
                         } => {
                             if let winit::keyboard::PhysicalKey::Code(keycode) = key_event.physical_key {
+                                if input_system.is_capture_key(keycode)
+                                    && key_event.state == ElementState::Pressed
+                                    && !key_event.repeat
+                                {
+                                    let captured = !input_system.is_captured();
+                                    input_system.set_capture(renderer.window(), captured);
+                                }
+                                if keycode == RELOAD_MODELS_KEY
+                                    && key_event.state == ElementState::Pressed
+                                    && !key_event.repeat
+                                {
+                                    reload_models(&mut renderer);
+                                }
                                 input_system.on_keyboard_input(keycode, key_event.state);
                             }
                         }
@@ -128,33 +201,98 @@ fn main() {
                         WindowEvent::CursorMoved { position, .. } => {
                             input_system.on_mouse_move((position.x, position.y));
                         }
+                        WindowEvent::MouseWheel { delta, .. } => {
+                            input_system.on_mouse_wheel(*delta);
+                        }
                         WindowEvent::Focused(false) => {
-                            let window = renderer.window();
-                            input_system.unlock_cursor(window);
-                            input_system.reset_mouse();
+                            input_system.set_capture(renderer.window(), false);
                         }
                         WindowEvent::RedrawRequested => {
                             let now = Instant::now();
                             let delta_time = now.duration_since(last_frame).as_secs_f32();
                             last_frame = now;
 
-                            renderer.update(&mut input_system, delta_time);
+                            // 拖动窗口期间沿用旧的交换链，尺寸稳定后才重建
+                            if resize_debounce.poll(now) {
+                                renderer.resize();
+                            }
+                            if model_watcher.poll(now) {
+                                info!("Model file changed on disk");
+                                reload_models(&mut renderer);
+                            }
+
+                            let external_packet = external_gui.as_ref().map(|gui| gui.read_packet());
+                            let gui_packet = external_packet.or_else(|| renderer.gui_packet());
+
+                            if let Some(packet) = &gui_packet {
+                                input_system.set_smoothing(packet.camera_smoothing != 0, packet.camera_smoothing_strength);
+                                input_system.set_move_speed(packet.camera_move_speed);
+                                input_system.set_boost_multiplier(packet.camera_boost_multiplier);
+                                input_system.set_constraints(packet.camera_constraints());
+                            }
+                            if let Some(benchmark) = &benchmark {
+                                input_system.set_camera_pose(Some(benchmark.camera_pose()));
+                            }
+
+                            // 暂停时跳过 update()，但仍然 draw() 以保持窗口响应
+                            let paused = gui_packet.is_some_and(|p| p.paused != 0);
+                            let step_id = gui_packet.map_or(last_step_id, |p| p.step_id);
+                            if !paused {
+                                // 输入和相机按帧更新，动画等逻辑按固定步长推进
+                                renderer.update(&mut input_system, delta_time);
+                                for step in fixed_timestep.advance(delta_time) {
+                                    renderer.fixed_update(step);
+                                }
+                            } else if step_id != last_step_id {
+                                // 单步：暂停状态下只推进一个逻辑步长，随后保持暂停
+                                renderer.update(&mut input_system, delta_time);
+                                renderer.fixed_update(fixed_timestep.step().unwrap_or(delta_time));
+                            } else if gui_packet.is_some_and(|p| p.camera_input_while_paused != 0) {
+                                renderer.update_camera(&mut input_system, delta_time);
+                            }
+                            last_step_id = step_id;
 
-                            if let Some(gui) = &external_gui {
-                                let packet = gui.read_packet();
-                                renderer.apply_gui_packet(&packet);
+                            if let Some(packet) = &external_packet {
+                                renderer.apply_gui_packet(packet);
                             }
+                            renderer.tick(&TickEvent::new(delta_time, now.duration_since(start_time).as_secs_f32()));
 
-                            if let Err(e) = renderer.draw() {
-                                error!("Draw failed: {}", e);
-                                eprintln!("Draw failed: {}", e);
-                                elwt.exit();
+                            match renderer.draw() {
+                                Ok(stats) => {
+                                    if let Some(benchmark) = benchmark.as_mut() {
+                                        benchmark.record(delta_time * 1000.0, stats.cpu_time_ms);
+                                        if benchmark.is_finished() {
+                                            println!("{}", benchmark.report(&renderer.present_stats()));
+                                            info!("Benchmark finished, shutting down...");
+                                            elwt.exit();
+                                        }
+                                    }
+                                }
+                                Err(e) => {
+                                    // 设备丢失（驱动更新、TDR）时正常退出而不是崩溃，提示用户重新启动
+                                    if matches!(e, DistRenderError::Graphics(GraphicsError::DeviceLost(_))) {
+                                        error!("GPU device lost, exiting: {}", e);
+                                        eprintln!("GPU device lost ({}). Please restart the application.", e);
+                                    } else {
+                                        error!("Draw failed: {}", e);
+                                        eprintln!("Draw failed: {}", e);
+                                    }
+                                    elwt.exit();
+                                }
                             }
+
+                            frame_limiter.wait();
                         }
                         _ => (),
                     }
                 }
             }
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta },
+                ..
+            } => {
+                input_system.on_mouse_motion(delta);
+            }
             Event::AboutToWait => {
                 renderer.window().request_redraw();
             }
@@ -163,8 +301,16 @@ fn main() {
     });
 }
 
+/// 重新加载场景模型，失败时保留当前网格并记录错误
+fn reload_models(renderer: &mut Renderer) {
+    match renderer.reload_models() {
+        Ok(()) => info!("Scene models reloaded"),
+        Err(e) => error!("Failed to reload models, keeping the current meshes: {}", e),
+    }
+}
+
 fn warn_external_gui_disabled() {
     tracing::warn!(
-        "外部 GUI 未启动（找不到 dist_render_gui 或共享内存创建失败）。你可以：\n- 先运行 `cargo build` 生成 dist_render_gui\n- 或把 dist_render_gui 放到与主程序同目录\n- 或使用 --no-external-gui 禁用外部 GUI"
+        "外部 GUI 未启动（找不到 dist_render_gui 或共享内存创建失败）。你可以：\n- 先运行 `cargo build` 生成 dist_render_gui\n- 或把 dist_render_gui 放到与主程序同目录\n- 或去掉 --external-gui 改用内置 GUI"
     );
 }
@@ -0,0 +1,75 @@
+//! 轴对齐包围盒模块
+//!
+//! 网格和场景的轴对齐包围盒，用于根据场景范围自动设置相机的近远裁剪面等。
+
+use super::{Matrix4, Vector3};
+
+/// 轴对齐包围盒
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    /// 最小角
+    pub min: Vector3,
+
+    /// 最大角
+    pub max: Vector3,
+}
+
+impl Aabb {
+    /// 由最小角和最大角创建
+    pub fn new(min: Vector3, max: Vector3) -> Self {
+        Self { min, max }
+    }
+
+    /// 包含所有点的最小包围盒，没有点时返回 `None`
+    pub fn from_points(points: impl IntoIterator<Item = Vector3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |aabb, p| Self::new(aabb.min.inf(&p), aabb.max.sup(&p))))
+    }
+
+    /// 同时包含两个包围盒的最小包围盒
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Self::new(self.min.inf(&other.min), self.max.sup(&other.max))
+    }
+
+    /// 中心点
+    pub fn center(&self) -> Vector3 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// 八个角点
+    pub fn corners(&self) -> [Vector3; 8] {
+        let (min, max) = (self.min, self.max);
+        std::array::from_fn(|i| {
+            Vector3::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            )
+        })
+    }
+
+    /// 经过 `matrix` 变换后的包围盒（包含变换后的八个角点）
+    pub fn transformed(&self, matrix: &Matrix4) -> Aabb {
+        let corners = self.corners().map(|corner| matrix.transform_point(&corner.into()).coords);
+        Self::from_points(corners).unwrap_or(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix::translation;
+
+    #[test]
+    fn test_from_points_and_transform() {
+        let aabb = Aabb::from_points([Vector3::new(1.0, -2.0, 0.0), Vector3::new(-1.0, 2.0, 3.0)]).unwrap();
+        assert_eq!(aabb, Aabb::new(Vector3::new(-1.0, -2.0, 0.0), Vector3::new(1.0, 2.0, 3.0)));
+        assert_eq!(aabb.center(), Vector3::new(0.0, 0.0, 1.5));
+        assert!(Aabb::from_points([]).is_none());
+
+        let moved = aabb.transformed(&translation(1.0, 0.0, 0.0));
+        assert_eq!(moved.min, Vector3::new(0.0, -2.0, 0.0));
+        assert_eq!(moved.max, Vector3::new(2.0, 2.0, 3.0));
+    }
+}
@@ -0,0 +1,196 @@
+//! 视锥体模块
+//!
+//! 从视图投影矩阵提取六个裁剪平面，用于 CPU 侧的可见性剔除。
+//!
+//! 平面提取采用 Gribb-Hartmann 方法，假设裁剪空间深度范围为 [-w, w]
+//! （与 `Matrix4::new_perspective` 一致）。
+
+use super::{Matrix4, Vector3, Vector4};
+
+/// 平面（法线指向视锥体内部）
+///
+/// 平面方程：`dot(normal, p) + d = 0`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    /// 单位法线
+    pub normal: Vector3,
+
+    /// 平面到原点的有向距离
+    pub d: f32,
+}
+
+impl Plane {
+    /// 创建平面（法线会被归一化）
+    pub fn new(normal: Vector3, d: f32) -> Self {
+        let length = normal.norm();
+        if length > 0.0 {
+            Self {
+                normal: normal / length,
+                d: d / length,
+            }
+        } else {
+            Self { normal, d }
+        }
+    }
+
+    /// 从 (a, b, c, d) 系数创建平面
+    fn from_coefficients(v: Vector4) -> Self {
+        Self::new(Vector3::new(v.x, v.y, v.z), v.w)
+    }
+
+    /// 点到平面的有向距离（正值表示位于法线一侧）
+    #[inline]
+    pub fn signed_distance(&self, point: &Vector3) -> f32 {
+        self.normal.dot(point) + self.d
+    }
+}
+
+/// 视锥体
+///
+/// 平面顺序：左、右、下、上、近、远。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// 从视图投影矩阵（`projection * view`）提取视锥体
+    pub fn from_matrix(view_proj: &Matrix4) -> Self {
+        let m = view_proj;
+        let row = |i: usize| Vector4::new(m[(i, 0)], m[(i, 1)], m[(i, 2)], m[(i, 3)]);
+        let (r0, r1, r2, r3) = (row(0), row(1), row(2), row(3));
+
+        Self {
+            planes: [
+                Plane::from_coefficients(r3 + r0), // 左
+                Plane::from_coefficients(r3 - r0), // 右
+                Plane::from_coefficients(r3 + r1), // 下
+                Plane::from_coefficients(r3 - r1), // 上
+                Plane::from_coefficients(r3 + r2), // 近
+                Plane::from_coefficients(r3 - r2), // 远
+            ],
+        }
+    }
+
+    /// 判断点是否位于视锥体内
+    pub fn contains_point(&self, point: &Vector3) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(point) >= 0.0)
+    }
+
+    /// 判断球体是否与视锥体相交
+    pub fn intersects_sphere(&self, center: &Vector3, radius: f32) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(center) >= -radius)
+    }
+
+    /// 判断轴对齐包围盒是否与视锥体相交
+    ///
+    /// 对每个平面取包围盒在法线方向上最远的顶点（正顶点），
+    /// 若该顶点位于平面外侧，则包围盒完全不可见。
+    /// 结果偏保守：少数位于视锥角落外的包围盒可能被判为相交。
+    pub fn intersects_aabb(&self, min: &Vector3, max: &Vector3) -> bool {
+        self.planes.iter().all(|plane| {
+            let positive = Vector3::new(
+                if plane.normal.x >= 0.0 { max.x } else { min.x },
+                if plane.normal.y >= 0.0 { max.y } else { min.y },
+                if plane.normal.z >= 0.0 { max.z } else { min.z },
+            );
+            plane.signed_distance(&positive) >= 0.0
+        })
+    }
+
+    /// 视锥体的 8 个角点
+    ///
+    /// 顺序：近平面的左下、右下、右上、左上，然后是远平面的相同四个角。
+    /// 每个角点是相邻三个平面的交点。
+    pub fn corners(&self) -> [Vector3; 8] {
+        let [left, right, bottom, top, near, far] = &self.planes;
+        [
+            intersect_planes(near, left, bottom),
+            intersect_planes(near, right, bottom),
+            intersect_planes(near, right, top),
+            intersect_planes(near, left, top),
+            intersect_planes(far, left, bottom),
+            intersect_planes(far, right, bottom),
+            intersect_planes(far, right, top),
+            intersect_planes(far, left, top),
+        ]
+    }
+}
+
+/// 三个平面的交点（平面近似平行时结果无意义）
+fn intersect_planes(a: &Plane, b: &Plane, c: &Plane) -> Vector3 {
+    let bc = b.normal.cross(&c.normal);
+    let ca = c.normal.cross(&a.normal);
+    let ab = a.normal.cross(&b.normal);
+    -(bc * a.d + ca * b.d + ab * c.d) / a.normal.dot(&bc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix;
+
+    fn test_frustum() -> Frustum {
+        let view = matrix::look_at(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(0.0, 0.0, -1.0),
+            &Vector3::new(0.0, 1.0, 0.0),
+        );
+        let proj = matrix::perspective(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+        Frustum::from_matrix(&(proj * view))
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let frustum = test_frustum();
+        assert!(frustum.contains_point(&Vector3::new(0.0, 0.0, -10.0)));
+        assert!(!frustum.contains_point(&Vector3::new(0.0, 0.0, 10.0)));
+        assert!(!frustum.contains_point(&Vector3::new(0.0, 0.0, -200.0)));
+    }
+
+    #[test]
+    fn test_intersects_aabb() {
+        let frustum = test_frustum();
+        let half = Vector3::new(1.0, 1.0, 1.0);
+
+        let front = Vector3::new(0.0, 0.0, -10.0);
+        assert!(frustum.intersects_aabb(&(front - half), &(front + half)));
+
+        let behind = Vector3::new(0.0, 0.0, 10.0);
+        assert!(!frustum.intersects_aabb(&(behind - half), &(behind + half)));
+
+        // 跨越近平面的包围盒仍然可见
+        assert!(frustum.intersects_aabb(&(-half), &half));
+    }
+
+    #[test]
+    fn test_corners() {
+        // 90 度视场、宽高比 1：近平面半宽 0.1，远平面半宽 100
+        let corners = test_frustum().corners();
+        let expected = [
+            Vector3::new(-0.1, -0.1, -0.1),
+            Vector3::new(0.1, -0.1, -0.1),
+            Vector3::new(0.1, 0.1, -0.1),
+            Vector3::new(-0.1, 0.1, -0.1),
+            Vector3::new(-100.0, -100.0, -100.0),
+            Vector3::new(100.0, -100.0, -100.0),
+            Vector3::new(100.0, 100.0, -100.0),
+            Vector3::new(-100.0, 100.0, -100.0),
+        ];
+
+        for (corner, expected) in corners.iter().zip(&expected) {
+            assert!((corner - expected).norm() < 1e-3 * expected.norm(), "{} != {}", corner, expected);
+        }
+    }
+
+    #[test]
+    fn test_intersects_sphere() {
+        let frustum = test_frustum();
+        assert!(frustum.intersects_sphere(&Vector3::new(0.0, 0.0, -5.0), 1.0));
+        assert!(!frustum.intersects_sphere(&Vector3::new(0.0, 0.0, 5.0), 1.0));
+    }
+}
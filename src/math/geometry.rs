@@ -3,12 +3,17 @@
 //! 提供网格处理相关的数学函数，包括：
 //! - 法线重建（从三角形面计算顶点法线）
 //! - 切线空间计算（用于法线贴图）
-//! - 法线平滑
+//! - 法线平滑（按位置、按平滑组或按夹角阈值）
 //!
 //! 这些函数用于后处理加载的网格数据。
 
+use std::collections::HashMap;
+
 use crate::geometry::vertex::Vertex;
 
+/// 按位置合并顶点时使用的距离阈值
+const WELD_EPSILON: f32 = 1e-5;
+
 /// 从三角形面重建顶点法线
 ///
 /// 遍历所有三角形，计算每个面的法线，然后将面法线累加到该面的三个顶点。
@@ -109,16 +114,6 @@ pub fn smooth_normals_by_position(vertices: &mut [Vertex], epsilon: f32) {
         return;
     }
 
-    fn quantize(v: [f32; 3], eps: f32) -> (i32, i32, i32) {
-        (
-            (v[0] / eps).round() as i32,
-            (v[1] / eps).round() as i32,
-            (v[2] / eps).round() as i32,
-        )
-    }
-
-    use std::collections::HashMap;
-
     let mut reference: HashMap<(i32, i32, i32), [f32; 3]> = HashMap::new();
     let mut sums: HashMap<(i32, i32, i32), [f32; 3]> = HashMap::new();
 
@@ -145,6 +140,81 @@ pub fn smooth_normals_by_position(vertices: &mut [Vertex], epsilon: f32) {
     }
 }
 
+/// 按平滑组重建法线
+///
+/// `face_groups` 为每个三角形所属的平滑组（对应 OBJ 的 `s` 指令），组号 0 表示关闭平滑，该面直接使用面法线。
+/// 同一平滑组内位置相同的顶点共享平均法线（包括 UV 接缝处拆开的顶点），不同平滑组之间保持硬边。
+///
+/// 被多个平滑组共用的顶点会被拆分为多个顶点，因此 `vertices` 可能增长，`indices` 会被改写。
+pub fn reconstruct_normals_by_group(vertices: &mut Vec<Vertex>, indices: &mut [u32], face_groups: &[u32]) {
+    /// 参与平均的范围：同一平滑组，或关闭平滑时的单个面
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum Smoothing {
+        Group(u32),
+        Face(usize),
+    }
+
+    let smoothing = |face: usize| match face_groups.get(face).copied().unwrap_or(0) {
+        0 => Smoothing::Face(face),
+        group => Smoothing::Group(group),
+    };
+
+    let face_normals = face_normals(vertices, indices);
+    let mut sums = HashMap::new();
+    for (corner, &index) in indices.iter().enumerate() {
+        let face = corner / 3;
+        let key = (quantize(vertices[index as usize].position, WELD_EPSILON), smoothing(face));
+        let sum = sums.entry(key).or_insert([0.0, 0.0, 0.0]);
+        add_assign(sum, face_normals[face]);
+    }
+
+    let corner_normals: Vec<[f32; 3]> = indices
+        .iter()
+        .enumerate()
+        .map(|(corner, &index)| {
+            let key = (quantize(vertices[index as usize].position, WELD_EPSILON), smoothing(corner / 3));
+            normalize(sums[&key])
+        })
+        .collect();
+    apply_corner_normals(vertices, indices, &corner_normals);
+}
+
+/// 按夹角阈值重建法线
+///
+/// 位置相同的顶点只平均与当前面夹角不超过 `max_angle_degrees` 的相邻面法线，
+/// 夹角更大的边保持硬边。用于没有平滑组信息的网格。
+///
+/// 硬边两侧的顶点会被拆分，因此 `vertices` 可能增长，`indices` 会被改写。
+pub fn reconstruct_normals_by_angle(vertices: &mut Vec<Vertex>, indices: &mut [u32], max_angle_degrees: f32) {
+    let min_cos = max_angle_degrees.to_radians().cos();
+    let face_normals = face_normals(vertices, indices);
+    let unit_normals: Vec<[f32; 3]> = face_normals.iter().map(|&n| normalize(n)).collect();
+
+    let mut faces_at: HashMap<(i32, i32, i32), Vec<usize>> = HashMap::new();
+    for (corner, &index) in indices.iter().enumerate() {
+        let faces = faces_at.entry(quantize(vertices[index as usize].position, WELD_EPSILON)).or_default();
+        if faces.last() != Some(&(corner / 3)) {
+            faces.push(corner / 3);
+        }
+    }
+
+    let corner_normals: Vec<[f32; 3]> = indices
+        .iter()
+        .enumerate()
+        .map(|(corner, &index)| {
+            let face = corner / 3;
+            let mut sum = [0.0, 0.0, 0.0];
+            for &other in &faces_at[&quantize(vertices[index as usize].position, WELD_EPSILON)] {
+                if other == face || dot(unit_normals[face], unit_normals[other]) >= min_cos {
+                    add_assign(&mut sum, face_normals[other]);
+                }
+            }
+            normalize(sum)
+        })
+        .collect();
+    apply_corner_normals(vertices, indices, &corner_normals);
+}
+
 /// 计算顶点的切线空间向量
 ///
 /// 使用UV坐标导数计算每个顶点的切线向量，用于法线贴图。
@@ -281,6 +351,65 @@ pub fn compute_tangent_space(vertices: &mut [Vertex], indices: &[u32]) {
 // 辅助函数
 // ============================================================================
 
+/// 辅助函数：计算每个三角形未归一化的面法线（长度与面积成正比，平均时按面积加权）
+fn face_normals(vertices: &[Vertex], indices: &[u32]) -> Vec<[f32; 3]> {
+    indices
+        .chunks_exact(3)
+        .map(|triangle| {
+            let p0 = vertices[triangle[0] as usize].position;
+            let p1 = vertices[triangle[1] as usize].position;
+            let p2 = vertices[triangle[2] as usize].position;
+            let edge1 = [p1[0] - p0[0], p1[1] - p0[1], p1[2] - p0[2]];
+            let edge2 = [p2[0] - p0[0], p2[1] - p0[1], p2[2] - p0[2]];
+            cross(edge1, edge2)
+        })
+        .collect()
+}
+
+/// 辅助函数：把每个三角形角的法线写回顶点
+///
+/// 同一顶点在不同的角上得到不同的法线时，为每种法线复制一个新顶点并改写索引。
+fn apply_corner_normals(vertices: &mut Vec<Vertex>, indices: &mut [u32], corner_normals: &[[f32; 3]]) {
+    let mut assigned: Vec<Option<[f32; 3]>> = vec![None; vertices.len()];
+    let mut splits: HashMap<(u32, [u32; 3]), u32> = HashMap::new();
+
+    for (index, &normal) in indices.iter_mut().zip(corner_normals) {
+        let vertex = *index as usize;
+        match assigned[vertex] {
+            None => {
+                assigned[vertex] = Some(normal);
+                vertices[vertex].normal = normal;
+            }
+            Some(existing) if existing == normal => {}
+            Some(_) => {
+                *index = *splits.entry((*index, normal.map(f32::to_bits))).or_insert_with(|| {
+                    let mut split = vertices[vertex];
+                    split.normal = normal;
+                    vertices.push(split);
+                    (vertices.len() - 1) as u32
+                });
+            }
+        }
+    }
+}
+
+/// 辅助函数：把位置量化为整数格点，用于按位置合并顶点
+fn quantize(v: [f32; 3], eps: f32) -> (i32, i32, i32) {
+    (
+        (v[0] / eps).round() as i32,
+        (v[1] / eps).round() as i32,
+        (v[2] / eps).round() as i32,
+    )
+}
+
+/// 辅助函数：向量累加
+#[inline]
+fn add_assign(sum: &mut [f32; 3], v: [f32; 3]) {
+    sum[0] += v[0];
+    sum[1] += v[1];
+    sum[2] += v[2];
+}
+
 /// 辅助函数：计算两个3D向量的叉乘
 ///
 /// 返回垂直于两个输入向量的向量，长度等于两向量张成的平行四边形面积。
@@ -398,6 +527,55 @@ mod tests {
         }
     }
 
+    /// 沿 X 轴折成直角的两个三角形，共用折痕上的两个顶点
+    fn folded_quad() -> (Vec<Vertex>, Vec<u32>) {
+        let vertices = vec![
+            Vertex::new([0.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0], [0.0, 0.0, 0.0]),
+            Vertex::new([1.0, 0.0, 0.0], [0.0, 0.0, 0.0], [1.0, 0.0], [0.0, 0.0, 0.0]),
+            Vertex::new([0.0, 0.0, -1.0], [0.0, 0.0, 0.0], [0.0, 1.0], [0.0, 0.0, 0.0]),
+            Vertex::new([0.0, -1.0, 0.0], [0.0, 0.0, 0.0], [0.0, 1.0], [0.0, 0.0, 0.0]),
+        ];
+        // 第一个面朝 +Y，第二个面朝 +Z
+        (vertices, vec![0, 1, 2, 1, 0, 3])
+    }
+
+    #[test]
+    fn test_reconstruct_normals_by_group_keeps_hard_edge_between_groups() {
+        let (mut vertices, mut indices) = folded_quad();
+        reconstruct_normals_by_group(&mut vertices, &mut indices, &[1, 2]);
+
+        // 折痕上的两个顶点各拆成两份，分别使用两个面的法线
+        assert_eq!(vertices.len(), 6);
+        for &index in &indices[..3] {
+            assert_eq!(vertices[index as usize].normal, [0.0, 1.0, 0.0]);
+        }
+        for &index in &indices[3..] {
+            assert_eq!(vertices[index as usize].normal, [0.0, 0.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn test_reconstruct_normals_by_group_smooths_within_group() {
+        let (mut vertices, mut indices) = folded_quad();
+        reconstruct_normals_by_group(&mut vertices, &mut indices, &[1, 1]);
+
+        assert_eq!(vertices.len(), 4);
+        let n = vertices[0].normal;
+        let expected = std::f32::consts::FRAC_1_SQRT_2;
+        assert!(n[0].abs() < 1e-6 && (n[1] - expected).abs() < 1e-6 && (n[2] - expected).abs() < 1e-6, "{:?}", n);
+    }
+
+    #[test]
+    fn test_reconstruct_normals_by_angle() {
+        let (mut vertices, mut indices) = folded_quad();
+        reconstruct_normals_by_angle(&mut vertices, &mut indices, 60.0);
+        assert_eq!(vertices.len(), 6);
+
+        let (mut vertices, mut indices) = folded_quad();
+        reconstruct_normals_by_angle(&mut vertices, &mut indices, 100.0);
+        assert_eq!(vertices.len(), 4);
+    }
+
     #[test]
     fn test_compute_tangent_space_simple() {
         // 创建一个简单的三角形，带有法线和UV
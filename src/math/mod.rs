@@ -9,9 +9,12 @@
 //! - **常量**：PI, TAU, DEG_TO_RAD 等
 //! - **工具函数**：clamp, lerp, smoothstep 等
 //! - **矩阵辅助函数**：translation, rotation, projection 等
-//! - **四元数辅助函数**：from_euler_angles, slerp 等
+//! - **四元数辅助函数**：from_euler_angles, to_euler_angles, slerp 等
 //! - **颜色空间转换**：linear_to_srgb, srgb_to_linear 等
 //! - **几何处理**：法线重建、切线空间计算（见 geometry 子模块）
+//! - **视锥体**：平面提取、包围体相交测试（见 frustum 子模块）
+//! - **包围盒**：轴对齐包围盒的合并和变换（见 aabb 子模块）
+//! - **随机数和噪声**：确定性的 xorshift 生成器、一维值噪声和 Halton 序列（见 noise 子模块）
 //!
 //! # 设计理念
 //!
@@ -77,6 +80,37 @@ impl Color {
         Vector3::new(self.r, self.g, self.b)
     }
 
+    /// 将所有通道限制到 [0, 1]
+    ///
+    /// 算术运算符不做限制，需要时在运算结束后调用。
+    pub fn saturated(&self) -> Self {
+        Self::new(
+            self.r.clamp(0.0, 1.0),
+            self.g.clamp(0.0, 1.0),
+            self.b.clamp(0.0, 1.0),
+            self.a.clamp(0.0, 1.0),
+        )
+    }
+
+    /// 转换为预乘 alpha 颜色（RGB 乘以 alpha）
+    pub fn premultiply(&self) -> Self {
+        Self::new(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+    }
+
+    /// 将当前颜色（前景）以非预乘 alpha 合成到背景之上（alpha-over）
+    ///
+    /// 结果同样是非预乘 alpha 颜色。两者完全透明时返回透明黑色。
+    pub fn blend_over(&self, bg: &Color) -> Self {
+        let a = self.a + bg.a * (1.0 - self.a);
+        if a <= 0.0 {
+            return Self::new(0.0, 0.0, 0.0, 0.0);
+        }
+
+        let fg = self.premultiply();
+        let bg = bg.premultiply() * (1.0 - self.a);
+        Self::new((fg.r + bg.r) / a, (fg.g + bg.g) / a, (fg.b + bg.b) / a, a)
+    }
+
     // 预定义颜色
     pub const WHITE: Color = Color { r: 1.0, g: 1.0, b: 1.0, a: 1.0 };
     pub const BLACK: Color = Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 };
@@ -88,6 +122,40 @@ impl Color {
     pub const MAGENTA: Color = Color { r: 1.0, g: 0.0, b: 1.0, a: 1.0 };
 }
 
+// 颜色算术：所有运算均按分量作用于 RGBA 四个通道，结果不做限制
+
+impl std::ops::Add for Color {
+    type Output = Color;
+
+    fn add(self, rhs: Color) -> Color {
+        Color::new(self.r + rhs.r, self.g + rhs.g, self.b + rhs.b, self.a + rhs.a)
+    }
+}
+
+impl std::ops::Sub for Color {
+    type Output = Color;
+
+    fn sub(self, rhs: Color) -> Color {
+        Color::new(self.r - rhs.r, self.g - rhs.g, self.b - rhs.b, self.a - rhs.a)
+    }
+}
+
+impl std::ops::Mul<f32> for Color {
+    type Output = Color;
+
+    fn mul(self, rhs: f32) -> Color {
+        Color::new(self.r * rhs, self.g * rhs, self.b * rhs, self.a * rhs)
+    }
+}
+
+impl std::ops::Mul<Color> for Color {
+    type Output = Color;
+
+    fn mul(self, rhs: Color) -> Color {
+        Color::new(self.r * rhs.r, self.g * rhs.g, self.b * rhs.b, self.a * rhs.a)
+    }
+}
+
 /// 数学常量
 pub mod constants {
     /// π
@@ -268,6 +336,15 @@ pub mod matrix {
     pub fn look_at(eye: &Vector3, target: &Vector3, up: &Vector3) -> Matrix4 {
         Matrix4::look_at_rh(&Point3::from(*eye), &Point3::from(*target), up)
     }
+
+    /// 法线矩阵：模型矩阵左上 3x3 的逆转置，扩展为 4x4（不含平移）
+    ///
+    /// 非均匀缩放时直接用模型矩阵变换法线会使其不再垂直于表面，逆转置可以保持垂直；
+    /// 变换后的法线长度会改变，着色器中需重新归一化。缩放为 0 导致矩阵不可逆时退回模型矩阵的 3x3 部分。
+    pub fn normal_matrix(model: &Matrix4) -> Matrix4 {
+        let linear: Matrix3 = model.fixed_view::<3, 3>(0, 0).into_owned();
+        linear.try_inverse().unwrap_or(linear).transpose().to_homogeneous()
+    }
 }
 
 /// 四元数辅助函数
@@ -279,6 +356,33 @@ pub mod quaternion {
         UnitQuaternion::from_euler_angles(roll, pitch, yaw)
     }
 
+    /// 把四元数分解为欧拉角，返回 `(yaw, pitch, roll)`
+    ///
+    /// 与 [`from_euler_angles`] 互逆：`from_euler_angles(yaw, pitch, roll)` 得到同一个旋转。
+    /// pitch 位于 ±90° 时欧拉角不唯一，此时只保证旋转相同。
+    pub fn to_euler_angles(quat: &Quaternion) -> (f32, f32, f32) {
+        let (roll, pitch, yaw) = quat.euler_angles();
+        (yaw, pitch, roll)
+    }
+
+    /// 按 Transform 的欧拉角约定创建四元数
+    ///
+    /// `euler` 为度数，x/y/z 分别是绕 X（pitch）、Y（yaw）、Z（roll）轴的旋转，
+    /// 组合顺序为 `Rz * Ry * Rx`，与 Transform 的世界矩阵一致。
+    pub fn from_euler_degrees(euler: &Vector3) -> Quaternion {
+        from_euler_angles(
+            euler.z.to_radians(),
+            euler.y.to_radians(),
+            euler.x.to_radians(),
+        )
+    }
+
+    /// [`from_euler_degrees`] 的逆变换，返回度数
+    pub fn to_euler_degrees(quat: &Quaternion) -> Vector3 {
+        let (z, y, x) = to_euler_angles(quat);
+        Vector3::new(x.to_degrees(), y.to_degrees(), z.to_degrees())
+    }
+
     /// 从轴角创建四元数
     pub fn from_axis_angle(axis: &Vector3, angle: f32) -> Quaternion {
         UnitQuaternion::from_axis_angle(&nalgebra::Unit::new_normalize(*axis), angle)
@@ -332,11 +436,35 @@ pub mod color_space {
     pub fn luminance(color: &Vector3) -> f32 {
         color.dot(&Vector3::new(0.299, 0.587, 0.114))
     }
+
+    /// HSV 转 RGB
+    ///
+    /// 色相 `h` 以圈为单位（超出 [0, 1) 时取小数部分），饱和度 `s` 和明度 `v` 范围 0-1。
+    pub fn hsv_to_rgb(h: f32, s: f32, v: f32) -> Vector3 {
+        let h = h.rem_euclid(1.0) * 6.0;
+        let channel = |offset: f32| {
+            let k = (offset + h) % 6.0;
+            v - v * s * k.min(4.0 - k).clamp(0.0, 1.0)
+        };
+        Vector3::new(channel(5.0), channel(3.0), channel(1.0))
+    }
 }
 
 // 几何处理模块（网格法线、切线等）
 pub mod geometry;
 
+// 视锥体（可见性剔除）
+pub mod frustum;
+
+// 射线（屏幕拾取）
+pub mod ray;
+
+// 轴对齐包围盒（裁剪面自动适配等）
+pub mod aabb;
+
+// 随机数和噪声（相机抖动等）
+pub mod noise;
+
 // 注意：由于 Rust 的孤儿规则，我们不能为 nalgebra 的 Vector 类型实现 bytemuck traits
 // 顶点结构使用原始数组，但提供了 from_vectors() 便利方法来使用 Vector 类型
 
@@ -353,6 +481,20 @@ mod tests {
         assert!((cross.z - 1.0).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_hsv_to_rgb() {
+        use color_space::hsv_to_rgb;
+
+        let close = |a: Vector3, b: Vector3| (a - b).norm() < 1e-5;
+        assert!(close(hsv_to_rgb(0.0, 1.0, 1.0), Vector3::new(1.0, 0.0, 0.0)));
+        assert!(close(hsv_to_rgb(1.0 / 3.0, 1.0, 1.0), Vector3::new(0.0, 1.0, 0.0)));
+        assert!(close(hsv_to_rgb(2.0 / 3.0, 1.0, 1.0), Vector3::new(0.0, 0.0, 1.0)));
+        assert!(close(hsv_to_rgb(1.0 / 6.0, 1.0, 0.5), Vector3::new(0.5, 0.5, 0.0)));
+        // 色相按圈循环，饱和度为 0 时为灰色
+        assert!(close(hsv_to_rgb(1.25, 1.0, 1.0), hsv_to_rgb(0.25, 1.0, 1.0)));
+        assert!(close(hsv_to_rgb(0.4, 0.0, 0.7), Vector3::new(0.7, 0.7, 0.7)));
+    }
+
     #[test]
     fn test_color_creation() {
         let color = Color::rgb(1.0, 0.5, 0.0);
@@ -360,6 +502,93 @@ mod tests {
         assert_eq!(color.a, 1.0);
     }
 
+    #[test]
+    fn test_color_arithmetic() {
+        let c = Color::new(0.5, 0.25, 1.0, 1.0);
+        assert_eq!(c + c, Color::new(1.0, 0.5, 2.0, 2.0));
+        assert_eq!(c - c, Color::new(0.0, 0.0, 0.0, 0.0));
+        assert_eq!(c * 2.0, Color::new(1.0, 0.5, 2.0, 2.0));
+        assert_eq!(c * Color::new(0.5, 1.0, 0.0, 1.0), Color::new(0.25, 0.25, 0.0, 1.0));
+
+        // 运算符不限制范围，saturated 负责截断
+        assert_eq!((c * 2.0).saturated(), Color::new(1.0, 0.5, 1.0, 1.0));
+        assert_eq!((c - Color::WHITE).saturated(), Color::new(0.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_color_premultiply() {
+        let c = Color::new(1.0, 0.5, 0.2, 0.5);
+        assert_eq!(c.premultiply(), Color::new(0.5, 0.25, 0.1, 0.5));
+    }
+
+    #[test]
+    fn test_color_blend_over_opaque_background() {
+        let fg = Color::new(1.0, 0.0, 0.0, 0.25);
+        let bg = Color::rgb(0.0, 0.0, 1.0);
+
+        let out = fg.blend_over(&bg);
+        assert!((out.r - 0.25).abs() < 1e-6);
+        assert!(out.g.abs() < 1e-6);
+        assert!((out.b - 0.75).abs() < 1e-6);
+        assert!((out.a - 1.0).abs() < 1e-6);
+
+        // 不透明前景完全覆盖背景，透明前景不改变背景
+        assert_eq!(Color::RED.blend_over(&bg), Color::RED);
+        assert_eq!(Color::new(1.0, 1.0, 1.0, 0.0).blend_over(&bg), bg);
+    }
+
+    #[test]
+    fn test_color_blend_over_transparent_background() {
+        let fg = Color::new(1.0, 1.0, 1.0, 0.5);
+        let bg = Color::new(0.0, 0.0, 0.0, 0.5);
+
+        let out = fg.blend_over(&bg);
+        assert!((out.a - 0.75).abs() < 1e-6);
+        // (1 * 0.5 + 0 * 0.25) / 0.75
+        assert!((out.r - 2.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_quaternion_euler_round_trip() {
+        let q = quaternion::from_euler_angles(0.3, -0.7, 1.2);
+        let (yaw, pitch, roll) = quaternion::to_euler_angles(&q);
+        assert!((yaw - 0.3).abs() < 1e-5);
+        assert!((pitch + 0.7).abs() < 1e-5);
+        assert!((roll - 1.2).abs() < 1e-5);
+
+        // 度数形式与 Rz * Ry * Rx 的矩阵组合一致
+        let euler = Vector3::new(30.0, -45.0, 60.0);
+        let q = quaternion::from_euler_degrees(&euler);
+        let m = matrix::rotation_z(60f32.to_radians())
+            * matrix::rotation_y((-45f32).to_radians())
+            * matrix::rotation_x(30f32.to_radians());
+        assert!((matrix::from_quaternion(&q) - m).norm() < 1e-5);
+        assert!((quaternion::to_euler_degrees(&q) - euler).norm() < 1e-3);
+    }
+
+    #[test]
+    fn test_normal_matrix_keeps_normals_perpendicular_under_non_uniform_scale() {
+        use matrix::{normal_matrix, rotation_y, scaling, translation};
+
+        let model = translation(1.0, 2.0, 3.0) * rotation_y(0.7) * scaling(4.0, 1.0, 0.5);
+        let normal_matrix = normal_matrix(&model);
+
+        // 斜面上的法线和两条切线
+        let normal = Vector3::new(1.0, 1.0, 0.0).normalize();
+        let tangents = [Vector3::new(1.0, -1.0, 0.0), Vector3::new(0.0, 0.0, 1.0)];
+
+        let transformed = normal_matrix.transform_vector(&normal).normalize();
+        assert!((transformed.norm() - 1.0).abs() < 1e-5);
+        for tangent in tangents {
+            let tangent = model.transform_vector(&tangent);
+            assert!(transformed.dot(&tangent).abs() < 1e-5);
+        }
+
+        // 直接使用模型矩阵的法线不再垂直于表面
+        let skewed = model.transform_vector(&normal).normalize();
+        assert!(skewed.dot(&model.transform_vector(&tangents[0])).abs() > 0.1);
+    }
+
     #[test]
     fn test_matrix_translation() {
         let mat = matrix::translation(1.0, 2.0, 3.0);
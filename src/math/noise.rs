@@ -0,0 +1,111 @@
+//! 随机数和噪声模块
+//!
+//! 确定性的伪随机数生成器和一维值噪声，相同的种子总是得到相同的序列，
+//! 便于复现相机抖动等效果和编写测试。另外提供 Halton 低差异序列，用于投影矩阵的亚像素抖动。
+
+/// xorshift32 伪随机数生成器
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u32,
+}
+
+impl Rng {
+    /// 用种子创建生成器（种子为 0 时替换为非零常量，xorshift 的状态不能为 0）
+    pub fn new(seed: u32) -> Self {
+        Self {
+            state: if seed == 0 { 0x9E37_79B9 } else { seed },
+        }
+    }
+
+    /// 下一个 32 位整数
+    pub fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state
+    }
+
+    /// `[0, 1)` 内均匀分布的浮点数
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// `[min, max)` 内均匀分布的浮点数
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
+
+/// 整数格点上的哈希值，映射到 `[-1, 1]`
+fn lattice(seed: u32, i: i32) -> f32 {
+    let mut h = seed ^ (i as u32).wrapping_mul(0x27D4_EB2D);
+    h = (h ^ (h >> 15)).wrapping_mul(0x85EB_CA6B);
+    h = (h ^ (h >> 13)).wrapping_mul(0xC2B2_AE35);
+    h ^= h >> 16;
+    (h >> 8) as f32 / ((1u32 << 24) - 1) as f32 * 2.0 - 1.0
+}
+
+/// 一维值噪声，结果在 `[-1, 1]` 内并随 `t` 连续变化
+///
+/// 整数格点上取由 `seed` 决定的随机值，格点之间用 smoothstep 插值。
+pub fn value_noise(seed: u32, t: f32) -> f32 {
+    let i = t.floor();
+    let f = t - i;
+    let s = f * f * (3.0 - 2.0 * f);
+    let (a, b) = (lattice(seed, i as i32), lattice(seed, i as i32 + 1));
+    a + (b - a) * s
+}
+
+/// Halton 序列中第 `index` 个值，`base` 为素数底数，结果在 `[0, 1)` 内
+///
+/// 把 `index` 按 `base` 进制的各位数字反转到小数点之后。`index` 从 1 开始时前几个值均匀分散，
+/// 以 2 和 3 为底的两个序列组合成二维的亚像素采样点。
+pub fn halton(mut index: u32, base: u32) -> f32 {
+    let mut result = 0.0;
+    let mut fraction = 1.0;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_is_deterministic_and_in_range() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        for _ in 0..100 {
+            let value = a.next_f32();
+            assert_eq!(value, b.next_f32());
+            assert!((0.0..1.0).contains(&value));
+        }
+        assert_ne!(Rng::new(7).next_u32(), Rng::new(8).next_u32());
+    }
+
+    #[test]
+    fn test_halton_sequence() {
+        let base2: Vec<f32> = (1..=4).map(|i| halton(i, 2)).collect();
+        assert_eq!(base2, vec![0.5, 0.25, 0.75, 0.125]);
+
+        let base3: Vec<f32> = (1..=4).map(|i| halton(i, 3)).collect();
+        let expected = [1.0 / 3.0, 2.0 / 3.0, 1.0 / 9.0, 4.0 / 9.0];
+        for (value, expected) in base3.iter().zip(expected) {
+            assert!((value - expected).abs() < 1e-6, "{} != {}", value, expected);
+        }
+        assert_eq!(halton(0, 2), 0.0);
+    }
+
+    #[test]
+    fn test_value_noise_is_continuous() {
+        for step in 0..1000 {
+            let t = step as f32 * 0.01;
+            let value = value_noise(3, t);
+            assert!((-1.0..=1.0).contains(&value));
+            assert!((value - value_noise(3, t + 0.001)).abs() < 0.01);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! 射线模块
+//!
+//! 从相机发出的射线，用于鼠标拾取等屏幕空间到世界空间的查询。
+
+use super::Vector3;
+
+/// 射线（方向为单位向量）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    /// 起点
+    pub origin: Vector3,
+
+    /// 单位方向
+    pub direction: Vector3,
+}
+
+impl Ray {
+    /// 创建射线（方向会被归一化）
+    pub fn new(origin: Vector3, direction: Vector3) -> Self {
+        Self {
+            origin,
+            direction: direction.try_normalize(1e-12).unwrap_or_default(),
+        }
+    }
+
+    /// 沿射线距离起点 `t` 处的点
+    pub fn at(&self, t: f32) -> Vector3 {
+        self.origin + self.direction * t
+    }
+
+    /// 点到射线的最短距离（点在起点后方时为到起点的距离）
+    pub fn distance_to_point(&self, point: Vector3) -> f32 {
+        let t = (point - self.origin).dot(&self.direction).max(0.0);
+        (point - self.at(t)).norm()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_to_point() {
+        let ray = Ray::new(Vector3::zeros(), Vector3::new(0.0, 0.0, -2.0));
+        assert_eq!(ray.direction, Vector3::new(0.0, 0.0, -1.0));
+        assert_eq!(ray.at(3.0), Vector3::new(0.0, 0.0, -3.0));
+
+        assert!((ray.distance_to_point(Vector3::new(1.0, 0.0, -5.0)) - 1.0).abs() < 1e-6);
+        // 起点后方的点按到起点的距离计算
+        assert!((ray.distance_to_point(Vector3::new(0.0, 0.0, 2.0)) - 2.0).abs() < 1e-6);
+    }
+}
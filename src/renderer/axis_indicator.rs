@@ -0,0 +1,99 @@
+//! 坐标轴指示器
+//!
+//! 在场景视口左下角始终显示的三轴指示（X 红、Y 绿、Z 蓝），只随相机旋转而转动，
+//! 不受模型变换和相机位置影响，便于确认当前的观察方向（尤其是坐标系转换之后）。
+//! 几何由 [`DebugDraw`] 生成，后端在角落的小视口中用调试线段通道绘制。
+
+use crate::component::GizmoAxis;
+use crate::gfx::letterbox::ViewportRect;
+use crate::math::{Matrix4, Vector3};
+use crate::renderer::debug_draw::DebugDraw;
+
+/// 默认边长（像素）
+pub const DEFAULT_AXIS_INDICATOR_SIZE: u32 = 96;
+
+/// 与场景视口边缘的距离（像素）
+const MARGIN: u32 = 12;
+
+/// 坐标轴长度占指示器半边长的比例，为线宽留出余量
+const AXIS_EXTENT: f32 = 0.8;
+
+/// 指示器的视图投影矩阵
+///
+/// 只取视图矩阵的旋转部分，正交投影把单位长度的坐标轴映射到指示器视口内；
+/// 视图空间 z ∈ [-1, 1] 映射到深度 [1, 0]。
+pub fn view_proj(view: &Matrix4) -> Matrix4 {
+    let mut rotation = Matrix4::identity();
+    rotation.fixed_view_mut::<3, 3>(0, 0).copy_from(&view.fixed_view::<3, 3>(0, 0));
+    #[rustfmt::skip]
+    let projection = Matrix4::new(
+        AXIS_EXTENT, 0.0, 0.0, 0.0,
+        0.0, AXIS_EXTENT, 0.0, 0.0,
+        0.0, 0.0, -0.5, 0.5,
+        0.0, 0.0, 0.0, 1.0,
+    );
+    projection * rotation
+}
+
+/// 添加三条坐标轴线段
+///
+/// 调试线段不做深度测试，后添加的覆盖先添加的，因此按离相机由远到近的顺序添加。
+pub fn draw(debug_draw: &mut DebugDraw, view: &Matrix4) {
+    let view_z = |axis: &GizmoAxis| (view * axis.direction().to_homogeneous()).z;
+    let mut axes = GizmoAxis::ALL;
+    axes.sort_by(|a, b| view_z(a).total_cmp(&view_z(b)));
+    for axis in axes {
+        debug_draw.line(Vector3::zeros(), axis.direction(), axis.color());
+    }
+}
+
+/// 指示器在场景视口左下角的正方形视口，场景视口放不下时返回 `None`
+pub fn viewport(scene: ViewportRect, size: u32) -> Option<ViewportRect> {
+    (size > 0 && size + 2 * MARGIN <= scene.width.min(scene.height)).then(|| ViewportRect {
+        x: scene.x + MARGIN,
+        y: scene.y + scene.height - MARGIN - size,
+        width: size,
+        height: size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Camera;
+
+    #[test]
+    fn test_ignores_camera_position() {
+        let mut camera = Camera::main_camera();
+        camera.set_position(Vector3::new(0.0, 0.0, 5.0));
+        let near = view_proj(&camera.view_matrix());
+        camera.set_position(Vector3::new(100.0, -20.0, 50.0));
+        assert_eq!(view_proj(&camera.view_matrix()), near);
+
+        // 默认相机朝向 -Z：X 轴指向屏幕右侧，Y 轴指向上方，Z 轴朝向相机
+        let tip = |axis: GizmoAxis| near * axis.direction().push(1.0);
+        assert!((tip(GizmoAxis::X) - Vector3::new(AXIS_EXTENT, 0.0, 0.5).push(1.0)).norm() < 1e-5);
+        assert!((tip(GizmoAxis::Y) - Vector3::new(0.0, AXIS_EXTENT, 0.5).push(1.0)).norm() < 1e-5);
+        assert!((tip(GizmoAxis::Z) - Vector3::new(0.0, 0.0, 0.0).push(1.0)).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_axes_drawn_far_to_near() {
+        let mut camera = Camera::main_camera();
+        camera.set_position(Vector3::zeros());
+        let mut debug_draw = DebugDraw::new();
+        draw(&mut debug_draw, &camera.view_matrix());
+
+        // 朝向相机的 Z 轴最后绘制，覆盖其他轴
+        let colors: Vec<[f32; 3]> = debug_draw.lines().iter().map(|line| line.color).collect();
+        assert_eq!(colors.len(), 3);
+        assert_eq!(colors[2], GizmoAxis::Z.color());
+    }
+
+    #[test]
+    fn test_viewport_in_bottom_left_corner() {
+        let scene = ViewportRect { x: 10, y: 20, width: 800, height: 600 };
+        assert_eq!(viewport(scene, 96), Some(ViewportRect { x: 22, y: 512, width: 96, height: 96 }));
+        assert_eq!(viewport(ViewportRect::full(100, 100), 96), None);
+    }
+}
@@ -10,9 +10,15 @@
 //! - **可扩展性**：方便添加新的图形后端
 //! - **零成本抽象**：使用 trait object 的开销可以忽略不计
 
-use crate::core::error::Result;
+use std::time::Instant;
+
+use image::RgbaImage;
+
+use crate::core::error::{GraphicsError, Result};
+use crate::core::event::TickEvent;
 use crate::core::input::InputSystem;
 use crate::gui::ipc::GuiStatePacket;
+use crate::renderer::stats::{FrameStats, PresentStats};
 use winit::event::WindowEvent;
 use winit::window::Window;
 
@@ -25,9 +31,20 @@ use winit::window::Window;
 /// - `window()`: 获取窗口引用，用于窗口相关操作
 /// - `resize()`: 处理窗口尺寸变化事件
 /// - `draw()`: 渲染一帧画面
+/// - `draw_with_stats()`: 渲染一帧画面并返回帧统计
 /// - `update()`: 更新渲染器状态（处理输入、更新相机等）
+/// - `fixed_update()`: 按固定步长推进动画等逻辑状态（默认不处理）
+/// - `update_camera()`: 仅根据输入更新相机（暂停时使用）
 /// - `apply_gui_packet()`: 应用 GUI 参数包
 /// - `handle_gui_event()`: 处理 GUI 事件（默认不处理）
+/// - `set_gui_enabled()`: 启用或禁用内置 GUI（默认不处理）
+/// - `read_depth()`: 读回深度缓冲（默认不支持）
+/// - `capture_region()`: 绘制一帧并截取画面（默认不支持）
+/// - `set_capture_hides_gui()`: 设置截图时是否隐藏 GUI（默认不处理）
+/// - `reload_models()`: 从磁盘重新加载场景模型（默认不支持）
+/// - `gui_packet()`: 获取内置 GUI 的参数包（默认没有内置 GUI）
+/// - `present_stats()`: 获取呈现统计（默认全部为零）
+/// - `wait_idle()`: 等待 GPU 完成所有已提交的工作
 ///
 /// # 示例
 ///
@@ -69,20 +86,71 @@ pub trait RenderBackend {
     /// - `Err(...)`: 渲染失败（如设备丢失、交换链过期等）
     fn draw(&mut self) -> Result<()>;
 
+    /// 渲染一帧画面并返回帧统计
+    ///
+    /// 后端应在记录命令时累计绘制调用和三角形数量。
+    ///
+    /// # 默认实现
+    ///
+    /// 调用 `draw()` 并只记录 CPU 耗时，绘制调用和三角形数量为 0。
+    fn draw_with_stats(&mut self) -> Result<FrameStats> {
+        let start = Instant::now();
+        self.draw()?;
+
+        let mut stats = FrameStats::default();
+        stats.finish(start);
+        Ok(stats)
+    }
+
     /// 更新渲染器状态
     ///
-    /// 在每帧渲染前调用，用于：
+    /// 在每帧渲染前调用一次，用于：
     /// - 处理用户输入
     /// - 更新相机位置和方向
-    /// - 更新动画状态
     /// - 更新 uniform buffer 等
     ///
+    /// 动画等需要与帧率无关的逻辑放在 [`fixed_update`](Self::fixed_update) 中。
+    ///
     /// # 参数
     ///
     /// * `input_system` - 输入系统的可变引用
     /// * `delta_time` - 距离上一帧的时间间隔（秒）
     fn update(&mut self, input_system: &mut InputSystem, delta_time: f32);
 
+    /// 按固定步长推进逻辑状态
+    ///
+    /// 主循环按配置的逻辑更新频率调用，每帧可能调用零次或多次，用于推进骨骼动画等
+    /// 需要与帧率无关的状态。
+    ///
+    /// # 参数
+    ///
+    /// * `delta_time` - 更新步长（秒）
+    ///
+    /// # 默认实现
+    ///
+    /// 默认不处理，没有动画的后端不需要重写。
+    fn fixed_update(&mut self, _delta_time: f32) {}
+
+    /// 每帧的时钟事件
+    ///
+    /// 主循环在绘制前调用（暂停时也调用），用于按运行时间驱动的效果，
+    /// 如清除颜色循环（[`SceneConfig::clear_color_cycle`](crate::core::SceneConfig::clear_color_cycle)）。
+    ///
+    /// # 默认实现
+    ///
+    /// 默认不处理。
+    fn tick(&mut self, _event: &TickEvent) {}
+
+    /// 仅更新相机
+    ///
+    /// 渲染循环暂停时调用，只处理相机输入，不推进其他状态。
+    ///
+    /// # 参数
+    ///
+    /// * `input_system` - 输入系统的可变引用
+    /// * `delta_time` - 距离上一帧的时间间隔（秒）
+    fn update_camera(&mut self, input_system: &mut InputSystem, delta_time: f32);
+
     /// 应用 GUI 参数包
     ///
     /// 当使用外部 GUI 进程时，通过共享内存传递参数。
@@ -109,8 +177,91 @@ pub trait RenderBackend {
     /// # 默认实现
     ///
     /// 默认返回 `false`，表示不处理 GUI 事件。
-    /// 只有 wgpu 后端需要重写此方法。
+    /// 带内置 GUI 的后端（wgpu、Vulkan、DX12、Metal）都重写了此方法。
     fn handle_gui_event(&mut self, _event: &WindowEvent) -> bool {
         false // 默认不处理
     }
+
+    /// 启用或禁用内置 GUI
+    ///
+    /// 禁用后不再绘制 GUI，也不再消费窗口事件，用于基准测试等不希望 GUI 影响结果的场合。
+    ///
+    /// # 默认实现
+    ///
+    /// 默认不做任何事，没有内置 GUI 的后端无需重写。
+    fn set_gui_enabled(&mut self, _enabled: bool) {}
+
+    /// 读回上一帧的深度缓冲
+    ///
+    /// 返回按行优先排列的线性深度（到相机的视空间距离），没有几何的像素为远裁剪面距离。
+    /// 主要用于测试和调试深度相关的功能，会等待 GPU 完成之前提交的所有工作。
+    ///
+    /// # 默认实现
+    ///
+    /// 默认返回错误，表示后端不支持深度读回。目前只有 wgpu 后端实现。
+    fn read_depth(&mut self) -> Result<Vec<f32>> {
+        Err(GraphicsError::CommandExecution("Depth readback is not supported by this backend".to_string()).into())
+    }
+
+    /// 绘制一帧并截取画面
+    ///
+    /// `rect` 为像素单位的裁剪矩形 `(x, y, width, height)`，超出帧缓冲的部分会被截掉，
+    /// 为 `None` 时截取整帧。返回裁剪后的 RGBA8 图像，可用于生成模型缩略图。
+    ///
+    /// # 默认实现
+    ///
+    /// 默认返回错误，表示后端不支持截图。目前只有 wgpu 后端实现。
+    fn capture_region(&mut self, _rect: Option<(u32, u32, u32, u32)>) -> Result<RgbaImage> {
+        Err(GraphicsError::CommandExecution("Frame capture is not supported by this backend".to_string()).into())
+    }
+
+    /// 设置截图时是否隐藏 GUI
+    ///
+    /// 开启时 [`capture_region`](Self::capture_region) 截取的那一帧不绘制 GUI，得到干净的画面。
+    ///
+    /// # 默认实现
+    ///
+    /// 默认不做任何事，不支持截图的后端无需重写。
+    fn set_capture_hides_gui(&mut self, _hide: bool) {}
+
+    /// 从磁盘重新加载场景模型
+    ///
+    /// 重新读取所有模型文件并重建顶点和索引缓冲，相机和灯光等状态保持不变。
+    /// 之前加载成功的模型这次加载失败时返回错误，并继续使用当前的网格。
+    ///
+    /// # 默认实现
+    ///
+    /// 默认返回错误，表示后端不支持重新加载模型。
+    fn reload_models(&mut self) -> Result<()> {
+        Err(GraphicsError::CommandExecution("Model reloading is not supported by this backend".to_string()).into())
+    }
+
+    /// 获取内置 GUI 的参数包
+    ///
+    /// 对于内置 GUI 的后端（如 wgpu + egui），返回当前 GUI 状态，
+    /// 供主循环读取暂停、单步等调试控制。
+    ///
+    /// # 默认实现
+    ///
+    /// 默认返回 `None`，表示没有内置 GUI。
+    fn gui_packet(&self) -> Option<GuiStatePacket> {
+        None
+    }
+
+    /// 获取呈现统计
+    ///
+    /// 返回已呈现和丢弃的帧数以及当前的呈现模式，供 GUI 和基准测试报告实际的垂直同步和丢帧情况。
+    ///
+    /// # 默认实现
+    ///
+    /// 默认返回全零的统计（呈现模式未知）。
+    fn present_stats(&self) -> PresentStats {
+        PresentStats::default()
+    }
+
+    /// 等待 GPU 空闲
+    ///
+    /// 委托给图形后端的 [`GraphicsBackend::wait_idle`](crate::gfx::GraphicsBackend::wait_idle)，
+    /// 在销毁渲染器或切换后端前调用。
+    fn wait_idle(&self) -> Result<()>;
 }
@@ -0,0 +1,113 @@
+//! 剖切平面
+//!
+//! 最多两个用户定义的平面，丢弃位于平面负侧（法线反方向）的片段，用于查看模型内部的剖面。
+//! 平面按 [`Plane`] 的方程 `dot(normal, p) + d` 传入着色器，未启用的平面传入 `(0, 0, 0, 1)`，
+//! 对任何点的距离都为 1，不会裁剪。wgpu 后端在片段着色器中丢弃。
+
+use crate::math::frustum::Plane;
+use crate::math::Vector3;
+
+/// 剖切平面的最大数量
+pub const MAX_CLIP_PLANES: usize = 2;
+
+/// 未启用的平面在着色器中的系数
+const DISABLED_PLANE: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+/// 单个剖切平面
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipPlane {
+    pub enabled: bool,
+    /// 保留一侧的方向（不需要归一化）
+    pub normal: [f32; 3],
+    /// 平面沿法线到原点的距离，保留 `dot(normal, p) >= offset` 的点
+    pub offset: f32,
+}
+
+impl ClipPlane {
+    /// 启用时的平面，未启用或法线为零时为 `None`
+    pub fn plane(&self) -> Option<Plane> {
+        let normal = Vector3::from(self.normal);
+        let length = normal.norm();
+        (self.enabled && length > 0.0).then(|| Plane::new(normal / length, -self.offset))
+    }
+}
+
+impl Default for ClipPlane {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            normal: [1.0, 0.0, 0.0],
+            offset: 0.0,
+        }
+    }
+}
+
+/// 所有剖切平面
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ClipPlanes {
+    pub planes: [ClipPlane; MAX_CLIP_PLANES],
+}
+
+impl ClipPlanes {
+    /// 着色器中的平面系数 `(normal, d)`
+    pub fn to_uniform(&self) -> [[f32; 4]; MAX_CLIP_PLANES] {
+        self.planes.map(|plane| match plane.plane() {
+            Some(plane) => [plane.normal.x, plane.normal.y, plane.normal.z, plane.d],
+            None => DISABLED_PLANE,
+        })
+    }
+
+    /// 世界空间中的点是否被裁剪（与着色器中的判断一致）
+    pub fn is_clipped(&self, point: &Vector3) -> bool {
+        self.planes
+            .iter()
+            .filter_map(ClipPlane::plane)
+            .any(|plane| plane.signed_distance(point) < 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::primitives::{uv_sphere, Winding};
+
+    #[test]
+    fn test_plane_hides_half_of_centered_sphere() {
+        let sphere = uv_sphere(1.0, 32, 16, Winding::Ccw);
+        let mut clip = ClipPlanes::default();
+        let hidden = |clip: &ClipPlanes| {
+            sphere.vertices.iter().filter(|v| clip.is_clipped(&Vector3::from(v.position))).count()
+        };
+        assert_eq!(hidden(&clip), 0);
+        assert_eq!(clip.to_uniform(), [DISABLED_PLANE; MAX_CLIP_PLANES]);
+
+        // 过球心、法线朝 +X 的平面隐藏 x < 0 的一半
+        clip.planes[0] = ClipPlane {
+            enabled: true,
+            normal: [2.0, 0.0, 0.0],
+            offset: 0.0,
+        };
+        for vertex in &sphere.vertices {
+            let position = Vector3::from(vertex.position);
+            assert_eq!(clip.is_clipped(&position), position.x < 0.0);
+        }
+        assert_eq!(clip.to_uniform()[0], [1.0, 0.0, 0.0, 0.0]);
+
+        // 平面沿法线移动后隐藏的部分变多，移到球外时全部隐藏
+        let half = hidden(&clip);
+        clip.planes[0].offset = 0.5;
+        assert!(hidden(&clip) > half);
+        clip.planes[0].offset = 1.5;
+        assert_eq!(hidden(&clip), sphere.vertices.len());
+
+        // 第二个平面叠加裁剪
+        clip.planes[0].offset = 0.0;
+        clip.planes[1] = ClipPlane {
+            enabled: true,
+            normal: [0.0, 1.0, 0.0],
+            offset: 0.0,
+        };
+        assert!(clip.is_clipped(&Vector3::new(0.5, -0.5, 0.0)));
+        assert!(!clip.is_clipped(&Vector3::new(0.5, 0.5, 0.0)));
+    }
+}
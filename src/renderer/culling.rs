@@ -0,0 +1,119 @@
+//! 实例剔除模块
+//!
+//! 在上传实例数据之前，使用相机视锥体在 CPU 侧过滤不可见的实例，
+//! 并统计绘制/剔除数量供性能面板显示。
+
+use crate::math::frustum::Frustum;
+use crate::math::{Matrix4, Vector3};
+
+/// 单个实例的数据
+#[derive(Debug, Clone, Copy)]
+pub struct InstanceData {
+    /// 模型矩阵
+    pub model: Matrix4,
+
+    /// 模型空间包围球半径（以模型原点为球心）
+    pub bounding_radius: f32,
+}
+
+impl InstanceData {
+    /// 创建实例数据
+    pub fn new(model: Matrix4, bounding_radius: f32) -> Self {
+        Self { model, bounding_radius }
+    }
+
+    /// 世界空间中的包围球球心
+    pub fn world_center(&self) -> Vector3 {
+        Vector3::new(self.model[(0, 3)], self.model[(1, 3)], self.model[(2, 3)])
+    }
+
+    /// 世界空间中的包围球半径（按最大缩放轴放大）
+    pub fn world_radius(&self) -> f32 {
+        let max_scale = (0..3)
+            .map(|i| self.model.fixed_view::<3, 1>(0, i).norm())
+            .fold(0.0f32, f32::max);
+        self.bounding_radius * max_scale
+    }
+}
+
+/// 剔除统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CullingStats {
+    /// 通过剔除、需要绘制的实例数
+    pub drawn: u32,
+
+    /// 被剔除的实例数
+    pub culled: u32,
+}
+
+/// 使用视锥体剔除实例列表
+///
+/// 每个实例的包围球被扩展为轴对齐包围盒后与视锥体做相交测试。
+///
+/// # 返回
+///
+/// 可见实例列表和剔除统计
+pub fn cull_instances(frustum: &Frustum, instances: &[InstanceData]) -> (Vec<InstanceData>, CullingStats) {
+    let (visible, stats) = cull_instance_indices(frustum, instances);
+    (visible.into_iter().map(|i| instances[i]).collect(), stats)
+}
+
+/// 使用视锥体剔除实例列表，返回可见实例的序号
+///
+/// 与 [`cull_instances`] 相同，但返回序号，便于调用方找到实例对应的绘制资源。
+pub fn cull_instance_indices(frustum: &Frustum, instances: &[InstanceData]) -> (Vec<usize>, CullingStats) {
+    let mut visible = Vec::with_capacity(instances.len());
+    let mut stats = CullingStats::default();
+
+    for (i, instance) in instances.iter().enumerate() {
+        let center = instance.world_center();
+        let extent = Vector3::repeat(instance.world_radius());
+
+        if frustum.intersects_aabb(&(center - extent), &(center + extent)) {
+            visible.push(i);
+            stats.drawn += 1;
+        } else {
+            stats.culled += 1;
+        }
+    }
+
+    (visible, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix;
+
+    #[test]
+    fn test_instances_behind_camera_are_culled() {
+        let view = matrix::look_at(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(0.0, 0.0, -1.0),
+            &Vector3::new(0.0, 1.0, 0.0),
+        );
+        let proj = matrix::perspective(std::f32::consts::FRAC_PI_3, 1.0, 0.1, 100.0);
+        let frustum = Frustum::from_matrix(&(proj * view));
+
+        let instances = [
+            InstanceData::new(matrix::translation(0.0, 0.0, -10.0), 1.0),
+            InstanceData::new(matrix::translation(0.0, 0.0, 10.0), 1.0),
+            InstanceData::new(matrix::translation(2.0, 0.0, 20.0), 1.0),
+        ];
+
+        let (visible, stats) = cull_instances(&frustum, &instances);
+
+        assert_eq!(visible.len(), 1);
+        assert_eq!(stats, CullingStats { drawn: 1, culled: 2 });
+        assert!((visible[0].world_center().z + 10.0).abs() < 1e-5);
+
+        let (visible_indices, _) = cull_instance_indices(&frustum, &instances);
+        assert_eq!(visible_indices, vec![0]);
+    }
+
+    #[test]
+    fn test_world_radius_uses_max_scale() {
+        let instance = InstanceData::new(matrix::scaling(1.0, 3.0, 2.0), 0.5);
+        assert!((instance.world_radius() - 1.5).abs() < 1e-5);
+    }
+}
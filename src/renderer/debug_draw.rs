@@ -0,0 +1,374 @@
+//! 调试绘制模块
+//!
+//! 收集调试线段（网格、坐标轴、包围盒等），并在 CPU 侧将每条线段展开为
+//! 屏幕空间宽度固定的四边形。
+//!
+//! Vulkan 和 wgpu 都不保证支持 `lineWidth > 1`，原生线段在多数设备上被限制为 1 像素，
+//! 因此调试线段统一以三角形的形式绘制，线宽以像素为单位，在各后端表现一致。
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::math::frustum::Frustum;
+use crate::math::matrix::normal_matrix;
+use crate::math::{Matrix3, Matrix4, Vector2, Vector3, Vector4};
+use crate::renderer::resources::vertex::MyVertex;
+
+/// 默认线宽（像素）
+pub const DEFAULT_LINE_WIDTH: f32 = 2.0;
+
+/// 视锥体近平面边框的颜色
+pub const FRUSTUM_NEAR_COLOR: [f32; 3] = [0.0, 1.0, 0.0];
+
+/// 视锥体远平面边框的颜色
+pub const FRUSTUM_FAR_COLOR: [f32; 3] = [1.0, 0.0, 0.0];
+
+/// 视锥体侧棱（连接近、远平面）的颜色
+pub const FRUSTUM_EDGE_COLOR: [f32; 3] = [1.0, 1.0, 0.0];
+
+/// 法线可视化中法线线段的颜色
+pub const NORMAL_COLOR: [f32; 3] = [0.0, 1.0, 0.0];
+
+/// 法线可视化中切线线段的颜色
+pub const TANGENT_COLOR: [f32; 3] = [1.0, 0.0, 0.0];
+
+/// 近平面裁剪使用的最小 w 值
+const MIN_CLIP_W: f32 = 1e-4;
+
+/// 调试线段
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugLine {
+    /// 世界空间起点
+    pub start: Vector3,
+
+    /// 世界空间终点
+    pub end: Vector3,
+
+    /// 线段颜色（RGB）
+    pub color: [f32; 3],
+}
+
+/// 法线可视化设置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalLines {
+    /// 线段的世界空间长度
+    pub length: f32,
+
+    /// 每隔多少个顶点绘制一个（密集网格用于减少线段数量），0 按 1 处理
+    pub stride: u32,
+
+    /// 是否同时绘制切线
+    pub tangents: bool,
+}
+
+impl Default for NormalLines {
+    fn default() -> Self {
+        Self {
+            length: 0.1,
+            stride: 1,
+            tangents: false,
+        }
+    }
+}
+
+/// 调试绘制顶点
+///
+/// 位置已经是裁剪空间坐标，顶点着色器直接输出即可。
+#[repr(C)]
+#[derive(Default, Clone, Copy, Debug, Pod, Zeroable)]
+pub struct DebugVertex {
+    pub position: [f32; 4],
+    pub color: [f32; 3],
+}
+
+/// 调试绘制器
+#[derive(Debug, Clone)]
+pub struct DebugDraw {
+    lines: Vec<DebugLine>,
+    line_width: f32,
+}
+
+impl DebugDraw {
+    /// 创建调试绘制器（使用默认线宽）
+    pub fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+            line_width: DEFAULT_LINE_WIDTH,
+        }
+    }
+
+    /// 线宽（像素）
+    pub fn line_width(&self) -> f32 {
+        self.line_width
+    }
+
+    /// 设置线宽（像素），小于 1 的值按 1 处理
+    pub fn set_line_width(&mut self, width: f32) {
+        self.line_width = width.max(1.0);
+    }
+
+    /// 添加一条线段
+    pub fn line(&mut self, start: Vector3, end: Vector3, color: [f32; 3]) {
+        self.lines.push(DebugLine { start, end, color });
+    }
+
+    /// 添加视锥体线框
+    ///
+    /// 近平面边框为绿色，远平面边框为红色，四条侧棱为黄色，共 12 条线段。
+    pub fn frustum(&mut self, frustum: &Frustum) {
+        let corners = frustum.corners();
+        for i in 0..4 {
+            let next = (i + 1) % 4;
+            self.line(corners[i], corners[next], FRUSTUM_NEAR_COLOR);
+            self.line(corners[i + 4], corners[next + 4], FRUSTUM_FAR_COLOR);
+            self.line(corners[i], corners[i + 4], FRUSTUM_EDGE_COLOR);
+        }
+    }
+
+    /// 添加从顶点出发沿法线（和切线）方向的线段，用于检查导入或重新计算的法线
+    ///
+    /// 法线为绿色，切线为红色；切线为零向量的顶点不绘制切线。`tangents` 与 `vertices`
+    /// 一一对应，`model` 为模型矩阵（法线按其逆转置变换，非均匀缩放下仍垂直于表面）。
+    pub fn vertex_normals(&mut self, model: &Matrix4, vertices: &[MyVertex], tangents: &[[f32; 3]], settings: &NormalLines) {
+        let linear: Matrix3 = model.fixed_view::<3, 3>(0, 0).into_owned();
+        // 与着色器使用相同的法线矩阵
+        let normal_transform: Matrix3 = normal_matrix(model).fixed_view::<3, 3>(0, 0).into_owned();
+
+        let stride = settings.stride.max(1) as usize;
+        for (i, vertex) in vertices.iter().enumerate().step_by(stride) {
+            let start = model.transform_point(&vertex.position.into()).coords;
+            if let Some(normal) = (normal_transform * Vector3::from(vertex.normal)).try_normalize(1e-6) {
+                self.line(start, start + normal * settings.length, NORMAL_COLOR);
+            }
+            if !settings.tangents {
+                continue;
+            }
+            let tangent = tangents.get(i).map(|tangent| linear * Vector3::from(*tangent));
+            if let Some(tangent) = tangent.and_then(|tangent| tangent.try_normalize(1e-6)) {
+                self.line(start, start + tangent * settings.length, TANGENT_COLOR);
+            }
+        }
+    }
+
+    /// 已添加的线段
+    pub fn lines(&self) -> &[DebugLine] {
+        &self.lines
+    }
+
+    /// 是否没有线段
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// 清空所有线段（通常每帧调用一次）
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// 生成三角形几何
+    ///
+    /// 每条可见线段展开为 4 个顶点、2 个三角形。完全位于相机后方的线段被丢弃，
+    /// 跨越相机平面的线段会先被裁剪。
+    ///
+    /// # 参数
+    ///
+    /// - `view_proj`: 视图投影矩阵（`projection * view`）
+    /// - `viewport`: 视口尺寸（像素）
+    ///
+    /// # 返回
+    ///
+    /// 顶点和三角形列表索引
+    pub fn build_geometry(&self, view_proj: &Matrix4, viewport: [f32; 2]) -> (Vec<DebugVertex>, Vec<u32>) {
+        let mut vertices = Vec::with_capacity(self.lines.len() * 4);
+        let mut indices = Vec::with_capacity(self.lines.len() * 6);
+
+        for line in &self.lines {
+            let start = view_proj * line.start.push(1.0);
+            let end = view_proj * line.end.push(1.0);
+
+            let Some(quad) = expand_line(start, end, self.line_width, viewport) else {
+                continue;
+            };
+
+            let base = vertices.len() as u32;
+            vertices.extend(quad.iter().map(|p| DebugVertex {
+                position: [p.x, p.y, p.z, p.w],
+                color: line.color,
+            }));
+            indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 1, base + 3]);
+        }
+
+        (vertices, indices)
+    }
+}
+
+impl Default for DebugDraw {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 将裁剪空间中的线段展开为屏幕空间宽度为 `width` 像素的四边形
+///
+/// 顶点顺序：起点两侧、终点两侧。偏移量乘以各自的 w，使透视除法后宽度保持恒定。
+///
+/// # 返回
+///
+/// 线段完全位于相机后方时返回 `None`
+pub fn expand_line(start: Vector4, end: Vector4, width: f32, viewport: [f32; 2]) -> Option<[Vector4; 4]> {
+    let (start, end) = clip_to_near(start, end)?;
+
+    let half_viewport = Vector2::new(viewport[0] * 0.5, viewport[1] * 0.5);
+    let screen_start = start.xy().component_mul(&half_viewport) / start.w;
+    let screen_end = end.xy().component_mul(&half_viewport) / end.w;
+
+    // 屏幕空间方向的法向量；退化线段沿 x 方向展开
+    let direction = (screen_end - screen_start)
+        .try_normalize(1e-6)
+        .unwrap_or_else(Vector2::x);
+    let normal = Vector2::new(-direction.y, direction.x);
+
+    // 半宽（像素）转换为 NDC 偏移
+    let offset = (normal * (width * 0.5)).component_div(&half_viewport);
+
+    let displace = |p: &Vector4, sign: f32| {
+        Vector4::new(p.x + sign * offset.x * p.w, p.y + sign * offset.y * p.w, p.z, p.w)
+    };
+
+    Some([
+        displace(&start, -1.0),
+        displace(&start, 1.0),
+        displace(&end, -1.0),
+        displace(&end, 1.0),
+    ])
+}
+
+/// 将线段裁剪到 w > 0 的一侧，避免透视除法翻转
+fn clip_to_near(start: Vector4, end: Vector4) -> Option<(Vector4, Vector4)> {
+    match (start.w >= MIN_CLIP_W, end.w >= MIN_CLIP_W) {
+        (true, true) => Some((start, end)),
+        (false, false) => None,
+        (start_inside, _) => {
+            let t = (MIN_CLIP_W - start.w) / (end.w - start.w);
+            let clipped = start + (end - start) * t;
+            if start_inside {
+                Some((start, clipped))
+            } else {
+                Some((clipped, end))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix;
+
+    fn view_proj() -> Matrix4 {
+        let view = matrix::look_at(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(0.0, 0.0, -1.0),
+            &Vector3::new(0.0, 1.0, 0.0),
+        );
+        let proj = matrix::perspective(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+        proj * view
+    }
+
+    fn to_screen(p: &[f32; 4], viewport: [f32; 2]) -> Vector2 {
+        Vector2::new(p[0] / p[3] * viewport[0] * 0.5, p[1] / p[3] * viewport[1] * 0.5)
+    }
+
+    #[test]
+    fn test_segment_expands_to_two_triangles() {
+        let viewport = [800.0, 800.0];
+        let mut debug_draw = DebugDraw::new();
+        debug_draw.set_line_width(4.0);
+        debug_draw.line(Vector3::new(0.0, 0.0, -5.0), Vector3::new(1.0, 0.0, -5.0), [1.0, 0.0, 0.0]);
+
+        let (vertices, indices) = debug_draw.build_geometry(&view_proj(), viewport);
+
+        assert_eq!(vertices.len(), 4);
+        assert_eq!(indices.len() / 3, 2);
+
+        // 水平线段在屏幕上的厚度为 4 像素
+        for (a, b) in [(0, 1), (2, 3)] {
+            let thickness = (to_screen(&vertices[b].position, viewport)
+                - to_screen(&vertices[a].position, viewport))
+                .norm();
+            assert!((thickness - 4.0).abs() < 1e-3, "thickness = {}", thickness);
+        }
+
+        // 屏幕空间长度不受影响：1 单位 / 距离 5 / tan(45°) * 400 = 80 像素
+        let length = (to_screen(&vertices[2].position, viewport)
+            - to_screen(&vertices[0].position, viewport))
+            .norm();
+        assert!((length - 80.0).abs() < 1e-2, "length = {}", length);
+    }
+
+    #[test]
+    fn test_frustum_outline_colors() {
+        let mut debug_draw = DebugDraw::new();
+        debug_draw.frustum(&Frustum::from_matrix(&view_proj()));
+
+        let lines = debug_draw.lines();
+        assert_eq!(lines.len(), 12);
+        for color in [FRUSTUM_NEAR_COLOR, FRUSTUM_FAR_COLOR, FRUSTUM_EDGE_COLOR] {
+            assert_eq!(lines.iter().filter(|line| line.color == color).count(), 4);
+        }
+        // 近平面边框位于 z = -near，远平面边框位于 z = -far
+        for (color, z) in [(FRUSTUM_NEAR_COLOR, -0.1f32), (FRUSTUM_FAR_COLOR, -100.0)] {
+            for line in lines.iter().filter(|line| line.color == color) {
+                assert!((line.start.z - z).abs() < 1e-3 * z.abs());
+                assert!((line.end.z - z).abs() < 1e-3 * z.abs());
+            }
+        }
+    }
+
+    #[test]
+    fn test_vertex_normals_follow_model_transform() {
+        let vertices: Vec<MyVertex> = (0..4)
+            .map(|i| MyVertex::new(i as f32, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0))
+            .collect();
+        let tangents = [[1.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0; 3], [1.0, 0.0, 0.0]];
+        // 平移并沿 y 轴放大两倍：法线方向不变，长度按设置
+        let model = Matrix4::new_translation(&Vector3::new(0.0, 0.0, -5.0)) * Matrix4::new_nonuniform_scaling(&Vector3::new(1.0, 2.0, 1.0));
+        let settings = NormalLines {
+            length: 0.5,
+            stride: 2,
+            tangents: true,
+        };
+
+        let mut debug_draw = DebugDraw::new();
+        debug_draw.vertex_normals(&model, &vertices, &tangents, &settings);
+
+        // 只绘制第 0、2 个顶点，第 2 个顶点没有切线
+        let lines = debug_draw.lines();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].color, NORMAL_COLOR);
+        assert_eq!(lines[0].start, Vector3::new(0.0, 0.0, -5.0));
+        assert!((lines[0].end - Vector3::new(0.0, 0.5, -5.0)).norm() < 1e-5);
+        assert_eq!(lines[1].color, TANGENT_COLOR);
+        assert!((lines[1].end - Vector3::new(0.5, 0.0, -5.0)).norm() < 1e-5);
+        assert_eq!((lines[2].start, lines[2].color), (Vector3::new(2.0, 0.0, -5.0), NORMAL_COLOR));
+    }
+
+    #[test]
+    fn test_segment_behind_camera_is_dropped() {
+        let mut debug_draw = DebugDraw::new();
+        debug_draw.line(Vector3::new(0.0, 0.0, 5.0), Vector3::new(1.0, 0.0, 5.0), [1.0, 1.0, 1.0]);
+
+        let (vertices, indices) = debug_draw.build_geometry(&view_proj(), [800.0, 600.0]);
+
+        assert!(vertices.is_empty());
+        assert!(indices.is_empty());
+    }
+
+    #[test]
+    fn test_segment_crossing_camera_is_clipped() {
+        let start = view_proj() * Vector4::new(0.0, 0.0, -5.0, 1.0);
+        let end = view_proj() * Vector4::new(0.0, 0.0, 5.0, 1.0);
+
+        let quad = expand_line(start, end, 2.0, [800.0, 600.0]).unwrap();
+        assert!(quad.iter().all(|p| p.w > 0.0));
+    }
+}
@@ -0,0 +1,81 @@
+//! 三角形填充模式
+//!
+//! 后端无关的填充模式选择。填充模式属于光栅化状态，各后端为每种支持的模式
+//! 预先创建一份场景管线，绘制时按当前模式选择，切换不需要重建任何资源。
+
+use crate::gfx::capabilities::DeviceCapabilities;
+
+/// 三角形填充模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillMode {
+    /// 填充三角形
+    #[default]
+    Solid,
+    /// 只绘制三角形的边（需要设备支持线框填充）
+    Wireframe,
+    /// 只绘制三角形的顶点（需要设备支持点填充）
+    Point,
+}
+
+impl FillMode {
+    /// 所有模式，按 GUI 中的显示顺序
+    pub const ALL: [FillMode; 3] = [FillMode::Solid, FillMode::Wireframe, FillMode::Point];
+
+    /// 从参数包中的序号解析，越界时返回默认模式
+    pub fn from_index(index: u32) -> Self {
+        Self::ALL.get(index as usize).copied().unwrap_or_default()
+    }
+
+    /// 在 [`ALL`](Self::ALL) 中的序号
+    pub fn index(self) -> u32 {
+        self as u32
+    }
+
+    /// GUI 中显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            FillMode::Solid => "Solid",
+            FillMode::Wireframe => "Wireframe",
+            FillMode::Point => "Point",
+        }
+    }
+
+    /// 设备是否支持该模式
+    pub fn is_supported(self, capabilities: &DeviceCapabilities) -> bool {
+        match self {
+            FillMode::Solid => true,
+            FillMode::Wireframe => capabilities.polygon_mode_line,
+            FillMode::Point => capabilities.polygon_mode_point,
+        }
+    }
+
+    /// 设备不支持时退回实心填充
+    pub fn or_supported(self, capabilities: &DeviceCapabilities) -> Self {
+        if self.is_supported(capabilities) {
+            self
+        } else {
+            FillMode::Solid
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_modes_fall_back_to_solid() {
+        for mode in FillMode::ALL {
+            assert_eq!(FillMode::from_index(mode.index()), mode);
+        }
+        assert_eq!(FillMode::from_index(99), FillMode::Solid);
+
+        let caps = DeviceCapabilities {
+            polygon_mode_line: true,
+            ..Default::default()
+        };
+        assert_eq!(FillMode::Wireframe.or_supported(&caps), FillMode::Wireframe);
+        assert_eq!(FillMode::Point.or_supported(&caps), FillMode::Solid);
+        assert!(FillMode::Solid.is_supported(&DeviceCapabilities::default()));
+    }
+}
@@ -17,7 +17,8 @@
 //! - **性能**：虚函数调用开销可忽略（通常 < 1ns）
 //! - **可维护性**：更符合开闭原则，代码更简洁
 
-use tracing::info;
+use image::RgbaImage;
+use tracing::{info, warn};
 use winit::event_loop::EventLoop;
 
 use crate::core::error::Result;
@@ -33,10 +34,19 @@ use crate::gui::ipc::GuiStatePacket;
 // 通用渲染器组件（与具体 API 无关）
 pub mod resources;  // 资源相关：vertex, resource, descriptor
 pub mod commands;   // 命令相关：command, sync
+pub mod axis_indicator; // 屏幕角落的坐标轴指示器
 pub mod backend_trait;
+pub mod clip_planes; // 剖切平面（丢弃平面负侧的片段）
+pub mod culling;     // 视锥体实例剔除
+pub mod debug_draw;  // 调试线段绘制
+pub mod fill_mode;   // 三角形填充模式（实心、线框、点）
+pub mod software;    // CPU 软件光栅化（无 GPU 时验证变换和剔除）
+pub mod stencil;     // 模板遮罩（先标记区域，再限制场景绘制）
+pub mod stats;       // 帧统计（绘制调用、三角形数、CPU 耗时）
 
 // 重新导出 trait
 pub use backend_trait::RenderBackend;
+pub use stats::{FrameStats, PresentStats};
 
 /// 渲染器
 ///
@@ -127,9 +137,9 @@ impl Renderer {
     ///
     /// # 返回值
     ///
-    /// 成功时返回 `Ok(())`，失败时返回错误
-    pub fn draw(&mut self) -> Result<()> {
-        self.backend.draw()
+    /// 成功时返回本帧的统计数据，失败时返回错误
+    pub fn draw(&mut self) -> Result<FrameStats> {
+        self.backend.draw_with_stats()
     }
 
     /// 更新渲染器状态
@@ -144,6 +154,32 @@ impl Renderer {
         self.backend.update(input_system, delta_time)
     }
 
+    /// 按固定步长推进动画等逻辑状态
+    ///
+    /// # 参数
+    ///
+    /// * `delta_time` - 更新步长（秒）
+    pub fn fixed_update(&mut self, delta_time: f32) {
+        self.backend.fixed_update(delta_time)
+    }
+
+    /// 传递每帧的时钟事件（暂停时也调用）
+    pub fn tick(&mut self, event: &crate::core::event::TickEvent) {
+        self.backend.tick(event)
+    }
+
+    /// 仅更新相机
+    ///
+    /// 渲染循环暂停时调用，只处理相机输入。
+    ///
+    /// # 参数
+    ///
+    /// * `input_system` - 输入系统的可变引用
+    /// * `delta_time` - 距离上一帧的时间间隔（秒）
+    pub fn update_camera(&mut self, input_system: &mut crate::core::input::InputSystem, delta_time: f32) {
+        self.backend.update_camera(input_system, delta_time)
+    }
+
     /// 获取窗口引用
     ///
     /// # 返回值
@@ -179,4 +215,57 @@ impl Renderer {
     pub fn handle_gui_event(&mut self, event: &winit::event::WindowEvent) -> bool {
         self.backend.handle_gui_event(event)
     }
+
+    /// 启用或禁用内置 GUI（基准测试时禁用）
+    pub fn set_gui_enabled(&mut self, enabled: bool) {
+        self.backend.set_gui_enabled(enabled)
+    }
+
+    /// 读回上一帧的线性深度（按行优先排列），后端不支持时返回错误
+    pub fn read_depth(&mut self) -> Result<Vec<f32>> {
+        self.backend.read_depth()
+    }
+
+    /// 绘制一帧并截取画面，`rect` 为裁剪矩形 `(x, y, width, height)`，后端不支持时返回错误
+    pub fn capture_region(&mut self, rect: Option<(u32, u32, u32, u32)>) -> Result<RgbaImage> {
+        self.backend.capture_region(rect)
+    }
+
+    /// 设置截图时是否隐藏 GUI
+    pub fn set_capture_hides_gui(&mut self, hide: bool) {
+        self.backend.set_capture_hides_gui(hide)
+    }
+
+    /// 从磁盘重新加载场景模型，失败时继续使用当前的网格并返回错误
+    pub fn reload_models(&mut self) -> Result<()> {
+        self.backend.reload_models()
+    }
+
+    /// 获取内置 GUI 的参数包
+    ///
+    /// # 返回值
+    ///
+    /// 内置 GUI 的后端返回 `Some(packet)`，否则返回 `None`
+    pub fn gui_packet(&self) -> Option<GuiStatePacket> {
+        self.backend.gui_packet()
+    }
+
+    /// 获取呈现统计（已呈现和丢弃的帧数、呈现模式）
+    pub fn present_stats(&self) -> PresentStats {
+        self.backend.present_stats()
+    }
+
+    /// 等待 GPU 完成所有已提交的工作
+    pub fn wait_idle(&self) -> Result<()> {
+        self.backend.wait_idle()
+    }
+}
+
+impl Drop for Renderer {
+    fn drop(&mut self) {
+        // 后端资源释放前必须确保 GPU 不再使用它们
+        if let Err(e) = self.backend.wait_idle() {
+            warn!("Failed to wait for GPU idle before teardown: {}", e);
+        }
+    }
 }
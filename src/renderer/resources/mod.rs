@@ -2,14 +2,26 @@
 //!
 //! 包含与渲染资源相关的所有类型和功能：
 //! - 顶点数据结构
+//! - 顶点属性布局（各后端据此生成输入描述）
 //! - 资源池管理
 //! - 描述符分配器
+//! - 场景网格（多个模型合并到同一组缓冲）
+//! - 纹理过滤模式
+//! - GPU 资源占用统计
 
 pub mod vertex;
+pub mod vertex_layout;
 pub mod resource;
 pub mod descriptor;
+pub mod scene_mesh;
+pub mod sampler;
+pub mod resource_stats;
 
 // 重新导出常用类型
 pub use vertex::{MyVertex, GeometryVertex};
+pub use vertex_layout::{HasVertexLayout, VertexLayout};
 pub use resource::FrameResourcePool;
 pub use descriptor::DescriptorAllocator;
+pub use scene_mesh::{ModelMesh, SceneMesh};
+pub use sampler::TextureFilter;
+pub use resource_stats::ResourceStats;
@@ -297,9 +297,9 @@ impl FrameResourcePool {
     ///
     /// # 参数
     ///
-    /// * `count` - 帧资源数量（通常为2或3）
+    /// * `count` - 帧资源数量（通常为2或3，低延迟模式下为1）
     pub fn new(count: usize) -> Self {
-        assert!(count >= 2, "At least 2 frame resources required");
+        assert!(count >= 1, "At least 1 frame resource required");
 
         let resources = (0..count)
             .map(|i| FrameResource::new(i))
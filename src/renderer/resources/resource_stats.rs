@@ -0,0 +1,159 @@
+//! GPU 资源统计
+//!
+//! 按类别累计顶点、索引、Uniform 缓冲和纹理占用的字节数与分配数量，
+//! 与 [`DescriptorAllocator`](super::DescriptorAllocator) 统计描述符数量的思路相同。
+//!
+//! 资源通过 [`ResourceStats::track`] 包装为 [`Tracked`]，创建时计入统计，
+//! 释放（drop）时自动扣除，后端无需手动维护计数。
+
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// 资源类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    /// 顶点缓冲（包括蒙皮和材质等附加顶点流）
+    Vertex,
+    /// 索引缓冲
+    Index,
+    /// Uniform / 常量缓冲
+    Uniform,
+    /// 纹理（包括深度缓冲）
+    Texture,
+}
+
+impl ResourceKind {
+    /// 所有类别（GUI 按此顺序显示）
+    pub const ALL: [Self; 4] = [Self::Vertex, Self::Index, Self::Uniform, Self::Texture];
+
+    /// 显示名称
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Vertex => "Vertex Buffers",
+            Self::Index => "Index Buffers",
+            Self::Uniform => "Uniform Buffers",
+            Self::Texture => "Textures",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// 某一类资源的占用
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceUsage {
+    /// 总字节数
+    pub bytes: u64,
+    /// 分配数量
+    pub count: u32,
+}
+
+#[derive(Debug, Default)]
+struct Counter {
+    bytes: AtomicU64,
+    count: AtomicU32,
+}
+
+/// 资源统计
+///
+/// 克隆得到的是同一份统计的句柄，可以同时交给后端和 GUI。
+#[derive(Debug, Clone, Default)]
+pub struct ResourceStats {
+    counters: Arc<[Counter; 4]>,
+}
+
+impl ResourceStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 把 `resource` 计入统计，返回的包装释放时扣除
+    pub fn track<T>(&self, resource: T, kind: ResourceKind, bytes: u64) -> Tracked<T> {
+        let counter = &self.counters[kind.index()];
+        counter.bytes.fetch_add(bytes, Ordering::Relaxed);
+        counter.count.fetch_add(1, Ordering::Relaxed);
+        Tracked {
+            resource,
+            stats: self.clone(),
+            kind,
+            bytes,
+        }
+    }
+
+    /// 某一类资源的当前占用
+    pub fn usage(&self, kind: ResourceKind) -> ResourceUsage {
+        let counter = &self.counters[kind.index()];
+        ResourceUsage {
+            bytes: counter.bytes.load(Ordering::Relaxed),
+            count: counter.count.load(Ordering::Relaxed),
+        }
+    }
+
+    /// 所有类别的总占用
+    pub fn total(&self) -> ResourceUsage {
+        ResourceKind::ALL.iter().fold(ResourceUsage::default(), |total, &kind| {
+            let usage = self.usage(kind);
+            ResourceUsage {
+                bytes: total.bytes + usage.bytes,
+                count: total.count + usage.count,
+            }
+        })
+    }
+
+    fn release(&self, kind: ResourceKind, bytes: u64) {
+        let counter = &self.counters[kind.index()];
+        counter.bytes.fetch_sub(bytes, Ordering::Relaxed);
+        counter.count.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// 计入统计的资源，解引用为内部资源
+#[derive(Debug)]
+pub struct Tracked<T> {
+    resource: T,
+    stats: ResourceStats,
+    kind: ResourceKind,
+    bytes: u64,
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.resource
+    }
+}
+
+impl<T> Drop for Tracked<T> {
+    fn drop(&mut self) {
+        self.stats.release(self.kind, self.bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracked_buffer_updates_totals_until_dropped() {
+        let stats = ResourceStats::new();
+        let vertex_buffer = stats.track(vec![0u8; 256], ResourceKind::Vertex, 256);
+        let index_buffer = stats.track(vec![0u8; 64], ResourceKind::Index, 64);
+
+        assert_eq!(vertex_buffer.len(), 256);
+        assert_eq!(stats.usage(ResourceKind::Vertex), ResourceUsage { bytes: 256, count: 1 });
+        assert_eq!(stats.total(), ResourceUsage { bytes: 320, count: 2 });
+
+        // 克隆的句柄共享同一份统计
+        let gui_stats = stats.clone();
+        drop(vertex_buffer);
+        assert_eq!(gui_stats.usage(ResourceKind::Vertex), ResourceUsage::default());
+        assert_eq!(gui_stats.total(), ResourceUsage { bytes: 64, count: 1 });
+
+        drop(index_buffer);
+        assert_eq!(stats.total(), ResourceUsage::default());
+    }
+}
@@ -0,0 +1,78 @@
+//! 纹理采样
+//!
+//! 后端无关的纹理过滤模式。各后端把它转换为自己的采样器描述，
+//! 运行时切换只需重建采样器和引用它的绑定组，不需要重建管线。
+
+use serde::{Deserialize, Serialize};
+
+/// 各向异性过滤的最大采样等级（配置 `graphics.max_anisotropy` 的上限）
+pub const MAX_ANISOTROPY: u16 = 16;
+
+/// 纹理过滤模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextureFilter {
+    /// 最近点采样，mip 级别也取最近的一级（像素风格纹理）
+    Nearest,
+    /// 双线性过滤，mip 级别取最近的一级
+    Bilinear,
+    /// 三线性过滤，相邻 mip 级别之间也做插值
+    Trilinear,
+    /// 三线性过滤加各向异性过滤（倾斜表面更清晰）
+    #[default]
+    Anisotropic,
+}
+
+impl TextureFilter {
+    /// 所有模式，按 GUI 中的显示顺序
+    pub const ALL: [TextureFilter; 4] = [
+        TextureFilter::Nearest,
+        TextureFilter::Bilinear,
+        TextureFilter::Trilinear,
+        TextureFilter::Anisotropic,
+    ];
+
+    /// 设备支持的最佳模式：支持各向异性过滤时使用各向异性，否则使用三线性
+    pub fn preferred(max_anisotropy: f32) -> Self {
+        if max_anisotropy > 1.0 {
+            TextureFilter::Anisotropic
+        } else {
+            TextureFilter::Trilinear
+        }
+    }
+
+    /// 从参数包中的序号解析，越界时返回默认模式
+    pub fn from_index(index: u32) -> Self {
+        Self::ALL.get(index as usize).copied().unwrap_or_default()
+    }
+
+    /// 在 [`ALL`](Self::ALL) 中的序号
+    pub fn index(self) -> u32 {
+        self as u32
+    }
+
+    /// GUI 中显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            TextureFilter::Nearest => "Nearest",
+            TextureFilter::Bilinear => "Bilinear",
+            TextureFilter::Trilinear => "Trilinear",
+            TextureFilter::Anisotropic => "Anisotropic",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_round_trip() {
+        for filter in TextureFilter::ALL {
+            assert_eq!(TextureFilter::from_index(filter.index()), filter);
+        }
+        assert_eq!(TextureFilter::from_index(99), TextureFilter::Anisotropic);
+        assert_eq!(TextureFilter::preferred(1.0), TextureFilter::Trilinear);
+        assert_eq!(TextureFilter::preferred(16.0), TextureFilter::Anisotropic);
+    }
+}
@@ -0,0 +1,1088 @@
+//! 场景网格
+//!
+//! 将场景中的所有模型加载并合并到同一组顶点/索引缓冲中，每个模型记录自己的索引范围，
+//! 各后端按模型逐个设置模型矩阵后绘制对应范围。
+//! 单个模型加载失败或文件不存在时，该模型位置使用场景配置的占位几何体，不影响其他模型。
+//!
+//! 模型配置了 LOD 级别时，各级简化网格也追加到同一组缓冲中，
+//! 绘制时按模型到相机的距离选择使用哪一级的索引范围。
+//!
+//! 带蒙皮的 glTF 模型额外记录每个顶点的关节索引和权重以及骨骼动画，不生成 LOD；
+//! 不支持蒙皮绘制的后端按绑定姿态绘制。
+//!
+//! 带材质的模型（glTF）把基础颜色写入顶点颜色，金属度和粗糙度另存一份与顶点一一对应的数据，
+//! 供 PBR 着色使用；不支持 PBR 的后端只使用顶点颜色。
+//! 子网格的材质可以在运行时修改（重写对应顶点后重新上传），修改过材质的模型不再使用简化级别。
+//! 半透明材质的不透明度也写入材质数据，其子网格由后端在不透明几何之后按从远到近的顺序单独绘制。
+//!
+//! 三角形带（如平面占位几何体）在支持图元重启的后端保持原样，其索引之后用重启索引补齐到
+//! 3 的倍数，保证后续模型的子网格面范围不变；不支持的后端在加载时转换为三角形列表。
+
+use std::ops::Range;
+use std::path::Path;
+
+use tracing::{info, warn};
+
+use crate::core::error::{DistRenderError, MeshLoadError, Result};
+use crate::core::scene::ModelConfig;
+use crate::geometry::material::Material;
+use crate::geometry::mesh::{IndexData, MeshData, PrimitiveTopology, Subset, PRIMITIVE_RESTART_INDEX};
+use crate::geometry::primitives::{PrimitiveShape, Winding};
+use crate::geometry::skinning::{AnimatedSkeleton, SkinnedMesh, MAX_JOINTS};
+use crate::geometry::uv::UvLayout;
+use crate::math::aabb::Aabb;
+use crate::math::{Matrix4, Vector3};
+use crate::renderer::resources::vertex::{
+    convert_geometry_vertex, create_default_triangle, MaterialVertex, MyVertex, SkinVertex,
+};
+
+/// 单个模型在合并缓冲中的范围
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelMesh {
+    /// 起始索引（合并后的索引缓冲）
+    pub index_start: u32,
+
+    /// 索引数量
+    pub index_count: u32,
+
+    /// 原始网格的顶点范围（合并缓冲，不含简化级别）
+    pub vertex_start: u32,
+    pub vertex_count: u32,
+
+    /// 模型空间包围球半径（用于视锥体剔除）
+    pub bounding_radius: f32,
+
+    /// 子网格（顶点和面的起始位置已换算到合并缓冲中）
+    pub subsets: Vec<Subset>,
+
+    /// 各子网格在模型空间中的中心（顶点包围盒的中心），用于半透明子网格排序
+    pub subset_centers: Vec<Vector3>,
+
+    /// 简化级别，按切换距离从近到远排列（不含原始网格）
+    pub lods: Vec<ModelLod>,
+
+    /// 骨骼动画，静态模型为 `None`
+    pub skeleton: Option<AnimatedSkeleton>,
+
+    /// 是否带 PBR 材质（没有材质的模型使用简单着色）
+    pub pbr: bool,
+
+    /// 材质表，子网格 ID 为其中的序号（没有材质的模型为空）
+    pub materials: Vec<Material>,
+
+    /// 子网格材质是否在运行时修改过
+    ///
+    /// 简化级别没有子网格划分，无法同步修改，修改过的模型总是使用原始网格。
+    pub edited: bool,
+
+    /// 原始网格索引的图元拓扑（简化级别总是三角形列表）
+    pub topology: PrimitiveTopology,
+}
+
+/// 单个简化级别在合并缓冲中的范围
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelLod {
+    /// 切换到该级别的最小距离
+    pub distance: f32,
+
+    /// 起始索引（合并后的索引缓冲）
+    pub index_start: u32,
+
+    /// 索引数量
+    pub index_count: u32,
+}
+
+impl ModelMesh {
+    /// 索引范围（原始网格）
+    pub fn index_range(&self) -> Range<u32> {
+        self.index_start..self.index_start + self.index_count
+    }
+
+    /// LOD 级别数量（包含原始网格）
+    pub fn lod_count(&self) -> usize {
+        self.lods.len() + 1
+    }
+
+    /// 按到相机的距离选择 LOD 级别，0 为原始网格（材质修改过的模型总是 0）
+    pub fn select_lod(&self, distance: f32) -> usize {
+        if self.edited {
+            return 0;
+        }
+        self.lods.iter().take_while(|lod| distance >= lod.distance).count()
+    }
+
+    /// 指定 LOD 级别的索引范围，越界时使用最粗糙的级别（没有 LOD 时为完整网格）
+    pub fn lod_range(&self, level: usize) -> Range<u32> {
+        match level.checked_sub(1).and_then(|i| self.lods.get(i).or(self.lods.last())) {
+            Some(lod) => lod.index_start..lod.index_start + lod.index_count,
+            None => self.index_range(),
+        }
+    }
+
+    /// 指定 LOD 级别的索引是否为三角形带
+    pub fn is_strip(&self, level: usize) -> bool {
+        level == 0 && self.topology == PrimitiveTopology::TriangleStrip
+    }
+
+    /// 子网格在合并缓冲中的索引范围（截断到原始网格范围内），序号越界时返回 `None`
+    pub fn subset_indices(&self, subset: usize) -> Option<Range<u32>> {
+        let subset = self.subsets.get(subset)?;
+        let model_range = self.index_range();
+        let start = subset.index_start().clamp(model_range.start, model_range.end);
+        let end = (start + subset.index_count()).min(model_range.end);
+        Some(start..end)
+    }
+
+    /// 子网格的材质是否半透明
+    pub fn is_transparent(&self, subset: usize) -> bool {
+        self.subsets
+            .get(subset)
+            .and_then(|subset| self.materials.get(subset.id as usize))
+            .is_some_and(|material| material.transparent)
+    }
+
+    /// 跳过隐藏子网格后要绘制的索引范围
+    ///
+    /// `hidden` 按子网格在模型中的序号标记是否隐藏（没有子网格的模型整体算作一个）。
+    /// 没有隐藏的子网格时返回 `level` 级别的完整范围；否则使用原始网格，
+    /// 相邻的可见子网格合并为一个范围。
+    pub fn visible_ranges(&self, level: usize, hidden: &[bool]) -> Vec<Range<u32>> {
+        if !hidden.contains(&true) {
+            return vec![self.lod_range(level)];
+        }
+        if self.subsets.is_empty() {
+            return Vec::new();
+        }
+
+        let mut ranges: Vec<Range<u32>> = Vec::new();
+        for i in 0..self.subsets.len() {
+            if hidden.get(i).copied().unwrap_or(false) {
+                continue;
+            }
+            let Some(range) = self.subset_indices(i) else {
+                continue;
+            };
+            match ranges.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => ranges.push(range),
+            }
+        }
+        ranges
+    }
+}
+
+/// 合并后的场景网格
+#[derive(Debug, Clone, Default)]
+pub struct SceneMesh {
+    /// 所有模型的顶点
+    pub vertices: Vec<MyVertex>,
+
+    /// 蒙皮数据，与 `vertices` 一一对应
+    pub skin_vertices: Vec<SkinVertex>,
+
+    /// 材质数据，与 `vertices` 一一对应
+    pub material_vertices: Vec<MaterialVertex>,
+
+    /// 切线，与 `vertices` 一一对应，只在 CPU 侧用于法线可视化（没有切线的顶点为零向量）
+    pub tangents: Vec<[f32; 3]>,
+
+    /// 所有模型的索引（已加上各模型的顶点偏移）
+    pub indices: Vec<u32>,
+
+    /// 各模型的范围，与场景配置中的模型一一对应
+    pub models: Vec<ModelMesh>,
+
+    /// 是否保留三角形带（后端支持图元重启），否则追加时转换为三角形列表
+    pub primitive_restart: bool,
+
+    /// 加载模型时是否使用磁盘缓存（见 [`mesh_cache`](crate::geometry::mesh_cache)）
+    pub mesh_cache: bool,
+
+    /// 加载失败、使用占位几何体的模型序号
+    pub fallback_models: Vec<usize>,
+}
+
+impl SceneMesh {
+    /// 加载场景中的所有模型
+    ///
+    /// 加载失败的模型使用 `fallback` 几何体。场景没有模型时只放入一个不属于任何模型的
+    /// 默认三角形，保证缓冲非空。`primitive_restart` 为 `false` 时三角形带转换为三角形列表，
+    /// `mesh_cache` 为 `true` 时静态模型通过磁盘缓存加载。
+    pub fn load(models: &[ModelConfig], fallback: PrimitiveShape, primitive_restart: bool, mesh_cache: bool) -> Self {
+        Self::load_with_lod(models, fallback, 1.0, 0, primitive_restart, mesh_cache)
+    }
+
+    /// 加载场景中的所有模型，把每个模型简化到约 `lod_ratio` 倍的三角形数量，再细分 `subdivision_levels` 级
+    ///
+    /// `lod_ratio` 为 1.0 且 `subdivision_levels` 为 0 时与 [`load`](Self::load) 相同。
+    /// 简化和细分总是从原始模型开始，多次生成不会累积误差。蒙皮模型不做简化和细分。
+    pub fn load_with_lod(
+        models: &[ModelConfig],
+        fallback: PrimitiveShape,
+        lod_ratio: f32,
+        subdivision_levels: u32,
+        primitive_restart: bool,
+        mesh_cache: bool,
+    ) -> Self {
+        let mut scene_mesh = Self {
+            primitive_restart,
+            mesh_cache,
+            ..Self::default()
+        };
+
+        for (index, model) in models.iter().enumerate() {
+            if let Some(skinned) = load_skinned_model(model) {
+                scene_mesh.push_skinned(skinned);
+                continue;
+            }
+
+            let Some(mesh_data) = load_model(model, lod_ratio, subdivision_levels, mesh_cache) else {
+                scene_mesh.push_fallback(fallback);
+                scene_mesh.fallback_models.push(index);
+                continue;
+            };
+
+            scene_mesh.push_mesh(mesh_data.clone());
+            for lod in model.sorted_lods() {
+                let mut lod_mesh = mesh_data.clone();
+                lod_mesh.decimate(lod.ratio);
+                info!(
+                    "Model LOD at distance {}: {} -> {} triangles",
+                    lod.distance,
+                    mesh_data.triangle_count(),
+                    lod_mesh.triangle_count()
+                );
+                let vertex_offset = scene_mesh.vertices.len();
+                scene_mesh.push_lod(lod.distance, convert_vertices(&lod_mesh), lod_mesh.indices.clone());
+                scene_mesh.apply_materials(vertex_offset, &lod_mesh);
+            }
+        }
+
+        if scene_mesh.vertices.is_empty() {
+            scene_mesh.vertices.extend_from_slice(&create_default_triangle());
+            scene_mesh.skin_vertices.resize(3, SkinVertex::default());
+            scene_mesh.material_vertices.resize(3, MaterialVertex::default());
+            scene_mesh.tangents.resize(3, [0.0; 3]);
+            scene_mesh.indices.extend_from_slice(&[0, 1, 2]);
+        }
+
+        info!(
+            "Scene mesh ready: {} models, {} vertices, {} indices",
+            scene_mesh.models.len(),
+            scene_mesh.vertices.len(),
+            scene_mesh.indices.len()
+        );
+
+        scene_mesh
+    }
+
+    /// 检查重新加载的结果
+    ///
+    /// 之前加载成功的模型这次使用了占位几何体（文件被删除、正在写入或格式错误）时返回错误，
+    /// 调用方应继续使用当前的网格。`previous_fallbacks` 为上一次加载的
+    /// [`fallback_models`](Self::fallback_models)，之前就加载失败的模型不算作错误。
+    pub fn check_reload(&self, previous_fallbacks: &[usize], models: &[ModelConfig]) -> Result<()> {
+        let failed: Vec<&str> = self
+            .fallback_models
+            .iter()
+            .filter(|index| !previous_fallbacks.contains(index))
+            .filter_map(|&index| models.get(index).map(|model| model.path.as_str()))
+            .collect();
+        if failed.is_empty() {
+            return Ok(());
+        }
+        Err(DistRenderError::MeshLoading(MeshLoadError::ValidationError(format!(
+            "Failed to reload {}",
+            failed.join(", ")
+        ))))
+    }
+
+    /// 追加一个模型（重启索引不加顶点偏移）
+    pub fn push(&mut self, vertices: Vec<MyVertex>, indices: Vec<u32>, subsets: Vec<Subset>) {
+        let vertex_offset = self.vertices.len() as u32;
+        let index_start = self.indices.len() as u32;
+        let face_offset = index_start / 3;
+
+        let bounding_radius = vertices
+            .iter()
+            .map(|v| Vector3::from(v.position).norm())
+            .fold(0.0f32, f32::max);
+        let subset_centers = subsets
+            .iter()
+            .map(|subset| {
+                let start = (subset.vertex_start as usize).min(vertices.len());
+                let end = (start + subset.vertex_count as usize).min(vertices.len());
+                bounds_center(&vertices[start..end])
+            })
+            .collect();
+
+        self.models.push(ModelMesh {
+            index_start,
+            index_count: indices.len() as u32,
+            vertex_start: vertex_offset,
+            vertex_count: vertices.len() as u32,
+            bounding_radius,
+            subset_centers,
+            subsets: subsets
+                .into_iter()
+                .map(|subset| Subset {
+                    vertex_start: subset.vertex_start + vertex_offset,
+                    face_start: subset.face_start + face_offset,
+                    ..subset
+                })
+                .collect(),
+            lods: Vec::new(),
+            skeleton: None,
+            pbr: false,
+            materials: Vec::new(),
+            edited: false,
+            topology: PrimitiveTopology::TriangleList,
+        });
+
+        self.vertices.extend(vertices);
+        self.skin_vertices.resize(self.vertices.len(), SkinVertex::default());
+        self.material_vertices.resize(self.vertices.len(), MaterialVertex::default());
+        self.tangents.resize(self.vertices.len(), [0.0; 3]);
+        self.indices.extend(
+            indices
+                .into_iter()
+                .map(|i| if i == PRIMITIVE_RESTART_INDEX { i } else { i + vertex_offset }),
+        );
+    }
+
+    /// 追加一个网格模型（包括材质）
+    ///
+    /// 三角形带在不使用图元重启时转换为三角形列表；保留时用重启索引把索引缓冲补齐到 3 的倍数。
+    pub fn push_mesh(&mut self, mut mesh_data: MeshData) {
+        if mesh_data.topology == PrimitiveTopology::TriangleStrip && !self.primitive_restart {
+            info!(
+                "Primitive restart is disabled or unsupported, converting triangle strips of {} to a triangle list",
+                mesh_data.name.as_deref().unwrap_or("mesh")
+            );
+            mesh_data.to_triangle_list();
+        }
+
+        let vertex_offset = self.vertices.len();
+        self.push(convert_vertices(&mesh_data), mesh_data.indices.clone(), mesh_data.subsets.clone());
+        self.apply_materials(vertex_offset, &mesh_data);
+
+        if mesh_data.topology == PrimitiveTopology::TriangleStrip {
+            if let Some(model) = self.models.last_mut() {
+                model.topology = PrimitiveTopology::TriangleStrip;
+            }
+            while !self.indices.len().is_multiple_of(3) {
+                self.indices.push(PRIMITIVE_RESTART_INDEX);
+            }
+        }
+    }
+
+    /// 追加一个蒙皮模型
+    pub fn push_skinned(&mut self, skinned: SkinnedMesh) {
+        let vertex_offset = self.vertices.len();
+        self.push(convert_vertices(&skinned.mesh), skinned.mesh.indices.clone(), skinned.mesh.subsets.clone());
+        self.apply_materials(vertex_offset, &skinned.mesh);
+
+        for (skin_vertex, (joints, weights)) in self.skin_vertices[vertex_offset..]
+            .iter_mut()
+            .zip(skinned.joint_indices.iter().zip(&skinned.joint_weights))
+        {
+            *skin_vertex = SkinVertex {
+                joints: *joints,
+                weights: *weights,
+            };
+        }
+        if let Some(model) = self.models.last_mut() {
+            model.skeleton = Some(AnimatedSkeleton::new(skinned.skeleton, skinned.animation));
+        }
+    }
+
+    /// 追加一个占位几何体模型
+    ///
+    /// 三角形使用带顶点颜色的默认三角形，其他几何体为白色。
+    pub fn push_fallback(&mut self, shape: PrimitiveShape) {
+        match shape {
+            PrimitiveShape::Triangle => self.push(create_default_triangle().to_vec(), vec![0, 1, 2], Vec::new()),
+            // 各后端共用同一份网格数据，按引擎约定使用逆时针绕序
+            shape => self.push_mesh(shape.mesh(Winding::Ccw)),
+        }
+    }
+
+    /// 为最后追加的模型添加一个简化级别
+    ///
+    /// 级别需按切换距离从近到远依次添加。没有模型时忽略。
+    pub fn push_lod(&mut self, distance: f32, vertices: Vec<MyVertex>, indices: Vec<u32>) {
+        let vertex_offset = self.vertices.len() as u32;
+        let index_start = self.indices.len() as u32;
+        let Some(model) = self.models.last_mut() else {
+            return;
+        };
+
+        model.lods.push(ModelLod {
+            distance,
+            index_start,
+            index_count: indices.len() as u32,
+        });
+        self.vertices.extend(vertices);
+        self.skin_vertices.resize(self.vertices.len(), SkinVertex::default());
+        self.material_vertices.resize(self.vertices.len(), MaterialVertex::default());
+        self.tangents.resize(self.vertices.len(), [0.0; 3]);
+        self.indices.extend(indices.into_iter().map(|i| i + vertex_offset));
+    }
+
+    /// 写入从 `vertex_offset` 开始的顶点的纹理坐标、切线、金属度和粗糙度
+    ///
+    /// 网格带材质时把最后追加的模型标记为 PBR，没有材质时只写入纹理坐标和切线。
+    fn apply_materials(&mut self, vertex_offset: usize, mesh_data: &MeshData) {
+        for (material_vertex, vertex) in self.material_vertices[vertex_offset..].iter_mut().zip(&mesh_data.vertices) {
+            material_vertex.tex_coord = vertex.texcoord;
+        }
+        for (tangent, vertex) in self.tangents[vertex_offset..].iter_mut().zip(&mesh_data.vertices) {
+            *tangent = vertex.tangent;
+        }
+
+        let Some(vertex_materials) = mesh_data.vertex_materials() else {
+            return;
+        };
+
+        for (material_vertex, material) in self.material_vertices[vertex_offset..].iter_mut().zip(vertex_materials) {
+            material_vertex.metallic = material.metallic;
+            material_vertex.roughness = material.roughness;
+            material_vertex.opacity = material.opacity();
+        }
+        if let Some(model) = self.models.last_mut() {
+            model.pbr = true;
+            model.materials = mesh_data.materials.clone();
+        }
+    }
+
+    /// 修改子网格的材质
+    ///
+    /// `selected` 为子网格的全局序号（与 [`subset_range`] 相同）。重写该子网格三角形引用的
+    /// 原始网格顶点的颜色、金属度和粗糙度，并把所属模型标记为已修改。
+    /// 子网格不存在或所属模型没有 PBR 材质时返回 `false`。
+    pub fn set_subset_material(&mut self, selected: usize, material: &Material) -> bool {
+        let Some((model_index, range)) = subset_range(&self.models, selected) else {
+            return false;
+        };
+        let model = &mut self.models[model_index];
+        if !model.pbr {
+            return false;
+        }
+        model.edited = true;
+
+        let color = [material.base_color[0], material.base_color[1], material.base_color[2]];
+        for &index in &self.indices[range.start as usize..range.end as usize] {
+            if index == PRIMITIVE_RESTART_INDEX {
+                continue;
+            }
+            self.vertices[index as usize].color = color;
+            let material_vertex = &mut self.material_vertices[index as usize];
+            material_vertex.metallic = material.metallic;
+            material_vertex.roughness = material.roughness;
+            material_vertex.opacity = material.opacity();
+        }
+        true
+    }
+
+    /// 打包后的索引数据（顶点数不超过 65535 时使用 16 位索引）
+    ///
+    /// 包含三角形带时总是使用 32 位索引，三角形带管线的重启索引格式因此固定。
+    pub fn index_data(&self) -> IndexData {
+        if self.has_strips() {
+            IndexData::U32(self.indices.clone())
+        } else {
+            IndexData::new(&self.indices, self.vertices.len())
+        }
+    }
+
+    /// 是否有模型使用三角形带
+    pub fn has_strips(&self) -> bool {
+        self.models.iter().any(|model| model.topology == PrimitiveTopology::TriangleStrip)
+    }
+
+    /// 所有模型在世界空间中的包围盒（按 `configs` 中的模型变换），没有顶点时为 `None`
+    pub fn world_bounds(&self, configs: &[ModelConfig]) -> Option<Aabb> {
+        self.models
+            .iter()
+            .zip(configs)
+            .filter_map(|(model, config)| {
+                let start = model.vertex_start as usize;
+                let vertices = &self.vertices[start..start + model.vertex_count as usize];
+                let local = Aabb::from_points(vertices.iter().map(|v| Vector3::from(v.position)))?;
+                Some(local.transformed(&config.transform.to_matrix()))
+            })
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// 模型原始网格的 UV 布局，模型不存在时为 `None`
+    pub fn uv_layout(&self, model_index: usize) -> Option<UvLayout> {
+        let model = self.models.get(model_index)?;
+        let range = model.index_range();
+        let triangles = model.topology.triangles(&self.indices[range.start as usize..range.end as usize]);
+        Some(UvLayout::new(&triangles, |index| self.material_vertices[index as usize].tex_coord))
+    }
+}
+
+/// 所有模型的子网格总数（没有子网格的模型算作一个）
+pub fn subset_count(models: &[ModelMesh]) -> usize {
+    models.iter().map(|model| model.subsets.len().max(1)).sum()
+}
+
+/// 按全局序号查找子网格
+///
+/// 子网格按模型顺序编号，没有子网格的模型整体算作一个。
+///
+/// # 返回
+///
+/// 所属模型的序号和子网格的索引范围，序号越界时返回 `None`
+pub fn subset_range(models: &[ModelMesh], selected: usize) -> Option<(usize, Range<u32>)> {
+    let mut remaining = selected;
+
+    for (model_index, model) in models.iter().enumerate() {
+        if model.subsets.is_empty() {
+            if remaining == 0 {
+                return Some((model_index, model.index_range()));
+            }
+            remaining -= 1;
+            continue;
+        }
+
+        if let Some(range) = model.subset_indices(remaining) {
+            return Some((model_index, range));
+        }
+        remaining -= model.subsets.len();
+    }
+
+    None
+}
+
+/// 按全局序号列出所有子网格的材质
+///
+/// 序号与 [`subset_range`] 相同；没有 PBR 材质的模型（及没有子网格的模型）为 `None`，
+/// 子网格 ID 越界时使用默认材质。
+pub fn subset_materials(models: &[ModelMesh]) -> Vec<Option<Material>> {
+    models
+        .iter()
+        .flat_map(|model| {
+            let materials: Vec<Option<Material>> = if model.pbr && !model.subsets.is_empty() {
+                model
+                    .subsets
+                    .iter()
+                    .map(|subset| Some(model.materials.get(subset.id as usize).cloned().unwrap_or_default()))
+                    .collect()
+            } else {
+                vec![None; model.subsets.len().max(1)]
+            };
+            materials
+        })
+        .collect()
+}
+
+/// 一次半透明子网格绘制
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransparentDraw {
+    /// 模型序号
+    pub model: usize,
+
+    /// 子网格在模型中的序号
+    pub subset: usize,
+
+    /// 索引范围（合并缓冲，原始网格）
+    pub indices: Range<u32>,
+
+    /// 子网格中心到相机的距离
+    pub distance: f32,
+}
+
+/// 可见模型中所有未隐藏的半透明子网格，按到相机的距离从远到近排列
+///
+/// 半透明子网格需要在不透明几何之后从远到近绘制才能正确混合。`model_matrices` 与 `models`
+/// 一一对应，`hidden` 按全局子网格序号标记隐藏（与 [`subset_range`] 相同）。
+pub fn transparent_draws(
+    models: &[ModelMesh],
+    model_matrices: &[Matrix4],
+    visible_models: &[usize],
+    hidden: &[bool],
+    camera_position: &Vector3,
+) -> Vec<TransparentDraw> {
+    let mut draws = Vec::new();
+    for &model_index in visible_models {
+        let model = &models[model_index];
+        let first = subset_count(&models[..model_index]);
+        for subset in (0..model.subsets.len()).filter(|&subset| model.is_transparent(subset)) {
+            if hidden.get(first + subset).copied().unwrap_or(false) {
+                continue;
+            }
+            let (Some(indices), Some(center)) = (model.subset_indices(subset), model.subset_centers.get(subset)) else {
+                continue;
+            };
+            let center = model_matrices[model_index].transform_point(&(*center).into()).coords;
+            draws.push(TransparentDraw {
+                model: model_index,
+                subset,
+                indices,
+                distance: (center - camera_position).norm(),
+            });
+        }
+    }
+    draws.sort_by(|a, b| b.distance.total_cmp(&a.distance));
+    draws
+}
+
+/// 按到相机的距离为每个模型选择 LOD 级别
+///
+/// `models` 与 `configs` 按序号一一对应，距离使用模型变换的位置计算。
+pub fn select_lods(models: &[ModelMesh], configs: &[ModelConfig], camera_position: &Vector3) -> Vec<usize> {
+    models
+        .iter()
+        .zip(configs)
+        .map(|(model, config)| {
+            let distance = (Vector3::from(config.transform.position) - camera_position).norm();
+            model.select_lod(distance)
+        })
+        .collect()
+}
+
+/// 加载单个模型，按 `lod_ratio` 简化并细分 `subdivision_levels` 级，失败时返回 `None`
+fn load_model(model: &ModelConfig, lod_ratio: f32, subdivision_levels: u32, mesh_cache: bool) -> Option<MeshData> {
+    if !Path::new(&model.path).exists() {
+        warn!("Model file not found: {}, using fallback primitive", model.path);
+        return None;
+    }
+
+    info!("Loading model from: {}", model.path);
+    match model.load_mesh(mesh_cache) {
+        Ok(mut mesh_data) => {
+            if lod_ratio < 1.0 {
+                let original = mesh_data.triangle_count();
+                mesh_data.decimate(lod_ratio);
+                info!("Model decimated: {} -> {} triangles", original, mesh_data.triangle_count());
+            }
+            if subdivision_levels > 0 {
+                let original = mesh_data.triangle_count();
+                mesh_data.subdivide(subdivision_levels);
+                info!("Model subdivided: {} -> {} triangles", original, mesh_data.triangle_count());
+            }
+            info!("Model loaded: {} vertices, {} indices", mesh_data.vertex_count(), mesh_data.index_count());
+            Some(mesh_data)
+        }
+        Err(e) => {
+            warn!("Failed to load model {}: {}, using fallback primitive", model.path, e);
+            None
+        }
+    }
+}
+
+/// 加载带蒙皮的模型，不是 glTF、没有蒙皮或加载失败时返回 `None`（之后按静态模型加载）
+fn load_skinned_model(model: &ModelConfig) -> Option<SkinnedMesh> {
+    if !Path::new(&model.path).exists() {
+        return None;
+    }
+
+    match model.load_skinned_mesh() {
+        Ok(Some(skinned)) if skinned.skeleton.joints.len() > MAX_JOINTS => {
+            warn!(
+                "Model {} has {} joints (max {}), drawing it as a static mesh",
+                model.path,
+                skinned.skeleton.joints.len(),
+                MAX_JOINTS
+            );
+            None
+        }
+        Ok(Some(skinned)) => {
+            if !model.lods.is_empty() {
+                info!("LODs are not generated for skinned model {}", model.path);
+            }
+            info!(
+                "Skinned model loaded: {} vertices, {} joints",
+                skinned.mesh.vertex_count(),
+                skinned.skeleton.joints.len()
+            );
+            Some(skinned)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            warn!("Failed to load skin from {}: {}", model.path, e);
+            None
+        }
+    }
+}
+
+/// 顶点包围盒的中心，没有顶点时为原点
+fn bounds_center(vertices: &[MyVertex]) -> Vector3 {
+    let Some(first) = vertices.first() else {
+        return Vector3::zeros();
+    };
+    let (min, max) = vertices.iter().fold(
+        (Vector3::from(first.position), Vector3::from(first.position)),
+        |(min, max), vertex| {
+            let position = Vector3::from(vertex.position);
+            (min.inf(&position), max.sup(&position))
+        },
+    );
+    (min + max) * 0.5
+}
+
+/// 转换为渲染顶点，带材质时顶点颜色为材质的基础颜色
+fn convert_vertices(mesh_data: &MeshData) -> Vec<MyVertex> {
+    let mut vertices: Vec<MyVertex> = mesh_data.vertices.iter().map(convert_geometry_vertex).collect();
+    if let Some(vertex_materials) = mesh_data.vertex_materials() {
+        for (vertex, material) in vertices.iter_mut().zip(vertex_materials) {
+            vertex.color = [material.base_color[0], material.base_color[1], material.base_color[2]];
+        }
+    }
+    vertices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::mesh::IndexWidth;
+
+    #[test]
+    fn test_missing_models_fall_back_per_slot() {
+        let models = vec![
+            ModelConfig {
+                path: "does/not/exist_a.obj".to_string(),
+                ..Default::default()
+            },
+            ModelConfig {
+                path: "does/not/exist_b.obj".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let scene_mesh = SceneMesh::load(&models, PrimitiveShape::Triangle, false, false);
+
+        assert_eq!(scene_mesh.models.len(), 2);
+        assert_eq!(scene_mesh.vertices.len(), 6);
+        assert_eq!(scene_mesh.models[1].index_range(), 3..6);
+        assert_eq!(&scene_mesh.indices[3..6], &[3, 4, 5]);
+        assert_eq!((scene_mesh.models[1].vertex_start, scene_mesh.models[1].vertex_count), (3, 3));
+        assert_eq!(scene_mesh.tangents.len(), scene_mesh.vertices.len());
+    }
+
+    #[test]
+    fn test_reload_fails_only_for_models_that_stopped_loading() {
+        let path = std::env::temp_dir().join(format!("dist_render_reload_{}.obj", std::process::id()));
+        std::fs::write(&path, "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n").unwrap();
+        let models = vec![
+            ModelConfig {
+                path: path.to_string_lossy().into_owned(),
+                ..Default::default()
+            },
+            ModelConfig {
+                path: "does/not/exist.obj".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let loaded = SceneMesh::load(&models, PrimitiveShape::Triangle, false, false);
+        assert_eq!(loaded.fallback_models, vec![1]);
+        // 一直缺失的模型不影响重新加载
+        let reloaded = SceneMesh::load(&models, PrimitiveShape::Triangle, false, false);
+        assert!(reloaded.check_reload(&loaded.fallback_models, &models).is_ok());
+
+        std::fs::remove_file(&path).unwrap();
+        let broken = SceneMesh::load(&models, PrimitiveShape::Triangle, false, false);
+        assert_eq!(broken.fallback_models, vec![0, 1]);
+        assert!(broken.check_reload(&loaded.fallback_models, &models).is_err());
+    }
+
+    #[test]
+    fn test_missing_model_uses_configured_primitive() {
+        let models = vec![ModelConfig {
+            path: "does/not/exist.obj".to_string(),
+            ..Default::default()
+        }];
+
+        let scene_mesh = SceneMesh::load(&models, PrimitiveShape::Cube, false, false);
+
+        assert_eq!(scene_mesh.vertices.len(), 24);
+        assert_eq!(scene_mesh.models[0].index_range(), 0..36);
+        assert!((scene_mesh.models[0].bounding_radius - 0.75f32.sqrt()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_world_bounds_use_model_transforms() {
+        let mut model = ModelConfig {
+            path: "does/not/exist.obj".to_string(),
+            ..Default::default()
+        };
+        model.transform.position = [0.0, 0.0, 5.0];
+        let models = vec![model];
+
+        let scene_mesh = SceneMesh::load(&models, PrimitiveShape::Cube, false, false);
+        let bounds = scene_mesh.world_bounds(&models).unwrap();
+
+        assert!((bounds.min - Vector3::new(-0.5, -0.5, 4.5)).norm() < 1e-5, "{:?}", bounds);
+        assert!((bounds.max - Vector3::new(0.5, 0.5, 5.5)).norm() < 1e-5, "{:?}", bounds);
+        assert!(SceneMesh::load(&[], PrimitiveShape::Cube, false, false).world_bounds(&[]).is_none());
+    }
+
+    #[test]
+    fn test_empty_scene_keeps_buffers_non_empty() {
+        let scene_mesh = SceneMesh::load(&[], PrimitiveShape::Cube, false, false);
+
+        assert!(scene_mesh.models.is_empty());
+        assert!(!scene_mesh.vertices.is_empty());
+        assert_eq!(subset_count(&scene_mesh.models), 0);
+        assert_eq!(subset_range(&scene_mesh.models, 0), None);
+    }
+
+    #[test]
+    fn test_strip_fallback_depends_on_primitive_restart() {
+        let models = vec![
+            ModelConfig {
+                path: "does/not/exist_a.obj".to_string(),
+                ..Default::default()
+            },
+            ModelConfig {
+                path: "does/not/exist_b.obj".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        // 平面占位几何体为单条带：4 个索引，补齐到 6 个后是下一个模型
+        let scene_mesh = SceneMesh::load(&models, PrimitiveShape::Plane, true, false);
+        assert_eq!(scene_mesh.models[0].topology, PrimitiveTopology::TriangleStrip);
+        assert_eq!(scene_mesh.models[0].index_range(), 0..4);
+        assert_eq!(scene_mesh.models[1].index_range(), 6..10);
+        assert_eq!(&scene_mesh.indices[4..10], &[PRIMITIVE_RESTART_INDEX, PRIMITIVE_RESTART_INDEX, 6, 4, 7, 5]);
+        assert_eq!(scene_mesh.index_data().width(), IndexWidth::U32);
+
+        // 不使用图元重启时转换为三角形列表
+        let scene_mesh = SceneMesh::load(&models, PrimitiveShape::Plane, false, false);
+        assert!(!scene_mesh.has_strips());
+        assert_eq!(scene_mesh.models[1].index_range(), 6..12);
+        assert_eq!(scene_mesh.index_data().width(), IndexWidth::U16);
+
+        // 带和列表展开后的 UV 布局相同
+        let strip_uvs = SceneMesh::load(&models, PrimitiveShape::Plane, true, false).uv_layout(0).unwrap();
+        let list_uvs = scene_mesh.uv_layout(0).unwrap();
+        assert_eq!(strip_uvs.triangles.len(), 2);
+        assert_eq!(strip_uvs.overlap_count(), 0);
+        assert_eq!(strip_uvs.outside_fraction, 0.0);
+        assert_eq!(strip_uvs.bounds(), Some(([0.0, 0.0], [1.0, 1.0])));
+        assert_eq!(strip_uvs.bounds(), list_uvs.bounds());
+        assert!(scene_mesh.uv_layout(2).is_none());
+    }
+
+    #[test]
+    fn test_subset_range_spans_models() {
+        let triangle = || create_default_triangle().to_vec();
+        let mut scene_mesh = SceneMesh::default();
+        scene_mesh.push(triangle(), vec![0, 1, 2], Vec::new());
+        scene_mesh.push(
+            [triangle(), triangle()].concat(),
+            vec![0, 1, 2, 3, 4, 5],
+            vec![Subset::new(0, 0, 3, 0, 1), Subset::new(1, 3, 3, 1, 1)],
+        );
+
+        assert_eq!(subset_count(&scene_mesh.models), 3);
+        assert_eq!(subset_range(&scene_mesh.models, 0), Some((0, 0..3)));
+        assert_eq!(subset_range(&scene_mesh.models, 1), Some((1, 3..6)));
+        assert_eq!(subset_range(&scene_mesh.models, 2), Some((1, 6..9)));
+        assert_eq!(subset_range(&scene_mesh.models, 3), None);
+
+        // 子网格的顶点起始位置换算到合并缓冲
+        assert_eq!(scene_mesh.models[1].subsets[1].vertex_start, 6);
+    }
+
+    #[test]
+    fn test_skinned_model_keeps_skin_vertices_aligned() {
+        use crate::geometry::skinning::Skeleton;
+        use crate::geometry::vertex::Vertex;
+        use crate::math::Matrix4;
+
+        let mut scene_mesh = SceneMesh::default();
+        scene_mesh.push(create_default_triangle().to_vec(), vec![0, 1, 2], Vec::new());
+        let mut mesh = MeshData::new();
+        mesh.vertices = vec![Vertex::default(); 3];
+        mesh.indices = vec![0, 1, 2];
+        scene_mesh.push_skinned(SkinnedMesh {
+            mesh,
+            joint_indices: vec![[1, 0, 0, 0]; 3],
+            joint_weights: vec![[1.0, 0.0, 0.0, 0.0]; 3],
+            skeleton: Skeleton {
+                joints: Vec::new(),
+                root_transform: Matrix4::identity(),
+            },
+            animation: None,
+        });
+
+        assert_eq!(scene_mesh.skin_vertices.len(), scene_mesh.vertices.len());
+        assert_eq!(scene_mesh.skin_vertices[0], SkinVertex::default());
+        assert_eq!(scene_mesh.skin_vertices[3].joints, [1, 0, 0, 0]);
+        assert_eq!(&scene_mesh.indices[3..6], &[3, 4, 5]);
+        assert!(scene_mesh.models[0].skeleton.is_none());
+        assert!(scene_mesh.models[1].skeleton.is_some());
+    }
+
+    #[test]
+    fn test_material_model_is_pbr() {
+        use crate::geometry::material::Material;
+        use crate::geometry::vertex::Vertex;
+
+        let mut mesh = MeshData::new();
+        mesh.vertices = vec![Vertex::default(); 3];
+        mesh.vertices[2].texcoord = [1.0, 0.5];
+        mesh.indices = vec![0, 1, 2];
+        mesh.subsets = vec![Subset::new(0, 0, 3, 0, 1)];
+        mesh.materials = vec![Material {
+            base_color: [0.5, 0.25, 1.0, 1.0],
+            metallic: 0.0,
+            roughness: 0.5,
+            ..Default::default()
+        }];
+
+        let mut scene_mesh = SceneMesh::default();
+        scene_mesh.push(create_default_triangle().to_vec(), vec![0, 1, 2], Vec::new());
+        scene_mesh.push(convert_vertices(&mesh), mesh.indices.clone(), mesh.subsets.clone());
+        scene_mesh.apply_materials(3, &mesh);
+
+        assert_eq!(scene_mesh.material_vertices.len(), scene_mesh.vertices.len());
+        assert_eq!(scene_mesh.material_vertices[0], MaterialVertex::default());
+        assert_eq!(scene_mesh.material_vertices[3], MaterialVertex { roughness: 0.5, ..Default::default() });
+        assert_eq!(scene_mesh.vertices[3].color, [0.5, 0.25, 1.0]);
+        assert!(!scene_mesh.models[0].pbr);
+        assert!(scene_mesh.models[1].pbr);
+
+        // 子网格材质按全局序号列出，修改后重写对应顶点
+        let materials = subset_materials(&scene_mesh.models);
+        assert_eq!(materials.len(), 2);
+        assert!(materials[0].is_none());
+        assert_eq!(materials[1].as_ref().map(|m| m.roughness), Some(0.5));
+
+        let gold = Material {
+            base_color: [1.0, 0.8, 0.2, 1.0],
+            metallic: 1.0,
+            roughness: 0.2,
+            ..Default::default()
+        };
+        assert!(!scene_mesh.set_subset_material(0, &gold));
+        assert!(scene_mesh.set_subset_material(1, &gold));
+        assert_eq!(scene_mesh.vertices[4].color, [1.0, 0.8, 0.2]);
+        // 修改材质保留纹理坐标
+        assert_eq!(
+            scene_mesh.material_vertices[5],
+            MaterialVertex { metallic: 1.0, roughness: 0.2, tex_coord: [1.0, 0.5], opacity: 1.0 }
+        );
+        assert!(scene_mesh.models[1].edited);
+        assert_eq!(scene_mesh.models[1].select_lod(1000.0), 0);
+    }
+
+    #[test]
+    fn test_visible_ranges_skip_hidden_subsets() {
+        let triangles = create_default_triangle().repeat(3);
+        let mut scene_mesh = SceneMesh::default();
+        scene_mesh.push(create_default_triangle().to_vec(), vec![0, 1, 2], Vec::new());
+        scene_mesh.push(
+            triangles,
+            (0..9).collect(),
+            vec![Subset::new(0, 0, 3, 0, 1), Subset::new(1, 3, 3, 1, 1), Subset::new(2, 6, 3, 2, 1)],
+        );
+        scene_mesh.push_lod(10.0, create_default_triangle().to_vec(), vec![0, 1, 2]);
+
+        let model = &scene_mesh.models[1];
+        assert_eq!(model.visible_ranges(1, &[false; 3]), vec![12..15]);
+        assert_eq!(model.visible_ranges(1, &[false, true, false]), vec![3..6, 9..12]);
+        assert_eq!(model.visible_ranges(0, &[true, false, false]), vec![6..12]);
+        assert_eq!(scene_mesh.models[0].visible_ranges(0, &[true]), Vec::<Range<u32>>::new());
+    }
+
+    #[test]
+    fn test_farther_camera_selects_coarser_lods() {
+        let triangles = |count: usize| {
+            let vertices = create_default_triangle().repeat(count);
+            let indices = (0..count as u32 * 3).collect::<Vec<_>>();
+            (vertices, indices)
+        };
+        let mut scene_mesh = SceneMesh::default();
+        let (vertices, indices) = triangles(8);
+        scene_mesh.push(vertices, indices, Vec::new());
+        for (distance, count) in [(10.0, 4), (20.0, 2), (40.0, 1)] {
+            let (vertices, indices) = triangles(count);
+            scene_mesh.push_lod(distance, vertices, indices);
+        }
+
+        let configs = vec![ModelConfig::default()];
+        let mut previous_level = 0;
+        let mut previous_count = u32::MAX;
+        for distance in [0.0, 5.0, 15.0, 25.0, 100.0] {
+            let camera = Vector3::new(0.0, 0.0, distance);
+            let level = select_lods(&scene_mesh.models, &configs, &camera)[0];
+            let range = scene_mesh.models[0].lod_range(level);
+
+            assert!(level >= previous_level);
+            assert!(range.len() as u32 <= previous_count);
+            previous_level = level;
+            previous_count = range.len() as u32;
+        }
+
+        assert_eq!(previous_level, 3);
+        assert_eq!(scene_mesh.models[0].lod_count(), 4);
+        assert_eq!(scene_mesh.models[0].lod_range(0), 0..24);
+        assert_eq!(scene_mesh.models[0].lod_range(3).len(), 3);
+        // 各级索引指向各自的顶点
+        let lod = scene_mesh.models[0].lod_range(1);
+        assert_eq!(scene_mesh.indices[lod.start as usize], 24);
+    }
+
+    #[test]
+    fn test_lod_range_without_lods_uses_full_mesh() {
+        let mut scene_mesh = SceneMesh::default();
+        scene_mesh.push(create_default_triangle().to_vec(), vec![0, 1, 2], Vec::new());
+
+        let model = &scene_mesh.models[0];
+        assert_eq!(model.lod_count(), 1);
+        assert_eq!(model.lod_range(2), model.index_range());
+    }
+
+    #[test]
+    fn test_transparent_quads_sort_back_to_front() {
+        use crate::geometry::vertex::Vertex;
+
+        // 两个半透明四边形分别位于 z = 0 和 z = -5，外加一个不透明四边形
+        let mut mesh = MeshData::new();
+        for (quad, z) in [0.0, -5.0, -10.0].into_iter().enumerate() {
+            for (x, y) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+                mesh.vertices.push(Vertex::new([x, y, z], [0.0, 0.0, 1.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+            }
+            let base = quad as u32 * 4;
+            mesh.indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+            mesh.subsets.push(Subset::new(quad as u32, base, 4, quad as u32 * 2, 2));
+        }
+        let glass = Material {
+            base_color: [0.2, 0.4, 1.0, 0.5],
+            transparent: true,
+            ..Default::default()
+        };
+        mesh.materials = vec![glass.clone(), glass, Material::default()];
+
+        let mut scene_mesh = SceneMesh::default();
+        scene_mesh.push(convert_vertices(&mesh), mesh.indices.clone(), mesh.subsets.clone());
+        scene_mesh.apply_materials(0, &mesh);
+        let model = &scene_mesh.models[0];
+        assert!(model.is_transparent(0) && model.is_transparent(1) && !model.is_transparent(2));
+        assert_eq!(scene_mesh.material_vertices[0].opacity, 0.5);
+        assert_eq!(scene_mesh.material_vertices[8].opacity, 1.0);
+
+        let matrices = [Matrix4::identity()];
+        let order = |camera: Vector3| -> Vec<usize> {
+            transparent_draws(&scene_mesh.models, &matrices, &[0], &[], &camera)
+                .iter()
+                .map(|draw| draw.subset)
+                .collect()
+        };
+        // 相机在 +z 一侧时远处的 z = -5 先画，在 -z 一侧时相反
+        assert_eq!(order(Vector3::new(0.5, 0.5, 10.0)), vec![1, 0]);
+        assert_eq!(order(Vector3::new(0.5, 0.5, -20.0)), vec![0, 1]);
+
+        // 隐藏的子网格不参与绘制
+        let hidden = [true, false, false];
+        let draws = transparent_draws(&scene_mesh.models, &matrices, &[0], &hidden, &Vector3::zeros());
+        assert_eq!(draws.len(), 1);
+        assert_eq!(draws[0].indices, 6..12);
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 use bytemuck::{Pod, Zeroable};
 use crate::math::Vector3;
+use crate::renderer::resources::vertex_layout::{HasVertexLayout, VertexFormat, VertexLayout};
 
 #[repr(C)]
 #[derive(Default, Clone, Copy, Debug, Pod, Zeroable)]
@@ -28,6 +29,64 @@ impl MyVertex {
     }
 }
 
+impl HasVertexLayout for MyVertex {
+    fn vertex_layout() -> VertexLayout {
+        VertexLayout::new(0)
+            .with("position", VertexFormat::Float32x3)
+            .with("normal", VertexFormat::Float32x3)
+            .with("color", VertexFormat::Float32x3)
+    }
+}
+
+/// 蒙皮顶点数据（关节索引和权重），与 `MyVertex` 一一对应，静态顶点的权重全为 0
+#[repr(C)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct SkinVertex {
+    pub joints: [u16; 4],
+    pub weights: [f32; 4],
+}
+
+/// 蒙皮数据放在单独的顶点缓冲中，着色器位置紧接在 `MyVertex` 之后
+impl HasVertexLayout for SkinVertex {
+    fn vertex_layout() -> VertexLayout {
+        VertexLayout::new(3)
+            .with("joints", VertexFormat::Uint16x4)
+            .with("weights", VertexFormat::Float32x4)
+    }
+}
+
+/// 材质顶点数据（金属度、粗糙度、纹理坐标和不透明度），与 `MyVertex` 一一对应，基础颜色存放在 `MyVertex::color` 中
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct MaterialVertex {
+    pub metallic: f32,
+    pub roughness: f32,
+    pub tex_coord: [f32; 2],
+    /// 不透明度，只有半透明材质小于 1
+    pub opacity: f32,
+}
+
+/// 金属度和粗糙度合并为一个属性，着色器位置紧接在 `SkinVertex` 之后
+impl HasVertexLayout for MaterialVertex {
+    fn vertex_layout() -> VertexLayout {
+        VertexLayout::new(5)
+            .with("material", VertexFormat::Float32x2)
+            .with("texcoord", VertexFormat::Float32x2)
+            .with("opacity", VertexFormat::Float32)
+    }
+}
+
+impl Default for MaterialVertex {
+    fn default() -> Self {
+        Self {
+            metallic: 0.0,
+            roughness: 0.0,
+            tex_coord: [0.0; 2],
+            opacity: 1.0,
+        }
+    }
+}
+
 pub fn create_default_triangle() -> [MyVertex; 3] {
     [
         MyVertex::new(0.0, 0.5, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0),
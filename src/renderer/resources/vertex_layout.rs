@@ -0,0 +1,131 @@
+//! 顶点属性布局
+//!
+//! 一个 `VertexLayout` 描述一个顶点缓冲中每个属性的名称、格式、偏移和着色器位置。
+//! 各后端从同一份布局生成自己的输入描述（wgpu 的 `VertexBufferLayout`、Vulkan 的 `VertexInputState`、
+//! DX12 的输入布局和 Metal 的顶点描述符），新增纹理坐标、切线等属性时只需修改顶点类型的布局定义。
+//!
+//! 属性名称同时用作 HLSL 语义（转换为大写），因此需要与着色器中的语义一致。
+
+use bytemuck::Pod;
+
+/// 顶点属性的数据格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VertexFormat {
+    Float32,
+    Float32x2,
+    Float32x3,
+    Float32x4,
+    Uint16x4,
+    /// 归一化到 0 ~ 1 的 4 个字节
+    Unorm8x4,
+}
+
+impl VertexFormat {
+    /// 字节大小
+    pub fn size(self) -> u32 {
+        match self {
+            VertexFormat::Float32 => 4,
+            VertexFormat::Float32x2 | VertexFormat::Uint16x4 => 8,
+            VertexFormat::Float32x3 => 12,
+            VertexFormat::Float32x4 => 16,
+            VertexFormat::Unorm8x4 => 4,
+        }
+    }
+}
+
+/// 一个顶点属性
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VertexAttribute {
+    /// 属性名称（小写），DX12 中作为 HLSL 语义使用
+    pub name: &'static str,
+    pub format: VertexFormat,
+    /// 相对顶点起始位置的字节偏移
+    pub offset: u32,
+    /// 着色器输入位置（location / attribute 序号）
+    pub location: u32,
+}
+
+impl VertexAttribute {
+    /// HLSL 语义名称
+    pub fn semantic(&self) -> String {
+        self.name.to_uppercase()
+    }
+}
+
+/// 一个顶点缓冲的属性布局
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VertexLayout {
+    stride: u32,
+    attributes: Vec<VertexAttribute>,
+    next_location: u32,
+}
+
+impl VertexLayout {
+    /// 创建空布局，之后添加的属性从 `first_location` 开始依次编号
+    pub fn new(first_location: u32) -> Self {
+        Self {
+            stride: 0,
+            attributes: Vec::new(),
+            next_location: first_location,
+        }
+    }
+
+    /// 在末尾紧密追加一个属性（与 `#[repr(C)]` 且没有填充的结构体一致）
+    pub fn with(mut self, name: &'static str, format: VertexFormat) -> Self {
+        self.attributes.push(VertexAttribute {
+            name,
+            format,
+            offset: self.stride,
+            location: self.next_location,
+        });
+        self.stride += format.size();
+        self.next_location += 1;
+        self
+    }
+
+    /// 顶点间隔（字节）
+    pub fn stride(&self) -> u32 {
+        self.stride
+    }
+
+    pub fn attributes(&self) -> &[VertexAttribute] {
+        &self.attributes
+    }
+}
+
+/// 带有属性布局的顶点类型
+///
+/// 自定义顶点需为 `#[repr(C)]`，布局按字段顺序定义，并与着色器输入一致。
+pub trait HasVertexLayout: Pod {
+    fn vertex_layout() -> VertexLayout;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::resources::vertex::{MaterialVertex, MyVertex, SkinVertex};
+
+    fn assert_matches_type<T: HasVertexLayout>() {
+        let layout = T::vertex_layout();
+        assert_eq!(layout.stride() as usize, std::mem::size_of::<T>());
+    }
+
+    #[test]
+    fn test_attributes_are_packed_in_order() {
+        let layout = VertexLayout::new(3)
+            .with("position", VertexFormat::Float32x3)
+            .with("joints", VertexFormat::Uint16x4)
+            .with("color", VertexFormat::Unorm8x4);
+        let offsets: Vec<_> = layout.attributes().iter().map(|a| (a.offset, a.location)).collect();
+        assert_eq!(offsets, vec![(0, 3), (12, 4), (20, 5)]);
+        assert_eq!(layout.stride(), 24);
+        assert_eq!(layout.attributes()[1].semantic(), "JOINTS");
+    }
+
+    #[test]
+    fn test_builtin_layouts_match_vertex_types() {
+        assert_matches_type::<MyVertex>();
+        assert_matches_type::<SkinVertex>();
+        assert_matches_type::<MaterialVertex>();
+    }
+}
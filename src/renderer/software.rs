@@ -0,0 +1,456 @@
+//! 软件光栅化模块
+//!
+//! 在 CPU 上把 `MeshData` 光栅化为深度缓冲和平面着色的颜色缓冲，用于在没有 GPU 的
+//! 环境（如 CI）中验证变换、剔除和光照方向。实现只追求正确和确定性，不追求速度。
+//!
+//! 约定与 GPU 后端保持一致：
+//! - 视图投影矩阵取自 `Camera::view_matrix` / `Camera::proj_matrix`（裁剪空间深度范围 [-w, w]）
+//! - 逆时针三角形为正面
+//! - 深度缓冲清空为 1.0，深度测试为"小于"
+//!
+//! 三角形带按 `MeshData::triangles` 展开后与三角形列表相同处理；三角形在近平面处裁剪，其余平面通过屏幕范围和深度范围处理。
+
+use image::{GrayImage, Luma, Rgba, RgbaImage};
+
+use crate::component::Camera;
+use crate::geometry::mesh::MeshData;
+use crate::math::frustum::Frustum;
+use crate::math::{Matrix4, Vector2, Vector3, Vector4};
+use crate::renderer::culling::InstanceData;
+
+/// CPU 图像（颜色 + 深度）
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoftwareImage {
+    width: u32,
+    height: u32,
+    color: Vec<[f32; 3]>,
+    depth: Vec<f32>,
+}
+
+impl SoftwareImage {
+    /// 创建图像，颜色清空为黑色，深度清空为 1.0
+    pub fn new(width: u32, height: u32) -> Self {
+        let len = (width * height) as usize;
+        Self {
+            width,
+            height,
+            color: vec![[0.0; 3]; len],
+            depth: vec![1.0; len],
+        }
+    }
+
+    /// 宽度（像素）
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// 高度（像素）
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 清空颜色和深度
+    pub fn clear(&mut self, clear_color: [f32; 3]) {
+        self.color.fill(clear_color);
+        self.depth.fill(1.0);
+    }
+
+    /// 像素颜色（线性值，范围 0-1），坐标原点在左上角
+    pub fn color_at(&self, x: u32, y: u32) -> [f32; 3] {
+        self.color[self.index(x, y)]
+    }
+
+    /// 像素深度（0 为近平面，1 为远平面或未覆盖）
+    pub fn depth_at(&self, x: u32, y: u32) -> f32 {
+        self.depth[self.index(x, y)]
+    }
+
+    /// 被几何覆盖的像素数
+    pub fn covered_pixels(&self) -> usize {
+        self.depth.iter().filter(|&&d| d < 1.0).count()
+    }
+
+    /// 转换为 8 位 RGBA 图像
+    pub fn color_image(&self) -> RgbaImage {
+        RgbaImage::from_fn(self.width, self.height, |x, y| {
+            let [r, g, b] = self.color_at(x, y).map(to_u8);
+            Rgba([r, g, b, 255])
+        })
+    }
+
+    /// 转换为 8 位灰度深度图（近处暗、远处亮）
+    pub fn depth_image(&self) -> GrayImage {
+        GrayImage::from_fn(self.width, self.height, |x, y| Luma([to_u8(self.depth_at(x, y))]))
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y * self.width + x) as usize
+    }
+}
+
+/// 单次绘制的统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RasterStats {
+    /// 整个网格是否被视锥体剔除
+    pub mesh_culled: bool,
+
+    /// 光栅化的三角形数
+    pub triangles_drawn: u32,
+
+    /// 背面剔除或完全位于近平面之前而丢弃的三角形数
+    pub triangles_culled: u32,
+}
+
+/// 软件光栅化器
+#[derive(Debug, Clone)]
+pub struct SoftwareRasterizer {
+    /// 光照方向（光线传播方向，与 `DirectionalLight::direction` 一致）
+    pub light_direction: Vector3,
+
+    /// 表面颜色
+    pub base_color: [f32; 3],
+
+    /// 环境光强度（背光面的亮度）
+    pub ambient: f32,
+
+    /// 是否剔除背面
+    pub cull_back_faces: bool,
+
+    image: SoftwareImage,
+}
+
+impl SoftwareRasterizer {
+    /// 创建指定尺寸的光栅化器
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            light_direction: Vector3::new(0.0, 0.0, -1.0),
+            base_color: [1.0, 1.0, 1.0],
+            ambient: 0.1,
+            cull_back_faces: true,
+            image: SoftwareImage::new(width, height),
+        }
+    }
+
+    /// 渲染结果
+    pub fn image(&self) -> &SoftwareImage {
+        &self.image
+    }
+
+    /// 清空颜色和深度
+    pub fn clear(&mut self, clear_color: [f32; 3]) {
+        self.image.clear(clear_color);
+    }
+
+    /// 绘制一个网格
+    ///
+    /// 先用模型包围球做视锥体剔除（与 GPU 后端的实例剔除相同），再逐三角形光栅化。
+    ///
+    /// # 参数
+    ///
+    /// - `mesh`: 三角形列表网格
+    /// - `model`: 模型矩阵
+    /// - `camera`: 相机（视图矩阵按需更新）
+    pub fn draw_mesh(&mut self, mesh: &MeshData, model: &Matrix4, camera: &mut Camera) -> RasterStats {
+        let view_proj = camera.proj_matrix() * camera.view_matrix();
+        let mut stats = RasterStats::default();
+
+        let bounding_radius = mesh
+            .vertices
+            .iter()
+            .map(|v| Vector3::from(v.position).norm())
+            .fold(0.0f32, f32::max);
+        let instance = InstanceData::new(*model, bounding_radius);
+        let center = instance.world_center();
+        let extent = Vector3::repeat(instance.world_radius());
+        if !Frustum::from_matrix(&view_proj).intersects_aabb(&(center - extent), &(center + extent)) {
+            stats.mesh_culled = true;
+            return stats;
+        }
+
+        let mvp = view_proj * model;
+        let light = -self.light_direction.try_normalize(1e-6).unwrap_or_default();
+
+        for triangle in mesh.triangles() {
+            let positions = [0, 1, 2].map(|i| Vector3::from(mesh.vertices[triangle[i] as usize].position));
+            let clip = positions.map(|p| mvp * p.push(1.0));
+
+            let polygon = clip_near(&clip);
+            if polygon.len() < 3 {
+                stats.triangles_culled += 1;
+                continue;
+            }
+
+            // 以裁剪后多边形的前三个顶点判断朝向（NDC 中逆时针为正面）
+            let ndc: Vec<Vector3> = polygon.iter().map(|c| c.xyz() / c.w).collect();
+            let front_facing = signed_area(ndc[0].xy(), ndc[1].xy(), ndc[2].xy()) > 0.0;
+            if self.cull_back_faces && !front_facing {
+                stats.triangles_culled += 1;
+                continue;
+            }
+
+            // 平面着色：世界空间面法线，背面（不剔除时）使用反向法线
+            let world = positions.map(|p| model.transform_point(&p.into()).coords);
+            let mut normal = (world[1] - world[0]).cross(&(world[2] - world[0])).try_normalize(1e-12).unwrap_or_default();
+            if !front_facing {
+                normal = -normal;
+            }
+            let intensity = self.ambient + (1.0 - self.ambient) * normal.dot(&light).max(0.0);
+            let color = self.base_color.map(|c| c * intensity);
+
+            let screen: Vec<Vector3> = ndc.iter().map(|p| self.to_screen(p)).collect();
+            for i in 1..screen.len() - 1 {
+                self.rasterize([screen[0], screen[i], screen[i + 1]], color);
+            }
+            stats.triangles_drawn += 1;
+        }
+
+        stats
+    }
+
+    /// NDC 坐标转换为屏幕坐标（像素，y 向下）和 [0, 1] 深度
+    fn to_screen(&self, ndc: &Vector3) -> Vector3 {
+        Vector3::new(
+            (ndc.x + 1.0) * 0.5 * self.image.width as f32,
+            (1.0 - ndc.y) * 0.5 * self.image.height as f32,
+            ndc.z * 0.5 + 0.5,
+        )
+    }
+
+    /// 光栅化屏幕空间三角形（在像素中心采样，深度线性插值）
+    fn rasterize(&mut self, triangle: [Vector3; 3], color: [f32; 3]) {
+        let [a, b, c] = triangle;
+        let area = signed_area(a.xy(), b.xy(), c.xy());
+        if area.abs() < 1e-12 {
+            return;
+        }
+
+        let width = self.image.width as f32;
+        let height = self.image.height as f32;
+        let min_x = a.x.min(b.x).min(c.x).floor().max(0.0) as u32;
+        let min_y = a.y.min(b.y).min(c.y).floor().max(0.0) as u32;
+        let max_x = a.x.max(b.x).max(c.x).ceil().min(width) as u32;
+        let max_y = a.y.max(b.y).max(c.y).ceil().min(height) as u32;
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = Vector2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let w0 = signed_area(b.xy(), c.xy(), p) / area;
+                let w1 = signed_area(c.xy(), a.xy(), p) / area;
+                let w2 = signed_area(a.xy(), b.xy(), p) / area;
+                if w0 < 0.0 || w1 < 0.0 || w2 < 0.0 {
+                    continue;
+                }
+
+                let depth = w0 * a.z + w1 * b.z + w2 * c.z;
+                let index = self.image.index(x, y);
+                if !(0.0..=1.0).contains(&depth) || depth >= self.image.depth[index] {
+                    continue;
+                }
+                self.image.depth[index] = depth;
+                self.image.color[index] = color;
+            }
+        }
+    }
+}
+
+/// 有向面积的两倍（逆时针为正，y 向上时）
+fn signed_area(a: Vector2, b: Vector2, c: Vector2) -> f32 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// 在裁剪空间中用近平面（z >= -w）裁剪三角形，返回凸多边形顶点
+fn clip_near(triangle: &[Vector4; 3]) -> Vec<Vector4> {
+    let distance = |v: &Vector4| v.z + v.w;
+    let mut polygon = Vec::with_capacity(4);
+
+    for i in 0..3 {
+        let current = triangle[i];
+        let next = triangle[(i + 1) % 3];
+        let (d_current, d_next) = (distance(&current), distance(&next));
+
+        if d_current >= 0.0 {
+            polygon.push(current);
+        }
+        if (d_current >= 0.0) != (d_next >= 0.0) {
+            let t = d_current / (d_current - d_next);
+            polygon.push(current + (next - current) * t);
+        }
+    }
+
+    polygon
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::vertex::Vertex;
+    use crate::math::matrix;
+
+    const SIZE: u32 = 64;
+
+    /// 相机位于 (0, 0, 5)，保持默认朝向（看向 -Z，即原点）
+    fn camera() -> Camera {
+        let mut camera = Camera::main_camera();
+        camera.set_lens(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+        camera.set_position(Vector3::new(0.0, 0.0, 5.0));
+        camera
+    }
+
+    /// z = 0 平面上的正方形（逆时针，法线朝向 +Z）
+    fn quad(half: f32) -> MeshData {
+        let mut mesh = MeshData::new();
+        for position in [[-half, -half, 0.0], [half, -half, 0.0], [half, half, 0.0], [-half, half, 0.0]] {
+            mesh.vertices.push(Vertex::new(position, [0.0, 0.0, 1.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+        }
+        mesh.indices.extend_from_slice(&[0, 1, 2, 0, 2, 3]);
+        mesh
+    }
+
+    /// 被覆盖像素的中心（像素坐标）
+    fn coverage_center(image: &SoftwareImage) -> Vector2 {
+        let mut sum = Vector2::zeros();
+        for y in 0..image.height() {
+            for x in 0..image.width() {
+                if image.depth_at(x, y) < 1.0 {
+                    sum += Vector2::new(x as f32 + 0.5, y as f32 + 0.5);
+                }
+            }
+        }
+        sum / image.covered_pixels() as f32
+    }
+
+    #[test]
+    fn test_quad_covers_expected_area() {
+        let mut rasterizer = SoftwareRasterizer::new(SIZE, SIZE);
+        let stats = rasterizer.draw_mesh(&quad(1.0), &Matrix4::identity(), &mut camera());
+
+        assert_eq!(stats, RasterStats { mesh_culled: false, triangles_drawn: 2, triangles_culled: 0 });
+
+        // 距离 5、视场 90°：边长 2 的正方形占屏幕宽度的 1/5
+        let image = rasterizer.image();
+        let expected = (SIZE as f32 / 5.0).powi(2);
+        assert!((image.covered_pixels() as f32 - expected).abs() <= 2.0 * SIZE as f32 / 5.0);
+        assert!((coverage_center(image) - Vector2::repeat(SIZE as f32 / 2.0)).norm() < 0.5);
+        assert_eq!(image.depth_at(0, 0), 1.0);
+
+        // 正对光源时为全亮度
+        assert_eq!(image.color_at(SIZE / 2, SIZE / 2), [1.0, 1.0, 1.0]);
+        assert_eq!(image.color_at(0, 0), [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_translation_moves_coverage() {
+        let mut rasterizer = SoftwareRasterizer::new(SIZE, SIZE);
+        rasterizer.draw_mesh(&quad(0.5), &matrix::translation(2.0, 1.0, 0.0), &mut camera());
+
+        // 每单位世界距离对应 SIZE / 10 像素，屏幕 y 向下
+        let center = coverage_center(rasterizer.image());
+        let expected = Vector2::new(SIZE as f32 / 2.0 + 2.0 * SIZE as f32 / 10.0, SIZE as f32 / 2.0 - SIZE as f32 / 10.0);
+        assert!((center - expected).norm() < 0.5, "center = {:?}", center);
+    }
+
+    #[test]
+    fn test_depth_test_keeps_nearest_surface() {
+        // 远处为红色，近处为绿色，绘制顺序不影响结果
+        let far = (matrix::translation(0.0, 0.0, -1.0), [1.0, 0.0, 0.0]);
+        let near = (Matrix4::identity(), [0.0, 1.0, 0.0]);
+
+        for order in [[far, near], [near, far]] {
+            let mut rasterizer = SoftwareRasterizer::new(SIZE, SIZE);
+            let mut camera = camera();
+            for (model, color) in order {
+                rasterizer.base_color = color;
+                rasterizer.draw_mesh(&quad(1.0), &model, &mut camera);
+            }
+            assert_eq!(rasterizer.image().color_at(SIZE / 2, SIZE / 2), [0.0, 1.0, 0.0]);
+        }
+    }
+
+    #[test]
+    fn test_back_faces_are_culled() {
+        let mut mesh = quad(1.0);
+        mesh.flip_winding();
+
+        let mut rasterizer = SoftwareRasterizer::new(SIZE, SIZE);
+        let stats = rasterizer.draw_mesh(&mesh, &Matrix4::identity(), &mut camera());
+        assert_eq!(stats.triangles_culled, 2);
+        assert_eq!(rasterizer.image().covered_pixels(), 0);
+
+        // 关闭背面剔除后可见，并按反向法线着色
+        rasterizer.cull_back_faces = false;
+        rasterizer.draw_mesh(&mesh, &Matrix4::identity(), &mut camera());
+        assert_eq!(rasterizer.image().color_at(SIZE / 2, SIZE / 2), [1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_strip_grid_renders_like_list_grid() {
+        use crate::geometry::primitives::{plane, plane_strip, Winding};
+
+        // 平面绕 X 轴旋转后朝向相机
+        let model = matrix::rotation_x(std::f32::consts::FRAC_PI_2);
+        let render = |mesh: &MeshData| {
+            let mut rasterizer = SoftwareRasterizer::new(SIZE, SIZE);
+            let stats = rasterizer.draw_mesh(mesh, &model, &mut camera());
+            (stats, rasterizer.image().clone())
+        };
+
+        let (list_stats, list_image) = render(&plane(2.0, 4, Winding::Ccw));
+        let (strip_stats, strip_image) = render(&plane_strip(2.0, 4, Winding::Ccw));
+        assert_eq!(list_stats.triangles_drawn, 32);
+        assert_eq!(strip_stats, list_stats);
+        assert!(strip_image.covered_pixels() > 0);
+        // 三角形的起始顶点不同，深度插值只有舍入误差
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                assert_eq!(strip_image.color_at(x, y), list_image.color_at(x, y));
+                assert!((strip_image.depth_at(x, y) - list_image.depth_at(x, y)).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mesh_outside_frustum_is_culled() {
+        let mut rasterizer = SoftwareRasterizer::new(SIZE, SIZE);
+        let stats = rasterizer.draw_mesh(&quad(1.0), &matrix::translation(0.0, 0.0, 20.0), &mut camera());
+
+        assert!(stats.mesh_culled);
+        assert_eq!(rasterizer.image().covered_pixels(), 0);
+    }
+
+    #[test]
+    fn test_triangle_crossing_near_plane_is_clipped() {
+        // 从相机前方延伸到相机后方的地面
+        let mut mesh = MeshData::new();
+        for position in [[-1.0, -1.0, 0.0], [1.0, -1.0, 0.0], [1.0, -1.0, 10.0], [-1.0, -1.0, 10.0]] {
+            mesh.vertices.push(Vertex::new(position, [0.0, 1.0, 0.0], [0.0, 0.0], [1.0, 0.0, 0.0]));
+        }
+        mesh.indices.extend_from_slice(&[0, 2, 1, 0, 3, 2]);
+
+        let mut rasterizer = SoftwareRasterizer::new(SIZE, SIZE);
+        rasterizer.cull_back_faces = false;
+        let stats = rasterizer.draw_mesh(&mesh, &Matrix4::identity(), &mut camera());
+
+        assert_eq!(stats.triangles_drawn, 2);
+        let image = rasterizer.image();
+        assert!(image.covered_pixels() > 0);
+        // 地面位于视线下方，只覆盖下半屏
+        assert!((0..SIZE).all(|x| image.depth_at(x, SIZE / 2 - 1) == 1.0));
+        assert!(image.depth_at(SIZE / 2, SIZE - 1) < 1.0);
+    }
+
+    #[test]
+    fn test_images_have_expected_size() {
+        let mut rasterizer = SoftwareRasterizer::new(16, 8);
+        rasterizer.draw_mesh(&quad(1.0), &Matrix4::identity(), &mut camera());
+
+        let color = rasterizer.image().color_image();
+        let depth = rasterizer.image().depth_image();
+        assert_eq!(color.dimensions(), (16, 8));
+        assert_eq!(depth.dimensions(), (16, 8));
+        assert_eq!(depth.get_pixel(0, 0)[0], 255);
+    }
+}
@@ -0,0 +1,179 @@
+//! 帧统计模块
+//!
+//! 后端在记录命令时累计绘制调用和三角形数量，并记录 CPU 侧的帧耗时，
+//! 由 [`RenderBackend::draw_with_stats`](crate::renderer::RenderBackend::draw_with_stats) 返回，
+//! 供性能面板和测试使用。
+//!
+//! 帧耗时中阻塞等待 GPU（围栏、交换链图像）的部分单独记录：等待占比高说明 CPU 在等 GPU（GPU 瓶颈），
+//! 几乎不等待说明 GPU 在等 CPU 提交（CPU 瓶颈），见 [`Bottleneck`]。
+//!
+//! 呈现统计 [`PresentStats`] 跨帧累计，由 [`RenderBackend::present_stats`](crate::renderer::RenderBackend::present_stats)
+//! 返回，反映垂直同步和丢帧的实际情况。
+
+use std::fmt;
+use std::time::Instant;
+
+/// 等待占比不低于该值时判断为 GPU 瓶颈
+pub const GPU_BOUND_WAIT_FRACTION: f32 = 0.5;
+
+/// 等待占比不高于该值时判断为 CPU 瓶颈
+pub const CPU_BOUND_WAIT_FRACTION: f32 = 0.1;
+
+/// 单帧渲染统计
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    /// 绘制调用次数
+    pub draw_calls: u32,
+
+    /// 提交绘制的三角形数量
+    pub triangles: u64,
+
+    /// CPU 侧记录和提交命令的耗时（毫秒）
+    pub cpu_time_ms: f32,
+
+    /// 其中阻塞等待 GPU 的耗时（毫秒）
+    pub gpu_wait_ms: f32,
+}
+
+impl FrameStats {
+    /// 记录一次索引绘制调用
+    pub fn record_draw(&mut self, index_count: u32) {
+        self.draw_calls += 1;
+        self.triangles += u64::from(index_count / 3);
+    }
+
+    /// 记录从 `start` 到现在的 CPU 耗时
+    pub fn finish(&mut self, start: Instant) {
+        self.cpu_time_ms = start.elapsed().as_secs_f32() * 1000.0;
+    }
+
+    /// 累加一段从 `start` 到现在的 GPU 等待（一帧内可多次调用）
+    pub fn record_wait(&mut self, start: Instant) {
+        self.gpu_wait_ms += start.elapsed().as_secs_f32() * 1000.0;
+    }
+
+    /// 等待 GPU 的耗时占 CPU 帧耗时的比例（0 ~ 1）
+    pub fn gpu_wait_fraction(&self) -> f32 {
+        if self.cpu_time_ms > 0.0 {
+            (self.gpu_wait_ms / self.cpu_time_ms).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// 帧的瓶颈判断
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bottleneck {
+    /// CPU 几乎不等待，GPU 在等 CPU 提交
+    Cpu,
+    /// CPU 大部分时间在等待 GPU
+    Gpu,
+    /// 介于两者之间
+    Balanced,
+}
+
+impl Bottleneck {
+    /// 按等待 GPU 的耗时占比判断
+    pub fn from_wait_fraction(fraction: f32) -> Self {
+        if fraction >= GPU_BOUND_WAIT_FRACTION {
+            Self::Gpu
+        } else if fraction <= CPU_BOUND_WAIT_FRACTION {
+            Self::Cpu
+        } else {
+            Self::Balanced
+        }
+    }
+}
+
+impl fmt::Display for Bottleneck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cpu => "CPU-bound",
+            Self::Gpu => "GPU-bound",
+            Self::Balanced => "Balanced",
+        })
+    }
+}
+
+/// 交换链的呈现模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PresentMode {
+    /// 未知（后端不提供呈现统计）
+    #[default]
+    Unknown,
+    /// 垂直同步，按队列顺序逐帧显示
+    Fifo,
+    /// 垂直同步，只显示最新的一帧，来不及显示的帧被替换
+    Mailbox,
+    /// 不等待垂直同步，可能出现撕裂
+    Immediate,
+}
+
+impl fmt::Display for PresentMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Unknown => "Unknown",
+            Self::Fifo => "FIFO",
+            Self::Mailbox => "Mailbox",
+            Self::Immediate => "Immediate",
+        })
+    }
+}
+
+/// 呈现统计（从渲染器创建开始累计）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PresentStats {
+    /// 已呈现的帧数
+    pub presented_frames: u64,
+
+    /// 丢弃的帧数
+    ///
+    /// DX12 为显示器刷新时没有新帧可显示的次数（由帧统计得出）；
+    /// Vulkan 和 wgpu 无法直接查询，以交换链次优或过期而重建的次数近似；
+    /// Metal 为等待 drawable 超时而没有呈现的帧数。
+    pub dropped_frames: u64,
+
+    /// 当前的呈现模式
+    pub present_mode: PresentMode,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_draw_accumulates() {
+        let mut stats = FrameStats::default();
+        stats.record_draw(36);
+        stats.record_draw(3);
+        stats.record_draw(0);
+
+        assert_eq!(stats.draw_calls, 3);
+        assert_eq!(stats.triangles, 13);
+    }
+
+    #[test]
+    fn test_finish_records_elapsed_time() {
+        let mut stats = FrameStats::default();
+        let start = Instant::now();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        stats.finish(start);
+
+        assert!(stats.cpu_time_ms >= 2.0);
+    }
+
+    #[test]
+    fn test_bottleneck_from_wait_fraction() {
+        let stats = FrameStats {
+            cpu_time_ms: 10.0,
+            gpu_wait_ms: 8.0,
+            ..Default::default()
+        };
+        assert!((stats.gpu_wait_fraction() - 0.8).abs() < 1e-6);
+        assert_eq!(Bottleneck::from_wait_fraction(stats.gpu_wait_fraction()), Bottleneck::Gpu);
+        assert_eq!(Bottleneck::from_wait_fraction(0.3), Bottleneck::Balanced);
+        assert_eq!(Bottleneck::from_wait_fraction(0.0), Bottleneck::Cpu);
+        assert_eq!(FrameStats::default().gpu_wait_fraction(), 0.0);
+    }
+}
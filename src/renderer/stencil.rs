@@ -0,0 +1,123 @@
+//! 模板遮罩
+//!
+//! 后端无关的模板测试状态。遮罩分两遍绘制：第一遍用全屏矩形把遮罩区域写入模板缓冲
+//! （不写颜色和深度），第二遍场景管线只在模板值等于参考值的像素上绘制，
+//! 用于 UI 遮罩和传送门效果。目前只有 Vulkan 和 DX12 后端支持，默认关闭。
+
+use serde::{Deserialize, Serialize};
+
+/// 遮罩区域写入模板缓冲的值
+pub const STENCIL_REFERENCE: u32 = 1;
+
+/// 模板比较函数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StencilCompare {
+    /// 总是通过
+    Always,
+    /// 模板值等于参考值时通过
+    Equal,
+}
+
+/// 通过模板测试后对模板值的操作（未通过时始终保持不变）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StencilOp {
+    /// 保持不变
+    Keep,
+    /// 替换为参考值
+    Replace,
+}
+
+/// 一遍绘制的模板状态，正反面相同
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StencilState {
+    pub compare: StencilCompare,
+    pub pass_op: StencilOp,
+    pub write_mask: u32,
+    pub reference: u32,
+}
+
+/// 遮罩的两遍绘制
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StencilPass {
+    /// 标记遮罩区域：写入模板值，不做深度测试，不写颜色和深度
+    Mark,
+    /// 限制在遮罩区域内绘制：只比较模板值，不修改模板缓冲
+    Restrict,
+}
+
+impl StencilPass {
+    /// 该遍的模板状态
+    pub fn state(self) -> StencilState {
+        match self {
+            StencilPass::Mark => StencilState {
+                compare: StencilCompare::Always,
+                pass_op: StencilOp::Replace,
+                write_mask: 0xFF,
+                reference: STENCIL_REFERENCE,
+            },
+            StencilPass::Restrict => StencilState {
+                compare: StencilCompare::Equal,
+                pass_op: StencilOp::Keep,
+                write_mask: 0,
+                reference: STENCIL_REFERENCE,
+            },
+        }
+    }
+
+    /// 该遍是否写入颜色并做深度测试
+    pub fn writes_color_and_depth(self) -> bool {
+        matches!(self, StencilPass::Restrict)
+    }
+}
+
+/// 矩形遮罩，场景只在矩形内可见
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StencilMask {
+    /// 窗口内的矩形 [x, y, 宽, 高]，以窗口尺寸归一化，原点在左上角
+    pub rect: [f32; 4],
+}
+
+impl StencilMask {
+    /// 矩形在 NDC 中的范围 [最小 x, 最小 y, 最大 x, 最大 y]，超出窗口的部分被截掉
+    ///
+    /// `y_down` 表示 NDC 的 y 轴向下（Vulkan），否则向上（DX12）。
+    pub fn ndc_bounds(&self, y_down: bool) -> [f32; 4] {
+        let [x, y, width, height] = self.rect;
+        let to_ndc = |v: f32| v.clamp(0.0, 1.0) * 2.0 - 1.0;
+        let (left, right) = (to_ndc(x), to_ndc(x + width));
+        let (top, bottom) = (to_ndc(y), to_ndc(y + height));
+        if y_down {
+            [left, top, right, bottom]
+        } else {
+            [left, -bottom, right, -top]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_ndc_bounds() {
+        let mask = StencilMask { rect: [0.25, 0.0, 0.5, 0.25] };
+        assert_eq!(mask.ndc_bounds(true), [-0.5, -1.0, 0.5, -0.5]);
+        assert_eq!(mask.ndc_bounds(false), [-0.5, 0.5, 0.5, 1.0]);
+
+        // 超出窗口的部分被截掉
+        let mask = StencilMask { rect: [-0.5, 0.5, 2.0, 1.0] };
+        assert_eq!(mask.ndc_bounds(true), [-1.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_passes_mark_then_restrict() {
+        let mark = StencilPass::Mark.state();
+        let restrict = StencilPass::Restrict.state();
+        assert_eq!((mark.compare, mark.pass_op), (StencilCompare::Always, StencilOp::Replace));
+        assert_eq!((restrict.compare, restrict.pass_op), (StencilCompare::Equal, StencilOp::Keep));
+        assert_eq!(mark.reference, restrict.reference);
+        assert_eq!(restrict.write_mask, 0);
+        assert!(!StencilPass::Mark.writes_color_and_depth());
+        assert!(StencilPass::Restrict.writes_color_and_depth());
+    }
+}